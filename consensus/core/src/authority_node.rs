@@ -20,6 +20,7 @@ use tracing::{info, warn};
 use crate::{
     BlockAPI as _, CommitConsumerArgs,
     authority_service::AuthorityService,
+    block::Slot,
     block_manager::BlockManager,
     block_verifier::SignedBlockVerifier,
     commit_observer::CommitObserver,
@@ -29,6 +30,8 @@ use crate::{
     core::{Core, CoreSignals},
     core_thread::{ChannelCoreThreadDispatcher, CoreThreadHandle},
     dag_state::DagState,
+    dag_visualizer::DagVisualizer,
+    error::ConsensusResult,
     leader_schedule::LeaderSchedule,
     leader_timeout::{LeaderTimeoutTask, LeaderTimeoutTaskHandle},
     metrics::initialise_metrics,
@@ -41,8 +44,12 @@ use crate::{
     synchronizer::{Synchronizer, SynchronizerHandle},
     transaction::{TransactionClient, TransactionConsumer, TransactionVerifier},
     transaction_certifier::TransactionCertifier,
+    universal_committer::universal_committer_builder::UniversalCommitterBuilder,
 };
 
+/// Number of rounds visualized when no range is requested.
+const VISUALIZED_ROUNDS: Round = 10;
+
 /// ConsensusAuthority is used by MySo to manage the lifetime of AuthorityNode.
 /// It hides the details of the implementation from the caller, MysticetiManager.
 #[allow(private_interfaces)]
@@ -113,6 +120,19 @@ impl ConsensusAuthority {
         }
     }
 
+    /// Collects rounds of the DAG cached by this authority, with the leader decisions made on
+    /// them. `end_round` defaults to the threshold clock round, and `start_round` to 10 rounds
+    /// before it. Blocks evicted from the cache are left out.
+    pub fn visualize_dag(
+        &self,
+        start_round: Option<Round>,
+        end_round: Option<Round>,
+    ) -> ConsensusResult<DagVisualizer> {
+        match self {
+            Self::WithTonic(authority) => authority.visualize_dag(start_round, end_round),
+        }
+    }
+
     #[cfg(test)]
     fn context(&self) -> &Arc<Context> {
         match self {
@@ -142,6 +162,9 @@ where
     core_thread_handle: CoreThreadHandle,
     subscriber: Subscriber<N::Client, AuthorityService<ChannelCoreThreadDispatcher>>,
     network_manager: N,
+
+    dag_state: Arc<RwLock<DagState>>,
+    leader_schedule: Arc<LeaderSchedule>,
 }
 
 impl<N> AuthorityNode<N>
@@ -288,7 +311,7 @@ where
         // kept in Core.
         let core = Core::new(
             context.clone(),
-            leader_schedule,
+            leader_schedule.clone(),
             tx_consumer,
             transaction_certifier.clone(),
             block_manager,
@@ -360,7 +383,7 @@ where
                 context.clone(),
                 network_client,
                 network_service.clone(),
-                dag_state,
+                dag_state.clone(),
             );
             for (peer, _) in context.committee.authorities() {
                 if peer != context.own_index {
@@ -389,6 +412,8 @@ where
             core_thread_handle,
             subscriber,
             network_manager,
+            dag_state,
+            leader_schedule,
         }
     }
 
@@ -429,6 +454,45 @@ where
         self.transaction_client.clone()
     }
 
+    pub(crate) fn visualize_dag(
+        &self,
+        start_round: Option<Round>,
+        end_round: Option<Round>,
+    ) -> ConsensusResult<DagVisualizer> {
+        // Elects leaders the same way as the committer of Core, from the shared leader schedule.
+        let committer = UniversalCommitterBuilder::new(
+            self.context.clone(),
+            self.leader_schedule.clone(),
+            self.dag_state.clone(),
+        )
+        .with_number_of_leaders(
+            self.context
+                .protocol_config
+                .mysticeti_num_leaders_per_round()
+                .unwrap_or(1),
+        )
+        .with_pipeline(true)
+        .build();
+
+        let dag_state = self.dag_state.read();
+        let end_round = end_round.unwrap_or_else(|| dag_state.threshold_clock_round());
+        let start_round = start_round
+            .unwrap_or_else(|| end_round.saturating_sub(VISUALIZED_ROUNDS))
+            .max(1);
+        DagVisualizer::from_dag_state(
+            &self.context,
+            &dag_state,
+            |round| {
+                committer
+                    .get_leaders(round)
+                    .into_iter()
+                    .map(|authority| Slot::new(round, authority))
+                    .collect()
+            },
+            start_round..=end_round,
+        )
+    }
+
     pub(crate) fn update_peer_address(
         &self,
         network_pubkey: NetworkPublicKey,
@@ -608,6 +672,13 @@ mod tests {
             }
         }
 
+        // The DAG of a running authority renders with the leaders it committed.
+        let dag_string = authorities[0]
+            .visualize_dag(Some(1), None)
+            .unwrap()
+            .to_dag_string();
+        assert!(dag_string.contains("committed in C1"), "{dag_string}");
+
         // Stop authority 1.
         let index = committee.to_authority_index(1).unwrap();
         authorities.remove(index.value()).stop().await;
@@ -44,6 +44,7 @@ use crate::{
     commit_observer::CommitObserver,
     context::Context,
     dag_state::DagState,
+    dag_visualizer::DagVisualizer,
     error::{ConsensusError, ConsensusResult},
     leader_schedule::LeaderSchedule,
     round_tracker::RoundTracker,
@@ -407,6 +408,9 @@ impl Core {
                 .leader_timeout_total
                 .with_label_values(&[&format!("{force}")])
                 .inc();
+            if tracing::enabled!(tracing::Level::TRACE) {
+                self.trace_dag();
+            }
             let result = self.try_propose(force);
             // The threshold clock round may have advanced, so a signal needs to be sent.
             self.try_signal_new_round();
@@ -1269,6 +1273,26 @@ impl Core {
             .collect()
     }
 
+    /// Logs the last rounds of the DAG with leader decisions, to help debug leader timeouts.
+    fn trace_dag(&self) {
+        const TRACED_ROUNDS: Round = 10;
+        let dag_state = self.dag_state.read();
+        let end_round = dag_state.threshold_clock_round();
+        let start_round = end_round.saturating_sub(TRACED_ROUNDS).max(1);
+        match DagVisualizer::from_dag_state(
+            &self.context,
+            &dag_state,
+            |round| self.leaders(round),
+            start_round..=end_round,
+        ) {
+            Ok(visualizer) => trace!(
+                "DAG at leader timeout for round {end_round}:\n{}",
+                visualizer.to_dag_string()
+            ),
+            Err(err) => trace!("Failed to visualize DAG: {err}"),
+        }
+    }
+
     /// Returns the 1st leader of the round.
     fn first_leader(&self, round: Round) -> AuthorityIndex {
         self.leaders(round).first().unwrap().authority
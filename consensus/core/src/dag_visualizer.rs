@@ -0,0 +1,666 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Renders a range of rounds of the DAG for debugging commit rule decisions.
+//!
+//! `DagVisualizer` collects blocks, leader decisions and committed sub-dags, either from a
+//! live `DagState` or from a replayed `DagExport`. It renders them in the text format read by
+//! `test_dag_parser`, so a DAG seen in production can be pasted into a test, and as a
+//! Graphviz graph.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    ops::RangeInclusive,
+};
+
+use consensus_config::AuthorityIndex;
+use consensus_types::block::{BlockRef, Round};
+
+use crate::{
+    block::{BlockAPI as _, GENESIS_ROUND, Slot, VerifiedBlock},
+    commit::{CommitAPI as _, CommitIndex},
+    context::Context,
+    dag_export::{DagExport, DagReplayReport, first_commit_at_or_after_round},
+    dag_state::DagState,
+    error::ConsensusResult,
+};
+
+/// The commit rule decision on a leader slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeaderDecision {
+    Committed(CommitIndex),
+    Skipped,
+    Undecided,
+}
+
+/// Fill colors of committed sub-dags, cycled by commit index.
+const COMMIT_COLORS: [&str; 6] = [
+    "#cfe2f3", "#d9ead3", "#fff2cc", "#f4cccc", "#d9d2e9", "#fce5cd",
+];
+
+/// A round range of the DAG with commit rule annotations.
+#[derive(Clone, Debug)]
+pub struct DagVisualizer {
+    num_authorities: usize,
+    rounds: RangeInclusive<Round>,
+    /// Ancestors of each block in the rounds.
+    blocks: BTreeMap<BlockRef, Vec<BlockRef>>,
+    leaders: BTreeMap<(Round, AuthorityIndex), LeaderDecision>,
+    committed: BTreeMap<BlockRef, CommitIndex>,
+}
+
+impl DagVisualizer {
+    pub fn new(num_authorities: usize, rounds: RangeInclusive<Round>) -> Self {
+        Self {
+            num_authorities,
+            rounds,
+            blocks: BTreeMap::new(),
+            leaders: BTreeMap::new(),
+            committed: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the blocks within the rounds of the visualizer.
+    pub fn add_blocks(&mut self, blocks: impl IntoIterator<Item = VerifiedBlock>) {
+        for block in blocks {
+            if self.rounds.contains(&block.round()) {
+                self.blocks
+                    .insert(block.reference(), block.ancestors().to_vec());
+            }
+        }
+    }
+
+    pub fn set_leader(&mut self, slot: Slot, status: LeaderDecision) {
+        if self.rounds.contains(&slot.round) {
+            self.leaders.insert((slot.round, slot.authority), status);
+        }
+    }
+
+    /// Marks blocks as part of the sub-dag of a commit.
+    pub fn set_committed(
+        &mut self,
+        commit_index: CommitIndex,
+        blocks: impl IntoIterator<Item = BlockRef>,
+    ) {
+        for block_ref in blocks {
+            if self.rounds.contains(&block_ref.round) {
+                self.committed.insert(block_ref, commit_index);
+            }
+        }
+    }
+
+    /// Collects the cached blocks of `rounds` from a live `DagState`, and the commits over them
+    /// from its store. `leaders` returns the leaders of a round with the current schedule.
+    pub(crate) fn from_dag_state(
+        context: &Context,
+        dag_state: &DagState,
+        leaders: impl Fn(Round) -> Vec<Slot>,
+        rounds: RangeInclusive<Round>,
+    ) -> ConsensusResult<Self> {
+        let mut visualizer = Self::new(context.committee.size(), rounds.clone());
+        for (authority, _) in context.committee.authorities() {
+            visualizer.add_blocks(dag_state.get_cached_blocks_in_range(
+                authority,
+                *rounds.start(),
+                rounds.end().saturating_add(1),
+                usize::MAX,
+            ));
+        }
+
+        let store = dag_state.store();
+        let last_commit_index = dag_state.last_commit_index();
+        let first_commit_index =
+            first_commit_at_or_after_round(store.as_ref(), *rounds.start(), last_commit_index)?;
+        let mut committed_leaders = BTreeMap::new();
+        if first_commit_index <= last_commit_index {
+            for commit in store.scan_commits((first_commit_index..=last_commit_index).into())? {
+                committed_leaders.insert(commit.leader(), commit.index());
+                visualizer.set_committed(commit.index(), commit.blocks().iter().copied());
+            }
+        }
+
+        let last_commit_round = dag_state.last_commit_round();
+        for round in rounds {
+            for slot in leaders(round) {
+                let status = committed_leaders
+                    .iter()
+                    .find(|(leader, _)| Slot::from(**leader) == slot)
+                    .map(|(_, index)| LeaderDecision::Committed(*index))
+                    .unwrap_or(if round <= last_commit_round {
+                        LeaderDecision::Skipped
+                    } else {
+                        LeaderDecision::Undecided
+                    });
+                visualizer.set_leader(slot, status);
+            }
+        }
+        Ok(visualizer)
+    }
+
+    /// Collects the blocks of `rounds` from an export, and the decisions made on them when
+    /// it was replayed.
+    pub fn from_replay(
+        export: &DagExport,
+        report: &DagReplayReport,
+        rounds: RangeInclusive<Round>,
+    ) -> ConsensusResult<Self> {
+        let mut visualizer = Self::new(export.stakes.len(), rounds);
+        visualizer.add_blocks(export.blocks()?);
+        for commit in &report.commits {
+            visualizer.set_leader(
+                commit.leader.into(),
+                LeaderDecision::Committed(commit.commit_ref.index),
+            );
+            visualizer.set_committed(commit.commit_ref.index, commit.blocks.iter().copied());
+        }
+        for slot in &report.skipped_leaders {
+            visualizer.set_leader(*slot, LeaderDecision::Skipped);
+        }
+        for slot in &report.undecided_leaders {
+            visualizer.set_leader(*slot, LeaderDecision::Undecided);
+        }
+        Ok(visualizer)
+    }
+
+    /// Renders the DAG in the format read by `test_dag_parser`, followed by comments listing
+    /// leaders, committed sub-dags, missing ancestors and equivocations, which the format
+    /// cannot express. Blocks of the first round list their actual ancestors, which the parser
+    /// drops as they are not rendered, and a comment marks that round as truncated. Ancestors
+    /// below the first round are left out for later rounds.
+    pub fn to_dag_string(&self) -> String {
+        let mut out = String::from("DAG {\n");
+        writeln!(out, "    Round 0 : {{ {} }},", self.num_authorities).unwrap();
+
+        let (first_round, last_round) = (*self.rounds.start(), *self.rounds.end());
+        for round in first_round.max(1)..=last_round {
+            let blocks = self.slot_blocks(round);
+            if blocks.is_empty() {
+                continue;
+            }
+            // Genesis blocks always exist in a parsed DAG.
+            let previous_slots: BTreeSet<_> = if round == 1 {
+                (0..self.num_authorities as u32)
+                    .map(|a| Slot::new(GENESIS_ROUND, AuthorityIndex::new_for_test(a)))
+                    .map(slot_key)
+                    .collect()
+            } else {
+                self.slot_blocks(round - 1)
+                    .iter()
+                    .map(|b| slot_key((**b).into()))
+                    .collect()
+            };
+
+            let connections = blocks
+                .iter()
+                .map(|block_ref| self.connections(block_ref, round, &previous_slots))
+                .collect::<Vec<_>>();
+            if blocks.len() == self.num_authorities
+                && !previous_slots.is_empty()
+                && connections.iter().all(|c| c == "*")
+            {
+                writeln!(out, "    Round {round} : {{ * }},").unwrap();
+                continue;
+            }
+            writeln!(out, "    Round {round} : {{").unwrap();
+            for (block_ref, connections) in blocks.iter().zip(connections) {
+                writeln!(
+                    out,
+                    "        {} -> [{connections}],",
+                    authority_name(block_ref.author)
+                )
+                .unwrap();
+            }
+            out.push_str("    },\n");
+        }
+        out.push_str("}\n");
+
+        if first_round > GENESIS_ROUND + 1 && !self.slot_blocks(first_round).is_empty() {
+            writeln!(
+                out,
+                "// Round {first_round} is truncated, its ancestors are listed but not rendered."
+            )
+            .unwrap();
+        }
+
+        if !self.leaders.is_empty() {
+            out.push_str("// Leaders:\n");
+            for ((round, authority), status) in &self.leaders {
+                let slot = slot_name(Slot::new(*round, *authority));
+                match status {
+                    LeaderDecision::Committed(index) => {
+                        writeln!(out, "//   {slot}: committed in C{index}").unwrap()
+                    }
+                    LeaderDecision::Skipped => writeln!(out, "//   {slot}: skipped").unwrap(),
+                    LeaderDecision::Undecided => writeln!(out, "//   {slot}: undecided").unwrap(),
+                }
+            }
+        }
+
+        let mut sub_dags: BTreeMap<CommitIndex, Vec<String>> = BTreeMap::new();
+        for (block_ref, index) in &self.committed {
+            sub_dags
+                .entry(*index)
+                .or_default()
+                .push(slot_name((*block_ref).into()));
+        }
+        if !sub_dags.is_empty() {
+            out.push_str("// Committed sub-dags:\n");
+            for (index, slots) in sub_dags {
+                writeln!(out, "//   C{index}: {}", slots.join(", ")).unwrap();
+            }
+        }
+
+        let missing = self.missing_ancestors();
+        if !missing.is_empty() {
+            out.push_str("// Missing ancestors:\n");
+            for (ancestor, referrers) in missing {
+                let referrers = referrers
+                    .iter()
+                    .map(|r| slot_name((*r).into()))
+                    .collect::<Vec<_>>();
+                writeln!(
+                    out,
+                    "//   {} referenced by {}",
+                    slot_name(ancestor.into()),
+                    referrers.join(", ")
+                )
+                .unwrap();
+            }
+        }
+
+        let equivocations = self.equivocations();
+        if !equivocations.is_empty() {
+            out.push_str("// Equivocations, only the first block of each slot is listed above:\n");
+            for (slot, count) in equivocations {
+                writeln!(out, "//   {}: {count} blocks", slot_name(slot)).unwrap();
+            }
+        }
+        out
+    }
+
+    /// Renders the DAG as a Graphviz graph, with rounds from left to right and edges from
+    /// blocks to their ancestors. Blocks of a committed sub-dag share a color, leaders have a
+    /// thick border (green when committed, red when skipped, orange when undecided) and
+    /// missing ancestors are dashed red nodes.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph DAG {\n");
+        out.push_str("    rankdir=LR;\n");
+        out.push_str("    node [shape=box, style=\"rounded,filled\", fillcolor=white, fontname=monospace];\n");
+
+        let missing = self.missing_ancestors();
+        let mut by_round: BTreeMap<Round, Vec<String>> = BTreeMap::new();
+        for block_ref in self.blocks.keys() {
+            by_round
+                .entry(block_ref.round)
+                .or_default()
+                .push(self.node_id(block_ref));
+        }
+        for block_ref in missing.keys() {
+            by_round
+                .entry(block_ref.round)
+                .or_default()
+                .push(self.node_id(block_ref));
+        }
+        for (round, nodes) in &by_round {
+            writeln!(
+                out,
+                "    subgraph round_{round} {{ rank=same; {} }}",
+                nodes
+                    .iter()
+                    .map(|n| format!("\"{n}\";"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+            .unwrap();
+        }
+
+        for block_ref in self.blocks.keys() {
+            let mut label = self.node_id(block_ref);
+            let mut attributes = vec![];
+            if let Some(index) = self.committed.get(block_ref) {
+                label.push_str(&format!("\\nC{index}"));
+                attributes.push(format!(
+                    "fillcolor=\"{}\"",
+                    COMMIT_COLORS[*index as usize % COMMIT_COLORS.len()]
+                ));
+            }
+            let status = self
+                .leaders
+                .get(&(block_ref.round, block_ref.author))
+                .map(|status| match status {
+                    LeaderDecision::Committed(_) => "darkgreen",
+                    LeaderDecision::Skipped => "red",
+                    LeaderDecision::Undecided => "orange",
+                });
+            if let Some(color) = status {
+                attributes.push(format!("penwidth=3, color={color}"));
+            }
+            attributes.push(format!("label=\"{label}\""));
+            writeln!(
+                out,
+                "    \"{}\" [{}];",
+                self.node_id(block_ref),
+                attributes.join(", ")
+            )
+            .unwrap();
+        }
+        for block_ref in missing.keys() {
+            writeln!(
+                out,
+                "    \"{}\" [style=\"rounded,dashed\", color=red, label=\"{}\\nmissing\"];",
+                self.node_id(block_ref),
+                slot_name((*block_ref).into())
+            )
+            .unwrap();
+        }
+        // Leader slots without a block.
+        for ((round, authority), status) in &self.leaders {
+            let slot = Slot::new(*round, *authority);
+            if !self.blocks.keys().any(|b| Slot::from(*b) == slot) {
+                writeln!(
+                    out,
+                    "    \"{0}\" [style=\"rounded,dotted\", color=red, label=\"{0}\\n{1}\"];",
+                    slot_name(slot),
+                    match status {
+                        LeaderDecision::Skipped => "skipped, no block",
+                        _ => "no block",
+                    }
+                )
+                .unwrap();
+            }
+        }
+
+        for (block_ref, ancestors) in &self.blocks {
+            for ancestor in ancestors {
+                if ancestor.round < *self.rounds.start() {
+                    continue;
+                }
+                writeln!(
+                    out,
+                    "    \"{}\" -> \"{}\";",
+                    self.node_id(block_ref),
+                    self.node_id(ancestor)
+                )
+                .unwrap();
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Returns the blocks of a round, keeping only the first block of each slot.
+    fn slot_blocks(&self, round: Round) -> Vec<&BlockRef> {
+        let mut seen = BTreeSet::new();
+        self.blocks
+            .keys()
+            .filter(|b| b.round == round && seen.insert(b.author))
+            .collect()
+    }
+
+    /// Formats the ancestors of a block, preferring the `*` and `-X` shorthands over listing
+    /// every ancestor of the previous round.
+    fn connections(
+        &self,
+        block_ref: &BlockRef,
+        round: Round,
+        previous_slots: &BTreeSet<(Round, AuthorityIndex)>,
+    ) -> String {
+        // Ancestors of the first round are not rendered, so list them as they are.
+        if round == *self.rounds.start() && round > GENESIS_ROUND + 1 {
+            let ancestors: BTreeSet<_> = self.blocks[block_ref]
+                .iter()
+                .map(|a| slot_key((*a).into()))
+                .collect();
+            return ancestors
+                .into_iter()
+                .map(|(r, a)| slot_name(Slot::new(r, a)))
+                .collect::<Vec<_>>()
+                .join(", ");
+        }
+        let ancestors: BTreeSet<_> = self.blocks[block_ref]
+            .iter()
+            .filter(|a| {
+                a.round == GENESIS_ROUND && round == 1
+                    || a.round >= *self.rounds.start() && self.blocks.contains_key(a)
+            })
+            .map(|a| slot_key((*a).into()))
+            .collect();
+        let (previous, older): (BTreeSet<_>, BTreeSet<_>) =
+            ancestors.into_iter().partition(|(r, _)| *r + 1 == round);
+        let older = older.into_iter().map(|(r, a)| slot_name(Slot::new(r, a)));
+
+        let excluded = previous_slots.difference(&previous).collect::<Vec<_>>();
+        let mut items = vec![];
+        if !previous_slots.is_empty() && previous.is_subset(previous_slots) {
+            // The parser resets to the whole previous round for every `-X`, so only a single
+            // exclusion can be expressed this way.
+            match excluded.as_slice() {
+                [] => items.push("*".to_string()),
+                [(r, a)] if previous.len() > 1 => {
+                    items.push(format!("-{}", slot_name(Slot::new(*r, *a))))
+                }
+                _ => items.extend(previous.iter().map(|(r, a)| slot_name(Slot::new(*r, *a)))),
+            }
+        } else {
+            items.extend(previous.iter().map(|(r, a)| slot_name(Slot::new(*r, *a))));
+        }
+        items.extend(older);
+        items.join(", ")
+    }
+
+    /// Ancestors within the rounds that are not among the blocks, with the blocks referencing
+    /// them.
+    fn missing_ancestors(&self) -> BTreeMap<BlockRef, Vec<BlockRef>> {
+        let mut missing: BTreeMap<BlockRef, Vec<BlockRef>> = BTreeMap::new();
+        for (block_ref, ancestors) in &self.blocks {
+            for ancestor in ancestors {
+                if ancestor.round >= *self.rounds.start()
+                    && ancestor.round > GENESIS_ROUND
+                    && !self.blocks.contains_key(ancestor)
+                {
+                    missing.entry(*ancestor).or_default().push(*block_ref);
+                }
+            }
+        }
+        missing
+    }
+
+    fn equivocations(&self) -> Vec<(Slot, usize)> {
+        let mut counts: BTreeMap<(Round, AuthorityIndex), usize> = BTreeMap::new();
+        for block_ref in self.blocks.keys() {
+            *counts
+                .entry((block_ref.round, block_ref.author))
+                .or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|((round, authority), count)| (Slot::new(round, authority), count))
+            .collect()
+    }
+
+    /// Node name of a block: its slot, followed by a digest prefix when the slot equivocates.
+    fn node_id(&self, block_ref: &BlockRef) -> String {
+        let slot = Slot::from(*block_ref);
+        let equivocating = self
+            .blocks
+            .keys()
+            .filter(|b| Slot::from(**b) == slot)
+            .nth(1)
+            .is_some();
+        if equivocating {
+            format!("{}#{}", slot_name(slot), &block_ref.digest.to_string()[..6])
+        } else {
+            slot_name(slot)
+        }
+    }
+}
+
+fn slot_key(slot: Slot) -> (Round, AuthorityIndex) {
+    (slot.round, slot.authority)
+}
+
+/// Names authorities as in `test_dag_parser`: `A` to `Z`, then `[26]` and above.
+fn authority_name(authority: AuthorityIndex) -> String {
+    match authority.value() {
+        index @ 0..26 => char::from(b'A' + index as u8).to_string(),
+        index => format!("[{index}]"),
+    }
+}
+
+fn slot_name(slot: Slot) -> String {
+    format!("{}{}", authority_name(slot.authority), slot.round)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        storage::{Store as _, WriteBatch, mem_store::MemStore},
+        test_dag_builder::DagBuilder,
+        test_dag_parser::parse_dag,
+    };
+
+    const DAG: &str = "DAG {
+        Round 0 : { 4 },
+        Round 1 : { * },
+        Round 2 : { * },
+        Round 3 : {
+            A -> [*],
+            B -> [-D2],
+            C -> [A2, B2],
+            D -> [*, A1],
+        },
+        Round 4 : { * },
+    }";
+
+    fn ancestor_slots(blocks: &[VerifiedBlock]) -> BTreeMap<(Round, AuthorityIndex), Vec<Slot>> {
+        blocks
+            .iter()
+            .map(|b| {
+                let mut ancestors = b
+                    .ancestors()
+                    .iter()
+                    .map(|a| Slot::from(*a))
+                    .collect::<Vec<_>>();
+                ancestors.sort_by_key(|s| slot_key(*s));
+                ((b.round(), b.author()), ancestors)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_dag_string_round_trip() {
+        let (_, dag_builder) = parse_dag(DAG).expect("Invalid dag");
+        let mut visualizer = DagVisualizer::new(4, 1..=4);
+        visualizer.add_blocks(dag_builder.all_blocks());
+        visualizer.set_leader(Slot::new_for_test(1, 1), LeaderDecision::Committed(1));
+        visualizer.set_leader(Slot::new_for_test(2, 2), LeaderDecision::Skipped);
+
+        let dag_string = visualizer.to_dag_string();
+        assert!(dag_string.contains("Round 1 : { * },"), "{dag_string}");
+        assert!(dag_string.contains("B -> [-D2],"), "{dag_string}");
+        assert!(dag_string.contains("C -> [A2, B2],"), "{dag_string}");
+        assert!(dag_string.contains("D -> [*, A1],"), "{dag_string}");
+        assert!(
+            dag_string.contains("//   B1: committed in C1"),
+            "{dag_string}"
+        );
+        assert!(dag_string.contains("//   C2: skipped"), "{dag_string}");
+
+        // The output parses back into the same DAG.
+        let (_, parsed) = parse_dag(&dag_string).expect("Invalid dag");
+        assert_eq!(
+            ancestor_slots(&parsed.all_blocks()),
+            ancestor_slots(&dag_builder.all_blocks())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_ancestors_and_dot() {
+        let (_, dag_builder) = parse_dag(DAG).expect("Invalid dag");
+        let mut visualizer = DagVisualizer::new(4, 2..=4);
+        // Leave out B2, which is referenced by round 3 blocks.
+        visualizer.add_blocks(
+            dag_builder
+                .all_blocks()
+                .into_iter()
+                .filter(|b| Slot::from(b.reference()) != Slot::new_for_test(2, 1)),
+        );
+        let committed = dag_builder.blocks(2..=2);
+        visualizer.set_committed(1, committed.iter().map(|b| b.reference()));
+        visualizer.set_leader(Slot::new_for_test(2, 0), LeaderDecision::Committed(1));
+        visualizer.set_leader(Slot::new_for_test(4, 3), LeaderDecision::Undecided);
+
+        let dag_string = visualizer.to_dag_string();
+        assert!(
+            dag_string.contains("//   B2 referenced by A3, B3, C3, D3"),
+            "{dag_string}"
+        );
+        // Ancestors below the first round are left out, except for the first round itself
+        // which is marked as truncated.
+        assert!(dag_string.contains("D -> [*],"), "{dag_string}");
+        assert!(
+            dag_string.contains("        A -> [A1, B1, C1, D1],"),
+            "{dag_string}"
+        );
+        assert!(
+            dag_string.contains("// Round 2 is truncated"),
+            "{dag_string}"
+        );
+
+        let dot = visualizer.to_dot();
+        assert!(dot.starts_with("digraph DAG {"), "{dot}");
+        assert!(
+            dot.contains("\"B2\" [style=\"rounded,dashed\", color=red, label=\"B2\\nmissing\"];"),
+            "{dot}"
+        );
+        assert!(
+            dot.contains(
+                "\"A2\" [fillcolor=\"#d9ead3\", penwidth=3, color=darkgreen, label=\"A2\\nC1\"];"
+            ),
+            "{dot}"
+        );
+        assert!(
+            dot.contains("\"D4\" [penwidth=3, color=orange, label=\"D4\"];"),
+            "{dot}"
+        );
+        assert!(dot.contains("\"C3\" -> \"B2\";"), "{dot}");
+        assert!(!dot.contains("-> \"A1\""), "{dot}");
+    }
+
+    #[tokio::test]
+    async fn test_from_replay() {
+        let (context, _) = Context::new_for_test(4);
+        let mut dag_builder = DagBuilder::new(Arc::new(context));
+        dag_builder.layers(1..=6).build();
+        let store = MemStore::new();
+        store
+            .write(WriteBatch::default().blocks(dag_builder.all_blocks()))
+            .unwrap();
+        let export = DagExport::from_store(
+            &store,
+            vec![1; 4],
+            myso_protocol_config::ProtocolVersion::MAX.as_u64(),
+            1..=6,
+        )
+        .unwrap();
+        let report = crate::DagReplayer::new(export.clone()).replay().unwrap();
+
+        let visualizer = DagVisualizer::from_replay(&export, &report, 1..=6).unwrap();
+        let dag_string = visualizer.to_dag_string();
+        for round in 1..=6 {
+            assert!(
+                dag_string.contains(&format!("Round {round} : {{ * }},")),
+                "{dag_string}"
+            );
+        }
+        assert!(dag_string.contains("committed in C4"), "{dag_string}");
+        assert!(dag_string.contains("undecided"), "{dag_string}");
+        assert!(dag_string.contains("//   C1: "), "{dag_string}");
+    }
+}
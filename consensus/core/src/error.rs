@@ -168,6 +168,12 @@ pub enum ConsensusError {
     #[error("RocksDB failure: {0}")]
     RocksDBFailure(#[from] TypedStoreError),
 
+    #[error("Commit {0} not found in storage")]
+    CommitNotFound(CommitIndex),
+
+    #[error("Unsupported protocol version: {0}")]
+    UnsupportedProtocolVersion(u64),
+
     #[error("Unknown network peer: {0}")]
     UnknownNetworkPeer(String),
 
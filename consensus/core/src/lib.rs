@@ -19,7 +19,9 @@ mod commit_vote_monitor;
 mod context;
 mod core;
 mod core_thread;
+mod dag_export;
 mod dag_state;
+mod dag_visualizer;
 mod error;
 mod leader_schedule;
 mod leader_scoring;
@@ -57,10 +59,15 @@ pub use authority_node::{ConsensusAuthority, NetworkType};
 pub use block::{BlockAPI, CertifiedBlock, CertifiedBlocksOutput};
 
 /// Exported API for testing and tools.
-pub use block::{TestBlock, Transaction, VerifiedBlock};
+pub use block::{Slot, TestBlock, Transaction, VerifiedBlock};
 pub use commit::{CommitAPI, CommitDigest, CommitIndex, CommitRange, CommitRef, CommittedSubDag};
 pub use commit_consumer::{CommitConsumerArgs, CommitConsumerMonitor};
 pub use context::Clock;
+pub use dag_export::{
+    CommitDivergence, DAG_EXPORT_VERSION, DagExport, DagReplayReport, DagReplayer, ReplayedCommit,
+    ScoresDivergence,
+};
+pub use dag_visualizer::{DagVisualizer, LeaderDecision};
 pub use metrics::Metrics;
 pub use transaction::{
     BlockStatus, ClientError, TransactionClient, TransactionVerifier, ValidationError,
@@ -77,8 +84,6 @@ pub use test_dag_builder::DagBuilder;
 pub use transaction_certifier::TransactionCertifier;
 
 // Exported API for simtests.
-#[cfg(msim)]
-pub use block::Slot;
 pub use commit_test_fixture::{
     CommitTestFixture, RandomDag, RandomDagConfig, RandomDagIterator, assert_commit_sequences_match,
 };
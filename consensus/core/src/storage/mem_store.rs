@@ -216,6 +216,21 @@ impl Store for MemStore {
             .map(|(k, v)| (CommitRef::new(k.0, k.1), v.clone())))
     }
 
+    fn scan_commit_info(
+        &self,
+        range: CommitRange,
+    ) -> ConsensusResult<Vec<(CommitRef, CommitInfo)>> {
+        let inner = self.inner.read();
+        Ok(inner
+            .commit_info
+            .range((
+                Included((range.start(), CommitDigest::MIN)),
+                Included((range.end(), CommitDigest::MAX)),
+            ))
+            .map(|(k, v)| (CommitRef::new(k.0, k.1), v.clone()))
+            .collect())
+    }
+
     fn read_last_finalized_commit(&self) -> ConsensusResult<Option<CommitRef>> {
         let inner = self.inner.read();
         Ok(inner
@@ -60,6 +60,10 @@ pub trait Store: Send + Sync {
     /// Reads the last commit info, written atomically with the last commit.
     fn read_last_commit_info(&self) -> ConsensusResult<Option<(CommitRef, CommitInfo)>>;
 
+    /// Reads all commit info stored for commits from start (inclusive) until end (inclusive).
+    fn scan_commit_info(&self, range: CommitRange)
+    -> ConsensusResult<Vec<(CommitRef, CommitInfo)>>;
+
     /// Reads the last finalized commit.
     fn read_last_finalized_commit(&self) -> ConsensusResult<Option<CommitRef>>;
 
@@ -361,6 +361,21 @@ impl Store for RocksDBStore {
         Ok(Some((CommitRef::new(key.0, key.1), commit_info)))
     }
 
+    fn scan_commit_info(
+        &self,
+        range: CommitRange,
+    ) -> ConsensusResult<Vec<(CommitRef, CommitInfo)>> {
+        let mut commit_info = vec![];
+        for result in self.commit_info.safe_range_iter((
+            Included((range.start(), CommitDigest::MIN)),
+            Included((range.end(), CommitDigest::MAX)),
+        )) {
+            let ((index, digest), info) = result?;
+            commit_info.push((CommitRef::new(index, digest), info));
+        }
+        Ok(commit_info)
+    }
+
     fn read_last_finalized_commit(&self) -> ConsensusResult<Option<CommitRef>> {
         let Some(result) = self
             .finalized_commits
@@ -10,7 +10,8 @@ use tempfile::TempDir;
 use super::{Store, WriteBatch, mem_store::MemStore, rocksdb_store::RocksDBStore};
 use crate::{
     block::{TestBlock, VerifiedBlock},
-    commit::{CommitDigest, TrustedCommit},
+    commit::{CommitDigest, CommitInfo, CommitRef, TrustedCommit},
+    leader_scoring::ReputationScores,
 };
 
 /// Test fixture for store tests. Wraps around various store implementations.
@@ -286,3 +287,53 @@ async fn read_and_scan_commits(
         assert_eq!(scanned_commits, written_commits,);
     }
 }
+
+#[rstest]
+#[tokio::test]
+async fn scan_commit_info(
+    #[values(new_rocksdb_teststore(), new_mem_teststore())] test_store: TestStore,
+) {
+    let store = test_store.store();
+
+    let written_commit_info = [10, 20, 30]
+        .into_iter()
+        .map(|index| {
+            (
+                CommitRef::new(index, CommitDigest::MIN),
+                CommitInfo {
+                    committed_rounds: vec![index; 4],
+                    reputation_scores: ReputationScores::new(
+                        (index - 9..=index).into(),
+                        vec![index as u64; 4],
+                    ),
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    store
+        .write(WriteBatch::default().commit_info(written_commit_info.clone()))
+        .unwrap();
+
+    {
+        let scanned = store
+            .scan_commit_info((31..=40).into())
+            .expect("Scan commit info should not fail");
+        assert!(scanned.is_empty(), "{:?}", scanned);
+    }
+
+    {
+        let scanned = store
+            .scan_commit_info((15..=30).into())
+            .expect("Scan commit info should not fail");
+        let scanned_refs = scanned.iter().map(|(r, _)| *r).collect::<Vec<_>>();
+        assert_eq!(
+            scanned_refs,
+            vec![written_commit_info[1].0, written_commit_info[2].0]
+        );
+        assert_eq!(scanned[0].1.committed_rounds, vec![20; 4]);
+        assert_eq!(
+            scanned[1].1.reputation_scores,
+            written_commit_info[2].1.reputation_scores
+        );
+    }
+}
@@ -26,6 +26,10 @@ fn default_file_format() -> FileFormat {
     FileFormat::Parquet
 }
 
+fn default_table_format() -> TableFormat {
+    TableFormat::Files
+}
+
 fn default_request_timeout_secs() -> u64 {
     30
 }
@@ -52,6 +56,20 @@ fn default_force_batch_cut_after_secs() -> u64 {
 pub enum FileFormat {
     Csv,
     Parquet,
+    /// Arrow IPC file format (Feather v2).
+    Arrow,
+}
+
+/// How the data files for a pipeline are organised into a table.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableFormat {
+    /// Data files are written on their own, under `{prefix}/epoch_{epoch}/`.
+    Files,
+    /// Data files are also committed to a Delta Lake transaction log under
+    /// `{prefix}/_delta_log/`, as a table partitioned by epoch. Columns that are added to the
+    /// pipeline's rows over time are added to the table's schema as they appear.
+    Delta,
 }
 
 /// Object store configuration for analytics output.
@@ -132,7 +150,7 @@ pub struct IndexerConfig {
     #[serde(default)]
     pub migration_id: Option<String>,
 
-    /// File format for output files (csv or parquet).
+    /// File format for output files (csv, parquet or arrow).
     #[serde(default = "default_file_format")]
     pub file_format: FileFormat,
 
@@ -234,9 +252,13 @@ impl BatchSizeConfig {
 pub struct PipelineConfig {
     /// Type of data to write i.e. checkpoint, object, transaction, etc
     pub pipeline: Pipeline,
-    /// File format to use (csv or parquet)
+    /// File format to use (csv, parquet or arrow)
     #[serde(default = "default_file_format")]
     pub file_format: FileFormat,
+    /// Table layout to write files in. Delta tables require the parquet file format, and are
+    /// not supported in migration mode.
+    #[serde(default = "default_table_format")]
+    pub table_format: TableFormat,
     pub package_id_filter: Option<String>,
     /// Snowflake table to monitor
     pub sf_table_id: Option<String>,
@@ -265,7 +287,25 @@ impl PipelineConfig {
     /// Returns an error if batch_size is required but not set, or if batch_size is invalid.
     /// In migration mode, batch_size is not required since file boundaries
     /// come from existing files.
+    ///
+    /// Also checks that the table format can be used with the file format and mode.
     pub fn validate(&self, is_migration_mode: bool) -> anyhow::Result<()> {
+        if self.table_format == TableFormat::Delta {
+            if self.file_format != FileFormat::Parquet {
+                anyhow::bail!(
+                    "pipeline '{}' uses the delta table format, which requires parquet files",
+                    self.pipeline
+                );
+            }
+
+            if is_migration_mode {
+                anyhow::bail!(
+                    "pipeline '{}' uses the delta table format, which is not supported in migration mode",
+                    self.pipeline
+                );
+            }
+        }
+
         if !is_migration_mode {
             match &self.batch_size {
                 None => anyhow::bail!(
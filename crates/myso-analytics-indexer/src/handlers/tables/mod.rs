@@ -36,9 +36,16 @@ pub mod checkpoint;
 pub mod df;
 pub mod event;
 pub mod move_call;
+pub mod move_event;
 pub mod object;
+pub mod orderbook_fill;
 pub mod package;
 pub mod package_bcs;
+pub mod social_follow;
+pub mod social_post;
+pub mod social_profile;
+pub mod social_tip;
+pub mod spt_trade;
 pub mod transaction;
 pub mod transaction_bcs;
 pub mod transaction_objects;
@@ -49,8 +56,14 @@ pub use df::DynamicFieldProcessor;
 pub use event::EventProcessor;
 pub use move_call::MoveCallProcessor;
 pub use object::ObjectProcessor;
+pub use orderbook_fill::OrderbookFillProcessor;
 pub use package::PackageProcessor;
 pub use package_bcs::PackageBCSProcessor;
+pub use social_follow::SocialFollowProcessor;
+pub use social_post::SocialPostProcessor;
+pub use social_profile::SocialProfileProcessor;
+pub use social_tip::SocialTipProcessor;
+pub use spt_trade::SptTradeProcessor;
 pub use transaction::TransactionProcessor;
 pub use transaction_bcs::TransactionBCSProcessor;
 pub use transaction_objects::TransactionObjectsProcessor;
@@ -0,0 +1,486 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shared decoding for the typed event tables (social and orderbook).
+//!
+//! Events are selected by their type and deserialized through the package
+//! resolver, the same way the object table decodes object contents, so field
+//! access is by name and survives package upgrades that append fields.
+
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::annotated_value::MoveValue;
+use myso_types::effects::TransactionEffectsAPI;
+use myso_types::full_checkpoint_content::Checkpoint;
+
+use crate::handlers::tables::get_move_struct;
+use crate::package_store::PackageCache;
+
+/// Selects events by the address, module and name of their type.
+pub struct EventFilter {
+    pub address: AccountAddress,
+    pub module: &'static str,
+    pub names: &'static [&'static str],
+}
+
+/// A matched event, with its fields deserialized.
+pub struct DecodedEvent {
+    pub transaction_digest: String,
+    pub event_index: u64,
+    /// The event struct name, e.g. `FollowEvent`.
+    pub name: String,
+    fields: BTreeMap<String, MoveValue>,
+}
+
+/// Decodes every event in `checkpoint` that matches one of `filters`, in
+/// transaction and event order.
+pub async fn decode_events(
+    checkpoint: &Checkpoint,
+    package_cache: &PackageCache,
+    filters: &[EventFilter],
+) -> Result<Vec<DecodedEvent>> {
+    let epoch = checkpoint.summary.data().epoch;
+    let resolver = package_cache.resolver_for_epoch(epoch);
+    let mut decoded = Vec::new();
+
+    for executed_tx in &checkpoint.transactions {
+        let Some(events) = &executed_tx.events else {
+            continue;
+        };
+        let digest = executed_tx.effects.transaction_digest();
+
+        for (idx, event) in events.data.iter().enumerate() {
+            let type_ = &event.type_;
+            let matched = filters.iter().any(|f| {
+                type_.address == f.address
+                    && type_.module.as_str() == f.module
+                    && f.names.contains(&type_.name.as_str())
+            });
+            if !matched {
+                continue;
+            }
+
+            let move_struct = get_move_struct(type_, &event.contents, &resolver)
+                .await
+                .with_context(|| format!("failed to decode event {type_} in {digest}"))?;
+
+            decoded.push(DecodedEvent {
+                transaction_digest: digest.base58_encode(),
+                event_index: idx as u64,
+                name: type_.name.to_string(),
+                fields: move_struct
+                    .fields
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value))
+                    .collect(),
+            });
+        }
+    }
+
+    Ok(decoded)
+}
+
+impl DecodedEvent {
+    fn field(&self, name: &str) -> Result<&MoveValue> {
+        self.fields
+            .get(name)
+            .ok_or_else(|| anyhow!("{} has no field `{name}`", self.name))
+    }
+
+    pub fn u64(&self, name: &str) -> Result<u64> {
+        match self.field(name)? {
+            MoveValue::U64(v) => Ok(*v),
+            other => bail!("{}.{name}: expected u64, got {other:?}", self.name),
+        }
+    }
+
+    pub fn u128(&self, name: &str) -> Result<u128> {
+        match self.field(name)? {
+            MoveValue::U128(v) => Ok(*v),
+            other => bail!("{}.{name}: expected u128, got {other:?}", self.name),
+        }
+    }
+
+    pub fn bool(&self, name: &str) -> Result<bool> {
+        match self.field(name)? {
+            MoveValue::Bool(v) => Ok(*v),
+            other => bail!("{}.{name}: expected bool, got {other:?}", self.name),
+        }
+    }
+
+    /// An `address` or `0x2::object::ID` field, as a full hex address.
+    pub fn address(&self, name: &str) -> Result<String> {
+        as_address(self.field(name)?)
+            .with_context(|| format!("{}.{name}: expected address", self.name))
+    }
+
+    /// A `0x1::string::String` field.
+    pub fn string(&self, name: &str) -> Result<String> {
+        as_string(self.field(name)?)
+            .with_context(|| format!("{}.{name}: expected string", self.name))
+    }
+
+    pub fn opt_u64(&self, name: &str) -> Result<Option<u64>> {
+        as_option(self.field(name)?)
+            .and_then(|v| {
+                v.map(|v| match v {
+                    MoveValue::U64(v) => Ok(*v),
+                    other => bail!("expected u64, got {other:?}"),
+                })
+                .transpose()
+            })
+            .with_context(|| format!("{}.{name}: expected Option<u64>", self.name))
+    }
+
+    pub fn opt_address(&self, name: &str) -> Result<Option<String>> {
+        as_option(self.field(name)?)
+            .and_then(|v| v.map(as_address).transpose())
+            .with_context(|| format!("{}.{name}: expected Option<address>", self.name))
+    }
+
+    pub fn opt_string(&self, name: &str) -> Result<Option<String>> {
+        as_option(self.field(name)?)
+            .and_then(|v| v.map(as_string).transpose())
+            .with_context(|| format!("{}.{name}: expected Option<String>", self.name))
+    }
+
+    /// A field that is a `String` in some event versions and an
+    /// `Option<String>` in others.
+    pub fn maybe_string(&self, name: &str) -> Result<Option<String>> {
+        match self.string(name) {
+            Ok(s) => Ok(Some(s)),
+            Err(_) => self.opt_string(name),
+        }
+    }
+
+    /// An `Option<vector<T>>` of addresses or strings, joined with commas.
+    pub fn opt_joined(&self, name: &str) -> Result<Option<String>> {
+        as_option(self.field(name)?)
+            .and_then(|v| {
+                v.map(|v| {
+                    let MoveValue::Vector(items) = v else {
+                        bail!("expected vector, got {v:?}");
+                    };
+                    items
+                        .iter()
+                        .map(|item| as_address(item).or_else(|_| as_string(item)))
+                        .collect::<Result<Vec<_>>>()
+                        .map(|items| items.join(","))
+                })
+                .transpose()
+            })
+            .with_context(|| format!("{}.{name}: expected Option<vector<_>>", self.name))
+    }
+}
+
+fn as_address(value: &MoveValue) -> Result<String> {
+    match value {
+        MoveValue::Address(a) | MoveValue::Signer(a) => Ok(a.to_canonical_string(true)),
+        MoveValue::Struct(s) if s.type_.name.as_str() == "ID" => match &s.fields[..] {
+            [(_, inner)] => as_address(inner),
+            _ => bail!("unexpected object id layout {:?}", s.type_),
+        },
+        other => bail!("expected address, got {other:?}"),
+    }
+}
+
+fn as_string(value: &MoveValue) -> Result<String> {
+    let MoveValue::Struct(s) = value else {
+        bail!("expected string, got {value:?}");
+    };
+    if s.type_.name.as_str() != "String" {
+        bail!("expected string, got {}", s.type_);
+    }
+    let [(_, MoveValue::Vector(bytes))] = &s.fields[..] else {
+        bail!("unexpected string layout {:?}", s.type_);
+    };
+    let bytes = bytes
+        .iter()
+        .map(|b| match b {
+            MoveValue::U8(b) => Ok(*b),
+            other => bail!("expected u8, got {other:?}"),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// Option in myso move is implemented as vector of size 0 or 1.
+fn as_option(value: &MoveValue) -> Result<Option<&MoveValue>> {
+    let MoveValue::Struct(s) = value else {
+        bail!("expected option, got {value:?}");
+    };
+    if s.type_.name.as_str() != "Option" {
+        bail!("expected option, got {}", s.type_);
+    }
+    let [(_, MoveValue::Vector(vec))] = &s.fields[..] else {
+        bail!("unexpected option layout {:?}", s.type_);
+    };
+    Ok(vec.first())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use move_core_types::annotated_value::MoveStruct;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::StructTag;
+    use myso_indexer_alt_framework::pipeline::Processor;
+    use myso_types::MYSO_SOCIAL_ADDRESS;
+    use myso_types::base_types::MySoAddress;
+    use myso_types::event::Event;
+    use myso_types::test_checkpoint_data_builder::TestCheckpointBuilder;
+    use serde::Serialize;
+
+    use super::*;
+    use crate::handlers::tables::SocialFollowProcessor;
+    use crate::handlers::tables::SocialProfileProcessor;
+    use crate::package_store::system_package_cache;
+
+    /// Layout of `social_graph::FollowEvent` and `social_graph::UnfollowEvent`.
+    #[derive(Serialize)]
+    struct FollowEvent {
+        follower: AccountAddress,
+        following: AccountAddress,
+    }
+
+    /// Layout of `profile::ProfileCreatedEvent`.
+    #[derive(Serialize)]
+    struct ProfileCreatedEvent {
+        profile_id: AccountAddress,
+        display_name: String,
+        username: String,
+        bio: String,
+        profile_picture: Option<String>,
+        cover_photo: Option<String>,
+        owner: AccountAddress,
+        created_at: u64,
+    }
+
+    fn string(s: &str) -> MoveValue {
+        MoveValue::Struct(MoveStruct {
+            type_: StructTag::from_str("0x1::string::String").unwrap(),
+            fields: vec![(
+                Identifier::from_str("bytes").unwrap(),
+                MoveValue::Vector(s.bytes().map(MoveValue::U8).collect()),
+            )],
+        })
+    }
+
+    fn option(v: Option<MoveValue>) -> MoveValue {
+        MoveValue::Struct(MoveStruct {
+            type_: StructTag::from_str("0x1::option::Option<u64>").unwrap(),
+            fields: vec![(
+                Identifier::from_str("vec").unwrap(),
+                MoveValue::Vector(v.into_iter().collect()),
+            )],
+        })
+    }
+
+    /// A social protocol event of type `module::name`, with `contents` as its BCS-encoded fields.
+    fn social_event(module: &str, name: &str, contents: &impl Serialize) -> Event {
+        let module = Identifier::from_str(module).unwrap();
+        let type_ = StructTag {
+            address: MYSO_SOCIAL_ADDRESS,
+            module: module.clone(),
+            name: Identifier::from_str(name).unwrap(),
+            type_params: vec![],
+        };
+
+        Event::new(
+            &MYSO_SOCIAL_ADDRESS,
+            &module,
+            MySoAddress::ZERO,
+            type_,
+            bcs::to_bytes(contents).unwrap(),
+        )
+    }
+
+    fn checkpoint(events: Vec<Event>) -> Arc<Checkpoint> {
+        let checkpoint = TestCheckpointBuilder::new(1)
+            .start_transaction(0)
+            .with_events(events)
+            .finish_transaction()
+            .build_checkpoint();
+        Arc::new(checkpoint)
+    }
+
+    fn event(fields: Vec<(&str, MoveValue)>) -> DecodedEvent {
+        DecodedEvent {
+            transaction_digest: String::new(),
+            event_index: 0,
+            name: "TestEvent".to_string(),
+            fields: fields
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_field_accessors() -> Result<()> {
+        let addr = AccountAddress::from_hex_literal("0x42")?;
+        let id = MoveValue::Struct(MoveStruct {
+            type_: StructTag::from_str("0x2::object::ID")?,
+            fields: vec![(Identifier::from_str("bytes")?, MoveValue::Address(addr))],
+        });
+        let event = event(vec![
+            ("amount", MoveValue::U64(7)),
+            ("pool_id", id),
+            ("name", string("alice")),
+            ("bio", option(Some(string("hi")))),
+            ("parent", option(None)),
+            ("nickname", option(None)),
+            (
+                "mentions",
+                option(Some(MoveValue::Vector(vec![
+                    MoveValue::Address(addr),
+                    MoveValue::Address(addr),
+                ]))),
+            ),
+        ]);
+
+        let full = addr.to_canonical_string(true);
+        assert_eq!(event.u64("amount")?, 7);
+        assert_eq!(event.address("pool_id")?, full);
+        assert_eq!(event.string("name")?, "alice");
+        assert_eq!(event.maybe_string("name")?.as_deref(), Some("alice"));
+        assert_eq!(event.maybe_string("bio")?.as_deref(), Some("hi"));
+        assert_eq!(event.opt_address("parent")?, None);
+        assert_eq!(event.maybe_string("nickname")?, None);
+        assert_eq!(
+            event.opt_joined("mentions")?,
+            Some(format!("{full},{full}"))
+        );
+        assert!(event.u64("name").is_err());
+        assert!(event.u64("missing").is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_social_follow_processor() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = Arc::new(system_package_cache(dir.path()));
+
+        let a = AccountAddress::from_hex_literal("0xa")?;
+        let b = AccountAddress::from_hex_literal("0xb")?;
+        let checkpoint = checkpoint(vec![
+            social_event(
+                "social_graph",
+                "FollowEvent",
+                &FollowEvent {
+                    follower: a,
+                    following: b,
+                },
+            ),
+            // Events of other types in the same module are skipped.
+            social_event("social_graph", "OtherEvent", &0u64),
+            social_event(
+                "social_graph",
+                "UnfollowEvent",
+                &FollowEvent {
+                    follower: b,
+                    following: a,
+                },
+            ),
+        ]);
+
+        let rows = SocialFollowProcessor::new(cache)
+            .process(&checkpoint)
+            .await?;
+
+        let rows: Vec<_> = rows
+            .iter()
+            .map(|r| {
+                (
+                    r.event_index,
+                    r.action.to_string(),
+                    r.follower.clone(),
+                    r.followee.clone(),
+                )
+            })
+            .collect();
+
+        let a = a.to_canonical_string(true);
+        let b = b.to_canonical_string(true);
+        assert_eq!(
+            rows,
+            vec![
+                (0, "Follow".to_string(), a.clone(), b.clone()),
+                (2, "Unfollow".to_string(), b, a),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_social_profile_processor() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = Arc::new(system_package_cache(dir.path()));
+
+        let profile_id = AccountAddress::from_hex_literal("0x1234")?;
+        let owner = AccountAddress::from_hex_literal("0xa")?;
+        let checkpoint = checkpoint(vec![social_event(
+            "profile",
+            "ProfileCreatedEvent",
+            &ProfileCreatedEvent {
+                profile_id,
+                display_name: "Alice".to_string(),
+                username: "alice".to_string(),
+                bio: "Hello".to_string(),
+                profile_picture: Some("https://example.com/a.png".to_string()),
+                cover_photo: None,
+                owner,
+                created_at: 42,
+            },
+        )]);
+
+        let rows = SocialProfileProcessor::new(cache)
+            .process(&checkpoint)
+            .await?;
+
+        let [row] = &rows[..] else {
+            panic!("Expected one row, got {}", rows.len());
+        };
+
+        assert_eq!(row.action.to_string(), "Created");
+        assert_eq!(row.profile_id, profile_id.to_canonical_string(true));
+        assert_eq!(row.owner, owner.to_canonical_string(true));
+        assert_eq!(row.username, "alice");
+        assert_eq!(row.display_name.as_deref(), Some("Alice"));
+        assert_eq!(row.bio, "Hello");
+        assert_eq!(
+            row.profile_picture.as_deref(),
+            Some("https://example.com/a.png")
+        );
+        assert_eq!(row.cover_photo, None);
+        assert_eq!(row.event_time, 42);
+        assert_eq!(row.checkpoint, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_malformed_event() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = Arc::new(system_package_cache(dir.path()));
+
+        // A `FollowEvent` that is missing its second address.
+        let a = AccountAddress::from_hex_literal("0xa")?;
+        let checkpoint = checkpoint(vec![social_event("social_graph", "FollowEvent", &a)]);
+
+        let err = SocialFollowProcessor::new(cache)
+            .process(&checkpoint)
+            .await
+            .err()
+            .expect("Decoding a truncated event should fail");
+        assert!(err.to_string().contains("failed to decode event"));
+        Ok(())
+    }
+}
@@ -280,3 +280,47 @@ impl Processor for ObjectProcessor {
         Ok(entries)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use myso_types::coin::Coin;
+    use myso_types::gas_coin::GAS;
+    use myso_types::test_checkpoint_data_builder::TestCheckpointBuilder;
+    use prometheus::Registry;
+
+    use super::*;
+    use crate::package_store::system_package_cache;
+
+    #[tokio::test]
+    async fn test_decode_coin_object() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = Arc::new(system_package_cache(dir.path()));
+        let processor = ObjectProcessor::new(cache, &None, Metrics::new(&Registry::new()));
+
+        let checkpoint = TestCheckpointBuilder::new(1)
+            .start_transaction(0)
+            .create_myso_object(0, 42)
+            .finish_transaction()
+            .build_checkpoint();
+
+        let rows = processor.process(&Arc::new(checkpoint)).await?;
+
+        let id = TestCheckpointBuilder::derive_object_id(0);
+        let row = rows
+            .iter()
+            .find(|r| r.object_id == id.to_string())
+            .expect("Created coin should have a row");
+
+        assert_eq!(
+            row.struct_tag,
+            Some(Coin::type_(GAS::type_tag()).to_string())
+        );
+        assert_eq!(row.coin_balance, Some(42));
+
+        let json: serde_json::Value =
+            serde_json::from_str(row.object_json.as_deref().expect("Coin should decode"))?;
+        assert_eq!(json["id"]["id"], id.to_string());
+        assert_eq!(json["balance"], "42");
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use myso_indexer_alt_framework::pipeline::Processor;
+use myso_types::ORDERBOOK_ADDRESS;
+use myso_types::base_types::EpochId;
+use myso_types::full_checkpoint_content::Checkpoint;
+
+use crate::Row;
+use crate::handlers::tables::move_event::EventFilter;
+use crate::handlers::tables::move_event::decode_events;
+use crate::package_store::PackageCache;
+use crate::pipeline::Pipeline;
+use crate::tables::OrderbookFillRow;
+
+const FILTERS: &[EventFilter] = &[EventFilter {
+    address: ORDERBOOK_ADDRESS,
+    module: "order_info",
+    names: &["OrderFilled"],
+}];
+
+pub struct OrderbookFillProcessor {
+    package_cache: Arc<PackageCache>,
+}
+
+impl OrderbookFillProcessor {
+    pub fn new(package_cache: Arc<PackageCache>) -> Self {
+        Self { package_cache }
+    }
+}
+
+impl Row for OrderbookFillRow {
+    fn get_epoch(&self) -> EpochId {
+        self.epoch
+    }
+
+    fn get_checkpoint(&self) -> u64 {
+        self.checkpoint
+    }
+}
+
+#[async_trait]
+impl Processor for OrderbookFillProcessor {
+    const NAME: &'static str = Pipeline::OrderbookFill.name();
+    const FANOUT: usize = 16;
+    type Value = OrderbookFillRow;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+        let epoch = checkpoint.summary.data().epoch;
+        let checkpoint_seq = checkpoint.summary.data().sequence_number;
+        let timestamp_ms = checkpoint.summary.data().timestamp_ms;
+
+        decode_events(checkpoint, &self.package_cache, FILTERS)
+            .await?
+            .into_iter()
+            .map(|event| {
+                Ok(OrderbookFillRow {
+                    transaction_digest: event.transaction_digest.clone(),
+                    event_index: event.event_index,
+                    checkpoint: checkpoint_seq,
+                    epoch,
+                    timestamp_ms,
+                    pool_id: event.address("pool_id")?,
+                    maker_order_id: event.u128("maker_order_id")?.to_string(),
+                    taker_order_id: event.u128("taker_order_id")?.to_string(),
+                    maker_client_order_id: event.u64("maker_client_order_id")?,
+                    taker_client_order_id: event.u64("taker_client_order_id")?,
+                    maker_balance_manager_id: event.address("maker_balance_manager_id")?,
+                    taker_balance_manager_id: event.address("taker_balance_manager_id")?,
+                    price: event.u64("price")?,
+                    taker_is_bid: event.bool("taker_is_bid")?,
+                    base_quantity: event.u64("base_quantity")?,
+                    quote_quantity: event.u64("quote_quantity")?,
+                    taker_fee: event.u64("taker_fee")?,
+                    taker_fee_is_myso: event.bool("taker_fee_is_myso")?,
+                    maker_fee: event.u64("maker_fee")?,
+                    maker_fee_is_myso: event.bool("maker_fee_is_myso")?,
+                    fill_timestamp_ms: event.u64("timestamp")?,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use myso_indexer_alt_framework::pipeline::Processor;
+use myso_types::MYSO_SOCIAL_ADDRESS;
+use myso_types::base_types::EpochId;
+use myso_types::full_checkpoint_content::Checkpoint;
+
+use crate::Row;
+use crate::handlers::tables::move_event::EventFilter;
+use crate::handlers::tables::move_event::decode_events;
+use crate::package_store::PackageCache;
+use crate::pipeline::Pipeline;
+use crate::tables::FollowAction;
+use crate::tables::SocialFollowRow;
+
+const FILTERS: &[EventFilter] = &[EventFilter {
+    address: MYSO_SOCIAL_ADDRESS,
+    module: "social_graph",
+    names: &["FollowEvent", "UnfollowEvent"],
+}];
+
+pub struct SocialFollowProcessor {
+    package_cache: Arc<PackageCache>,
+}
+
+impl SocialFollowProcessor {
+    pub fn new(package_cache: Arc<PackageCache>) -> Self {
+        Self { package_cache }
+    }
+}
+
+impl Row for SocialFollowRow {
+    fn get_epoch(&self) -> EpochId {
+        self.epoch
+    }
+
+    fn get_checkpoint(&self) -> u64 {
+        self.checkpoint
+    }
+}
+
+#[async_trait]
+impl Processor for SocialFollowProcessor {
+    const NAME: &'static str = Pipeline::SocialFollow.name();
+    const FANOUT: usize = 16;
+    type Value = SocialFollowRow;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+        let epoch = checkpoint.summary.data().epoch;
+        let checkpoint_seq = checkpoint.summary.data().sequence_number;
+        let timestamp_ms = checkpoint.summary.data().timestamp_ms;
+
+        decode_events(checkpoint, &self.package_cache, FILTERS)
+            .await?
+            .into_iter()
+            .map(|event| {
+                let (action, followee) = if event.name == "FollowEvent" {
+                    (FollowAction::Follow, event.address("following")?)
+                } else {
+                    (FollowAction::Unfollow, event.address("unfollowed")?)
+                };
+                Ok(SocialFollowRow {
+                    transaction_digest: event.transaction_digest.clone(),
+                    event_index: event.event_index,
+                    checkpoint: checkpoint_seq,
+                    epoch,
+                    timestamp_ms,
+                    action,
+                    follower: event.address("follower")?,
+                    followee,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use myso_indexer_alt_framework::pipeline::Processor;
+use myso_types::MYSO_SOCIAL_ADDRESS;
+use myso_types::base_types::EpochId;
+use myso_types::full_checkpoint_content::Checkpoint;
+
+use crate::Row;
+use crate::handlers::tables::move_event::EventFilter;
+use crate::handlers::tables::move_event::decode_events;
+use crate::package_store::PackageCache;
+use crate::pipeline::Pipeline;
+use crate::tables::SocialPostRow;
+
+const FILTERS: &[EventFilter] = &[EventFilter {
+    address: MYSO_SOCIAL_ADDRESS,
+    module: "post",
+    names: &["PostCreatedEvent"],
+}];
+
+pub struct SocialPostProcessor {
+    package_cache: Arc<PackageCache>,
+}
+
+impl SocialPostProcessor {
+    pub fn new(package_cache: Arc<PackageCache>) -> Self {
+        Self { package_cache }
+    }
+}
+
+impl Row for SocialPostRow {
+    fn get_epoch(&self) -> EpochId {
+        self.epoch
+    }
+
+    fn get_checkpoint(&self) -> u64 {
+        self.checkpoint
+    }
+}
+
+#[async_trait]
+impl Processor for SocialPostProcessor {
+    const NAME: &'static str = Pipeline::SocialPost.name();
+    const FANOUT: usize = 16;
+    type Value = SocialPostRow;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+        let epoch = checkpoint.summary.data().epoch;
+        let checkpoint_seq = checkpoint.summary.data().sequence_number;
+        let timestamp_ms = checkpoint.summary.data().timestamp_ms;
+
+        decode_events(checkpoint, &self.package_cache, FILTERS)
+            .await?
+            .into_iter()
+            .map(|event| {
+                Ok(SocialPostRow {
+                    transaction_digest: event.transaction_digest.clone(),
+                    event_index: event.event_index,
+                    checkpoint: checkpoint_seq,
+                    epoch,
+                    timestamp_ms,
+                    post_id: event.address("post_id")?,
+                    owner: event.address("owner")?,
+                    profile_id: event.address("profile_id")?,
+                    post_type: event.string("post_type")?,
+                    parent_post_id: event.opt_address("parent_post_id")?,
+                    content: event.string("content")?,
+                    mentions: event.opt_joined("mentions")?,
+                    metadata_json: event.opt_string("metadata_json")?,
+                    mydata_id: event.opt_address("mydata_id")?,
+                    promotion_id: event.opt_address("promotion_id")?,
+                    revenue_redirect_to: event.opt_address("revenue_redirect_to")?,
+                    revenue_redirect_percentage: event.opt_u64("revenue_redirect_percentage")?,
+                    enable_spt: event.bool("enable_spt")?,
+                    enable_poc: event.bool("enable_poc")?,
+                    enable_spot: event.bool("enable_spot")?,
+                    spt_id: event.opt_address("spt_id")?,
+                    spot_id: event.opt_address("spot_id")?,
+                })
+            })
+            .collect()
+    }
+}
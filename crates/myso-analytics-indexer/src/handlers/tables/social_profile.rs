@@ -0,0 +1,87 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use myso_indexer_alt_framework::pipeline::Processor;
+use myso_types::MYSO_SOCIAL_ADDRESS;
+use myso_types::base_types::EpochId;
+use myso_types::full_checkpoint_content::Checkpoint;
+
+use crate::Row;
+use crate::handlers::tables::move_event::EventFilter;
+use crate::handlers::tables::move_event::decode_events;
+use crate::package_store::PackageCache;
+use crate::pipeline::Pipeline;
+use crate::tables::ProfileAction;
+use crate::tables::SocialProfileRow;
+
+const FILTERS: &[EventFilter] = &[EventFilter {
+    address: MYSO_SOCIAL_ADDRESS,
+    module: "profile",
+    names: &["ProfileCreatedEvent", "ProfileUpdatedEvent"],
+}];
+
+pub struct SocialProfileProcessor {
+    package_cache: Arc<PackageCache>,
+}
+
+impl SocialProfileProcessor {
+    pub fn new(package_cache: Arc<PackageCache>) -> Self {
+        Self { package_cache }
+    }
+}
+
+impl Row for SocialProfileRow {
+    fn get_epoch(&self) -> EpochId {
+        self.epoch
+    }
+
+    fn get_checkpoint(&self) -> u64 {
+        self.checkpoint
+    }
+}
+
+#[async_trait]
+impl Processor for SocialProfileProcessor {
+    const NAME: &'static str = Pipeline::SocialProfile.name();
+    const FANOUT: usize = 16;
+    type Value = SocialProfileRow;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+        let epoch = checkpoint.summary.data().epoch;
+        let checkpoint_seq = checkpoint.summary.data().sequence_number;
+        let timestamp_ms = checkpoint.summary.data().timestamp_ms;
+
+        decode_events(checkpoint, &self.package_cache, FILTERS)
+            .await?
+            .into_iter()
+            .map(|event| {
+                let (action, event_time) = if event.name == "ProfileCreatedEvent" {
+                    (ProfileAction::Created, event.u64("created_at")?)
+                } else {
+                    (ProfileAction::Updated, event.u64("updated_at")?)
+                };
+                Ok(SocialProfileRow {
+                    transaction_digest: event.transaction_digest.clone(),
+                    event_index: event.event_index,
+                    checkpoint: checkpoint_seq,
+                    epoch,
+                    timestamp_ms,
+                    action,
+                    profile_id: event.address("profile_id")?,
+                    owner: event.address("owner")?,
+                    username: event.string("username")?,
+                    display_name: event.maybe_string("display_name")?,
+                    bio: event.string("bio")?,
+                    profile_picture: event.opt_string("profile_picture")?,
+                    cover_photo: event.opt_string("cover_photo")?,
+                    event_time,
+                })
+            })
+            .collect()
+    }
+}
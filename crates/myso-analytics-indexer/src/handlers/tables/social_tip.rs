@@ -0,0 +1,77 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use myso_indexer_alt_framework::pipeline::Processor;
+use myso_types::MYSO_SOCIAL_ADDRESS;
+use myso_types::base_types::EpochId;
+use myso_types::full_checkpoint_content::Checkpoint;
+
+use crate::Row;
+use crate::handlers::tables::move_event::EventFilter;
+use crate::handlers::tables::move_event::decode_events;
+use crate::package_store::PackageCache;
+use crate::pipeline::Pipeline;
+use crate::tables::SocialTipRow;
+
+const FILTERS: &[EventFilter] = &[EventFilter {
+    address: MYSO_SOCIAL_ADDRESS,
+    module: "post",
+    names: &["TipEvent"],
+}];
+
+pub struct SocialTipProcessor {
+    package_cache: Arc<PackageCache>,
+}
+
+impl SocialTipProcessor {
+    pub fn new(package_cache: Arc<PackageCache>) -> Self {
+        Self { package_cache }
+    }
+}
+
+impl Row for SocialTipRow {
+    fn get_epoch(&self) -> EpochId {
+        self.epoch
+    }
+
+    fn get_checkpoint(&self) -> u64 {
+        self.checkpoint
+    }
+}
+
+#[async_trait]
+impl Processor for SocialTipProcessor {
+    const NAME: &'static str = Pipeline::SocialTip.name();
+    const FANOUT: usize = 16;
+    type Value = SocialTipRow;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+        let epoch = checkpoint.summary.data().epoch;
+        let checkpoint_seq = checkpoint.summary.data().sequence_number;
+        let timestamp_ms = checkpoint.summary.data().timestamp_ms;
+
+        decode_events(checkpoint, &self.package_cache, FILTERS)
+            .await?
+            .into_iter()
+            .map(|event| {
+                Ok(SocialTipRow {
+                    transaction_digest: event.transaction_digest.clone(),
+                    event_index: event.event_index,
+                    checkpoint: checkpoint_seq,
+                    epoch,
+                    timestamp_ms,
+                    object_id: event.address("object_id")?,
+                    from_address: event.address("from")?,
+                    to_address: event.address("to")?,
+                    amount: event.u64("amount")?,
+                    is_post: event.bool("is_post")?,
+                })
+            })
+            .collect()
+    }
+}
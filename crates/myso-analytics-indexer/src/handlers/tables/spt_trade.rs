@@ -0,0 +1,88 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use myso_indexer_alt_framework::pipeline::Processor;
+use myso_types::MYSO_SOCIAL_ADDRESS;
+use myso_types::base_types::EpochId;
+use myso_types::full_checkpoint_content::Checkpoint;
+
+use crate::Row;
+use crate::handlers::tables::move_event::EventFilter;
+use crate::handlers::tables::move_event::decode_events;
+use crate::package_store::PackageCache;
+use crate::pipeline::Pipeline;
+use crate::tables::SptTradeRow;
+use crate::tables::TradeSide;
+
+const FILTERS: &[EventFilter] = &[EventFilter {
+    address: MYSO_SOCIAL_ADDRESS,
+    module: "social_proof_tokens",
+    names: &["TokenBoughtEvent", "TokenSoldEvent"],
+}];
+
+pub struct SptTradeProcessor {
+    package_cache: Arc<PackageCache>,
+}
+
+impl SptTradeProcessor {
+    pub fn new(package_cache: Arc<PackageCache>) -> Self {
+        Self { package_cache }
+    }
+}
+
+impl Row for SptTradeRow {
+    fn get_epoch(&self) -> EpochId {
+        self.epoch
+    }
+
+    fn get_checkpoint(&self) -> u64 {
+        self.checkpoint
+    }
+}
+
+#[async_trait]
+impl Processor for SptTradeProcessor {
+    const NAME: &'static str = Pipeline::SptTrade.name();
+    const FANOUT: usize = 16;
+    type Value = SptTradeRow;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+        let epoch = checkpoint.summary.data().epoch;
+        let checkpoint_seq = checkpoint.summary.data().sequence_number;
+        let timestamp_ms = checkpoint.summary.data().timestamp_ms;
+
+        decode_events(checkpoint, &self.package_cache, FILTERS)
+            .await?
+            .into_iter()
+            .map(|event| {
+                let (side, trader) = if event.name == "TokenBoughtEvent" {
+                    (TradeSide::Buy, event.address("buyer")?)
+                } else {
+                    (TradeSide::Sell, event.address("seller")?)
+                };
+                Ok(SptTradeRow {
+                    transaction_digest: event.transaction_digest.clone(),
+                    event_index: event.event_index,
+                    checkpoint: checkpoint_seq,
+                    epoch,
+                    timestamp_ms,
+                    pool_id: event.address("id")?,
+                    trader,
+                    side,
+                    amount: event.u64("amount")?,
+                    myso_amount: event.u64("myso_amount")?,
+                    fee_amount: event.u64("fee_amount")?,
+                    creator_fee: event.u64("creator_fee")?,
+                    platform_fee: event.u64("platform_fee")?,
+                    treasury_fee: event.u64("treasury_fee")?,
+                    new_price: event.u64("new_price")?,
+                })
+            })
+            .collect()
+    }
+}
@@ -70,6 +70,7 @@ pub async fn build_analytics_indexer(
         last_checkpoint: adjusted_last_checkpoint,
         pipeline: vec![],
         task: Default::default(),
+        backfill: Default::default(),
     };
 
     let client_args = myso_indexer_alt_framework::ingestion::ClientArgs {
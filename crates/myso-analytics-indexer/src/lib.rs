@@ -5,7 +5,9 @@
 //! Analytics indexer for MySo blockchain data.
 //!
 //! This crate provides an analytics indexer that processes MySo blockchain data
-//! and writes it to columnar formats (CSV, Parquet) for analytics workloads.
+//! and writes it to columnar formats (CSV, Parquet, Arrow IPC) for analytics workloads,
+//! optionally as Delta tables. Output written to a local directory can be explored with SQL
+//! through the `query` module.
 
 pub mod config;
 pub mod handlers;
@@ -14,6 +16,7 @@ pub mod metrics;
 pub mod package_store;
 pub mod pipeline;
 pub mod progress_monitoring;
+pub mod query;
 pub mod schema;
 pub mod store;
 pub mod tables;
@@ -25,6 +28,7 @@ pub use config::FileFormat;
 pub use config::IndexerConfig;
 pub use config::OutputStoreConfig;
 pub use config::PipelineConfig;
+pub use config::TableFormat;
 pub use handlers::AnalyticsHandler;
 pub use handlers::Batch;
 pub use handlers::Row;
@@ -2,9 +2,12 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::env;
+use std::path::PathBuf;
 
+use anyhow::Context as _;
 use anyhow::Result;
+use clap::Parser;
+use clap::Subcommand;
 use myso_futures::service::Error;
 use prometheus::Registry;
 use tracing::info;
@@ -12,6 +15,33 @@ use tracing::info;
 use myso_analytics_indexer::IndexerConfig;
 use myso_analytics_indexer::build_analytics_indexer;
 use myso_analytics_indexer::metrics::Metrics;
+use myso_analytics_indexer::query::run_query;
+
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Args {
+    /// Path to the indexer's configuration yaml file.
+    config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a SQL query over the tables in a local output directory, and print the results as CSV.
+    ///
+    /// Each directory in the output directory is a table named after it (e.g. `checkpoints`).
+    Query {
+        /// The directory that the indexer wrote its output to (the `path` of a `file` output
+        /// store).
+        #[arg(long)]
+        dir: PathBuf,
+
+        /// The query to run, in SQLite's dialect of SQL.
+        sql: String,
+    },
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -19,10 +49,13 @@ async fn main() -> Result<()> {
         .with_env()
         .init();
 
-    let args: Vec<String> = env::args().collect();
-    assert_eq!(args.len(), 2, "configuration yaml file is required");
+    let args = Args::parse();
+    if let Some(Command::Query { dir, sql }) = args.command {
+        return run_query(&dir, &sql, std::io::stdout().lock());
+    }
 
-    let config: IndexerConfig = serde_yaml::from_str(&std::fs::read_to_string(&args[1])?)?;
+    let config_path = args.config.context("configuration yaml file is required")?;
+    let config: IndexerConfig = serde_yaml::from_str(&std::fs::read_to_string(&config_path)?)?;
     info!("Parsed config: {:#?}", config);
 
     let registry_service = mysten_metrics::start_prometheus_server(
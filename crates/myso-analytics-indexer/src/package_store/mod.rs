@@ -232,3 +232,17 @@ impl PackageCache {
         Ok(id.into())
     }
 }
+
+/// A package cache at `path`, preloaded with the system packages, so tests can decode system
+/// types without an RPC to fetch packages from.
+#[cfg(test)]
+pub(crate) fn system_package_cache(path: &Path) -> PackageCache {
+    let cache = PackageCache::new(path, "http://localhost:1");
+    let packages: Vec<_> = myso_framework::BuiltInFramework::genesis_objects().collect();
+    cache
+        .base_store
+        .tables
+        .update_batch(&packages)
+        .expect("Failed to write system packages");
+    cache
+}
@@ -24,15 +24,39 @@ use crate::handlers::tables::DynamicFieldProcessor;
 use crate::handlers::tables::EventProcessor;
 use crate::handlers::tables::MoveCallProcessor;
 use crate::handlers::tables::ObjectProcessor;
+use crate::handlers::tables::OrderbookFillProcessor;
 use crate::handlers::tables::PackageBCSProcessor;
 use crate::handlers::tables::PackageProcessor;
+use crate::handlers::tables::SocialFollowProcessor;
+use crate::handlers::tables::SocialPostProcessor;
+use crate::handlers::tables::SocialProfileProcessor;
+use crate::handlers::tables::SocialTipProcessor;
+use crate::handlers::tables::SptTradeProcessor;
 use crate::handlers::tables::TransactionBCSProcessor;
 use crate::handlers::tables::TransactionObjectsProcessor;
 use crate::handlers::tables::TransactionProcessor;
 use crate::handlers::tables::WrappedObjectProcessor;
 use crate::metrics::Metrics;
 use crate::package_store::PackageCache;
+use crate::schema::RowSchema;
 use crate::store::AnalyticsStore;
+use crate::tables::CheckpointRow;
+use crate::tables::DynamicFieldRow;
+use crate::tables::EventRow;
+use crate::tables::MoveCallRow;
+use crate::tables::MovePackageRow;
+use crate::tables::ObjectRow;
+use crate::tables::OrderbookFillRow;
+use crate::tables::PackageBCSRow;
+use crate::tables::SocialFollowRow;
+use crate::tables::SocialPostRow;
+use crate::tables::SocialProfileRow;
+use crate::tables::SocialTipRow;
+use crate::tables::SptTradeRow;
+use crate::tables::TransactionBCSRow;
+use crate::tables::TransactionObjectRow;
+use crate::tables::TransactionRow;
+use crate::tables::WrappedObjectRow;
 
 /// Register a sequential pipeline with the analytics handler.
 async fn register_sequential_pipeline<P, T>(
@@ -80,6 +104,12 @@ pub enum Pipeline {
     MovePackageBCS,
     DynamicField,
     WrappedObject,
+    SocialProfile,
+    SocialPost,
+    SocialFollow,
+    SocialTip,
+    SptTrade,
+    OrderbookFill,
 }
 
 impl Pipeline {
@@ -99,6 +129,12 @@ impl Pipeline {
             Pipeline::MovePackageBCS => "MovePackageBCS",
             Pipeline::DynamicField => "DynamicField",
             Pipeline::WrappedObject => "WrappedObject",
+            Pipeline::SocialProfile => "SocialProfile",
+            Pipeline::SocialPost => "SocialPost",
+            Pipeline::SocialFollow => "SocialFollow",
+            Pipeline::SocialTip => "SocialTip",
+            Pipeline::SptTrade => "SptTrade",
+            Pipeline::OrderbookFill => "OrderbookFill",
         }
     }
 
@@ -118,6 +154,35 @@ impl Pipeline {
             Pipeline::MovePackageBCS => "move_package_bcs",
             Pipeline::DynamicField => "dynamic_field",
             Pipeline::WrappedObject => "wrapped_object",
+            Pipeline::SocialProfile => "social_profile",
+            Pipeline::SocialPost => "social_post",
+            Pipeline::SocialFollow => "social_follow",
+            Pipeline::SocialTip => "social_tip",
+            Pipeline::SptTrade => "spt_trade",
+            Pipeline::OrderbookFill => "orderbook_fill",
+        }
+    }
+
+    /// Returns the column names of the rows this pipeline writes.
+    pub fn schema(&self) -> &'static [&'static str] {
+        match self {
+            Pipeline::Checkpoint => CheckpointRow::schema(),
+            Pipeline::Transaction => TransactionRow::schema(),
+            Pipeline::TransactionBCS => TransactionBCSRow::schema(),
+            Pipeline::TransactionObjects => TransactionObjectRow::schema(),
+            Pipeline::Object => ObjectRow::schema(),
+            Pipeline::Event => EventRow::schema(),
+            Pipeline::MoveCall => MoveCallRow::schema(),
+            Pipeline::MovePackage => MovePackageRow::schema(),
+            Pipeline::MovePackageBCS => PackageBCSRow::schema(),
+            Pipeline::DynamicField => DynamicFieldRow::schema(),
+            Pipeline::WrappedObject => WrappedObjectRow::schema(),
+            Pipeline::SocialProfile => SocialProfileRow::schema(),
+            Pipeline::SocialPost => SocialPostRow::schema(),
+            Pipeline::SocialFollow => SocialFollowRow::schema(),
+            Pipeline::SocialTip => SocialTipRow::schema(),
+            Pipeline::SptTrade => SptTradeRow::schema(),
+            Pipeline::OrderbookFill => OrderbookFillRow::schema(),
         }
     }
 
@@ -194,6 +259,54 @@ impl Pipeline {
                 )
                 .await
             }
+            Pipeline::SocialProfile => {
+                register_sequential_pipeline(
+                    indexer,
+                    SocialProfileProcessor::new(package_cache.clone()),
+                    sequential_config,
+                )
+                .await
+            }
+            Pipeline::SocialPost => {
+                register_sequential_pipeline(
+                    indexer,
+                    SocialPostProcessor::new(package_cache.clone()),
+                    sequential_config,
+                )
+                .await
+            }
+            Pipeline::SocialFollow => {
+                register_sequential_pipeline(
+                    indexer,
+                    SocialFollowProcessor::new(package_cache.clone()),
+                    sequential_config,
+                )
+                .await
+            }
+            Pipeline::SocialTip => {
+                register_sequential_pipeline(
+                    indexer,
+                    SocialTipProcessor::new(package_cache.clone()),
+                    sequential_config,
+                )
+                .await
+            }
+            Pipeline::SptTrade => {
+                register_sequential_pipeline(
+                    indexer,
+                    SptTradeProcessor::new(package_cache.clone()),
+                    sequential_config,
+                )
+                .await
+            }
+            Pipeline::OrderbookFill => {
+                register_sequential_pipeline(
+                    indexer,
+                    OrderbookFillProcessor::new(package_cache.clone()),
+                    sequential_config,
+                )
+                .await
+            }
         }
     }
 }
@@ -0,0 +1,385 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local SQL queries over analytics output.
+//!
+//! Runs SQL against the files that the indexer has written to a local directory (the `path` of a
+//! `file` output store), without loading them into a warehouse first. Each directory under the
+//! output directory is a table, named after the directory (e.g. `checkpoints`, `transactions`).
+//! Tables that the query mentions are loaded into an in-memory SQLite database before the query
+//! is run, so this is meant for exploring modest amounts of data, not for whole-chain analysis.
+//!
+//! Parquet and Arrow files carry their own column names. CSV files do not, so CSV tables can only
+//! be loaded if they are in the default output path of a pipeline, which determines their
+//! columns. Files written with different versions of a table's schema can be mixed: the table has
+//! the union of all the files' columns, and rows from files that lack a column read it as NULL.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::bail;
+use arrow::array::Array;
+use arrow::array::AsArray;
+use arrow::array::RecordBatch;
+use arrow::datatypes::DataType;
+use arrow::datatypes::Int64Type;
+use arrow::datatypes::UInt64Type;
+use arrow::ipc::reader::FileReader;
+use arrow::util::display::array_value_to_string;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use rusqlite::Connection;
+use rusqlite::params_from_iter;
+use rusqlite::types::Value;
+use rusqlite::types::ValueRef;
+use strum::IntoEnumIterator;
+
+use crate::pipeline::Pipeline;
+
+/// A table being loaded into SQLite, tracking the columns it has been created with so far.
+struct Table<'c> {
+    conn: &'c Connection,
+    name: String,
+    columns: Vec<String>,
+}
+
+/// Run `sql` over the tables in `output_dir`, writing the results to `out` as CSV, with a header.
+pub fn run_query(output_dir: &Path, sql: &str, out: impl Write) -> Result<()> {
+    let mut conn = Connection::open_in_memory()?;
+
+    let words: HashSet<String> = sql
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map(str::to_lowercase)
+        .collect();
+
+    for (name, dir) in discover_tables(output_dir)? {
+        if words.contains(&name.to_lowercase()) {
+            load_table(&mut conn, &name, &dir)
+                .with_context(|| format!("Failed to load table {name}"))?;
+        }
+    }
+
+    let mut stmt = conn.prepare(sql)?;
+    let column_count = stmt.column_count();
+
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(stmt.column_names())?;
+
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let mut record = Vec::with_capacity(column_count);
+        for i in 0..column_count {
+            record.push(match row.get_ref(i)? {
+                ValueRef::Null => String::new(),
+                ValueRef::Integer(v) => v.to_string(),
+                ValueRef::Real(v) => v.to_string(),
+                ValueRef::Text(v) => String::from_utf8_lossy(v).into_owned(),
+                ValueRef::Blob(v) => v.iter().map(|b| format!("{b:02x}")).collect(),
+            });
+        }
+        writer.write_record(&record)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// The tables in `output_dir`: every directory in it that isn't hidden or metadata (prefixed
+/// with `.` or `_`), sorted by name.
+fn discover_tables(output_dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut tables = vec![];
+    for entry in std::fs::read_dir(output_dir)
+        .with_context(|| format!("Failed to read output directory {}", output_dir.display()))?
+    {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type()?.is_dir() && !name.starts_with(['.', '_']) {
+            tables.push((name, entry.path()));
+        }
+    }
+
+    tables.sort();
+    Ok(tables)
+}
+
+/// Every data file under `dir`, in path order, skipping hidden and metadata directories (such as
+/// a Delta table's `_delta_log`).
+fn data_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|e| e.path());
+
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with(['.', '_']) {
+            continue;
+        }
+
+        if entry.file_type()?.is_dir() {
+            data_files(&entry.path(), files)?;
+        } else {
+            files.push(entry.path());
+        }
+    }
+
+    Ok(())
+}
+
+fn load_table(conn: &mut Connection, name: &str, dir: &Path) -> Result<()> {
+    let mut files = vec![];
+    data_files(dir, &mut files)?;
+
+    // CSV files don't have a header, so their columns come from the pipeline that writes to the
+    // table's directory by default.
+    let csv_schema = Pipeline::iter()
+        .find(|p| p.default_path() == name)
+        .map(|p| p.schema());
+
+    let tx = conn.transaction()?;
+    let mut table = Table {
+        conn: &tx,
+        name: name.to_string(),
+        columns: vec![],
+    };
+
+    for file in files {
+        match file.extension().and_then(|e| e.to_str()) {
+            Some("parquet") => {
+                let reader =
+                    ParquetRecordBatchReaderBuilder::try_new(File::open(&file)?)?.build()?;
+                for batch in reader {
+                    table.insert_batch(&batch?)?;
+                }
+            }
+
+            Some("arrow") => {
+                let reader = FileReader::try_new(File::open(&file)?, None)?;
+                for batch in reader {
+                    table.insert_batch(&batch?)?;
+                }
+            }
+
+            Some("csv") => {
+                let Some(schema) = csv_schema else {
+                    bail!(
+                        "Cannot load {}: CSV files can only be queried in a pipeline's default output path",
+                        file.display()
+                    );
+                };
+
+                table.insert_csv(schema, &file)?;
+            }
+
+            _ => continue,
+        }
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
+impl Table<'_> {
+    /// Make sure the table exists and has all of `columns`, creating it or adding columns to it
+    /// as necessary.
+    fn ensure_columns<'s>(&mut self, columns: impl IntoIterator<Item = &'s str>) -> Result<()> {
+        let new: Vec<&str> = columns
+            .into_iter()
+            .filter(|c| !self.columns.iter().any(|e| e == c))
+            .collect();
+
+        if new.is_empty() {
+            return Ok(());
+        }
+
+        if self.columns.is_empty() {
+            let columns: Vec<_> = new.iter().map(|c| quote(c)).collect();
+            self.conn.execute(
+                &format!(
+                    "CREATE TABLE {} ({})",
+                    quote(&self.name),
+                    columns.join(", ")
+                ),
+                [],
+            )?;
+        } else {
+            for column in &new {
+                self.conn.execute(
+                    &format!(
+                        "ALTER TABLE {} ADD COLUMN {}",
+                        quote(&self.name),
+                        quote(column)
+                    ),
+                    [],
+                )?;
+            }
+        }
+
+        self.columns.extend(new.into_iter().map(String::from));
+        Ok(())
+    }
+
+    fn insert_batch(&mut self, batch: &RecordBatch) -> Result<()> {
+        let schema = batch.schema();
+        let columns: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        self.ensure_columns(columns.iter().copied())?;
+
+        let mut stmt = self.conn.prepare(&insert_statement(&self.name, &columns))?;
+        for row in 0..batch.num_rows() {
+            let values = batch
+                .columns()
+                .iter()
+                .map(|array| sql_value(array.as_ref(), row))
+                .collect::<Result<Vec<_>>>()?;
+            stmt.execute(params_from_iter(values))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert_csv(&mut self, schema: &[&str], file: &Path) -> Result<()> {
+        self.ensure_columns(schema.iter().copied())?;
+
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(b'|')
+            .from_path(file)?;
+
+        let mut stmt = self.conn.prepare(&insert_statement(&self.name, schema))?;
+        for record in reader.records() {
+            let record = record?;
+            let values = record.iter().map(|field| {
+                if field.is_empty() {
+                    Value::Null
+                } else if let Ok(v) = field.parse::<i64>() {
+                    Value::Integer(v)
+                } else {
+                    Value::Text(field.to_string())
+                }
+            });
+            stmt.execute(params_from_iter(values))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The value at `row` in `array`, as a SQLite value. Unsigned integers that don't fit in a
+/// SQLite integer, and types that SQLite has no equivalent for, are stored as text.
+fn sql_value(array: &dyn Array, row: usize) -> Result<Value> {
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+
+    Ok(match array.data_type() {
+        DataType::UInt64 => {
+            let v = array.as_primitive::<UInt64Type>().value(row);
+            i64::try_from(v).map_or_else(|_| Value::Text(v.to_string()), Value::Integer)
+        }
+        DataType::Int64 => Value::Integer(array.as_primitive::<Int64Type>().value(row)),
+        DataType::Boolean => Value::Integer(array.as_boolean().value(row) as i64),
+        DataType::Utf8 => Value::Text(array.as_string::<i32>().value(row).to_string()),
+        _ => Value::Text(array_value_to_string(array, row)?),
+    })
+}
+
+fn insert_statement(table: &str, columns: &[&str]) -> String {
+    let names: Vec<_> = columns.iter().map(|c| quote(c)).collect();
+    let params = vec!["?"; columns.len()].join(", ");
+    format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        quote(table),
+        names.join(", "),
+        params
+    )
+}
+
+/// Quote an identifier for use in SQL.
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::ArrayRef;
+    use arrow::array::StringArray;
+    use arrow::array::UInt64Array;
+    use parquet::arrow::ArrowWriter;
+
+    use super::*;
+
+    fn write_parquet(path: &Path, columns: Vec<(&str, ArrayRef)>) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+        let mut writer =
+            ArrowWriter::try_new(File::create(path).unwrap(), batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    fn query(dir: &Path, sql: &str) -> String {
+        let mut out = Vec::new();
+        run_query(dir, sql, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_query_across_schema_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let table = dir.path().join("checkpoints");
+
+        write_parquet(
+            &table.join("epoch_0/0_2.parquet"),
+            vec![(
+                "checkpoint",
+                Arc::new(UInt64Array::from(vec![0, 1])) as ArrayRef,
+            )],
+        );
+
+        // A later file has a column that the earlier one did not.
+        write_parquet(
+            &table.join("epoch_1/2_3.parquet"),
+            vec![
+                (
+                    "checkpoint",
+                    Arc::new(UInt64Array::from(vec![2])) as ArrayRef,
+                ),
+                (
+                    "digest",
+                    Arc::new(StringArray::from(vec!["abc"])) as ArrayRef,
+                ),
+            ],
+        );
+
+        // Metadata directories are not data.
+        std::fs::create_dir_all(table.join("_delta_log")).unwrap();
+        std::fs::write(table.join("_delta_log/00000000000000000000.json"), "{}").unwrap();
+
+        assert_eq!(
+            query(
+                dir.path(),
+                "SELECT checkpoint, digest FROM checkpoints ORDER BY checkpoint"
+            ),
+            "checkpoint,digest\n0,\n1,\n2,abc\n",
+        );
+    }
+
+    #[test]
+    fn test_query_csv_uses_pipeline_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema = Pipeline::MoveCall.schema();
+
+        let row: Vec<String> = (0..schema.len()).map(|i| i.to_string()).collect();
+        let path = dir.path().join("move_call/epoch_0/0_1.csv");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, format!("{}\n", row.join("|"))).unwrap();
+
+        let sql = format!("SELECT {} FROM move_call", quote(schema[1]));
+        assert_eq!(query(dir.path(), &sql), format!("{}\n1\n", schema[1]));
+    }
+}
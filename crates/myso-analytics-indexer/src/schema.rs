@@ -14,9 +14,12 @@ use std::sync::Arc;
 use myso_types::dynamic_field::DynamicFieldType;
 use thiserror::Error;
 
+use crate::tables::FollowAction;
 use crate::tables::InputObjectKind;
 use crate::tables::ObjectStatus;
 use crate::tables::OwnerType;
+use crate::tables::ProfileAction;
+use crate::tables::TradeSide;
 
 /// Error type for column access operations.
 #[derive(Debug, Error)]
@@ -120,6 +123,24 @@ impl<'a> From<&'a Option<DynamicFieldType>> for ColumnValue<'a> {
     }
 }
 
+impl<'a> From<&'a ProfileAction> for ColumnValue<'a> {
+    fn from(value: &'a ProfileAction) -> Self {
+        Self::Str(Cow::Owned(value.to_string()))
+    }
+}
+
+impl<'a> From<&'a FollowAction> for ColumnValue<'a> {
+    fn from(value: &'a FollowAction) -> Self {
+        Self::Str(Cow::Owned(value.to_string()))
+    }
+}
+
+impl<'a> From<&'a TradeSide> for ColumnValue<'a> {
+    fn from(value: &'a TradeSide) -> Self {
+        Self::Str(Cow::Owned(value.to_string()))
+    }
+}
+
 /// Trait for types that can describe their columnar schema and provide column values.
 ///
 /// This trait enables generic serialization of row types to columnar formats.
@@ -0,0 +1,598 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Delta Lake transaction log for pipelines written with the `delta` table format.
+//!
+//! Data files are written to their usual paths (`{prefix}/epoch_{epoch}/{start}_{end}.parquet`),
+//! and once a file has been uploaded, it is committed to the log at `{prefix}/_delta_log/` in its
+//! own version, as an `add` action in the `_epoch` partition. This lets Delta readers treat the
+//! pipeline's output as a table partitioned by epoch, without changing where files are written.
+//!
+//! The table's schema evolves with the pipeline's rows: when a file contains columns the table
+//! has not seen before, they are appended to the table's schema (in a new `metaData` action) in
+//! the same version that adds the file. Columns are never removed (files without them read as
+//! null), and changing the type of an existing column is an error.
+//!
+//! Versions are written with a conditional create, so that two writers cannot commit the same
+//! version. If a commit loses that race, the log is reloaded and the commit retried.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::bail;
+use arrow::datatypes::DataType;
+use arrow::datatypes::Schema;
+use bytes::Bytes;
+use myso_storage::object_store::util::find_all_dirs_with_epoch_prefix;
+use myso_types::base_types::EpochId;
+use object_store::Error as ObjectStoreError;
+use object_store::ObjectStore;
+use object_store::ObjectStoreExt as _;
+use object_store::PutMode;
+use object_store::PutOptions;
+use object_store::PutPayload;
+use object_store::path::Path as ObjectPath;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::info;
+
+/// Name of the column that tables are partitioned by. Prefixed with an underscore so that it
+/// does not collide with the `epoch` column that many tables already have.
+const PARTITION_COLUMN: &str = "_epoch";
+
+/// The Delta protocol versions that the log is written with (the lowest versions available).
+const MIN_READER_VERSION: u32 = 1;
+const MIN_WRITER_VERSION: u32 = 2;
+
+/// One line of a commit file, holding a single action.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct Action {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<Protocol>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta_data: Option<Metadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    add: Option<Add>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Protocol {
+    min_reader_version: u32,
+    min_writer_version: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct Metadata {
+    id: String,
+    format: Format,
+    schema_string: String,
+    partition_columns: Vec<String>,
+    configuration: BTreeMap<String, String>,
+    created_time: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Format {
+    provider: String,
+    options: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Add {
+    path: String,
+    partition_values: BTreeMap<String, String>,
+    size: u64,
+    modification_time: i64,
+    data_change: bool,
+}
+
+/// A table schema, as it is stored in `Metadata::schema_string`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+struct StructType {
+    #[serde(rename = "type")]
+    type_: String,
+    fields: Vec<StructField>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+struct StructField {
+    name: String,
+    #[serde(rename = "type")]
+    type_: String,
+    nullable: bool,
+    metadata: BTreeMap<String, String>,
+}
+
+/// The transaction log of one pipeline's table.
+pub(crate) struct DeltaLog {
+    object_store: Arc<dyn ObjectStore>,
+    /// The pipeline's output prefix, which is the root of the table.
+    prefix: String,
+    /// The state of the log, loaded on first use.
+    state: Option<LogState>,
+}
+
+/// What the log contains, as of its latest version.
+struct LogState {
+    /// The version the next commit will be written as.
+    next_version: u64,
+    /// The table's metadata, if the log has been created.
+    metadata: Option<Metadata>,
+    /// The table's columns (excluding the partition column), in order.
+    columns: Vec<StructField>,
+    /// Paths (relative to the table root) of the files added to the table.
+    added: HashSet<String>,
+}
+
+impl DeltaLog {
+    pub(crate) fn new(object_store: Arc<dyn ObjectStore>, prefix: String) -> Self {
+        Self {
+            object_store,
+            prefix,
+            state: None,
+        }
+    }
+
+    /// Add the data file at `path`, in `epoch`, to the table, evolving the table's schema to
+    /// include any new columns in `bytes` (the file's contents). Adding a file that is already in
+    /// the table is a no-op, so commits can be safely retried.
+    pub(crate) async fn commit(
+        &mut self,
+        path: &ObjectPath,
+        epoch: EpochId,
+        bytes: &Bytes,
+    ) -> Result<()> {
+        let result = self.try_commit(path, epoch, bytes).await;
+        if result.is_err() {
+            // Forget the state so the next attempt starts from the log as it is in the store,
+            // in case another writer committed in the meantime.
+            self.state = None;
+        }
+
+        result
+    }
+
+    async fn try_commit(&mut self, path: &ObjectPath, epoch: EpochId, bytes: &Bytes) -> Result<()> {
+        if self.state.is_none() {
+            self.load().await?;
+        }
+
+        self.commit_file(path, epoch, bytes).await
+    }
+
+    async fn commit_file(
+        &mut self,
+        path: &ObjectPath,
+        epoch: EpochId,
+        bytes: &Bytes,
+    ) -> Result<()> {
+        let relative = self.relative_path(path)?;
+        let state = self.state.as_mut().expect("log state loaded before commit");
+        if state.added.contains(&relative) {
+            return Ok(());
+        }
+
+        let columns = parquet_columns(bytes)?;
+        let evolved = evolve_columns(&state.columns, &columns)?;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let mut actions = vec![];
+        let metadata = match &state.metadata {
+            None => {
+                actions.push(Action {
+                    protocol: Some(Protocol {
+                        min_reader_version: MIN_READER_VERSION,
+                        min_writer_version: MIN_WRITER_VERSION,
+                    }),
+                    ..Default::default()
+                });
+
+                Some(Metadata {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    format: Format {
+                        provider: "parquet".to_string(),
+                        options: BTreeMap::new(),
+                    },
+                    schema_string: schema_string(&evolved)?,
+                    partition_columns: vec![PARTITION_COLUMN.to_string()],
+                    configuration: BTreeMap::new(),
+                    created_time: now,
+                })
+            }
+
+            Some(metadata) if evolved != state.columns => Some(Metadata {
+                schema_string: schema_string(&evolved)?,
+                ..metadata.clone()
+            }),
+
+            Some(_) => None,
+        };
+
+        if let Some(metadata) = &metadata {
+            actions.push(Action {
+                meta_data: Some(metadata.clone()),
+                ..Default::default()
+            });
+        }
+
+        actions.push(Action {
+            add: Some(Add {
+                path: relative.clone(),
+                partition_values: BTreeMap::from([(
+                    PARTITION_COLUMN.to_string(),
+                    epoch.to_string(),
+                )]),
+                size: bytes.len() as u64,
+                modification_time: now,
+                data_change: true,
+            }),
+            ..Default::default()
+        });
+
+        let mut commit = Vec::new();
+        for action in &actions {
+            serde_json::to_writer(&mut commit, action)?;
+            commit.push(b'\n');
+        }
+
+        let version = state.next_version;
+        self.object_store
+            .put_opts(
+                &version_path(&self.prefix, version),
+                PutPayload::from(commit),
+                PutOptions {
+                    mode: PutMode::Create,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| match e {
+                ObjectStoreError::AlreadyExists { path, .. } => {
+                    anyhow::anyhow!("Delta log version {path} was committed by another writer")
+                }
+                e => e.into(),
+            })?;
+
+        if evolved != state.columns && state.metadata.is_some() {
+            info!(prefix = self.prefix, version, "Evolved Delta table schema");
+        }
+
+        state.next_version = version + 1;
+        state.columns = evolved;
+        if metadata.is_some() {
+            state.metadata = metadata;
+        }
+        state.added.insert(relative);
+        Ok(())
+    }
+
+    /// Replay the log to find its latest version, schema and files. Then add any data files in
+    /// the latest epoch that the log is missing: the uploader commits each file right after
+    /// uploading it, so if it stopped between the two, the missing file is the last one it
+    /// uploaded.
+    async fn load(&mut self) -> Result<()> {
+        let mut state = LogState {
+            next_version: 0,
+            metadata: None,
+            columns: vec![],
+            added: HashSet::new(),
+        };
+
+        let log_dir = ObjectPath::from(format!("{}/_delta_log", self.prefix));
+        let listing = self
+            .object_store
+            .list_with_delimiter(Some(&log_dir))
+            .await?;
+
+        let mut versions: Vec<u64> = listing
+            .objects
+            .iter()
+            .filter_map(|obj| obj.location.filename()?.strip_suffix(".json")?.parse().ok())
+            .collect();
+        versions.sort();
+
+        for version in versions {
+            let bytes = self
+                .object_store
+                .get(&version_path(&self.prefix, version))
+                .await?
+                .bytes()
+                .await?;
+
+            for line in bytes.split(|b| *b == b'\n').filter(|l| !l.is_empty()) {
+                let action: Action = serde_json::from_slice(line)
+                    .with_context(|| format!("Failed to parse Delta log version {version}"))?;
+
+                if let Some(metadata) = action.meta_data {
+                    let schema: StructType = serde_json::from_str(&metadata.schema_string)
+                        .context("Failed to parse Delta table schema")?;
+                    state.columns = schema
+                        .fields
+                        .into_iter()
+                        .filter(|f| f.name != PARTITION_COLUMN)
+                        .collect();
+                    state.metadata = Some(metadata);
+                }
+
+                if let Some(add) = action.add {
+                    state.added.insert(add.path);
+                }
+            }
+
+            state.next_version = version + 1;
+        }
+
+        self.state = Some(state);
+        self.reconcile_latest_epoch().await
+    }
+
+    async fn reconcile_latest_epoch(&mut self) -> Result<()> {
+        let prefix = ObjectPath::from(self.prefix.as_str());
+        let epoch_dirs = find_all_dirs_with_epoch_prefix(&self.object_store, Some(&prefix)).await?;
+        let Some((&epoch, epoch_path)) = epoch_dirs.last_key_value() else {
+            return Ok(());
+        };
+
+        let listing = self
+            .object_store
+            .list_with_delimiter(Some(epoch_path))
+            .await?;
+
+        let mut missing = vec![];
+        for obj in listing.objects {
+            if !obj.location.as_ref().ends_with(".parquet") {
+                continue;
+            }
+
+            let relative = self.relative_path(&obj.location)?;
+            let state = self.state.as_ref().expect("state set before reconciling");
+            if !state.added.contains(&relative) {
+                missing.push(obj.location);
+            }
+        }
+
+        missing.sort_by_key(|p| {
+            p.filename()
+                .and_then(super::parse_checkpoint_range)
+                .map(|r| r.start)
+        });
+
+        for path in missing {
+            info!(prefix = self.prefix, %path, "Adding file missing from Delta log");
+            let bytes = self.object_store.get(&path).await?.bytes().await?;
+            self.commit_file(&path, epoch, &bytes).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Path of a data file relative to the root of the table.
+    fn relative_path(&self, path: &ObjectPath) -> Result<String> {
+        let prefix = ObjectPath::from(self.prefix.as_str());
+        let Some(parts) = path.prefix_match(&prefix) else {
+            bail!("{path} is not in table {}", self.prefix);
+        };
+
+        Ok(parts
+            .map(|p| p.as_ref().to_string())
+            .collect::<Vec<_>>()
+            .join("/"))
+    }
+}
+
+/// Path of the commit file for `version` of the table at `prefix`.
+fn version_path(prefix: &str, version: u64) -> ObjectPath {
+    ObjectPath::from(format!("{prefix}/_delta_log/{version:020}.json"))
+}
+
+/// The columns of the parquet file in `bytes`, as Delta schema fields.
+fn parquet_columns(bytes: &Bytes) -> Result<Vec<StructField>> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(bytes.clone())
+        .context("Failed to read parquet schema")?;
+    arrow_columns(builder.schema())
+}
+
+fn arrow_columns(schema: &Schema) -> Result<Vec<StructField>> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let type_ = match field.data_type() {
+                // Delta has no unsigned types, so unsigned columns are read as signed.
+                DataType::UInt64 | DataType::Int64 => "long",
+                DataType::Boolean => "boolean",
+                DataType::Utf8 | DataType::LargeUtf8 => "string",
+                DataType::Binary | DataType::LargeBinary => "binary",
+                other => bail!("Column {} has unsupported type {other}", field.name()),
+            };
+
+            Ok(StructField {
+                name: field.name().clone(),
+                type_: type_.to_string(),
+                nullable: field.is_nullable(),
+                metadata: BTreeMap::new(),
+            })
+        })
+        .collect()
+}
+
+/// The table's columns after adding a file with `columns`: existing columns keep their position
+/// (and become nullable if the file does not have them), and new columns are appended.
+fn evolve_columns(current: &[StructField], columns: &[StructField]) -> Result<Vec<StructField>> {
+    let mut evolved = current.to_vec();
+
+    for field in &mut evolved {
+        match columns.iter().find(|c| c.name == field.name) {
+            Some(c) if c.type_ != field.type_ => bail!(
+                "Column {} changed type from {} to {}",
+                field.name,
+                field.type_,
+                c.type_,
+            ),
+            Some(c) => field.nullable |= c.nullable,
+            None => field.nullable = true,
+        }
+    }
+
+    for column in columns {
+        if !current.iter().any(|f| f.name == column.name) {
+            evolved.push(StructField {
+                // Files written before this column existed don't have it.
+                nullable: !current.is_empty() || column.nullable,
+                ..column.clone()
+            });
+        }
+    }
+
+    Ok(evolved)
+}
+
+/// The schema string for a table with `columns`, followed by the partition column.
+fn schema_string(columns: &[StructField]) -> Result<String> {
+    let mut fields = columns.to_vec();
+    fields.push(StructField {
+        name: PARTITION_COLUMN.to_string(),
+        type_: "long".to_string(),
+        nullable: false,
+        metadata: BTreeMap::new(),
+    });
+
+    Ok(serde_json::to_string(&StructType {
+        type_: "struct".to_string(),
+        fields,
+    })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::ArrayRef;
+    use arrow::array::BooleanArray;
+    use arrow::array::RecordBatch;
+    use arrow::array::UInt64Array;
+    use object_store::memory::InMemory;
+    use parquet::arrow::ArrowWriter;
+
+    fn parquet_file(columns: Vec<(&str, ArrayRef)>) -> Bytes {
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+        let mut buffer = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buffer, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        Bytes::from(buffer)
+    }
+
+    async fn read_actions(store: &Arc<dyn ObjectStore>, version: u64) -> Vec<Action> {
+        let path = ObjectPath::from(format!("table/_delta_log/{version:020}.json"));
+        let bytes = store.get(&path).await.unwrap().bytes().await.unwrap();
+        bytes
+            .split(|b| *b == b'\n')
+            .filter(|l| !l.is_empty())
+            .map(|l| serde_json::from_slice(l).unwrap())
+            .collect()
+    }
+
+    fn columns(action: &Action) -> Vec<String> {
+        let metadata = action.meta_data.as_ref().unwrap();
+        let schema: StructType = serde_json::from_str(&metadata.schema_string).unwrap();
+        schema.fields.into_iter().map(|f| f.name).collect()
+    }
+
+    #[tokio::test]
+    async fn test_commit_evolves_schema() {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut log = DeltaLog::new(store.clone(), "table".to_string());
+
+        let a = parquet_file(vec![(
+            "a",
+            Arc::new(UInt64Array::from(vec![1])) as ArrayRef,
+        )]);
+        let ab = parquet_file(vec![
+            ("a", Arc::new(UInt64Array::from(vec![2])) as ArrayRef),
+            ("b", Arc::new(BooleanArray::from(vec![true])) as ArrayRef),
+        ]);
+
+        log.commit(&ObjectPath::from("table/epoch_0/0_1.parquet"), 0, &a)
+            .await
+            .unwrap();
+        log.commit(&ObjectPath::from("table/epoch_0/1_2.parquet"), 0, &a)
+            .await
+            .unwrap();
+        log.commit(&ObjectPath::from("table/epoch_1/2_3.parquet"), 1, &ab)
+            .await
+            .unwrap();
+
+        // The first version creates the table.
+        let v0 = read_actions(&store, 0).await;
+        assert!(v0[0].protocol.is_some());
+        assert_eq!(columns(&v0[1]), vec!["a", "_epoch"]);
+        assert_eq!(v0[2].add.as_ref().unwrap().path, "epoch_0/0_1.parquet");
+
+        // The second version only adds a file, because the schema has not changed.
+        let v1 = read_actions(&store, 1).await;
+        assert_eq!(v1.len(), 1);
+        assert!(v1[0].add.is_some());
+
+        // The third version adds the new column.
+        let v2 = read_actions(&store, 2).await;
+        assert_eq!(columns(&v2[0]), vec!["a", "b", "_epoch"]);
+        let add = v2[1].add.as_ref().unwrap();
+        assert_eq!(add.path, "epoch_1/2_3.parquet");
+        assert_eq!(add.partition_values[PARTITION_COLUMN], "1");
+    }
+
+    #[tokio::test]
+    async fn test_load_adds_missing_files() {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let a = parquet_file(vec![(
+            "a",
+            Arc::new(UInt64Array::from(vec![1])) as ArrayRef,
+        )]);
+
+        let committed = ObjectPath::from("table/epoch_0/0_1.parquet");
+        store.put(&committed, a.clone().into()).await.unwrap();
+        let mut log = DeltaLog::new(store.clone(), "table".to_string());
+        log.commit(&committed, 0, &a).await.unwrap();
+
+        // A file is uploaded, but the writer stops before committing it.
+        let uncommitted = ObjectPath::from("table/epoch_0/1_2.parquet");
+        store.put(&uncommitted, a.clone().into()).await.unwrap();
+
+        // A new writer picks up the missing file before committing its own.
+        let mut log = DeltaLog::new(store.clone(), "table".to_string());
+        log.commit(&ObjectPath::from("table/epoch_0/2_3.parquet"), 0, &a)
+            .await
+            .unwrap();
+
+        let v1 = read_actions(&store, 1).await;
+        assert_eq!(v1[0].add.as_ref().unwrap().path, "epoch_0/1_2.parquet");
+        let v2 = read_actions(&store, 2).await;
+        assert_eq!(v2[0].add.as_ref().unwrap().path, "epoch_0/2_3.parquet");
+    }
+
+    #[test]
+    fn test_evolve_rejects_type_change() {
+        let long = StructField {
+            name: "a".to_string(),
+            type_: "long".to_string(),
+            nullable: true,
+            metadata: BTreeMap::new(),
+        };
+        let string = StructField {
+            type_: "string".to_string(),
+            ..long.clone()
+        };
+
+        assert!(evolve_columns(&[long], &[string]).is_err());
+    }
+}
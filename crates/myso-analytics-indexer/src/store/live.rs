@@ -35,6 +35,10 @@ impl LiveStore {
         Self { object_store }
     }
 
+    pub(crate) fn object_store(&self) -> &Arc<dyn ObjectStore> {
+        &self.object_store
+    }
+
     /// Determine the watermark by scanning file names in the object store.
     ///
     /// 1. Find epoch directories under `{pipeline}/epoch_*`
@@ -199,6 +203,7 @@ mod tests {
             pipeline_configs: vec![PipelineConfig {
                 pipeline: Pipeline::Checkpoint,
                 file_format: crate::config::FileFormat::Parquet,
+                table_format: crate::config::TableFormat::Files,
                 package_id_filter: None,
                 sf_table_id: None,
                 sf_checkpoint_col_id: None,
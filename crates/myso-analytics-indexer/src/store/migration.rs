@@ -110,6 +110,10 @@ impl MigrationStore {
         }
     }
 
+    pub(crate) fn object_store(&self) -> &Arc<dyn ObjectStore> {
+        &self.object_store
+    }
+
     /// Load file ranges and find the starting/ending checkpoints for migration.
     ///
     /// This snaps `first_checkpoint` to file boundaries:
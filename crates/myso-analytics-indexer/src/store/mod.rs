@@ -115,6 +115,7 @@ impl Batch {
     }
 }
 
+mod delta;
 mod live;
 mod migration;
 mod uploader;
@@ -234,6 +235,14 @@ impl StoreMode {
         }
     }
 
+    /// The object store that files are written to.
+    pub(crate) fn object_store(&self) -> &Arc<dyn object_store::ObjectStore> {
+        match self {
+            StoreMode::Live(store) => store.object_store(),
+            StoreMode::Migration(store) => store.object_store(),
+        }
+    }
+
     /// Spawn an upload worker for this mode.
     ///
     /// Returns the sender for queueing files and the worker's JoinHandle.
@@ -728,6 +737,7 @@ pub(crate) fn construct_object_store_path(
     let extension = match file_format {
         FileFormat::Csv => "csv",
         FileFormat::Parquet => "parquet",
+        FileFormat::Arrow => "arrow",
     };
     ObjectPath::from(format!(
         "{}/epoch_{}/{}_{}.{}",
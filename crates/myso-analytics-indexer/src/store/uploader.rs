@@ -31,12 +31,15 @@ use tracing::warn;
 
 use crate::config::FileFormat;
 use crate::config::IndexerConfig;
+use crate::config::TableFormat;
 use crate::handlers::CheckpointRows;
 use crate::handlers::record_file_metrics;
 use crate::metrics::Metrics;
 use crate::store::StoreMode;
 use crate::store::WatermarkUpdateError;
 use crate::store::construct_object_store_path;
+use crate::store::delta::DeltaLog;
+use crate::writers::ArrowIpcWriter;
 use crate::writers::CsvWriter;
 use crate::writers::ParquetWriter;
 
@@ -111,6 +114,11 @@ pub fn spawn_uploader(
         config.max_concurrent_serialization,
     );
 
+    let delta_log = config
+        .get_pipeline_config(&pipeline_name)
+        .filter(|p| p.table_format == TableFormat::Delta)
+        .map(|_| DeltaLog::new(mode.object_store().clone(), output_prefix.clone()));
+
     let uploader = SequentialUploader::new(
         upload_rx,
         pipeline_name,
@@ -118,6 +126,7 @@ pub fn spawn_uploader(
         mode,
         metrics,
         Duration::from_secs(config.watermark_update_interval_secs),
+        delta_log,
     );
 
     // Spawn both tasks, join them together
@@ -277,6 +286,9 @@ struct SequentialUploader {
     watermark_update_interval: Duration,
     /// Latest uploaded watermark (epoch, checkpoint_hi_inclusive).
     latest_watermark: Option<(EpochId, u64)>,
+    /// Transaction log that uploaded files are committed to, for pipelines written as Delta
+    /// tables.
+    delta_log: Option<DeltaLog>,
 }
 
 impl SequentialUploader {
@@ -287,6 +299,7 @@ impl SequentialUploader {
         mode: StoreMode,
         metrics: Metrics,
         watermark_update_interval: Duration,
+        delta_log: Option<DeltaLog>,
     ) -> Self {
         Self {
             rx,
@@ -299,6 +312,7 @@ impl SequentialUploader {
             last_watermark_update: None,
             watermark_update_interval,
             latest_watermark: None,
+            delta_log,
         }
     }
 
@@ -352,6 +366,7 @@ impl SequentialUploader {
                 .await
             {
                 Ok(()) => {
+                    self.commit_to_delta_log_with_retry(&path, file).await;
                     let checkpoint_hi = file.checkpoint_range.end - 1;
 
                     record_file_metrics(&self.metrics, &self.pipeline_name, file.bytes.len());
@@ -419,6 +434,26 @@ impl SequentialUploader {
             .await
     }
 
+    /// Add an uploaded file to the pipeline's Delta log (if it has one), retrying until it
+    /// succeeds.
+    async fn commit_to_delta_log_with_retry(&mut self, path: &ObjectPath, file: &SerializedFile) {
+        let Some(delta_log) = &mut self.delta_log else {
+            return;
+        };
+
+        let mut backoff = Backoff::new();
+        while let Err(e) = delta_log.commit(path, file.epoch, &file.bytes).await {
+            warn!(
+                pipeline = %self.pipeline_name,
+                checkpoint_range = ?file.checkpoint_range,
+                error = %e,
+                backoff_ms = backoff.current_ms(),
+                "Failed to commit file to Delta log, retrying"
+            );
+            backoff.sleep_and_advance().await;
+        }
+    }
+
     /// Update watermark with retry on transient errors, panic on concurrent writer.
     async fn update_watermark_with_retry(&self, epoch: EpochId, checkpoint_hi: u64) {
         let mut backoff = Backoff::new();
@@ -480,5 +515,15 @@ fn serialize_rows(
                 .map(|opt| opt.unwrap_or_default())
                 .map(Bytes::from)
         }
+        FileFormat::Arrow => {
+            let mut writer = ArrowIpcWriter::new()?;
+            for checkpoint in checkpoints {
+                writer.write(checkpoint)?;
+            }
+            writer
+                .flush(schema)
+                .map(|opt| opt.unwrap_or_default())
+                .map(Bytes::from)
+        }
     }
 }
@@ -282,3 +282,163 @@ pub struct WrappedObjectRow {
     pub(crate) json_path: String,
     pub(crate) struct_tag: Option<String>,
 }
+
+// Whether a social profile row records a creation or an update.
+#[derive(Serialize, Clone, Display)]
+pub enum ProfileAction {
+    Created,
+    Updated,
+}
+
+// Direction of a social graph edge change.
+#[derive(Serialize, Clone, Display)]
+pub enum FollowAction {
+    Follow,
+    Unfollow,
+}
+
+// Side of a social proof token trade, from the trader's point of view.
+#[derive(Serialize, Clone, Display)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+// Social profile creations and updates, decoded from `profile` events.
+// Identity is via `transaction_digest` and `event_index`.
+#[derive(Serialize, Clone, SerializeRow)]
+pub struct SocialProfileRow {
+    // indexes
+    pub(crate) transaction_digest: String,
+    pub(crate) event_index: u64,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // profile info
+    pub(crate) action: ProfileAction,
+    pub(crate) profile_id: String,
+    pub(crate) owner: String,
+    pub(crate) username: String,
+    pub(crate) display_name: Option<String>,
+    pub(crate) bio: String,
+    pub(crate) profile_picture: Option<String>,
+    pub(crate) cover_photo: Option<String>,
+    // `created_at` or `updated_at` as reported by the event
+    pub(crate) event_time: u64,
+}
+
+// Posts and comments, decoded from `PostCreatedEvent`.
+#[derive(Serialize, Clone, SerializeRow)]
+pub struct SocialPostRow {
+    // indexes
+    pub(crate) transaction_digest: String,
+    pub(crate) event_index: u64,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // post info
+    pub(crate) post_id: String,
+    pub(crate) owner: String,
+    pub(crate) profile_id: String,
+    pub(crate) post_type: String,
+    pub(crate) parent_post_id: Option<String>,
+    pub(crate) content: String,
+    // commas separated list of mentioned addresses
+    pub(crate) mentions: Option<String>,
+    pub(crate) metadata_json: Option<String>,
+    pub(crate) mydata_id: Option<String>,
+    pub(crate) promotion_id: Option<String>,
+    pub(crate) revenue_redirect_to: Option<String>,
+    pub(crate) revenue_redirect_percentage: Option<u64>,
+    pub(crate) enable_spt: bool,
+    pub(crate) enable_poc: bool,
+    pub(crate) enable_spot: bool,
+    pub(crate) spt_id: Option<String>,
+    pub(crate) spot_id: Option<String>,
+}
+
+// Follow and unfollow edges, decoded from `social_graph` events.
+#[derive(Serialize, Clone, SerializeRow)]
+pub struct SocialFollowRow {
+    // indexes
+    pub(crate) transaction_digest: String,
+    pub(crate) event_index: u64,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // edge info
+    pub(crate) action: FollowAction,
+    pub(crate) follower: String,
+    pub(crate) followee: String,
+}
+
+// Tips on posts, comments and profiles, decoded from `TipEvent`.
+#[derive(Serialize, Clone, SerializeRow)]
+pub struct SocialTipRow {
+    // indexes
+    pub(crate) transaction_digest: String,
+    pub(crate) event_index: u64,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // tip info
+    pub(crate) object_id: String,
+    pub(crate) from_address: String,
+    pub(crate) to_address: String,
+    pub(crate) amount: u64,
+    pub(crate) is_post: bool,
+}
+
+// Social proof token buys and sells, decoded from `social_proof_tokens` events.
+#[derive(Serialize, Clone, SerializeRow)]
+pub struct SptTradeRow {
+    // indexes
+    pub(crate) transaction_digest: String,
+    pub(crate) event_index: u64,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // trade info
+    pub(crate) pool_id: String,
+    pub(crate) trader: String,
+    pub(crate) side: TradeSide,
+    pub(crate) amount: u64,
+    pub(crate) myso_amount: u64,
+    // fees, in MYSO
+    pub(crate) fee_amount: u64,
+    pub(crate) creator_fee: u64,
+    pub(crate) platform_fee: u64,
+    pub(crate) treasury_fee: u64,
+    pub(crate) new_price: u64,
+}
+
+// Orderbook fills, decoded from `order_info::OrderFilled`.
+// A fill is one maker order matched against one taker order.
+#[derive(Serialize, Clone, SerializeRow)]
+pub struct OrderbookFillRow {
+    // indexes
+    pub(crate) transaction_digest: String,
+    pub(crate) event_index: u64,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // fill info
+    pub(crate) pool_id: String,
+    // u128 order ids, as decimal strings
+    pub(crate) maker_order_id: String,
+    pub(crate) taker_order_id: String,
+    pub(crate) maker_client_order_id: u64,
+    pub(crate) taker_client_order_id: u64,
+    pub(crate) maker_balance_manager_id: String,
+    pub(crate) taker_balance_manager_id: String,
+    pub(crate) price: u64,
+    pub(crate) taker_is_bid: bool,
+    pub(crate) base_quantity: u64,
+    pub(crate) quote_quantity: u64,
+    pub(crate) taker_fee: u64,
+    pub(crate) taker_fee_is_myso: bool,
+    pub(crate) maker_fee: u64,
+    pub(crate) maker_fee_is_myso: bool,
+    // `timestamp` as reported by the event
+    pub(crate) fill_timestamp_ms: u64,
+}
@@ -0,0 +1,40 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use arrow::ipc::writer::FileWriter;
+
+use crate::handlers::CheckpointRows;
+use crate::writers::record_batch::RecordBatchBuilder;
+
+/// Writes table entries to the Arrow IPC file format in memory.
+pub struct ArrowIpcWriter {
+    batch: RecordBatchBuilder,
+}
+
+impl ArrowIpcWriter {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            batch: RecordBatchBuilder::new(),
+        })
+    }
+
+    /// Writes the given rows to the Arrow buffer.
+    pub fn write(&mut self, checkpoint: &CheckpointRows) -> Result<()> {
+        self.batch.write(checkpoint)
+    }
+
+    /// Flushes accumulated rows to an in-memory Arrow IPC file.
+    ///
+    /// Takes schema as a parameter since `schema()` is not object-safe.
+    pub fn flush(&mut self, schema: &[&str]) -> Result<Option<Vec<u8>>> {
+        let Some(batch) = self.batch.finish(schema)? else {
+            return Ok(None);
+        };
+
+        let mut writer = FileWriter::try_new(Vec::new(), &batch.schema())?;
+        writer.write(&batch)?;
+        Ok(Some(writer.into_inner()?))
+    }
+}
@@ -5,10 +5,13 @@
 //! Output format writers for analytics data.
 //!
 //! This module provides writers for serializing analytics data to different
-//! columnar formats like CSV and Parquet.
+//! columnar formats like CSV, Parquet and Arrow IPC.
 
 mod csv;
+mod ipc;
 mod parquet;
+mod record_batch;
 
 pub use csv::CsvWriter;
+pub use ipc::ArrowIpcWriter;
 pub use parquet::ParquetWriter;
@@ -0,0 +1,132 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use arrow_array::ArrayRef;
+use arrow_array::RecordBatch;
+use arrow_array::builder::ArrayBuilder;
+use arrow_array::builder::BooleanBuilder;
+use arrow_array::builder::GenericStringBuilder;
+use arrow_array::builder::Int64Builder;
+use arrow_array::builder::UInt64Builder;
+
+use crate::handlers::CheckpointRows;
+use crate::schema::ColumnValue;
+
+type StrBuilder = GenericStringBuilder<i32>;
+
+enum ColumnBuilder {
+    U64(UInt64Builder),
+    I64(Int64Builder),
+    Bool(BooleanBuilder),
+    Str(StrBuilder),
+}
+
+impl ColumnBuilder {
+    fn as_any_builder(&mut self) -> &mut dyn ArrayBuilder {
+        match self {
+            Self::U64(b) => b,
+            Self::I64(b) => b,
+            Self::Bool(b) => b,
+            Self::Str(b) => b,
+        }
+    }
+
+    fn finish(self) -> ArrayRef {
+        match self {
+            Self::U64(mut b) => Arc::new(b.finish()),
+            Self::I64(mut b) => Arc::new(b.finish()),
+            Self::Bool(mut b) => Arc::new(b.finish()),
+            Self::Str(mut b) => Arc::new(b.finish()),
+        }
+    }
+}
+
+/// Accumulates table entries into Arrow columns, for the writers of Arrow-based formats.
+pub(crate) struct RecordBatchBuilder {
+    builders: Vec<ColumnBuilder>,
+}
+
+impl RecordBatchBuilder {
+    pub(crate) fn new() -> Self {
+        Self { builders: vec![] }
+    }
+
+    /// Appends the given rows to the column builders.
+    ///
+    /// Uses `RowSchema::get_column()` for dynamic column access via trait objects.
+    pub(crate) fn write(&mut self, checkpoint: &CheckpointRows) -> Result<()> {
+        if checkpoint.is_empty() {
+            return Ok(());
+        }
+
+        for row in checkpoint.iter() {
+            // Lazily sample the first row to infer the schema
+            if self.builders.is_empty() {
+                for col_idx in 0..row.column_count() {
+                    let value = row.get_column(col_idx)?;
+                    self.builders.push(match value {
+                        ColumnValue::U64(_) | ColumnValue::OptionU64(_) => {
+                            ColumnBuilder::U64(UInt64Builder::new())
+                        }
+                        ColumnValue::I64(_) => ColumnBuilder::I64(Int64Builder::new()),
+                        ColumnValue::Bool(_) => ColumnBuilder::Bool(BooleanBuilder::new()),
+                        ColumnValue::Str(_) | ColumnValue::OptionStr(_) => {
+                            ColumnBuilder::Str(StrBuilder::new())
+                        }
+                    });
+                }
+            }
+
+            for col_idx in 0..row.column_count() {
+                let value = row.get_column(col_idx)?;
+                match (&mut self.builders[col_idx], value) {
+                    (ColumnBuilder::U64(b), ColumnValue::U64(v)) => b.append_value(v),
+                    (ColumnBuilder::I64(b), ColumnValue::I64(v)) => b.append_value(v),
+                    (ColumnBuilder::Bool(b), ColumnValue::Bool(v)) => b.append_value(v),
+                    (ColumnBuilder::Str(b), ColumnValue::Str(v)) => b.append_value(v.as_ref()),
+
+                    (ColumnBuilder::U64(b), ColumnValue::OptionU64(opt)) => match opt {
+                        Some(v) => b.append_value(v),
+                        None => b.append_null(),
+                    },
+                    (ColumnBuilder::Str(b), ColumnValue::OptionStr(opt)) => match opt {
+                        Some(v) => b.append_value(v.as_ref()),
+                        None => b.append_null(),
+                    },
+
+                    _ => return Err(anyhow!("type mismatch on column {}", col_idx)),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Turns the accumulated columns into a record batch, with columns named by `schema`.
+    ///
+    /// Returns `None` if no rows have been written since the last call.
+    pub(crate) fn finish(&mut self, schema: &[&str]) -> Result<Option<RecordBatch>> {
+        // Nothing to flush if builders aren't initialized or are empty
+        if self.builders.is_empty()
+            || self
+                .builders
+                .iter_mut()
+                .all(|b| b.as_any_builder().is_empty())
+        {
+            return Ok(None);
+        }
+
+        // Turn builders into Arrow arrays.
+        let arrays: Vec<ArrayRef> = std::mem::take(&mut self.builders)
+            .into_iter()
+            .map(|b| b.finish())
+            .collect();
+
+        Ok(Some(RecordBatch::try_from_iter(schema.iter().zip(arrays))?))
+    }
+}
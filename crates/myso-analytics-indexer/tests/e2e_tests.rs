@@ -33,6 +33,7 @@ use myso_analytics_indexer::config::FileFormat;
 use myso_analytics_indexer::config::IndexerConfig;
 use myso_analytics_indexer::config::OutputStoreConfig;
 use myso_analytics_indexer::config::PipelineConfig;
+use myso_analytics_indexer::config::TableFormat;
 use myso_analytics_indexer::metrics::Metrics;
 use myso_analytics_indexer::pipeline::Pipeline;
 use myso_analytics_indexer::store::AnalyticsStore;
@@ -42,7 +43,13 @@ use myso_analytics_indexer::tables::EventRow;
 use myso_analytics_indexer::tables::MoveCallRow;
 use myso_analytics_indexer::tables::MovePackageRow;
 use myso_analytics_indexer::tables::ObjectRow;
+use myso_analytics_indexer::tables::OrderbookFillRow;
 use myso_analytics_indexer::tables::PackageBCSRow;
+use myso_analytics_indexer::tables::SocialFollowRow;
+use myso_analytics_indexer::tables::SocialPostRow;
+use myso_analytics_indexer::tables::SocialProfileRow;
+use myso_analytics_indexer::tables::SocialTipRow;
+use myso_analytics_indexer::tables::SptTradeRow;
 use myso_analytics_indexer::tables::TransactionBCSRow;
 use myso_analytics_indexer::tables::TransactionObjectRow;
 use myso_analytics_indexer::tables::TransactionRow;
@@ -64,6 +71,12 @@ fn pipeline_schema(pipeline: &Pipeline) -> &'static [&'static str] {
         Pipeline::MovePackageBCS => PackageBCSRow::schema(),
         Pipeline::DynamicField => DynamicFieldRow::schema(),
         Pipeline::WrappedObject => WrappedObjectRow::schema(),
+        Pipeline::SocialProfile => SocialProfileRow::schema(),
+        Pipeline::SocialPost => SocialPostRow::schema(),
+        Pipeline::SocialFollow => SocialFollowRow::schema(),
+        Pipeline::SocialTip => SocialTipRow::schema(),
+        Pipeline::SptTrade => SptTradeRow::schema(),
+        Pipeline::OrderbookFill => OrderbookFillRow::schema(),
     }
 }
 
@@ -71,6 +84,7 @@ fn default_pipeline_config(pipeline: Pipeline) -> PipelineConfig {
     PipelineConfig {
         pipeline,
         file_format: FileFormat::Parquet,
+        table_format: TableFormat::Files,
         package_id_filter: None,
         sf_table_id: None,
         sf_checkpoint_col_id: None,
@@ -776,6 +790,113 @@ async fn test_csv_file_format() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_arrow_file_format() {
+    let mut harness = TestHarness::new();
+    let checkpoint_seq = harness.add_checkpoint();
+
+    let mut config = harness.default_config();
+    config.last_checkpoint = Some(checkpoint_seq);
+    for pipeline_config in &mut config.pipeline_configs {
+        pipeline_config.file_format = FileFormat::Arrow;
+    }
+
+    harness.run_indexer(config).await;
+
+    let files = harness.list_files("checkpoints/epoch_0").await;
+    let arrow_files = filter_by_extension(&files, ".arrow");
+    assert_eq!(
+        arrow_files.len(),
+        1,
+        "Expected exactly 1 Arrow file in checkpoints/epoch_0"
+    );
+
+    let bytes = harness
+        .object_store()
+        .get(arrow_files[0])
+        .await
+        .expect("Failed to get Arrow file")
+        .bytes()
+        .await
+        .expect("Failed to read Arrow bytes");
+    let reader = arrow::ipc::reader::FileReader::try_new(std::io::Cursor::new(bytes), None)
+        .expect("Failed to create Arrow reader");
+
+    let schema = reader.schema();
+    let columns: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(columns, CheckpointRow::schema());
+
+    let rows: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+    assert_eq!(rows, 1, "Expected 1 row in Arrow file");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_delta_table_format() {
+    let mut harness = TestHarness::new();
+    harness.add_checkpoint();
+    harness.advance_epoch();
+    let checkpoint_seq = harness.add_checkpoint();
+
+    let mut config = harness.default_config();
+    config.last_checkpoint = Some(checkpoint_seq);
+    for pipeline_config in &mut config.pipeline_configs {
+        pipeline_config.table_format = TableFormat::Delta;
+    }
+
+    harness.run_indexer(config).await;
+
+    let data_files = filter_by_extension(&harness.list_files("checkpoints").await, ".parquet")
+        .into_iter()
+        .map(|p| p.as_ref().strip_prefix("checkpoints/").unwrap().to_string())
+        .collect::<Vec<_>>();
+
+    let mut log_files = harness.list_files("checkpoints/_delta_log").await;
+    log_files.sort();
+    assert_eq!(
+        log_files.len(),
+        data_files.len(),
+        "Expected one Delta log version per data file"
+    );
+
+    // Every data file is added to the table, in its epoch's partition.
+    let mut added = vec![];
+    for (version, path) in log_files.iter().enumerate() {
+        assert_eq!(
+            path.filename().unwrap(),
+            format!("{version:020}.json"),
+            "Delta log versions should be contiguous"
+        );
+
+        let bytes = harness
+            .object_store()
+            .get(path)
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+
+        for line in bytes.split(|b| *b == b'\n').filter(|l| !l.is_empty()) {
+            let action: serde_json::Value = serde_json::from_slice(line).unwrap();
+            if let Some(add) = action.get("add") {
+                let path = add["path"].as_str().unwrap().to_string();
+                let epoch = &add["partitionValues"]["_epoch"];
+                assert!(path.starts_with(&format!("epoch_{}/", epoch.as_str().unwrap())));
+                added.push(path);
+            }
+
+            if version == 0 && action.get("metaData").is_some() {
+                assert_eq!(action["metaData"]["partitionColumns"][0], "_epoch");
+            }
+        }
+    }
+
+    added.sort();
+    let mut expected = data_files;
+    expected.sort();
+    assert_eq!(added, expected);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_multiple_pipelines() {
     let mut harness = TestHarness::new();
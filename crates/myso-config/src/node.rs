@@ -1677,6 +1677,51 @@ mod tests {
         let _template: NodeConfig = serde_yaml::from_str(FILE).unwrap();
     }
 
+    #[test]
+    fn cost_aware_traffic_control_policy_config() {
+        use myso_types::traffic_control::{PolicyConfig, PolicyType};
+
+        const CONFIG: &str = r#"
+spam-policy-type:
+  composite:
+    - access-list:
+        path: /opt/myso/config/access-list
+        reload-interval-secs: 30
+    - token-bucket:
+        client-capacity: 5000
+        client-refill-rate: 500
+        gas-budget-unit: 1000000
+        method-costs:
+          myso_dryRunTransactionBlock: 50
+          myso_getObject: 1
+error-policy-type:
+  FreqThreshold:
+    client-threshold: 50
+spam-sample-rate: 1
+"#;
+        let config: PolicyConfig = serde_yaml::from_str(CONFIG).unwrap();
+        let PolicyType::Composite(policies) = config.spam_policy_type else {
+            panic!("Expected composite spam policy");
+        };
+        let [
+            PolicyType::AccessList(access_list),
+            PolicyType::TokenBucket(token_bucket),
+        ] = policies.as_slice()
+        else {
+            panic!("Expected access-list and token-bucket policies");
+        };
+        assert_eq!(access_list.reload_interval_secs, 30);
+        assert_eq!(token_bucket.client_capacity, 5000);
+        assert_eq!(token_bucket.gas_budget_unit, Some(1_000_000));
+        assert_eq!(token_bucket.method_costs.len(), 2);
+        // unset fields fall back to their defaults
+        assert_eq!(token_bucket.default_cost, 1);
+        assert!(matches!(
+            config.error_policy_type,
+            PolicyType::FreqThreshold(_)
+        ));
+    }
+
     #[test]
     fn load_key_pairs_to_node_config() {
         let protocol_key_pair: AuthorityKeyPair =
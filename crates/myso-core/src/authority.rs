@@ -176,8 +176,10 @@ use crate::authority::authority_store::{ExecutionLockReadGuard, ObjectLockStatus
 use crate::authority::authority_store_pruner::{
     AuthorityStorePruner, EPOCH_DURATION_MS_FOR_TESTING,
 };
+use crate::authority::authority_store_tables::AuthorityPerpetualTables;
 use crate::authority::epoch_start_configuration::EpochStartConfigTrait;
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
+use crate::authority::storage_stats::StorageStats;
 use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
 use crate::execution_cache::{
@@ -252,6 +254,7 @@ pub mod epoch_start_configuration;
 pub mod execution_time_estimator;
 pub mod shared_object_congestion_tracker;
 pub mod shared_object_version_manager;
+pub mod storage_stats;
 pub mod submitted_transaction_cache;
 pub mod test_authority_builder;
 pub mod transaction_deferral;
@@ -940,6 +943,7 @@ pub struct AuthorityState {
     tx_execution_shutdown: Mutex<Option<oneshot::Sender<()>>>,
 
     pub metrics: Arc<AuthorityMetrics>,
+    perpetual_tables: Arc<AuthorityPerpetualTables>,
     _pruner: AuthorityStorePruner,
     _authority_per_epoch_pruner: AuthorityPerEpochStorePruner,
 
@@ -1854,14 +1858,20 @@ impl AuthorityState {
         &self,
         params: TrafficControlReconfigParams,
     ) -> Result<TrafficControlReconfigParams, MySoError> {
-        if let Some(traffic_controller) = self.traffic_controller.as_ref() {
-            traffic_controller.admin_reconfigure(params).await
-        } else {
-            Err(MySoErrorKind::InvalidAdminRequest(
+        self.admin_traffic_controller()?
+            .admin_reconfigure(params)
+            .await
+    }
+
+    /// The traffic controller, for use by admin endpoints which should fail
+    /// if traffic control is not enabled on this node.
+    pub fn admin_traffic_controller(&self) -> Result<&Arc<TrafficController>, MySoError> {
+        self.traffic_controller.as_ref().ok_or_else(|| {
+            MySoErrorKind::InvalidAdminRequest(
                 "Traffic controller is not configured on this node".to_string(),
             )
-            .into())
-        }
+            .into()
+        })
     }
 
     #[instrument(level = "trace", skip_all)]
@@ -3601,6 +3611,7 @@ impl AuthorityState {
             execution_scheduler,
             tx_execution_shutdown: Mutex::new(Some(tx_execution_shutdown)),
             metrics,
+            perpetual_tables: store.perpetual_tables.clone(),
             _pruner,
             _authority_per_epoch_pruner,
             db_checkpoint_config: db_checkpoint_config.clone(),
@@ -3728,6 +3739,15 @@ impl AuthorityState {
         &self.execution_cache_trait_pointers.cache_commit
     }
 
+    /// Storage statistics of the perpetual tables and advice on the pruning configuration.
+    pub fn storage_stats(&self) -> MySoResult<StorageStats> {
+        StorageStats::new(
+            &self.perpetual_tables,
+            &self.checkpoint_store,
+            &self.config.authority_store_pruning_config,
+        )
+    }
+
     pub fn database_for_testing(&self) -> Arc<AuthorityStore> {
         self.execution_cache_trait_pointers
             .testing_api
@@ -0,0 +1,335 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Storage statistics of the perpetual tables, computed online from RocksDB properties, and
+//! recommendations for the node's [`AuthorityStorePruningConfig`].
+
+use crate::authority::authority_store_tables::AuthorityPerpetualTables;
+use crate::checkpoints::CheckpointStore;
+use myso_config::node::AuthorityStorePruningConfig;
+use myso_types::committee::EpochId;
+use myso_types::error::MySoResult;
+use myso_types::messages_checkpoint::CheckpointSequenceNumber;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use typed_store::traits::TableStorageStats;
+
+/// Tables holding transaction history, removed by the checkpoint pruner.
+const CHECKPOINT_PRUNED_TABLES: &[&str] = &[
+    "transactions",
+    "effects",
+    "executed_effects",
+    "events_2",
+    "executed_transactions_to_checkpoint",
+    "unchanged_loaded_runtime_objects",
+];
+
+/// Share of a table's sst files that may be dead data before periodic compaction is advised.
+const DEAD_DATA_RATIO_THRESHOLD: f64 = 0.2;
+/// Share of deletions among the entries of the objects table past which tombstones are worth
+/// pruning.
+const TOMBSTONE_RATIO_THRESHOLD: f64 = 0.1;
+const WRITE_AMPLIFICATION_THRESHOLD: f64 = 30.0;
+const PENDING_COMPACTION_BYTES_THRESHOLD: u64 = 64 << 30;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StorageStats {
+    pub epoch: EpochId,
+    pub highest_executed_checkpoint: CheckpointSequenceNumber,
+    pub highest_pruned_checkpoint: CheckpointSequenceNumber,
+    /// Statistics of every non-empty table, keyed by column family name.
+    pub tables: BTreeMap<String, TableStats>,
+    pub pruning_config: AuthorityStorePruningConfig,
+    pub recommended_pruning_config: AuthorityStorePruningConfig,
+    pub recommendations: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TableStats {
+    #[serde(flatten)]
+    pub stats: TableStorageStats,
+    pub reclaimable_by_compaction: u64,
+    /// Space the checkpoint pruner would free under the recommended configuration. Only
+    /// estimated for tables holding transaction history.
+    pub reclaimable_by_pruning: Option<u64>,
+}
+
+impl StorageStats {
+    pub fn new(
+        perpetual_tables: &AuthorityPerpetualTables,
+        checkpoint_store: &CheckpointStore,
+        config: &AuthorityStorePruningConfig,
+    ) -> MySoResult<Self> {
+        let (highest_executed_checkpoint, epoch) = checkpoint_store
+            .get_highest_executed_checkpoint()?
+            .map(|c| (*c.sequence_number(), c.epoch()))
+            .unwrap_or_default();
+        let highest_pruned_checkpoint = checkpoint_store
+            .get_highest_pruned_checkpoint_seq_number()?
+            .unwrap_or_default();
+
+        let tables: BTreeMap<_, _> = perpetual_tables
+            .storage_stats()?
+            .into_iter()
+            .filter(|(_, stats)| stats.total_sst_files_size + stats.total_blob_files_size > 0)
+            .map(|(name, stats)| {
+                let table = TableStats {
+                    reclaimable_by_compaction: stats.reclaimable_by_compaction(),
+                    reclaimable_by_pruning: None,
+                    stats,
+                };
+                (name, table)
+            })
+            .collect();
+
+        let mut this = Self {
+            epoch,
+            highest_executed_checkpoint,
+            highest_pruned_checkpoint,
+            tables,
+            pruning_config: config.clone(),
+            recommended_pruning_config: config.clone(),
+            recommendations: vec![],
+        };
+        this.recommend();
+
+        let prunable = match this
+            .recommended_pruning_config
+            .num_epochs_to_retain_for_checkpoints()
+        {
+            Some(num_epochs_to_retain) if epoch >= num_epochs_to_retain => checkpoint_store
+                .get_epoch_last_checkpoint_seq_number(epoch - num_epochs_to_retain)?
+                .map(|last| last.saturating_sub(highest_pruned_checkpoint))
+                .unwrap_or_default(),
+            _ => 0,
+        };
+        this.estimate_reclaimable_by_pruning(prunable);
+        Ok(this)
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.tables
+            .values()
+            .map(|t| t.stats.total_sst_files_size + t.stats.total_blob_files_size)
+            .sum()
+    }
+
+    /// Fills in `reclaimable_by_pruning` of the transaction history tables, given the number of
+    /// retained checkpoints the recommended configuration would prune.
+    fn estimate_reclaimable_by_pruning(&mut self, prunable: CheckpointSequenceNumber) {
+        // Transaction history is spread evenly enough over checkpoints that the share of
+        // prunable checkpoints estimates the share of prunable history.
+        let retained = self
+            .highest_executed_checkpoint
+            .saturating_sub(self.highest_pruned_checkpoint);
+        for name in CHECKPOINT_PRUNED_TABLES {
+            if let Some(table) = self.tables.get_mut(*name) {
+                let size = table.stats.live_data_size as f64;
+                table.reclaimable_by_pruning = Some(if retained == 0 {
+                    0
+                } else {
+                    (size * prunable.min(retained) as f64 / retained as f64) as u64
+                });
+            }
+        }
+    }
+
+    fn recommend(&mut self) {
+        let recommended = &mut self.recommended_pruning_config;
+        let recommendations = &mut self.recommendations;
+
+        if recommended.num_epochs_to_retain == u64::MAX {
+            recommended.num_epochs_to_retain = 1;
+            recommendations.push(
+                "Object pruning is disabled and every object version is kept: set \
+                 num-epochs-to-retain to 1"
+                    .to_string(),
+            );
+        }
+
+        let history_size: u64 = CHECKPOINT_PRUNED_TABLES
+            .iter()
+            .filter_map(|name| self.tables.get(*name))
+            .map(|t| t.stats.live_data_size)
+            .sum();
+        if recommended.num_epochs_to_retain_for_checkpoints.is_none() && history_size > 0 {
+            recommended.num_epochs_to_retain_for_checkpoints = Some(2);
+            recommendations.push(format!(
+                "Transaction history of all epochs is kept ({history_size} bytes): set \
+                 num-epochs-to-retain-for-checkpoints to 2 unless the node must serve full history"
+            ));
+        }
+
+        if let Some(objects) = self.tables.get("objects") {
+            let entries = objects.stats.estimated_live_keys + objects.stats.estimated_dead_keys;
+            let deletions = objects.stats.num_deletions;
+            if recommended.killswitch_tombstone_pruning
+                && entries > 0
+                && deletions as f64 / entries as f64 > TOMBSTONE_RATIO_THRESHOLD
+            {
+                recommended.killswitch_tombstone_pruning = false;
+                recommendations.push(format!(
+                    "{deletions} of the {entries} entries of the objects table are deletions: \
+                     enable tombstone pruning by unsetting killswitch-tombstone-pruning"
+                ));
+            }
+        }
+
+        let total_size: u64 = self
+            .tables
+            .values()
+            .map(|t| t.stats.total_sst_files_size)
+            .sum();
+        let dead_size: u64 = self
+            .tables
+            .values()
+            .map(|t| t.reclaimable_by_compaction)
+            .sum();
+        if recommended.periodic_compaction_threshold_days.is_none()
+            && total_size > 0
+            && dead_size as f64 / total_size as f64 > DEAD_DATA_RATIO_THRESHOLD
+        {
+            recommended.periodic_compaction_threshold_days = Some(1);
+            recommendations.push(format!(
+                "{dead_size} of {total_size} bytes of sst files are dead data: set \
+                 periodic-compaction-threshold-days to 1"
+            ));
+        }
+
+        for (name, table) in &self.tables {
+            if table.stats.write_amplification > WRITE_AMPLIFICATION_THRESHOLD {
+                if !recommended.smooth {
+                    recommended.smooth = true;
+                    recommendations.push(
+                        "Pruning runs in bursts: set smooth to spread deletions over the epoch"
+                            .to_string(),
+                    );
+                }
+                recommendations.push(format!(
+                    "Write amplification of {name} is {:.1}: consider a lower \
+                     max-checkpoints-in-batch to make pruning batches smaller",
+                    table.stats.write_amplification
+                ));
+            }
+            if table.stats.pending_compaction_bytes > PENDING_COMPACTION_BYTES_THRESHOLD {
+                recommendations.push(format!(
+                    "Compaction of {name} is {} bytes behind: consider compacting it with \
+                     `myso-tool db-tool compact` while the node is stopped",
+                    table.stats.pending_compaction_bytes
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(sst_size: u64, live_data_size: u64) -> TableStats {
+        TableStats {
+            stats: TableStorageStats {
+                total_sst_files_size: sst_size,
+                live_data_size,
+                ..Default::default()
+            },
+            reclaimable_by_compaction: sst_size - live_data_size,
+            reclaimable_by_pruning: None,
+        }
+    }
+
+    fn stats(
+        config: AuthorityStorePruningConfig,
+        tables: impl IntoIterator<Item = (&'static str, TableStats)>,
+    ) -> StorageStats {
+        let mut stats = StorageStats {
+            epoch: 10,
+            highest_executed_checkpoint: 1000,
+            highest_pruned_checkpoint: 0,
+            tables: tables
+                .into_iter()
+                .map(|(name, table)| (name.to_string(), table))
+                .collect(),
+            pruning_config: config.clone(),
+            recommended_pruning_config: config,
+            recommendations: vec![],
+        };
+        stats.recommend();
+        stats
+    }
+
+    #[test]
+    fn test_recommendations() {
+        let config = AuthorityStorePruningConfig {
+            num_epochs_to_retain: 1,
+            num_epochs_to_retain_for_checkpoints: Some(2),
+            periodic_compaction_threshold_days: Some(1),
+            ..Default::default()
+        };
+        let stats = stats(
+            config,
+            [
+                ("objects", table(100, 90)),
+                ("transactions", table(100, 100)),
+            ],
+        );
+        assert!(stats.recommendations.is_empty());
+
+        let config = AuthorityStorePruningConfig {
+            num_epochs_to_retain: u64::MAX,
+            num_epochs_to_retain_for_checkpoints: None,
+            periodic_compaction_threshold_days: None,
+            ..Default::default()
+        };
+        let stats = stats(
+            config,
+            [
+                ("objects", table(100, 50)),
+                ("transactions", table(100, 100)),
+            ],
+        );
+        let recommended = &stats.recommended_pruning_config;
+        assert_eq!(recommended.num_epochs_to_retain, 1);
+        assert_eq!(recommended.num_epochs_to_retain_for_checkpoints, Some(2));
+        assert_eq!(recommended.periodic_compaction_threshold_days, Some(1));
+        assert_eq!(stats.recommendations.len(), 3);
+    }
+
+    #[test]
+    fn test_reclaimable_by_pruning() {
+        let mut stats = stats(
+            AuthorityStorePruningConfig::default(),
+            [
+                ("objects", table(100, 100)),
+                ("transactions", table(1000, 800)),
+                ("effects", table(500, 400)),
+            ],
+        );
+        stats.highest_pruned_checkpoint = 200;
+
+        // A quarter of the 800 retained checkpoints are prunable.
+        stats.estimate_reclaimable_by_pruning(200);
+        assert_eq!(
+            stats.tables["transactions"].reclaimable_by_pruning,
+            Some(200)
+        );
+        assert_eq!(stats.tables["effects"].reclaimable_by_pruning, Some(100));
+        assert_eq!(stats.tables["objects"].reclaimable_by_pruning, None);
+
+        // The estimate never exceeds the live data of a table.
+        stats.estimate_reclaimable_by_pruning(2000);
+        assert_eq!(
+            stats.tables["transactions"].reclaimable_by_pruning,
+            Some(800)
+        );
+
+        stats.estimate_reclaimable_by_pruning(0);
+        assert_eq!(stats.tables["transactions"].reclaimable_by_pruning, Some(0));
+
+        // Nothing is retained once everything executed has been pruned.
+        stats.highest_pruned_checkpoint = stats.highest_executed_checkpoint;
+        stats.estimate_reclaimable_by_pruning(200);
+        assert_eq!(stats.tables["transactions"].reclaimable_by_pruning, Some(0));
+    }
+}
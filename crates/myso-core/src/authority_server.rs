@@ -71,7 +71,10 @@ use crate::consensus_adapter::ConnectionMonitorStatusForTests;
 use crate::{
     authority::{AuthorityState, consensus_tx_status_cache::ConsensusTxStatus},
     consensus_adapter::{ConsensusAdapter, ConsensusAdapterMetrics},
-    traffic_controller::{TrafficController, parse_ip, policies::TrafficTally},
+    traffic_controller::{
+        TrafficController, parse_ip,
+        policies::{RequestInfo, TrafficTally},
+    },
 };
 use crate::{
     authority::{
@@ -1658,6 +1661,7 @@ impl ValidatorService {
     fn handle_traffic_resp<T>(
         &self,
         client: Option<IpAddr>,
+        method: &str,
         wrapped_response: WrappedServiceResponse<T>,
     ) -> Result<tonic::Response<T>, tonic::Status> {
         let (error, spam_weight, unwrapped_response) = match wrapped_response {
@@ -1679,6 +1683,10 @@ impl ValidatorService {
                     (error_weight, error_type)
                 }),
                 spam_weight,
+                request: RequestInfo {
+                    method: Some(method.to_string()),
+                    gas_budget: None,
+                },
                 timestamp: SystemTime::now(),
             })
         }
@@ -1717,9 +1725,12 @@ macro_rules! handle_with_decoration {
         // check if either IP is blocked, in which case return early
         $self.handle_traffic_req(client.clone()).await?;
 
-        // handle traffic tallying
+        // handle traffic tallying, naming the request after the service method
+        let method = stringify!($func_name)
+            .trim_start_matches("handle_")
+            .trim_end_matches("_impl");
         let wrapped_response = $self.$func_name($request).await;
-        $self.handle_traffic_resp(client, wrapped_response)
+        $self.handle_traffic_resp(client, method, wrapped_response)
     }};
 }
 
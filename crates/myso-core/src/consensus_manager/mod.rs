@@ -13,7 +13,8 @@ use consensus_config::{
     ProtocolKeyPair,
 };
 use consensus_core::{
-    Clock, CommitConsumerArgs, CommitConsumerMonitor, CommitIndex, ConsensusAuthority, NetworkType,
+    Clock, CommitConsumerArgs, CommitConsumerMonitor, CommitIndex, ConsensusAuthority,
+    DagVisualizer, NetworkType,
 };
 use core::panic;
 use fastcrypto::traits::KeyPair as _;
@@ -389,6 +390,30 @@ impl ConsensusManager {
         ReplayWaiter::new(consumer_monitor_receiver)
     }
 
+    /// Renders rounds of the DAG of the running consensus authority, with its leader decisions.
+    /// See `ConsensusAuthority::visualize_dag` for the default rounds.
+    pub fn visualize_dag(
+        &self,
+        start_round: Option<u32>,
+        end_round: Option<u32>,
+    ) -> MySoResult<DagVisualizer> {
+        let Some(authority) = self.authority.load_full() else {
+            return Err(MySoErrorKind::GenericAuthorityError {
+                error: "Consensus authority node is not running".to_string(),
+            }
+            .into());
+        };
+        authority
+            .0
+            .visualize_dag(start_round, end_round)
+            .map_err(|e| {
+                MySoErrorKind::GenericAuthorityError {
+                    error: format!("Failed to visualize the consensus DAG: {e}"),
+                }
+                .into()
+            })
+    }
+
     pub fn get_storage_base_path(&self) -> PathBuf {
         self.consensus_config.db_path().to_path_buf()
     }
@@ -12,6 +12,8 @@ use fs::File;
 use myso_types::error::{MySoError, MySoErrorKind};
 use mysten_common::fatal;
 use prometheus::IntGauge;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
 use std::fs;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::ops::Add;
@@ -36,8 +38,89 @@ use tracing::{debug, error, info, trace, warn};
 
 pub const METRICS_INTERVAL_SECS: u64 = 2;
 pub const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 300;
+/// Number of most recent tallies kept for dry-running policies
+pub const RECENT_TALLIES_CAPACITY: usize = 10_000;
 
-type Blocklist = Arc<DashMap<IpAddr, SystemTime>>;
+/// The most recent tallies, which policies are dry-run against. Tallies are
+/// recorded whether or not the controller is in dry-run mode, so that a
+/// candidate policy can be tried against live traffic while the current one
+/// keeps blocking.
+#[derive(Default)]
+struct RecentTallies {
+    tallies: ParkingLotMutex<VecDeque<TrafficTally>>,
+}
+
+impl RecentTallies {
+    fn record(&self, tally: &TrafficTally) {
+        let mut tallies = self.tallies.lock();
+        if tallies.len() >= RECENT_TALLIES_CAPACITY {
+            tallies.pop_front();
+        }
+        tallies.push_back(tally.clone());
+    }
+}
+
+/// What caused a client to be added to a blocklist.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BlockSource {
+    /// Blocked by the spam policy of the given type
+    SpamPolicy(&'static str),
+    /// Blocked by the error policy of the given type
+    ErrorPolicy(&'static str),
+    /// Blocked through the admin API
+    Manual,
+}
+
+#[derive(Clone, Debug)]
+pub struct BlocklistEntry {
+    pub expiry: SystemTime,
+    pub source: BlockSource,
+}
+
+/// A client currently on one of the blocklists, as reported to operators.
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockedClient {
+    pub client: IpAddr,
+    /// Whether the client was seen through a fullnode rather than directly
+    pub proxied: bool,
+    /// Seconds since the unix epoch at which the block expires
+    pub expires_at_secs: u64,
+    pub source: BlockSource,
+}
+
+/// Which tallies a policy is dry-run against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DryRunTarget {
+    /// Every recent tally, as seen by the spam policy
+    #[default]
+    Spam,
+    /// Only recent tallies of failed requests, as seen by the error policy
+    Error,
+}
+
+/// A client that a dry-run policy would have blocked.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunBlock {
+    pub client: IpAddr,
+    pub proxied: bool,
+    /// Index of the first sample that would have blocked the client
+    pub first_blocked_sample: usize,
+    /// Number of samples that would have blocked the client
+    pub blocked_samples: usize,
+}
+
+/// Outcome of replaying recent tallies through a policy. Samples are replayed
+/// back to back, so time windowed policies see them as a single burst and the
+/// report errs on the side of blocking too much.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PolicyDryRunReport {
+    pub samples: usize,
+    pub would_block: Vec<DryRunBlock>,
+}
+
+type Blocklist = Arc<DashMap<IpAddr, BlocklistEntry>>;
 
 #[derive(Clone)]
 pub struct Blocklists {
@@ -58,6 +141,7 @@ pub enum Acl {
 #[derive(Clone)]
 pub struct TrafficController {
     tally_channel: Arc<ParkingLotMutex<Option<mpsc::Sender<TrafficTally>>>>,
+    recent_tallies: Arc<RecentTallies>,
     acl: Acl,
     metrics: Arc<TrafficControllerMetrics>,
     spam_policy: Option<Arc<Mutex<TrafficControlPolicy>>>,
@@ -105,6 +189,7 @@ impl TrafficController {
                     .collect();
                 Self {
                     tally_channel: Arc::new(ParkingLotMutex::new(None)),
+                    recent_tallies: Arc::new(RecentTallies::default()),
                     acl: Acl::Allowlist(allowlist),
                     metrics,
                     policy_config: Arc::new(RwLock::new(policy_config)),
@@ -122,6 +207,7 @@ impl TrafficController {
                 ));
                 let this = Self {
                     tally_channel: Arc::new(ParkingLotMutex::new(None)),
+                    recent_tallies: Arc::new(RecentTallies::default()),
                     acl: Acl::Blocklists(Blocklists {
                         clients: Arc::new(DashMap::new()),
                         proxied_clients: Arc::new(DashMap::new()),
@@ -172,6 +258,7 @@ impl TrafficController {
         let clear_loop_metrics = self.metrics.clone();
         let tally_loop_policy_config = policy_config.clone();
         let tally_loop_fw_config = self.fw_config.clone();
+        let tally_loop_recent_tallies = self.recent_tallies.clone();
 
         let spam_policy = self
             .spam_policy
@@ -191,6 +278,7 @@ impl TrafficController {
             error_policy_clone,
             tally_loop_fw_config,
             tally_loop_blocklists,
+            tally_loop_recent_tallies,
             tally_loop_metrics,
             mem_drainfile_present,
         ));
@@ -290,6 +378,145 @@ impl TrafficController {
         }
     }
 
+    fn blocklists(&self) -> Result<&Blocklists, MySoError> {
+        match &self.acl {
+            Acl::Blocklists(blocklists) => Ok(blocklists),
+            Acl::Allowlist(_) => Err(MySoErrorKind::InvalidAdminRequest(
+                "Traffic controller is running with an allowlist and has no blocklists".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    /// Lists the clients that are currently blocked. Clients whose blocking
+    /// was delegated to the node firewall are listed by the firewall instead.
+    pub fn blocked_clients(&self) -> Result<Vec<BlockedClient>, MySoError> {
+        let blocklists = self.blocklists()?;
+        let now = SystemTime::now();
+        let list = |blocklist: &Blocklist, proxied: bool| {
+            blocklist
+                .iter()
+                .filter(|entry| entry.expiry > now)
+                .map(|entry| BlockedClient {
+                    client: *entry.key(),
+                    proxied,
+                    expires_at_secs: entry
+                        .expiry
+                        .duration_since(SystemTime::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs(),
+                    source: entry.source,
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut clients = list(&blocklists.clients, false);
+        clients.extend(list(&blocklists.proxied_clients, true));
+        Ok(clients)
+    }
+
+    /// Blocks `client` for `ttl`, replacing any existing block.
+    pub fn admin_block(
+        &self,
+        client: IpAddr,
+        proxied: bool,
+        ttl: Duration,
+    ) -> Result<(), MySoError> {
+        let blocklists = self.blocklists()?;
+        let (blocklist, gauge) = self.blocklist_and_gauge(blocklists, proxied);
+        let entry = BlocklistEntry {
+            expiry: SystemTime::now() + ttl,
+            source: BlockSource::Manual,
+        };
+        if blocklist.insert(client, entry).is_none() {
+            gauge.inc();
+        }
+        info!("Manually blocked client {client:?} (proxied: {proxied}) for {ttl:?}");
+        Ok(())
+    }
+
+    /// Removes `client` from the blocklist, returning whether it was blocked.
+    pub fn admin_unblock(&self, client: IpAddr, proxied: bool) -> Result<bool, MySoError> {
+        let blocklists = self.blocklists()?;
+        let (blocklist, gauge) = self.blocklist_and_gauge(blocklists, proxied);
+        let removed = blocklist.remove(&client).is_some();
+        if removed {
+            gauge.dec();
+            info!("Manually unblocked client {client:?} (proxied: {proxied})");
+        }
+        Ok(removed)
+    }
+
+    fn blocklist_and_gauge<'a>(
+        &'a self,
+        blocklists: &'a Blocklists,
+        proxied: bool,
+    ) -> (&'a Blocklist, &'a IntGauge) {
+        if proxied {
+            (
+                &blocklists.proxied_clients,
+                &self.metrics.proxy_ip_blocklist_len,
+            )
+        } else {
+            (
+                &blocklists.clients,
+                &self.metrics.connection_ip_blocklist_len,
+            )
+        }
+    }
+
+    /// Replays the most recent tallies through a fresh instance of the given
+    /// policy and reports which clients it would have blocked. The live
+    /// policies and blocklists are left untouched.
+    pub async fn dry_run_policy(
+        &self,
+        policy_type: PolicyType,
+        target: DryRunTarget,
+    ) -> Result<PolicyDryRunReport, MySoError> {
+        self.blocklists()?;
+        if matches!(policy_type, PolicyType::TestPanicOnInvocation) {
+            return Err(MySoErrorKind::InvalidAdminRequest(
+                "TestPanicOnInvocation policy cannot be dry-run".to_string(),
+            )
+            .into());
+        }
+        let samples: Vec<_> = self
+            .recent_tallies
+            .tallies
+            .lock()
+            .iter()
+            .filter(|tally| target == DryRunTarget::Spam || tally.error_info.is_some())
+            .cloned()
+            .collect();
+        let policy_config = { self.policy_config.read().await.clone() };
+        let mut policy = TrafficControlPolicy::from_config(policy_type, policy_config).await;
+
+        let mut blocked = BTreeMap::new();
+        let num_samples = samples.len();
+        for (i, tally) in samples.into_iter().enumerate() {
+            let PolicyResponse {
+                block_client,
+                block_proxied_client,
+            } = policy.handle_tally(tally);
+            for (client, proxied) in [(block_client, false), (block_proxied_client, true)] {
+                if let Some(client) = client {
+                    blocked
+                        .entry((proxied, client))
+                        .or_insert_with(|| DryRunBlock {
+                            client,
+                            proxied,
+                            first_blocked_sample: i,
+                            blocked_samples: 0,
+                        })
+                        .blocked_samples += 1;
+                }
+            }
+        }
+        Ok(PolicyDryRunReport {
+            samples: num_samples,
+            would_block: blocked.into_values().collect(),
+        })
+    }
+
     fn open_tally_channel(&self, tx: mpsc::Sender<TrafficTally>) {
         self.tally_channel.lock().replace(tx);
     }
@@ -414,7 +641,7 @@ impl TrafficController {
         // due to aquiring the lock on get, then holding across the remove
         let (should_block, should_remove) = {
             match blocklist.get(client) {
-                Some(expiration) if now >= *expiration => (false, true),
+                Some(entry) if now >= entry.expiry => (false, true),
                 None => (false, false),
                 _ => (true, false),
             }
@@ -436,10 +663,10 @@ async fn run_clear_blocklists_loop(blocklists: Blocklists, metrics: Arc<TrafficC
     loop {
         tokio::time::sleep(Duration::from_secs(3)).await;
         let now = SystemTime::now();
-        blocklists.clients.retain(|_, expiration| now < *expiration);
+        blocklists.clients.retain(|_, entry| now < entry.expiry);
         blocklists
             .proxied_clients
-            .retain(|_, expiration| now < *expiration);
+            .retain(|_, entry| now < entry.expiry);
         metrics
             .connection_ip_blocklist_len
             .set(blocklists.clients.len() as i64);
@@ -456,6 +683,7 @@ async fn run_tally_loop(
     error_policy: Arc<Mutex<TrafficControlPolicy>>,
     fw_config: Option<RemoteFirewallConfig>,
     blocklists: Blocklists,
+    recent_tallies: Arc<RecentTallies>,
     metrics: Arc<TrafficControllerMetrics>,
     mut mem_drainfile_present: bool,
 ) {
@@ -477,6 +705,7 @@ async fn run_tally_loop(
                 metrics.tallies.inc();
                 match received {
                     Some(tally) => {
+                        recent_tallies.record(&tally);
                         // TODO: spawn a task to handle tallying concurrently
                         if let Err(err) = handle_spam_tally(
                             spam_policy.clone(),
@@ -601,7 +830,13 @@ async fn handle_error_tally(
         .tally_error_types
         .with_label_values(&[error_type.as_str()])
         .inc();
-    let resp = policy.lock().await.handle_tally(tally);
+    let (resp, source) = {
+        let mut policy = policy.lock().await;
+        (
+            policy.handle_tally(tally),
+            BlockSource::ErrorPolicy(policy.name()),
+        )
+    };
     metrics.error_tally_handled.inc();
     if let Some(fw_config) = fw_config
         && fw_config.delegate_error_blocking
@@ -619,7 +854,7 @@ async fn handle_error_tally(
         )
         .await;
     }
-    handle_policy_response(resp, policy_config, blocklists, metrics).await;
+    handle_policy_response(resp, source, policy_config, blocklists, metrics).await;
     Ok(())
 }
 
@@ -636,7 +871,13 @@ async fn handle_spam_tally(
     if !(tally.spam_weight.is_sampled() && policy_config.spam_sample_rate.is_sampled()) {
         return Ok(());
     }
-    let resp = policy.lock().await.handle_tally(tally.clone());
+    let (resp, source) = {
+        let mut policy = policy.lock().await;
+        (
+            policy.handle_tally(tally.clone()),
+            BlockSource::SpamPolicy(policy.name()),
+        )
+    };
     metrics.tally_handled.inc();
     if let Some(fw_config) = fw_config
         && fw_config.delegate_spam_blocking
@@ -654,12 +895,13 @@ async fn handle_spam_tally(
         )
         .await;
     }
-    handle_policy_response(resp, policy_config, blocklists, metrics).await;
+    handle_policy_response(resp, source, policy_config, blocklists, metrics).await;
     Ok(())
 }
 
 async fn handle_policy_response(
     response: PolicyResponse,
+    source: BlockSource,
     policy_config: &PolicyConfig,
     blocklists: Arc<Blocklists>,
     metrics: Arc<TrafficControllerMetrics>,
@@ -678,7 +920,10 @@ async fn handle_policy_response(
             .clients
             .insert(
                 client,
-                SystemTime::now() + Duration::from_secs(*connection_blocklist_ttl_sec),
+                BlocklistEntry {
+                    expiry: SystemTime::now() + Duration::from_secs(*connection_blocklist_ttl_sec),
+                    source,
+                },
             )
             .is_none()
     {
@@ -691,7 +936,10 @@ async fn handle_policy_response(
             .proxied_clients
             .insert(
                 client,
-                SystemTime::now() + Duration::from_secs(*proxy_blocklist_ttl_sec),
+                BlocklistEntry {
+                    expiry: SystemTime::now() + Duration::from_secs(*proxy_blocklist_ttl_sec),
+                    source,
+                },
             )
             .is_none()
     {
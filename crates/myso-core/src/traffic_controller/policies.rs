@@ -3,10 +3,18 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashMap, net::IpAddr, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    path::Path,
+    sync::{Arc, Weak},
+};
 
 use count_min_sketch::CountMinSketch32;
-use myso_types::traffic_control::{FreqThresholdConfig, PolicyConfig, PolicyType, Weight};
+use futures::future::{BoxFuture, FutureExt};
+use myso_types::traffic_control::{
+    AccessListConfig, FreqThresholdConfig, PolicyConfig, PolicyType, TokenBucketConfig, Weight,
+};
 use mysten_metrics::spawn_monitored_task;
 use parking_lot::RwLock;
 use std::cmp::Reverse;
@@ -15,7 +23,9 @@ use std::fmt::Debug;
 use std::hash::Hash;
 use std::time::Duration;
 use std::time::{Instant, SystemTime};
-use tracing::{info, trace};
+use tracing::{info, trace, warn};
+
+use crate::traffic_controller::parse_ip;
 
 const HIGHEST_RATES_CAPACITY: usize = 20;
 
@@ -235,9 +245,20 @@ pub struct TrafficTally {
     pub through_fullnode: Option<IpAddr>,
     pub error_info: Option<(Weight, String)>,
     pub spam_weight: Weight,
+    pub request: RequestInfo,
     pub timestamp: SystemTime,
 }
 
+/// Describes the request that produced a tally, so that cost-aware
+/// policies can weigh requests by the work they cause the node to do.
+#[derive(Clone, Debug, Default)]
+pub struct RequestInfo {
+    /// RPC method name, e.g. `myso_getObject` or `submit_transaction`
+    pub method: Option<String>,
+    /// Gas budget of the transaction carried by the request, if any
+    pub gas_budget: Option<u64>,
+}
+
 impl TrafficTally {
     pub fn new(
         direct: Option<IpAddr>,
@@ -250,9 +271,15 @@ impl TrafficTally {
             through_fullnode,
             error_info,
             spam_weight,
+            request: RequestInfo::default(),
             timestamp: SystemTime::now(),
         }
     }
+
+    pub fn with_request(mut self, request: RequestInfo) -> Self {
+        self.request = request;
+        self
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -272,6 +299,9 @@ pub trait Policy {
 // not object safe, so we can't use a trait object instead
 pub enum TrafficControlPolicy {
     FreqThreshold(FreqThresholdPolicy),
+    TokenBucket(TokenBucketPolicy),
+    AccessList(AccessListPolicy),
+    Composite(CompositePolicy),
     NoOp(NoOpPolicy),
     // Test policies below this point
     TestNConnIP(TestNConnIPPolicy),
@@ -283,6 +313,9 @@ impl Policy for TrafficControlPolicy {
         match self {
             TrafficControlPolicy::NoOp(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::FreqThreshold(policy) => policy.handle_tally(tally),
+            TrafficControlPolicy::TokenBucket(policy) => policy.handle_tally(tally),
+            TrafficControlPolicy::AccessList(policy) => policy.handle_tally(tally),
+            TrafficControlPolicy::Composite(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::TestNConnIP(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::TestPanicOnInvocation(policy) => policy.handle_tally(tally),
         }
@@ -292,6 +325,9 @@ impl Policy for TrafficControlPolicy {
         match self {
             TrafficControlPolicy::NoOp(policy) => policy.policy_config(),
             TrafficControlPolicy::FreqThreshold(policy) => policy.policy_config(),
+            TrafficControlPolicy::TokenBucket(policy) => policy.policy_config(),
+            TrafficControlPolicy::AccessList(policy) => policy.policy_config(),
+            TrafficControlPolicy::Composite(policy) => policy.policy_config(),
             TrafficControlPolicy::TestNConnIP(policy) => policy.policy_config(),
             TrafficControlPolicy::TestPanicOnInvocation(policy) => policy.policy_config(),
         }
@@ -311,6 +347,16 @@ impl TrafficControlPolicy {
             PolicyType::FreqThreshold(freq_threshold_config) => Self::FreqThreshold(
                 FreqThresholdPolicy::new(policy_config, freq_threshold_config),
             ),
+            PolicyType::TokenBucket(token_bucket_config) => {
+                Self::TokenBucket(TokenBucketPolicy::new(policy_config, token_bucket_config))
+            }
+            PolicyType::AccessList(access_list_config) => {
+                Self::AccessList(AccessListPolicy::new(policy_config, access_list_config).await)
+            }
+            PolicyType::Composite(policy_types) => {
+                let policies = Self::from_configs(policy_types, policy_config.clone()).await;
+                Self::Composite(CompositePolicy::new(policy_config, policies))
+            }
             PolicyType::TestNConnIP(n) => {
                 Self::TestNConnIP(TestNConnIPPolicy::new(policy_config, n).await)
             }
@@ -319,6 +365,34 @@ impl TrafficControlPolicy {
             }
         }
     }
+
+    /// Name of the policy type, as used in config.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TrafficControlPolicy::NoOp(_) => "NoOp",
+            TrafficControlPolicy::FreqThreshold(_) => "freq-threshold",
+            TrafficControlPolicy::TokenBucket(_) => "token-bucket",
+            TrafficControlPolicy::AccessList(_) => "access-list",
+            TrafficControlPolicy::Composite(_) => "composite",
+            TrafficControlPolicy::TestNConnIP(_) => "TestNConnIP",
+            TrafficControlPolicy::TestPanicOnInvocation(_) => "TestPanicOnInvocation",
+        }
+    }
+
+    // Boxed, as composite policies may be nested
+    fn from_configs(
+        policy_types: Vec<PolicyType>,
+        policy_config: PolicyConfig,
+    ) -> BoxFuture<'static, Vec<Self>> {
+        async move {
+            let mut policies = Vec::with_capacity(policy_types.len());
+            for policy_type in policy_types {
+                policies.push(Self::from_config(policy_type, policy_config.clone()).await);
+            }
+            policies
+        }
+        .boxed()
+    }
 }
 
 ////////////// *** Policy definitions *** //////////////
@@ -421,6 +495,302 @@ impl FreqThresholdPolicy {
     }
 }
 
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: u64) -> Self {
+        Self {
+            tokens: capacity as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, capacity: u64, refill_rate: u64) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_rate as f64).min(capacity as f64);
+        self.last_refill = now;
+    }
+
+    /// Returns false if the bucket did not hold enough tokens to pay `cost`,
+    /// in which case the bucket is emptied.
+    fn consume(&mut self, cost: u64, capacity: u64, refill_rate: u64) -> bool {
+        self.refill(capacity, refill_rate);
+        if self.tokens >= cost as f64 {
+            self.tokens -= cost as f64;
+            true
+        } else {
+            self.tokens = 0.0;
+            false
+        }
+    }
+
+    fn is_full(&self, capacity: u64, refill_rate: u64) -> bool {
+        let elapsed = self.last_refill.elapsed().as_secs_f64();
+        self.tokens + elapsed * refill_rate as f64 >= capacity as f64
+    }
+}
+
+/// Unlike `FreqThresholdPolicy`, which counts requests, this policy charges
+/// each request a cost so that e.g. a dry run weighs more than a `getObject`.
+/// Since costs are only charged for sampled tallies, `spam-sample-rate` should
+/// normally be 1.0 when this is used as the spam policy.
+pub struct TokenBucketPolicy {
+    pub config: PolicyConfig,
+    pub bucket_config: TokenBucketConfig,
+    buckets: HashMap<(ClientType, IpAddr), TokenBucket>,
+}
+
+impl TokenBucketPolicy {
+    pub fn new(config: PolicyConfig, bucket_config: TokenBucketConfig) -> Self {
+        Self {
+            config,
+            bucket_config,
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// The number of tokens charged for the given request.
+    pub fn request_cost(&self, request: &RequestInfo) -> u64 {
+        let method_cost = request
+            .method
+            .as_ref()
+            .and_then(|method| self.bucket_config.method_costs.get(method))
+            .copied()
+            .unwrap_or(self.bucket_config.default_cost);
+        let gas_cost = match (request.gas_budget, self.bucket_config.gas_budget_unit) {
+            (Some(gas_budget), Some(unit)) if unit > 0 => gas_budget / unit,
+            _ => 0,
+        };
+        method_cost.saturating_add(gas_cost)
+    }
+
+    pub fn handle_tally(&mut self, tally: TrafficTally) -> PolicyResponse {
+        let cost = self.request_cost(&tally.request);
+        if self.buckets.len() > self.bucket_config.max_tracked_clients {
+            self.forget_idle_clients();
+        }
+        let block_client = tally
+            .direct
+            .filter(|client| !self.consume(ClientType::Direct, *client, cost));
+        let block_proxied_client = tally
+            .through_fullnode
+            .filter(|client| !self.consume(ClientType::ThroughFullnode, *client, cost));
+        trace!(
+            "TokenBucketPolicy handling tally -- cost: {cost}, block_client: {block_client:?}, block_proxied_client: {block_proxied_client:?}",
+        );
+        PolicyResponse {
+            block_client,
+            block_proxied_client,
+        }
+    }
+
+    fn limits(&self, client_type: &ClientType) -> (u64, u64) {
+        match client_type {
+            ClientType::Direct => (
+                self.bucket_config.client_capacity,
+                self.bucket_config.client_refill_rate,
+            ),
+            ClientType::ThroughFullnode => (
+                self.bucket_config.proxied_client_capacity,
+                self.bucket_config.proxied_client_refill_rate,
+            ),
+        }
+    }
+
+    fn consume(&mut self, client_type: ClientType, client: IpAddr, cost: u64) -> bool {
+        let (capacity, refill_rate) = self.limits(&client_type);
+        self.buckets
+            .entry((client_type, client))
+            .or_insert_with(|| TokenBucket::new(capacity))
+            .consume(cost, capacity, refill_rate)
+    }
+
+    /// A full bucket is equivalent to an untracked client, so these can be
+    /// dropped without changing the outcome of any future tally.
+    fn forget_idle_clients(&mut self) {
+        let (client_limits, proxied_client_limits) = (
+            self.limits(&ClientType::Direct),
+            self.limits(&ClientType::ThroughFullnode),
+        );
+        self.buckets.retain(|(client_type, _), bucket| {
+            let (capacity, refill_rate) = match client_type {
+                ClientType::Direct => client_limits,
+                ClientType::ThroughFullnode => proxied_client_limits,
+            };
+            !bucket.is_full(capacity, refill_rate)
+        });
+        if self.buckets.len() > self.bucket_config.max_tracked_clients {
+            warn!(
+                "TokenBucketPolicy tracking {} active clients, above the configured maximum of {}",
+                self.buckets.len(),
+                self.bucket_config.max_tracked_clients,
+            );
+        }
+    }
+
+    fn policy_config(&self) -> &PolicyConfig {
+        &self.config
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct AccessLists {
+    pub allow: HashSet<IpAddr>,
+    pub deny: HashSet<IpAddr>,
+}
+
+impl AccessLists {
+    /// Parses a file with one `allow <ip>` or `deny <ip>` entry per line.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut lists = Self::default();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (list, ip) = match line.split_once(char::is_whitespace) {
+                Some(("allow", ip)) => (&mut lists.allow, ip),
+                Some(("deny", ip)) => (&mut lists.deny, ip),
+                _ => {
+                    return Err(format!(
+                        "line {}: expected `allow <ip>` or `deny <ip>`",
+                        i + 1
+                    ));
+                }
+            };
+            let ip = parse_ip(ip.trim())
+                .ok_or_else(|| format!("line {}: invalid ip address {:?}", i + 1, ip.trim()))?;
+            list.insert(ip);
+        }
+        Ok(lists)
+    }
+
+    fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        Self::parse(&contents)
+    }
+}
+
+/// Blocks clients on the deny list as soon as they are tallied. The allow
+/// list only has an effect within a `CompositePolicy`, where it exempts
+/// clients from being blocked by the other policies.
+#[derive(Clone)]
+pub struct AccessListPolicy {
+    pub config: PolicyConfig,
+    lists: Arc<RwLock<AccessLists>>,
+}
+
+impl AccessListPolicy {
+    pub async fn new(config: PolicyConfig, access_list_config: AccessListConfig) -> Self {
+        let lists = AccessLists::load(&access_list_config.path).unwrap_or_else(|e| {
+            warn!("Failed to load traffic control access list, starting empty: {e}");
+            AccessLists::default()
+        });
+        let lists = Arc::new(RwLock::new(lists));
+        spawn_monitored_task!(run_reload_access_lists(
+            Arc::downgrade(&lists),
+            access_list_config,
+        ));
+        Self { config, lists }
+    }
+
+    pub fn is_allowed(&self, client: &IpAddr) -> bool {
+        self.lists.read().allow.contains(client)
+    }
+
+    pub fn is_denied(&self, client: &IpAddr) -> bool {
+        self.lists.read().deny.contains(client)
+    }
+
+    fn handle_tally(&mut self, tally: TrafficTally) -> PolicyResponse {
+        PolicyResponse {
+            block_client: tally.direct.filter(|client| self.is_denied(client)),
+            block_proxied_client: tally
+                .through_fullnode
+                .filter(|client| self.is_denied(client)),
+        }
+    }
+
+    fn policy_config(&self) -> &PolicyConfig {
+        &self.config
+    }
+}
+
+/// Re-reads the access lists every `reload_interval_secs`, swapping them in
+/// when they change. If the file cannot be read or parsed, the previous
+/// lists are kept. Exits once the policy has been dropped.
+async fn run_reload_access_lists(lists: Weak<RwLock<AccessLists>>, config: AccessListConfig) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(config.reload_interval_secs)).await;
+        let Some(lists) = lists.upgrade() else {
+            return;
+        };
+        match AccessLists::load(&config.path) {
+            Ok(new_lists) if *lists.read() != new_lists => {
+                info!(
+                    "Reloaded traffic control access list with {} allowed and {} denied clients",
+                    new_lists.allow.len(),
+                    new_lists.deny.len(),
+                );
+                *lists.write() = new_lists;
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to reload traffic control access list: {e}"),
+        }
+    }
+}
+
+/// Combines several policies. Every tally is handled by each policy, and
+/// a client is blocked if any of them would block it, unless it is on the
+/// allow list of one of the `AccessListPolicy`s.
+pub struct CompositePolicy {
+    pub config: PolicyConfig,
+    pub policies: Vec<TrafficControlPolicy>,
+}
+
+impl CompositePolicy {
+    pub fn new(config: PolicyConfig, policies: Vec<TrafficControlPolicy>) -> Self {
+        Self { config, policies }
+    }
+
+    fn is_allowed(&self, client: &IpAddr) -> bool {
+        self.policies.iter().any(|policy| match policy {
+            TrafficControlPolicy::AccessList(policy) => policy.is_allowed(client),
+            TrafficControlPolicy::Composite(policy) => policy.is_allowed(client),
+            _ => false,
+        })
+    }
+
+    fn handle_tally(&mut self, tally: TrafficTally) -> PolicyResponse {
+        let mut response = PolicyResponse::default();
+        for policy in self.policies.iter_mut() {
+            let PolicyResponse {
+                block_client,
+                block_proxied_client,
+            } = policy.handle_tally(tally.clone());
+            response.block_client = response.block_client.or(block_client);
+            response.block_proxied_client = response.block_proxied_client.or(block_proxied_client);
+        }
+        response.block_client = response
+            .block_client
+            .filter(|client| !self.is_allowed(client));
+        response.block_proxied_client = response
+            .block_proxied_client
+            .filter(|client| !self.is_allowed(client));
+        response
+    }
+
+    fn policy_config(&self) -> &PolicyConfig {
+        &self.config
+    }
+}
+
 ////////////// *** Test policies below this point *** //////////////
 
 #[derive(Clone)]
@@ -548,6 +918,7 @@ mod tests {
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4))),
             error_info: None,
             spam_weight: Weight::one(),
+            request: RequestInfo::default(),
             timestamp: SystemTime::now(),
         };
         let bob = TrafficTally {
@@ -555,6 +926,7 @@ mod tests {
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(4, 3, 2, 1))),
             error_info: None,
             spam_weight: Weight::one(),
+            request: RequestInfo::default(),
             timestamp: SystemTime::now(),
         };
         let charlie = TrafficTally {
@@ -562,6 +934,7 @@ mod tests {
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(5, 6, 7, 8))),
             error_info: None,
             spam_weight: Weight::one(),
+            request: RequestInfo::default(),
             timestamp: SystemTime::now(),
         };
 
@@ -679,6 +1052,196 @@ mod tests {
         assert_eq!(proxied_rate, 1);
     }
 
+    fn tally(direct: Option<IpAddr>, method: &str, gas_budget: Option<u64>) -> TrafficTally {
+        TrafficTally::new(direct, None, None, Weight::one()).with_request(RequestInfo {
+            method: Some(method.to_string()),
+            gas_budget,
+        })
+    }
+
+    #[sim_test]
+    async fn test_token_bucket_policy() {
+        // Buckets hold 10 tokens and refill at 5 tokens per second. Heavy
+        // requests cost 5 tokens, others 1, plus 1 token per 1000 gas budget.
+        let mut policy = TokenBucketPolicy::new(
+            PolicyConfig::default(),
+            TokenBucketConfig {
+                client_capacity: 10,
+                client_refill_rate: 5,
+                default_cost: 1,
+                method_costs: [("heavy".to_string(), 5)].into_iter().collect(),
+                gas_budget_unit: Some(1000),
+                ..Default::default()
+            },
+        );
+        let alice = Some(IpAddr::V4(Ipv4Addr::new(8, 7, 6, 5)));
+        let bob = Some(IpAddr::V4(Ipv4Addr::new(4, 3, 2, 1)));
+
+        assert_eq!(policy.request_cost(&tally(alice, "light", None).request), 1);
+        assert_eq!(policy.request_cost(&tally(alice, "heavy", None).request), 5);
+        assert_eq!(
+            policy.request_cost(&tally(alice, "light", Some(3500)).request),
+            4
+        );
+
+        // alice can spend her whole bucket on cheap requests, but no more
+        for i in 0..10 {
+            let response = policy.handle_tally(tally(alice, "light", None));
+            assert_eq!(response.block_client, None, "Blocked at i = {}", i);
+        }
+        let response = policy.handle_tally(tally(alice, "light", None));
+        assert_eq!(response.block_client, alice);
+
+        // bob exhausts his bucket with two heavy requests
+        for _ in 0..2 {
+            let response = policy.handle_tally(tally(bob, "heavy", None));
+            assert_eq!(response.block_client, None);
+        }
+        let response = policy.handle_tally(tally(bob, "light", None));
+        assert_eq!(response.block_client, bob);
+
+        // after a second alice has refilled enough for one heavy request only
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let response = policy.handle_tally(tally(alice, "heavy", None));
+        assert_eq!(response.block_client, None);
+        let response = policy.handle_tally(tally(alice, "heavy", None));
+        assert_eq!(response.block_client, alice);
+
+        // bob's bucket never holds more than its capacity
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let response = policy.handle_tally(tally(bob, "light", Some(10_000)));
+        assert_eq!(response.block_client, bob);
+    }
+
+    #[sim_test]
+    async fn test_token_bucket_forgets_idle_clients() {
+        let mut policy = TokenBucketPolicy::new(
+            PolicyConfig::default(),
+            TokenBucketConfig {
+                client_capacity: 10,
+                client_refill_rate: 10,
+                max_tracked_clients: 2,
+                ..Default::default()
+            },
+        );
+        for i in 0..3 {
+            let client = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, i)));
+            policy.handle_tally(tally(client, "light", None));
+        }
+        assert_eq!(policy.buckets.len(), 3);
+
+        // all buckets refill within a second, so they are dropped on the next tally
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        policy.handle_tally(tally(
+            Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 3))),
+            "light",
+            None,
+        ));
+        assert_eq!(policy.buckets.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_access_lists() {
+        let lists =
+            AccessLists::parse("# operators\nallow 1.2.3.4\n\n  deny 5.6.7.8:9000\ndeny ::1\n")
+                .unwrap();
+        assert_eq!(
+            lists.allow,
+            HashSet::from([IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4))])
+        );
+        assert_eq!(
+            lists.deny,
+            HashSet::from([
+                IpAddr::V4(Ipv4Addr::new(5, 6, 7, 8)),
+                "::1".parse::<IpAddr>().unwrap(),
+            ])
+        );
+
+        assert!(AccessLists::parse("block 1.2.3.4").is_err());
+        assert!(AccessLists::parse("deny not-an-ip").is_err());
+    }
+
+    #[sim_test]
+    async fn test_access_list_policy_hot_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("access-list");
+        std::fs::write(&path, "deny 5.6.7.8\n").unwrap();
+        let denied = Some(IpAddr::V4(Ipv4Addr::new(5, 6, 7, 8)));
+        let other = Some(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)));
+
+        let mut policy = AccessListPolicy::new(
+            PolicyConfig::default(),
+            AccessListConfig {
+                path: path.clone(),
+                reload_interval_secs: 1,
+            },
+        )
+        .await;
+        let response = policy.handle_tally(tally(denied, "light", None));
+        assert_eq!(response.block_client, denied);
+        let response = policy.handle_tally(tally(other, "light", None));
+        assert_eq!(response.block_client, None);
+
+        // swap the denied client, the change is picked up on the next reload
+        std::fs::write(&path, "deny 1.2.3.4\n").unwrap();
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        let response = policy.handle_tally(tally(denied, "light", None));
+        assert_eq!(response.block_client, None);
+        let response = policy.handle_tally(tally(other, "light", None));
+        assert_eq!(response.block_client, other);
+
+        // a malformed file keeps the previous lists in place
+        std::fs::write(&path, "deny\n").unwrap();
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        let response = policy.handle_tally(tally(other, "light", None));
+        assert_eq!(response.block_client, other);
+    }
+
+    #[sim_test]
+    async fn test_composite_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("access-list");
+        std::fs::write(&path, "allow 1.2.3.4\ndeny 5.6.7.8\n").unwrap();
+        let allowed = Some(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)));
+        let denied = Some(IpAddr::V4(Ipv4Addr::new(5, 6, 7, 8)));
+        let other = Some(IpAddr::V4(Ipv4Addr::new(8, 7, 6, 5)));
+
+        let mut policy = TrafficControlPolicy::from_config(
+            PolicyType::Composite(vec![
+                PolicyType::AccessList(AccessListConfig {
+                    path,
+                    reload_interval_secs: 1,
+                }),
+                PolicyType::TokenBucket(TokenBucketConfig {
+                    client_capacity: 3,
+                    client_refill_rate: 1,
+                    default_cost: 1,
+                    ..Default::default()
+                }),
+            ]),
+            PolicyConfig::default(),
+        )
+        .await;
+
+        // denied clients are blocked right away
+        let response = policy.handle_tally(tally(denied, "light", None));
+        assert_eq!(response.block_client, denied);
+
+        // other clients are blocked once their bucket is empty
+        for _ in 0..3 {
+            let response = policy.handle_tally(tally(other, "light", None));
+            assert_eq!(response.block_client, None);
+        }
+        let response = policy.handle_tally(tally(other, "light", None));
+        assert_eq!(response.block_client, other);
+
+        // allowed clients are never blocked, whatever the other policies say
+        for _ in 0..10 {
+            let response = policy.handle_tally(tally(allowed, "light", None));
+            assert_eq!(response.block_client, None);
+        }
+    }
+
     #[sim_test]
     async fn test_traffic_sketch_mem_estimate() {
         // Test for getting a rough estimate of memory usage for the traffic sketch
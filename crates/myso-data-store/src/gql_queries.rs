@@ -4,7 +4,7 @@
 
 //! GQL Queries
 //! Interface to the rpc for the gql schema defined in `crates\myso-indexer-alt-graphql/schema.graphql`.
-//! Built in 3 modules: epoch_query, txn_query, object_query.
+//! Built in 5 modules: epoch_query, txn_query, events_query, checkpoint_query, object_query.
 //! No GQL type escapes this module. From here we return structures defined in this crate
 //! or bcs encoded data of runtime structures.
 //!
@@ -208,6 +208,195 @@ pub(crate) mod txn_query {
     }
 }
 
+pub(crate) mod events_query {
+    use super::*;
+    use myso_types::{effects::TransactionEvents, event::Event};
+
+    #[derive(cynic::Scalar, Debug, Clone)]
+    #[cynic(graphql_type = "Base64")]
+    pub(crate) struct Base64(pub String);
+
+    #[derive(cynic::QueryVariables)]
+    pub(crate) struct TransactionEventsArgs {
+        pub digest: String,
+        pub after: Option<String>,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    #[cynic(variables = "TransactionEventsArgs")]
+    pub(crate) struct Query {
+        #[arguments(digest: $digest)]
+        transaction: Option<Transaction>,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    #[cynic(variables = "TransactionEventsArgs")]
+    pub(crate) struct Transaction {
+        effects: Option<TransactionEffects>,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    #[cynic(variables = "TransactionEventsArgs")]
+    pub(crate) struct TransactionEffects {
+        #[arguments(first: 50, after: $after)]
+        events: Option<EventConnection>,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    pub(crate) struct EventConnection {
+        nodes: Vec<EventFragment>,
+        page_info: PageInfo,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    #[cynic(graphql_type = "Event")]
+    pub(crate) struct EventFragment {
+        event_bcs: Option<Base64>,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    pub(crate) struct PageInfo {
+        has_next_page: bool,
+        end_cursor: Option<String>,
+    }
+
+    /// Page through the events emitted by the transaction `digest`, in emission order.
+    pub(crate) async fn query(
+        digest: String,
+        data_store: &DataStore,
+    ) -> Result<Option<TransactionEvents>, Error> {
+        let mut events = vec![];
+        let mut after = None;
+
+        loop {
+            let query = Query::build(TransactionEventsArgs {
+                digest: digest.clone(),
+                after: after.take(),
+            });
+            let response = data_store
+                .run_query(&query)
+                .await
+                .context("Failed to run transaction events query")?;
+
+            let Some(transaction) = response.data.and_then(|data| data.transaction) else {
+                return Ok(None);
+            };
+            let connection = transaction
+                .effects
+                .and_then(|effects| effects.events)
+                .ok_or_else(|| {
+                    anyhow!(format!("Events not available for transaction {}", digest))
+                })?;
+
+            for node in connection.nodes {
+                let bytes = CryptoBase64::decode(
+                    &node
+                        .event_bcs
+                        .ok_or_else(|| anyhow!("Missing event bcs in transaction events response"))?
+                        .0,
+                )
+                .context(format!("Event does not decode for digest: {}", digest))?;
+                let event: Event = bcs::from_bytes(&bytes)
+                    .context(format!("Cannot deserialize event for digest {}", digest))?;
+                events.push(event);
+            }
+            if !connection.page_info.has_next_page {
+                return Ok(Some(TransactionEvents { data: events }));
+            }
+
+            after = Some(connection.page_info.end_cursor.ok_or_else(|| {
+                anyhow!(format!(
+                    "Missing cursor paginating events for transaction {}",
+                    digest
+                ))
+            })?);
+        }
+    }
+}
+
+pub(crate) mod checkpoint_query {
+    use super::*;
+
+    #[derive(cynic::QueryVariables)]
+    pub(crate) struct CheckpointTransactionsArgs {
+        pub checkpoint: Option<u64>,
+        pub after: Option<String>,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    #[cynic(variables = "CheckpointTransactionsArgs")]
+    pub(crate) struct Query {
+        #[arguments(sequenceNumber: $checkpoint)]
+        checkpoint: Option<Checkpoint>,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    #[cynic(variables = "CheckpointTransactionsArgs")]
+    pub(crate) struct Checkpoint {
+        #[arguments(first: 50, after: $after)]
+        transactions: Option<TransactionConnection>,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    pub(crate) struct TransactionConnection {
+        nodes: Vec<Transaction>,
+        page_info: PageInfo,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    pub(crate) struct Transaction {
+        digest: String,
+    }
+
+    #[derive(cynic::QueryFragment)]
+    pub(crate) struct PageInfo {
+        has_next_page: bool,
+        end_cursor: Option<String>,
+    }
+
+    /// Page through the transactions in `checkpoint`, returning their digests in execution order.
+    pub(crate) async fn query(
+        checkpoint: u64,
+        data_store: &DataStore,
+    ) -> Result<Option<Vec<String>>, Error> {
+        let mut digests = vec![];
+        let mut after = None;
+
+        loop {
+            let query = Query::build(CheckpointTransactionsArgs {
+                checkpoint: Some(checkpoint),
+                after: after.take(),
+            });
+            let response = data_store
+                .run_query(&query)
+                .await
+                .context("Failed to run checkpoint transactions query")?;
+
+            let Some(checkpoint_frag) = response.data.and_then(|data| data.checkpoint) else {
+                return Ok(None);
+            };
+            let connection = checkpoint_frag.transactions.ok_or_else(|| {
+                anyhow!(format!(
+                    "Transactions not available for checkpoint {}",
+                    checkpoint
+                ))
+            })?;
+
+            digests.extend(connection.nodes.into_iter().map(|txn| txn.digest));
+            if !connection.page_info.has_next_page {
+                return Ok(Some(digests));
+            }
+
+            after = Some(connection.page_info.end_cursor.ok_or_else(|| {
+                anyhow!(format!(
+                    "Missing cursor paginating transactions for checkpoint {}",
+                    checkpoint
+                ))
+            })?);
+        }
+    }
+}
+
 pub(crate) mod object_query {
     use myso_types::object::Object;
 
@@ -349,3 +538,127 @@ pub(crate) mod chain_id_query {
         Ok(chain_id)
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{Value, json};
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate, matchers::method};
+
+    use super::*;
+
+    /// A GraphQL server for a network whose checkpoint 1 contains transactions `a`, `b` and `c`,
+    /// served two at a time, and that has no other checkpoints.
+    pub(crate) async fn mock_checkpoints() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(|request: &Request| {
+                let body: Value = request.body_json().unwrap();
+                let variables = &body["variables"];
+
+                let checkpoint = match (
+                    variables["checkpoint"].as_u64(),
+                    variables["after"].as_str(),
+                ) {
+                    (Some(1), None) => json!({
+                        "transactions": {
+                            "nodes": [{ "digest": "a" }, { "digest": "b" }],
+                            "pageInfo": { "hasNextPage": true, "endCursor": "1" },
+                        }
+                    }),
+                    (Some(1), Some("1")) => json!({
+                        "transactions": {
+                            "nodes": [{ "digest": "c" }],
+                            "pageInfo": { "hasNextPage": false, "endCursor": "2" },
+                        }
+                    }),
+                    _ => Value::Null,
+                };
+
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "data": { "checkpoint": checkpoint }
+                }))
+            })
+            .mount(&server)
+            .await;
+
+        server
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_query() {
+        let server = mock_checkpoints().await;
+        let store = DataStore::new_for_testing(&server.uri()).unwrap();
+
+        let digests = checkpoint_query::query(1, &store).await.unwrap();
+        assert_eq!(
+            digests,
+            Some(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+
+        // Two pages were fetched.
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+        assert!(checkpoint_query::query(2, &store).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_events_query() {
+        use myso_types::event::Event;
+
+        let events = [
+            Event::random_for_testing(),
+            Event::random_for_testing(),
+            Event::random_for_testing(),
+        ];
+        let event_bcs: Vec<_> = events
+            .iter()
+            .map(|event| json!({ "eventBcs": CryptoBase64::encode(bcs::to_bytes(event).unwrap()) }))
+            .collect();
+
+        // Transaction `a` emitted three events, served two at a time.
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(move |request: &Request| {
+                let body: Value = request.body_json().unwrap();
+                let variables = &body["variables"];
+
+                let transaction = match (variables["digest"].as_str(), variables["after"].as_str())
+                {
+                    (Some("a"), None) => json!({
+                        "effects": { "events": {
+                            "nodes": event_bcs[..2],
+                            "pageInfo": { "hasNextPage": true, "endCursor": "1" },
+                        }}
+                    }),
+                    (Some("a"), Some("1")) => json!({
+                        "effects": { "events": {
+                            "nodes": event_bcs[2..],
+                            "pageInfo": { "hasNextPage": false, "endCursor": "2" },
+                        }}
+                    }),
+                    _ => Value::Null,
+                };
+
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "data": { "transaction": transaction }
+                }))
+            })
+            .mount(&server)
+            .await;
+        let store = DataStore::new_for_testing(&server.uri()).unwrap();
+
+        let fetched = events_query::query("a".to_string(), &store)
+            .await
+            .unwrap()
+            .expect("Transaction should be found");
+        assert_eq!(fetched.data, events);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+        assert!(
+            events_query::query("b".to_string(), &store)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
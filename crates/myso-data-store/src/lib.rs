@@ -13,6 +13,7 @@
 //! - [`TransactionStore`] - Retrieve transaction data and effects by digest
 //! - [`EpochStore`] - Retrieve epoch information and protocol configuration
 //! - [`ObjectStore`] - Retrieve objects by their keys with flexible version queries
+//! - [`CheckpointStore`] - Retrieve the transactions executed in a checkpoint
 //!
 //! ## Store Implementations
 //!
@@ -38,8 +39,11 @@ pub use node::Node;
 
 use anyhow::{Error, Result};
 use myso_types::{
-    base_types::ObjectID, effects::TransactionEffects, object::Object,
-    supported_protocol_versions::ProtocolConfig, transaction::TransactionData,
+    base_types::ObjectID,
+    effects::{TransactionEffects, TransactionEvents},
+    object::Object,
+    supported_protocol_versions::ProtocolConfig,
+    transaction::TransactionData,
 };
 use std::io::Write;
 
@@ -67,6 +71,15 @@ pub trait TransactionStore {
         &self,
         tx_digest: &str,
     ) -> Result<Option<TransactionInfo>, Error>;
+
+    /// Given a transaction digest, return the events the transaction emitted when it was
+    /// executed on chain.
+    /// Returns `None` if the transaction is not found or the store does not keep events,
+    /// which is the default. Events are not needed to replay a transaction, only to explain
+    /// how a replay diverged.
+    fn transaction_events(&self, _tx_digest: &str) -> Result<Option<TransactionEvents>, Error> {
+        Ok(None)
+    }
 }
 
 /// Epoch data.
@@ -126,6 +139,14 @@ pub trait ObjectStore {
     fn get_objects(&self, keys: &[ObjectKey]) -> Result<Vec<Option<(Object, u64)>>, Error>;
 }
 
+/// A `CheckpointStore` lists the transactions executed in a checkpoint, so that a range of
+/// checkpoints can be replayed without knowing the transaction digests up front.
+pub trait CheckpointStore {
+    /// Return the digests of the transactions in `checkpoint`, in execution order.
+    /// Returns `None` if the checkpoint is not found.
+    fn checkpoint_transactions(&self, checkpoint: u64) -> Result<Option<Vec<String>>, Error>;
+}
+
 // ============================================================================
 // Set up trait
 // ============================================================================
@@ -189,6 +210,16 @@ pub trait ObjectStoreWriter: ObjectStore {
     ) -> Result<(), Error>;
 }
 
+/// Write-back trait for checkpoint contents.
+pub trait CheckpointStoreWriter: CheckpointStore {
+    /// Store the digests of the transactions in `checkpoint`, in execution order.
+    fn write_checkpoint_transactions(
+        &self,
+        checkpoint: u64,
+        digests: &[String],
+    ) -> Result<(), Error>;
+}
+
 // ============================================================================
 // Store summary reporting
 // ============================================================================
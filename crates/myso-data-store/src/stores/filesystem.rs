@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! File system implementation of the data store interfaces: `TransactionStore`, `EpochStore`,
-//! and `ObjectStore`.
+//! `ObjectStore`, and `CheckpointStore`.
 //! Data is persisted on disk under a simple, human-inspectable directory layout.
 //!
 //! # Directory Structure
@@ -16,6 +16,8 @@
 //!       <tx_digest>              (BCS: TransactionFileData)
 //!     epoch/
 //!       <epoch_id>               (BCS: EpochFileData)
+//!     checkpoint/
+//!       <sequence_number>        (text: one transaction digest per line)
 //!     objects/
 //!       <object_id>/
 //!         <version>              (BCS: myso_types::object::Object)
@@ -34,6 +36,8 @@
 //!   includes the original `TransactionData`, its `TransactionEffects`, and the execution
 //!   `checkpoint`.
 //! - Epoch files in `epoch/<epoch_id>` store `EpochFileData` as BCS, capturing epoch metadata.
+//! - Checkpoint files in `checkpoint/<sequence_number>` list the digests of the transactions in
+//!   the checkpoint, in execution order, one per line.
 //! - Object files in `objects/<object_id>/<version>` store the `Object` at the corresponding
 //!   version as BCS.
 //!
@@ -57,9 +61,9 @@
 //! it learns the concrete versions.
 
 use crate::{
-    EpochData, EpochStore, EpochStoreWriter, ObjectKey, ObjectStore, ObjectStoreWriter, SetupStore,
-    StoreSummary, TransactionInfo, TransactionStore, TransactionStoreWriter, VersionQuery,
-    node::Node,
+    CheckpointStore, CheckpointStoreWriter, EpochData, EpochStore, EpochStoreWriter, ObjectKey,
+    ObjectStore, ObjectStoreWriter, SetupStore, StoreSummary, TransactionInfo, TransactionStore,
+    TransactionStoreWriter, VersionQuery, node::Node,
 };
 use anyhow::{Context, Error, Result, anyhow};
 use myso_types::{
@@ -86,6 +90,7 @@ pub const NODE_MAPPING_FILE: &str = "node_mapping.csv";
 pub const OBJECTS_DIR: &str = "objects";
 pub const TRANSACTION_DIR: &str = "transaction";
 pub const EPOCH_DIR: &str = "epoch";
+pub const CHECKPOINT_DIR: &str = "checkpoint";
 pub const ROOT_VERSIONS_FILE: &str = "root_versions";
 pub const CHECKPOINT_VERSIONS_FILE: &str = "checkpoint_versions";
 
@@ -259,6 +264,10 @@ impl FileSystemStore {
         Ok(self.node_dir()?.join(EPOCH_DIR))
     }
 
+    fn checkpoint_dir(&self) -> Result<PathBuf, Error> {
+        Ok(self.node_dir()?.join(CHECKPOINT_DIR))
+    }
+
     fn objects_dir(&self) -> Result<PathBuf, Error> {
         Ok(self.node_dir()?.join(OBJECTS_DIR))
     }
@@ -610,6 +619,43 @@ impl TransactionStoreWriter for FileSystemStore {
     }
 }
 
+impl CheckpointStore for FileSystemStore {
+    fn checkpoint_transactions(&self, checkpoint: u64) -> Result<Option<Vec<String>>, Error> {
+        let file_path = self.checkpoint_dir()?.join(checkpoint.to_string());
+        if !file_path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&file_path).with_context(|| {
+            format!("Failed to load transactions for checkpoint: {}", checkpoint)
+        })?;
+        Ok(Some(
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect(),
+        ))
+    }
+}
+
+impl CheckpointStoreWriter for FileSystemStore {
+    fn write_checkpoint_transactions(
+        &self,
+        checkpoint: u64,
+        digests: &[String],
+    ) -> Result<(), Error> {
+        let dir = self.checkpoint_dir()?;
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        let file_path = dir.join(checkpoint.to_string());
+        let content: String = digests.iter().map(|d| format!("{d}\n")).collect();
+        fs::write(&file_path, content)
+            .with_context(|| format!("Failed to write file: {}", file_path.display()))?;
+        Ok(())
+    }
+}
+
 impl EpochStoreWriter for FileSystemStore {
     fn write_epoch_info(&self, epoch: u64, epoch_data: EpochData) -> Result<(), Error> {
         let file_path = self.epoch_dir()?.join(epoch.to_string());
@@ -814,3 +860,44 @@ impl FileSystemStore {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A store rooted at `base_path`, with mainnet mapped to a chain directory.
+    fn store(base_path: &Path) -> FileSystemStore {
+        let store = FileSystemStore {
+            node: Node::Mainnet,
+            base_path: base_path.to_owned(),
+            metrics: FsStoreMetrics::default(),
+            root_versions_map: RwLock::new(BTreeMap::new()),
+            checkpoint_versions_map: RwLock::new(BTreeMap::new()),
+        };
+        store.setup(Some("chain".to_string())).unwrap();
+        store
+    }
+
+    #[test]
+    fn test_checkpoint_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(dir.path());
+        assert!(store.checkpoint_transactions(1).unwrap().is_none());
+
+        let digests = vec!["a".to_string(), "b".to_string()];
+        store.write_checkpoint_transactions(1, &digests).unwrap();
+        store.write_checkpoint_transactions(2, &[]).unwrap();
+
+        assert_eq!(store.checkpoint_transactions(1).unwrap(), Some(digests));
+        assert_eq!(store.checkpoint_transactions(2).unwrap(), Some(vec![]));
+        assert!(store.checkpoint_transactions(3).unwrap().is_none());
+
+        // The file is human-editable, so surrounding whitespace and blank lines are ignored.
+        let path = dir.path().join("chain").join(CHECKPOINT_DIR).join("3");
+        fs::write(&path, " c \n\nd\n").unwrap();
+        assert_eq!(
+            store.checkpoint_transactions(3).unwrap(),
+            Some(vec!["c".to_string(), "d".to_string()])
+        );
+    }
+}
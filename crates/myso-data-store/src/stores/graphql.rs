@@ -2,19 +2,19 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-//! An implementation of the data store interfaces: `TransactionStore`, `EpochStore` and `ObjectStore`
-//! backed by the RPC GQL endpoint. Schema in `crates/myso-indexer-alt-graphql/schema.graphql`.
+//! An implementation of the data store interfaces: `TransactionStore`, `EpochStore`, `ObjectStore`
+//! and `CheckpointStore` backed by the RPC GQL endpoint. Schema in `crates/myso-indexer-alt-graphql/schema.graphql`.
 //! The RPC calls are implemented in `gql_queries.rs`.
 
 use crate::{
-    EpochData, EpochStore, ObjectKey, ObjectStore, SetupStore, StoreSummary, TransactionInfo,
-    TransactionStore, VersionQuery, gql_queries, node::Node,
+    CheckpointStore, EpochData, EpochStore, ObjectKey, ObjectStore, SetupStore, StoreSummary,
+    TransactionInfo, TransactionStore, VersionQuery, gql_queries, node::Node,
 };
 use anyhow::{Context, Error, Result};
 use cynic::{GraphQlResponse, Operation};
 use myso_types::{
     committee::ProtocolVersion,
-    effects::TransactionEffects,
+    effects::{TransactionEffects, TransactionEvents},
     object::Object,
     supported_protocol_versions::{Chain, ProtocolConfig},
     transaction::TransactionData,
@@ -123,6 +123,10 @@ impl TransactionStore for DataStore {
             }
         }
     }
+
+    fn transaction_events(&self, digest: &str) -> Result<Option<TransactionEvents>, Error> {
+        block_on!(self.events(digest))
+    }
 }
 
 impl EpochStore for DataStore {
@@ -236,6 +240,12 @@ impl ObjectStore for DataStore {
     }
 }
 
+impl CheckpointStore for DataStore {
+    fn checkpoint_transactions(&self, checkpoint: u64) -> Result<Option<Vec<String>>, Error> {
+        block_on!(self.checkpoint(checkpoint))
+    }
+}
+
 impl SetupStore for DataStore {
     fn setup(&self, _chain_id: Option<String>) -> Result<Option<String>, Error> {
         // Return the chain identifier
@@ -266,6 +276,19 @@ impl DataStore {
         })
     }
 
+    /// A store that sends its queries to `url`, for tests that run against a mock server.
+    #[cfg(test)]
+    pub(crate) fn new_for_testing(url: &str) -> Result<Self, Error> {
+        Ok(Self {
+            client: reqwest::Client::new(),
+            rpc: reqwest::Url::parse(url)?,
+            node: Node::Custom(url.to_string()),
+            epoch_map: RwLock::new(BTreeMap::new()),
+            version: "test".to_string(),
+            metrics: DataStoreMetrics::default(),
+        })
+    }
+
     pub fn node(&self) -> &Node {
         &self.node
     }
@@ -324,6 +347,21 @@ impl DataStore {
         data
     }
 
+    async fn events(&self, digest: &str) -> Result<Option<TransactionEvents>, Error> {
+        let _span = debug_span!("gql_events_query", digest = %digest).entered();
+        debug!(op = "events_query", phase = "start", "events query");
+        let t0 = Instant::now();
+        let data = gql_queries::events_query::query(digest.to_string(), self).await;
+        let elapsed = t0.elapsed().as_millis();
+        debug!(
+            op = "events_query",
+            phase = "end",
+            elapsed_ms = elapsed,
+            "events query"
+        );
+        data
+    }
+
     async fn epoch(&self, epoch_id: u64) -> Result<Option<EpochData>, Error> {
         let _span = debug_span!("gql_epoch_query", epoch = epoch_id).entered();
         debug!(op = "epoch_query", phase = "start", "epoch query");
@@ -339,6 +377,21 @@ impl DataStore {
         data
     }
 
+    async fn checkpoint(&self, checkpoint: u64) -> Result<Option<Vec<String>>, Error> {
+        let _span = debug_span!("gql_checkpoint_query", checkpoint).entered();
+        debug!(op = "checkpoint_query", phase = "start", "checkpoint query");
+        let t0 = Instant::now();
+        let data = gql_queries::checkpoint_query::query(checkpoint, self).await;
+        let elapsed = t0.elapsed().as_millis();
+        debug!(
+            op = "checkpoint_query",
+            phase = "end",
+            elapsed_ms = elapsed,
+            "checkpoint query"
+        );
+        data
+    }
+
     async fn objects(&self, keys: &[ObjectKey]) -> Result<Vec<Option<(Object, u64)>>, Error> {
         let _span = debug_span!("gql_objects_query", num_keys = keys.len()).entered();
         debug!(op = "objects_query", phase = "start", "objects query");
@@ -435,3 +488,22 @@ impl StoreSummary for DataStore {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::gql_queries::tests::mock_checkpoints;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_checkpoint_transactions() {
+        let server = mock_checkpoints().await;
+        let store = DataStore::new_for_testing(&server.uri()).unwrap();
+
+        assert_eq!(
+            store.checkpoint_transactions(1).unwrap(),
+            Some(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+        assert!(store.checkpoint_transactions(2).unwrap().is_none());
+    }
+}
@@ -2,7 +2,8 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-//! In-memory implementation of the data store interfaces: `TransactionStore`, `EpochStore`, and `ObjectStore`.
+//! In-memory implementation of the data store interfaces: `TransactionStore`, `EpochStore`,
+//! `ObjectStore`, and `CheckpointStore`.
 //! The `InMemoryStore` provides fast in-memory lookups.
 //!
 //! This store is purely cache-based - it only returns data that has been explicitly stored in memory.
@@ -25,9 +26,9 @@
 //! ```
 
 use crate::{
-    EpochData, EpochStore, EpochStoreWriter, ObjectKey, ObjectStore, ObjectStoreWriter, SetupStore,
-    StoreSummary, TransactionInfo, TransactionStore, TransactionStoreWriter, VersionQuery,
-    node::Node,
+    CheckpointStore, CheckpointStoreWriter, EpochData, EpochStore, EpochStoreWriter, ObjectKey,
+    ObjectStore, ObjectStoreWriter, SetupStore, StoreSummary, TransactionInfo, TransactionStore,
+    TransactionStoreWriter, VersionQuery, node::Node,
 };
 use anyhow::{Error, Result};
 use myso_types::{
@@ -59,6 +60,8 @@ struct InMemoryStoreInner {
     /// Cache mapping (ObjectID, checkpoint) -> actual_version  
     /// Used for VersionQuery::AtCheckpoint lookups
     checkpoint_cache: BTreeMap<(ObjectID, u64), u64>,
+    /// Cache mapping checkpoint -> digests of the transactions in it
+    checkpoint_transactions_cache: BTreeMap<u64, Vec<String>>,
     /// Metrics: hit/miss counters for API calls
     metrics: MemStoreMetrics,
 }
@@ -104,6 +107,7 @@ impl InMemoryStore {
             object_cache: BTreeMap::new(),
             root_version_cache: BTreeMap::new(),
             checkpoint_cache: BTreeMap::new(),
+            checkpoint_transactions_cache: BTreeMap::new(),
             metrics: MemStoreMetrics::default(),
         }))
     }
@@ -126,6 +130,7 @@ impl InMemoryStore {
         inner.object_cache.clear();
         inner.root_version_cache.clear();
         inner.checkpoint_cache.clear();
+        inner.checkpoint_transactions_cache.clear();
     }
 
     /// Get cache statistics
@@ -329,6 +334,33 @@ impl TransactionStoreWriter for InMemoryStore {
     }
 }
 
+impl CheckpointStore for InMemoryStore {
+    fn checkpoint_transactions(&self, checkpoint: u64) -> Result<Option<Vec<String>>, Error> {
+        Ok(self
+            .0
+            .read()
+            .unwrap()
+            .checkpoint_transactions_cache
+            .get(&checkpoint)
+            .cloned())
+    }
+}
+
+impl CheckpointStoreWriter for InMemoryStore {
+    fn write_checkpoint_transactions(
+        &self,
+        checkpoint: u64,
+        digests: &[String],
+    ) -> Result<(), Error> {
+        self.0
+            .write()
+            .unwrap()
+            .checkpoint_transactions_cache
+            .insert(checkpoint, digests.to_vec());
+        Ok(())
+    }
+}
+
 impl EpochStoreWriter for InMemoryStore {
     fn write_epoch_info(&self, epoch: u64, epoch_data: EpochData) -> Result<(), Error> {
         self.0
@@ -473,3 +505,22 @@ impl StoreSummary for InMemoryStore {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_transactions() {
+        let store = InMemoryStore::new(Node::Mainnet);
+        assert!(store.checkpoint_transactions(1).unwrap().is_none());
+
+        let digests = vec!["a".to_string(), "b".to_string()];
+        store.write_checkpoint_transactions(1, &digests).unwrap();
+        assert_eq!(store.checkpoint_transactions(1).unwrap(), Some(digests));
+        assert!(store.checkpoint_transactions(2).unwrap().is_none());
+
+        store.clear_all_caches();
+        assert!(store.checkpoint_transactions(1).unwrap().is_none());
+    }
+}
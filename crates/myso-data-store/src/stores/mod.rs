@@ -14,8 +14,8 @@ mod in_memory_lru;
 mod read_through;
 
 pub use filesystem::{
-    CHECKPOINT_VERSIONS_FILE, DATA_STORE_DIR, EPOCH_DIR, FileSystemStore, NODE_MAPPING_FILE,
-    OBJECTS_DIR, ROOT_VERSIONS_FILE, TRANSACTION_DIR,
+    CHECKPOINT_DIR, CHECKPOINT_VERSIONS_FILE, DATA_STORE_DIR, EPOCH_DIR, FileSystemStore,
+    NODE_MAPPING_FILE, OBJECTS_DIR, ROOT_VERSIONS_FILE, TRANSACTION_DIR,
 };
 pub use graphql::DataStore;
 pub use in_memory::InMemoryStore;
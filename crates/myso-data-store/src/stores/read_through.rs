@@ -3,11 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    EpochData, EpochStore, ObjectKey, ObjectStore, ReadDataStore, ReadWriteDataStore, SetupStore,
-    StoreSummary, TransactionInfo, TransactionStore,
+    CheckpointStore, CheckpointStoreWriter, EpochData, EpochStore, ObjectKey, ObjectStore,
+    ReadDataStore, ReadWriteDataStore, SetupStore, StoreSummary, TransactionInfo, TransactionStore,
 };
 use anyhow::{Error, Result};
-use myso_types::{object::Object, supported_protocol_versions::ProtocolConfig};
+use myso_types::{
+    effects::TransactionEvents, object::Object, supported_protocol_versions::ProtocolConfig,
+};
 
 /// A read-through store that composes a primary (cache) and a secondary (source) store.
 /// It tries the primary first; on miss it reads from the secondary and writes back to the primary.
@@ -53,6 +55,13 @@ where
                 }),
         }
     }
+
+    fn transaction_events(&self, tx_digest: &str) -> Result<Option<TransactionEvents>, Error> {
+        match self.primary.transaction_events(tx_digest)? {
+            Some(events) => Ok(Some(events)),
+            None => self.secondary.transaction_events(tx_digest),
+        }
+    }
 }
 
 impl<P, S> EpochStore for ReadThroughStore<P, S>
@@ -124,6 +133,26 @@ where
     }
 }
 
+impl<P, S> CheckpointStore for ReadThroughStore<P, S>
+where
+    P: ReadWriteDataStore + CheckpointStoreWriter,
+    S: ReadDataStore + CheckpointStore,
+{
+    fn checkpoint_transactions(&self, checkpoint: u64) -> Result<Option<Vec<String>>, Error> {
+        match self.primary.checkpoint_transactions(checkpoint)? {
+            Some(digests) => Ok(Some(digests)),
+            None => match self.secondary.checkpoint_transactions(checkpoint)? {
+                Some(digests) => {
+                    self.primary
+                        .write_checkpoint_transactions(checkpoint, &digests)?;
+                    Ok(Some(digests))
+                }
+                None => Ok(None),
+            },
+        }
+    }
+}
+
 impl<P, S> StoreSummary for ReadThroughStore<P, S>
 where
     P: ReadWriteDataStore + StoreSummary,
@@ -149,3 +178,56 @@ where
         Ok(chain_id.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{node::Node, stores::InMemoryStore};
+
+    use super::*;
+
+    fn digests(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_checkpoint_transactions_primary_hit() {
+        let primary = InMemoryStore::new(Node::Mainnet);
+        let secondary = InMemoryStore::new(Node::Mainnet);
+        primary
+            .write_checkpoint_transactions(1, &digests(&["a"]))
+            .unwrap();
+        secondary
+            .write_checkpoint_transactions(1, &digests(&["b"]))
+            .unwrap();
+
+        let store = ReadThroughStore::new(primary, secondary);
+        assert_eq!(
+            store.checkpoint_transactions(1).unwrap(),
+            Some(digests(&["a"]))
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_transactions_fallback() {
+        let secondary = InMemoryStore::new(Node::Mainnet);
+        secondary
+            .write_checkpoint_transactions(1, &digests(&["a", "b"]))
+            .unwrap();
+
+        let store = ReadThroughStore::new(InMemoryStore::new(Node::Mainnet), secondary);
+        assert_eq!(
+            store.checkpoint_transactions(1).unwrap(),
+            Some(digests(&["a", "b"]))
+        );
+
+        // The result was written back to the primary.
+        assert_eq!(
+            store.primary.checkpoint_transactions(1).unwrap(),
+            Some(digests(&["a", "b"]))
+        );
+
+        // Misses in both stores are not cached.
+        assert!(store.checkpoint_transactions(2).unwrap().is_none());
+        assert!(store.primary.checkpoint_transactions(2).unwrap().is_none());
+    }
+}
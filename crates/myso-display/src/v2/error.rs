@@ -5,6 +5,7 @@
 use std::collections::BTreeSet;
 use std::fmt;
 use std::mem;
+use std::ops::Range;
 use std::sync::Arc;
 
 use move_core_types::annotated_visitor as AV;
@@ -64,6 +65,9 @@ pub enum FormatError {
         err: String,
     },
 
+    #[error("Invalid operands for operator at byte offset {offset}: {reason}")]
+    OperandInvalid { offset: usize, reason: &'static str },
+
     #[error("Odd number of characters in hex {0}")]
     OddHexLiteral(OwnedLexeme),
 
@@ -151,6 +155,38 @@ pub(crate) enum Match<T> {
 }
 
 impl FormatError {
+    /// The range of bytes in the format string that this error relates to, if it can be
+    /// attributed to a particular location. Errors that are only associated with a position, and
+    /// not a whole token, span the single byte at that position.
+    pub fn span(&self) -> Option<Range<usize>> {
+        use FormatError as E;
+
+        match self {
+            E::InvalidHexCharacter(lexeme)
+            | E::InvalidIdentifier(lexeme)
+            | E::OddHexLiteral(lexeme)
+            | E::UnexpectedRemaining(lexeme)
+            | E::UnexpectedToken { actual: lexeme, .. } => Some(lexeme.span()),
+
+            E::InvalidNumber { offset, .. }
+            | E::OperandInvalid { offset, .. }
+            | E::TransformInvalid_ { offset, .. }
+            | E::VectorArity { offset, .. }
+            | E::VectorTypeMismatch { offset, .. } => Some(*offset..*offset + 1),
+
+            E::Bcs(_)
+            | E::Store(_)
+            | E::TooBig
+            | E::TooDeep
+            | E::TooManyLoads
+            | E::TooMuchOutput
+            | E::TransformInvalid(_)
+            | E::UnexpectedEos { .. }
+            | E::VectorNoType
+            | E::Visitor(_) => None,
+        }
+    }
+
     /// Indicate that the error occurred while processing an expression at `offset`.
     pub(crate) fn for_expr_at_offset(self, offset: usize) -> Self {
         match self {
@@ -75,16 +75,16 @@ impl<S: V::Store> Interpreter<S> {
         .collect()
     }
 
-    /// Evaluate each `chain` in turn until one succeeds (produces a non-`None` value).
+    /// Evaluate each `term` in turn until one succeeds (produces a non-`None` value).
     ///
-    /// Returns the result from the first chain that produces a value, or `Ok(None)` if none do.
+    /// Returns the result from the first term that produces a value, or `Ok(None)` if none do.
     /// Propagates any errors encountered during evaluation.
     async fn eval_alts<'s>(
         &'s self,
-        alts: &'s [P::Chain<'s>],
+        alts: &'s [P::Term<'s>],
     ) -> Result<Option<V::Value<'s>>, FormatError> {
-        for chain in alts {
-            if let Some(v) = self.eval_chain(chain).await? {
+        for term in alts {
+            if let Some(v) = self.eval_term(term).await? {
                 return Ok(Some(v));
             }
         }
@@ -92,6 +92,59 @@ impl<S: V::Store> Interpreter<S> {
         Ok(None)
     }
 
+    /// Evaluate a term, which is either a chain, or an operator applied to other terms.
+    ///
+    /// The operands of a binary operator are evaluated concurrently, and the operator is applied
+    /// if both produce a value. A conditional evaluates its condition first, and then only the
+    /// branch that it selects. If any operand evaluates to `None`, so does the term.
+    async fn eval_term<'s>(
+        &'s self,
+        term: &'s P::Term<'s>,
+    ) -> Result<Option<V::Value<'s>>, FormatError> {
+        match term {
+            P::Term::Chain(chain) => self.eval_chain(chain).await,
+
+            P::Term::BinOp(binop) => {
+                let P::BinOp {
+                    offset,
+                    op,
+                    lhs,
+                    rhs,
+                } = binop.as_ref();
+
+                let (lhs, rhs) =
+                    join!(Box::pin(self.eval_term(lhs)), Box::pin(self.eval_term(rhs)));
+                let (Some(lhs), Some(rhs)) = (lhs?, rhs?) else {
+                    return Ok(None);
+                };
+
+                lhs.apply(*op, *offset, rhs)
+            }
+
+            P::Term::Cond(cond) => {
+                let P::Cond {
+                    offset,
+                    cond,
+                    then,
+                    else_,
+                } = cond.as_ref();
+
+                let Some(value) = Box::pin(self.eval_term(cond)).await? else {
+                    return Ok(None);
+                };
+
+                let Some(cond) = value.as_bool() else {
+                    return Err(FormatError::OperandInvalid {
+                        offset: *offset,
+                        reason: "expected boolean condition",
+                    });
+                };
+
+                Box::pin(self.eval_term(if cond { then } else { else_ })).await
+            }
+        }
+    }
+
     /// Evaluate a chain of field accesses against a root expression.
     ///
     /// If the chain does not have a root expression, the object being displayed is used as the
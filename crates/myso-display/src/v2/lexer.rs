@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::fmt;
+use std::ops::Range;
 
 /// Lexer for Display V2 format strings. Format strings are a mix of text and expressions.
 /// Expressions are enclosed in braces and may contain multiple alternates, separated by pipes, and
@@ -51,6 +52,10 @@ pub(crate) enum Token {
     Comma,
     /// '.'
     Dot,
+    /// '=='
+    EEqual,
+    /// '>='
+    GEqual,
     /// An identifier
     Ident,
     /// '<'
@@ -61,17 +66,29 @@ pub(crate) enum Token {
     LBracket,
     /// '{{'
     LLBrace,
+    /// '<='
+    LEqual,
     /// '('
     LParen,
+    /// '-'
+    Minus,
+    /// '!='
+    NEqual,
     /// A decimal number, optionally separated by underscores.
     NumDec,
     /// A hexadecimal number, prefixed with '0x' (not included in the span), optionally separated
     /// by underscores.
     NumHex,
+    /// '%'
+    Percent,
     /// '|'
     Pipe,
+    /// '+'
+    Plus,
     /// '#'
     Pound,
+    /// '?'
+    Question,
     /// '>'
     RAngle,
     /// '}'
@@ -82,6 +99,10 @@ pub(crate) enum Token {
     RParen,
     /// '}}'
     RRBrace,
+    /// '/'
+    Slash,
+    /// '*'
+    Star,
     /// Strings are surrounded by single quotes. Quotes and backslashes inside strings are escaped
     /// with backslashes.
     String,
@@ -151,8 +172,14 @@ impl<'s> Lexer<'s> {
         Some(match bytes.first()? {
             b'-' if bytes.get(1) == Some(&b'>') => self.take(ws, T::Arrow, 2),
 
+            b'-' => self.take(ws, T::Minus, 1),
+
             b'=' if bytes.get(1) == Some(&b'>') => self.take(ws, T::AArrow, 2),
 
+            b'=' if bytes.get(1) == Some(&b'=') => self.take(ws, T::EEqual, 2),
+
+            b'!' if bytes.get(1) == Some(&b'=') => self.take(ws, T::NEqual, 2),
+
             b'@' => self.take(ws, T::At, 1),
 
             b':' if bytes.get(1) == Some(&b':') => self.take(ws, T::CColon, 2),
@@ -176,6 +203,8 @@ impl<'s> Lexer<'s> {
                 self.take_until(ws, T::Ident, |c| !is_valid_identifier_byte(c))
             }
 
+            b'<' if bytes.get(1) == Some(&b'=') => self.take(ws, T::LEqual, 2),
+
             b'<' => self.take(ws, T::LAngle, 1),
 
             b'{' => {
@@ -187,10 +216,18 @@ impl<'s> Lexer<'s> {
 
             b'(' => self.take(ws, T::LParen, 1),
 
+            b'%' => self.take(ws, T::Percent, 1),
+
             b'|' => self.take(ws, T::Pipe, 1),
 
+            b'+' => self.take(ws, T::Plus, 1),
+
             b'#' => self.take(ws, T::Pound, 1),
 
+            b'?' => self.take(ws, T::Question, 1),
+
+            b'>' if bytes.get(1) == Some(&b'=') => self.take(ws, T::GEqual, 2),
+
             b'>' => self.take(ws, T::RAngle, 1),
 
             b'}' => {
@@ -202,6 +239,10 @@ impl<'s> Lexer<'s> {
 
             b')' => self.take(ws, T::RParen, 1),
 
+            b'/' => self.take(ws, T::Slash, 1),
+
+            b'*' => self.take(ws, T::Star, 1),
+
             b'\'' => {
                 // Set the escaped indicator to true initially so we don't interpret the starting
                 // quote as an ending quote.
@@ -284,6 +325,14 @@ impl Lexeme<'_> {
     }
 }
 
+impl OwnedLexeme {
+    /// The range of bytes in the source string that this lexeme spans (excluding any leading
+    /// whitespace).
+    pub(crate) fn span(&self) -> Range<usize> {
+        self.2..self.2 + self.3.len()
+    }
+}
+
 impl<'s> Iterator for Lexer<'s> {
     type Item = Lexeme<'s>;
 
@@ -313,21 +362,31 @@ impl fmt::Display for OwnedLexeme {
             L(_, T::CColon, _, _) => write!(f, "'::'"),
             L(_, T::Comma, _, _) => write!(f, "','"),
             L(_, T::Dot, _, _) => write!(f, "'.'"),
+            L(_, T::EEqual, _, _) => write!(f, "'=='"),
+            L(_, T::GEqual, _, _) => write!(f, "'>='"),
             L(_, T::Ident, _, s) => write!(f, "identifier {s:?}"),
             L(_, T::LAngle, _, _) => write!(f, "'<'"),
             L(_, T::LBrace, _, _) => write!(f, "'{{'"),
             L(_, T::LBracket, _, _) => write!(f, "'['"),
             L(_, T::LLBrace, _, _) => write!(f, "'{{{{'"),
+            L(_, T::LEqual, _, _) => write!(f, "'<='"),
             L(_, T::LParen, _, _) => write!(f, "'('"),
+            L(_, T::Minus, _, _) => write!(f, "'-'"),
+            L(_, T::NEqual, _, _) => write!(f, "'!='"),
             L(_, T::NumDec, _, s) => write!(f, "decimal number {s:?}"),
             L(_, T::NumHex, _, s) => write!(f, "hexadecimal number {s:?}"),
+            L(_, T::Percent, _, _) => write!(f, "'%'"),
             L(_, T::Pipe, _, _) => write!(f, "'|'"),
+            L(_, T::Plus, _, _) => write!(f, "'+'"),
             L(_, T::Pound, _, _) => write!(f, "'#'"),
+            L(_, T::Question, _, _) => write!(f, "'?'"),
             L(_, T::RAngle, _, _) => write!(f, "'>'"),
             L(_, T::RBrace, _, _) => write!(f, "'}}'"),
             L(_, T::RBracket, _, _) => write!(f, "']'"),
             L(_, T::RParen, _, _) => write!(f, "')'"),
             L(_, T::RRBrace, _, _) => write!(f, "'}}}}'"),
+            L(_, T::Slash, _, _) => write!(f, "'/'"),
+            L(_, T::Star, _, _) => write!(f, "'*'"),
             L(_, T::String, _, s) => write!(f, "string {s:?}"),
             L(_, T::Text, _, s) => write!(f, "text {s:?}"),
             L(_, T::Unexpected, _, s) => {
@@ -362,21 +421,31 @@ impl fmt::Display for Token {
             T::CColon => write!(f, "'::'"),
             T::Comma => write!(f, "','"),
             T::Dot => write!(f, "'.'"),
+            T::EEqual => write!(f, "'=='"),
+            T::GEqual => write!(f, "'>='"),
             T::Ident => write!(f, "an identifier"),
             T::LAngle => write!(f, "'<'"),
             T::LBrace => write!(f, "'{{'"),
             T::LBracket => write!(f, "'['"),
             T::LLBrace => write!(f, "'{{{{'"),
+            T::LEqual => write!(f, "'<='"),
             T::LParen => write!(f, "'('"),
+            T::Minus => write!(f, "'-'"),
+            T::NEqual => write!(f, "'!='"),
             T::NumDec => write!(f, "a decimal number"),
             T::NumHex => write!(f, "a hexadecimal number"),
+            T::Percent => write!(f, "'%'"),
             T::Pipe => write!(f, "'|'"),
+            T::Plus => write!(f, "'+'"),
             T::Pound => write!(f, "'#'"),
+            T::Question => write!(f, "'?'"),
             T::RAngle => write!(f, "'>'"),
             T::RBrace => write!(f, "'}}'"),
             T::RBracket => write!(f, "']'"),
             T::RParen => write!(f, "')'"),
             T::RRBrace => write!(f, "'}}}}'"),
+            T::Slash => write!(f, "'/'"),
+            T::Star => write!(f, "'*'"),
             T::String => write!(f, "a string"),
             T::Text => write!(f, "text"),
             T::Unexpected => write!(f, "unexpected input"),
@@ -562,17 +631,69 @@ mod tests {
     /// Unexpected characters are tokenized so that the parser can produce an error.
     #[test]
     fn test_unexpected_characters() {
-        assert_snapshot!(text(r#"anything goes {? % ! 🔥}"#), @r###"
+        assert_snapshot!(text(r#"anything goes {$ ~ ! 🔥}"#), @r###"
         L(false, Text, 0, "anything goes ")
         L(false, LBrace, 14, "{")
-        L(false, Unexpected, 15, "?")
-        L(true, Unexpected, 17, "%")
+        L(false, Unexpected, 15, "$")
+        L(true, Unexpected, 17, "~")
         L(true, Unexpected, 19, "!")
         L(true, Unexpected, 21, "\xF0\x9F\x94\xA5")
         L(false, RBrace, 25, "}")
         "###);
     }
 
+    /// Arithmetic operators are single characters, and '-' is only an operator when it is not
+    /// part of an arrow.
+    #[test]
+    fn test_arithmetic_operators() {
+        assert_snapshot!(text(r#"{x + 1u8 - y * z / w % v->[u]}"#), @r###"
+        L(false, LBrace, 0, "{")
+        L(false, Ident, 1, "x")
+        L(true, Plus, 3, "+")
+        L(true, NumDec, 5, "1")
+        L(false, Ident, 6, "u8")
+        L(true, Minus, 9, "-")
+        L(true, Ident, 11, "y")
+        L(true, Star, 13, "*")
+        L(true, Ident, 15, "z")
+        L(true, Slash, 17, "/")
+        L(true, Ident, 19, "w")
+        L(true, Percent, 21, "%")
+        L(true, Ident, 23, "v")
+        L(false, Arrow, 24, "->")
+        L(false, LBracket, 26, "[")
+        L(false, Ident, 27, "u")
+        L(false, RBracket, 28, "]")
+        L(false, RBrace, 29, "}")
+        "###);
+    }
+
+    /// Comparison operators are tokenized greedily, so '<=' and '>=' are single tokens.
+    #[test]
+    fn test_comparison_and_conditional_operators() {
+        assert_snapshot!(text(r#"{a == b != c < d <= e > f >= g ? h : i}"#), @r###"
+        L(false, LBrace, 0, "{")
+        L(false, Ident, 1, "a")
+        L(true, EEqual, 3, "==")
+        L(true, Ident, 6, "b")
+        L(true, NEqual, 8, "!=")
+        L(true, Ident, 11, "c")
+        L(true, LAngle, 13, "<")
+        L(true, Ident, 15, "d")
+        L(true, LEqual, 17, "<=")
+        L(true, Ident, 20, "e")
+        L(true, RAngle, 22, ">")
+        L(true, Ident, 24, "f")
+        L(true, GEqual, 26, ">=")
+        L(true, Ident, 29, "g")
+        L(true, Question, 31, "?")
+        L(true, Ident, 33, "h")
+        L(true, Colon, 35, ":")
+        L(true, Ident, 37, "i")
+        L(false, RBrace, 38, "}")
+        "###);
+    }
+
     // Escaped curlies shouldn't be tokenized greedily. '{{{' in text mode should be tokenized as
     // '{{' and '{', while '}}}' in expr mode should be tokenized as '}' and '}}'. This test
     // exercises these and similar cases.
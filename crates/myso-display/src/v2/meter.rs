@@ -19,11 +19,28 @@ pub struct Limits {
     pub max_loads: usize,
 }
 
+/// How much of each limit has been consumed by parsing a Display.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// The deepest level of nesting reached by any format string.
+    pub depth: usize,
+
+    /// The number of AST nodes allocated across all format strings.
+    pub nodes: usize,
+
+    /// The number of object loads that the parsed formats could perform.
+    pub loads: usize,
+}
+
 /// The available budget left for limits that are tracked across all invocations to the parser for
 /// a single Display.
 pub(crate) struct Budget {
     pub nodes: usize,
     pub loads: usize,
+
+    /// The smallest depth budget left over by any meter sharing this budget, to track the maximum
+    /// depth reached while parsing.
+    pub depth: usize,
 }
 
 pub(crate) struct Meter<'b> {
@@ -36,6 +53,16 @@ impl Limits {
         Budget {
             nodes: self.max_nodes,
             loads: self.max_loads,
+            depth: self.max_depth,
+        }
+    }
+
+    /// Measure how much of these limits has been consumed, based on what is left in `budget`.
+    pub(crate) fn usage(&self, budget: &Budget) -> Usage {
+        Usage {
+            depth: self.max_depth.saturating_sub(budget.depth),
+            nodes: self.max_nodes.saturating_sub(budget.nodes),
+            loads: self.max_loads.saturating_sub(budget.loads),
         }
     }
 }
@@ -54,8 +81,11 @@ impl<'b> Meter<'b> {
             return Err(FormatError::TooDeep);
         }
 
+        let depth_budget = self.depth_budget - 1;
+        self.budget.depth = self.budget.depth.min(depth_budget);
+
         Ok(Meter {
-            depth_budget: self.depth_budget - 1,
+            depth_budget,
             budget: self.budget,
         })
     }
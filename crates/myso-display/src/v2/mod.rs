@@ -29,6 +29,7 @@ mod writer;
 pub use crate::v2::error::FormatError;
 pub use crate::v2::interpreter::Interpreter;
 pub use crate::v2::meter::Limits;
+pub use crate::v2::meter::Usage;
 pub use crate::v2::value::OwnedSlice;
 pub use crate::v2::value::Store;
 pub use crate::v2::value::Value;
@@ -45,6 +46,7 @@ pub struct Format<'s>(Vec<Strand<'s>>);
 /// A collection of format strings that are evaluated to a string-to-string mapping.
 pub struct Display<'s> {
     fields: Vec<Field<'s>>,
+    usage: Usage,
 }
 
 /// Parsed key-value pair for a single field in the format.
@@ -171,7 +173,13 @@ impl<'s> Display<'s> {
             fields.push(Field { key, val });
         }
 
-        Ok(Self { fields })
+        let usage = limits.usage(&budget);
+        Ok(Self { fields, usage })
+    }
+
+    /// How much of the `limits` that this Display was parsed with were consumed by parsing it.
+    pub fn usage(&self) -> Usage {
+        self.usage
     }
 
     /// Render the format with the provided `interpreter`
@@ -1640,6 +1648,206 @@ mod tests {
         "###);
     }
 
+    #[tokio::test]
+    async fn test_display_decimal() {
+        let bytes = bcs::to_bytes(&(1_234_500_000u64, 5u8, 0u64)).unwrap();
+        let layout = struct_(
+            "0x1::m::S",
+            vec![("amount", L::U64), ("small", L::U8), ("zero", L::U64)],
+        );
+
+        let formats = [
+            ("fixed", "{amount:fixed(9)}"),
+            ("places", "{amount:fixed(9, 2)}"),
+            ("whole", "{amount:fixed(9, 0)}"),
+            ("pad", "{small:fixed(3, 5)}"),
+            ("decimal", "{amount:decimal(9)}"),
+            ("integral", "{1000u64:decimal(3)}"),
+            ("zero", "{zero:decimal(6)}"),
+            ("u256", "{1u256:decimal(18)}"),
+            ("invalid", "{'foo':decimal(2)}"),
+        ];
+
+        let output = format(
+            MockStore::default(),
+            Limits::default(),
+            bytes,
+            layout,
+            usize::MAX,
+            ONE_MB,
+            formats,
+        )
+        .await
+        .unwrap();
+
+        assert_debug_snapshot!(output, @r###"
+        {
+            "fixed": Ok(
+                String("1.234500000"),
+            ),
+            "places": Ok(
+                String("1.23"),
+            ),
+            "whole": Ok(
+                String("1"),
+            ),
+            "pad": Ok(
+                String("0.00500"),
+            ),
+            "decimal": Ok(
+                String("1.2345"),
+            ),
+            "integral": Ok(
+                String("1"),
+            ),
+            "zero": Ok(
+                String("0"),
+            ),
+            "u256": Ok(
+                String("0.000000000000000001"),
+            ),
+            "invalid": Err(
+                TransformInvalid_ {
+                    offset: 0,
+                    reason: "expected unsigned integer",
+                },
+            ),
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn test_display_arithmetic() {
+        let bytes = bcs::to_bytes(&(100u64, 3u8, 255u8)).unwrap();
+        let layout = struct_("0x1::m::S", vec![("x", L::U64), ("y", L::U8), ("z", L::U8)]);
+
+        let formats = [
+            ("add", "{x + y}"),
+            ("precedence", "{x - y * 2u8 % 4u8}"),
+            ("divide", "{x / y}"),
+            ("overflow", "{z + 1u8 | 'overflow'}"),
+            ("widen", "{z + 1u16}"),
+            ("underflow", "{y - x | 'underflow'}"),
+            ("div_zero", "{x / 0u64 | 'undefined'}"),
+            ("scaled", "{x * 1000u64:fixed(3, 1)}"),
+            ("invalid", "{x + 'foo'}"),
+        ];
+
+        let output = format(
+            MockStore::default(),
+            Limits::default(),
+            bytes,
+            layout,
+            usize::MAX,
+            ONE_MB,
+            formats,
+        )
+        .await
+        .unwrap();
+
+        assert_debug_snapshot!(output, @r###"
+        {
+            "add": Ok(
+                String("103"),
+            ),
+            "precedence": Ok(
+                String("98"),
+            ),
+            "divide": Ok(
+                String("33"),
+            ),
+            "overflow": Ok(
+                String("overflow"),
+            ),
+            "widen": Ok(
+                String("256"),
+            ),
+            "underflow": Ok(
+                String("underflow"),
+            ),
+            "div_zero": Ok(
+                String("undefined"),
+            ),
+            "scaled": Ok(
+                String("100.0"),
+            ),
+            "invalid": Err(
+                OperandInvalid {
+                    offset: 3,
+                    reason: "expected unsigned integer operands",
+                },
+            ),
+        }
+        "###);
+    }
+
+    #[tokio::test]
+    async fn test_display_conditional() {
+        let bytes = bcs::to_bytes(&(true, 5u64)).unwrap();
+        let layout = struct_("0x1::m::S", vec![("flag", L::Bool), ("count", L::U64)]);
+
+        let formats = [
+            ("flag", "{flag ? 'yes' : 'no'}"),
+            ("compare", "{count > 10u64 ? 'many' : 'few'}"),
+            ("equal", "{count == 5u8 ? 'five' : 'other'}"),
+            ("strings", "{'a' != 'b' ? 'differ' : 'same'}"),
+            (
+                "nested",
+                "{count < 3u64 ? 'low' : count < 8u64 ? 'mid' : 'high'}",
+            ),
+            ("missing", "{foo ? 'yes' : 'no' | 'fallback'}"),
+            ("not_bool", "{count ? 'yes' : 'no'}"),
+            ("mismatch", "{flag == 1u8 ? 'yes' : 'no'}"),
+        ];
+
+        let output = format(
+            MockStore::default(),
+            Limits::default(),
+            bytes,
+            layout,
+            usize::MAX,
+            ONE_MB,
+            formats,
+        )
+        .await
+        .unwrap();
+
+        assert_debug_snapshot!(output, @r###"
+        {
+            "flag": Ok(
+                String("yes"),
+            ),
+            "compare": Ok(
+                String("few"),
+            ),
+            "equal": Ok(
+                String("five"),
+            ),
+            "strings": Ok(
+                String("differ"),
+            ),
+            "nested": Ok(
+                String("mid"),
+            ),
+            "missing": Ok(
+                String("fallback"),
+            ),
+            "not_bool": Err(
+                OperandInvalid {
+                    offset: 7,
+                    reason: "expected boolean condition",
+                },
+            ),
+            "mismatch": Err(
+                OperandInvalid {
+                    offset: 6,
+                    reason: "expected operands of the same type",
+                },
+            ),
+        }
+        "###);
+    }
+
     #[tokio::test]
     async fn test_display_hex() {
         let bytes = bcs::to_bytes(&(
@@ -2483,6 +2691,21 @@ mod tests {
         assert!(matches!(res, Err(Error::TooBig)));
     }
 
+    #[test]
+    fn test_display_usage() {
+        let display =
+            Display::parse(Limits::default(), [("name", "{a.b}"), ("x", "{c->[d]}")]).unwrap();
+
+        assert_eq!(
+            display.usage(),
+            Usage {
+                depth: 2,
+                nodes: 12,
+                loads: 1,
+            }
+        );
+    }
+
     #[tokio::test]
     async fn test_display_output_size_limits() {
         let bytes = bcs::to_bytes(&42u64).unwrap();
@@ -47,11 +47,59 @@ pub struct Expr<'s> {
     /// Byte offset in the source string where the expression starts.
     pub(crate) offset: usize,
 
-    pub(crate) alternates: Vec<Chain<'s>>,
+    pub(crate) alternates: Vec<Term<'s>>,
 
     pub(crate) transform: Option<Transform>,
 }
 
+/// Terms combine chains using arithmetic, comparison, and conditional operators.
+#[derive(PartialEq, Eq)]
+pub enum Term<'s> {
+    Chain(Chain<'s>),
+    BinOp(Box<BinOp<'s>>),
+    Cond(Box<Cond<'s>>),
+}
+
+/// A binary operator applied to two terms.
+#[derive(PartialEq, Eq)]
+pub struct BinOp<'s> {
+    /// Byte offset in the source string of the operator.
+    pub(crate) offset: usize,
+
+    pub(crate) op: Op,
+    pub(crate) lhs: Term<'s>,
+    pub(crate) rhs: Term<'s>,
+}
+
+/// A conditional term, which evaluates to `then` if `cond` evaluates to `true`, and `else_` if it
+/// evaluates to `false`.
+#[derive(PartialEq, Eq)]
+pub struct Cond<'s> {
+    /// Byte offset in the source string of the '?'.
+    pub(crate) offset: usize,
+
+    pub(crate) cond: Term<'s>,
+    pub(crate) then: Term<'s>,
+    pub(crate) else_: Term<'s>,
+}
+
+/// Binary operators, in order of increasing precedence: comparisons, then addition and
+/// subtraction, then multiplication, division, and remainder.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
 /// Chains are a sequence of nested field accesses.
 #[derive(PartialEq, Eq)]
 pub struct Chain<'s> {
@@ -143,6 +191,13 @@ pub enum Fields<'s> {
 pub enum Transform {
     Base64(Base64Modifier),
     Bcs(Base64Modifier),
+    /// Treat an integer as a decimal number scaled up by `10^scale`, and display it with trailing
+    /// zeroes removed from its fractional part.
+    Decimal(u8),
+    /// Treat an integer as a decimal number scaled up by `10^scale` (the first parameter), and
+    /// display it with exactly `places` (the second parameter) fractional digits, truncating any
+    /// extra digits.
+    Fixed(u8, u8),
     Hex,
     Json,
     #[default]
@@ -158,6 +213,10 @@ pub(crate) struct Parser<'s> {
     lexer: Peekable2<Lexer<'s>>,
 }
 
+/// The largest scale supported by decimal transforms. Any larger, and the integer part of every
+/// `u256` would be zero.
+pub(crate) const MAX_DECIMAL_SCALE: u8 = 77;
+
 /// Helper macro for constructing an `Expected` enum variant based on the kind of pattern being
 /// matched on in the parser. The first argument is the kind, which denotes whether the pattern was
 /// looking for a token, regardless of the contents of the underlying slice, or a literal match on
@@ -235,7 +294,17 @@ macro_rules! match_token_opt {
 ///
 ///   part     ::= TEXT | '{{' | '}}'
 ///
-///   expr     ::= '{' chain ('|' chain)* (':' xform)? '}'
+///   expr     ::= '{' term ('|' term)* (':' xform)? '}'
+///
+///   term     ::= cmp ('?' term ':' term)?
+///
+///   cmp      ::= sum (cmp_op sum)?
+///
+///   sum      ::= product (('+' | '-') product)*
+///
+///   product  ::= chain (('*' | '/' | '%') chain)*
+///
+///   cmp_op   ::= '==' | '!=' | '<' | '<=' | '>' | '>='
 ///
 ///   chain    ::= (literal | IDENT) accessor*
 ///
@@ -279,6 +348,8 @@ macro_rules! match_token_opt {
 ///              | 'hex'
 ///              | 'base64' xmod?
 ///              | 'bcs' xmod?
+///              | 'decimal' '(' NUM_DEC ')'
+///              | 'fixed' '(' NUM_DEC (',' NUM_DEC)? ','? ')'
 ///              | 'json'
 ///              | 'timestamp'
 ///              | 'url'
@@ -375,7 +446,7 @@ impl<'s> Parser<'s> {
             off
         }};
 
-        let mut alternates = vec![self.parse_chain(meter)?];
+        let mut alternates = vec![self.parse_term(meter)?];
         let mut transform = None;
 
         loop {
@@ -396,7 +467,7 @@ impl<'s> Parser<'s> {
 
                 Tok(_, T::Pipe, _, _) => {
                     self.lexer.next();
-                    alternates.push(self.parse_chain(meter)?);
+                    alternates.push(self.parse_term(meter)?);
                 }
             }
         }
@@ -409,6 +480,129 @@ impl<'s> Parser<'s> {
         })
     }
 
+    fn parse_term<'b>(&mut self, meter: &mut Meter<'b>) -> Result<Term<'s>, FormatError> {
+        let cond = self.parse_cmp(meter)?;
+
+        let offset = match match_token_opt! { self.lexer;
+            Tok(_, T::Question, off, _) => { self.lexer.next(); off }
+        } {
+            Match::Found(offset) => offset,
+            Match::Tried(_, _) => return Ok(cond),
+        };
+
+        let meter = &mut meter.nest()?;
+        let then = self.parse_term(meter)?;
+        match_token! { self.lexer; Tok(_, T::Colon, _, _) => self.lexer.next() };
+        let else_ = self.parse_term(meter)?;
+
+        meter.alloc()?;
+        Ok(Term::Cond(Box::new(Cond {
+            offset,
+            cond,
+            then,
+            else_,
+        })))
+    }
+
+    fn parse_cmp<'b>(&mut self, meter: &mut Meter<'b>) -> Result<Term<'s>, FormatError> {
+        let lhs = self.parse_sum(meter)?;
+
+        let (offset, op) = match match_token_opt! { self.lexer;
+            Tok(_, T::EEqual, off, _) => (off, Op::Eq),
+            Tok(_, T::NEqual, off, _) => (off, Op::Ne),
+            Tok(_, T::LAngle, off, _) => (off, Op::Lt),
+            Tok(_, T::LEqual, off, _) => (off, Op::Le),
+            Tok(_, T::RAngle, off, _) => (off, Op::Gt),
+            Tok(_, T::GEqual, off, _) => (off, Op::Ge),
+        } {
+            Match::Found(found) => found,
+            Match::Tried(_, _) => return Ok(lhs),
+        };
+
+        self.lexer.next();
+        let meter = &mut meter.nest()?;
+        let rhs = self.parse_sum(meter)?;
+
+        meter.alloc()?;
+        Ok(Term::BinOp(Box::new(BinOp {
+            offset,
+            op,
+            lhs,
+            rhs,
+        })))
+    }
+
+    fn parse_sum<'b>(&mut self, meter: &mut Meter<'b>) -> Result<Term<'s>, FormatError> {
+        let lhs = self.parse_product(meter)?;
+        self.parse_sum_operands(lhs, meter)
+    }
+
+    /// Parse the remaining operands of a sum, associating to the left. Each operator nests the
+    /// meter, because it adds a level to the depth of the resulting term.
+    fn parse_sum_operands<'b>(
+        &mut self,
+        lhs: Term<'s>,
+        meter: &mut Meter<'b>,
+    ) -> Result<Term<'s>, FormatError> {
+        let (offset, op) = match match_token_opt! { self.lexer;
+            Tok(_, T::Plus, off, _) => (off, Op::Add),
+            Tok(_, T::Minus, off, _) => (off, Op::Sub),
+        } {
+            Match::Found(found) => found,
+            Match::Tried(_, _) => return Ok(lhs),
+        };
+
+        self.lexer.next();
+        let meter = &mut meter.nest()?;
+        let rhs = self.parse_product(meter)?;
+
+        meter.alloc()?;
+        let lhs = Term::BinOp(Box::new(BinOp {
+            offset,
+            op,
+            lhs,
+            rhs,
+        }));
+
+        self.parse_sum_operands(lhs, meter)
+    }
+
+    fn parse_product<'b>(&mut self, meter: &mut Meter<'b>) -> Result<Term<'s>, FormatError> {
+        let lhs = Term::Chain(self.parse_chain(meter)?);
+        self.parse_product_operands(lhs, meter)
+    }
+
+    /// Parse the remaining operands of a product, associating to the left. Each operator nests
+    /// the meter, because it adds a level to the depth of the resulting term.
+    fn parse_product_operands<'b>(
+        &mut self,
+        lhs: Term<'s>,
+        meter: &mut Meter<'b>,
+    ) -> Result<Term<'s>, FormatError> {
+        let (offset, op) = match match_token_opt! { self.lexer;
+            Tok(_, T::Star, off, _) => (off, Op::Mul),
+            Tok(_, T::Slash, off, _) => (off, Op::Div),
+            Tok(_, T::Percent, off, _) => (off, Op::Rem),
+        } {
+            Match::Found(found) => found,
+            Match::Tried(_, _) => return Ok(lhs),
+        };
+
+        self.lexer.next();
+        let meter = &mut meter.nest()?;
+        let rhs = Term::Chain(self.parse_chain(meter)?);
+
+        meter.alloc()?;
+        let lhs = Term::BinOp(Box::new(BinOp {
+            offset,
+            op,
+            lhs,
+            rhs,
+        }));
+
+        self.parse_product_operands(lhs, meter)
+    }
+
     fn parse_chain<'b>(&mut self, meter: &mut Meter<'b>) -> Result<Chain<'s>, FormatError> {
         let meter = &mut meter.nest()?;
         let mut accessors = vec![];
@@ -989,6 +1183,38 @@ impl<'s> Parser<'s> {
                 Transform::Bcs(self.parse_xmod()?)
             },
 
+            Lit(_, T::Ident, _, "decimal") => {
+                self.lexer.next();
+                match_token! { self.lexer; Tok(_, T::LParen, _, _) => self.lexer.next() };
+                let scale = self.parse_decimal_scale("decimal scale")?;
+
+                match_token_opt! { self.lexer; Tok(_, T::Comma, _, _) => { self.lexer.next(); } };
+                match_token! { self.lexer; Tok(_, T::RParen, _, _) => self.lexer.next() };
+                Transform::Decimal(scale)
+            },
+
+            Lit(_, T::Ident, _, "fixed") => {
+                self.lexer.next();
+                match_token! { self.lexer; Tok(_, T::LParen, _, _) => self.lexer.next() };
+                let scale = self.parse_decimal_scale("decimal scale")?;
+
+                let delimited = match_token_opt! { self.lexer;
+                    Tok(_, T::Comma, _, _) => { self.lexer.next(); }
+                };
+
+                let places = match (delimited, self.lexer.peek()) {
+                    (Match::Found(_), Some(Lex(_, T::NumDec, _, _))) => {
+                        let places = self.parse_decimal_scale("decimal places")?;
+                        match_token_opt! { self.lexer; Tok(_, T::Comma, _, _) => { self.lexer.next(); } };
+                        places
+                    }
+                    _ => scale,
+                };
+
+                match_token! { self.lexer; Tok(_, T::RParen, _, _) => self.lexer.next() };
+                Transform::Fixed(scale, places)
+            },
+
             Lit(_, T::Ident, _, "hex") => {
                 self.lexer.next();
                 Transform::Hex
@@ -1050,6 +1276,24 @@ impl<'s> Parser<'s> {
         Ok(xmod)
     }
 
+    /// Parse a decimal number of digits, for use in a decimal transform.
+    fn parse_decimal_scale(&mut self, what: &'static str) -> Result<u8, FormatError> {
+        let (offset, scale) = match_token! { self.lexer; Tok(_, T::NumDec, off, n) => {
+            self.lexer.next();
+            (off, read_u8(off, n, 10, what)?)
+        }};
+
+        if scale > MAX_DECIMAL_SCALE {
+            return Err(FormatError::InvalidNumber {
+                what,
+                offset,
+                err: format!("must be at most {MAX_DECIMAL_SCALE}"),
+            });
+        }
+
+        Ok(scale)
+    }
+
     /// Parse end-of-string. Any remaining input is considered an error.
     fn parse_eos(&mut self) -> Result<(), FormatError> {
         if let Some(lex) = self.lexer.next() {
@@ -1134,6 +1378,56 @@ impl fmt::Debug for Expr<'_> {
     }
 }
 
+impl fmt::Debug for Term<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Chain(chain) => chain.fmt(f),
+            Term::BinOp(binop) => binop.fmt(f),
+            Term::Cond(cond) => cond.fmt(f),
+        }
+    }
+}
+
+impl fmt::Debug for BinOp<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        self.lhs.fmt(f)?;
+        write!(f, " {:?}@{} ", self.op, self.offset)?;
+        self.rhs.fmt(f)?;
+        write!(f, ")")
+    }
+}
+
+impl fmt::Debug for Cond<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        self.cond.fmt(f)?;
+        write!(f, " ?@{} ", self.offset)?;
+        self.then.fmt(f)?;
+        write!(f, " : ")?;
+        self.else_.fmt(f)?;
+        write!(f, ")")
+    }
+}
+
+impl fmt::Debug for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Op::Eq => "==",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+        })
+    }
+}
+
 impl fmt::Debug for Chain<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut accessors = self.accessors.iter();
@@ -1261,6 +1555,8 @@ impl fmt::Debug for Transform {
         match self {
             Transform::Base64(xmod) => write!(f, "base64{xmod:?}"),
             Transform::Bcs(xmod) => write!(f, "bcs{xmod:?}"),
+            Transform::Decimal(scale) => write!(f, "decimal({scale})"),
+            Transform::Fixed(scale, places) => write!(f, "fixed({scale}, {places})"),
             Transform::Hex => write!(f, "hex"),
             Transform::Json => write!(f, "json"),
             Transform::Str => write!(f, "str"),
@@ -1415,6 +1711,7 @@ mod tests {
     use super::Expr as E;
     use super::Literal as L;
     use super::Strand as S;
+    use super::Term as T;
     use super::*;
 
     fn strands(src: &str) -> String {
@@ -1491,10 +1788,10 @@ mod tests {
             strands,
             vec![S::Expr(E {
                 offset: 0,
-                alternates: vec![C {
+                alternates: vec![T::Chain(C {
                     root: None,
                     accessors: vec![A::Field(ident_str!("foo"))],
-                }],
+                })],
                 transform: None,
             })]
         );
@@ -1509,10 +1806,10 @@ mod tests {
             strands,
             vec![S::Expr(E {
                 offset: 0,
-                alternates: vec![C {
+                alternates: vec![T::Chain(C {
                     root: None,
                     accessors: vec![A::Field(ident_str!("foo"))],
-                }],
+                })],
                 transform: Some(Transform::Str),
             })]
         );
@@ -1527,10 +1824,10 @@ mod tests {
             strands,
             vec![S::Expr(E {
                 offset: 0,
-                alternates: vec![C {
+                alternates: vec![T::Chain(C {
                     root: None,
                     accessors: vec![A::Field(ident_str!("foo")), A::Field(ident_str!("bar"))],
-                }],
+                })],
                 transform: None,
             })]
         );
@@ -1547,10 +1844,10 @@ mod tests {
                 S::Text("foo ".into()),
                 S::Expr(E {
                     offset: 4,
-                    alternates: vec![C {
+                    alternates: vec![T::Chain(C {
                         root: None,
                         accessors: vec![A::Field(ident_str!("bar"))],
-                    }],
+                    })],
                     transform: None,
                 }),
                 S::Text(" baz".into()),
@@ -1568,18 +1865,18 @@ mod tests {
             vec![S::Expr(E {
                 offset: 0,
                 alternates: vec![
-                    C {
+                    T::Chain(C {
                         root: None,
                         accessors: vec![A::Field(ident_str!("foo"))],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: None,
                         accessors: vec![A::Field(ident_str!("bar"))],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: None,
                         accessors: vec![A::Field(ident_str!("baz"))],
-                    },
+                    }),
                 ],
                 transform: None,
             })]
@@ -1595,7 +1892,7 @@ mod tests {
             strands,
             vec![S::Expr(E {
                 offset: 0,
-                alternates: vec![C {
+                alternates: vec![T::Chain(C {
                     root: None,
                     accessors: vec![
                         A::Field(ident_str!("foo")),
@@ -1608,7 +1905,7 @@ mod tests {
                             accessors: vec![A::Field(ident_str!("baz"))],
                         }),
                     ],
-                }],
+                })],
                 transform: None,
             })]
         );
@@ -1624,7 +1921,7 @@ mod tests {
             vec![S::Expr(E {
                 offset: 0,
                 alternates: vec![
-                    C {
+                    T::Chain(C {
                         root: None,
                         accessors: vec![
                             A::Field(ident_str!("foo")),
@@ -1639,8 +1936,8 @@ mod tests {
                                 ],
                             }),
                         ],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: None,
                         accessors: vec![
                             A::Field(ident_str!("qux")),
@@ -1649,7 +1946,7 @@ mod tests {
                                 accessors: vec![A::Field(ident_str!("quy"))],
                             }),
                         ],
-                    },
+                    }),
                 ],
                 transform: None,
             })]
@@ -1667,32 +1964,32 @@ mod tests {
             vec![S::Expr(E {
                 offset: 0,
                 alternates: vec![
-                    C {
+                    T::Chain(C {
                         root: Some(L::Bool(true)),
                         accessors: vec![],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: Some(L::Address(
                             AccountAddress::from_hex_literal("0x1234").unwrap()
                         )),
                         accessors: vec![],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: Some(L::U64(5678)),
                         accessors: vec![],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: Some(L::ByteArray(vec![0xab, 0xcd, 0xef])),
                         accessors: vec![],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: Some(L::ByteArray(b"hello".to_vec())),
                         accessors: vec![],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: Some(L::String("world".into())),
                         accessors: vec![],
-                    },
+                    }),
                 ],
                 transform: None,
             })]
@@ -1717,7 +2014,7 @@ mod tests {
             vec![S::Expr(E {
                 offset: 0,
                 alternates: vec![
-                    C {
+                    T::Chain(C {
                         root: Some(L::Vector(Box::new(Vector {
                             offset: 2,
                             type_: None,
@@ -1737,8 +2034,8 @@ mod tests {
                             ],
                         }))),
                         accessors: vec![],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: Some(L::Vector(Box::new(Vector {
                             offset: 41,
                             type_: Some(TypeTag::U16),
@@ -1754,24 +2051,24 @@ mod tests {
                             ],
                         }))),
                         accessors: vec![],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: Some(L::Vector(Box::new(Vector {
                             offset: 82,
                             type_: Some(TypeTag::U32),
                             elements: vec![],
                         }))),
                         accessors: vec![],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: Some(L::Vector(Box::new(Vector {
                             offset: 111,
                             type_: Some(TypeTag::U64),
                             elements: vec![],
                         }))),
                         accessors: vec![],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: Some(L::Vector(Box::new(Vector {
                             offset: 142,
                             type_: Some(
@@ -1780,7 +2077,7 @@ mod tests {
                             elements: vec![],
                         }))),
                         accessors: vec![],
-                    },
+                    }),
                 ],
                 transform: None,
             })]
@@ -1801,7 +2098,7 @@ mod tests {
             vec![S::Expr(E {
                 offset: 0,
                 alternates: vec![
-                    C {
+                    T::Chain(C {
                         root: Some(L::Struct(Box::new(Struct {
                             type_: StructTag::from_str("0x1::string::String").unwrap(),
                             fields: Fields::Positional(vec![
@@ -1837,8 +2134,8 @@ mod tests {
                             ]),
                         }))),
                         accessors: vec![],
-                    },
-                    C {
+                    }),
+                    T::Chain(C {
                         root: Some(L::Enum(Box::new(Enum {
                             type_: StructTag::from_str("0x2::coin::Coin<0x2::myso::MYSO>").unwrap(),
                             variant_name: Some("Foo"),
@@ -1852,7 +2149,7 @@ mod tests {
                             )]),
                         }))),
                         accessors: vec![],
-                    },
+                    }),
                 ],
                 transform: None,
             })]
@@ -2097,6 +2394,28 @@ mod tests {
         assert_snapshot!(strands(r#"{foo.0[bar].1.baz}"#));
     }
 
+    #[test]
+    fn test_arithmetic_precedence() {
+        assert_snapshot!(strands(r#"{a + b * c - d % 2u8}"#));
+    }
+
+    #[test]
+    fn test_comparison_conditional() {
+        assert_snapshot!(strands(r#"{a.b >= 10u64 ? 'big' : 'small'}"#));
+    }
+
+    #[test]
+    fn test_conditional_alternates_with_transform() {
+        assert_snapshot!(strands(r#"{x == y ? a : b | c : hex}"#));
+    }
+
+    #[test]
+    fn test_decimal_transforms() {
+        assert_snapshot!(strands(
+            r#"{foo:fixed(9)} {bar:fixed(9, 2)} {baz:decimal(6)}"#
+        ));
+    }
+
     /**
      * Error Cases
      *
@@ -2145,7 +2464,7 @@ mod tests {
 
     #[test]
     fn test_unexpected_characters() {
-        assert_snapshot!(strands(r#"anything goes {? % ! 🔥}"#));
+        assert_snapshot!(strands(r#"anything goes {$ ~ ! 🔥}"#));
     }
 
     #[test]
@@ -2166,6 +2485,16 @@ mod tests {
         assert_snapshot!(strands(r#"{foo | bar | baz |}"#));
     }
 
+    #[test]
+    fn test_conditional_missing_else() {
+        assert_snapshot!(strands(r#"{a ? b}"#));
+    }
+
+    #[test]
+    fn test_decimal_scale_too_large() {
+        assert_snapshot!(strands(r#"{foo:fixed(78)}"#));
+    }
+
     #[test]
     fn test_hex_address_overflow() {
         assert_snapshot!(strands(
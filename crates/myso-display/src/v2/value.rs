@@ -34,6 +34,7 @@ use serde::ser::SerializeTupleVariant;
 
 use crate::v2::error::FormatError;
 use crate::v2::parser::Base64Modifier;
+use crate::v2::parser::Op;
 use crate::v2::parser::Transform;
 use crate::v2::writer::JsonWriter;
 use crate::v2::writer::StringWriter;
@@ -91,6 +92,17 @@ pub enum Atom<'s> {
     U256(U256),
 }
 
+/// The widths of Move's unsigned integer types, ordered from narrowest to widest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Width {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+}
+
 /// A single step in a chain of accesses, with its inner expression (if there is one) evaluated.
 pub enum Accessor<'s> {
     Field(&'s str),
@@ -186,6 +198,11 @@ impl Value<'_> {
                 Ok(write!(w, "{}", xmod.engine().encode(bytes))?)
             }
 
+            Transform::Decimal(scale) => Atom::try_from(self)?.format_as_decimal(scale, None, w),
+            Transform::Fixed(scale, places) => {
+                Atom::try_from(self)?.format_as_decimal(scale, Some(places), w)
+            }
+
             Transform::Hex => Atom::try_from(self)?.format_as_hex(w),
             Transform::Json => Err(FormatError::TransformInvalid("unexpected 'json' in string")),
             Transform::Str => Atom::try_from(self)?.format_as_str(w),
@@ -297,6 +314,127 @@ impl Value<'_> {
             | V::Vector(_) => None,
         }
     }
+
+    /// Attempt to coerce this value into an unsigned integer, returning its value widened to a
+    /// `U256`, alongside its original width.
+    pub(crate) fn as_numeric(&self) -> Option<(U256, Width)> {
+        use MoveTypeLayout as L;
+        use Value as V;
+
+        match self {
+            // Numeric literals in Display
+            V::U8(n) => Some(((*n).into(), Width::U8)),
+            V::U16(n) => Some(((*n).into(), Width::U16)),
+            V::U32(n) => Some(((*n).into(), Width::U32)),
+            V::U64(n) => Some(((*n).into(), Width::U64)),
+            V::U128(n) => Some(((*n).into(), Width::U128)),
+            V::U256(n) => Some((*n, Width::U256)),
+
+            // Numeric values sliced out of Move values
+            V::Slice(Slice {
+                layout,
+                bytes: data,
+            }) => match layout {
+                L::U8 => Some((bcs::from_bytes::<u8>(data).ok()?.into(), Width::U8)),
+                L::U16 => Some((bcs::from_bytes::<u16>(data).ok()?.into(), Width::U16)),
+                L::U32 => Some((bcs::from_bytes::<u32>(data).ok()?.into(), Width::U32)),
+                L::U64 => Some((bcs::from_bytes::<u64>(data).ok()?.into(), Width::U64)),
+                L::U128 => Some((bcs::from_bytes::<u128>(data).ok()?.into(), Width::U128)),
+                L::U256 => Some((bcs::from_bytes::<U256>(data).ok()?, Width::U256)),
+                L::Address | L::Bool | L::Enum(_) | L::Signer | L::Struct(_) | L::Vector(_) => None,
+            },
+
+            // Everything else is not numeric
+            V::Address(_)
+            | V::Bool(_)
+            | V::Bytes(_)
+            | V::Enum(_)
+            | V::String(_)
+            | V::Struct(_)
+            | V::Vector(_) => None,
+        }
+    }
+
+    /// Attempt to coerce this value into a `bool`, if it is a boolean literal, or a boolean sliced
+    /// out of a Move value.
+    pub(crate) fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            Value::Slice(Slice {
+                layout: MoveTypeLayout::Bool,
+                bytes,
+            }) => bcs::from_bytes(bytes).ok(),
+            _ => None,
+        }
+    }
+
+    /// Apply the binary operator `op` to this value and `rhs`.
+    ///
+    /// Arithmetic operators accept unsigned integers of any width, and produce a result as wide as
+    /// the wider of their operands. If the result overflows or underflows that width, or the
+    /// operation divides by zero, the operation evaluates to `Ok(None)`, like a failed field
+    /// access. Ordering comparisons accept unsigned integers, and equality comparisons accept
+    /// unsigned integers, or two values of the same type. Other operands produce an error,
+    /// attributed to the operator at byte offset `offset`.
+    pub(crate) fn apply(
+        self,
+        op: Op,
+        offset: usize,
+        rhs: Value<'_>,
+    ) -> Result<Option<Value<'static>>, FormatError> {
+        let numeric = self.as_numeric().zip(rhs.as_numeric());
+
+        if let (Op::Eq | Op::Ne, None) = (op, numeric) {
+            if self.type_() != rhs.type_() {
+                return Err(FormatError::OperandInvalid {
+                    offset,
+                    reason: "expected operands of the same type",
+                });
+            }
+
+            let eq = bcs::to_bytes(&self)? == bcs::to_bytes(&rhs)?;
+            return Ok(Some(Value::Bool(eq == (op == Op::Eq))));
+        }
+
+        let Some(((l, lw), (r, rw))) = numeric else {
+            return Err(FormatError::OperandInvalid {
+                offset,
+                reason: "expected unsigned integer operands",
+            });
+        };
+
+        let width = lw.max(rw);
+        let result = match op {
+            Op::Eq => return Ok(Some(Value::Bool(l == r))),
+            Op::Ne => return Ok(Some(Value::Bool(l != r))),
+            Op::Lt => return Ok(Some(Value::Bool(l < r))),
+            Op::Le => return Ok(Some(Value::Bool(l <= r))),
+            Op::Gt => return Ok(Some(Value::Bool(l > r))),
+            Op::Ge => return Ok(Some(Value::Bool(l >= r))),
+
+            Op::Add => l.checked_add(r),
+            Op::Sub => l.checked_sub(r),
+            Op::Mul => l.checked_mul(r),
+            Op::Div => l.checked_div(r),
+            Op::Rem => l.checked_rem(r),
+        };
+
+        Ok(result.and_then(|n| width.value(n)))
+    }
+}
+
+impl Width {
+    /// Represent `n` as a value of this width, if it fits.
+    fn value(self, n: U256) -> Option<Value<'static>> {
+        Some(match self {
+            Width::U8 => Value::U8(n.try_into().ok()?),
+            Width::U16 => Value::U16(n.try_into().ok()?),
+            Width::U32 => Value::U32(n.try_into().ok()?),
+            Width::U64 => Value::U64(n.try_into().ok()?),
+            Width::U128 => Value::U128(n.try_into().ok()?),
+            Width::U256 => Value::U256(n),
+        })
+    }
 }
 
 impl Atom<'_> {
@@ -348,6 +486,47 @@ impl Atom<'_> {
         Ok(())
     }
 
+    /// Interpret the atom as a decimal number, scaled up by `10^scale`, and format it. If `places`
+    /// is provided, the fractional part is truncated or padded to exactly that many digits,
+    /// otherwise trailing zeroes are removed from the fractional part (and the decimal point is
+    /// omitted if the number is integral).
+    fn format_as_decimal(
+        &self,
+        scale: u8,
+        places: Option<u8>,
+        w: &mut StringWriter<'_>,
+    ) -> Result<(), FormatError> {
+        let n = self
+            .as_u256()
+            .ok_or_else(|| FormatError::TransformInvalid("expected unsigned integer"))?;
+
+        // Pad the digits so that there is always at least one digit before the decimal point.
+        let scale = scale as usize;
+        let digits = format!("{:0>width$}", n.to_string(), width = scale + 1);
+        let (int, frac) = digits.split_at(digits.len() - scale);
+
+        match places {
+            None => {
+                let frac = frac.trim_end_matches('0');
+                if frac.is_empty() {
+                    write!(w, "{int}")?;
+                } else {
+                    write!(w, "{int}.{frac}")?;
+                }
+            }
+
+            Some(0) => write!(w, "{int}")?,
+
+            Some(places) => {
+                let places = places as usize;
+                let frac = &frac[..places.min(frac.len())];
+                write!(w, "{int}.{frac:0<places$}")?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Coerce the atom into an `i64`, interpreted as an offset in milliseconds since the Unix
     /// epoch, and format it as an ISO8601 timestamp.
     fn format_as_timestamp(&self, w: &mut StringWriter<'_>) -> Result<(), FormatError> {
@@ -410,6 +589,19 @@ impl Atom<'_> {
         Ok(())
     }
 
+    /// Attempt to coerce this atom into a `U256`, if it is an unsigned integer.
+    fn as_u256(&self) -> Option<U256> {
+        match self {
+            Atom::U8(n) => Some((*n).into()),
+            Atom::U16(n) => Some((*n).into()),
+            Atom::U32(n) => Some((*n).into()),
+            Atom::U64(n) => Some((*n).into()),
+            Atom::U128(n) => Some((*n).into()),
+            Atom::U256(n) => Some(*n),
+            _ => None,
+        }
+    }
+
     /// Attempt to coerce this atom into an `i64`, if possible.
     fn as_i64(&self) -> Option<i64> {
         match self {
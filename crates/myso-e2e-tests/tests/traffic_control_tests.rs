@@ -13,7 +13,8 @@ use jsonrpsee::{core::client::ClientT, rpc_params};
 use myso_core::authority_client::AuthorityAPI;
 use myso_core::authority_client::make_network_authority_clients_with_network_config;
 use myso_core::traffic_controller::{
-    TrafficController, TrafficSim, nodefw_test_server::NodeFwTestServer,
+    BlockSource, DryRunTarget, TrafficController, TrafficSim, nodefw_test_server::NodeFwTestServer,
+    policies::TrafficTally,
 };
 use myso_json_rpc_types::{
     MySoTransactionBlockEffectsAPI, MySoTransactionBlockResponse,
@@ -29,11 +30,13 @@ use myso_types::{
     messages_grpc::SubmitTxRequest,
     signature::GenericSignature,
     traffic_control::{
-        FreqThresholdConfig, PolicyConfig, PolicyType, RemoteFirewallConfig, Weight,
+        AccessListConfig, FreqThresholdConfig, PolicyConfig, PolicyType, RemoteFirewallConfig,
+        TokenBucketConfig, Weight,
     },
     transaction_driver_types::ExecuteTransactionRequestType,
 };
 use std::fs::File;
+use std::net::{IpAddr, Ipv4Addr};
 use std::num::NonZeroUsize;
 use std::time::Duration;
 use test_cluster::{TestCluster, TestClusterBuilder};
@@ -621,6 +624,177 @@ async fn test_fullnode_traffic_control_spam_delegated() -> Result<(), anyhow::Er
     Ok(())
 }
 
+#[tokio::test]
+async fn test_fullnode_traffic_control_token_bucket_delegated() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let txn_count = 10;
+    let port = 65002;
+    let policy_config = PolicyConfig {
+        connection_blocklist_ttl_sec: 120,
+        // A single transaction execution (cost 10) followed by a handful of
+        // reads (cost 1 each) exhausts the bucket, as it barely refills.
+        spam_policy_type: PolicyType::TokenBucket(TokenBucketConfig {
+            client_capacity: 15,
+            client_refill_rate: 0,
+            default_cost: 1,
+            ..Default::default()
+        }),
+        spam_sample_rate: Weight::one(),
+        dry_run: false,
+        ..Default::default()
+    };
+    let firewall_config = RemoteFirewallConfig {
+        remote_fw_url: format!("http://127.0.0.1:{}", port),
+        delegate_spam_blocking: true,
+        delegate_error_blocking: false,
+        destination_port: 9000,
+        drain_path: tempfile::tempdir().unwrap().keep().join("drain"),
+        drain_timeout_secs: 10,
+    };
+    let test_cluster = TestClusterBuilder::new()
+        .with_fullnode_policy_config(Some(policy_config))
+        .with_fullnode_fw_config(Some(firewall_config))
+        .build()
+        .await;
+
+    // start test firewall server
+    let mut server = NodeFwTestServer::new();
+    server.start(port).await;
+    // await for the server to start
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+    let context = test_cluster.wallet;
+    let jsonrpc_client = &test_cluster.fullnode_handle.rpc_client;
+    let mut txns = batch_make_transfer_transactions(&context, 1).await;
+
+    let txn = txns.swap_remove(0);
+    let tx_digest = txn.digest();
+    let (tx_bytes, signatures) = txn.to_tx_bytes_and_signatures();
+    let params = rpc_params![
+        tx_bytes,
+        signatures,
+        MySoTransactionBlockResponseOptions::new(),
+        ExecuteTransactionRequestType::WaitForLocalExecution
+    ];
+    let response: MySoTransactionBlockResponse = jsonrpc_client
+        .request("myso_executeTransactionBlock", params)
+        .await
+        .unwrap();
+    assert_eq!(&response.digest, tx_digest);
+
+    // blocking is delegated, so requests keep succeeding at the node
+    for _ in 0..txn_count {
+        let response: Result<MySoTransactionBlockResponse, _> = jsonrpc_client
+            .request("myso_getTransactionBlock", rpc_params![*tx_digest])
+            .await;
+        assert!(response.is_ok(), "Expected request to succeed");
+    }
+    let fw_blocklist = server.list_addresses_rpc().await;
+    assert!(
+        !fw_blocklist.is_empty(),
+        "Expected blocklist to be non-empty"
+    );
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fullnode_traffic_control_access_list_delegated() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let port = 65003;
+    let access_list = tempfile::tempdir().unwrap().keep().join("access-list");
+    std::fs::write(&access_list, "deny 127.0.0.1\n").unwrap();
+    let policy_config = PolicyConfig {
+        connection_blocklist_ttl_sec: 120,
+        spam_policy_type: PolicyType::AccessList(AccessListConfig {
+            path: access_list,
+            reload_interval_secs: 1,
+        }),
+        spam_sample_rate: Weight::one(),
+        dry_run: false,
+        ..Default::default()
+    };
+    let firewall_config = RemoteFirewallConfig {
+        remote_fw_url: format!("http://127.0.0.1:{}", port),
+        delegate_spam_blocking: true,
+        delegate_error_blocking: false,
+        destination_port: 9000,
+        drain_path: tempfile::tempdir().unwrap().keep().join("drain"),
+        drain_timeout_secs: 10,
+    };
+    let test_cluster = TestClusterBuilder::new()
+        .with_fullnode_policy_config(Some(policy_config))
+        .with_fullnode_fw_config(Some(firewall_config))
+        .build()
+        .await;
+
+    // start test firewall server
+    let mut server = NodeFwTestServer::new();
+    server.start(port).await;
+    // await for the server to start
+    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+    let jsonrpc_client = &test_cluster.fullnode_handle.rpc_client;
+
+    // the first tally from a denied client is enough to block it
+    let _: String = jsonrpc_client
+        .request("myso_getLatestCheckpointSequenceNumber", rpc_params![])
+        .await
+        .unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    let fw_blocklist = server.list_addresses_rpc().await;
+    assert!(
+        fw_blocklist
+            .iter()
+            .any(|address| address.source_address == "127.0.0.1"),
+        "Expected denied client to be delegated to the firewall"
+    );
+    server.stop().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fullnode_traffic_control_composite_allow_list() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let txn_count = 10;
+    let access_list = tempfile::tempdir().unwrap().keep().join("access-list");
+    std::fs::write(&access_list, "allow 127.0.0.1\n").unwrap();
+    let policy_config = PolicyConfig {
+        connection_blocklist_ttl_sec: 3,
+        // The token bucket alone would block the client within a few
+        // requests, but the allow list exempts it.
+        spam_policy_type: PolicyType::Composite(vec![
+            PolicyType::AccessList(AccessListConfig {
+                path: access_list,
+                reload_interval_secs: 1,
+            }),
+            PolicyType::TokenBucket(TokenBucketConfig {
+                client_capacity: 3,
+                client_refill_rate: 0,
+                default_cost: 1,
+                ..Default::default()
+            }),
+        ]),
+        spam_sample_rate: Weight::one(),
+        dry_run: false,
+        ..Default::default()
+    };
+    let test_cluster = TestClusterBuilder::new()
+        .with_fullnode_policy_config(Some(policy_config))
+        .build()
+        .await;
+    let jsonrpc_client = &test_cluster.fullnode_handle.rpc_client;
+
+    for _ in 0..txn_count {
+        let response: Result<String, _> = jsonrpc_client
+            .request("myso_getLatestCheckpointSequenceNumber", rpc_params![])
+            .await;
+        assert!(
+            response.is_ok(),
+            "Expected allow-listed client to be served"
+        );
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_traffic_control_dead_mans_switch() -> Result<(), anyhow::Error> {
     telemetry_subscribers::init_for_testing();
@@ -690,6 +864,98 @@ async fn test_traffic_control_manual_set_dead_mans_switch() -> Result<(), anyhow
     Ok(())
 }
 
+#[tokio::test]
+async fn test_traffic_control_admin_blocklist() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let policy_config = PolicyConfig {
+        connection_blocklist_ttl_sec: 60,
+        spam_policy_type: PolicyType::TestNConnIP(3),
+        spam_sample_rate: Weight::one(),
+        dry_run: false,
+        ..Default::default()
+    };
+    let tc = TrafficController::init_for_test(policy_config, None).await;
+    let alice = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+    let bob = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2));
+
+    for _ in 0..3 {
+        tc.tally(TrafficTally::new(Some(alice), None, None, Weight::one()));
+    }
+    // give the tally loop a chance to process the tallies
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(!tc.check(&Some(alice), &None).await);
+
+    let blocked = tc.blocked_clients()?;
+    assert_eq!(blocked.len(), 1);
+    assert_eq!(blocked[0].client, alice);
+    assert!(!blocked[0].proxied);
+    assert_eq!(blocked[0].source, BlockSource::SpamPolicy("TestNConnIP"));
+
+    // a stricter policy would have blocked alice from her second request on, and tallies are
+    // recorded for dry runs even though traffic control is not in dry-run mode
+    let report = tc
+        .dry_run_policy(PolicyType::TestNConnIP(2), DryRunTarget::Spam)
+        .await?;
+    assert_eq!(report.samples, 3);
+    assert_eq!(report.would_block.len(), 1);
+    assert_eq!(report.would_block[0].client, alice);
+    assert_eq!(report.would_block[0].first_blocked_sample, 1);
+    assert_eq!(report.would_block[0].blocked_samples, 2);
+
+    // none of the requests failed, so there is nothing to replay for the error policy
+    let report = tc
+        .dry_run_policy(PolicyType::TestNConnIP(1), DryRunTarget::Error)
+        .await?;
+    assert_eq!(report.samples, 0);
+    assert!(report.would_block.is_empty());
+
+    // manually unblock alice and block bob instead
+    assert!(tc.admin_unblock(alice, false)?);
+    assert!(!tc.admin_unblock(alice, false)?);
+    assert!(tc.check(&Some(alice), &None).await);
+    tc.admin_block(bob, false, Duration::from_secs(60))?;
+    assert!(!tc.check(&Some(bob), &None).await);
+    let blocked = tc.blocked_clients()?;
+    assert_eq!(blocked.len(), 1);
+    assert_eq!(blocked[0].client, bob);
+    assert_eq!(blocked[0].source, BlockSource::Manual);
+
+    // dry runs leave the blocklists untouched
+    tc.dry_run_policy(PolicyType::TestNConnIP(1), DryRunTarget::Spam)
+        .await?;
+    assert!(tc.check(&Some(alice), &None).await);
+    assert!(
+        tc.blocked_clients()?
+            .iter()
+            .any(|b| b.client == bob && b.source == BlockSource::Manual)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_traffic_control_admin_allowlist_mode() {
+    telemetry_subscribers::init_for_testing();
+    let policy_config = PolicyConfig {
+        allow_list: Some(vec![String::from("127.0.0.1")]),
+        dry_run: false,
+        ..Default::default()
+    };
+    let tc = TrafficController::init_for_test(policy_config, None).await;
+    let client = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2));
+
+    // allowlist mode keeps no blocklists to inspect or edit
+    assert!(tc.blocked_clients().is_err());
+    assert!(
+        tc.admin_block(client, false, Duration::from_secs(60))
+            .is_err()
+    );
+    assert!(
+        tc.dry_run_policy(PolicyType::NoOp, DryRunTarget::Spam)
+            .await
+            .is_err()
+    );
+}
+
 #[sim_test]
 async fn test_traffic_sketch_no_blocks() {
     telemetry_subscribers::init_for_testing();
@@ -0,0 +1,393 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use anyhow::Context;
+use anyhow::bail;
+use move_core_types::identifier::Identifier;
+use myso_types::MYSO_SOCIAL_PACKAGE_ID;
+use myso_types::base_types::MySoAddress;
+use myso_types::base_types::ObjectID;
+use myso_types::base_types::SequenceNumber;
+use myso_types::crypto::AccountKeyPair;
+use myso_types::effects::TransactionEffectsAPI;
+use myso_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use myso_types::transaction::Argument;
+use myso_types::transaction::ObjectArg;
+use myso_types::transaction::SharedObjectMutability;
+use myso_types::transaction::Transaction;
+use myso_types::transaction::TransactionData;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+
+use myso_indexer_alt_e2e_tests::FullCluster;
+use myso_indexer_alt_e2e_tests::find;
+
+/// 5 MYSO gas budget
+const DEFAULT_GAS_BUDGET: u64 = 5_000_000_000;
+
+const SHARED_QUERY: &str = r#"
+query($type: String!) {
+    objects(filter: { type: $type }) {
+        nodes {
+            address
+            owner { ... on Shared { initialSharedVersion } }
+        }
+    }
+}
+"#;
+
+const SOCIAL_QUERY: &str = r#"
+query($address: MySoAddress!, $first: Int, $after: String) {
+    address(address: $address) {
+        profile {
+            username
+            displayName
+            bio
+            owner { address }
+        }
+        followers(first: $first, after: $after) {
+            pageInfo { hasNextPage endCursor }
+            nodes { address }
+        }
+        following(first: $first, after: $after) {
+            pageInfo { hasNextPage endCursor }
+            nodes { address }
+        }
+    }
+}
+"#;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Social {
+    profile: Option<Profile>,
+    followers: Option<Connection>,
+    following: Option<Connection>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Profile {
+    username: String,
+    display_name: Option<String>,
+    bio: String,
+    owner: Node,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Connection {
+    page_info: PageInfo,
+    nodes: Vec<Node>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Eq)]
+struct Node {
+    address: String,
+}
+
+/// A network without the social protocol bootstrapped has no profiles or social graph, so the
+/// social fields on addresses are all `null`.
+#[tokio::test]
+async fn test_social_not_bootstrapped() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    let (a, _, _) = cluster.funded_account(DEFAULT_GAS_BUDGET).unwrap();
+    cluster.create_checkpoint().await;
+
+    let social = query_social(&cluster, a, None, None).await.unwrap();
+    assert_eq!(social.profile, None);
+    assert!(social.followers.is_none());
+    assert!(social.following.is_none());
+}
+
+#[tokio::test]
+async fn test_profile() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    let social = bootstrap(&mut cluster).await;
+
+    let (a, a_kp, _) = cluster.funded_account(DEFAULT_GAS_BUDGET).unwrap();
+    let (b, _, _) = cluster.funded_account(DEFAULT_GAS_BUDGET).unwrap();
+    create_profile(&mut cluster, &social, a, &a_kp, "alice", "Alice", "Hello");
+    cluster.create_checkpoint().await;
+
+    let profile = query_social(&cluster, a, None, None)
+        .await
+        .unwrap()
+        .profile
+        .expect("Profile should exist");
+
+    assert_eq!(
+        profile,
+        Profile {
+            username: "alice".to_owned(),
+            display_name: Some("Alice".to_owned()),
+            bio: "Hello".to_owned(),
+            owner: Node {
+                address: a.to_string(),
+            },
+        }
+    );
+
+    // An address without a profile, on a network where the protocol is bootstrapped.
+    let social = query_social(&cluster, b, None, None).await.unwrap();
+    assert_eq!(social.profile, None);
+}
+
+#[tokio::test]
+async fn test_followers_and_following() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    let social = bootstrap(&mut cluster).await;
+
+    let (a, a_kp, _) = cluster.funded_account(DEFAULT_GAS_BUDGET).unwrap();
+    let mut followers = vec![];
+    for _ in 0..3 {
+        let (f, f_kp, _) = cluster.funded_account(DEFAULT_GAS_BUDGET).unwrap();
+        follow(&mut cluster, &social, f, &f_kp, a);
+        followers.push(f);
+    }
+
+    follow(&mut cluster, &social, a, &a_kp, followers[1]);
+    cluster.create_checkpoint().await;
+
+    // Paginate through the followers of `a`, in the order they followed it.
+    let mut seen = vec![];
+    let mut cursor = None;
+    loop {
+        let page = query_social(&cluster, a, Some(2), cursor)
+            .await
+            .unwrap()
+            .followers
+            .expect("Social graph should exist");
+
+        seen.extend(page.nodes.into_iter().map(|n| n.address));
+        if !page.page_info.has_next_page {
+            break;
+        }
+
+        cursor = page.page_info.end_cursor;
+    }
+
+    let expect: Vec<_> = followers.iter().map(|f| f.to_string()).collect();
+    assert_eq!(seen, expect);
+
+    let social_a = query_social(&cluster, a, None, None).await.unwrap();
+    let following = social_a.following.expect("Social graph should exist");
+    assert_eq!(
+        following.nodes,
+        vec![Node {
+            address: followers[1].to_string()
+        }]
+    );
+
+    // Following is one-directional, and addresses that are not in the graph have empty sets.
+    let social_f = query_social(&cluster, followers[0], None, None)
+        .await
+        .unwrap();
+    let following = social_f.following.expect("Social graph should exist");
+    let followers_of_f = social_f.followers.expect("Social graph should exist");
+    assert_eq!(
+        following.nodes,
+        vec![Node {
+            address: a.to_string()
+        }]
+    );
+    assert!(followers_of_f.nodes.is_empty());
+    assert!(!followers_of_f.page_info.has_next_page);
+}
+
+/// The shared objects of the social protocol that tests interact with.
+struct SocialObjects {
+    username_registry: ObjectArg,
+    social_graph: ObjectArg,
+}
+
+/// Bootstrap the social protocol by claiming its admin capabilities, which creates its shared
+/// registries, and return references to the registries the tests use.
+async fn bootstrap(cluster: &mut FullCluster) -> SocialObjects {
+    cluster.create_checkpoint().await;
+    let bootstrap_key = shared_object(cluster, "0x2::bootstrap_key::BootstrapKey")
+        .await
+        .unwrap();
+
+    let (sender, kp, _) = cluster.funded_account(DEFAULT_GAS_BUDGET).unwrap();
+    call(
+        cluster,
+        sender,
+        &kp,
+        "bootstrap",
+        "claim_all_admin_capabilities",
+        |builder| vec![builder.obj(bootstrap_key).unwrap()],
+    );
+    cluster.create_checkpoint().await;
+
+    SocialObjects {
+        username_registry: shared_object(cluster, "0x50c1::profile::UsernameRegistry")
+            .await
+            .unwrap(),
+        social_graph: shared_object(cluster, "0x50c1::social_graph::SocialGraph")
+            .await
+            .unwrap(),
+    }
+}
+
+fn create_profile(
+    cluster: &mut FullCluster,
+    social: &SocialObjects,
+    sender: MySoAddress,
+    kp: &AccountKeyPair,
+    username: &str,
+    display_name: &str,
+    bio: &str,
+) {
+    call(
+        cluster,
+        sender,
+        kp,
+        "profile",
+        "create_profile",
+        |builder| {
+            vec![
+                builder.obj(social.username_registry).unwrap(),
+                builder.pure(display_name.to_owned()).unwrap(),
+                builder.pure(username.to_owned()).unwrap(),
+                builder.pure(bio.to_owned()).unwrap(),
+                builder.pure(Vec::<u8>::new()).unwrap(),
+                builder.pure(Vec::<u8>::new()).unwrap(),
+            ]
+        },
+    );
+}
+
+fn follow(
+    cluster: &mut FullCluster,
+    social: &SocialObjects,
+    sender: MySoAddress,
+    kp: &AccountKeyPair,
+    address: MySoAddress,
+) {
+    call(cluster, sender, kp, "social_graph", "follow", |builder| {
+        vec![
+            builder.obj(social.social_graph).unwrap(),
+            builder.pure(address).unwrap(),
+        ]
+    });
+}
+
+/// Run a transaction from `sender` that calls `module::function` in the social protocol package
+/// with the arguments that `args` adds to the transaction, and check that it succeeded. Each call
+/// is paid for with a fresh gas coin from the faucet.
+fn call(
+    cluster: &mut FullCluster,
+    sender: MySoAddress,
+    kp: &AccountKeyPair,
+    module: &str,
+    function: &str,
+    args: impl FnOnce(&mut ProgrammableTransactionBuilder) -> Vec<Argument>,
+) {
+    let fx = cluster.request_gas(sender, DEFAULT_GAS_BUDGET).unwrap();
+    let gas = find::address_owned_by(&fx, sender).expect("Failed to find gas object");
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let args = args(&mut builder);
+    builder.programmable_move_call(
+        MYSO_SOCIAL_PACKAGE_ID,
+        Identifier::new(module).unwrap(),
+        Identifier::new(function).unwrap(),
+        vec![],
+        args,
+    );
+
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        builder.finish(),
+        DEFAULT_GAS_BUDGET,
+        cluster.reference_gas_price(),
+    );
+
+    let (fx, err) = cluster
+        .execute_transaction(Transaction::from_data_and_signer(data, vec![kp]))
+        .expect("Failed to execute transaction");
+
+    assert!(fx.status().is_ok(), "{module}::{function} failed: {err:?}");
+}
+
+/// Find the only live object of type `type_` through GraphQL, and return it as a mutable shared
+/// object argument.
+async fn shared_object(cluster: &FullCluster, type_: &str) -> anyhow::Result<ObjectArg> {
+    let response = graphql(cluster, SHARED_QUERY, json!({ "type": type_ })).await?;
+    let nodes = response
+        .pointer("/data/objects/nodes")
+        .and_then(Value::as_array)
+        .context("Failed to find objects in response")?;
+
+    let [node] = nodes.as_slice() else {
+        bail!("Expected one {type_}, found {}", nodes.len());
+    };
+
+    let id = node["address"].as_str().context("Missing address")?;
+    let version = node
+        .pointer("/owner/initialSharedVersion")
+        .and_then(Value::as_u64)
+        .with_context(|| format!("{type_} is not shared"))?;
+
+    Ok(ObjectArg::SharedObject {
+        id: ObjectID::from_str(id)?,
+        initial_shared_version: SequenceNumber::from_u64(version),
+        mutability: SharedObjectMutability::Mutable,
+    })
+}
+
+async fn query_social(
+    cluster: &FullCluster,
+    address: MySoAddress,
+    first: Option<u64>,
+    after: Option<String>,
+) -> anyhow::Result<Social> {
+    let response = graphql(
+        cluster,
+        SOCIAL_QUERY,
+        json!({
+            "address": address.to_string(),
+            "first": first,
+            "after": after,
+        }),
+    )
+    .await?;
+
+    let address = response
+        .pointer("/data/address")
+        .context("Failed to find address in response")?;
+
+    Ok(serde_json::from_value(address.clone())?)
+}
+
+async fn graphql(cluster: &FullCluster, query: &str, variables: Value) -> anyhow::Result<Value> {
+    let response: Value = reqwest::Client::new()
+        .post(cluster.graphql_url().as_str())
+        .json(&json!({
+            "query": query,
+            "variables": variables,
+        }))
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    if let Some(errors) = response.get("errors") {
+        bail!("GraphQL errors: {errors}");
+    }
+
+    Ok(response)
+}
@@ -7,6 +7,8 @@ use std::net::Ipv4Addr;
 use std::net::SocketAddr;
 
 use anyhow::Context;
+use fastcrypto::encoding::Base64;
+use fastcrypto::encoding::Encoding;
 use myso_futures::service::Service;
 use myso_indexer_alt_jsonrpc::NodeArgs;
 use myso_indexer_alt_jsonrpc::RpcArgs;
@@ -117,6 +119,23 @@ impl FnDelegationTestCluster {
         Ok((tx_digest, tx_bytes, sigs))
     }
 
+    /// Builds a simple transaction and returns its sender, and its kind as Base64-encoded BCS bytes,
+    /// to be used for dev-inspect.
+    async fn transfer_transaction_kind(&self) -> anyhow::Result<(MySoAddress, String)> {
+        let addresses = self.onchain_cluster.wallet.get_addresses();
+
+        let recipient = addresses[1];
+        let tx = self
+            .onchain_cluster
+            .test_transaction_builder()
+            .await
+            .transfer_myso(Some(1_000), recipient)
+            .build();
+        let kind = Base64::encode(bcs::to_bytes(tx.kind())?);
+
+        Ok((tx.sender(), kind))
+    }
+
     /// Builds a transaction that would abort if called by a normal user.
     async fn privileged_transaction(&self) -> anyhow::Result<(String, String, Vec<String>)> {
         let tx: myso_types::transaction::TransactionData = self
@@ -387,6 +406,60 @@ async fn test_dry_run_with_invalid_tx() {
     );
 }
 
+#[sim_test]
+async fn test_dev_inspect() {
+    let test_cluster = FnDelegationTestCluster::new()
+        .await
+        .expect("Failed to create test cluster");
+
+    let (sender, tx_kind) = test_cluster.transfer_transaction_kind().await.unwrap();
+
+    let response = test_cluster
+        .execute_jsonrpc(
+            "myso_devInspectTransactionBlock".to_string(),
+            json!({
+                "sender_address": sender,
+                "tx_bytes": tx_kind,
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response["result"]["effects"]["status"]["status"], "success");
+    assert!(response["result"]["results"].is_array());
+}
+
+#[sim_test]
+async fn test_dev_inspect_gas_budget_too_high() {
+    let test_cluster = FnDelegationTestCluster::new()
+        .await
+        .expect("Failed to create test cluster");
+
+    let (sender, tx_kind) = test_cluster.transfer_transaction_kind().await.unwrap();
+
+    let response = test_cluster
+        .execute_jsonrpc(
+            "myso_devInspectTransactionBlock".to_string(),
+            json!({
+                "sender_address": sender,
+                "tx_bytes": tx_kind,
+                "additional_args": {
+                    "gasBudget": u64::MAX.to_string(),
+                },
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response["error"]["code"], -32602);
+    assert!(
+        response["error"]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid Params: Gas budget")
+    );
+}
+
 #[sim_test]
 async fn test_get_stakes_and_by_ids() {
     let test_cluster = FnDelegationTestCluster::new()
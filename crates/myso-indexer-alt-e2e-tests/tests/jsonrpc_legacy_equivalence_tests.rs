@@ -0,0 +1,336 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that read methods served by the indexer-backed JSON-RPC server return the same responses
+//! as the fullnode's JSON-RPC server, for the same chain.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::Context;
+use myso_indexer_alt_e2e_tests::OffchainCluster;
+use myso_indexer_alt_e2e_tests::OffchainClusterConfig;
+use myso_indexer_alt_framework::ingestion::ClientArgs;
+use myso_indexer_alt_framework::ingestion::ingestion_client::IngestionClientArgs;
+use myso_macros::sim_test;
+use myso_swarm_config::genesis_config::AccountConfig;
+use prometheus::Registry;
+use reqwest::Client;
+use serde_json::Value;
+use serde_json::json;
+use tempfile::TempDir;
+use test_cluster::TestCluster;
+use test_cluster::TestClusterBuilder;
+use url::Url;
+
+struct EquivalenceTestCluster {
+    onchain: TestCluster,
+    offchain: OffchainCluster,
+    client: Client,
+    #[allow(unused)]
+    temp_dir: TempDir,
+}
+
+impl EquivalenceTestCluster {
+    /// Creates a validator cluster with a fullnode, and an off-chain cluster that indexes the
+    /// checkpoints that the fullnode produces.
+    async fn new() -> anyhow::Result<Self> {
+        let temp_dir = TempDir::new()?;
+        let ingestion_dir = temp_dir.path().to_path_buf();
+
+        let onchain = TestClusterBuilder::new()
+            .with_num_validators(1)
+            .with_data_ingestion_dir(ingestion_dir.clone())
+            .with_epoch_duration_ms(300_000) // 5 minutes
+            .with_accounts(vec![
+                AccountConfig {
+                    address: None,
+                    gas_amounts: vec![1_000_000_000_000; 2],
+                };
+                4
+            ])
+            .build()
+            .await;
+
+        let offchain = OffchainCluster::new(
+            ClientArgs {
+                ingestion: IngestionClientArgs {
+                    local_ingestion_path: Some(ingestion_dir),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            OffchainClusterConfig::default(),
+            &Registry::new(),
+        )
+        .await?;
+
+        Ok(Self {
+            onchain,
+            offchain,
+            client: Client::new(),
+            temp_dir,
+        })
+    }
+
+    /// Transfers some MYSO between two accounts, and returns the digest of the transaction.
+    async fn transfer(&self) -> String {
+        let recipient = self.onchain.get_addresses()[1];
+        let tx = self
+            .onchain
+            .test_transaction_builder()
+            .await
+            .transfer_myso(Some(1_000), recipient)
+            .build();
+
+        let digest = tx.digest().to_string();
+        self.onchain.sign_and_execute_transaction(&tx).await;
+        digest
+    }
+
+    /// Waits for the indexer and the consistent store to catch up with the fullnode's latest
+    /// checkpoint.
+    async fn wait_for_offchain(&self) -> anyhow::Result<()> {
+        let latest = call(
+            &self.client,
+            Url::parse(self.onchain.rpc_url())?,
+            "myso_getLatestCheckpointSequenceNumber",
+            json!([]),
+        )
+        .await?;
+
+        let latest: u64 = latest["result"]
+            .as_str()
+            .context("Missing latest checkpoint")?
+            .parse()?;
+
+        self.offchain
+            .wait_for_indexer(latest, Duration::from_secs(30))
+            .await
+            .context("Timed out waiting for indexer")?;
+
+        self.offchain
+            .wait_for_consistent_store(latest, Duration::from_secs(30))
+            .await
+            .context("Timed out waiting for consistent store")?;
+
+        Ok(())
+    }
+
+    /// Send the same request to the fullnode and the off-chain RPC, and return both results (in
+    /// that order).
+    async fn call_both(&self, method: &str, params: Value) -> anyhow::Result<(Value, Value)> {
+        let legacy = call(
+            &self.client,
+            Url::parse(self.onchain.rpc_url())?,
+            method,
+            params.clone(),
+        )
+        .await?;
+
+        let alt = call(&self.client, self.offchain.jsonrpc_url(), method, params).await?;
+
+        Ok((legacy["result"].clone(), alt["result"].clone()))
+    }
+
+    /// Assert that both RPCs return the same, non-null, result for the request.
+    async fn assert_equivalent(&self, method: &str, params: Value) {
+        let (legacy, alt) = self.call_both(method, params).await.unwrap();
+        assert!(!legacy.is_null(), "{method}: fullnode returned no result");
+        assert_eq!(legacy, alt, "{method}: responses differ");
+    }
+}
+
+async fn call(client: &Client, url: Url, method: &str, params: Value) -> anyhow::Result<Value> {
+    let query = json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+        "id": 1
+    });
+
+    client
+        .post(url)
+        .json(&query)
+        .send()
+        .await
+        .context("Request to JSON-RPC server failed")?
+        .json()
+        .await
+        .context("Failed to parse JSON-RPC response")
+}
+
+#[sim_test]
+async fn test_multi_get_transaction_blocks() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = EquivalenceTestCluster::new().await.unwrap();
+
+    let d0 = cluster.transfer().await;
+    let d1 = cluster.transfer().await;
+    cluster.wait_for_offchain().await.unwrap();
+
+    cluster
+        .assert_equivalent(
+            "myso_multiGetTransactionBlocks",
+            json!([
+                [d0, d1],
+                {
+                    "showInput": true,
+                    "showRawInput": true,
+                    "showEffects": true,
+                    "showEvents": true,
+                    "showBalanceChanges": true,
+                }
+            ]),
+        )
+        .await;
+}
+
+#[sim_test]
+async fn test_get_checkpoints() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = EquivalenceTestCluster::new().await.unwrap();
+
+    cluster.transfer().await;
+    cluster.wait_for_offchain().await.unwrap();
+
+    cluster
+        .assert_equivalent("myso_getCheckpoints", json!([null, 2, false]))
+        .await;
+    cluster
+        .assert_equivalent("myso_getCheckpoints", json!(["1", 1, false]))
+        .await;
+}
+
+#[sim_test]
+async fn test_get_all_coins() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = EquivalenceTestCluster::new().await.unwrap();
+
+    cluster.transfer().await;
+    cluster.wait_for_offchain().await.unwrap();
+
+    // Both RPCs order coins of the same type by descending balance and then by ID, but they order
+    // coin types differently: the fullnode by the coin type's string representation, and the
+    // off-chain RPC by the serialized type. Coins are compared in order within each coin type.
+    let owner = cluster.onchain.get_addresses()[0];
+    let (legacy, alt) = cluster
+        .call_both("mysox_getAllCoins", json!([owner, null, 50]))
+        .await
+        .unwrap();
+
+    let coins = |page: &Value| -> BTreeMap<String, Vec<String>> {
+        let mut coins: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for coin in page["data"].as_array().expect("Missing coins") {
+            coins
+                .entry(coin["coinType"].as_str().unwrap().to_owned())
+                .or_default()
+                .push(coin["coinObjectId"].as_str().unwrap().to_owned());
+        }
+        coins
+    };
+
+    assert!(!coins(&legacy).is_empty());
+    assert_eq!(coins(&legacy), coins(&alt));
+    assert_eq!(legacy["hasNextPage"], alt["hasNextPage"]);
+}
+
+#[sim_test]
+async fn test_get_epochs() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = EquivalenceTestCluster::new().await.unwrap();
+
+    cluster.transfer().await;
+    cluster.onchain.trigger_reconfiguration().await;
+    cluster.transfer().await;
+    cluster.wait_for_offchain().await.unwrap();
+
+    cluster
+        .assert_equivalent("mysox_getEpochs", json!([null, 10, false]))
+        .await;
+    cluster
+        .assert_equivalent("mysox_getEpochs", json!([null, 1, true]))
+        .await;
+    cluster
+        .assert_equivalent("mysox_getEpochs", json!(["0", 1, false]))
+        .await;
+}
+
+#[sim_test]
+async fn test_get_current_epoch() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = EquivalenceTestCluster::new().await.unwrap();
+
+    cluster.transfer().await;
+    cluster.wait_for_offchain().await.unwrap();
+    cluster
+        .assert_equivalent("mysox_getCurrentEpoch", json!([]))
+        .await;
+
+    cluster.onchain.trigger_reconfiguration().await;
+    cluster.transfer().await;
+    cluster.wait_for_offchain().await.unwrap();
+    cluster
+        .assert_equivalent("mysox_getCurrentEpoch", json!([]))
+        .await;
+}
+
+#[sim_test]
+async fn test_get_total_supply() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = EquivalenceTestCluster::new().await.unwrap();
+    cluster.wait_for_offchain().await.unwrap();
+
+    cluster
+        .assert_equivalent("mysox_getTotalSupply", json!(["0x2::myso::MYSO"]))
+        .await;
+}
+
+#[sim_test]
+async fn test_get_committee_info() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = EquivalenceTestCluster::new().await.unwrap();
+    cluster.wait_for_offchain().await.unwrap();
+
+    cluster
+        .assert_equivalent("mysox_getCommitteeInfo", json!([]))
+        .await;
+    cluster
+        .assert_equivalent("mysox_getCommitteeInfo", json!(["0"]))
+        .await;
+}
+
+#[sim_test]
+async fn test_get_protocol_config() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = EquivalenceTestCluster::new().await.unwrap();
+    cluster.wait_for_offchain().await.unwrap();
+
+    cluster
+        .assert_equivalent("myso_getProtocolConfig", json!([]))
+        .await;
+}
+
+#[sim_test]
+async fn test_normalized_move() {
+    telemetry_subscribers::init_for_testing();
+    let cluster = EquivalenceTestCluster::new().await.unwrap();
+    cluster.wait_for_offchain().await.unwrap();
+
+    cluster
+        .assert_equivalent("myso_getNormalizedMoveModule", json!(["0x2", "coin"]))
+        .await;
+    cluster
+        .assert_equivalent(
+            "myso_getNormalizedMoveStruct",
+            json!(["0x2", "coin", "Coin"]),
+        )
+        .await;
+    cluster
+        .assert_equivalent(
+            "myso_getMoveFunctionArgTypes",
+            json!(["0x2", "coin", "split"]),
+        )
+        .await;
+}
@@ -0,0 +1,719 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Backfill coordination for concurrent pipelines.
+//!
+//! A pipeline that is added to an indexer that has been running for a while needs to index
+//! everything from the indexer's first checkpoint up to the point the rest of the indexer has
+//! reached. Rather than ingesting that range serially, the coordinator:
+//!
+//! - Pins the range to backfill, in a `{pipeline}{DELIMITER}backfill` watermark, so that restarts
+//!   backfill the same range, even if they are started with a different `--first-checkpoint`.
+//! - Splits the range into shards, and runs each shard as a tasked pipeline
+//!   (`{pipeline}{DELIMITER}backfill-{lo}-{hi}`) on its own indexer, ingesting from the checkpoint
+//!   store. Each shard tracks its progress in its own watermark, so a restarted backfill skips
+//!   shards that have finished and resumes the rest where they left off.
+//! - Once every shard has finished, writes the pipeline's main watermark at the end of the range
+//!   and continues the pipeline from the next checkpoint, so that there is no gap or overlap
+//!   between backfilled and live data.
+//!
+//! The live tip is ingested once, by the indexer that the pipeline was added to. While the shards
+//! run, the checkpoints it sends the backfilled pipeline are dropped (so that they do not hold up
+//! its other pipelines). After the handover, only the dropped checkpoints are fetched again, and
+//! the pipeline then continues on the indexer's ingestion.
+//!
+//! Shards and the live pipeline report to the metrics of the indexer the pipeline was added to.
+
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::ensure;
+use tokio::sync::mpsc;
+use tracing::info;
+
+use crate::Indexer;
+use crate::IndexerArgs;
+use crate::TaskArgs;
+use crate::ingestion::ClientArgs;
+use crate::ingestion::IngestionConfig;
+use crate::ingestion::IngestionService;
+use crate::metrics::IndexerMetrics;
+use crate::metrics::IngestionMetrics;
+use crate::pipeline::concurrent::ConcurrentConfig;
+use crate::pipeline::concurrent::{self};
+use crate::service::Service;
+use crate::store::CommitterWatermark;
+use crate::store::Connection;
+use crate::store::Store;
+use crate::store::pipeline_task;
+use crate::types::full_checkpoint_content::Checkpoint;
+
+/// How often shards refetch the main pipeline's reader watermark. The main pipeline is not written
+/// to until the backfill finishes, so this only needs to be frequent enough to notice it exists.
+const SHARD_READER_INTERVAL_MS: u64 = 60_000;
+
+/// Command-line arguments for backfilling pipelines.
+#[derive(clap::Args, Default, Debug, Clone)]
+pub struct BackfillArgs {
+    /// Backfill pipelines added with `concurrent_pipeline_with_backfill` that have no watermark
+    /// yet, splitting the range to backfill into this many shards that are ingested in parallel.
+    /// The range starts at `--first-checkpoint`.
+    ///
+    /// Shards ingest from the checkpoint store, so a store ingestion source is required.
+    #[arg(long)]
+    pub backfill_shards: Option<usize>,
+
+    /// The checkpoint to finish backfilling at (inclusive), after which pipelines continue from
+    /// the live tip. Defaults to the checkpoint before the earliest next checkpoint of the
+    /// indexer's other pipelines.
+    #[arg(long, requires = "backfill_shards")]
+    pub backfill_last_checkpoint: Option<u64>,
+}
+
+/// The ingestion configuration and metrics that the coordinator creates its shard indexers with.
+#[derive(Clone)]
+pub(crate) struct BackfillContext {
+    pub client_args: ClientArgs,
+    pub ingestion_config: IngestionConfig,
+    pub metrics: Arc<IndexerMetrics>,
+    pub ingestion_metrics: Arc<IngestionMetrics>,
+}
+
+impl<S: Store> Indexer<S> {
+    /// Adds a concurrent pipeline that is backfilled before it joins the live tip, if the indexer
+    /// was configured with `--backfill-shards` and the pipeline does not have a watermark yet.
+    /// Otherwise this behaves like [Self::concurrent_pipeline].
+    ///
+    /// `make_handler` is called once per shard, and once more for the live pipeline.
+    ///
+    /// Unless `--backfill-last-checkpoint` is set, the backfill ends where the indexer's other
+    /// pipelines will resume from, so they must be added before this pipeline.
+    pub async fn concurrent_pipeline_with_backfill<H>(
+        &mut self,
+        make_handler: impl Fn() -> H + Send + Sync + 'static,
+        config: ConcurrentConfig,
+    ) -> Result<()>
+    where
+        H: concurrent::Handler<Store = S> + Send + Sync + 'static,
+    {
+        let enabled = self
+            .enabled_pipelines
+            .as_ref()
+            .is_none_or(|e| e.contains(H::NAME));
+
+        let Some(shards) = self.backfill.backfill_shards.filter(|_| enabled) else {
+            return self.concurrent_pipeline(make_handler(), config).await;
+        };
+
+        ensure!(shards > 0, "--backfill-shards must be positive");
+        ensure!(
+            self.task.is_none(),
+            "Pipelines cannot be backfilled from a tasked indexer"
+        );
+
+        let mut conn = self
+            .store
+            .connect()
+            .await
+            .context("Failed to establish connection to store")?;
+
+        if conn.committer_watermark(H::NAME).await?.is_some() {
+            info!(pipeline = H::NAME, "Already backfilled, joining live tip");
+            drop(conn);
+            return self.concurrent_pipeline(make_handler(), config).await;
+        }
+
+        let last = match self.backfill.backfill_last_checkpoint {
+            Some(last) => last,
+            None => self
+                .initial_commit_hi()
+                .context(
+                    "No other pipelines to backfill up to, set --backfill-last-checkpoint or add \
+                    this pipeline after the others",
+                )?
+                .checked_sub(1)
+                .context("Other pipelines have not started, nothing to backfill")?,
+        };
+
+        // Pin the range on the first attempt, so that restarts (during which the rest of the
+        // indexer has made progress, or that are configured differently) resume the same shards.
+        let plan = pipeline_task::<S>(H::NAME, Some("backfill"))?;
+        let (first, last) = pin_range(&mut conn, &plan, self.default_next_checkpoint, last)
+            .await
+            .with_context(|| format!("Failed to pin backfill range for {plan}"))?
+            .into_inner();
+        drop(conn);
+
+        if first > last {
+            info!(pipeline = H::NAME, first, last, "Nothing to backfill");
+            return self.concurrent_pipeline(make_handler(), config).await;
+        }
+
+        let ingestion = &self.backfill_context.client_args.ingestion;
+        ensure!(
+            ingestion.remote_store_url.is_some()
+                || ingestion.remote_store_s3.is_some()
+                || ingestion.remote_store_gcs.is_some()
+                || ingestion.remote_store_azure.is_some()
+                || ingestion.local_ingestion_path.is_some(),
+            "Backfills ingest from the checkpoint store, but no store source is configured",
+        );
+
+        ensure!(
+            self.added_pipelines.insert(H::NAME),
+            "Pipeline {:?} already added",
+            H::NAME,
+        );
+
+        if let Some(enabled_pipelines) = &mut self.enabled_pipelines {
+            enabled_pipelines.remove(H::NAME);
+        }
+
+        // The pipeline joins the indexer's ingestion after the backfill, so ingestion needs to
+        // start early enough to cover it.
+        self.first_ingestion_checkpoint = (last + 1).min(self.first_ingestion_checkpoint);
+
+        let (checkpoint_rx, commit_hi_tx) = self.ingestion_service.subscribe();
+        self.pipelines.push(backfill::<H, S>(
+            shard_ranges(first..=last, shards),
+            make_handler,
+            config,
+            self.store.clone(),
+            self.backfill_context.clone(),
+            checkpoint_rx,
+            commit_hi_tx,
+        ));
+
+        Ok(())
+    }
+}
+
+/// Read the range pinned in the backfill's `plan` watermark, or pin `first..=last` if this is the
+/// first attempt. The plan watermark is never committed to by a pipeline, so it records the last
+/// checkpoint of the range as its `checkpoint_hi_inclusive`, and the first as its `tx_hi`.
+async fn pin_range<C: Connection>(
+    conn: &mut C,
+    plan: &str,
+    first: u64,
+    last: u64,
+) -> Result<RangeInclusive<u64>> {
+    if let Some(pinned) = conn.committer_watermark(plan).await? {
+        return Ok(pinned.tx_hi..=pinned.checkpoint_hi_inclusive);
+    }
+
+    conn.set_committer_watermark(
+        plan,
+        CommitterWatermark {
+            checkpoint_hi_inclusive: last,
+            tx_hi: first,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    Ok(first..=last)
+}
+
+/// Split `range` into at most `shards` contiguous, non-empty ranges of near-equal size.
+pub(crate) fn shard_ranges(range: RangeInclusive<u64>, shards: usize) -> Vec<RangeInclusive<u64>> {
+    let (lo, hi) = range.into_inner();
+    if lo > hi || shards == 0 {
+        return vec![];
+    }
+
+    let len = hi - lo + 1;
+    let shards = (shards as u64).min(len);
+    let (size, rem) = (len / shards, len % shards);
+
+    let mut ranges = Vec::with_capacity(shards as usize);
+    let mut start = lo;
+    for i in 0..shards {
+        let end = start + size - 1 + u64::from(i < rem);
+        ranges.push(start..=end);
+        start = end + 1;
+    }
+
+    ranges
+}
+
+/// Start a service that backfills pipeline `H` by running each of `shards` in parallel, and then
+/// continues the pipeline from the next checkpoint on, using checkpoints from `checkpoint_rx`.
+fn backfill<H, S>(
+    shards: Vec<RangeInclusive<u64>>,
+    make_handler: impl Fn() -> H + Send + Sync + 'static,
+    config: ConcurrentConfig,
+    store: S,
+    context: BackfillContext,
+    mut checkpoint_rx: mpsc::Receiver<Arc<Checkpoint>>,
+    commit_hi_tx: mpsc::UnboundedSender<(&'static str, u64)>,
+) -> Service
+where
+    H: concurrent::Handler<Store = S> + Send + Sync + 'static,
+    S: Store,
+{
+    Service::new().spawn_aborting(async move {
+        let first = *shards.first().context("No shards to backfill")?.start();
+        let last = *shards.last().context("No shards to backfill")?.end();
+        info!(
+            pipeline = H::NAME,
+            first,
+            last,
+            shards = shards.len(),
+            "Starting backfill"
+        );
+
+        let shard_client_args = ClientArgs {
+            ingestion: context.client_args.ingestion.clone(),
+            streaming: Default::default(),
+        };
+
+        let mut running = Service::new();
+        let mut last_task = String::new();
+        for shard in shards {
+            let task = format!("backfill-{}-{}", shard.start(), shard.end());
+            let shard_task = pipeline_task::<S>(H::NAME, Some(&task))?;
+            last_task = shard_task.clone();
+
+            let watermark = store
+                .connect()
+                .await?
+                .committer_watermark(&shard_task)
+                .await?;
+
+            if watermark.is_some_and(|w| w.checkpoint_hi_inclusive >= *shard.end()) {
+                info!(pipeline = H::NAME, task, "Shard already backfilled");
+                continue;
+            }
+
+            // Each shard gets its own ingestion service, reading from the checkpoint store.
+            let mut indexer = Indexer::with_metrics(
+                store.clone(),
+                IndexerArgs {
+                    first_checkpoint: Some(*shard.start()),
+                    last_checkpoint: Some(*shard.end()),
+                    task: TaskArgs::tasked(task, SHARD_READER_INTERVAL_MS),
+                    ..Default::default()
+                },
+                shard_client_args.clone(),
+                context.ingestion_config.clone(),
+                context.metrics.clone(),
+                context.ingestion_metrics.clone(),
+            )?;
+
+            indexer
+                .concurrent_pipeline(make_handler(), config.clone())
+                .await?;
+            running = running.merge(indexer.run().await?);
+        }
+
+        // Drop the checkpoints the indexer's ingestion sends while the shards run, remembering the
+        // highest one, so that everything after the backfill up to it can be fetched again.
+        let mut dropped_hi = last;
+        let mut shards_done = std::pin::pin!(running.join());
+        loop {
+            tokio::select! {
+                result = &mut shards_done => {
+                    result?;
+                    break;
+                }
+
+                Some(checkpoint) = checkpoint_rx.recv() => {
+                    dropped_hi = dropped_hi.max(checkpoint.summary.sequence_number);
+                }
+            }
+        }
+
+        // Hand over to the live tip: the main watermark picks up where the final shard ended.
+        let mut conn = store.connect().await?;
+        let watermark = conn
+            .committer_watermark(&last_task)
+            .await?
+            .with_context(|| format!("No watermark for {last_task} after backfill"))?;
+
+        ensure!(
+            watermark.checkpoint_hi_inclusive >= last,
+            "Backfill of {} stopped at {}, before {last}",
+            H::NAME,
+            watermark.checkpoint_hi_inclusive,
+        );
+
+        conn.set_committer_watermark(
+            H::NAME,
+            CommitterWatermark {
+                checkpoint_hi_inclusive: last,
+                ..watermark
+            },
+        )
+        .await?;
+
+        if first > 0 {
+            conn.set_reader_watermark(H::NAME, first).await?;
+            conn.set_pruner_watermark(H::NAME, first).await?;
+        }
+
+        drop(conn);
+        info!(
+            pipeline = H::NAME,
+            last, dropped_hi, "Backfill complete, joining live tip"
+        );
+
+        // Fetch the checkpoints that were dropped during the backfill again, from the checkpoint
+        // store.
+        let mut service = Service::new();
+        let (_, mut catch_up_rx) = mpsc::channel(1);
+        if dropped_hi > last {
+            let mut ingestion = IngestionService::with_metrics(
+                shard_client_args,
+                context.ingestion_config.clone(),
+                context.ingestion_metrics.clone(),
+            )?;
+
+            (catch_up_rx, _) = ingestion.subscribe();
+            service = ingestion
+                .run(last + 1..=dropped_hi, None)
+                .await
+                .context("Failed to start catch-up ingestion")?;
+        }
+
+        // Feed the pipeline the re-fetched checkpoints, and the checkpoints from the indexer's
+        // ingestion that it did not drop.
+        let (tx, rx) = mpsc::channel(context.ingestion_config.checkpoint_buffer_size);
+        let forward = Service::new().spawn_aborting(async move {
+            let (mut catching_up, mut live) = (true, true);
+            while catching_up || live {
+                let checkpoint = tokio::select! {
+                    biased;
+
+                    checkpoint = catch_up_rx.recv(), if catching_up => {
+                        let Some(checkpoint) = checkpoint else {
+                            catching_up = false;
+                            continue;
+                        };
+                        checkpoint
+                    }
+
+                    checkpoint = checkpoint_rx.recv(), if live => {
+                        let Some(checkpoint) = checkpoint else {
+                            live = false;
+                            continue;
+                        };
+
+                        if checkpoint.summary.sequence_number <= dropped_hi {
+                            continue;
+                        }
+                        checkpoint
+                    }
+                };
+
+                if tx.send(checkpoint).await.is_err() {
+                    break;
+                }
+            }
+
+            Ok(())
+        });
+
+        let pipeline = concurrent::pipeline::<H>(
+            make_handler(),
+            last + 1,
+            config,
+            store,
+            None,
+            rx,
+            commit_hi_tx,
+            context.metrics,
+        );
+
+        service.merge(forward).merge(pipeline).join().await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use myso_synthetic_ingestion::synthetic_ingestion;
+    use prometheus::Registry;
+
+    use crate::FieldCount;
+    use crate::ingestion::ingestion_client::IngestionClientArgs;
+    use crate::mocks::store::MockStore;
+    use crate::mocks::store::MockWatermark;
+    use crate::pipeline::Processor;
+    use crate::pipeline::concurrent::BatchStatus;
+
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Clone, FieldCount)]
+    struct MockValue(u64);
+
+    struct Backfilled;
+
+    struct Live;
+
+    #[async_trait]
+    impl Processor for Backfilled {
+        const NAME: &'static str = "backfilled";
+        type Value = MockValue;
+
+        async fn process(
+            &self,
+            checkpoint: &Arc<myso_types::full_checkpoint_content::Checkpoint>,
+        ) -> anyhow::Result<Vec<Self::Value>> {
+            Ok(vec![MockValue(checkpoint.summary.sequence_number)])
+        }
+    }
+
+    #[async_trait]
+    impl concurrent::Handler for Backfilled {
+        type Store = MockStore;
+        type Batch = Vec<MockValue>;
+
+        fn batch(
+            &self,
+            batch: &mut Self::Batch,
+            values: &mut std::vec::IntoIter<Self::Value>,
+        ) -> BatchStatus {
+            batch.extend(values);
+            BatchStatus::Pending
+        }
+
+        async fn commit<'a>(
+            &self,
+            batch: &Self::Batch,
+            conn: &mut <Self::Store as Store>::Connection<'a>,
+        ) -> anyhow::Result<usize> {
+            for value in batch {
+                conn.0
+                    .commit_data(Self::NAME, value.0, vec![value.0])
+                    .await?;
+            }
+            Ok(batch.len())
+        }
+    }
+
+    #[async_trait]
+    impl Processor for Live {
+        const NAME: &'static str = "live";
+        type Value = MockValue;
+
+        async fn process(
+            &self,
+            checkpoint: &Arc<myso_types::full_checkpoint_content::Checkpoint>,
+        ) -> anyhow::Result<Vec<Self::Value>> {
+            Ok(vec![MockValue(checkpoint.summary.sequence_number)])
+        }
+    }
+
+    #[async_trait]
+    impl concurrent::Handler for Live {
+        type Store = MockStore;
+        type Batch = Vec<MockValue>;
+
+        fn batch(
+            &self,
+            batch: &mut Self::Batch,
+            values: &mut std::vec::IntoIter<Self::Value>,
+        ) -> BatchStatus {
+            batch.extend(values);
+            BatchStatus::Pending
+        }
+
+        async fn commit<'a>(
+            &self,
+            _batch: &Self::Batch,
+            _conn: &mut <Self::Store as Store>::Connection<'a>,
+        ) -> anyhow::Result<usize> {
+            Ok(0)
+        }
+    }
+
+    async fn indexer(store: MockStore, ingestion_dir: &std::path::Path) -> Indexer<MockStore> {
+        Indexer::new(
+            store,
+            IndexerArgs {
+                last_checkpoint: Some(29),
+                backfill: BackfillArgs {
+                    backfill_shards: Some(3),
+                    backfill_last_checkpoint: None,
+                },
+                ..Default::default()
+            },
+            ClientArgs {
+                ingestion: IngestionClientArgs {
+                    local_ingestion_path: Some(ingestion_dir.to_owned()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            IngestionConfig::default(),
+            None,
+            &Registry::new(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_shard_ranges() {
+        assert_eq!(shard_ranges(0..=9, 3), vec![0..=3, 4..=6, 7..=9]);
+        assert_eq!(shard_ranges(5..=6, 4), vec![5..=5, 6..=6]);
+        assert_eq!(shard_ranges(7..=7, 1), vec![7..=7]);
+        assert!(shard_ranges(8..=7, 2).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_hands_over_to_live_tip() {
+        let ingestion_dir = tempfile::tempdir().unwrap();
+        synthetic_ingestion::generate_ingestion(synthetic_ingestion::Config {
+            ingestion_dir: ingestion_dir.path().to_owned(),
+            starting_checkpoint: 0,
+            num_checkpoints: 30,
+            checkpoint_size: 1,
+        })
+        .await;
+
+        // The live pipeline has been running for a while, and has reached checkpoint 19.
+        let store = MockStore::default().with_watermark(
+            Live::NAME,
+            MockWatermark {
+                checkpoint_hi_inclusive: 19,
+                ..Default::default()
+            },
+        );
+
+        let mut indexer = indexer(store.clone(), ingestion_dir.path()).await;
+        indexer
+            .concurrent_pipeline(Live, ConcurrentConfig::default())
+            .await
+            .unwrap();
+        indexer
+            .concurrent_pipeline_with_backfill(|| Backfilled, ConcurrentConfig::default())
+            .await
+            .unwrap();
+
+        indexer.run().await.unwrap().join().await.unwrap();
+
+        // Every checkpoint was committed exactly once, across the backfill and the live tip.
+        let data = store.data.get(Backfilled::NAME).unwrap();
+        let mut committed: Vec<_> = data.iter().map(|e| *e.key()).collect();
+        committed.sort();
+        assert_eq!(committed, (0..30).collect::<Vec<_>>());
+
+        // The backfill range was pinned, and each shard tracked its own progress.
+        let plan = store.watermark("backfilled@backfill").unwrap();
+        assert_eq!((plan.tx_hi, plan.checkpoint_hi_inclusive), (0, 19));
+        for (task, hi) in [
+            ("backfilled@backfill-0-6", 6),
+            ("backfilled@backfill-7-13", 13),
+            ("backfilled@backfill-14-19", 19),
+        ] {
+            assert_eq!(store.watermark(task).unwrap().checkpoint_hi_inclusive, hi);
+        }
+
+        assert_eq!(
+            store
+                .watermark(Backfilled::NAME)
+                .unwrap()
+                .checkpoint_hi_inclusive,
+            29
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backfill_resumes_pinned_range() {
+        let ingestion_dir = tempfile::tempdir().unwrap();
+        synthetic_ingestion::generate_ingestion(synthetic_ingestion::Config {
+            ingestion_dir: ingestion_dir.path().to_owned(),
+            starting_checkpoint: 0,
+            num_checkpoints: 30,
+            checkpoint_size: 1,
+        })
+        .await;
+
+        // An earlier attempt pinned the range 5..=14, and the indexer is restarted with a
+        // different first checkpoint, after the live pipeline has made progress.
+        let store = MockStore::default()
+            .with_watermark(
+                Live::NAME,
+                MockWatermark {
+                    checkpoint_hi_inclusive: 19,
+                    ..Default::default()
+                },
+            )
+            .with_watermark(
+                "backfilled@backfill",
+                MockWatermark {
+                    checkpoint_hi_inclusive: 14,
+                    tx_hi: 5,
+                    ..Default::default()
+                },
+            );
+
+        let mut indexer = indexer(store.clone(), ingestion_dir.path()).await;
+        indexer
+            .concurrent_pipeline(Live, ConcurrentConfig::default())
+            .await
+            .unwrap();
+        indexer
+            .concurrent_pipeline_with_backfill(|| Backfilled, ConcurrentConfig::default())
+            .await
+            .unwrap();
+
+        // The indexer's ingestion starts early enough for the pipeline to join it after the
+        // pinned range.
+        assert_eq!(indexer.initial_commit_hi(), Some(15));
+
+        indexer.run().await.unwrap().join().await.unwrap();
+
+        let data = store.data.get(Backfilled::NAME).unwrap();
+        let mut committed: Vec<_> = data.iter().map(|e| *e.key()).collect();
+        committed.sort();
+        assert_eq!(committed, (5..30).collect::<Vec<_>>());
+
+        for (task, hi) in [
+            ("backfilled@backfill-5-8", 8),
+            ("backfilled@backfill-9-11", 11),
+            ("backfilled@backfill-12-14", 14),
+        ] {
+            assert_eq!(store.watermark(task).unwrap().checkpoint_hi_inclusive, hi);
+        }
+
+        let watermark = store.watermark(Backfilled::NAME).unwrap();
+        assert_eq!(watermark.checkpoint_hi_inclusive, 29);
+        assert_eq!(watermark.reader_lo, 5);
+    }
+
+    #[tokio::test]
+    async fn test_backfilled_pipeline_joins_live_tip() {
+        let ingestion_dir = tempfile::tempdir().unwrap();
+        synthetic_ingestion::generate_ingestion(synthetic_ingestion::Config {
+            ingestion_dir: ingestion_dir.path().to_owned(),
+            starting_checkpoint: 0,
+            num_checkpoints: 30,
+            checkpoint_size: 1,
+        })
+        .await;
+
+        // A pipeline that has already been backfilled is added like any other.
+        let store = MockStore::default().with_watermark(
+            Backfilled::NAME,
+            MockWatermark {
+                checkpoint_hi_inclusive: 24,
+                ..Default::default()
+            },
+        );
+
+        let mut indexer = indexer(store.clone(), ingestion_dir.path()).await;
+        indexer
+            .concurrent_pipeline_with_backfill(|| Backfilled, ConcurrentConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(indexer.initial_commit_hi(), Some(25));
+        assert!(store.watermark("backfilled@backfill").is_none());
+    }
+}
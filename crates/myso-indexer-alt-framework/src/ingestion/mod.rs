@@ -112,7 +112,19 @@ impl IngestionService {
         metrics_prefix: Option<&str>,
         registry: &Registry,
     ) -> Result<Self> {
-        let metrics = IngestionMetrics::new(metrics_prefix, registry);
+        Self::with_metrics(
+            args,
+            config,
+            IngestionMetrics::new(metrics_prefix, registry),
+        )
+    }
+
+    /// Like [Self::new], but reporting to metrics that have already been registered.
+    pub(crate) fn with_metrics(
+        args: ClientArgs,
+        config: IngestionConfig,
+        metrics: Arc<IngestionMetrics>,
+    ) -> Result<Self> {
         let ingestion_client = IngestionClient::new(args.ingestion, metrics.clone())?;
         let streaming_client = args
             .streaming
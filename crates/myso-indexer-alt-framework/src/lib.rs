@@ -21,6 +21,8 @@ use myso_indexer_alt_framework_store_traits::pipeline_task;
 use prometheus::Registry;
 use tracing::info;
 
+use crate::backfill::BackfillArgs;
+use crate::backfill::BackfillContext;
 use crate::metrics::IngestionMetrics;
 use crate::pipeline::Processor;
 use crate::pipeline::concurrent::ConcurrentConfig;
@@ -37,6 +39,7 @@ pub use myso_futures::service;
 pub use myso_indexer_alt_framework_store_traits as store;
 pub use myso_types as types;
 
+pub mod backfill;
 #[cfg(feature = "cluster")]
 pub mod cluster;
 pub mod ingestion;
@@ -44,6 +47,8 @@ pub mod metrics;
 pub mod pipeline;
 #[cfg(feature = "postgres")]
 pub mod postgres;
+#[cfg(any(feature = "sqlite", test))]
+pub mod sqlite;
 
 #[cfg(test)]
 pub mod mocks;
@@ -73,6 +78,10 @@ pub struct IndexerArgs {
     /// Additional configurations for running a tasked indexer.
     #[clap(flatten)]
     pub task: TaskArgs,
+
+    /// Additional configurations for backfilling newly added pipelines.
+    #[clap(flatten)]
+    pub backfill: BackfillArgs,
 }
 
 /// Command-line arguments for configuring a tasked indexer.
@@ -157,6 +166,13 @@ pub struct Indexer<S: Store> {
     /// unless overridden by [Self::default_next_checkpoint].
     first_ingestion_checkpoint: u64,
 
+    /// How pipelines added with [Self::concurrent_pipeline_with_backfill] should be backfilled.
+    backfill: BackfillArgs,
+
+    /// The configuration the indexer was created with, which backfills reuse to create the
+    /// indexers that run their shards.
+    backfill_context: BackfillContext,
+
     /// The service handles for every pipeline, used to manage lifetimes and graceful shutdown.
     pipelines: Vec<Service>,
 }
@@ -207,18 +223,44 @@ impl<S: Store> Indexer<S> {
         ingestion_config: IngestionConfig,
         metrics_prefix: Option<&str>,
         registry: &Registry,
+    ) -> Result<Self> {
+        Self::with_metrics(
+            store,
+            indexer_args,
+            client_args,
+            ingestion_config,
+            IndexerMetrics::new(metrics_prefix, registry),
+            IngestionMetrics::new(metrics_prefix, registry),
+        )
+    }
+
+    /// Like [Self::new], but reporting to metrics that have already been registered, so that the
+    /// indexers a backfill creates report into the same registry as the indexer that created them.
+    pub(crate) fn with_metrics(
+        store: S,
+        indexer_args: IndexerArgs,
+        client_args: ClientArgs,
+        ingestion_config: IngestionConfig,
+        metrics: Arc<IndexerMetrics>,
+        ingestion_metrics: Arc<IngestionMetrics>,
     ) -> Result<Self> {
         let IndexerArgs {
             first_checkpoint,
             last_checkpoint,
             pipeline,
             task,
+            backfill,
         } = indexer_args;
 
-        let metrics = IndexerMetrics::new(metrics_prefix, registry);
+        let backfill_context = BackfillContext {
+            client_args: client_args.clone(),
+            ingestion_config: ingestion_config.clone(),
+            metrics: metrics.clone(),
+            ingestion_metrics: ingestion_metrics.clone(),
+        };
 
         let ingestion_service =
-            IngestionService::new(client_args, ingestion_config, metrics_prefix, registry)?;
+            IngestionService::with_metrics(client_args, ingestion_config, ingestion_metrics)?;
 
         Ok(Self {
             store,
@@ -234,6 +276,8 @@ impl<S: Store> Indexer<S> {
             },
             added_pipelines: BTreeSet::new(),
             first_ingestion_checkpoint: u64::MAX,
+            backfill,
+            backfill_context,
             pipelines: vec![],
         })
     }
@@ -1889,6 +1933,7 @@ mod tests {
             last_checkpoint: Some(15),
             pipeline: vec![],
             task: TaskArgs::tasked("task".to_string(), 10),
+            backfill: Default::default(),
         };
         let temp_dir = tempfile::tempdir().unwrap();
         synthetic_ingestion::generate_ingestion(synthetic_ingestion::Config {
@@ -1979,6 +2024,7 @@ mod tests {
             last_checkpoint: Some(25),
             pipeline: vec![],
             task: TaskArgs::tasked("task".to_string(), 10),
+            backfill: Default::default(),
         };
         let temp_dir = tempfile::tempdir().unwrap();
         synthetic_ingestion::generate_ingestion(synthetic_ingestion::Config {
@@ -2089,6 +2135,7 @@ mod tests {
             last_checkpoint: Some(500),
             pipeline: vec![],
             task: TaskArgs::tasked("task".to_string(), 10 /* reader_interval_ms */),
+            backfill: Default::default(),
         };
         let client_args = ClientArgs {
             ingestion: IngestionClientArgs {
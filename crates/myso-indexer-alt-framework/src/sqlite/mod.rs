@@ -0,0 +1,485 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An embedded SQLite store for the indexer framework.
+//!
+//! This store lets custom indexers run in tests and on small deployments without a Postgres
+//! server. SQLite only supports a single writer at a time, so the store wraps a single connection
+//! behind an async mutex: every [`Connection`] handed out by the store holds that lock until it is
+//! dropped. Both concurrent and sequential pipelines are supported, but concurrent pipelines
+//! commit one batch at a time.
+//!
+//! Handlers write to the store through the `rusqlite::Connection` that [`Connection`] derefs to.
+
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use prometheus::Registry;
+use rusqlite::params;
+use tokio::sync::Mutex;
+use tokio::sync::MutexGuard;
+use tracing::info;
+
+use crate::Indexer;
+use crate::IndexerArgs;
+use crate::ingestion::ClientArgs;
+use crate::ingestion::IngestionConfig;
+
+mod store;
+
+/// How long a connection waits for another process holding the database file's write lock.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The migration that creates the table backing the framework's watermark operations. It is always
+/// applied before any migrations supplied by the indexer.
+const WATERMARKS_MIGRATION: Migration = Migration {
+    name: "0000_watermarks",
+    sql: "CREATE TABLE watermarks (
+        pipeline TEXT PRIMARY KEY,
+        epoch_hi_inclusive INTEGER NOT NULL,
+        checkpoint_hi_inclusive INTEGER NOT NULL,
+        tx_hi INTEGER NOT NULL,
+        timestamp_ms_hi_inclusive INTEGER NOT NULL,
+        reader_lo INTEGER NOT NULL,
+        pruner_timestamp_ms INTEGER NOT NULL,
+        pruner_hi INTEGER NOT NULL
+    )",
+};
+
+/// A store backed by a single SQLite database, either a file or in memory.
+#[derive(Clone)]
+pub struct SqliteStore(Arc<Mutex<rusqlite::Connection>>);
+
+/// Wrapper struct over the locked SQLite connection for dealing with the `Store` trait.
+pub struct Connection<'c>(MutexGuard<'c, rusqlite::Connection>);
+
+/// A named batch of SQL statements that brings the database schema up-to-date for an indexer.
+/// Each migration is applied at most once, in the order it is supplied.
+#[derive(Clone, Copy, Debug)]
+pub struct Migration {
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+impl SqliteStore {
+    /// Open (or create) the database at `path`. The database is put in WAL mode so that readers
+    /// in other processes are not blocked by the indexer's writes.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let conn = rusqlite::Connection::open(path)
+            .with_context(|| format!("Failed to open SQLite database at {}", path.display()))?;
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        Self::new(conn)
+    }
+
+    /// Create a store backed by a private in-memory database, which is discarded when the last
+    /// clone of the store is dropped.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::new(rusqlite::Connection::open_in_memory()?)
+    }
+
+    fn new(conn: rusqlite::Connection) -> Result<Self> {
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.pragma_update(None, "foreign_keys", "ON")?;
+        Ok(Self(Arc::new(Mutex::new(conn))))
+    }
+
+    /// Apply the framework's own migrations followed by `migrations`, skipping any that have
+    /// already been applied. Returns the names of the migrations that were applied.
+    pub async fn run_migrations(&self, migrations: &[Migration]) -> Result<Vec<&'static str>> {
+        let mut conn = self.0.lock().await;
+
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS __migrations (
+                name TEXT PRIMARY KEY,
+                applied_at_ms INTEGER NOT NULL
+            )",
+        )?;
+
+        info!("Running migrations ...");
+        let mut applied = vec![];
+        for migration in std::iter::once(&WATERMARKS_MIGRATION).chain(migrations) {
+            let tx = conn.transaction()?;
+            let exists: bool = tx.query_row(
+                "SELECT EXISTS (SELECT 1 FROM __migrations WHERE name = ?1)",
+                params![migration.name],
+                |row| row.get(0),
+            )?;
+
+            if exists {
+                continue;
+            }
+
+            tx.execute_batch(migration.sql)
+                .with_context(|| format!("Failed to run migration {}", migration.name))?;
+            tx.execute(
+                "INSERT INTO __migrations (name, applied_at_ms) VALUES (?1, ?2)",
+                params![migration.name, chrono::Utc::now().timestamp_millis()],
+            )?;
+            tx.commit()?;
+            applied.push(migration.name);
+        }
+
+        info!("Migrations complete.");
+        Ok(applied)
+    }
+}
+
+impl Deref for Connection<'_> {
+    type Target = rusqlite::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Connection<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// An opinionated indexer implementation that uses an embedded SQLite database as the store.
+impl Indexer<SqliteStore> {
+    /// Create a new instance of the indexer framework, writing to the SQLite database at `path`
+    /// (created if it does not exist). `indexer_args`, `client_args` and `ingestion_config` are as
+    /// for [`Indexer::new`].
+    ///
+    /// `migrations` bring the database schema up-to-date for the specific instance of the indexer,
+    /// and are run as part of initializing the indexer, after the migrations necessary to support
+    /// watermark operations.
+    pub async fn new_from_sqlite(
+        path: impl AsRef<Path>,
+        migrations: &[Migration],
+        indexer_args: IndexerArgs,
+        client_args: ClientArgs,
+        ingestion_config: IngestionConfig,
+        metrics_prefix: Option<&str>,
+        registry: &Registry,
+    ) -> Result<Self> {
+        let store = SqliteStore::open(path)?;
+
+        // At indexer initialization, we ensure that the DB schema is up-to-date.
+        store
+            .run_migrations(migrations)
+            .await
+            .context("Failed to run pending migrations")?;
+
+        Indexer::new(
+            store,
+            indexer_args,
+            client_args,
+            ingestion_config,
+            metrics_prefix,
+            registry,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use myso_indexer_alt_framework_store_traits::CommitterWatermark;
+    use myso_indexer_alt_framework_store_traits::Connection as _;
+    use myso_indexer_alt_framework_store_traits::Store;
+    use myso_indexer_alt_framework_store_traits::TransactionalStore;
+    use myso_synthetic_ingestion::synthetic_ingestion;
+    use myso_types::full_checkpoint_content::Checkpoint;
+    use scoped_futures::ScopedFutureExt;
+
+    use crate::ingestion::ingestion_client::IngestionClientArgs;
+    use crate::pipeline::Processor;
+    use crate::pipeline::concurrent::BatchStatus;
+    use crate::pipeline::concurrent::ConcurrentConfig;
+    use crate::pipeline::concurrent::{self};
+    use crate::pipeline::sequential::SequentialConfig;
+    use crate::pipeline::sequential::{self};
+
+    use super::*;
+
+    const MIGRATIONS: &[Migration] = &[Migration {
+        name: "0001_checkpoints",
+        sql: "CREATE TABLE checkpoints (
+            pipeline TEXT NOT NULL,
+            sequence_number INTEGER NOT NULL,
+            PRIMARY KEY (pipeline, sequence_number)
+        )",
+    }];
+
+    struct StoredCheckpoint(u64);
+
+    /// Writes the sequence number of every checkpoint it sees to the `checkpoints` table.
+    struct CheckpointSeqs;
+
+    struct SeqCheckpointSeqs;
+
+    #[async_trait]
+    impl Processor for CheckpointSeqs {
+        const NAME: &'static str = "concurrent";
+        type Value = StoredCheckpoint;
+
+        async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+            Ok(vec![StoredCheckpoint(checkpoint.summary.sequence_number)])
+        }
+    }
+
+    #[async_trait]
+    impl Processor for SeqCheckpointSeqs {
+        const NAME: &'static str = "sequential";
+        type Value = StoredCheckpoint;
+
+        async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+            Ok(vec![StoredCheckpoint(checkpoint.summary.sequence_number)])
+        }
+    }
+
+    fn insert(conn: &rusqlite::Connection, pipeline: &str, values: &[StoredCheckpoint]) -> usize {
+        let mut stmt = conn
+            .prepare_cached(
+                "INSERT OR IGNORE INTO checkpoints (pipeline, sequence_number) VALUES (?1, ?2)",
+            )
+            .unwrap();
+        values
+            .iter()
+            .map(|v| stmt.execute(params![pipeline, v.0 as i64]).unwrap())
+            .sum()
+    }
+
+    #[async_trait]
+    impl concurrent::Handler for CheckpointSeqs {
+        type Store = SqliteStore;
+        type Batch = Vec<StoredCheckpoint>;
+
+        fn batch(
+            &self,
+            batch: &mut Self::Batch,
+            values: &mut std::vec::IntoIter<Self::Value>,
+        ) -> BatchStatus {
+            batch.extend(values);
+            BatchStatus::Pending
+        }
+
+        async fn commit<'a>(
+            &self,
+            batch: &Self::Batch,
+            conn: &mut Connection<'a>,
+        ) -> Result<usize> {
+            Ok(insert(conn, Self::NAME, batch))
+        }
+    }
+
+    #[async_trait]
+    impl sequential::Handler for SeqCheckpointSeqs {
+        type Store = SqliteStore;
+        type Batch = Vec<StoredCheckpoint>;
+
+        fn batch(&self, batch: &mut Self::Batch, values: std::vec::IntoIter<Self::Value>) {
+            batch.extend(values);
+        }
+
+        async fn commit<'a>(
+            &self,
+            batch: &Self::Batch,
+            conn: &mut Connection<'a>,
+        ) -> Result<usize> {
+            Ok(insert(conn, Self::NAME, batch))
+        }
+    }
+
+    async fn test_store() -> SqliteStore {
+        let store = SqliteStore::open_in_memory().unwrap();
+        store.run_migrations(MIGRATIONS).await.unwrap();
+        store
+    }
+
+    async fn stored_checkpoints(store: &SqliteStore, pipeline: &str) -> Vec<u64> {
+        let conn = store.connect().await.unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT sequence_number FROM checkpoints WHERE pipeline = ?1
+                ORDER BY sequence_number",
+            )
+            .unwrap();
+        stmt.query_map(params![pipeline], |row| row.get::<_, i64>(0))
+            .unwrap()
+            .map(|r| r.unwrap() as u64)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_migrations_are_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("indexer.db");
+
+        let store = SqliteStore::open(&path).unwrap();
+        let applied = store.run_migrations(MIGRATIONS).await.unwrap();
+        assert_eq!(applied, vec!["0000_watermarks", "0001_checkpoints"]);
+        drop(store);
+
+        let store = SqliteStore::open(&path).unwrap();
+        let applied = store.run_migrations(MIGRATIONS).await.unwrap();
+        assert!(applied.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_committer_watermark_only_advances() {
+        let store = test_store().await;
+        let mut conn = store.connect().await.unwrap();
+
+        assert_eq!(conn.committer_watermark("p").await.unwrap(), None);
+        assert!(
+            conn.set_committer_watermark("p", CommitterWatermark::new_for_testing(10))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !conn
+                .set_committer_watermark("p", CommitterWatermark::new_for_testing(5))
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            conn.committer_watermark("p").await.unwrap(),
+            Some(CommitterWatermark::new_for_testing(10))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_watermark() {
+        let store = test_store().await;
+        let mut conn = store.connect().await.unwrap();
+
+        // Starting from genesis does not create a watermark.
+        assert_eq!(conn.init_watermark("a", 0).await.unwrap(), None);
+        assert_eq!(conn.committer_watermark("a").await.unwrap(), None);
+
+        assert_eq!(conn.init_watermark("b", 100).await.unwrap(), Some(99));
+        let reader = conn.reader_watermark("b").await.unwrap().unwrap();
+        assert_eq!(reader.reader_lo, 100);
+
+        // An existing watermark is preserved.
+        conn.set_committer_watermark("b", CommitterWatermark::new_for_testing(150))
+            .await
+            .unwrap();
+        assert_eq!(conn.init_watermark("b", 100).await.unwrap(), Some(150));
+    }
+
+    #[tokio::test]
+    async fn test_reader_and_pruner_watermarks() {
+        let store = test_store().await;
+        let mut conn = store.connect().await.unwrap();
+        conn.init_watermark("p", 10).await.unwrap();
+
+        assert!(conn.set_reader_watermark("p", 20).await.unwrap());
+        assert!(!conn.set_reader_watermark("p", 15).await.unwrap());
+
+        let pruner = conn
+            .pruner_watermark("p", Duration::from_secs(60))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pruner.reader_lo, 20);
+        assert_eq!(pruner.pruner_hi, 10);
+        assert!(pruner.wait_for_ms > 0 && pruner.wait_for_ms <= 60_000);
+
+        assert!(conn.set_pruner_watermark("p", 20).await.unwrap());
+        let pruner = conn
+            .pruner_watermark("p", Duration::ZERO)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pruner.pruner_hi, 20);
+        assert!(pruner.wait_for().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transaction_rolls_back_on_error() {
+        let store = test_store().await;
+
+        let result: Result<()> = store
+            .transaction(|conn| {
+                async move {
+                    conn.set_committer_watermark("p", CommitterWatermark::new_for_testing(10))
+                        .await?;
+                    anyhow::bail!("boom")
+                }
+                .scope_boxed()
+            })
+            .await;
+        assert!(result.is_err());
+
+        let mut conn = store.connect().await.unwrap();
+        assert_eq!(conn.committer_watermark("p").await.unwrap(), None);
+        assert!(conn.is_autocommit());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_and_sequential_pipelines() {
+        let dir = tempfile::tempdir().unwrap();
+        let ingestion_dir = tempfile::tempdir().unwrap();
+        synthetic_ingestion::generate_ingestion(synthetic_ingestion::Config {
+            ingestion_dir: ingestion_dir.path().to_owned(),
+            starting_checkpoint: 0,
+            num_checkpoints: 20,
+            checkpoint_size: 1,
+        })
+        .await;
+
+        let mut indexer = Indexer::new_from_sqlite(
+            dir.path().join("indexer.db"),
+            MIGRATIONS,
+            IndexerArgs {
+                last_checkpoint: Some(19),
+                ..Default::default()
+            },
+            ClientArgs {
+                ingestion: IngestionClientArgs {
+                    local_ingestion_path: Some(ingestion_dir.path().to_owned()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            IngestionConfig::default(),
+            None,
+            &Registry::new(),
+        )
+        .await
+        .unwrap();
+
+        indexer
+            .concurrent_pipeline(CheckpointSeqs, ConcurrentConfig::default())
+            .await
+            .unwrap();
+        indexer
+            .sequential_pipeline(SeqCheckpointSeqs, SequentialConfig::default())
+            .await
+            .unwrap();
+
+        let store = indexer.store().clone();
+        indexer.run().await.unwrap().join().await.unwrap();
+
+        let expected: Vec<u64> = (0..20).collect();
+        assert_eq!(
+            stored_checkpoints(&store, CheckpointSeqs::NAME).await,
+            expected
+        );
+        assert_eq!(
+            stored_checkpoints(&store, SeqCheckpointSeqs::NAME).await,
+            expected
+        );
+
+        let mut conn = store.connect().await.unwrap();
+        for pipeline in [CheckpointSeqs::NAME, SeqCheckpointSeqs::NAME] {
+            let watermark = conn.committer_watermark(pipeline).await.unwrap().unwrap();
+            assert_eq!(watermark.checkpoint_hi_inclusive, 19);
+        }
+    }
+}
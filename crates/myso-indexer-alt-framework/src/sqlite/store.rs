@@ -0,0 +1,235 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use myso_indexer_alt_framework_store_traits as store;
+use rusqlite::OptionalExtension;
+use rusqlite::params;
+use scoped_futures::ScopedBoxFuture;
+
+use crate::sqlite::Connection;
+use crate::sqlite::SqliteStore;
+
+#[async_trait]
+impl store::Connection for Connection<'_> {
+    async fn init_watermark(
+        &mut self,
+        pipeline_task: &str,
+        default_next_checkpoint: u64,
+    ) -> anyhow::Result<Option<u64>> {
+        let Some(checkpoint_hi_inclusive) = default_next_checkpoint.checked_sub(1) else {
+            // Do not create a watermark record with checkpoint_hi_inclusive = -1.
+            return Ok(self
+                .committer_watermark(pipeline_task)
+                .await?
+                .map(|w| w.checkpoint_hi_inclusive));
+        };
+
+        // There may be an existing entry, in which case it is left as is and returned.
+        self.execute(
+            "INSERT INTO watermarks (
+                pipeline,
+                epoch_hi_inclusive,
+                checkpoint_hi_inclusive,
+                tx_hi,
+                timestamp_ms_hi_inclusive,
+                reader_lo,
+                pruner_timestamp_ms,
+                pruner_hi
+            ) VALUES (?1, 0, ?2, 0, 0, ?3, ?4, ?3)
+            ON CONFLICT (pipeline) DO NOTHING",
+            params![
+                pipeline_task,
+                checkpoint_hi_inclusive as i64,
+                default_next_checkpoint as i64,
+                Utc::now().timestamp_millis(),
+            ],
+        )?;
+
+        let checkpoint_hi_inclusive: i64 = self.query_row(
+            "SELECT checkpoint_hi_inclusive FROM watermarks WHERE pipeline = ?1",
+            params![pipeline_task],
+            |row| row.get(0),
+        )?;
+
+        Ok(Some(checkpoint_hi_inclusive as u64))
+    }
+
+    async fn committer_watermark(
+        &mut self,
+        pipeline_task: &str,
+    ) -> anyhow::Result<Option<store::CommitterWatermark>> {
+        Ok(self
+            .query_row(
+                "SELECT
+                    epoch_hi_inclusive,
+                    checkpoint_hi_inclusive,
+                    tx_hi,
+                    timestamp_ms_hi_inclusive
+                FROM watermarks WHERE pipeline = ?1",
+                params![pipeline_task],
+                |row| {
+                    Ok(store::CommitterWatermark {
+                        epoch_hi_inclusive: row.get::<_, i64>(0)? as u64,
+                        checkpoint_hi_inclusive: row.get::<_, i64>(1)? as u64,
+                        tx_hi: row.get::<_, i64>(2)? as u64,
+                        timestamp_ms_hi_inclusive: row.get::<_, i64>(3)? as u64,
+                    })
+                },
+            )
+            .optional()?)
+    }
+
+    async fn reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+    ) -> anyhow::Result<Option<store::ReaderWatermark>> {
+        Ok(self
+            .query_row(
+                "SELECT checkpoint_hi_inclusive, reader_lo FROM watermarks WHERE pipeline = ?1",
+                params![pipeline],
+                |row| {
+                    Ok(store::ReaderWatermark {
+                        checkpoint_hi_inclusive: row.get::<_, i64>(0)? as u64,
+                        reader_lo: row.get::<_, i64>(1)? as u64,
+                    })
+                },
+            )
+            .optional()?)
+    }
+
+    async fn pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        delay: Duration,
+    ) -> anyhow::Result<Option<store::PrunerWatermark>> {
+        // The store is embedded, so the indexer's clock is also the store's clock.
+        //
+        //     |---------- + delay ---------------------|
+        //                             |--- wait_for ---|
+        //     |-----------------------|----------------|
+        //     ^                       ^
+        //     pruner_timestamp        NOW()
+        Ok(self
+            .query_row(
+                "SELECT ?2 + pruner_timestamp_ms - ?3, pruner_hi, reader_lo
+                FROM watermarks WHERE pipeline = ?1",
+                params![
+                    pipeline,
+                    delay.as_millis() as i64,
+                    Utc::now().timestamp_millis(),
+                ],
+                |row| {
+                    Ok(store::PrunerWatermark {
+                        wait_for_ms: row.get(0)?,
+                        pruner_hi: row.get::<_, i64>(1)? as u64,
+                        reader_lo: row.get::<_, i64>(2)? as u64,
+                    })
+                },
+            )
+            .optional()?)
+    }
+
+    async fn set_committer_watermark(
+        &mut self,
+        pipeline_task: &str,
+        watermark: store::CommitterWatermark,
+    ) -> anyhow::Result<bool> {
+        // There is an existing entry, so only write the new `hi` values, and only if they raise
+        // the watermark.
+        Ok(self.execute(
+            "INSERT INTO watermarks (
+                pipeline,
+                epoch_hi_inclusive,
+                checkpoint_hi_inclusive,
+                tx_hi,
+                timestamp_ms_hi_inclusive,
+                reader_lo,
+                pruner_timestamp_ms,
+                pruner_hi
+            ) VALUES (?1, ?2, ?3, ?4, ?5, 0, 0, 0)
+            ON CONFLICT (pipeline) DO UPDATE SET
+                epoch_hi_inclusive = excluded.epoch_hi_inclusive,
+                checkpoint_hi_inclusive = excluded.checkpoint_hi_inclusive,
+                tx_hi = excluded.tx_hi,
+                timestamp_ms_hi_inclusive = excluded.timestamp_ms_hi_inclusive
+            WHERE watermarks.checkpoint_hi_inclusive < excluded.checkpoint_hi_inclusive",
+            params![
+                pipeline_task,
+                watermark.epoch_hi_inclusive as i64,
+                watermark.checkpoint_hi_inclusive as i64,
+                watermark.tx_hi as i64,
+                watermark.timestamp_ms_hi_inclusive as i64,
+            ],
+        )? > 0)
+    }
+
+    async fn set_reader_watermark(
+        &mut self,
+        pipeline: &'static str,
+        reader_lo: u64,
+    ) -> anyhow::Result<bool> {
+        Ok(self.execute(
+            "UPDATE watermarks SET reader_lo = ?2, pruner_timestamp_ms = ?3
+            WHERE pipeline = ?1 AND reader_lo < ?2",
+            params![pipeline, reader_lo as i64, Utc::now().timestamp_millis()],
+        )? > 0)
+    }
+
+    async fn set_pruner_watermark(
+        &mut self,
+        pipeline: &'static str,
+        pruner_hi: u64,
+    ) -> anyhow::Result<bool> {
+        Ok(self.execute(
+            "UPDATE watermarks SET pruner_hi = ?2 WHERE pipeline = ?1",
+            params![pipeline, pruner_hi as i64],
+        )? > 0)
+    }
+}
+
+#[async_trait]
+impl store::Store for SqliteStore {
+    type Connection<'c> = Connection<'c>;
+
+    async fn connect<'c>(&'c self) -> anyhow::Result<Self::Connection<'c>> {
+        Ok(Connection(self.0.lock().await))
+    }
+}
+
+#[async_trait]
+impl store::TransactionalStore for SqliteStore {
+    async fn transaction<'a, R, F>(&self, f: F) -> anyhow::Result<R>
+    where
+        R: Send + 'a,
+        F: Send + 'a,
+        F: for<'r> FnOnce(
+            &'r mut Self::Connection<'_>,
+        ) -> ScopedBoxFuture<'a, 'r, anyhow::Result<R>>,
+    {
+        let mut conn = store::Store::connect(self).await?;
+
+        // A previous transaction whose future was dropped part way through will still be open.
+        if !conn.is_autocommit() {
+            conn.execute_batch("ROLLBACK")?;
+        }
+
+        // `IMMEDIATE` takes the write lock up front, so that the transaction cannot fail part way
+        // through because another process started writing to the same file.
+        conn.execute_batch("BEGIN IMMEDIATE")?;
+        let result = match f(&mut conn).await {
+            Ok(r) => conn.execute_batch("COMMIT").map(|()| r).map_err(Into::into),
+            Err(e) => Err(e),
+        };
+
+        if result.is_err() && !conn.is_autocommit() {
+            conn.execute_batch("ROLLBACK")?;
+        }
+
+        result
+    }
+}
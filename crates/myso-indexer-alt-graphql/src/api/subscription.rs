@@ -0,0 +1,524 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+
+use anyhow::anyhow;
+use async_graphql::Context;
+use async_graphql::Subscription as SubscriptionRoot;
+use async_graphql::connection::Connection;
+use async_graphql::connection::CursorType;
+use async_stream::try_stream;
+use futures::Stream;
+use tokio::time::MissedTickBehavior;
+use tokio::time::interval;
+
+use crate::api::scalars::uint53::UInt53;
+use crate::api::types::available_range::AvailableRangeKey;
+use crate::api::types::checkpoint::Checkpoint;
+use crate::api::types::event::Event;
+use crate::api::types::event::filter::EventFilter;
+use crate::api::types::transaction::Transaction;
+use crate::api::types::transaction::filter::TransactionFilter;
+use crate::api::types::transaction::filter::TransactionFilterValidator as TFValidator;
+use crate::config::WatermarkConfig;
+use crate::error::RpcError;
+use crate::error::bad_user_input;
+use crate::error::upcast;
+use crate::extensions::query_limits::rich;
+use crate::pagination::Page;
+use crate::pagination::PageLimits;
+use crate::pagination::PaginationConfig;
+use crate::scope::Scope;
+use crate::task::watermark::WatermarksLock;
+
+pub(crate) struct Subscription;
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Cannot resume from checkpoint {0}, data is only available from checkpoint {1}")]
+    Pruned(u64, u64),
+}
+
+/// Subscriptions stream data as it is indexed, one checkpoint at a time and in checkpoint order.
+///
+/// By default, a subscription starts with the first checkpoint that is indexed after it was made. Subscriptions can resume from an earlier point by passing the last checkpoint they saw as `afterCheckpoint`, as long as the data for the checkpoints after it has not been pruned.
+///
+/// Entities produced by a subscription are viewed as of the checkpoint that they belong to.
+#[SubscriptionRoot]
+impl Subscription {
+    /// Stream checkpoints as they are indexed.
+    async fn checkpoints(
+        &self,
+        ctx: &Context<'_>,
+        after_checkpoint: Option<UInt53>,
+    ) -> Result<impl Stream<Item = Result<Checkpoint, RpcError>>, RpcError<Error>> {
+        let after_checkpoint = after_checkpoint.map(u64::from);
+        check_available(ctx, after_checkpoint, "checkpoints", vec![]).await?;
+
+        Ok(try_stream! {
+            for await scope in checkpoint_scopes(ctx, after_checkpoint) {
+                let (scope, cp) = scope?;
+                if let Some(checkpoint) = Checkpoint::with_sequence_number(scope, Some(cp)) {
+                    yield checkpoint;
+                }
+            }
+        })
+    }
+
+    /// Stream transactions that match `filter`, as they are indexed.
+    ///
+    /// Transactions are streamed in the order they were executed in. Checkpoint bounds in the filter limit which checkpoints are streamed, and the subscription ends once it has passed the upper bound, if there is one.
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        after_checkpoint: Option<UInt53>,
+        #[graphql(validator(custom = "TFValidator"))] filter: Option<TransactionFilter>,
+    ) -> Result<impl Stream<Item = Result<Transaction, RpcError>>, RpcError<Error>> {
+        let after_checkpoint = after_checkpoint.map(u64::from);
+        let filter = filter.unwrap_or_default();
+        check_available(
+            ctx,
+            after_checkpoint,
+            "transactions",
+            filter.active_filters(),
+        )
+        .await?;
+
+        Ok(try_stream! {
+            for await scope in checkpoint_scopes(ctx, after_checkpoint) {
+                let (scope, cp) = scope?;
+                let bounds = (filter.after_checkpoint, filter.at_checkpoint, filter.before_checkpoint);
+                match in_bounds(cp, bounds) {
+                    Bound::Before => continue,
+                    Bound::After => break,
+                    Bound::In => {}
+                }
+
+                let filter = TransactionFilter {
+                    after_checkpoint: None,
+                    at_checkpoint: Some(cp.into()),
+                    before_checkpoint: None,
+                    ..filter.clone()
+                };
+
+                let limits = page_limits(ctx, "transactions")?;
+                let transactions = drain(limits, |page| {
+                    Transaction::paginate(ctx, scope.clone(), page, filter.clone())
+                })
+                .await?;
+
+                for transaction in transactions {
+                    yield transaction;
+                }
+            }
+        })
+    }
+
+    /// Stream events that match `filter`, as they are indexed.
+    ///
+    /// Events are streamed in the order they were emitted in. Checkpoint bounds in the filter limit which checkpoints are streamed, and the subscription ends once it has passed the upper bound, if there is one.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        after_checkpoint: Option<UInt53>,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event, RpcError>>, RpcError<Error>> {
+        let after_checkpoint = after_checkpoint.map(u64::from);
+        let filter = filter.unwrap_or_default();
+        check_available(ctx, after_checkpoint, "events", filter.active_filters()).await?;
+
+        Ok(try_stream! {
+            for await scope in checkpoint_scopes(ctx, after_checkpoint) {
+                let (scope, cp) = scope?;
+                let bounds = (filter.after_checkpoint, filter.at_checkpoint, filter.before_checkpoint);
+                match in_bounds(cp, bounds) {
+                    Bound::Before => continue,
+                    Bound::After => break,
+                    Bound::In => {}
+                }
+
+                let filter = EventFilter {
+                    after_checkpoint: None,
+                    at_checkpoint: Some(cp.into()),
+                    before_checkpoint: None,
+                    ..filter.clone()
+                };
+
+                let limits = page_limits(ctx, "events")?;
+                let events = drain(limits, |page| {
+                    Event::paginate(ctx, scope.clone(), page, filter.clone())
+                })
+                .await?;
+
+                for event in events {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+/// Where a checkpoint lies relative to the checkpoint bounds of a filter.
+#[derive(Debug, PartialEq, Eq)]
+enum Bound {
+    Before,
+    In,
+    After,
+}
+
+/// Check that the data needed to resume a subscription from the checkpoint after
+/// `after_checkpoint` has not been pruned. `field` and `filters` identify the subscription field,
+/// whose pipelines are the same as the query it shares a name with.
+async fn check_available(
+    ctx: &Context<'_>,
+    after_checkpoint: Option<u64>,
+    field: &str,
+    filters: Vec<String>,
+) -> Result<(), RpcError<Error>> {
+    let Some(after_checkpoint) = after_checkpoint else {
+        return Ok(());
+    };
+
+    let lock: &WatermarksLock = ctx.data()?;
+    let watermarks = lock.read().await.clone();
+
+    let available_range_key = AvailableRangeKey {
+        type_: "Subscription".to_string(),
+        field: Some(field.to_string()),
+        filters: Some(filters),
+    };
+
+    let reader_lo = available_range_key.reader_lo(&watermarks).map_err(upcast)?;
+
+    if after_checkpoint + 1 < reader_lo {
+        return Err(bad_user_input(Error::Pruned(after_checkpoint, reader_lo)));
+    }
+
+    Ok(())
+}
+
+/// Produce a scope for every checkpoint after `after_checkpoint` (or after the latest checkpoint,
+/// if it is not provided), as it becomes available, along with that checkpoint's sequence number.
+///
+/// Each scope is viewed at its checkpoint, and reads in that scope are consistent with the
+/// watermarks at the time the checkpoint became available. The meter for rich queries is reset
+/// before each checkpoint, so that its limit applies to each checkpoint's worth of data, rather
+/// than over the lifetime of the subscription.
+fn checkpoint_scopes<'c>(
+    ctx: &'c Context<'c>,
+    after_checkpoint: Option<u64>,
+) -> impl Stream<Item = Result<(Scope, u64), RpcError>> + 'c {
+    try_stream! {
+        let lock: &WatermarksLock = ctx.data()?;
+        let config: &WatermarkConfig = ctx.data()?;
+
+        let mut next = match after_checkpoint {
+            Some(cp) => cp + 1,
+            None => lock.read().await.high_watermark().checkpoint() + 1,
+        };
+
+        let mut poll = interval(config.watermark_polling_interval);
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            poll.tick().await;
+
+            let watermarks = lock.read().await.clone();
+            let scope = Scope::with_watermarks(ctx, watermarks.clone())?;
+            let hi = watermarks.high_watermark().checkpoint();
+
+            while next <= hi {
+                let scope = scope
+                    .with_checkpoint_viewed_at(ctx, next)
+                    .ok_or_else(|| anyhow!("Checkpoint {next} is not available"))?;
+
+                rich::reset(ctx);
+                yield (scope, next);
+                next += 1;
+            }
+        }
+    }
+}
+
+/// Where checkpoint `cp` lies relative to the (`after`, `at`, `before`) checkpoint bounds of a
+/// filter.
+fn in_bounds(
+    cp: u64,
+    (after, at, before): (Option<UInt53>, Option<UInt53>, Option<UInt53>),
+) -> Bound {
+    let after = after.map(u64::from);
+    let at = at.map(u64::from);
+    let before = before.map(u64::from);
+
+    if after.is_some_and(|a| cp <= a) || at.is_some_and(|a| cp < a) {
+        Bound::Before
+    } else if before.is_some_and(|b| cp >= b) || at.is_some_and(|a| cp > a) {
+        Bound::After
+    } else {
+        Bound::In
+    }
+}
+
+/// The page limits for the `Query` field that corresponds to a subscription field.
+fn page_limits<'c>(ctx: &'c Context<'_>, field: &'static str) -> Result<&'c PageLimits, RpcError> {
+    let pagination: &PaginationConfig = ctx.data()?;
+    Ok(pagination.limits("Query", field))
+}
+
+/// Fetch every node from a paginated query, by fetching pages of the maximum size from the front
+/// until there are no more pages.
+async fn drain<C, N, F, Fut>(limits: &PageLimits, mut fetch: F) -> Result<Vec<N>, RpcError>
+where
+    C: CursorType,
+    F: FnMut(Page<C>) -> Fut,
+    Fut: Future<Output = Result<Connection<String, N>, RpcError>>,
+    N: async_graphql::OutputType,
+{
+    let mut nodes = vec![];
+    let mut after = None;
+
+    loop {
+        let page = Page::from_params(limits, Some(limits.max as u64), after, None, None)?;
+        let conn = fetch(page).await?;
+
+        let last = conn.edges.last().map(|e| e.cursor.clone());
+        nodes.extend(conn.edges.into_iter().map(|e| e.node));
+
+        match last {
+            Some(cursor) if conn.has_next_page => {
+                let cursor = C::decode_cursor(&cursor)
+                    .map_err(|e| anyhow!("Failed to decode cursor {cursor:?}: {e}"))?;
+                after = Some(cursor);
+            }
+            _ => break,
+        }
+    }
+
+    Ok(nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_graphql::EmptyMutation;
+    use async_graphql::Object;
+    use async_graphql::Response;
+    use async_graphql::Schema;
+    use futures::StreamExt;
+    use myso_indexer_alt_reader::package_resolver::DbPackageStore;
+    use myso_indexer_alt_reader::package_resolver::PackageCache;
+    use myso_indexer_alt_reader::pg_reader::PgReader;
+    use prometheus::Registry;
+    use tokio::sync::RwLock;
+    use tokio::time::timeout;
+
+    use crate::api::scalars::cursor::JsonCursor;
+    use crate::config::Limits;
+    use crate::extensions::query_limits::rich::RichQueryMeter;
+    use crate::task::watermark::Watermarks;
+
+    use super::*;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn noop(&self) -> bool {
+            true
+        }
+    }
+
+    struct Root;
+
+    #[SubscriptionRoot]
+    impl Root {
+        /// Stream the checkpoints that `checkpoint_scopes` produces scopes for, spending one rich
+        /// query per checkpoint.
+        async fn scopes(
+            &self,
+            ctx: &Context<'_>,
+            after_checkpoint: Option<UInt53>,
+        ) -> Result<impl Stream<Item = Result<UInt53, RpcError>>, RpcError<Error>> {
+            let after_checkpoint = after_checkpoint.map(u64::from);
+            check_available(ctx, after_checkpoint, "checkpoints", vec![]).await?;
+
+            Ok(try_stream! {
+                for await scope in checkpoint_scopes(ctx, after_checkpoint) {
+                    let (scope, cp) = scope?;
+                    assert_eq!(scope.checkpoint_viewed_at(), Some(cp));
+                    rich::debit::<Infallible>(ctx)?;
+                    yield UInt53::from(cp);
+                }
+            })
+        }
+    }
+
+    /// Watermarks where checkpoints are available from `lo` to `hi`, inclusive.
+    fn watermarks(lo: u64, hi: u64) -> Arc<Watermarks> {
+        Arc::new(Watermarks::for_tests(hi, [("cp_sequence_numbers", lo)]))
+    }
+
+    async fn schema(lock: WatermarksLock) -> Schema<Query, EmptyMutation, Root> {
+        let registry = Registry::new();
+        let pg_reader = PgReader::new(None, None, Default::default(), &registry)
+            .await
+            .unwrap();
+        let pg_loader = Arc::new(pg_reader.as_data_loader());
+
+        Schema::build(Query, EmptyMutation, Root)
+            .extension(RichQueryMeter)
+            .data(lock)
+            .data(WatermarkConfig {
+                watermark_polling_interval: Duration::from_millis(10),
+            })
+            .data(Arc::new(PackageCache::new(DbPackageStore::new(pg_loader))))
+            .data(Limits {
+                max_rich_queries: 1,
+                ..Default::default()
+            })
+            .finish()
+    }
+
+    fn checkpoint(response: Response) -> u64 {
+        assert!(response.is_ok(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        data["scopes"].as_u64().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_scopes_resume() {
+        let lock: WatermarksLock = Arc::new(RwLock::new(watermarks(0, 5)));
+        let schema = schema(lock.clone()).await;
+        let mut stream = schema.execute_stream("subscription { scopes(afterCheckpoint: 2) }");
+
+        // Checkpoints that are already available are produced straight away, and the rich query
+        // limit applies to each checkpoint separately.
+        for cp in 3..=5 {
+            assert_eq!(checkpoint(stream.next().await.unwrap()), cp);
+        }
+
+        // Later checkpoints are produced as the watermark advances.
+        assert!(
+            timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        *lock.write().await = watermarks(0, 7);
+        for cp in 6..=7 {
+            assert_eq!(checkpoint(stream.next().await.unwrap()), cp);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_scopes_from_latest() {
+        let lock: WatermarksLock = Arc::new(RwLock::new(watermarks(0, 5)));
+        let schema = schema(lock.clone()).await;
+        let mut stream = schema.execute_stream("subscription { scopes }");
+
+        // Without a checkpoint to resume from, the stream starts after the latest checkpoint.
+        assert!(
+            timeout(Duration::from_millis(100), stream.next())
+                .await
+                .is_err()
+        );
+
+        *lock.write().await = watermarks(0, 6);
+        assert_eq!(checkpoint(stream.next().await.unwrap()), 6);
+    }
+
+    #[tokio::test]
+    async fn test_check_available() {
+        let lock: WatermarksLock = Arc::new(RwLock::new(watermarks(3, 5)));
+        let schema = schema(lock.clone()).await;
+
+        // Resuming after checkpoint 1 needs checkpoint 2, which has been pruned.
+        let response = schema
+            .execute_stream("subscription { scopes(afterCheckpoint: 1) }")
+            .next()
+            .await
+            .unwrap();
+        assert_eq!(
+            response.errors[0].message,
+            "Cannot resume from checkpoint 1, data is only available from checkpoint 3"
+        );
+
+        // Resuming after checkpoint 2 only needs data from checkpoint 3 onwards.
+        let response = schema
+            .execute_stream("subscription { scopes(afterCheckpoint: 2) }")
+            .next()
+            .await
+            .unwrap();
+        assert_eq!(checkpoint(response), 3);
+
+        // The data that checkpoint subscriptions rely on is not available at all.
+        *lock.write().await = Arc::new(Watermarks::for_tests(5, []));
+        let response = schema
+            .execute_stream("subscription { scopes(afterCheckpoint: 2) }")
+            .next()
+            .await
+            .unwrap();
+        assert!(
+            response.errors[0].message.contains("querying checkpoints"),
+            "{:?}",
+            response.errors
+        );
+    }
+
+    #[test]
+    fn test_in_bounds() {
+        let b = |after: Option<u64>, at: Option<u64>, before: Option<u64>| {
+            (
+                after.map(UInt53::from),
+                at.map(UInt53::from),
+                before.map(UInt53::from),
+            )
+        };
+
+        assert_eq!(in_bounds(0, b(None, None, None)), Bound::In);
+        assert_eq!(in_bounds(u64::MAX, b(None, None, None)), Bound::In);
+
+        assert_eq!(in_bounds(5, b(Some(5), None, None)), Bound::Before);
+        assert_eq!(in_bounds(6, b(Some(5), None, None)), Bound::In);
+
+        assert_eq!(in_bounds(9, b(None, None, Some(10))), Bound::In);
+        assert_eq!(in_bounds(10, b(None, None, Some(10))), Bound::After);
+
+        assert_eq!(in_bounds(6, b(None, Some(7), None)), Bound::Before);
+        assert_eq!(in_bounds(7, b(None, Some(7), None)), Bound::In);
+        assert_eq!(in_bounds(8, b(None, Some(7), None)), Bound::After);
+
+        // Bounds that exclude every checkpoint.
+        assert_eq!(in_bounds(5, b(Some(5), None, Some(6))), Bound::Before);
+        assert_eq!(in_bounds(6, b(Some(5), None, Some(6))), Bound::After);
+    }
+
+    #[tokio::test]
+    async fn test_drain() {
+        let limits = PageLimits { default: 2, max: 3 };
+
+        let mut pages = vec![];
+        let nodes = drain(&limits, |page: Page<JsonCursor<usize>>| {
+            pages.push((page.after().map(|c| **c), page.limit()));
+            async move { page.paginate_indices(8, |i| Ok::<_, RpcError>(i as u64)) }
+        })
+        .await
+        .unwrap();
+
+        // Pages are fetched at the maximum size, each starting after the last one.
+        assert_eq!(nodes, (0..8).collect::<Vec<u64>>());
+        assert_eq!(pages, vec![(None, 3), (Some(2), 3), (Some(5), 3)]);
+
+        let nodes = drain(&limits, |page: Page<JsonCursor<usize>>| async move {
+            page.paginate_indices(0, |i| Ok::<_, RpcError>(i as u64))
+        })
+        .await
+        .unwrap();
+        assert!(nodes.is_empty());
+    }
+}
@@ -33,16 +33,19 @@ use crate::api::types::object::Object;
 use crate::api::types::object::ObjectKey;
 use crate::api::types::object_filter::ObjectFilter;
 use crate::api::types::object_filter::ObjectFilterValidator as OFValidator;
+use crate::api::types::social::profile::Profile;
+use crate::api::types::social::social_graph::CFollow;
+use crate::api::types::social::social_graph::SocialGraph;
 use crate::api::types::transaction::CTransaction;
 use crate::api::types::transaction::Transaction;
 use crate::api::types::transaction::filter::TransactionFilter;
 use crate::api::types::transaction::filter::TransactionFilterValidator as TFValidator;
 use crate::error::RpcError;
 use crate::error::bad_user_input;
+use crate::error::upcast;
 use crate::pagination::Page;
 use crate::pagination::PaginationConfig;
 use crate::scope::Scope;
-use crate::task::watermark::Watermarks;
 
 /// The possible relationship types for a transaction: sent or affected.
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
@@ -297,6 +300,60 @@ impl Address {
         .transpose()
     }
 
+    /// The addresses that follow this address in the social protocol, in the order they started following it.
+    ///
+    /// Returns `null` if the social protocol has not been set up on this network.
+    pub(crate) async fn followers(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<CFollow>,
+        last: Option<u64>,
+        before: Option<CFollow>,
+    ) -> Option<Result<Connection<String, Address>, RpcError<object::Error>>> {
+        async {
+            let pagination: &PaginationConfig = ctx.data()?;
+            let limits = pagination.limits("Address", "followers");
+            let page = Page::from_params(limits, first, after, last, before)?;
+
+            let Some(graph) = SocialGraph::singleton(ctx, self.scope.clone()).await? else {
+                return Ok(None);
+            };
+
+            let followers = graph.followers_of(ctx, self.address, page).await;
+            followers.map(Some).map_err(upcast)
+        }
+        .await
+        .transpose()
+    }
+
+    /// The addresses that this address follows in the social protocol, in the order it started following them.
+    ///
+    /// Returns `null` if the social protocol has not been set up on this network.
+    pub(crate) async fn following(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<CFollow>,
+        last: Option<u64>,
+        before: Option<CFollow>,
+    ) -> Option<Result<Connection<String, Address>, RpcError<object::Error>>> {
+        async {
+            let pagination: &PaginationConfig = ctx.data()?;
+            let limits = pagination.limits("Address", "following");
+            let page = Page::from_params(limits, first, after, last, before)?;
+
+            let Some(graph) = SocialGraph::singleton(ctx, self.scope.clone()).await? else {
+                return Ok(None);
+            };
+
+            let following = graph.followed_by(ctx, self.address, page).await;
+            following.map(Some).map_err(upcast)
+        }
+        .await
+        .transpose()
+    }
+
     /// Access dynamic fields on an object using their types and BCS-encoded names.
     ///
     /// Returns a list of dynamic fields that is guaranteed to be the same length as `keys`. If a dynamic field in `keys` could not be found in the store, its corresponding entry in the result will be `null`.
@@ -392,6 +449,18 @@ impl Address {
         )
     }
 
+    /// The social protocol profile owned by this address.
+    ///
+    /// Returns `null` if this address does not own a profile, or the social protocol has not been set up on this network.
+    pub(crate) async fn profile(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Option<Result<Profile, RpcError<object::Error>>> {
+        Profile::by_owner(ctx, self.scope.clone(), self.address)
+            .await
+            .transpose()
+    }
+
     /// Transactions associated with this address.
     ///
     /// Similar behavior to the `transactions` in Query but supporting the additional `AddressTransactionRelationship` filter, which defaults to `SENT`.
@@ -460,7 +529,7 @@ impl Address {
         let scope = if let Some(v) = key.root_version {
             scope.with_root_version(v.into())
         } else if let Some(cp) = key.at_checkpoint {
-            let watermark: &Arc<Watermarks> = ctx.data()?;
+            let watermark = scope.watermarks(ctx)?;
             if u64::from(cp) > watermark.high_watermark().checkpoint() {
                 return Err(bad_user_input(Error::Future(cp.into())));
             }
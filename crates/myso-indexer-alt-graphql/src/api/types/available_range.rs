@@ -3,7 +3,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::BTreeSet;
-use std::sync::Arc;
 
 use anyhow::anyhow;
 use async_graphql::Context;
@@ -83,7 +82,7 @@ impl AvailableRange {
         available_range_key: AvailableRangeKey,
     ) -> Result<Self, RpcError<Error>> {
         available_range_key.validate(&ctx.schema_env.registry)?;
-        let watermarks: &Arc<Watermarks> = ctx.data()?;
+        let watermarks = scope.watermarks(ctx)?;
         let first = available_range_key.reader_lo(watermarks).map_err(upcast)?;
 
         Ok(Self {
@@ -239,6 +238,10 @@ collect_pipelines! {
     Address.[transactions] => Query.transactions(.., "affectedAddress");
     Address.[balance, balances, multiGetBalances, objects] => IAddressable.*;
     Address.[dynamicField, dynamicFields, dynamicObjectField, multiGetDynamicFields, multiGetDynamicObjectFields] => IMoveObject.*;
+    Address.[followers, following, profile] |pipelines, _filters| {
+        pipelines.insert("consistent".to_string());
+        pipelines.insert("obj_versions".to_string());
+    };
 
     Checkpoint.[transactions] => Query.transactions(.., "atCheckpoint");
 
@@ -374,6 +377,12 @@ collect_pipelines! {
         }
     };
 
+    SocialGraph.[followers, following] |pipelines, _filters| {
+        pipelines.insert("obj_versions".to_string());
+    };
+
+    Subscription.[checkpoints, events, transactions] => Query.*;
+
     TransactionEffects.[balanceChanges] |pipelines, _filters| {
         pipelines.insert("tx_balance_changes".to_string());
         pipelines.insert("tx_digests".to_string());
@@ -2,8 +2,6 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
-
 use anyhow::Context as _;
 use async_graphql::Context;
 use async_graphql::Object;
@@ -37,7 +35,6 @@ use crate::error::RpcError;
 use crate::pagination::Page;
 use crate::pagination::PaginationConfig;
 use crate::scope::Scope;
-use crate::task::watermark::Watermarks;
 
 pub(crate) mod filter;
 
@@ -258,7 +255,7 @@ impl Checkpoint {
         page: Page<CCheckpoint>,
         filter: CheckpointFilter,
     ) -> Result<Connection<String, Checkpoint>, RpcError> {
-        let watermarks: &Arc<Watermarks> = ctx.data()?;
+        let watermarks = scope.watermarks(ctx)?;
         let available_range_key = AvailableRangeKey {
             type_: "Query".to_string(),
             field: Some("checkpoints".to_string()),
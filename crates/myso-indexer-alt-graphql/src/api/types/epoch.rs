@@ -57,7 +57,6 @@ use crate::error::upcast;
 use crate::pagination::Page;
 use crate::pagination::PaginationConfig;
 use crate::scope::Scope;
-use crate::task::watermark::Watermarks;
 
 pub(crate) type CEpoch = JsonCursor<usize>;
 
@@ -408,7 +407,7 @@ impl Epoch {
     /// If the epoch has not finished yet, this number is computed based on the number of transactions at the latest known checkpoint.
     async fn total_transactions(&self, ctx: &Context<'_>) -> Option<Result<UInt53, RpcError>> {
         async {
-            let watermarks: &Arc<Watermarks> = ctx.data()?;
+            let watermarks = self.scope.watermarks(ctx)?;
             let (sequence_numbers, end) = try_join!(self.sequence_numbers(ctx), self.end(ctx))?;
 
             let Some(start) = &sequence_numbers.start else {
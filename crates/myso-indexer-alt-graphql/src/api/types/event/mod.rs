@@ -2,8 +2,6 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
-
 use anyhow::Context as _;
 use async_graphql::Context;
 use async_graphql::Object;
@@ -37,7 +35,6 @@ use crate::error::RpcError;
 use crate::extensions::query_limits;
 use crate::pagination::Page;
 use crate::scope::Scope;
-use crate::task::watermark::Watermarks;
 
 pub(crate) mod filter;
 mod lookups;
@@ -139,7 +136,7 @@ impl Event {
         query_limits::rich::debit(ctx)?;
         let pg_reader: &PgReader = ctx.data()?;
 
-        let watermarks: &Arc<Watermarks> = ctx.data()?;
+        let watermarks = scope.watermarks(ctx)?;
         let available_range_key = AvailableRangeKey {
             type_: "Query".to_string(),
             field: Some("events".to_string()),
@@ -2,8 +2,6 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
-
 use async_graphql::Context;
 use diesel::sql_types::BigInt;
 use myso_pg_db::query::Query;
@@ -14,7 +12,6 @@ use crate::api::types::checkpoint::filter::checkpoint_bounds;
 use crate::error::RpcError;
 use crate::pagination::Page;
 use crate::scope::Scope;
-use crate::task::watermark::Watermarks;
 
 pub(crate) trait CheckpointBounds {
     fn after_checkpoint(&self) -> Option<UInt53>;
@@ -58,7 +55,7 @@ pub(crate) trait CheckpointBounds {
             return Ok(None);
         };
 
-        let watermarks: &Arc<Watermarks> = ctx.data()?;
+        let watermarks = scope.watermarks(ctx)?;
         let global_tx_hi = watermarks.high_watermark().transaction();
 
         let query = query!(
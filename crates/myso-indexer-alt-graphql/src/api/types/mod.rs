@@ -36,6 +36,7 @@ pub(crate) mod owner;
 pub(crate) mod protocol_configs;
 pub(crate) mod service_config;
 pub(crate) mod simulation_result;
+pub(crate) mod social;
 pub(crate) mod stake_subsidy;
 pub(crate) mod transaction;
 pub(crate) mod transaction_effects;
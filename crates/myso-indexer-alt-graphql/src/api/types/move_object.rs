@@ -38,6 +38,10 @@ use crate::api::types::object::VersionFilter;
 use crate::api::types::object_filter::ObjectFilter;
 use crate::api::types::object_filter::ObjectFilterValidator as OFValidator;
 use crate::api::types::owner::Owner;
+use crate::api::types::social::platform::Platform;
+use crate::api::types::social::post::Post;
+use crate::api::types::social::profile::Profile;
+use crate::api::types::social::social_graph::SocialGraph;
 use crate::api::types::transaction::CTransaction;
 use crate::api::types::transaction::Transaction;
 use crate::api::types::transaction::filter::TransactionFilter;
@@ -173,6 +177,32 @@ impl MoveObject {
         DynamicField::from_move_object(self, ctx).await
     }
 
+    /// Attempts to convert the object into a Platform, from the social protocol.
+    pub(crate) async fn as_platform(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<Platform>, RpcError> {
+        Platform::from_move_object(self, ctx).await
+    }
+
+    /// Attempts to convert the object into a Post, from the social protocol.
+    pub(crate) async fn as_post(&self, ctx: &Context<'_>) -> Result<Option<Post>, RpcError> {
+        Post::from_move_object(self, ctx).await
+    }
+
+    /// Attempts to convert the object into a Profile, from the social protocol.
+    pub(crate) async fn as_profile(&self, ctx: &Context<'_>) -> Result<Option<Profile>, RpcError> {
+        Profile::from_move_object(self, ctx).await
+    }
+
+    /// Attempts to convert the object into the SocialGraph, from the social protocol.
+    pub(crate) async fn as_social_graph(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<SocialGraph>, RpcError> {
+        SocialGraph::from_move_object(self, ctx).await
+    }
+
     /// Fetch the total balance for coins with marker type `coinType` (e.g. `0x2::myso::MYSO`), owned by this address.
     ///
     /// If the address does not own any coins of that type, a balance of zero is returned.
@@ -66,7 +66,6 @@ use crate::extensions::query_limits;
 use crate::pagination::Page;
 use crate::pagination::PaginationConfig;
 use crate::scope::Scope;
-use crate::task::watermark::Watermarks;
 
 #[derive(Clone)]
 pub(crate) struct MovePackage {
@@ -627,7 +626,7 @@ impl MovePackage {
                 .map_err(upcast)
         } else if let Some(cp) = key.at_checkpoint {
             // Validate checkpoint isn't in the future
-            let watermark: &Arc<Watermarks> = ctx.data()?;
+            let watermark = scope.watermarks(ctx)?;
             if u64::from(cp) > watermark.high_watermark().checkpoint() {
                 return Err(bad_user_input(Error::Future(cp.into())));
             }
@@ -78,7 +78,6 @@ use crate::pagination::Page;
 use crate::pagination::PageLimits;
 use crate::pagination::PaginationConfig;
 use crate::scope::Scope;
-use crate::task::watermark::Watermarks;
 
 /// Interface implemented by versioned on-chain values that are addressable by an ID (also referred to as its address). This includes Move objects and packages.
 #[allow(clippy::duplicated_attributes)]
@@ -731,7 +730,7 @@ impl Object {
                 .map_err(upcast)
         } else if let Some(cp) = key.at_checkpoint {
             // Validate checkpoint isn't in the future
-            let watermark: &Arc<Watermarks> = ctx.data()?;
+            let watermark = scope.watermarks(ctx)?;
             if u64::from(cp) > watermark.high_watermark().checkpoint() {
                 return Err(bad_user_input(Error::Future(cp.into())));
             }
@@ -0,0 +1,86 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Types that resolve objects from the social protocol (published at `0x50c1`) into typed fields.
+
+use anyhow::Context as _;
+use async_graphql::Context;
+use move_core_types::language_storage::StructTag;
+use myso_types::TypeTag;
+use myso_types::base_types::MySoAddress as NativeAddress;
+use myso_types::base_types::ObjectID;
+use myso_types::dynamic_field::Field;
+use myso_types::dynamic_field::derive_dynamic_field_id;
+use serde::de::DeserializeOwned;
+
+use crate::api::types::object;
+use crate::api::types::object::Object;
+use crate::error::RpcError;
+use crate::error::upcast;
+use crate::scope::Scope;
+
+pub(crate) mod platform;
+pub(crate) mod post;
+pub(crate) mod profile;
+pub(crate) mod social_graph;
+
+/// Load the contents of the live object of type `type_`, for types that only have one instance
+/// (such as the shared registries that the social protocol creates when it is bootstrapped).
+///
+/// Returns `None` if no such object exists, or its contents could not be loaded.
+async fn singleton_contents<T: DeserializeOwned>(
+    ctx: &Context<'_>,
+    scope: Scope,
+    type_: StructTag,
+) -> Result<Option<T>, RpcError<object::Error>> {
+    let Some(object) = Object::singleton(ctx, scope, type_).await? else {
+        return Ok(None);
+    };
+
+    let Some(contents) = object.contents(ctx).await.map_err(upcast)? else {
+        return Ok(None);
+    };
+
+    let move_object = contents
+        .data
+        .try_as_move()
+        .context("Query by type returned a package")?;
+
+    Ok(Some(
+        bcs::from_bytes(move_object.contents()).context("Failed to deserialize singleton")?,
+    ))
+}
+
+/// Look up the value for `key` in the `myso::table::Table<address, V>` with ID `table`, as of the
+/// checkpoint `scope` is viewed at.
+///
+/// Returns `None` if the table does not contain an entry for `key`.
+async fn address_table_entry<V: DeserializeOwned>(
+    ctx: &Context<'_>,
+    scope: Scope,
+    table: ObjectID,
+    key: NativeAddress,
+) -> Result<Option<V>, RpcError> {
+    let key = bcs::to_bytes(&key).context("Failed to serialize table key")?;
+    let field_id = derive_dynamic_field_id(table, &TypeTag::Address, &key)
+        .context("Failed to derive table entry ID")?;
+
+    let Some(object) = Object::latest(ctx, scope, field_id.into()).await? else {
+        return Ok(None);
+    };
+
+    let Some(contents) = object.contents(ctx).await? else {
+        return Ok(None);
+    };
+
+    let move_object = contents
+        .data
+        .try_as_move()
+        .context("Table entry is not a Move object")?;
+
+    let field: Field<NativeAddress, V> =
+        bcs::from_bytes(move_object.contents()).context("Failed to deserialize table entry")?;
+
+    Ok(Some(field.value))
+}
@@ -0,0 +1,246 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use anyhow::anyhow;
+use async_graphql::Context;
+use async_graphql::Enum;
+use async_graphql::Object;
+use move_core_types::language_storage::StructTag;
+use myso_types::base_types::MySoAddress as NativeAddress;
+use myso_types::social::Platform as NativePlatform;
+use tokio::sync::OnceCell;
+
+use crate::api::scalars::big_int::BigInt;
+use crate::api::scalars::myso_address::MySoAddress;
+use crate::api::scalars::uint53::UInt53;
+use crate::api::types::address::Address;
+use crate::api::types::move_object::MoveObject;
+use crate::api::types::object::Object;
+use crate::error::RpcError;
+use crate::scope::Scope;
+
+pub(crate) struct Platform {
+    pub(crate) super_: MoveObject,
+
+    contents: OnceCell<Option<NativePlatform>>,
+}
+
+/// The stage of its lifecycle that a platform is in.
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum PlatformStatus {
+    /// The platform is being developed, and has not been released.
+    Development,
+
+    /// The platform has been released as an alpha.
+    Alpha,
+
+    /// The platform has been released as a beta.
+    Beta,
+
+    /// The platform is live.
+    Live,
+
+    /// The platform is temporarily unavailable for maintenance.
+    Maintenance,
+
+    /// The platform is being wound down.
+    Sunset,
+
+    /// The platform has been shut down.
+    Shutdown,
+}
+
+/// A social media platform (an application built on the social protocol) that posts are made on.
+#[Object]
+impl Platform {
+    /// The platform's ID.
+    async fn address(&self, ctx: &Context<'_>) -> Result<MySoAddress, RpcError> {
+        self.super_.address(ctx).await
+    }
+
+    /// The epoch in which the platform was registered.
+    async fn created_at_epoch(&self, ctx: &Context<'_>) -> Option<Result<UInt53, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.created_at.into()))
+    }
+
+    /// A description of the platform.
+    async fn description(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.description.as_str()))
+    }
+
+    /// The address of the platform's developer.
+    async fn developer(&self, ctx: &Context<'_>) -> Option<Result<Address, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        let scope = self.scope().without_root_bound();
+        Some(platform.map(|p| Address::with_address(scope, p.developer)))
+    }
+
+    /// URLs associated with the platform.
+    async fn links(&self, ctx: &Context<'_>) -> Option<Result<&[String], RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.links.as_slice()))
+    }
+
+    /// URL of the platform's logo.
+    async fn logo(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.logo.as_str()))
+    }
+
+    /// The platform's name.
+    async fn name(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.name.as_str()))
+    }
+
+    /// The on-chain object that this platform is stored in.
+    async fn object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The names of the platforms (websites, apps, etc.) that this platform is available on.
+    async fn platforms(&self, ctx: &Context<'_>) -> Option<Result<&[String], RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.platforms.as_slice()))
+    }
+
+    /// The platform's primary category.
+    async fn primary_category(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.primary_category.as_str()))
+    }
+
+    /// URL of the platform's privacy policy.
+    async fn privacy_policy(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.privacy_policy.as_str()))
+    }
+
+    /// When the platform was, or will be, released, as recorded by its developer.
+    async fn release_date(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.release_date.as_str()))
+    }
+
+    /// The platform's secondary category, if it has one.
+    async fn secondary_category(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        platform
+            .map(|p| p.secondary_category.as_deref())
+            .transpose()
+    }
+
+    /// When the platform was, or will be, shut down, as recorded by its developer.
+    async fn shutdown_date(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        platform.map(|p| p.shutdown_date.as_deref()).transpose()
+    }
+
+    /// The stage of its lifecycle that the platform is in. `null` if the platform's status is not recognized.
+    async fn status(&self, ctx: &Context<'_>) -> Option<Result<PlatformStatus, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        platform
+            .map(|p| PlatformStatus::from_native(p.status.status))
+            .transpose()
+    }
+
+    /// The platform's tagline.
+    async fn tagline(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.tagline.as_str()))
+    }
+
+    /// URL of the platform's terms of service.
+    async fn terms_of_service(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.terms_of_service.as_str()))
+    }
+
+    /// The balance of MYSO in the platform's treasury.
+    async fn treasury_balance(&self, ctx: &Context<'_>) -> Option<Result<BigInt, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| BigInt::from(p.treasury.value())))
+    }
+
+    /// Whether the platform is governed by a DAO.
+    async fn wants_dao_governance(&self, ctx: &Context<'_>) -> Option<Result<bool, RpcError>> {
+        let platform = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(platform.map(|p| p.wants_dao_governance))
+    }
+}
+
+impl Platform {
+    /// Create a Platform from a `MoveObject`, assuming (but not checking) that it is a Platform.
+    pub(crate) fn from_super(super_: MoveObject) -> Self {
+        Self {
+            super_,
+            contents: OnceCell::new(),
+        }
+    }
+
+    /// Create a Platform from a `MoveObject`, after checking whether it is a Platform.
+    pub(crate) async fn from_move_object(
+        move_object: &MoveObject,
+        ctx: &Context<'_>,
+    ) -> Result<Option<Self>, RpcError> {
+        let Some(native) = move_object.native(ctx).await?.as_ref() else {
+            return Ok(None);
+        };
+
+        if !NativePlatform::is_platform(&StructTag::from(native.type_().clone())) {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::from_super(move_object.clone())))
+    }
+
+    /// A Platform at `address`, as of the checkpoint `scope` is viewed at. Its contents are
+    /// loaded lazily, assuming (but not checking) that it is a Platform.
+    pub(crate) fn with_address(scope: Scope, address: NativeAddress) -> Self {
+        Self::from_super(MoveObject::from_super(Object::with_address(scope, address)))
+    }
+
+    fn scope(&self) -> &Scope {
+        &self.super_.super_.super_.scope
+    }
+
+    /// Get the native Platform data, loading it lazily if needed.
+    async fn native(&self, ctx: &Context<'_>) -> Result<&Option<NativePlatform>, RpcError> {
+        self.contents
+            .get_or_try_init(async || {
+                let Some(native_move) = self.super_.native(ctx).await?.as_ref() else {
+                    return Ok(None);
+                };
+
+                if !NativePlatform::is_platform(&StructTag::from(native_move.type_().clone())) {
+                    return Err(anyhow!("Not a Platform").into());
+                }
+
+                Ok(Some(
+                    bcs::from_bytes(native_move.contents())
+                        .context("Failed to deserialize Platform")?,
+                ))
+            })
+            .await
+    }
+}
+
+impl PlatformStatus {
+    /// Interpret the status code stored in a `social_contracts::platform::PlatformStatus`.
+    fn from_native(status: u8) -> Option<Self> {
+        Some(match status {
+            0 => Self::Development,
+            1 => Self::Alpha,
+            2 => Self::Beta,
+            3 => Self::Live,
+            4 => Self::Maintenance,
+            5 => Self::Sunset,
+            6 => Self::Shutdown,
+            _ => return None,
+        })
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use anyhow::anyhow;
+use async_graphql::Context;
+use async_graphql::Object;
+use async_graphql::SimpleObject;
+use move_core_types::language_storage::StructTag;
+use myso_types::base_types::MySoAddress as NativeAddress;
+use myso_types::social::Post as NativePost;
+use tokio::sync::OnceCell;
+
+use crate::api::scalars::big_int::BigInt;
+use crate::api::scalars::myso_address::MySoAddress;
+use crate::api::scalars::uint53::UInt53;
+use crate::api::types::address::Address;
+use crate::api::types::move_object::MoveObject;
+use crate::api::types::object::Object;
+use crate::api::types::social::platform::Platform;
+use crate::api::types::social::profile::Profile;
+use crate::error::RpcError;
+use crate::scope::Scope;
+
+pub(crate) struct Post {
+    pub(crate) super_: MoveObject,
+
+    contents: OnceCell<Option<NativePost>>,
+}
+
+/// The ways that other users are allowed to interact with a post.
+#[derive(SimpleObject)]
+pub(crate) struct PostPermissions {
+    /// Whether the post can be commented on.
+    allow_comments: bool,
+
+    /// Whether users can react to the post.
+    allow_reactions: bool,
+
+    /// Whether the post can be reposted.
+    allow_reposts: bool,
+
+    /// Whether the post can be quoted in a repost.
+    allow_quotes: bool,
+
+    /// Whether the post can be tipped.
+    allow_tips: bool,
+}
+
+/// A post (or comment, or repost) made by a profile, on a platform.
+#[Object]
+impl Post {
+    /// The post's ID.
+    async fn address(&self, ctx: &Context<'_>) -> Result<MySoAddress, RpcError> {
+        self.super_.address(ctx).await
+    }
+
+    /// The number of comments on the post.
+    async fn comment_count(&self, ctx: &Context<'_>) -> Option<Result<UInt53, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(post.map(|p| p.comment_count.into()))
+    }
+
+    /// The content of the post.
+    async fn content(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(post.map(|p| p.content.as_str()))
+    }
+
+    /// The epoch in which the post was created.
+    async fn created_at_epoch(&self, ctx: &Context<'_>) -> Option<Result<UInt53, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(post.map(|p| p.created_at.into()))
+    }
+
+    /// URLs of media attached to the post.
+    async fn media(&self, ctx: &Context<'_>) -> Option<Result<&[String], RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        post.map(|p| p.media.as_deref()).transpose()
+    }
+
+    /// The profiles mentioned in the post.
+    async fn mentions(&self, ctx: &Context<'_>) -> Option<Result<Vec<Profile>, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        let scope = self.scope().without_root_bound();
+        post.map(|p| {
+            p.mentions.as_ref().map(|mentions| {
+                mentions
+                    .iter()
+                    .map(|id| Profile::with_address(scope.clone(), *id))
+                    .collect()
+            })
+        })
+        .transpose()
+    }
+
+    /// Additional metadata attached to the post, as a JSON string.
+    async fn metadata_json(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        post.map(|p| p.metadata_json.as_deref()).transpose()
+    }
+
+    /// The on-chain object that this post is stored in.
+    async fn object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The address that owns this post.
+    async fn owner(&self, ctx: &Context<'_>) -> Option<Result<Address, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        let scope = self.scope().without_root_bound();
+        Some(post.map(|p| Address::with_address(scope, p.owner)))
+    }
+
+    /// The post that this post is a comment on, or a repost of.
+    async fn parent_post(&self, ctx: &Context<'_>) -> Option<Result<Post, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        let scope = self.scope().without_root_bound();
+        post.map(|p| p.parent_post_id.map(|id| Post::with_address(scope, id)))
+            .transpose()
+    }
+
+    /// The ways that other users are allowed to interact with the post.
+    async fn permissions(&self, ctx: &Context<'_>) -> Option<Result<PostPermissions, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(post.map(|p| PostPermissions::from_native(p.permissions)))
+    }
+
+    /// The platform that the post was made on.
+    async fn platform(&self, ctx: &Context<'_>) -> Option<Result<Platform, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        let scope = self.scope().without_root_bound();
+        Some(post.map(|p| Platform::with_address(scope, p.platform_id)))
+    }
+
+    /// The kind of post: `standard`, `comment`, `repost`, or `quote_repost`.
+    async fn post_type(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(post.map(|p| p.post_type.as_str()))
+    }
+
+    /// The profile of the post's author.
+    async fn profile(&self, ctx: &Context<'_>) -> Option<Result<Profile, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        let scope = self.scope().without_root_bound();
+        Some(post.map(|p| Profile::with_address(scope, p.profile_id)))
+    }
+
+    /// The total number of reactions to the post.
+    async fn reaction_count(&self, ctx: &Context<'_>) -> Option<Result<UInt53, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(post.map(|p| p.reaction_count.into()))
+    }
+
+    /// Whether the post has been removed from its platform by the platform's moderators.
+    async fn removed_from_platform(&self, ctx: &Context<'_>) -> Option<Result<bool, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(post.map(|p| p.removed_from_platform))
+    }
+
+    /// The number of times the post has been reposted.
+    async fn repost_count(&self, ctx: &Context<'_>) -> Option<Result<UInt53, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(post.map(|p| p.repost_count.into()))
+    }
+
+    /// The percentage (between 0 and 100) of the post's revenue that is redirected to the creator of the content it was derived from.
+    async fn revenue_redirect_percentage(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Option<Result<UInt53, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        post.map(|p| p.revenue_redirect_percentage.map(UInt53::from))
+            .transpose()
+    }
+
+    /// The address that the post's revenue is (partially) redirected to, if the post was found to be derived from someone else's content.
+    async fn revenue_redirect_to(&self, ctx: &Context<'_>) -> Option<Result<Address, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        let scope = self.scope().without_root_bound();
+        post.map(|p| {
+            p.revenue_redirect_to
+                .map(|a| Address::with_address(scope, a))
+        })
+        .transpose()
+    }
+
+    /// The total amount of MYSO that the post has been tipped.
+    async fn tips_received(&self, ctx: &Context<'_>) -> Option<Result<BigInt, RpcError>> {
+        let post = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(post.map(|p| BigInt::from(p.tips_received)))
+    }
+}
+
+impl Post {
+    /// Create a Post from a `MoveObject`, assuming (but not checking) that it is a Post.
+    pub(crate) fn from_super(super_: MoveObject) -> Self {
+        Self {
+            super_,
+            contents: OnceCell::new(),
+        }
+    }
+
+    /// Create a Post from a `MoveObject`, after checking whether it is a Post.
+    pub(crate) async fn from_move_object(
+        move_object: &MoveObject,
+        ctx: &Context<'_>,
+    ) -> Result<Option<Self>, RpcError> {
+        let Some(native) = move_object.native(ctx).await?.as_ref() else {
+            return Ok(None);
+        };
+
+        if !NativePost::is_post(&StructTag::from(native.type_().clone())) {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::from_super(move_object.clone())))
+    }
+
+    /// A Post at `address`, as of the checkpoint `scope` is viewed at. Its contents are loaded
+    /// lazily, assuming (but not checking) that it is a Post.
+    pub(crate) fn with_address(scope: Scope, address: NativeAddress) -> Self {
+        Self::from_super(MoveObject::from_super(Object::with_address(scope, address)))
+    }
+
+    fn scope(&self) -> &Scope {
+        &self.super_.super_.super_.scope
+    }
+
+    /// Get the native Post data, loading it lazily if needed.
+    async fn native(&self, ctx: &Context<'_>) -> Result<&Option<NativePost>, RpcError> {
+        self.contents
+            .get_or_try_init(async || {
+                let Some(native_move) = self.super_.native(ctx).await?.as_ref() else {
+                    return Ok(None);
+                };
+
+                if !NativePost::is_post(&StructTag::from(native_move.type_().clone())) {
+                    return Err(anyhow!("Not a Post").into());
+                }
+
+                Ok(Some(
+                    bcs::from_bytes(native_move.contents())
+                        .context("Failed to deserialize Post")?,
+                ))
+            })
+            .await
+    }
+}
+
+impl PostPermissions {
+    /// Unpack the permissions bitfield stored in a `social_contracts::post::Post`.
+    fn from_native(permissions: u8) -> Self {
+        Self {
+            allow_comments: permissions & 1 != 0,
+            allow_reactions: permissions & 2 != 0,
+            allow_reposts: permissions & 4 != 0,
+            allow_quotes: permissions & 8 != 0,
+            allow_tips: permissions & 16 != 0,
+        }
+    }
+}
@@ -0,0 +1,398 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use anyhow::anyhow;
+use async_graphql::Context;
+use async_graphql::Object;
+use async_graphql::SimpleObject;
+use move_core_types::language_storage::StructTag;
+use myso_types::base_types::MySoAddress as NativeAddress;
+use myso_types::social::Profile as NativeProfile;
+use myso_types::social::ProfileBadge as NativeBadge;
+use myso_types::social::UsernameRegistry;
+use tokio::sync::OnceCell;
+
+use crate::api::scalars::big_int::BigInt;
+use crate::api::scalars::myso_address::MySoAddress;
+use crate::api::scalars::uint53::UInt53;
+use crate::api::types::address::Address;
+use crate::api::types::move_object::MoveObject;
+use crate::api::types::object;
+use crate::api::types::object::Object;
+use crate::api::types::social::address_table_entry;
+use crate::api::types::social::platform::Platform;
+use crate::api::types::social::singleton_contents;
+use crate::error::RpcError;
+use crate::error::upcast;
+use crate::scope::Scope;
+
+pub(crate) struct Profile {
+    pub(crate) super_: MoveObject,
+
+    contents: OnceCell<Option<NativeProfile>>,
+}
+
+/// A badge that a platform has issued to a profile. Badges cannot be transferred, and stay with the profile they were issued to.
+pub(crate) struct ProfileBadge {
+    scope: Scope,
+    native: NativeBadge,
+}
+
+/// The usernames that a profile has linked from other social networks. These are stored encrypted, and are returned as they are stored.
+#[derive(SimpleObject)]
+pub(crate) struct SocialUsernames {
+    /// Facebook username.
+    facebook: Option<String>,
+
+    /// GitHub username.
+    github: Option<String>,
+
+    /// Instagram username.
+    instagram: Option<String>,
+
+    /// LinkedIn username.
+    linkedin: Option<String>,
+
+    /// Reddit username.
+    reddit: Option<String>,
+
+    /// Twitch username.
+    twitch: Option<String>,
+
+    /// X (formerly Twitter) username.
+    x: Option<String>,
+}
+
+/// A user's profile in the social protocol.
+#[Object]
+impl Profile {
+    /// The profile's ID.
+    async fn address(&self, ctx: &Context<'_>) -> Result<MySoAddress, RpcError> {
+        self.super_.address(ctx).await
+    }
+
+    /// The badges that have been issued to this profile, in the order they were issued.
+    async fn badges(&self, ctx: &Context<'_>) -> Option<Result<Vec<ProfileBadge>, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        let scope = self.scope();
+        Some(profile.map(|p| {
+            p.badges
+                .iter()
+                .map(|b| ProfileBadge {
+                    scope: scope.without_root_bound(),
+                    native: b.clone(),
+                })
+                .collect()
+        }))
+    }
+
+    /// The profile's bio.
+    async fn bio(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(profile.map(|p| p.bio.as_str()))
+    }
+
+    /// URL of the profile's cover photo.
+    async fn cover_photo(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        profile.map(|p| p.cover_photo.as_deref()).transpose()
+    }
+
+    /// The epoch in which the profile was created.
+    async fn created_at_epoch(&self, ctx: &Context<'_>) -> Option<Result<UInt53, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(profile.map(|p| p.created_at.into()))
+    }
+
+    /// The profile's display name, if it has one.
+    async fn display_name(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        profile.map(|p| p.display_name.as_deref()).transpose()
+    }
+
+    /// The minimum amount of MYSO that must be paid to send a message to this profile, if paid messaging is enabled.
+    async fn min_message_cost(&self, ctx: &Context<'_>) -> Option<Result<BigInt, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        profile
+            .map(|p| {
+                p.min_message_cost
+                    .filter(|_| p.paid_messaging_enabled)
+                    .map(BigInt::from)
+            })
+            .transpose()
+    }
+
+    /// The minimum offer, in MYSO, that the profile's owner is willing to accept for the profile.
+    async fn min_offer_amount(&self, ctx: &Context<'_>) -> Option<Result<BigInt, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        profile
+            .map(|p| p.min_offer_amount.map(BigInt::from))
+            .transpose()
+    }
+
+    /// The on-chain object that this profile is stored in.
+    async fn object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+
+    /// The address that owns this profile.
+    async fn owner(&self, ctx: &Context<'_>) -> Option<Result<Address, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        let scope = self.scope();
+        Some(profile.map(|p| Address::with_address(scope.without_root_bound(), p.owner)))
+    }
+
+    /// Whether the profile accepts paid messages.
+    async fn paid_messaging_enabled(&self, ctx: &Context<'_>) -> Option<Result<bool, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(profile.map(|p| p.paid_messaging_enabled))
+    }
+
+    /// URL of the profile's picture.
+    async fn profile_picture(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        profile.map(|p| p.profile_picture.as_deref()).transpose()
+    }
+
+    /// The badge that the profile has chosen to display. Falls back to the first badge issued to the profile if it has not chosen one.
+    async fn selected_badge(&self, ctx: &Context<'_>) -> Option<Result<ProfileBadge, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        let scope = self.scope();
+        profile
+            .map(|p| {
+                let badge = match &p.selected_badge_id {
+                    Some(id) => p.badges.iter().find(|b| &b.badge_id == id),
+                    None => p.badges.first(),
+                };
+
+                badge.map(|b| ProfileBadge {
+                    scope: scope.without_root_bound(),
+                    native: b.clone(),
+                })
+            })
+            .transpose()
+    }
+
+    /// The usernames that this profile has linked from other social networks.
+    async fn social_usernames(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Option<Result<SocialUsernames, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(profile.map(|p| SocialUsernames {
+            facebook: p.facebook_username.clone(),
+            github: p.github_username.clone(),
+            instagram: p.instagram_username.clone(),
+            linkedin: p.linkedin_username.clone(),
+            reddit: p.reddit_username.clone(),
+            twitch: p.twitch_username.clone(),
+            x: p.x_username.clone(),
+        }))
+    }
+
+    /// The profile's username. Usernames are unique, and cannot be changed once the profile has been created.
+    async fn username(&self, ctx: &Context<'_>) -> Option<Result<&str, RpcError>> {
+        let profile = self.native(ctx).await.map(Option::as_ref).transpose()?;
+        Some(profile.map(|p| p.username.as_str()))
+    }
+}
+
+#[Object]
+impl ProfileBadge {
+    /// Identifies the badge, among the badges issued by its platform.
+    async fn badge_id(&self) -> &str {
+        &self.native.badge_id
+    }
+
+    /// The badge's tier, between 1 and 100, which determines how it ranks relative to other badges.
+    async fn badge_type(&self) -> u8 {
+        self.native.badge_type
+    }
+
+    /// What the badge represents.
+    async fn description(&self) -> &str {
+        &self.native.description
+    }
+
+    /// URL of a small icon that is displayed next to the username of profiles that display this badge.
+    async fn icon_url(&self) -> &str {
+        &self.native.icon_url
+    }
+
+    /// The epoch in which the badge was issued.
+    async fn issued_at_epoch(&self) -> UInt53 {
+        self.native.issued_at.into()
+    }
+
+    /// The address of the platform admin or moderator that issued the badge.
+    async fn issued_by(&self) -> Address {
+        Address::with_address(self.scope.clone(), self.native.issued_by)
+    }
+
+    /// URL of the badge's media (an image, video, etc).
+    async fn media_url(&self) -> &str {
+        &self.native.media_url
+    }
+
+    /// The badge's name.
+    async fn name(&self) -> &str {
+        &self.native.name
+    }
+
+    /// The platform that issued the badge.
+    async fn platform(&self) -> Platform {
+        Platform::with_address(self.scope.clone(), self.native.platform_id)
+    }
+}
+
+impl Profile {
+    /// Create a Profile from a `MoveObject`, assuming (but not checking) that it is a Profile.
+    pub(crate) fn from_super(super_: MoveObject) -> Self {
+        Self {
+            super_,
+            contents: OnceCell::new(),
+        }
+    }
+
+    /// Create a Profile from a `MoveObject`, after checking whether it is a Profile.
+    pub(crate) async fn from_move_object(
+        move_object: &MoveObject,
+        ctx: &Context<'_>,
+    ) -> Result<Option<Self>, RpcError> {
+        let Some(native) = move_object.native(ctx).await?.as_ref() else {
+            return Ok(None);
+        };
+
+        if !NativeProfile::is_profile(&StructTag::from(native.type_().clone())) {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::from_super(move_object.clone())))
+    }
+
+    /// A Profile at `address`, as of the checkpoint `scope` is viewed at. Its contents are loaded
+    /// lazily, assuming (but not checking) that it is a Profile.
+    pub(crate) fn with_address(scope: Scope, address: NativeAddress) -> Self {
+        Self::from_super(MoveObject::from_super(Object::with_address(scope, address)))
+    }
+
+    /// Find the profile owned by `owner`, using the username registry's mapping from owners to
+    /// their profiles.
+    ///
+    /// Returns `None` if the registry could not be found, or `owner` does not have a profile.
+    pub(crate) async fn by_owner(
+        ctx: &Context<'_>,
+        scope: Scope,
+        owner: NativeAddress,
+    ) -> Result<Option<Self>, RpcError<object::Error>> {
+        let scope = scope.without_root_bound();
+        let Some(registry): Option<UsernameRegistry> =
+            singleton_contents(ctx, scope.clone(), UsernameRegistry::type_()).await?
+        else {
+            return Ok(None);
+        };
+
+        let profile_id: Option<NativeAddress> =
+            address_table_entry(ctx, scope.clone(), registry.address_profiles.id, owner)
+                .await
+                .map_err(upcast)?;
+
+        Ok(profile_id.map(|id| Self::with_address(scope, id)))
+    }
+
+    fn scope(&self) -> &Scope {
+        &self.super_.super_.super_.scope
+    }
+
+    /// Get the native Profile data, loading it lazily if needed.
+    async fn native(&self, ctx: &Context<'_>) -> Result<&Option<NativeProfile>, RpcError> {
+        self.contents
+            .get_or_try_init(async || {
+                let Some(native_move) = self.super_.native(ctx).await?.as_ref() else {
+                    return Ok(None);
+                };
+
+                if !NativeProfile::is_profile(&StructTag::from(native_move.type_().clone())) {
+                    return Err(anyhow!("Not a Profile").into());
+                }
+
+                Ok(Some(
+                    bcs::from_bytes(native_move.contents())
+                        .context("Failed to deserialize Profile")?,
+                ))
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::EmptyMutation;
+    use async_graphql::EmptySubscription;
+    use async_graphql::Schema;
+    use myso_types::base_types::ObjectID;
+    use serde_json::json;
+
+    use super::*;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn profile(&self) -> Profile {
+            let native = NativeProfile {
+                id: ObjectID::ZERO,
+                display_name: None,
+                bio: String::new(),
+                profile_picture: None,
+                cover_photo: None,
+                created_at: 7,
+                owner: NativeAddress::ZERO,
+                username: "alice".to_string(),
+                facebook_username: None,
+                github_username: None,
+                instagram_username: None,
+                linkedin_username: None,
+                reddit_username: None,
+                twitch_username: None,
+                x_username: None,
+                min_offer_amount: None,
+                badges: vec![NativeBadge {
+                    badge_id: "badge".to_string(),
+                    name: "Badge".to_string(),
+                    description: String::new(),
+                    media_url: String::new(),
+                    icon_url: String::new(),
+                    platform_id: NativeAddress::ZERO,
+                    issued_at: 9,
+                    issued_by: NativeAddress::ZERO,
+                    badge_type: 1,
+                }],
+                selected_badge_id: None,
+                min_message_cost: None,
+                paid_messaging_enabled: false,
+                version: 1,
+            };
+
+            Profile {
+                contents: OnceCell::from(Some(native)),
+                ..Profile::with_address(Scope::for_tests(), NativeAddress::ZERO)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_epochs() {
+        let response = Schema::new(Query, EmptyMutation, EmptySubscription)
+            .execute("{ profile { createdAtEpoch badges { issuedAtEpoch } } }")
+            .await;
+
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({ "profile": { "createdAtEpoch": 7, "badges": [{ "issuedAtEpoch": 9 }] } }),
+        );
+    }
+}
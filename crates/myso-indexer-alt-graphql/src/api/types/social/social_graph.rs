@@ -0,0 +1,206 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use anyhow::anyhow;
+use async_graphql::Context;
+use async_graphql::Object;
+use async_graphql::connection::Connection;
+use move_core_types::language_storage::StructTag;
+use myso_types::base_types::MySoAddress as NativeAddress;
+use myso_types::base_types::ObjectID;
+use myso_types::collection_types::VecSet;
+use myso_types::social::SocialGraph as NativeSocialGraph;
+use tokio::sync::OnceCell;
+
+use crate::api::scalars::cursor::JsonCursor;
+use crate::api::scalars::myso_address::MySoAddress;
+use crate::api::types::address::Address;
+use crate::api::types::move_object::MoveObject;
+use crate::api::types::object;
+use crate::api::types::object::Object;
+use crate::api::types::social::address_table_entry;
+use crate::error::RpcError;
+use crate::pagination::Page;
+use crate::pagination::PaginationConfig;
+use crate::scope::Scope;
+
+pub(crate) struct SocialGraph {
+    pub(crate) super_: MoveObject,
+
+    contents: OnceCell<Option<NativeSocialGraph>>,
+}
+
+/// Cursor for paginating the followers of, or the addresses followed by, an address. Points to
+/// the position of an address in the set of addresses.
+pub(crate) type CFollow = JsonCursor<usize>;
+
+/// The shared object that records which addresses follow each other in the social protocol.
+#[Object]
+impl SocialGraph {
+    /// The SocialGraph's ID.
+    async fn address(&self, ctx: &Context<'_>) -> Result<MySoAddress, RpcError> {
+        self.super_.address(ctx).await
+    }
+
+    /// The addresses that follow `address`, in the order they started following it.
+    async fn followers(
+        &self,
+        ctx: &Context<'_>,
+        address: MySoAddress,
+        first: Option<u64>,
+        after: Option<CFollow>,
+        last: Option<u64>,
+        before: Option<CFollow>,
+    ) -> Option<Result<Connection<String, Address>, RpcError>> {
+        Some(
+            async {
+                let pagination: &PaginationConfig = ctx.data()?;
+                let limits = pagination.limits("SocialGraph", "followers");
+                let page = Page::from_params(limits, first, after, last, before)?;
+                self.followers_of(ctx, address.into(), page).await
+            }
+            .await,
+        )
+    }
+
+    /// The addresses that `address` follows, in the order it started following them.
+    async fn following(
+        &self,
+        ctx: &Context<'_>,
+        address: MySoAddress,
+        first: Option<u64>,
+        after: Option<CFollow>,
+        last: Option<u64>,
+        before: Option<CFollow>,
+    ) -> Option<Result<Connection<String, Address>, RpcError>> {
+        Some(
+            async {
+                let pagination: &PaginationConfig = ctx.data()?;
+                let limits = pagination.limits("SocialGraph", "following");
+                let page = Page::from_params(limits, first, after, last, before)?;
+                self.followed_by(ctx, address.into(), page).await
+            }
+            .await,
+        )
+    }
+
+    /// The on-chain object that the social graph is stored in.
+    async fn object(&self) -> MoveObject {
+        self.super_.clone()
+    }
+}
+
+impl SocialGraph {
+    /// Create a SocialGraph from a `MoveObject`, assuming (but not checking) that it is a
+    /// SocialGraph.
+    pub(crate) fn from_super(super_: MoveObject) -> Self {
+        Self {
+            super_,
+            contents: OnceCell::new(),
+        }
+    }
+
+    /// Create a SocialGraph from a `MoveObject`, after checking whether it is a SocialGraph.
+    pub(crate) async fn from_move_object(
+        move_object: &MoveObject,
+        ctx: &Context<'_>,
+    ) -> Result<Option<Self>, RpcError> {
+        let Some(native) = move_object.native(ctx).await?.as_ref() else {
+            return Ok(None);
+        };
+
+        if !NativeSocialGraph::is_social_graph(&StructTag::from(native.type_().clone())) {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::from_super(move_object.clone())))
+    }
+
+    /// Find the social graph, as of the checkpoint `scope` is viewed at.
+    ///
+    /// Returns `None` if the social protocol has not been bootstrapped on this network.
+    pub(crate) async fn singleton(
+        ctx: &Context<'_>,
+        scope: Scope,
+    ) -> Result<Option<Self>, RpcError<object::Error>> {
+        let scope = scope.without_root_bound();
+        Ok(Object::singleton(ctx, scope, NativeSocialGraph::type_())
+            .await?
+            .map(|obj| Self::from_super(MoveObject::from_super(obj))))
+    }
+
+    /// A page of the addresses that follow `address`.
+    pub(crate) async fn followers_of(
+        &self,
+        ctx: &Context<'_>,
+        address: NativeAddress,
+        page: Page<CFollow>,
+    ) -> Result<Connection<String, Address>, RpcError> {
+        let Some(graph) = self.native(ctx).await? else {
+            return Ok(Connection::new(false, false));
+        };
+
+        self.paginate(ctx, graph.followers.id, address, page).await
+    }
+
+    /// A page of the addresses that `address` follows.
+    pub(crate) async fn followed_by(
+        &self,
+        ctx: &Context<'_>,
+        address: NativeAddress,
+        page: Page<CFollow>,
+    ) -> Result<Connection<String, Address>, RpcError> {
+        let Some(graph) = self.native(ctx).await? else {
+            return Ok(Connection::new(false, false));
+        };
+
+        self.paginate(ctx, graph.following.id, address, page).await
+    }
+
+    /// Paginate the set of addresses stored against `address` in `table`, one of the social
+    /// graph's `Table<address, VecSet<address>>`s.
+    async fn paginate(
+        &self,
+        ctx: &Context<'_>,
+        table: ObjectID,
+        address: NativeAddress,
+        page: Page<CFollow>,
+    ) -> Result<Connection<String, Address>, RpcError> {
+        let scope = self.scope().without_root_bound();
+        let addresses: Option<VecSet<NativeAddress>> =
+            address_table_entry(ctx, scope.clone(), table, address).await?;
+
+        let addresses = addresses.map_or_else(Vec::new, |s| s.contents);
+        page.paginate_indices(addresses.len(), |i| {
+            Ok(Address::with_address(scope.clone(), addresses[i]))
+        })
+    }
+
+    fn scope(&self) -> &Scope {
+        &self.super_.super_.super_.scope
+    }
+
+    /// Get the native SocialGraph data, loading it lazily if needed.
+    async fn native(&self, ctx: &Context<'_>) -> Result<&Option<NativeSocialGraph>, RpcError> {
+        self.contents
+            .get_or_try_init(async || {
+                let Some(native_move) = self.super_.native(ctx).await?.as_ref() else {
+                    return Ok(None);
+                };
+
+                if !NativeSocialGraph::is_social_graph(&StructTag::from(
+                    native_move.type_().clone(),
+                )) {
+                    return Err(anyhow!("Not a SocialGraph").into());
+                }
+
+                Ok(Some(
+                    bcs::from_bytes(native_move.contents())
+                        .context("Failed to deserialize SocialGraph")?,
+                ))
+            })
+            .await
+    }
+}
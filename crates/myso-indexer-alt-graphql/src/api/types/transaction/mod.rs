@@ -49,7 +49,6 @@ use crate::error::RpcError;
 use crate::extensions::query_limits;
 use crate::pagination::Page;
 use crate::scope::Scope;
-use crate::task::watermark::Watermarks;
 
 pub(crate) mod filter;
 
@@ -256,7 +255,7 @@ impl Transaction {
         page: Page<CTransaction>,
         filter: TransactionFilter,
     ) -> Result<Connection<String, Transaction>, RpcError> {
-        let watermarks: &Arc<Watermarks> = ctx.data()?;
+        let watermarks = scope.watermarks(ctx)?;
         let available_range_key = AvailableRangeKey {
             type_: "Query".to_string(),
             field: Some("transactions".to_string()),
@@ -183,6 +183,7 @@ pub struct LimitsLayer {
     pub max_rich_queries: Option<usize>,
 }
 
+#[derive(Clone)]
 pub struct WatermarkConfig {
     /// How long to wait between updating the watermark.
     pub watermark_polling_interval: Duration,
@@ -2,11 +2,18 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 
 use anyhow::anyhow;
 use async_graphql::Context;
+use async_graphql::Request;
+use async_graphql::ServerResult;
+use async_graphql::extensions::Extension;
+use async_graphql::extensions::ExtensionContext;
+use async_graphql::extensions::ExtensionFactory;
+use async_graphql::extensions::NextPrepareRequest;
 
 use crate::config::Limits;
 use crate::error::RpcError;
@@ -18,6 +25,31 @@ use crate::error::resource_exhausted;
 #[derive(Default)]
 pub(crate) struct Meter(AtomicUsize);
 
+/// Extension factory that gives every request its own [Meter]. The meter is added to the request,
+/// rather than to the data its handler adds, because subscriptions on the same websocket share the
+/// data that is added to the connection.
+pub(crate) struct RichQueryMeter;
+
+struct RichQueryMeterExt;
+
+impl ExtensionFactory for RichQueryMeter {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RichQueryMeterExt)
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for RichQueryMeterExt {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        next.run(ctx, request.data(Meter::default())).await
+    }
+}
+
 /// Increment the rich query meter by one. If the meter exceeds the configured limit, a
 /// `RESOURCE_EXHAUSTED` error is returned.
 pub(crate) fn debit<E>(ctx: &Context<'_>) -> Result<(), RpcError<E>>
@@ -39,3 +71,122 @@ where
     }
     Ok(())
 }
+
+/// Reset the rich query meter, if there is one. Subscriptions reset their meter as they produce
+/// each batch of results, so that the limit applies to each batch rather than the whole
+/// subscription.
+pub(crate) fn reset(ctx: &Context<'_>) {
+    if let Some(meter) = ctx.data_opt::<Meter>() {
+        meter.0.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use async_graphql::Data;
+    use async_graphql::EmptyMutation;
+    use async_graphql::Object;
+    use async_graphql::Schema;
+    use async_graphql::Subscription;
+    use futures::Stream;
+    use futures::StreamExt;
+
+    use crate::error::code;
+
+    use super::*;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn rich(&self, ctx: &Context<'_>) -> Result<bool, RpcError> {
+            debit::<Infallible>(ctx)?;
+            Ok(true)
+        }
+    }
+
+    struct Root;
+
+    #[Subscription]
+    impl Root {
+        /// Debit the meter once per item, without resetting it.
+        async fn rich(
+            &self,
+            ctx: &Context<'_>,
+            items: usize,
+        ) -> impl Stream<Item = Result<bool, RpcError>> {
+            futures::stream::iter(0..items).map(move |_| debit(ctx).map(|_| true))
+        }
+    }
+
+    fn schema(max_rich_queries: usize) -> Schema<Query, EmptyMutation, Root> {
+        Schema::build(Query, EmptyMutation, Root)
+            .extension(RichQueryMeter)
+            .data(Limits {
+                max_rich_queries,
+                ..Default::default()
+            })
+            .finish()
+    }
+
+    fn error_code(response: &async_graphql::Response) -> Option<String> {
+        let error = response.errors.first()?;
+        let code = error.extensions.as_ref()?.get("code")?;
+        match code {
+            async_graphql::Value::String(code) => Some(code.clone()),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_meter_per_query() {
+        let schema = schema(2);
+
+        // Each query gets its own meter.
+        assert!(schema.execute("{ a: rich b: rich }").await.is_ok());
+        assert!(schema.execute("{ a: rich b: rich }").await.is_ok());
+
+        let response = schema.execute("{ a: rich b: rich c: rich }").await;
+        assert_eq!(
+            error_code(&response).as_deref(),
+            Some(code::RESOURCE_EXHAUSTED)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_meter_per_subscription() {
+        let schema = schema(2);
+
+        // Subscriptions on the same websocket share their session data, but still get their own
+        // meter.
+        let session = Arc::new(Data::default());
+        for _ in 0..2 {
+            let responses: Vec<_> = schema
+                .execute_stream_with_session_data(
+                    Request::new("subscription { rich(items: 2) }"),
+                    session.clone(),
+                )
+                .collect()
+                .await;
+
+            assert_eq!(responses.len(), 2);
+            assert!(responses.iter().all(|r| r.is_ok()));
+        }
+
+        let responses: Vec<_> = schema
+            .execute_stream_with_session_data(
+                Request::new("subscription { rich(items: 3) }"),
+                session.clone(),
+            )
+            .collect()
+            .await;
+
+        assert!(responses[..2].iter().all(|r| r.is_ok()));
+        assert_eq!(
+            error_code(&responses[2]).as_deref(),
+            Some(code::RESOURCE_EXHAUSTED)
+        );
+    }
+}
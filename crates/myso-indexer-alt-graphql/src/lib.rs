@@ -11,28 +11,33 @@ use api::types::address::IAddressable;
 use api::types::move_datatype::IMoveDatatype;
 use api::types::move_object::IMoveObject;
 use api::types::object::IObject;
-use async_graphql::EmptySubscription;
+use async_graphql::Data;
 use async_graphql::ObjectType;
 use async_graphql::Schema;
 use async_graphql::SchemaBuilder;
 use async_graphql::SubscriptionType;
 use async_graphql::extensions::ExtensionFactory;
+use async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
 use async_graphql::http::GraphiQLSource;
+use async_graphql_axum::GraphQLProtocol;
 use async_graphql_axum::GraphQLRequest;
 use async_graphql_axum::GraphQLResponse;
+use async_graphql_axum::GraphQLWebSocket;
 use axum::Extension;
 use axum::Router;
 use axum::extract::ConnectInfo;
 use axum::extract::MatchedPath;
+use axum::extract::WebSocketUpgrade;
 use axum::http::Method;
 use axum::response::Html;
+use axum::response::IntoResponse;
 use axum::routing::MethodRouter;
 use axum::routing::get;
 use axum::routing::post;
 use axum_extra::TypedHeader;
 use config::RpcConfig;
 use extensions::query_limits::QueryLimitsChecker;
-use extensions::query_limits::rich;
+use extensions::query_limits::rich::RichQueryMeter;
 use extensions::query_limits::show_usage::ShowUsage;
 use extensions::timeout::Timeout;
 use headers::ContentLength;
@@ -64,6 +69,7 @@ use url::Url;
 
 use crate::api::mutation::Mutation;
 use crate::api::query::Query;
+use crate::api::subscription::Subscription;
 use crate::error::PanicHandler;
 use crate::extensions::logging::Logging;
 use crate::extensions::logging::Session;
@@ -257,8 +263,8 @@ impl Default for RpcArgs {
 }
 
 /// The GraphQL schema this service will serve, without any extensions or context added.
-pub fn schema() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
-    Schema::build(Query::default(), Mutation, EmptySubscription)
+pub fn schema() -> SchemaBuilder<Query, Mutation, Subscription> {
+    Schema::build(Query::default(), Mutation, Subscription)
         .register_output_type::<IAddressable>()
         .register_output_type::<IMoveDatatype>()
         .register_output_type::<IMoveObject>()
@@ -356,7 +362,7 @@ pub async fn start_rpc(
     );
 
     let watermark_task = WatermarkTask::new(
-        config.watermark,
+        config.watermark.clone(),
         pg_pipelines,
         pg_reader.clone(),
         bigtable_reader,
@@ -367,6 +373,7 @@ pub async fn start_rpc(
 
     let rpc = rpc
         .route("/graphql", post(graphql))
+        .route("/graphql/ws", get(graphql_ws))
         .route("/graphql/health", get(health::check))
         .layer(watermark_task.watermarks())
         .layer(config.health)
@@ -376,8 +383,10 @@ pub async fn start_rpc(
             config.limits.query_limits(),
             metrics,
         ))
+        .extension(RichQueryMeter)
         .data(config.limits.pagination())
         .data(config.limits)
+        .data(config.watermark.clone())
         .data(config.zklogin)
         .data(chain_identifier)
         .data(pg_reader)
@@ -400,7 +409,7 @@ pub async fn start_rpc(
 /// Handler for RPC requests (POST requests making GraphQL queries).
 async fn graphql(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    Extension(schema): Extension<Schema<Query, Mutation, EmptySubscription>>,
+    Extension(schema): Extension<Schema<Query, Mutation, Subscription>>,
     Extension(watermark): Extension<WatermarksLock>,
     TypedHeader(content_length): TypedHeader<ContentLength>,
     show_usage: Option<TypedHeader<ShowUsage>>,
@@ -410,8 +419,7 @@ async fn graphql(
         .into_inner()
         .data(content_length)
         .data(Session::new(addr))
-        .data(watermark.read().await.clone())
-        .data(rich::Meter::default());
+        .data(watermark.read().await.clone());
 
     if let Some(TypedHeader(show_usage)) = show_usage {
         request = request.data(show_usage);
@@ -420,10 +428,42 @@ async fn graphql(
     schema.execute(request).await.into()
 }
 
+/// Handler for subscriptions, which are served over a websocket (using either the `graphql-ws` or
+/// `graphql-transport-ws` protocol).
+///
+/// Unlike queries, subscriptions outlive any single snapshot of the watermarks, so instead of a
+/// snapshot, they are given access to the watermarks lock, to track new watermarks as they are
+/// published.
+async fn graphql_ws(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Extension(schema): Extension<Schema<Query, Mutation, Subscription>>,
+    Extension(watermark): Extension<WatermarksLock>,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> impl IntoResponse {
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            let mut data = Data::default();
+            data.insert(Session::new(addr));
+            data.insert(watermark);
+
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .with_data(data)
+                .serve()
+        })
+}
+
 /// Handler for GET requests for the online IDE. GraphQL requests are forwarded to the POST handler
-/// at the same path.
+/// at the same path, and subscriptions are sent to the websocket handler.
 async fn graphiql(path: MatchedPath) -> Html<String> {
-    Html(GraphiQLSource::build().endpoint(path.as_str()).finish())
+    let subscriptions = format!("{}/ws", path.as_str());
+    Html(
+        GraphiQLSource::build()
+            .endpoint(path.as_str())
+            .subscription_endpoint(&subscriptions)
+            .finish(),
+    )
 }
 
 #[cfg(test)]
@@ -446,6 +486,9 @@ mod tests {
     use reqwest::Client;
     use serde_json::Value;
     use serde_json::json;
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpStream;
 
     use crate::error::code;
     use crate::extensions::logging::Session;
@@ -549,4 +592,71 @@ mod tests {
         // Verify the panic is recorded in metrics
         assert_eq!(metrics.queries_panicked.get(), 1);
     }
+
+    /// Subscriptions are served over a websocket, using the protocol that the client asks for.
+    #[tokio::test]
+    async fn test_graphql_ws_handshake() {
+        let registry = Registry::new();
+        let rpc_listen_address =
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), get_available_port());
+
+        let rpc = RpcService::new(
+            RpcArgs {
+                rpc_listen_address,
+                no_ide: true,
+            },
+            "test",
+            schema(),
+            &registry,
+        )
+        .route("/graphql/ws", get(graphql_ws))
+        .layer(WatermarksLock::default());
+
+        let _svc = rpc.run().await.unwrap();
+
+        let mut stream = TcpStream::connect(rpc_listen_address).await.unwrap();
+        stream
+            .write_all(
+                format!(
+                    "GET /graphql/ws HTTP/1.1\r\n\
+                     Host: {rpc_listen_address}\r\n\
+                     Connection: Upgrade\r\n\
+                     Upgrade: websocket\r\n\
+                     Sec-WebSocket-Version: 13\r\n\
+                     Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                     Sec-WebSocket-Protocol: graphql-transport-ws\r\n\
+                     \r\n"
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+
+        // Read the response head a byte at a time, to avoid reading into the first frame.
+        let mut head = vec![];
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+
+        let head = String::from_utf8(head).unwrap().to_lowercase();
+        assert!(head.starts_with("http/1.1 101"), "{head}");
+        assert!(
+            head.contains("sec-websocket-protocol: graphql-transport-ws"),
+            "{head}"
+        );
+
+        // Client frames must be masked, but the mask can be all zeroes.
+        let init = br#"{"type":"connection_init"}"#;
+        let mut frame = vec![0x81, 0x80 | init.len() as u8, 0, 0, 0, 0];
+        frame.extend_from_slice(init);
+        stream.write_all(&frame).await.unwrap();
+
+        let mut header = [0u8; 2];
+        stream.read_exact(&mut header).await.unwrap();
+        assert_eq!(header[0], 0x81, "expected a single text frame");
+
+        let mut ack = vec![0u8; header[1] as usize];
+        stream.read_exact(&mut ack).await.unwrap();
+        assert_eq!(ack, br#"{"type":"connection_ack"}"#);
+    }
 }
@@ -78,6 +78,11 @@ pub(crate) struct Scope {
 
     /// Limits for package/type resolution.
     resolver_limits: myso_package_resolver::Limits,
+
+    /// Watermarks that reads in this scope should be consistent with, overriding the snapshot
+    /// taken at the start of the request. This is set for scopes created by subscriptions, which
+    /// outlive the snapshot they started with.
+    watermarks: Option<Arc<Watermarks>>,
 }
 
 impl Scope {
@@ -94,6 +99,26 @@ impl Scope {
             execution_objects: Arc::new(BTreeMap::new()),
             package_store: package_store.clone(),
             resolver_limits: limits.package_resolver(),
+            watermarks: None,
+        })
+    }
+
+    /// Create a new top-level scope that is consistent with `watermarks`, rather than the
+    /// watermarks snapshot taken at the start of the request.
+    pub(crate) fn with_watermarks<E: std::error::Error>(
+        ctx: &Context<'_>,
+        watermarks: Arc<Watermarks>,
+    ) -> Result<Self, RpcError<E>> {
+        let package_store: &Arc<PackageCache> = ctx.data()?;
+        let limits: &Limits = ctx.data()?;
+
+        Ok(Self {
+            checkpoint_viewed_at: Some(watermarks.high_watermark().checkpoint()),
+            root_bound: None,
+            execution_objects: Arc::new(BTreeMap::new()),
+            package_store: package_store.clone(),
+            resolver_limits: limits.package_resolver(),
+            watermarks: Some(watermarks),
         })
     }
 
@@ -119,6 +144,7 @@ impl Scope {
             execution_objects: Arc::new(BTreeMap::new()),
             package_store: Arc::new(EmptyPackageStore),
             resolver_limits: Limits::default().package_resolver(),
+            watermarks: None,
         }
     }
 
@@ -129,7 +155,7 @@ impl Scope {
         ctx: &Context<'_>,
         checkpoint_viewed_at: u64,
     ) -> Option<Self> {
-        let watermark: &Arc<Watermarks> = ctx.data().ok()?;
+        let watermark = self.watermarks(ctx).ok()?;
         let cp_hi_inclusive = watermark.high_watermark().checkpoint();
         (checkpoint_viewed_at <= cp_hi_inclusive).then(|| Self {
             checkpoint_viewed_at: Some(checkpoint_viewed_at),
@@ -137,6 +163,7 @@ impl Scope {
             execution_objects: Arc::clone(&self.execution_objects),
             package_store: self.package_store.clone(),
             resolver_limits: self.resolver_limits.clone(),
+            watermarks: self.watermarks.clone(),
         })
     }
 
@@ -148,6 +175,7 @@ impl Scope {
             execution_objects: Arc::clone(&self.execution_objects),
             package_store: self.package_store.clone(),
             resolver_limits: self.resolver_limits.clone(),
+            watermarks: self.watermarks.clone(),
         }
     }
 
@@ -159,6 +187,7 @@ impl Scope {
             execution_objects: Arc::clone(&self.execution_objects),
             package_store: self.package_store.clone(),
             resolver_limits: self.resolver_limits.clone(),
+            watermarks: self.watermarks.clone(),
         }
     }
 
@@ -170,6 +199,19 @@ impl Scope {
             execution_objects: Arc::clone(&self.execution_objects),
             package_store: self.package_store.clone(),
             resolver_limits: self.resolver_limits.clone(),
+            watermarks: self.watermarks.clone(),
+        }
+    }
+
+    /// The watermarks that reads in this scope should be consistent with: Either the watermarks
+    /// this scope was created with, or the snapshot taken at the start of the request.
+    pub(crate) fn watermarks<'c>(
+        &'c self,
+        ctx: &'c Context<'_>,
+    ) -> async_graphql::Result<&'c Arc<Watermarks>> {
+        match &self.watermarks {
+            Some(watermarks) => Ok(watermarks),
+            None => ctx.data(),
         }
     }
 
@@ -247,6 +289,7 @@ impl Scope {
             execution_objects,
             package_store: self.package_store.clone(),
             resolver_limits: self.resolver_limits.clone(),
+            watermarks: self.watermarks.clone(),
         })
     }
 
@@ -254,6 +254,30 @@ impl Watermarks {
             .as_millis() as u64
     }
 
+    /// Watermarks for tests, where every pipeline in `pipelines` (given as pairs of pipeline name
+    /// and inclusive lower bound) is available up to and including checkpoint `checkpoint_hi`.
+    #[cfg(test)]
+    pub(crate) fn for_tests(
+        checkpoint_hi: u64,
+        pipelines: impl IntoIterator<Item = (&'static str, u64)>,
+    ) -> Self {
+        let mut watermarks = Self::default();
+        watermarks.global_hi.checkpoint = checkpoint_hi as i64;
+        for (pipeline, checkpoint_lo) in pipelines {
+            watermarks.merge(WatermarkRow {
+                pipeline: pipeline.to_owned(),
+                epoch_hi_inclusive: 0,
+                checkpoint_hi_inclusive: checkpoint_hi as i64,
+                tx_hi: 0,
+                timestamp_ms_hi_inclusive: 0,
+                epoch_lo: 0,
+                checkpoint_lo: checkpoint_lo as i64,
+                tx_lo: 0,
+            });
+        }
+        watermarks
+    }
+
     fn merge(&mut self, row: WatermarkRow) {
         let pipeline = Pipeline {
             hi: Watermark {
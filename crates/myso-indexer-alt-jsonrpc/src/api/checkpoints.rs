@@ -3,9 +3,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Context as _;
+use diesel::QueryDsl;
+use diesel::dsl::max;
+use futures::future::try_join_all;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
+use myso_indexer_alt_schema::schema::cp_sequence_numbers;
 use myso_json_rpc_types::Checkpoint;
+use myso_json_rpc_types::CheckpointPage;
 use myso_open_rpc::Module;
 use myso_open_rpc_macros::open_rpc;
 use myso_types::myso_serde::BigInt;
@@ -15,6 +20,7 @@ use crate::context::Context;
 use crate::error::InternalContext;
 use crate::error::RpcError;
 use crate::error::invalid_params;
+use crate::paginate;
 
 #[open_rpc(namespace = "myso", tag = "Checkpoints API")]
 #[rpc(server, namespace = "myso")]
@@ -26,14 +32,30 @@ trait CheckpointsApi {
         /// Checkpoint sequence number.
         seq: BigInt<u64>,
     ) -> RpcResult<Checkpoint>;
+
+    /// Return a page of checkpoints, in ascending or descending order of sequence number. The
+    /// cursor is exclusive, so the page starts from the checkpoint after the one it points to.
+    #[method(name = "getCheckpoints")]
+    async fn get_checkpoints(
+        &self,
+        /// The sequence number of the checkpoint to start paginating from (exclusive).
+        cursor: Option<BigInt<u64>>,
+        /// Maximum number of checkpoints to return per page.
+        limit: Option<usize>,
+        /// Order of results, by sequence number.
+        descending_order: bool,
+    ) -> RpcResult<CheckpointPage>;
 }
 
 pub(crate) struct Checkpoints(pub Context);
 
-#[derive(thiserror::Error, Debug, Clone)]
+#[derive(thiserror::Error, Debug)]
 enum Error {
     #[error("Checkpoint {0} not found")]
     NotFound(u64),
+
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] paginate::Error),
 }
 
 #[async_trait::async_trait]
@@ -44,6 +66,18 @@ impl CheckpointsApiServer for Checkpoints {
             format!("Failed to fetch checkpoint at sequence number {seq:?}")
         })?)
     }
+
+    async fn get_checkpoints(
+        &self,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> RpcResult<CheckpointPage> {
+        let Self(ctx) = self;
+        Ok(page(ctx, cursor.map(|c| *c), limit, descending_order)
+            .await
+            .internal_context("Failed to fetch checkpoints")?)
+    }
 }
 
 impl RpcModule for Checkpoints {
@@ -67,3 +101,67 @@ async fn response(ctx: &Context, seq: u64) -> Result<Checkpoint, RpcError<Error>
 
     Ok(Checkpoint::from((summary, contents, signature.signature)))
 }
+
+/// Load a page of checkpoints, following the fullnode's pagination semantics: Checkpoints are
+/// enumerated by sequence number, starting after `cursor`, up to the latest checkpoint the RPC
+/// knows about.
+async fn page(
+    ctx: &Context,
+    cursor: Option<u64>,
+    limit: Option<usize>,
+    descending: bool,
+) -> Result<CheckpointPage, RpcError<Error>> {
+    use cp_sequence_numbers::dsl as c;
+
+    let config = &ctx.config().checkpoints;
+    let limit = limit.unwrap_or(config.default_page_size);
+    if limit > config.max_page_size {
+        return Err(invalid_params(Error::from(
+            paginate::Error::ExceededMaxPageSize {
+                requested: limit,
+                max: config.max_page_size,
+            },
+        )));
+    }
+
+    let latest: Option<i64> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .first(c::cp_sequence_numbers.select(max(c::cp_sequence_number)))
+        .await
+        .context("Failed to fetch latest checkpoint")?;
+
+    let Some(latest) = latest.map(|l| l as u64) else {
+        return Ok(CheckpointPage {
+            data: vec![],
+            next_cursor: None,
+            has_next_page: false,
+        });
+    };
+
+    // Fetch one more checkpoint than requested, to detect whether there is a next page.
+    let sequence_numbers: Vec<u64> = match (cursor, descending) {
+        (None, false) => (0..=latest).take(limit + 1).collect(),
+        (None, true) => (0..=latest).rev().take(limit + 1).collect(),
+        (Some(c), false) => (c.saturating_add(1)..=latest).take(limit + 1).collect(),
+        (Some(c), true) => (0..c.min(latest + 1)).rev().take(limit + 1).collect(),
+    };
+
+    let mut data = try_join_all(sequence_numbers.into_iter().map(|seq| response(ctx, seq))).await?;
+
+    let has_next_page = data.len() > limit;
+    data.truncate(limit);
+
+    // Unlike other paginated endpoints, the fullnode only returns a cursor if there is a next page.
+    let next_cursor = has_next_page
+        .then(|| data.last().map(|c| BigInt::from(c.sequence_number)))
+        .flatten();
+
+    Ok(CheckpointPage {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
@@ -20,14 +20,18 @@ use myso_json_rpc_types::Page as PageResponse;
 use myso_open_rpc::Module;
 use myso_open_rpc_macros::open_rpc;
 use myso_types::MYSO_FRAMEWORK_ADDRESS;
+use myso_types::balance::Supply;
 use myso_types::base_types::MySoAddress;
 use myso_types::base_types::ObjectID;
 use myso_types::coin::COIN_METADATA_STRUCT_NAME;
 use myso_types::coin::COIN_MODULE_NAME;
 use myso_types::coin::COIN_STRUCT_NAME;
 use myso_types::coin::CoinMetadata;
+use myso_types::coin::TreasuryCap;
 use myso_types::coin_registry::Currency;
+use myso_types::coin_registry::SupplyState;
 use myso_types::gas_coin::GAS;
+use myso_types::gas_coin::TOTAL_SUPPLY_MIST;
 use myso_types::object::Object;
 
 use crate::api::rpc_module::RpcModule;
@@ -58,6 +62,22 @@ trait CoinsApi {
         limit: Option<usize>,
     ) -> RpcResult<PageResponse<Coin, String>>;
 
+    /// Return all Coin objects owned by an address, of any coin type.
+    ///
+    /// Coins of the same type are ordered by descending balance, and then by ID. Unlike the
+    /// fullnode, which orders coin types by their string representation, coin types are ordered by
+    /// their serialized form, so pages can group coin types in a different order.
+    #[method(name = "getAllCoins")]
+    async fn get_all_coins(
+        &self,
+        /// the owner's MySo address
+        owner: MySoAddress,
+        /// optional paging cursor
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<Coin, String>>;
+
     /// Return metadata (e.g., symbol, decimals) for a coin. Note that if the coin's metadata was
     /// wrapped in the transaction that published its marker type, or the latest version of the
     /// metadata object is wrapped or deleted, it will not be found.
@@ -86,6 +106,15 @@ trait CoinsApi {
         /// optional type names for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::myso::MYSO if not specified.
         coin_type: Option<String>,
     ) -> RpcResult<Balance>;
+
+    /// Return the total supply for a coin. The supply is read from the coin's entry in the coin
+    /// registry if it has one, and otherwise from its `TreasuryCap`.
+    #[method(name = "getTotalSupply")]
+    async fn get_total_supply(
+        &self,
+        /// type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC)
+        coin_type: String,
+    ) -> RpcResult<Supply>;
 }
 
 pub(crate) struct Coins(pub Context);
@@ -97,6 +126,9 @@ pub(crate) enum Error {
 
     #[error("Failed to parse type {0:?}: {1}")]
     BadType(String, anyhow::Error),
+
+    #[error("Cannot find supply for coin type {0:?}")]
+    SupplyNotFound(String),
 }
 
 type Cursor = BcsCursor<Vec<u8>>;
@@ -125,63 +157,32 @@ impl CoinsApiServer for Coins {
         };
 
         let Self(ctx) = self;
-        let config = &ctx.config().coins;
-
-        let page: Page<Cursor> = Page::from_params::<Error>(
-            config.default_page_size,
-            config.max_page_size,
+        Ok(coins_response(
+            ctx,
+            owner,
+            object_type.to_canonical_string(/* with_prefix */ true),
             cursor,
             limit,
-            None,
-        )?;
-
-        let consistent_reader = ctx.consistent_reader();
-
-        // Coin balances are stored as bitwise negation, so iterating in regular (forward) order
-        // yields highest balances first.
-        let results = consistent_reader
-            .list_owned_objects(
-                None, /* checkpoint */
-                OwnerKind::Address,
-                Some(owner.to_string()),
-                Some(object_type.to_canonical_string(/* with_prefix */ true)),
-                Some(page.limit as u32),
-                page.cursor.as_ref().map(|c| c.0.clone()),
-                None,
-                true,
-            )
-            .await
-            .context("Failed to list owned coin objects")
-            .map_err(RpcError::<Error>::from)?;
-
-        let coin_ids: Vec<_> = results
-            .results
-            .iter()
-            .map(|obj_ref| obj_ref.value.0)
-            .collect();
-
-        let next_cursor = results
-            .results
-            .last()
-            .map(|edge| BcsCursor(edge.token.clone()).encode())
-            .transpose()
-            .context("Failed to encode cursor")
-            .map_err(RpcError::<Error>::from)?;
+        )
+        .await?)
+    }
 
-        let coin_futures = coin_ids.iter().map(|id| coin_response(ctx, *id));
+    async fn get_all_coins(
+        &self,
+        owner: MySoAddress,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<Coin, String>> {
+        // A type filter without type parameters matches coins of every type.
+        let object_type = format!(
+            "{}::{}::{}",
+            MYSO_FRAMEWORK_ADDRESS.to_canonical_string(/* with_prefix */ true),
+            COIN_MODULE_NAME,
+            COIN_STRUCT_NAME,
+        );
 
-        let coins = future::join_all(coin_futures)
-            .await
-            .into_iter()
-            .zip(coin_ids)
-            .map(|(r, id)| r.with_internal_context(|| format!("Failed to get object {id}")))
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(PageResponse {
-            data: coins,
-            next_cursor,
-            has_next_page: results.has_next_page,
-        })
+        let Self(ctx) = self;
+        Ok(coins_response(ctx, owner, object_type, cursor, limit).await?)
     }
 
     async fn get_coin_metadata(&self, coin_type: String) -> RpcResult<Option<MySoCoinMetadata>> {
@@ -267,6 +268,13 @@ impl CoinsApiServer for Coins {
 
         Ok(try_from_proto(response)?)
     }
+
+    async fn get_total_supply(&self, coin_type: String) -> RpcResult<Supply> {
+        let Self(ctx) = self;
+        Ok(supply_response(ctx, &coin_type)
+            .await
+            .with_internal_context(|| format!("Failed to fetch total supply for {coin_type:?}"))?)
+    }
 }
 
 impl RpcModule for Coins {
@@ -296,6 +304,74 @@ fn try_from_proto(proto: ProtoBalance) -> Result<Balance, RpcError<Error>> {
     })
 }
 
+/// Fetch a page of coins owned by `owner` whose type matches the `object_type` filter, in
+/// descending order of balance.
+async fn coins_response(
+    ctx: &Context,
+    owner: MySoAddress,
+    object_type: String,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<PageResponse<Coin, String>, RpcError<Error>> {
+    let config = &ctx.config().coins;
+
+    let page: Page<Cursor> = Page::from_params::<Error>(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        None,
+    )?;
+
+    let consistent_reader = ctx.consistent_reader();
+
+    // Coin balances are stored as bitwise negation, so iterating in regular (forward) order
+    // yields highest balances first.
+    let results = consistent_reader
+        .list_owned_objects(
+            None, /* checkpoint */
+            OwnerKind::Address,
+            Some(owner.to_string()),
+            Some(object_type),
+            Some(page.limit as u32),
+            page.cursor.as_ref().map(|c| c.0.clone()),
+            None,
+            true,
+        )
+        .await
+        .context("Failed to list owned coin objects")
+        .map_err(RpcError::<Error>::from)?;
+
+    let coin_ids: Vec<_> = results
+        .results
+        .iter()
+        .map(|obj_ref| obj_ref.value.0)
+        .collect();
+
+    let next_cursor = results
+        .results
+        .last()
+        .map(|edge| BcsCursor(edge.token.clone()).encode())
+        .transpose()
+        .context("Failed to encode cursor")
+        .map_err(RpcError::<Error>::from)?;
+
+    let coin_futures = coin_ids.iter().map(|id| coin_response(ctx, *id));
+
+    let coins = future::join_all(coin_futures)
+        .await
+        .into_iter()
+        .zip(coin_ids)
+        .map(|(r, id)| r.with_internal_context(|| format!("Failed to get object {id}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PageResponse {
+        data: coins,
+        next_cursor,
+        has_next_page: results.has_next_page,
+    })
+}
+
 async fn coin_response(ctx: &Context, id: ObjectID) -> Result<Coin, RpcError<Error>> {
     let (object, coin_type, balance) = object_with_coin_data(ctx, id).await?;
 
@@ -321,6 +397,11 @@ async fn coin_registry_response(
     let coin_type = TypeTag::from_str(coin_type)
         .map_err(|e| invalid_params(Error::BadType(coin_type.to_owned(), e)))?;
 
+    Ok(currency(ctx, coin_type).await?.map(Into::into))
+}
+
+/// Load the coin registry's `Currency` entry for `coin_type`, if there is one.
+async fn currency(ctx: &Context, coin_type: TypeTag) -> Result<Option<Currency>, RpcError<Error>> {
     let currency_id = Currency::derive_object_id(coin_type)
         .context("Failed to derive object id for coin registry Currency")?;
 
@@ -338,7 +419,72 @@ async fn coin_registry_response(
     let currency: Currency =
         bcs::from_bytes(move_object.contents()).context("Failed to parse Currency object")?;
 
-    Ok(Some(currency.into()))
+    Ok(Some(currency))
+}
+
+/// Like the fullnode, the supply of a coin is sourced from (in order of preference): the supply
+/// recorded in its `Currency`, if it is fixed or can only be burned; the `TreasuryCap` that its
+/// `Currency` points to; or any `TreasuryCap` for the coin type.
+async fn supply_response(ctx: &Context, coin_type: &str) -> Result<Supply, RpcError<Error>> {
+    let coin_struct = StructTag::from_str(coin_type)
+        .map_err(|e| invalid_params(Error::BadType(coin_type.to_owned(), e)))?;
+
+    if GAS::is_gas(&coin_struct) {
+        return Ok(Supply {
+            value: TOTAL_SUPPLY_MIST,
+        });
+    }
+
+    let treasury_cap_id =
+        match currency(ctx, TypeTag::Struct(Box::new(coin_struct.clone()))).await? {
+            Some(Currency {
+                supply: Some(SupplyState::Fixed(value) | SupplyState::BurnOnly(value)),
+                ..
+            }) => return Ok(Supply { value }),
+            Some(currency) => currency.treasury_cap_id,
+            None => None,
+        };
+
+    let treasury_cap_id = match treasury_cap_id {
+        Some(id) => Some(id),
+        None => ctx
+            .consistent_reader()
+            .list_objects_by_type(
+                None,
+                TreasuryCap::type_(coin_struct).to_canonical_string(/* with_prefix */ true),
+                Some(1),
+                None,
+                None,
+                false,
+            )
+            .await
+            .context("Failed to load object reference for TreasuryCap")?
+            .results
+            .into_iter()
+            .next()
+            .map(|obj_ref| obj_ref.value.0),
+    };
+
+    let Some(id) = treasury_cap_id else {
+        return Err(invalid_params(Error::SupplyNotFound(coin_type.to_owned())));
+    };
+
+    let Some(object) = load_live(ctx, id)
+        .await
+        .context("Failed to load latest version of TreasuryCap")?
+    else {
+        return Err(invalid_params(Error::SupplyNotFound(coin_type.to_owned())));
+    };
+
+    let move_object = object
+        .data
+        .try_as_move()
+        .context("TreasuryCap is not a Move object")?;
+
+    let treasury_cap = TreasuryCap::from_bcs_bytes(move_object.contents())
+        .context("Failed to parse TreasuryCap object")?;
+
+    Ok(treasury_cap.total_supply)
 }
 
 /// Given the inner coin type, i.e 0x2::myso::MYSO, load the CoinMetadata object.
@@ -0,0 +1,257 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use futures::future::try_join_all;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use myso_indexer_alt_reader::epochs::EpochEndKey;
+use myso_indexer_alt_reader::epochs::EpochStartKey;
+use myso_indexer_alt_schema::epochs::StoredEpochStart;
+use myso_indexer_alt_schema::schema::kv_epoch_starts;
+use myso_json_rpc_types::EndOfEpochInfo;
+use myso_json_rpc_types::EpochInfo;
+use myso_json_rpc_types::EpochPage;
+use myso_open_rpc::Module;
+use myso_open_rpc_macros::open_rpc;
+use myso_types::myso_serde::BigInt;
+use myso_types::myso_system_state::MySoSystemState;
+use myso_types::myso_system_state::MySoSystemStateTrait;
+use myso_types::myso_system_state::myso_system_state_summary::MySoSystemStateSummary;
+use tokio::join;
+
+use crate::api::rpc_module::RpcModule;
+use crate::context::Context;
+use crate::error::InternalContext;
+use crate::error::RpcError;
+use crate::error::invalid_params;
+use crate::paginate;
+
+#[open_rpc(namespace = "mysox", tag = "Epochs API")]
+#[rpc(server, namespace = "mysox")]
+trait EpochsApi {
+    /// Return a page of epochs, in ascending or descending order of epoch ID. The cursor is
+    /// exclusive, so the page starts from the epoch after the one it points to.
+    #[method(name = "getEpochs")]
+    async fn get_epochs(
+        &self,
+        /// The epoch ID to start paginating from (exclusive).
+        cursor: Option<BigInt<u64>>,
+        /// Maximum number of epochs to return per page.
+        limit: Option<usize>,
+        /// Order of results, defaulting to ascending order (false), by epoch ID.
+        descending_order: Option<bool>,
+    ) -> RpcResult<EpochPage>;
+
+    /// Return information about the latest epoch that the RPC knows about.
+    #[method(name = "getCurrentEpoch")]
+    async fn get_current_epoch(&self) -> RpcResult<EpochInfo>;
+}
+
+pub(crate) struct Epochs(pub Context);
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("Epoch {0} not found")]
+    NotFound(u64),
+
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] paginate::Error),
+}
+
+#[async_trait::async_trait]
+impl EpochsApiServer for Epochs {
+    async fn get_epochs(
+        &self,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<EpochPage> {
+        let Self(ctx) = self;
+        Ok(page(ctx, cursor.map(|c| *c), limit, descending_order)
+            .await
+            .internal_context("Failed to fetch epochs")?)
+    }
+
+    async fn get_current_epoch(&self) -> RpcResult<EpochInfo> {
+        let Self(ctx) = self;
+        let epoch = latest_epoch::<Error>(ctx)
+            .await
+            .internal_context("Failed to fetch latest epoch")?;
+
+        Ok(response(ctx, epoch)
+            .await
+            .with_internal_context(|| format!("Failed to fetch epoch {epoch}"))?)
+    }
+}
+
+impl RpcModule for Epochs {
+    fn schema(&self) -> Module {
+        EpochsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// The ID of the latest epoch to have started, according to the `kv_epoch_starts` table.
+pub(crate) async fn latest_epoch<E: std::error::Error>(ctx: &Context) -> Result<u64, RpcError<E>> {
+    use kv_epoch_starts::dsl as e;
+
+    let epoch: i64 = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .first(e::kv_epoch_starts.select(e::epoch).order(e::epoch.desc()))
+        .await
+        .context("Failed to fetch the latest epoch")?;
+
+    Ok(epoch as u64)
+}
+
+/// Deserialize the summary of the system state as of the start of the epoch described by `start`.
+pub(crate) fn system_state_summary(
+    start: &StoredEpochStart,
+) -> anyhow::Result<MySoSystemStateSummary> {
+    let system_state: MySoSystemState =
+        bcs::from_bytes(&start.system_state).context("Failed to deserialize system state")?;
+
+    Ok(system_state.into_myso_system_state_summary())
+}
+
+/// Load a page of epochs, ordered by epoch ID and starting after `cursor`.
+async fn page(
+    ctx: &Context,
+    cursor: Option<u64>,
+    limit: Option<usize>,
+    descending_order: Option<bool>,
+) -> Result<EpochPage, RpcError<Error>> {
+    use kv_epoch_starts::dsl as e;
+
+    let config = &ctx.config().epochs;
+    let limit = limit.unwrap_or(config.default_page_size);
+    if limit > config.max_page_size {
+        return Err(invalid_params(Error::from(
+            paginate::Error::ExceededMaxPageSize {
+                requested: limit,
+                max: config.max_page_size,
+            },
+        )));
+    }
+
+    let mut query = e::kv_epoch_starts
+        .select(e::epoch)
+        .limit(limit as i64 + 1)
+        .into_boxed();
+
+    if descending_order.unwrap_or(false) {
+        query = query.order(e::epoch.desc());
+        if let Some(c) = cursor {
+            query = query.filter(e::epoch.lt(c as i64));
+        }
+    } else {
+        query = query.order(e::epoch.asc());
+        if let Some(c) = cursor {
+            query = query.filter(e::epoch.gt(c as i64));
+        }
+    }
+
+    let mut epochs: Vec<i64> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(query)
+        .await
+        .context("Failed to fetch epochs")?;
+
+    let has_next_page = epochs.len() > limit;
+    epochs.truncate(limit);
+
+    let data = try_join_all(epochs.into_iter().map(|e| response(ctx, e as u64))).await?;
+    let next_cursor = data
+        .last()
+        .map(|e| BigInt::from(e.epoch))
+        .or(cursor.map(BigInt::from));
+
+    Ok(EpochPage {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Load information about the start and (if it has finished) the end of `epoch`, and combine it
+/// into an `EpochInfo`.
+///
+/// Some end-of-epoch information (the protocol version and reference gas price it changes to) is
+/// only recorded at the start of the following epoch. The number of transactions in a finished
+/// epoch is derived from where the previous epoch ended, and is reported as zero for the epoch
+/// that is still in progress.
+pub(crate) async fn response(ctx: &Context, epoch: u64) -> Result<EpochInfo, RpcError<Error>> {
+    let pg_loader = ctx.pg_loader();
+
+    let prev_end = async {
+        if epoch == 0 {
+            Ok(None)
+        } else {
+            pg_loader.load_one(EpochEndKey(epoch - 1)).await
+        }
+    };
+
+    let (start, end, next_start, prev_end) = join!(
+        pg_loader.load_one(EpochStartKey(epoch)),
+        pg_loader.load_one(EpochEndKey(epoch)),
+        pg_loader.load_one(EpochStartKey(epoch + 1)),
+        prev_end,
+    );
+
+    let start = start
+        .context("Failed to fetch epoch start information")?
+        .ok_or_else(|| invalid_params(Error::NotFound(epoch)))?;
+    let end = end.context("Failed to fetch epoch end information")?;
+    let next_start = next_start.context("Failed to fetch next epoch start information")?;
+    let prev_end = prev_end.context("Failed to fetch previous epoch end information")?;
+
+    let validators = system_state_summary(&start)?.active_validators;
+
+    let tx_lo = prev_end.map_or(0, |e| e.tx_hi as u64);
+    let epoch_total_transactions = end.as_ref().map_or(0, |e| e.tx_hi as u64 - tx_lo);
+
+    let end_of_epoch_info = end.map(|end| EndOfEpochInfo {
+        last_checkpoint_id: end.cp_hi as u64 - 1,
+        epoch_end_timestamp: end.end_timestamp_ms as u64,
+        protocol_version: next_start
+            .as_ref()
+            .map_or(start.protocol_version, |s| s.protocol_version)
+            as u64,
+        reference_gas_price: next_start
+            .as_ref()
+            .map_or(start.reference_gas_price, |s| s.reference_gas_price)
+            as u64,
+        total_stake: end.total_stake.unwrap_or(0) as u64,
+        storage_fund_reinvestment: end.storage_fund_reinvestment.unwrap_or(0) as u64,
+        storage_charge: end.storage_charge.unwrap_or(0) as u64,
+        storage_rebate: end.storage_rebate.unwrap_or(0) as u64,
+        storage_fund_balance: end.storage_fund_balance.unwrap_or(0) as u64,
+        stake_subsidy_amount: end.stake_subsidy_amount.unwrap_or(0) as u64,
+        total_gas_fees: end.total_gas_fees.unwrap_or(0) as u64,
+        total_stake_rewards_distributed: end.total_stake_rewards_distributed.unwrap_or(0) as u64,
+        leftover_storage_fund_inflow: end.leftover_storage_fund_inflow.unwrap_or(0) as u64,
+    });
+
+    Ok(EpochInfo {
+        epoch,
+        validators,
+        epoch_total_transactions,
+        first_checkpoint_id: start.cp_lo as u64,
+        epoch_start_timestamp: start.start_timestamp_ms as u64,
+        end_of_epoch_info,
+        reference_gas_price: Some(start.reference_gas_price as u64),
+    })
+}
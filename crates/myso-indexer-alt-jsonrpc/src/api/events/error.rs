@@ -0,0 +1,20 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use myso_types::digests::TransactionDigest;
+
+#[derive(thiserror::Error, Debug)]
+pub(super) enum Error {
+    #[error("'Any' filter combines {actual} filters, but at most {max} are supported")]
+    AnyFilterTooLarge { actual: usize, max: usize },
+
+    #[error("'Any' filters cannot be nested")]
+    NestedAnyFilter,
+
+    #[error("Transaction {0} not found")]
+    NotFound(TransactionDigest),
+
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+}
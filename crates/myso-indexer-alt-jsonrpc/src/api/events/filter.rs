@@ -0,0 +1,830 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::Range;
+
+use anyhow::Context as _;
+use diesel::AppearsOnTable;
+use diesel::Column;
+use diesel::Expression;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use diesel::QuerySource;
+use diesel::dsl::max;
+use diesel::dsl::min;
+use diesel::expression::MixedAggregates;
+use diesel::expression::ValidGrouping;
+use diesel::expression::is_aggregate::Never;
+use diesel::expression::is_aggregate::No;
+use diesel::pg::Pg;
+use diesel::query_builder::BoxedSelectStatement;
+use diesel::query_builder::FromClause;
+use diesel::query_builder::QueryFragment;
+use diesel::sql_types::BigInt as SqlBigInt;
+use futures::future::try_join_all;
+use myso_indexer_alt_reader::tx_digests::TxDigestKey;
+use myso_indexer_alt_schema::schema::cp_sequence_numbers;
+use myso_indexer_alt_schema::schema::ev_emit_mod;
+use myso_indexer_alt_schema::schema::ev_struct_inst;
+use myso_indexer_alt_schema::schema::tx_affected_addresses;
+use myso_indexer_alt_schema::schema::tx_digests;
+use myso_json_rpc_types::EventFilter;
+use myso_json_rpc_types::Page as PageResponse;
+use myso_sql_macro::sql;
+use myso_types::base_types::MySoAddress;
+use myso_types::base_types::ObjectID;
+use myso_types::digests::TransactionDigest;
+use myso_types::event::Event;
+use myso_types::event::EventID;
+use myso_types::messages_checkpoint::CheckpointContents;
+use myso_types::messages_checkpoint::CheckpointSummary;
+
+use crate::api::events::error::Error;
+use crate::context::Context;
+use crate::error::RpcError;
+use crate::error::invalid_params;
+use crate::paginate;
+
+/// An event that matched a filter, along with enough information to identify it and position it
+/// in the results.
+pub(super) struct EventEntry {
+    pub tx_sequence_number: u64,
+    pub digest: TransactionDigest,
+    pub ix: u64,
+    pub event: Event,
+    pub timestamp_ms: Option<u64>,
+}
+
+/// Where to look for the transactions that could have emitted events matching a filter. Every
+/// event matching the filter was emitted by one of these transactions, but not every event these
+/// transactions emitted necessarily matches the filter.
+enum Source {
+    /// Every transaction.
+    All,
+
+    /// Transactions sent by this address.
+    Sender(MySoAddress),
+
+    /// Transactions that emitted an event from a function in this module.
+    EmitModule { package: ObjectID, module: String },
+
+    /// Transactions that emitted an event whose type is defined in this module, optionally
+    /// narrowed down to a type name, and its instantiation (the BCS-encoded type parameters).
+    StructInst {
+        package: ObjectID,
+        module: String,
+        name: Option<String>,
+        instantiation: Option<Vec<u8>>,
+    },
+}
+
+/// Fetch a page of events that satisfy the given `filter` and pagination parameters.
+///
+/// Like the fullnode implementation, events are ordered by the sequence number of the transaction
+/// that emitted them, and then by their position in that transaction's events. The `cursor` is
+/// exclusive, and the next cursor points at the last event in the page (if there is one). Unlike
+/// the fullnode, `Any` filters are supported, as long as they are not nested.
+pub(super) async fn events(
+    ctx: &Context,
+    filter: &EventFilter,
+    cursor: Option<EventID>,
+    limit: Option<usize>,
+    descending_order: Option<bool>,
+) -> Result<PageResponse<EventEntry, EventID>, RpcError<Error>> {
+    let config = &ctx.config().events;
+
+    let limit = limit.unwrap_or(config.default_page_size);
+    if limit > config.max_page_size {
+        return Err(invalid_params(Error::from(
+            paginate::Error::ExceededMaxPageSize {
+                requested: limit,
+                max: config.max_page_size,
+            },
+        )));
+    }
+
+    let descending = descending_order.unwrap_or(false);
+
+    let cursor = match cursor {
+        Some(EventID {
+            tx_digest,
+            event_seq,
+        }) => Some((tx_sequence_number(ctx, tx_digest).await?, event_seq)),
+        None => None,
+    };
+
+    let mut entries = if let EventFilter::Any(filters) = filter {
+        if filters.len() > config.max_any_filters {
+            return Err(invalid_params(Error::AnyFilterTooLarge {
+                actual: filters.len(),
+                max: config.max_any_filters,
+            }));
+        }
+
+        if filters.iter().any(|f| matches!(f, EventFilter::Any(_))) {
+            return Err(invalid_params(Error::NestedAnyFilter));
+        }
+
+        // Each scan returns the first `limit + 1` events matching its filter, so the first
+        // `limit + 1` events matching any of the filters must be among them.
+        let scans = try_join_all(
+            filters
+                .iter()
+                .map(|f| scan(ctx, f, cursor, limit, descending)),
+        )
+        .await?;
+
+        merge(scans, descending)
+    } else {
+        scan(ctx, filter, cursor, limit, descending).await?
+    };
+
+    let has_next_page = entries.len() > limit;
+    entries.truncate(limit);
+
+    let next_cursor = entries.last().map(|e| EventID {
+        tx_digest: e.digest,
+        event_seq: e.ix,
+    });
+
+    Ok(PageResponse {
+        data: entries,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Find the first `limit + 1` events (or fewer, if there are not that many) that match `filter`
+/// and come strictly after `cursor`, a (transaction sequence number, event index) pair.
+///
+/// Candidate transactions are fetched from the database in batches, and then their events are
+/// loaded and filtered, until enough events are found, or there are no more candidates.
+async fn scan(
+    ctx: &Context,
+    filter: &EventFilter,
+    cursor: Option<(u64, u64)>,
+    limit: usize,
+    descending: bool,
+) -> Result<Vec<EventEntry>, RpcError<Error>> {
+    let scan_size = ctx.config().events.tx_scan_size.max(limit + 1);
+    let (source, mut range) = plan(ctx, filter).await?;
+
+    // Only the cursor's transaction and the transactions after it can contain events after the
+    // cursor.
+    if let Some((tx, _)) = cursor {
+        if descending {
+            range.end = range.end.min(tx.saturating_add(1));
+        } else {
+            range.start = range.start.max(tx);
+        }
+    }
+
+    let mut entries = vec![];
+    while !range.is_empty() {
+        let tx_sequence_numbers = candidates(ctx, &source, &range, descending, scan_size).await?;
+        let exhausted = tx_sequence_numbers.len() < scan_size;
+
+        match tx_sequence_numbers.last() {
+            None => break,
+            Some(&last) if descending => range.end = last,
+            Some(&last) => range.start = last + 1,
+        }
+
+        let digests = tx_digests(ctx, &tx_sequence_numbers).await?;
+        let contents = ctx
+            .kv_loader()
+            .load_many_transaction_events(digests.iter().map(|(_, d)| *d).collect())
+            .await
+            .context("Failed to load transaction events")?;
+
+        for (tx_sequence_number, digest) in digests {
+            let contents = contents
+                .get(&digest)
+                .with_context(|| format!("Failed to find events for transaction {digest}"))?;
+
+            let timestamp_ms = contents.timestamp_ms();
+            let mut events: Vec<_> = contents.events()?.into_iter().enumerate().collect();
+            if descending {
+                events.reverse();
+            }
+
+            for (ix, event) in events {
+                let ix = ix as u64;
+                if !after_cursor(cursor, tx_sequence_number, ix, descending)
+                    || !matches(filter, &event)
+                {
+                    continue;
+                }
+
+                entries.push(EventEntry {
+                    tx_sequence_number,
+                    digest,
+                    ix,
+                    event,
+                    timestamp_ms,
+                });
+
+                if entries.len() > limit {
+                    return Ok(entries);
+                }
+            }
+        }
+
+        if exhausted {
+            break;
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Combine the results of scanning for each filter in an `Any` filter into a single list of
+/// events, in the order given by `descending`, without duplicates (an event can match more than
+/// one filter).
+fn merge(scans: Vec<Vec<EventEntry>>, descending: bool) -> Vec<EventEntry> {
+    let mut entries: Vec<_> = scans.into_iter().flatten().collect();
+
+    entries.sort_by_key(|e| (e.tx_sequence_number, e.ix));
+    entries.dedup_by_key(|e| (e.tx_sequence_number, e.ix));
+    if descending {
+        entries.reverse();
+    }
+
+    entries
+}
+
+/// Whether the event at position `ix` in transaction `tx_sequence_number` comes strictly after
+/// `cursor` in the order given by `descending`. Only the position within the cursor's own
+/// transaction needs to be checked: scans only visit transactions at or after the cursor's.
+fn after_cursor(
+    cursor: Option<(u64, u64)>,
+    tx_sequence_number: u64,
+    ix: u64,
+    descending: bool,
+) -> bool {
+    match cursor {
+        Some((tx, seq)) if tx == tx_sequence_number => {
+            if descending {
+                ix < seq
+            } else {
+                ix > seq
+            }
+        }
+        _ => true,
+    }
+}
+
+/// Decide where to look for the transactions that could have emitted events matching `filter`,
+/// and the range of transaction sequence numbers to look in.
+async fn plan(
+    ctx: &Context,
+    filter: &EventFilter,
+) -> Result<(Source, Range<u64>), RpcError<Error>> {
+    let everything = 0..i64::MAX as u64;
+
+    use EventFilter as F;
+    Ok(match filter {
+        F::All([]) => (Source::All, everything),
+
+        F::Transaction(digest) => {
+            let tx = tx_sequence_number(ctx, *digest).await?;
+            (Source::All, tx..tx + 1)
+        }
+
+        F::Sender(sender) => (Source::Sender(*sender), everything),
+
+        F::MoveModule { package, module } => (
+            Source::EmitModule {
+                package: *package,
+                module: module.to_string(),
+            },
+            everything,
+        ),
+
+        F::MoveEventType(tag) => (
+            Source::StructInst {
+                package: tag.address.into(),
+                module: tag.module.to_string(),
+                name: Some(tag.name.to_string()),
+                instantiation: Some(
+                    bcs::to_bytes(&tag.type_params)
+                        .context("Failed to serialize type parameters")?,
+                ),
+            },
+            everything,
+        ),
+
+        F::MoveEventModule { package, module } => (
+            Source::StructInst {
+                package: *package,
+                module: module.to_string(),
+                name: None,
+                instantiation: None,
+            },
+            everything,
+        ),
+
+        F::TimeRange {
+            start_time,
+            end_time,
+        } => {
+            let lo = tx_at_time(ctx, *start_time).await?;
+            let hi = tx_at_time(ctx, *end_time).await?;
+            (Source::All, lo..hi)
+        }
+
+        F::Any(_) => return Err(invalid_params(Error::NestedAnyFilter)),
+    })
+}
+
+/// Whether `event` matches `filter`. This is applied to all the events emitted by candidate
+/// transactions, to weed out the events that did not cause the transaction to be selected.
+fn matches(filter: &EventFilter, event: &Event) -> bool {
+    use EventFilter as F;
+    match filter {
+        F::All([]) | F::Transaction(_) | F::TimeRange { .. } => true,
+        F::Any(filters) => filters.iter().any(|f| matches(f, event)),
+        F::Sender(sender) => &event.sender == sender,
+        F::MoveModule { package, module } => {
+            &event.package_id == package && &event.transaction_module == module
+        }
+        F::MoveEventType(tag) => &event.type_ == tag,
+        F::MoveEventModule { package, module } => {
+            ObjectID::from(event.type_.address) == *package && &event.type_.module == module
+        }
+    }
+}
+
+/// Fetch a batch of up to `limit` sequence numbers for transactions from `source` within `range`,
+/// in ascending or descending order.
+async fn candidates(
+    ctx: &Context,
+    source: &Source,
+    range: &Range<u64>,
+    descending: bool,
+    limit: usize,
+) -> Result<Vec<u64>, RpcError<Error>> {
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let results: Vec<i64> = match source {
+        Source::All => {
+            use tx_digests::dsl as d;
+
+            let query = d::tx_digests.select(d::tx_sequence_number).into_boxed();
+
+            conn.results(bound(
+                range,
+                descending,
+                limit,
+                "tx_digests",
+                d::tx_sequence_number,
+                query,
+            ))
+            .await
+        }
+
+        Source::Sender(sender) => {
+            use tx_affected_addresses::dsl as a;
+
+            let query = a::tx_affected_addresses
+                .select(a::tx_sequence_number)
+                .filter(a::affected.eq(sender.to_inner()))
+                .filter(a::sender.eq(sender.to_inner()))
+                .into_boxed();
+
+            conn.results(bound(
+                range,
+                descending,
+                limit,
+                "tx_affected_addresses",
+                a::tx_sequence_number,
+                query,
+            ))
+            .await
+        }
+
+        Source::EmitModule { package, module } => {
+            use ev_emit_mod::dsl as e;
+
+            let query = e::ev_emit_mod
+                .select(e::tx_sequence_number)
+                .filter(e::package.eq(package.as_slice()))
+                .filter(e::module.eq(module.as_str()))
+                .into_boxed();
+
+            conn.results(bound(
+                range,
+                descending,
+                limit,
+                "ev_emit_mod",
+                e::tx_sequence_number,
+                query,
+            ))
+            .await
+        }
+
+        Source::StructInst {
+            package,
+            module,
+            name,
+            instantiation,
+        } => {
+            use ev_struct_inst::dsl as e;
+
+            let mut query = e::ev_struct_inst
+                .select(e::tx_sequence_number)
+                .filter(e::package.eq(package.as_slice()))
+                .filter(e::module.eq(module.as_str()))
+                .into_boxed();
+
+            if let Some(name) = name {
+                query = query.filter(e::name.eq(name.as_str()));
+            }
+
+            if let Some(instantiation) = instantiation {
+                query = query.filter(e::instantiation.eq(instantiation.as_slice()));
+            }
+
+            conn.results(bound(
+                range,
+                descending,
+                limit,
+                "ev_struct_inst",
+                e::tx_sequence_number,
+                query,
+            ))
+            .await
+        }
+    }
+    .context("Failed to fetch transaction sequence numbers")?;
+
+    Ok(results.into_iter().map(|tx| tx as u64).collect())
+}
+
+/// Modify `query` to only return transactions in `range`, in the order given by `descending`,
+/// fetching at most `limit` results. Like transaction queries, results are also bounded by the
+/// reader low watermark of the `pipeline` that `query` reads from, and of `tx_digests` (which is
+/// used to translate the results into digests).
+fn bound<'q, TX, ST, QS>(
+    range: &Range<u64>,
+    descending: bool,
+    limit: usize,
+    pipeline: &'static str,
+    tx_sequence_number: TX,
+    mut query: BoxedSelectStatement<'q, ST, FromClause<QS>, Pg>,
+) -> BoxedSelectStatement<'q, ST, FromClause<QS>, Pg>
+where
+    QS: QuerySource,
+    TX: Copy + Send + Sync + 'q,
+    TX: ValidGrouping<()> + QueryFragment<Pg>,
+    TX: Column<Table = QS> + AppearsOnTable<QS>,
+    TX: ExpressionMethods + Expression<SqlType = SqlBigInt>,
+    TX::IsAggregate: MixedAggregates<Never, Output = No>,
+{
+    query = query.filter(tx_sequence_number.ge(sql!(as SqlBigInt,
+        "COALESCE(
+            (
+                SELECT
+                    MAX(tx_lo)
+                FROM
+                    watermarks w
+                INNER JOIN
+                    cp_sequence_numbers c
+                ON
+                    w.reader_lo = c.cp_sequence_number
+                WHERE
+                    w.pipeline IN ({Text}, 'tx_digests')
+            ),
+            0
+        )",
+        pipeline,
+    )));
+
+    query = query
+        .filter(tx_sequence_number.ge(range.start as i64))
+        .filter(tx_sequence_number.lt(range.end.min(i64::MAX as u64) as i64));
+
+    if descending {
+        query = query.order(tx_sequence_number.desc());
+    } else {
+        query = query.order(tx_sequence_number.asc());
+    }
+
+    query.limit(limit as i64)
+}
+
+/// Pair each transaction sequence number in `tx_sequence_numbers` with its digest, preserving
+/// their order.
+async fn tx_digests(
+    ctx: &Context,
+    tx_sequence_numbers: &[u64],
+) -> Result<Vec<(u64, TransactionDigest)>, RpcError<Error>> {
+    let keys: Vec<_> = tx_sequence_numbers
+        .iter()
+        .copied()
+        .map(TxDigestKey)
+        .collect();
+    let stored = ctx
+        .pg_loader()
+        .load_many(keys)
+        .await
+        .context("Failed to load transaction digests")?;
+
+    let mut digests = Vec::with_capacity(tx_sequence_numbers.len());
+    for &seq in tx_sequence_numbers {
+        let bytes = stored
+            .get(&TxDigestKey(seq))
+            .with_context(|| format!("Missing transaction digest for transaction {seq}"))?
+            .tx_digest
+            .as_slice();
+
+        let digest = TransactionDigest::try_from(bytes)
+            .context("Failed to deserialize transaction digest")?;
+
+        digests.push((seq, digest));
+    }
+
+    Ok(digests)
+}
+
+/// Find the sequence number of the transaction with the given `digest`, by finding its position in
+/// the checkpoint that included it.
+async fn tx_sequence_number(
+    ctx: &Context,
+    digest: TransactionDigest,
+) -> Result<u64, RpcError<Error>> {
+    let tx = ctx
+        .kv_loader()
+        .load_one_transaction(digest)
+        .await
+        .context("Failed to fetch transaction from store")?
+        .ok_or_else(|| invalid_params(Error::NotFound(digest)))?;
+
+    let cp = tx
+        .cp_sequence_number()
+        .with_context(|| format!("Missing checkpoint for transaction {digest}"))?;
+
+    let (summary, contents) = checkpoint(ctx, cp).await?;
+    let ix = contents
+        .iter()
+        .position(|d| d.transaction == digest)
+        .with_context(|| format!("Transaction {digest} not found in checkpoint {cp}"))?;
+
+    Ok(summary.network_total_transactions - contents.size() as u64 + ix as u64)
+}
+
+/// Find the sequence number of the first transaction in the first checkpoint with a timestamp at
+/// or after `timestamp_ms`. If there is no such checkpoint, this is one past the last transaction
+/// that the RPC knows about.
+///
+/// Checkpoint timestamps are monotonically non-decreasing, so this is found by binary searching
+/// over the checkpoints in the `cp_sequence_numbers` table.
+async fn tx_at_time(ctx: &Context, timestamp_ms: u64) -> Result<u64, RpcError<Error>> {
+    use cp_sequence_numbers::dsl as c;
+
+    let (cp_lo, cp_hi): (Option<i64>, Option<i64>) = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .first(
+            c::cp_sequence_numbers.select((min(c::cp_sequence_number), max(c::cp_sequence_number))),
+        )
+        .await
+        .context("Failed to fetch checkpoint bounds")?;
+
+    let (Some(cp_lo), Some(cp_hi)) = (cp_lo, cp_hi) else {
+        return Ok(0);
+    };
+
+    let cp_hi = cp_hi as u64;
+    let (mut lo, mut hi) = (cp_lo as u64, cp_hi + 1);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let (summary, _) = checkpoint(ctx, mid).await?;
+        if summary.timestamp_ms < timestamp_ms {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+
+    if lo > cp_hi {
+        let (summary, _) = checkpoint(ctx, cp_hi).await?;
+        Ok(summary.network_total_transactions)
+    } else {
+        let (summary, contents) = checkpoint(ctx, lo).await?;
+        Ok(summary.network_total_transactions - contents.size() as u64)
+    }
+}
+
+/// Load the summary and contents of the checkpoint with sequence number `cp`, which is expected to
+/// exist.
+async fn checkpoint(
+    ctx: &Context,
+    cp: u64,
+) -> Result<(CheckpointSummary, CheckpointContents), RpcError<Error>> {
+    let (summary, contents, _) = ctx
+        .kv_loader()
+        .load_one_checkpoint(cp)
+        .await
+        .context("Failed to load checkpoint")?
+        .with_context(|| format!("Checkpoint {cp} not found"))?;
+
+    Ok((summary, contents))
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::language_storage::StructTag;
+    use move_core_types::language_storage::TypeTag;
+
+    use super::*;
+
+    fn event(package: ObjectID, module: &str, type_: &str) -> Event {
+        Event {
+            package_id: package,
+            transaction_module: module.parse().unwrap(),
+            sender: MySoAddress::ZERO,
+            type_: type_.parse::<StructTag>().unwrap(),
+            contents: vec![],
+        }
+    }
+
+    fn entry(tx_sequence_number: u64, ix: u64) -> EventEntry {
+        EventEntry {
+            tx_sequence_number,
+            digest: TransactionDigest::random(),
+            ix,
+            event: Event::random_for_testing(),
+            timestamp_ms: None,
+        }
+    }
+
+    fn positions(entries: &[EventEntry]) -> Vec<(u64, u64)> {
+        entries
+            .iter()
+            .map(|e| (e.tx_sequence_number, e.ix))
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_module_and_type() {
+        let pkg = ObjectID::from_single_byte(0x42);
+        let other = ObjectID::from_single_byte(0x43);
+
+        // Emitted from `pkg::emitter`, with a type defined in `0x43::types`.
+        let ev = event(pkg, "emitter", "0x43::types::Ev<u64>");
+
+        assert!(matches(&EventFilter::All([]), &ev));
+        assert!(matches(
+            &EventFilter::Transaction(TransactionDigest::random()),
+            &ev
+        ));
+
+        // Module filters match on the function that emitted the event...
+        assert!(matches(
+            &EventFilter::MoveModule {
+                package: pkg,
+                module: "emitter".parse().unwrap(),
+            },
+            &ev,
+        ));
+        assert!(!matches(
+            &EventFilter::MoveModule {
+                package: other,
+                module: "emitter".parse().unwrap(),
+            },
+            &ev,
+        ));
+        assert!(!matches(
+            &EventFilter::MoveModule {
+                package: pkg,
+                module: "types".parse().unwrap(),
+            },
+            &ev,
+        ));
+
+        // ...while event module filters match on the module that defined its type.
+        assert!(matches(
+            &EventFilter::MoveEventModule {
+                package: other,
+                module: "types".parse().unwrap(),
+            },
+            &ev,
+        ));
+        assert!(!matches(
+            &EventFilter::MoveEventModule {
+                package: pkg,
+                module: "emitter".parse().unwrap(),
+            },
+            &ev,
+        ));
+
+        // Event type filters match the type exactly, including its type parameters.
+        assert!(matches(
+            &EventFilter::MoveEventType("0x43::types::Ev<u64>".parse().unwrap()),
+            &ev,
+        ));
+        assert!(!matches(
+            &EventFilter::MoveEventType("0x43::types::Ev<u8>".parse().unwrap()),
+            &ev,
+        ));
+        assert!(!matches(
+            &EventFilter::MoveEventType("0x43::types::Other<u64>".parse().unwrap()),
+            &ev,
+        ));
+
+        let ev = event(pkg, "emitter", "0x43::types::Ev");
+        assert!(!matches(
+            &EventFilter::MoveEventType(StructTag {
+                type_params: vec![TypeTag::U64],
+                ..ev.type_.clone()
+            }),
+            &ev,
+        ));
+    }
+
+    #[test]
+    fn test_matches_sender_and_any() {
+        let sender = MySoAddress::random_for_testing_only();
+        let mut ev = Event::random_for_testing();
+        ev.sender = sender;
+
+        assert!(matches(&EventFilter::Sender(sender), &ev));
+        assert!(!matches(
+            &EventFilter::Sender(MySoAddress::random_for_testing_only()),
+            &ev,
+        ));
+
+        let any = EventFilter::Any(vec![
+            EventFilter::Sender(MySoAddress::random_for_testing_only()),
+            EventFilter::Sender(sender),
+        ]);
+        assert!(matches(&any, &ev));
+
+        let none = EventFilter::Any(vec![
+            EventFilter::Sender(MySoAddress::random_for_testing_only()),
+            EventFilter::MoveEventType(StructTag {
+                name: "Other".parse().unwrap(),
+                ..ev.type_.clone()
+            }),
+        ]);
+        assert!(!matches(&none, &ev));
+        assert!(!matches(&EventFilter::Any(vec![]), &ev));
+    }
+
+    #[test]
+    fn test_after_cursor() {
+        // No cursor, or a cursor in another transaction.
+        assert!(after_cursor(None, 5, 0, false));
+        assert!(after_cursor(Some((4, 3)), 5, 0, false));
+        assert!(after_cursor(Some((6, 3)), 5, 0, true));
+
+        // The cursor itself is excluded in both directions.
+        assert!(!after_cursor(Some((5, 3)), 5, 3, false));
+        assert!(!after_cursor(Some((5, 3)), 5, 3, true));
+
+        assert!(after_cursor(Some((5, 3)), 5, 4, false));
+        assert!(!after_cursor(Some((5, 3)), 5, 2, false));
+        assert!(after_cursor(Some((5, 3)), 5, 2, true));
+        assert!(!after_cursor(Some((5, 3)), 5, 4, true));
+    }
+
+    #[test]
+    fn test_merge() {
+        let scans = || {
+            vec![
+                vec![entry(1, 0), entry(3, 1), entry(7, 0)],
+                vec![entry(1, 1), entry(3, 1), entry(4, 0)],
+                vec![],
+            ]
+        };
+
+        let asc = merge(scans(), false);
+        assert_eq!(
+            positions(&asc),
+            vec![(1, 0), (1, 1), (3, 1), (4, 0), (7, 0)],
+        );
+
+        let desc = merge(
+            scans()
+                .into_iter()
+                .map(|mut s| {
+                    s.reverse();
+                    s
+                })
+                .collect(),
+            true,
+        );
+        assert_eq!(
+            positions(&desc),
+            vec![(7, 0), (4, 0), (3, 1), (1, 1), (1, 0)],
+        );
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::future;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use myso_json_rpc_types::EventFilter;
+use myso_json_rpc_types::MySoEvent;
+use myso_json_rpc_types::Page;
+use myso_open_rpc::Module;
+use myso_open_rpc_macros::open_rpc;
+use myso_types::digests::TransactionDigest;
+use myso_types::event::EventID;
+
+use crate::api::rpc_module::RpcModule;
+use crate::context::Context;
+use crate::error::InternalContext;
+
+mod error;
+mod filter;
+mod response;
+
+#[open_rpc(namespace = "myso", tag = "Events API")]
+#[rpc(server, namespace = "myso")]
+trait EventsApi {
+    /// Fetch the events emitted by a transaction, in the order they were emitted.
+    #[method(name = "getEvents")]
+    async fn get_events(
+        &self,
+        /// The digest of the transaction whose events are being queried.
+        transaction_digest: TransactionDigest,
+    ) -> RpcResult<Vec<MySoEvent>>;
+}
+
+#[open_rpc(namespace = "mysox", tag = "Query Events API")]
+#[rpc(server, namespace = "mysox")]
+trait QueryEventsApi {
+    /// Query events based on their properties (sender, emitting module, type, etc). Returns a
+    /// paginated list of events.
+    ///
+    /// Events are ordered by the transaction that emitted them, and then by the order in which
+    /// they were emitted within that transaction. If a cursor is provided, the query will start
+    /// from the event after the one pointed to by this cursor, otherwise pagination starts from
+    /// the first event that meets the query criteria.
+    ///
+    /// The definition of "first" event is changed by the `descending_order` parameter, which is
+    /// optional, and defaults to false, meaning that the oldest event is shown first.
+    ///
+    /// The size of each page is controlled by the `limit` parameter.
+    #[method(name = "queryEvents")]
+    async fn query_events(
+        &self,
+        /// The query criteria.
+        query: EventFilter,
+        /// Cursor to start paginating from.
+        cursor: Option<EventID>,
+        /// Maximum number of events to return per page.
+        limit: Option<usize>,
+        /// Order of results, defaulting to ascending order (false), by sequence on-chain.
+        descending_order: Option<bool>,
+    ) -> RpcResult<Page<MySoEvent, EventID>>;
+}
+
+pub(crate) struct Events(pub Context);
+
+pub(crate) struct QueryEvents(pub Context);
+
+#[async_trait::async_trait]
+impl EventsApiServer for Events {
+    async fn get_events(&self, transaction_digest: TransactionDigest) -> RpcResult<Vec<MySoEvent>> {
+        let Self(ctx) = self;
+        Ok(response::transaction_events(ctx, transaction_digest)
+            .await
+            .with_internal_context(|| {
+                format!("Failed to get events for transaction {transaction_digest}")
+            })?)
+    }
+}
+
+#[async_trait::async_trait]
+impl QueryEventsApiServer for QueryEvents {
+    async fn query_events(
+        &self,
+        query: EventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<Page<MySoEvent, EventID>> {
+        let Self(ctx) = self;
+
+        let Page {
+            data: entries,
+            next_cursor,
+            has_next_page,
+        } = filter::events(ctx, &query, cursor, limit, descending_order).await?;
+
+        let data = future::join_all(
+            entries
+                .into_iter()
+                .map(|e| response::event(ctx, e.digest, e.ix, e.event, e.timestamp_ms)),
+        )
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .internal_context("Failed to convert events into responses")?;
+
+        Ok(Page {
+            data,
+            next_cursor: next_cursor.or(cursor),
+            has_next_page,
+        })
+    }
+}
+
+impl RpcModule for Events {
+    fn schema(&self) -> Module {
+        EventsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+impl RpcModule for QueryEvents {
+    fn schema(&self) -> Module {
+        QueryEventsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// Cursors are the same `EventID`s that fullnodes return, so that clients can page through
+    /// results from either.
+    #[test]
+    fn test_legacy_cursor_roundtrip() {
+        let digest = TransactionDigest::random();
+        let legacy = json!({
+            "txDigest": digest.to_string(),
+            "eventSeq": "3",
+        });
+
+        let cursor: EventID = serde_json::from_value(legacy.clone()).unwrap();
+        assert_eq!(
+            cursor,
+            EventID {
+                tx_digest: digest,
+                event_seq: 3,
+            }
+        );
+        assert_eq!(serde_json::to_value(cursor).unwrap(), legacy);
+
+        // Pages carry the cursor in the same format.
+        let page: Page<MySoEvent, EventID> = Page {
+            data: vec![],
+            next_cursor: Some(cursor),
+            has_next_page: false,
+        };
+        let page = serde_json::to_value(page).unwrap();
+        assert_eq!(page["nextCursor"], legacy);
+
+        let cursor: EventID = serde_json::from_value(page["nextCursor"].clone()).unwrap();
+        assert_eq!(cursor.event_seq, 3);
+    }
+
+    #[test]
+    fn test_legacy_cursor_rejects_numeric_seq() {
+        let legacy = json!({
+            "txDigest": TransactionDigest::random().to_string(),
+            "eventSeq": 3,
+        });
+        assert!(serde_json::from_value::<EventID>(legacy).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context as _;
+use futures::future::try_join_all;
+use move_core_types::annotated_value::MoveDatatypeLayout;
+use move_core_types::annotated_value::MoveTypeLayout;
+use myso_json_rpc_types::MySoEvent;
+use myso_types::digests::TransactionDigest;
+use myso_types::event::Event;
+
+use crate::api::events::error::Error;
+use crate::context::Context;
+use crate::error::RpcError;
+use crate::error::invalid_params;
+use crate::error::rpc_bail;
+
+/// Fetch all the events emitted by the transaction identified by `digest`, in the order they were
+/// emitted.
+pub(super) async fn transaction_events(
+    ctx: &Context,
+    digest: TransactionDigest,
+) -> Result<Vec<MySoEvent>, RpcError<Error>> {
+    let contents = ctx
+        .kv_loader()
+        .load_many_transaction_events(vec![digest])
+        .await
+        .context("Failed to fetch events from store")?;
+
+    let contents = contents
+        .get(&digest)
+        .ok_or_else(|| invalid_params(Error::NotFound(digest)))?;
+
+    let timestamp_ms = contents.timestamp_ms();
+    try_join_all(
+        contents
+            .events()?
+            .into_iter()
+            .enumerate()
+            .map(|(ix, e)| event(ctx, digest, ix as u64, e, timestamp_ms)),
+    )
+    .await
+}
+
+/// Convert the `ix`-th event emitted by transaction `digest` into a response, resolving its layout
+/// to render its contents as JSON.
+pub(super) async fn event(
+    ctx: &Context,
+    digest: TransactionDigest,
+    ix: u64,
+    event: Event,
+    timestamp_ms: Option<u64>,
+) -> Result<MySoEvent, RpcError<Error>> {
+    let layout = match ctx
+        .package_resolver()
+        .type_layout(event.type_.clone().into())
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to resolve layout for {}",
+                event.type_.to_canonical_display(/* with_prefix */ true)
+            )
+        })? {
+        MoveTypeLayout::Struct(s) => MoveDatatypeLayout::Struct(s),
+        MoveTypeLayout::Enum(e) => MoveDatatypeLayout::Enum(e),
+        _ => rpc_bail!(
+            "Event {ix} from {digest} is not a struct or enum: {}",
+            event.type_.to_canonical_string(/* with_prefix */ true)
+        ),
+    };
+
+    Ok(MySoEvent::try_from(event, digest, ix, timestamp_ms, layout)
+        .with_context(|| format!("Failed to convert Event {ix} from {digest} into response"))?)
+}
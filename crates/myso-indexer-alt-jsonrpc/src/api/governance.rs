@@ -2,23 +2,29 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use diesel::ExpressionMethods;
 use diesel::QueryDsl;
-
+use fastcrypto::traits::ToFromBytes;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::http_client::HttpClient;
 use jsonrpsee::proc_macros::rpc;
+use myso_indexer_alt_reader::epochs::EpochStartKey;
 use myso_indexer_alt_schema::schema::kv_epoch_starts;
 use myso_json_rpc_api::GovernanceReadApiClient;
 use myso_json_rpc_types::DelegatedStake;
+use myso_json_rpc_types::MySoCommittee;
 use myso_json_rpc_types::ValidatorApys;
 use myso_open_rpc::Module;
 use myso_open_rpc_macros::open_rpc;
 use myso_types::MYSO_SYSTEM_STATE_OBJECT_ID;
 use myso_types::TypeTag;
+use myso_types::base_types::AuthorityName;
 use myso_types::base_types::MySoAddress;
 use myso_types::base_types::ObjectID;
+use myso_types::committee::Committee;
 use myso_types::dynamic_field::Field;
 use myso_types::dynamic_field::derive_dynamic_field_id;
 use myso_types::myso_serde::BigInt;
@@ -28,11 +34,16 @@ use myso_types::myso_system_state::myso_system_state_inner_v1::MySoSystemStateIn
 use myso_types::myso_system_state::myso_system_state_inner_v2::MySoSystemStateInnerV2;
 use myso_types::myso_system_state::myso_system_state_summary::MySoSystemStateSummary;
 
+use crate::api::epochs::Error;
+use crate::api::epochs::latest_epoch;
+use crate::api::epochs::system_state_summary;
 use crate::api::rpc_module::RpcModule;
 use crate::context::Context;
 use crate::data::load_live_deserialized;
+use crate::error::InternalContext;
 use crate::error::RpcError;
 use crate::error::client_error_to_error_object;
+use crate::error::invalid_params;
 use crate::error::rpc_bail;
 
 #[open_rpc(namespace = "mysox", tag = "Governance API")]
@@ -45,6 +56,14 @@ trait GovernanceApi {
     /// Return a summary of the latest version of the MySo System State object (0x5), on-chain.
     #[method(name = "getLatestMySoSystemState")]
     async fn get_latest_myso_system_state(&self) -> RpcResult<MySoSystemStateSummary>;
+
+    /// Return the committee information for the asked `epoch`.
+    #[method(name = "getCommitteeInfo")]
+    async fn get_committee_info(
+        &self,
+        /// The epoch of interest. If None, default to the latest epoch
+        epoch: Option<BigInt<u64>>,
+    ) -> RpcResult<MySoCommittee>;
 }
 
 #[open_rpc(namespace = "mysox", tag = "Delegation Governance API")]
@@ -84,6 +103,12 @@ impl GovernanceApiServer for Governance {
     async fn get_latest_myso_system_state(&self) -> RpcResult<MySoSystemStateSummary> {
         Ok(latest_myso_system_state_response(&self.0).await?)
     }
+
+    async fn get_committee_info(&self, epoch: Option<BigInt<u64>>) -> RpcResult<MySoCommittee> {
+        Ok(committee_response(&self.0, epoch.map(|e| *e))
+            .await
+            .with_internal_context(|| format!("Failed to fetch committee for epoch {epoch:?}"))?)
+    }
 }
 
 #[async_trait::async_trait]
@@ -190,3 +215,34 @@ async fn latest_myso_system_state_response(
         v => rpc_bail!("Unexpected inner system state version: {v}"),
     })
 }
+
+/// Load data and generate response for `getCommitteeInfo`. The committee is made up of the active
+/// validators in the system state at the start of the epoch, weighted by their voting power.
+async fn committee_response(
+    ctx: &Context,
+    epoch: Option<u64>,
+) -> Result<MySoCommittee, RpcError<Error>> {
+    let epoch = match epoch {
+        Some(epoch) => epoch,
+        None => latest_epoch(ctx).await?,
+    };
+
+    let start = ctx
+        .pg_loader()
+        .load_one(EpochStartKey(epoch))
+        .await
+        .context("Failed to fetch epoch start information")?
+        .ok_or_else(|| invalid_params(Error::NotFound(epoch)))?;
+
+    let voting_rights = system_state_summary(&start)?
+        .active_validators
+        .into_iter()
+        .map(|v| {
+            let name = AuthorityName::from_bytes(&v.protocol_pubkey_bytes)
+                .context("Failed to parse validator protocol public key")?;
+            Ok((name, v.voting_power))
+        })
+        .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+
+    Ok(Committee::new(epoch, voting_rights).into())
+}
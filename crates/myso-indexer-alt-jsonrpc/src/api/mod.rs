@@ -5,9 +5,12 @@
 pub(crate) mod checkpoints;
 pub(crate) mod coin;
 pub(crate) mod dynamic_fields;
+pub(crate) mod epochs;
+pub(crate) mod events;
 pub(crate) mod governance;
 pub(crate) mod move_utils;
 pub(crate) mod objects;
+pub(crate) mod protocol_config;
 pub(crate) mod rpc_module;
 pub(crate) mod transactions;
 pub mod write;
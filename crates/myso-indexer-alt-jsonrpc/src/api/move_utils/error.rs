@@ -2,6 +2,8 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
+use myso_types::base_types::ObjectID;
+
 #[derive(thiserror::Error, Debug)]
 pub(super) enum Error {
     #[error("Invalid Move identifier: {0:?}")]
@@ -12,4 +14,7 @@ pub(super) enum Error {
 
     #[error("Type resolution limit reached: {0}")]
     ResolutionLimit(myso_package_resolver::error::Error),
+
+    #[error("Struct {0}::{1}::{2} not found")]
+    StructNotFound(ObjectID, String, String),
 }
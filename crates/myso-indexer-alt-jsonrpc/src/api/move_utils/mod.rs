@@ -4,7 +4,10 @@
 
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
+use myso_json_rpc_types::MoveFunctionArgType;
 use myso_json_rpc_types::MySoMoveNormalizedFunction;
+use myso_json_rpc_types::MySoMoveNormalizedModule;
+use myso_json_rpc_types::MySoMoveNormalizedStruct;
 use myso_open_rpc::Module;
 use myso_open_rpc_macros::open_rpc;
 use myso_types::base_types::ObjectID;
@@ -18,6 +21,23 @@ mod response;
 #[open_rpc(namespace = "myso", tag = "Move APIs")]
 #[rpc(server, namespace = "myso")]
 trait MoveApi {
+    /// Return a structured representation of a Move module.
+    #[method(name = "getNormalizedMoveModule")]
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> RpcResult<MySoMoveNormalizedModule>;
+
+    /// Return a structured representation of a Move struct.
+    #[method(name = "getNormalizedMoveStruct")]
+    async fn get_normalized_move_struct(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        struct_name: String,
+    ) -> RpcResult<MySoMoveNormalizedStruct>;
+
     #[method(name = "getNormalizedMoveFunction")]
     async fn get_normalized_move_function(
         &self,
@@ -25,12 +45,40 @@ trait MoveApi {
         module_name: String,
         function_name: String,
     ) -> RpcResult<MySoMoveNormalizedFunction>;
+
+    /// Return the argument types of a Move function, based on normalized Type.
+    #[method(name = "getMoveFunctionArgTypes")]
+    async fn get_move_function_arg_types(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+    ) -> RpcResult<Vec<MoveFunctionArgType>>;
 }
 
 pub(crate) struct MoveUtils(pub Context);
 
 #[async_trait::async_trait]
 impl MoveApiServer for MoveUtils {
+    async fn get_normalized_move_module(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> RpcResult<MySoMoveNormalizedModule> {
+        let Self(ctx) = self;
+        Ok(response::module(ctx, package, &module_name).await?)
+    }
+
+    async fn get_normalized_move_struct(
+        &self,
+        package: ObjectID,
+        module_name: String,
+        struct_name: String,
+    ) -> RpcResult<MySoMoveNormalizedStruct> {
+        let Self(ctx) = self;
+        Ok(response::struct_(ctx, package, &module_name, &struct_name).await?)
+    }
+
     async fn get_normalized_move_function(
         &self,
         package: ObjectID,
@@ -40,6 +88,16 @@ impl MoveApiServer for MoveUtils {
         let Self(ctx) = self;
         Ok(response::function(ctx, package, &module_name, &function_name).await?)
     }
+
+    async fn get_move_function_arg_types(
+        &self,
+        package: ObjectID,
+        module: String,
+        function: String,
+    ) -> RpcResult<Vec<MoveFunctionArgType>> {
+        let Self(ctx) = self;
+        Ok(response::function_arg_types(ctx, package, &module, &function).await?)
+    }
 }
 
 impl RpcModule for MoveUtils {
@@ -3,17 +3,24 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::anyhow;
+use move_binary_format::CompiledModule;
 use move_binary_format::file_format::Ability;
 use move_binary_format::file_format::AbilitySet;
 use move_binary_format::file_format::Visibility;
+use move_binary_format::normalized;
+use myso_json_rpc_types::MoveFunctionArgType;
 use myso_json_rpc_types::MySoMoveAbility;
 use myso_json_rpc_types::MySoMoveAbilitySet;
 use myso_json_rpc_types::MySoMoveNormalizedFunction;
+use myso_json_rpc_types::MySoMoveNormalizedModule;
+use myso_json_rpc_types::MySoMoveNormalizedStruct;
 use myso_json_rpc_types::MySoMoveNormalizedType;
 use myso_json_rpc_types::MySoMoveVisibility;
+use myso_json_rpc_types::ObjectValueKind;
 use myso_package_resolver::FunctionDef;
 use myso_package_resolver::OpenSignature;
 use myso_package_resolver::OpenSignatureBody;
+use myso_package_resolver::PackageStore as _;
 use myso_package_resolver::Reference;
 use myso_types::Identifier;
 use myso_types::base_types::ObjectID;
@@ -23,6 +30,59 @@ use crate::context::Context;
 use crate::error::RpcError;
 use crate::error::invalid_params;
 
+/// Load information about a module, and convert it into a JSON-RPC response.
+pub(super) async fn module(
+    ctx: &Context,
+    package: ObjectID,
+    module: &str,
+) -> Result<MySoMoveNormalizedModule, RpcError<Error>> {
+    if !Identifier::is_valid(module) {
+        return Err(invalid_params(Error::BadIdentifier(module.to_owned())));
+    }
+
+    let package = ctx
+        .package_resolver()
+        .package_store()
+        .fetch(*package)
+        .await
+        .map_err(resolver_error)?;
+
+    let module = package.module(module).map_err(resolver_error)?;
+    Ok(normalized_module(module.bytecode()))
+}
+
+/// Load information about a struct, and convert it into a JSON-RPC response.
+pub(super) async fn struct_(
+    ctx: &Context,
+    package: ObjectID,
+    module: &str,
+    name: &str,
+) -> Result<MySoMoveNormalizedStruct, RpcError<Error>> {
+    if !Identifier::is_valid(module) {
+        return Err(invalid_params(Error::BadIdentifier(module.to_owned())));
+    }
+
+    if !Identifier::is_valid(name) {
+        return Err(invalid_params(Error::BadIdentifier(name.to_owned())));
+    }
+
+    let pkg = ctx
+        .package_resolver()
+        .package_store()
+        .fetch(*package)
+        .await
+        .map_err(resolver_error)?;
+
+    let bytecode = pkg.module(module).map_err(resolver_error)?.bytecode();
+    normalized_struct(bytecode, name).ok_or_else(|| {
+        invalid_params(Error::StructNotFound(
+            package,
+            module.to_owned(),
+            name.to_owned(),
+        ))
+    })
+}
+
 /// Load information about a function, and convert it into a JSON-RPC response.
 pub(super) async fn function(
     ctx: &Context,
@@ -30,57 +90,106 @@ pub(super) async fn function(
     module: &str,
     name: &str,
 ) -> Result<MySoMoveNormalizedFunction, RpcError<Error>> {
-    use Error as E;
+    let sig = function_def(ctx, package, module, name).await?;
+    Ok(normalized_function(&sig))
+}
+
+/// Load a function's signature, and describe how each of its parameters can be passed as a
+/// transaction argument (as a pure value, or as an object by value or by reference).
+pub(super) async fn function_arg_types(
+    ctx: &Context,
+    package: ObjectID,
+    module: &str,
+    name: &str,
+) -> Result<Vec<MoveFunctionArgType>, RpcError<Error>> {
+    let sig = function_def(ctx, package, module, name).await?;
+    Ok(sig.parameters.iter().map(arg_type).collect())
+}
 
+async fn function_def(
+    ctx: &Context,
+    package: ObjectID,
+    module: &str,
+    name: &str,
+) -> Result<FunctionDef, RpcError<Error>> {
     if !Identifier::is_valid(module) {
-        return Err(invalid_params(E::BadIdentifier(module.to_owned())));
+        return Err(invalid_params(Error::BadIdentifier(module.to_owned())));
     }
 
     if !Identifier::is_valid(name) {
-        return Err(invalid_params(E::BadIdentifier(name.to_owned())));
+        return Err(invalid_params(Error::BadIdentifier(name.to_owned())));
     }
 
-    let sig = ctx
-        .package_resolver()
+    ctx.package_resolver()
         .function_signature(*package, module, name)
         .await
-        .map_err(|e| {
-            use myso_package_resolver::error::Error as PRE;
-            match &e {
-                // These errors can be triggered by passing a type that doesn't exist for the
-                // dynamic field name.
-                PRE::NotAPackage(_)
-                | PRE::PackageNotFound(_)
-                | PRE::ModuleNotFound(_, _)
-                | PRE::FunctionNotFound(_, _, _) => invalid_params(E::NotFound(e)),
-
-                // These errors can be triggered by requesting a type whose layout is too large
-                // (requires too may resources to resolve)
-                PRE::TooManyTypeNodes(_, _)
-                | PRE::TooManyTypeParams(_, _)
-                | PRE::TypeParamNesting(_, _) => invalid_params(E::ResolutionLimit(e)),
-
-                // The other errors are a form of internal error.
-                PRE::Bcs(_)
-                | PRE::Store { .. }
-                | PRE::DatatypeNotFound(_, _, _)
-                | PRE::Deserialize(_)
-                | PRE::EmptyPackage(_)
-                | PRE::LinkageNotFound(_)
-                | PRE::NoTypeOrigin(_, _, _)
-                | PRE::NotAnIdentifier(_)
-                | PRE::TypeArityMismatch(_, _)
-                | PRE::TypeParamOOB(_, _)
-                | PRE::UnexpectedReference
-                | PRE::UnexpectedSigner
-                | PRE::UnexpectedError(_)
-                | PRE::ValueNesting(_) => {
-                    RpcError::from(anyhow!(e).context("Failed to resolve type layout"))
-                }
-            }
-        })?;
+        .map_err(resolver_error)
+}
 
-    Ok(normalized_function(&sig))
+/// Map errors from the package resolver into user errors (for packages, modules and functions
+/// that don't exist, or types that are too large to resolve) or internal errors.
+fn resolver_error(e: myso_package_resolver::error::Error) -> RpcError<Error> {
+    use Error as E;
+    use myso_package_resolver::error::Error as PRE;
+
+    match &e {
+        // These errors can be triggered by requesting a package, module or function that doesn't
+        // exist.
+        PRE::NotAPackage(_)
+        | PRE::PackageNotFound(_)
+        | PRE::ModuleNotFound(_, _)
+        | PRE::FunctionNotFound(_, _, _) => invalid_params(E::NotFound(e)),
+
+        // These errors can be triggered by requesting a type whose layout is too large
+        // (requires too may resources to resolve)
+        PRE::TooManyTypeNodes(_, _)
+        | PRE::TooManyTypeParams(_, _)
+        | PRE::TypeParamNesting(_, _) => invalid_params(E::ResolutionLimit(e)),
+
+        // The other errors are a form of internal error.
+        PRE::Bcs(_)
+        | PRE::Store { .. }
+        | PRE::DatatypeNotFound(_, _, _)
+        | PRE::Deserialize(_)
+        | PRE::EmptyPackage(_)
+        | PRE::LinkageNotFound(_)
+        | PRE::NoTypeOrigin(_, _, _)
+        | PRE::NotAnIdentifier(_)
+        | PRE::TypeArityMismatch(_, _)
+        | PRE::TypeParamOOB(_, _)
+        | PRE::UnexpectedReference
+        | PRE::UnexpectedSigner
+        | PRE::UnexpectedError(_)
+        | PRE::ValueNesting(_) => {
+            RpcError::from(anyhow!(e).context("Failed to resolve type layout"))
+        }
+    }
+}
+
+/// Normalize `bytecode` the same way the fullnode does, leaving out function bodies.
+fn normalized_module(bytecode: &CompiledModule) -> MySoMoveNormalizedModule {
+    let pool = &mut normalized::RcPool::new();
+    let module = normalized::Module::new(pool, bytecode, /* include_code */ false);
+    (&module).into()
+}
+
+fn normalized_struct(bytecode: &CompiledModule, name: &str) -> Option<MySoMoveNormalizedStruct> {
+    let pool = &mut normalized::RcPool::new();
+    let module = normalized::Module::new(pool, bytecode, /* include_code */ false);
+    let struct_ = module.structs.get(name)?;
+    Some((&**struct_).into())
+}
+
+fn arg_type(sig: &OpenSignature) -> MoveFunctionArgType {
+    use MoveFunctionArgType as A;
+    use ObjectValueKind as K;
+
+    match (&sig.ref_, &sig.body) {
+        (Some(Reference::Immutable), _) => A::Object(K::ByImmutableReference),
+        (Some(Reference::Mutable), _) => A::Object(K::ByMutableReference),
+        (None, OpenSignatureBody::Datatype(_, _)) => A::Object(K::ByValue),
+        (None, _) => A::Pure,
+    }
 }
 
 fn normalized_function(sig: &FunctionDef) -> MySoMoveNormalizedFunction {
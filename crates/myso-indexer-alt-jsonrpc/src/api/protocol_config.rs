@@ -0,0 +1,172 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use myso_indexer_alt_schema::epochs::StoredFeatureFlag;
+use myso_indexer_alt_schema::epochs::StoredProtocolConfig;
+use myso_indexer_alt_schema::schema::kv_epoch_starts;
+use myso_indexer_alt_schema::schema::kv_feature_flags;
+use myso_indexer_alt_schema::schema::kv_protocol_configs;
+use myso_json_rpc_types::MySoProtocolConfigValue;
+use myso_json_rpc_types::ProtocolConfigResponse;
+use myso_open_rpc::Module;
+use myso_open_rpc_macros::open_rpc;
+use myso_protocol_config::ProtocolConfig;
+use myso_protocol_config::ProtocolConfigValue;
+use myso_protocol_config::ProtocolVersion;
+use myso_types::myso_serde::BigInt;
+
+use crate::api::rpc_module::RpcModule;
+use crate::context::Context;
+use crate::error::InternalContext;
+use crate::error::RpcError;
+use crate::error::invalid_params;
+
+#[open_rpc(namespace = "myso", tag = "Protocol Config API")]
+#[rpc(server, namespace = "myso")]
+trait ProtocolConfigApi {
+    /// Return the protocol config table for the given version number. If the version number is
+    /// not specified, the protocol version of the latest epoch is used.
+    #[method(name = "getProtocolConfig")]
+    async fn get_protocol_config(
+        &self,
+        /// An optional protocol version specifier. If omitted, the latest protocol config table
+        /// known to the RPC is returned.
+        version: Option<BigInt<u64>>,
+    ) -> RpcResult<ProtocolConfigResponse>;
+}
+
+pub(crate) struct ProtocolConfigs(pub Context);
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Protocol version {0} not found")]
+    NotFound(u64),
+}
+
+#[async_trait::async_trait]
+impl ProtocolConfigApiServer for ProtocolConfigs {
+    async fn get_protocol_config(
+        &self,
+        version: Option<BigInt<u64>>,
+    ) -> RpcResult<ProtocolConfigResponse> {
+        let Self(ctx) = self;
+        Ok(response(ctx, version.map(|v| *v))
+            .await
+            .with_internal_context(|| format!("Failed to fetch protocol config {version:?}"))?)
+    }
+}
+
+impl RpcModule for ProtocolConfigs {
+    fn schema(&self) -> Module {
+        ProtocolConfigApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Load the protocol configs and feature flags for `version` (or the latest epoch's protocol
+/// version) from the database.
+async fn response(
+    ctx: &Context,
+    version: Option<u64>,
+) -> Result<ProtocolConfigResponse, RpcError<Error>> {
+    use kv_epoch_starts::dsl as e;
+    use kv_feature_flags::dsl as f;
+    use kv_protocol_configs::dsl as p;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let version = match version {
+        Some(version) => version,
+        None => {
+            let version: i64 = conn
+                .first(
+                    e::kv_epoch_starts
+                        .select(e::protocol_version)
+                        .order(e::epoch.desc()),
+                )
+                .await
+                .context("Failed to fetch the latest protocol version")?;
+            version as u64
+        }
+    };
+
+    let configs: Vec<StoredProtocolConfig> = conn
+        .results(p::kv_protocol_configs.filter(p::protocol_version.eq(version as i64)))
+        .await
+        .context("Failed to fetch protocol configs")?;
+
+    let flags: Vec<StoredFeatureFlag> = conn
+        .results(f::kv_feature_flags.filter(f::protocol_version.eq(version as i64)))
+        .await
+        .context("Failed to fetch feature flags")?;
+
+    if configs.is_empty() && flags.is_empty() {
+        return Err(invalid_params(Error::NotFound(version)));
+    }
+
+    // The database stores config values as strings, so the binary's own protocol config is used
+    // to recover the type of each value.
+    // SAFETY: Only the types of the attributes are read from this config, not their values.
+    let types = ProtocolConfig::get_for_max_version_UNSAFE();
+
+    let attributes = configs
+        .into_iter()
+        .map(|c| {
+            let value = c
+                .config_value
+                .map(|v| config_value(types.lookup_attr(c.config_name.clone()), &v))
+                .transpose()
+                .with_context(|| format!("Failed to parse protocol config {}", c.config_name))?;
+            Ok((c.config_name, value))
+        })
+        .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+
+    let feature_flags = flags
+        .into_iter()
+        .map(|f| (f.flag_name, f.flag_value))
+        .collect();
+
+    Ok(ProtocolConfigResponse {
+        min_supported_protocol_version: ProtocolVersion::MIN,
+        max_supported_protocol_version: ProtocolVersion::MAX,
+        protocol_version: ProtocolVersion::new(version),
+        feature_flags,
+        attributes,
+    })
+}
+
+/// Parse a config `value` according to the type of `template`. Configs that are not set in the
+/// binary's config are parsed as booleans if possible, and as `u64`s otherwise.
+fn config_value(
+    template: Option<ProtocolConfigValue>,
+    value: &str,
+) -> anyhow::Result<MySoProtocolConfigValue> {
+    use MySoProtocolConfigValue as V;
+    use ProtocolConfigValue as T;
+
+    Ok(match template {
+        Some(T::u16(_)) => V::U16(value.parse()?),
+        Some(T::u32(_)) => V::U32(value.parse()?),
+        Some(T::u64(_)) => V::U64(value.parse()?),
+        Some(T::bool(_)) => V::Bool(value.parse()?),
+        None => match value.parse() {
+            Ok(b) => V::Bool(b),
+            Err(_) => V::U64(value.parse()?),
+        },
+    })
+}
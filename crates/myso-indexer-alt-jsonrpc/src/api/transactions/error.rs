@@ -7,6 +7,9 @@ use myso_types::digests::TransactionDigest;
 
 #[derive(thiserror::Error, Debug)]
 pub(super) enum Error {
+    #[error("Transaction {0} requested more than once")]
+    DuplicateKey(TransactionDigest),
+
     #[error("Cannot filter by function name {function:?} without specifying a module")]
     MissingModule { function: String },
 
@@ -24,4 +27,7 @@ pub(super) enum Error {
         .1.to_canonical_display(/* with_prefix */ true),
     )]
     PrunedObject(TransactionDigest, ObjectID, u64),
+
+    #[error("Requested {requested} keys, exceeding maximum {max}")]
+    TooManyKeys { requested: usize, max: usize },
 }
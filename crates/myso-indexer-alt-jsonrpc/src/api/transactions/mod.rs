@@ -2,6 +2,8 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
+
 use futures::future;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
@@ -18,6 +20,7 @@ use crate::api::transactions::filter::MySoTransactionBlockResponseQuery;
 use crate::context::Context;
 use crate::error::InternalContext;
 use crate::error::RpcError;
+use crate::error::invalid_params;
 use crate::error::rpc_bail;
 
 mod error;
@@ -36,6 +39,18 @@ trait TransactionsApi {
         /// Options controlling the output format.
         options: Option<MySoTransactionBlockResponseOptions>,
     ) -> RpcResult<MySoTransactionBlockResponse>;
+
+    /// Fetch a batch of transactions by their transaction digests. Responses are returned in the
+    /// same order as the digests in the request, and requesting a digest more than once is an
+    /// error.
+    #[method(name = "multiGetTransactionBlocks")]
+    async fn multi_get_transaction_blocks(
+        &self,
+        /// The digests of the queried transactions.
+        digests: Vec<TransactionDigest>,
+        /// Options controlling the output format.
+        options: Option<MySoTransactionBlockResponseOptions>,
+    ) -> RpcResult<Vec<MySoTransactionBlockResponse>>;
 }
 
 #[open_rpc(namespace = "mysox", tag = "Query Transactions API")]
@@ -84,6 +99,39 @@ impl TransactionsApiServer for Transactions {
                 .with_internal_context(|| format!("Failed to get transaction {digest}"))?,
         )
     }
+
+    async fn multi_get_transaction_blocks(
+        &self,
+        digests: Vec<TransactionDigest>,
+        options: Option<MySoTransactionBlockResponseOptions>,
+    ) -> RpcResult<Vec<MySoTransactionBlockResponse>> {
+        let Self(ctx) = self;
+        let config = &ctx.config().transactions;
+        if digests.len() > config.max_multi_get_transactions {
+            return Err(invalid_params(Error::TooManyKeys {
+                requested: digests.len(),
+                max: config.max_multi_get_transactions,
+            })
+            .into());
+        }
+
+        let mut seen = HashSet::new();
+        if let Some(d) = digests.iter().find(|d| !seen.insert(**d)) {
+            return Err(invalid_params(Error::DuplicateKey(*d)).into());
+        }
+
+        let options = options.unwrap_or_default();
+        let tx_futures = digests
+            .iter()
+            .map(|d| response::transaction(ctx, *d, &options));
+
+        Ok(future::join_all(tx_futures)
+            .await
+            .into_iter()
+            .zip(digests)
+            .map(|(r, d)| r.with_internal_context(|| format!("Failed to get transaction {d}")))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
 }
 
 #[async_trait::async_trait]
@@ -6,14 +6,19 @@ use fastcrypto::encoding::Base64;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::http_client::HttpClient;
 use jsonrpsee::proc_macros::rpc;
+use myso_json_rpc_types::DevInspectArgs;
+use myso_json_rpc_types::DevInspectResults;
 use myso_json_rpc_types::DryRunTransactionBlockResponse;
 use myso_json_rpc_types::MySoTransactionBlockResponse;
 use myso_json_rpc_types::MySoTransactionBlockResponseOptions;
 use myso_open_rpc::Module;
 use myso_open_rpc_macros::open_rpc;
+use myso_types::base_types::MySoAddress;
+use myso_types::myso_serde::BigInt;
 use myso_types::transaction_driver_types::ExecuteTransactionRequestType;
 
 use crate::api::rpc_module::RpcModule;
+use crate::config::NodeConfig;
 use crate::error::client_error_to_error_object;
 use crate::error::invalid_params;
 
@@ -36,6 +41,25 @@ pub trait WriteApi {
         request_type: Option<ExecuteTransactionRequestType>,
     ) -> RpcResult<MySoTransactionBlockResponse>;
 
+    /// Runs the transaction in dev-inspect mode. Which allows for nearly any
+    /// transaction (or Move call) with any arguments. Detailed results are
+    /// provided, including both the transaction effects and any return values.
+    /// The gas budget is capped by the RPC's configuration, and defaults to that cap if it is not
+    /// provided.
+    #[method(name = "devInspectTransactionBlock")]
+    async fn dev_inspect_transaction_block(
+        &self,
+        sender_address: MySoAddress,
+        /// BCS encoded TransactionKind(as opposed to TransactionData, which include gasBudget and gasPrice)
+        tx_bytes: Base64,
+        /// Gas is not charged, but gas usage is still calculated. Default to use reference gas price
+        gas_price: Option<BigInt<u64>>,
+        /// The epoch to perform the call. Will be set from the system state object if not provided
+        epoch: Option<BigInt<u64>>,
+        /// Additional arguments including gas_budget, gas_objects, gas_sponsor and skip_checks.
+        additional_args: Option<DevInspectArgs>,
+    ) -> RpcResult<DevInspectResults>;
+
     /// Return transaction execution effects including the gas cost summary,
     /// while the effects are not committed to the chain.
     #[method(name = "dryRunTransactionBlock")]
@@ -45,17 +69,20 @@ pub trait WriteApi {
     ) -> RpcResult<DryRunTransactionBlockResponse>;
 }
 
-pub(crate) struct Write(pub HttpClient);
+pub(crate) struct Write(pub HttpClient, pub NodeConfig);
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("WaitForLocalExecution mode is deprecated")]
     DeprecatedWaitForLocalExecution,
+
+    #[error("Gas budget {requested} exceeds the maximum dev-inspect gas budget of {max}")]
+    GasBudgetTooHigh { requested: u64, max: u64 },
 }
 
 impl Write {
-    pub(crate) fn new(client: HttpClient) -> Self {
-        Self(client)
+    pub(crate) fn new(client: HttpClient, config: NodeConfig) -> Self {
+        Self(client, config)
     }
 }
 
@@ -77,6 +104,40 @@ impl WriteApiServer for Write {
             .map_err(client_error_to_error_object)
     }
 
+    async fn dev_inspect_transaction_block(
+        &self,
+        sender_address: MySoAddress,
+        tx_bytes: Base64,
+        gas_price: Option<BigInt<u64>>,
+        epoch: Option<BigInt<u64>>,
+        additional_args: Option<DevInspectArgs>,
+    ) -> RpcResult<DevInspectResults> {
+        let max = self.1.max_dev_inspect_gas_budget;
+        let mut additional_args = additional_args.unwrap_or_default();
+        match additional_args.gas_budget {
+            Some(requested) if *requested > max => {
+                return Err(invalid_params(Error::GasBudgetTooHigh {
+                    requested: *requested,
+                    max,
+                })
+                .into());
+            }
+            Some(_) => {}
+            None => additional_args.gas_budget = Some(BigInt::from(max)),
+        }
+
+        self.0
+            .dev_inspect_transaction_block(
+                sender_address,
+                tx_bytes,
+                gas_price,
+                epoch,
+                Some(additional_args),
+            )
+            .await
+            .map_err(client_error_to_error_object)
+    }
+
     async fn dry_run_transaction_block(
         &self,
         tx_bytes: Base64,
@@ -26,6 +26,15 @@ pub struct RpcConfig {
     /// Configuration for coin-related RPC methods.
     pub coins: CoinsConfig,
 
+    /// Configuration for event-related RPC methods.
+    pub events: EventsConfig,
+
+    /// Configuration for checkpoint-related RPC methods.
+    pub checkpoints: CheckpointsConfig,
+
+    /// Configuration for epoch-related RPC methods.
+    pub epochs: EpochsConfig,
+
     /// Configuration for methods that require a fullnode RPC connection,
     /// including transaction execution, dry-running, and delegation coin queries etc.
     pub node: NodeConfig,
@@ -42,6 +51,9 @@ pub struct RpcLayer {
     pub dynamic_fields: DynamicFieldsLayer,
     pub transactions: TransactionsLayer,
     pub coins: CoinsLayer,
+    pub events: EventsLayer,
+    pub checkpoints: CheckpointsLayer,
+    pub epochs: EpochsLayer,
     pub node: NodeLayer,
     pub package_resolver: PackageResolverLayer,
 }
@@ -131,6 +143,9 @@ pub struct TransactionsConfig {
 
     /// The interval between tx_digest retry attempts in milliseconds.
     pub tx_retry_interval_ms: u64,
+
+    /// The maximum number of digests that can be queried in a single multi-get request.
+    pub max_multi_get_transactions: usize,
 }
 
 #[DefaultConfig]
@@ -141,6 +156,7 @@ pub struct TransactionsLayer {
     pub max_page_size: Option<usize>,
     pub tx_retry_count: Option<usize>,
     pub tx_retry_interval_ms: Option<u64>,
+    pub max_multi_get_transactions: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -161,12 +177,78 @@ pub struct CoinsLayer {
     pub max_page_size: Option<usize>,
 }
 
+#[derive(Debug, Clone)]
+pub struct EventsConfig {
+    /// The default page size limit when querying events, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying events. Requesting a page larger than this
+    /// is a user error.
+    pub max_page_size: usize,
+
+    /// The number of candidate transactions to fetch from the database in one go when scanning
+    /// for events that match a filter.
+    pub tx_scan_size: usize,
+
+    /// The maximum number of filters that can be combined in an `Any` event filter.
+    pub max_any_filters: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EventsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+    pub tx_scan_size: Option<usize>,
+    pub max_any_filters: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckpointsConfig {
+    /// The default page size limit when querying checkpoints, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying checkpoints. Requesting a page larger than
+    /// this is a user error.
+    pub max_page_size: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EpochsConfig {
+    /// The default page size limit when querying epochs, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying epochs. Requesting a page larger than this
+    /// is a user error.
+    pub max_page_size: usize,
+}
+
+#[DefaultConfig]
+#[derive(Clone, Default, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EpochsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+}
+
 #[derive(Clone, Debug)]
 pub struct NodeConfig {
     /// The value of the header to be sent to the fullnode RPC, used to distinguish between different instances.
     pub header_value: String,
     /// The maximum size of the request body allowed.
     pub max_request_size: u32,
+    /// The maximum gas budget that a dev-inspect request can ask for. Requests that don't specify
+    /// a budget are run with this budget.
+    pub max_dev_inspect_gas_budget: u64,
 }
 
 #[DefaultConfig]
@@ -175,6 +257,7 @@ pub struct NodeConfig {
 pub struct NodeLayer {
     pub header_value: Option<String>,
     pub max_request_size: Option<u32>,
+    pub max_dev_inspect_gas_budget: Option<u64>,
 }
 
 #[DefaultConfig]
@@ -196,6 +279,9 @@ impl RpcLayer {
             dynamic_fields: DynamicFieldsConfig::default().into(),
             transactions: TransactionsConfig::default().into(),
             coins: CoinsConfig::default().into(),
+            events: EventsConfig::default().into(),
+            checkpoints: CheckpointsConfig::default().into(),
+            epochs: EpochsConfig::default().into(),
             package_resolver: PackageResolverLayer::default(),
             node: NodeConfig::default().into(),
         }
@@ -207,6 +293,9 @@ impl RpcLayer {
             dynamic_fields: self.dynamic_fields.finish(DynamicFieldsConfig::default()),
             transactions: self.transactions.finish(TransactionsConfig::default()),
             coins: self.coins.finish(CoinsConfig::default()),
+            events: self.events.finish(EventsConfig::default()),
+            checkpoints: self.checkpoints.finish(CheckpointsConfig::default()),
+            epochs: self.epochs.finish(EpochsConfig::default()),
             node: self.node.finish(NodeConfig::default()),
             package_resolver: self.package_resolver.finish(),
         }
@@ -256,6 +345,9 @@ impl TransactionsLayer {
             tx_retry_interval_ms: self
                 .tx_retry_interval_ms
                 .unwrap_or(base.tx_retry_interval_ms),
+            max_multi_get_transactions: self
+                .max_multi_get_transactions
+                .unwrap_or(base.max_multi_get_transactions),
         }
     }
 }
@@ -269,6 +361,35 @@ impl CoinsLayer {
     }
 }
 
+impl EventsLayer {
+    pub fn finish(self, base: EventsConfig) -> EventsConfig {
+        EventsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+            tx_scan_size: self.tx_scan_size.unwrap_or(base.tx_scan_size),
+            max_any_filters: self.max_any_filters.unwrap_or(base.max_any_filters),
+        }
+    }
+}
+
+impl CheckpointsLayer {
+    pub fn finish(self, base: CheckpointsConfig) -> CheckpointsConfig {
+        CheckpointsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl EpochsLayer {
+    pub fn finish(self, base: EpochsConfig) -> EpochsConfig {
+        EpochsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
 impl NodeConfig {
     pub fn client(&self, fullnode_rpc_url: url::Url) -> anyhow::Result<HttpClient> {
         let mut headers = HeaderMap::new();
@@ -290,6 +411,9 @@ impl NodeLayer {
         NodeConfig {
             header_value: self.header_value.unwrap_or(base.header_value),
             max_request_size: self.max_request_size.unwrap_or(base.max_request_size),
+            max_dev_inspect_gas_budget: self
+                .max_dev_inspect_gas_budget
+                .unwrap_or(base.max_dev_inspect_gas_budget),
         }
     }
 }
@@ -312,6 +436,9 @@ impl Default for RpcConfig {
             dynamic_fields: DynamicFieldsConfig::default(),
             transactions: TransactionsConfig::default(),
             coins: CoinsConfig::default(),
+            events: EventsConfig::default(),
+            checkpoints: CheckpointsConfig::default(),
+            epochs: EpochsConfig::default(),
             node: NodeConfig::default(),
             package_resolver: PackageResolverLayer::default().finish(),
         }
@@ -351,6 +478,7 @@ impl Default for TransactionsConfig {
             max_page_size: 100,
             tx_retry_count: 5,
             tx_retry_interval_ms: 100,
+            max_multi_get_transactions: 50,
         }
     }
 }
@@ -364,11 +492,45 @@ impl Default for CoinsConfig {
     }
 }
 
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+            tx_scan_size: 200,
+            max_any_filters: 10,
+        }
+    }
+}
+
+impl Default for CheckpointsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl Default for EpochsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
 impl Default for NodeConfig {
     fn default() -> Self {
+        // SAFETY: Only used to pick a default limit, which is then enforced by the RPC on top of
+        // the fullnode's own limits.
+        let config = ProtocolConfig::get_for_max_version_UNSAFE();
+
         Self {
             header_value: "myso-indexer-alt-jsonrpc".to_string(),
             max_request_size: (10 * 2) << 20, // 10MB
+            max_dev_inspect_gas_budget: config.max_tx_gas(),
         }
     }
 }
@@ -422,6 +584,7 @@ impl From<TransactionsConfig> for TransactionsLayer {
             max_page_size: Some(config.max_page_size),
             tx_retry_count: Some(config.tx_retry_count),
             tx_retry_interval_ms: Some(config.tx_retry_interval_ms),
+            max_multi_get_transactions: Some(config.max_multi_get_transactions),
         }
     }
 }
@@ -435,11 +598,41 @@ impl From<CoinsConfig> for CoinsLayer {
     }
 }
 
+impl From<EventsConfig> for EventsLayer {
+    fn from(config: EventsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+            tx_scan_size: Some(config.tx_scan_size),
+            max_any_filters: Some(config.max_any_filters),
+        }
+    }
+}
+
+impl From<CheckpointsConfig> for CheckpointsLayer {
+    fn from(config: CheckpointsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+        }
+    }
+}
+
+impl From<EpochsConfig> for EpochsLayer {
+    fn from(config: EpochsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+        }
+    }
+}
+
 impl From<NodeConfig> for NodeLayer {
     fn from(config: NodeConfig) -> Self {
         Self {
             header_value: Some(config.header_value),
             max_request_size: Some(config.max_request_size),
+            max_dev_inspect_gas_budget: Some(config.max_dev_inspect_gas_budget),
         }
     }
 }
@@ -28,11 +28,15 @@ use url::Url;
 use crate::api::checkpoints::Checkpoints;
 use crate::api::coin::Coins;
 use crate::api::dynamic_fields::DynamicFields;
+use crate::api::epochs::Epochs;
+use crate::api::events::Events;
+use crate::api::events::QueryEvents;
 use crate::api::governance::DelegationGovernance;
 use crate::api::governance::Governance;
 use crate::api::move_utils::MoveUtils;
 use crate::api::objects::Objects;
 use crate::api::objects::QueryObjects;
+use crate::api::protocol_config::ProtocolConfigs;
 use crate::api::rpc_module::RpcModule;
 use crate::api::transactions::QueryTransactions;
 use crate::api::transactions::Transactions;
@@ -292,17 +296,21 @@ pub async fn start_rpc(
     rpc.add_module(Checkpoints(context.clone()))?;
     rpc.add_module(Coins(context.clone()))?;
     rpc.add_module(DynamicFields(context.clone()))?;
+    rpc.add_module(Epochs(context.clone()))?;
+    rpc.add_module(Events(context.clone()))?;
     rpc.add_module(Governance(context.clone()))?;
     rpc.add_module(MoveUtils(context.clone()))?;
     rpc.add_module(Objects(context.clone()))?;
+    rpc.add_module(ProtocolConfigs(context.clone()))?;
     rpc.add_module(QueryObjects(context.clone()))?;
+    rpc.add_module(QueryEvents(context.clone()))?;
     rpc.add_module(QueryTransactions(context.clone()))?;
     rpc.add_module(Transactions(context.clone()))?;
 
     if let Some(fullnode_rpc_url) = node_args.fullnode_rpc_url {
         let client = context.config().node.client(fullnode_rpc_url)?;
         rpc.add_module(DelegationGovernance::new(client.clone()))?;
-        rpc.add_module(Write::new(client))?;
+        rpc.add_module(Write::new(client, context.config().node.clone()))?;
     } else {
         warn!(
             "No fullnode rpc url provided, DelegationGovernance and Write modules will not be added."
@@ -64,8 +64,9 @@ impl SnapshotRestorer {
             &local_store_config,
             NonZeroUsize::new(args.concurrency).unwrap(),
             m,
-            true, // skip_reset_local_store
-            3,    // max_retries
+            true,  // skip_reset_local_store
+            3,     // max_retries
+            false, // stage_object_files
         )
         .await?;
         let db = Db::for_write(args.database_url.clone(), args.db_args.clone()).await?;
@@ -149,8 +150,9 @@ impl SnapshotRestorer {
                             &&bucket,
                             &part_num,
                             Some(512), // max_timeout_secs
+                            3,         // max_retries
                         )
-                        .await;
+                        .await?;
                         debug!(
                             bucket = bucket,
                             part_num = part_num,
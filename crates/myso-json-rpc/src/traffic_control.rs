@@ -3,14 +3,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use axum::extract::ConnectInfo;
+use fastcrypto::encoding::Base64;
 use futures::FutureExt;
 use jsonrpsee::MethodResponse;
 use jsonrpsee::server::middleware::rpc::RpcServiceT;
 use jsonrpsee::types::{ErrorCode, ErrorObject, Id};
-use myso_core::traffic_controller::{TrafficController, parse_ip, policies::TrafficTally};
+use myso_core::traffic_controller::{
+    TrafficController, parse_ip,
+    policies::{RequestInfo, TrafficTally},
+};
 use myso_json_rpc_api::TRANSACTION_EXECUTION_CLIENT_ERROR_CODE;
 use myso_types::traffic_control::ClientIdSource;
 use myso_types::traffic_control::Weight;
+use myso_types::transaction::{TransactionData, TransactionDataAPI};
 use std::net::IpAddr;
 use std::time::SystemTime;
 use std::{net::SocketAddr, sync::Arc};
@@ -18,6 +23,13 @@ use tracing::error;
 
 const TOO_MANY_REQUESTS_MSG: &str = "Too many requests";
 
+/// Methods whose first parameter is a BCS serialized `TransactionData`,
+/// from which the gas budget is read for cost-aware policies
+const TRANSACTION_METHODS: [&str; 2] = [
+    "myso_executeTransactionBlock",
+    "myso_dryRunTransactionBlock",
+];
+
 #[derive(Clone)]
 pub struct TrafficControllerService<S> {
     inner: S,
@@ -50,8 +62,9 @@ where
                 if let Err(response) = handle_traffic_req(&traffic_controller, &client).await {
                     response
                 } else {
+                    let request = request_info(&req);
                     let response = service.call(req).await;
-                    handle_traffic_resp(&traffic_controller, client, &response).await;
+                    handle_traffic_resp(&traffic_controller, client, request, &response).await;
                     response
                 }
             } else {
@@ -76,9 +89,26 @@ async fn handle_traffic_req(
     }
 }
 
+fn request_info(req: &jsonrpsee::types::Request<'_>) -> RequestInfo {
+    let method = req.method_name();
+    let gas_budget = TRANSACTION_METHODS
+        .contains(&method)
+        .then(|| {
+            let tx_bytes = req.params().sequence().next::<Base64>().ok()?;
+            let tx_data: TransactionData = bcs::from_bytes(&tx_bytes.to_vec().ok()?).ok()?;
+            Some(tx_data.gas_budget())
+        })
+        .flatten();
+    RequestInfo {
+        method: Some(method.to_string()),
+        gas_budget,
+    }
+}
+
 async fn handle_traffic_resp(
     traffic_controller: &Arc<TrafficController>,
     client: Option<IpAddr>,
+    request: RequestInfo,
     response: &MethodResponse,
 ) {
     let error = response.as_error_code().map(ErrorCode::from);
@@ -95,9 +125,11 @@ async fn handle_traffic_resp(
         // such as `myso_executeTransactionBlock`, as this can enable
         // node operators who wish to rate limit their transcation
         // traffic and incentivize high volume clients to choose a
-        // mysotable rpc provider (or run their own). Later we may want
-        // to provide a weight distribution based on the method being called.
+        // mysotable rpc provider (or run their own). Policies that
+        // weigh requests by method and gas budget, such as
+        // `TokenBucket`, use `request` instead.
         spam_weight: Weight::one(),
+        request,
         timestamp: SystemTime::now(),
     });
 }
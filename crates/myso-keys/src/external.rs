@@ -330,6 +330,68 @@ impl External {
     }
 }
 
+/// Checks that a signature over a hashed message was produced by the key of `address`.
+pub(crate) fn verify_hashed_signature(
+    signature: &Signature,
+    address: MySoAddress,
+    msg: &[u8],
+) -> Result<(), signature::Error> {
+    match signature {
+        Signature::Ed25519MySoSignature(s) => {
+            let (sig, pk) = s.get_verification_inputs().map_err(|e| {
+                signature::Error::from_source(anyhow!("Failed to get verification inputs: {}", e))
+            })?;
+            let signature_address = MySoAddress::from(&pk);
+            if signature_address != address {
+                return Err(signature::Error::from_source(anyhow!(
+                    "Signature address {} does not match expected address {}",
+                    signature_address,
+                    address
+                )));
+            }
+
+            pk.verify(msg, &sig).map_err(|e| {
+                signature::Error::from_source(anyhow!("Signature verification failed: {}", e))
+            })?;
+        }
+        Signature::Secp256k1MySoSignature(s) => {
+            let (sig, pk) = s.get_verification_inputs().map_err(|e| {
+                signature::Error::from_source(anyhow!("Failed to get verification inputs: {}", e))
+            })?;
+            let signature_address = MySoAddress::from(&pk);
+            if signature_address != address {
+                return Err(signature::Error::from_source(anyhow!(
+                    "Signature address {} does not match expected address {}",
+                    signature_address,
+                    address
+                )));
+            }
+
+            pk.verify(msg, &sig).map_err(|e| {
+                signature::Error::from_source(anyhow!("Signature verification failed: {}", e))
+            })?;
+        }
+        Signature::Secp256r1MySoSignature(s) => {
+            let (sig, pk) = s.get_verification_inputs().map_err(|e| {
+                signature::Error::from_source(anyhow!("Failed to get verification inputs: {}", e))
+            })?;
+            let signature_address = MySoAddress::from(&pk);
+            if signature_address != address {
+                return Err(signature::Error::from_source(anyhow!(
+                    "Signature address {} does not match expected address {}",
+                    signature_address,
+                    address
+                )));
+            }
+
+            pk.verify(msg, &sig).map_err(|e| {
+                signature::Error::from_source(anyhow!("Signature verification failed: {}", e))
+            })?;
+        }
+    }
+    Ok(())
+}
+
 impl Serialize for External {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -467,70 +529,7 @@ impl AccountKeystore for External {
             signature::Error::from_source(anyhow!("Failed to parse sign response: {}", e))
         })?;
 
-        let address = MySoAddress::from(&public_key);
-        match &result.signature {
-            Signature::Ed25519MySoSignature(s) => {
-                let (sig, pk) = s.get_verification_inputs().map_err(|e| {
-                    signature::Error::from_source(anyhow!(
-                        "Failed to get verification inputs: {}",
-                        e
-                    ))
-                })?;
-                let signature_address = MySoAddress::from(&pk);
-                if signature_address != address {
-                    return Err(signature::Error::from_source(anyhow!(
-                        "Signature address {} does not match expected address {}",
-                        signature_address,
-                        address
-                    )));
-                }
-
-                pk.verify(msg, &sig).map_err(|e| {
-                    signature::Error::from_source(anyhow!("Signature verification failed: {}", e))
-                })?;
-            }
-            Signature::Secp256k1MySoSignature(s) => {
-                let (sig, pk) = s.get_verification_inputs().map_err(|e| {
-                    signature::Error::from_source(anyhow!(
-                        "Failed to get verification inputs: {}",
-                        e
-                    ))
-                })?;
-                let signature_address = MySoAddress::from(&pk);
-                if signature_address != address {
-                    return Err(signature::Error::from_source(anyhow!(
-                        "Signature address {} does not match expected address {}",
-                        signature_address,
-                        address
-                    )));
-                }
-
-                pk.verify(msg, &sig).map_err(|e| {
-                    signature::Error::from_source(anyhow!("Signature verification failed: {}", e))
-                })?;
-            }
-            Signature::Secp256r1MySoSignature(s) => {
-                let (sig, pk) = s.get_verification_inputs().map_err(|e| {
-                    signature::Error::from_source(anyhow!(
-                        "Failed to get verification inputs: {}",
-                        e
-                    ))
-                })?;
-                let signature_address = MySoAddress::from(&pk);
-                if signature_address != address {
-                    return Err(signature::Error::from_source(anyhow!(
-                        "Signature address {} does not match expected address {}",
-                        signature_address,
-                        address
-                    )));
-                }
-
-                pk.verify(msg, &sig).map_err(|e| {
-                    signature::Error::from_source(anyhow!("Signature verification failed: {}", e))
-                })?;
-            }
-        }
-
+        verify_hashed_signature(&result.signature, MySoAddress::from(&public_key), msg)?;
         Ok(result.signature)
     }
 
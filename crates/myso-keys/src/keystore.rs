@@ -6,6 +6,7 @@ pub use crate::external::External;
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 use crate::key_identity::KeyIdentity;
 use crate::random_names::{random_name, random_names};
+pub use crate::remote::RemoteSigner;
 
 use anyhow::{Context, anyhow, bail, ensure};
 use async_trait::async_trait;
@@ -39,6 +40,7 @@ pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
     External(External),
+    Remote(RemoteSigner),
 }
 
 pub struct LocalGenerate {
@@ -225,6 +227,11 @@ impl Display for Keystore {
             Keystore::External(_external) => {
                 writeln!(writer, "Keystore Type : External")
             }
+            Keystore::Remote(remote) => {
+                writeln!(writer, "Keystore Type : Remote")?;
+                write!(writer, "Remote Signer : {}", remote.endpoint)?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -391,6 +398,11 @@ impl AccountKeystore for FileBasedKeystore {
 }
 
 impl FileBasedKeystore {
+    /// Path of the keystore file, if it is persisted.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
     pub fn load_or_create(path: &PathBuf) -> Result<Self, anyhow::Error> {
         let keys = if path.exists() {
             #[cfg(unix)]
@@ -564,7 +576,7 @@ impl FileBasedKeystore {
 }
 
 #[cfg(unix)]
-fn set_reduced_file_permissions(path: impl AsRef<Path>) -> Result<(), anyhow::Error> {
+pub(crate) fn set_reduced_file_permissions(path: impl AsRef<Path>) -> Result<(), anyhow::Error> {
     let path = path.as_ref();
     let metadata = fs::metadata(path)?;
     let mode = metadata.permissions().mode();
@@ -8,3 +8,6 @@ pub mod key_identity;
 pub mod keypair_file;
 pub mod keystore;
 pub mod random_names;
+pub mod remote;
+pub mod signing_policy;
+pub mod threshold;
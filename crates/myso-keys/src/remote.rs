@@ -0,0 +1,588 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Keystore for keys held by a local signing daemon, such as a service in front of an HSM.
+//! Private keys never reach the CLI: the keystore file only records the daemon endpoint and
+//! the public keys it indexed.
+//!
+//! # Protocol
+//!
+//! The CLI opens one connection per request to the daemon, either over a Unix domain socket
+//! (`unix:/run/myso-signer.sock`) or over TCP (`tcp:127.0.0.1:9300`), writes a single
+//! JSON-RPC 2.0 request terminated by a newline and reads a single response line. Methods:
+//!
+//! - `keys`, with `null` params, returns `{"keys": [{"key_id": .., "public_key": ..}]}` with
+//!   every key the daemon holds. Public keys are Base64 encoded `flag || pk`.
+//! - `public_key`, with a key id as params, returns `{"key_id": .., "public_key": ..}`.
+//! - `create_key`, with `null` params, returns a new key in the same form. Optional.
+//! - `sign`, with params `{"key_id": .., "msg": .., "intent": ..}`, returns
+//!   `{"signature": ..}`. `msg` is the Base64 BCS encoding of the value signed under
+//!   `intent`, such as the `TransactionData` of a transaction, so that the daemon can inspect
+//!   it before signing.
+//! - `sign_hashed`, with params `{"key_id": .., "msg": ..}`, signs a Base64 encoded digest.
+//!
+//! Daemons enforce their own policies, for instance with
+//! [`crate::signing_policy::SigningPolicy`], and refuse requests with the error code
+//! [`POLICY_VIOLATION_ERROR_CODE`]. Every signature is verified against the indexed public key
+//! before it is returned.
+
+use crate::external::{
+    ExternalKey, KeysResponse, SignRequest, SignResponse, verify_hashed_signature,
+};
+use crate::keystore::{
+    ALIASES_FILE_EXTENSION, AccountKeystore, Alias, GenerateOptions, GeneratedKey, validate_alias,
+};
+use crate::random_names::random_name;
+
+use anyhow::{Context, Error, anyhow, bail};
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose};
+use fastcrypto::traits::EncodeDecodeBase64;
+use jsonrpc::Endpoint;
+use jsonrpc::client::JsonRpcError;
+use myso_types::base_types::MySoAddress;
+use myso_types::crypto::{MySoKeyPair, PublicKey, Signature};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::json;
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// JSON-RPC error code a daemon returns when its policy refuses a request.
+pub const POLICY_VIOLATION_ERROR_CODE: i32 = -32001;
+
+/// Contents of the keystore file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct RemoteSignerFile {
+    endpoint: String,
+    #[serde(default)]
+    keys: BTreeMap<MySoAddress, ExternalKey>,
+}
+
+#[derive(Debug)]
+/// Keystore signing with keys held by a local signing daemon.
+pub struct RemoteSigner {
+    /// Address of the daemon, `unix:<path>` or `tcp:<host>:<port>`
+    pub endpoint: String,
+    /// Holds a map of addresses to aliases
+    pub aliases: BTreeMap<MySoAddress, Alias>,
+    /// Holds a map of addresses to the keys of the daemon
+    pub keys: BTreeMap<MySoAddress, ExternalKey>,
+    path: Option<PathBuf>,
+}
+
+impl RemoteSigner {
+    pub fn new(endpoint: String, path: Option<PathBuf>) -> Self {
+        Self {
+            endpoint,
+            aliases: BTreeMap::default(),
+            keys: BTreeMap::default(),
+            path,
+        }
+    }
+
+    /// Load the endpoint, keys and aliases from a keystore file. Unlike other keystores the
+    /// file must exist, since it names the daemon to use.
+    pub fn load(path: &PathBuf) -> Result<Self, Error> {
+        let file_store = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read remote signer keystore: {}", path.display()))?;
+        let file: RemoteSignerFile = serde_json::from_str(&file_store)
+            .with_context(|| format!("Cannot parse remote signer keystore: {}", path.display()))?;
+
+        let aliases_path = path.with_extension(ALIASES_FILE_EXTENSION);
+        let aliases: BTreeMap<MySoAddress, Alias> = if aliases_path.exists() {
+            let aliases_store = std::fs::read_to_string(&aliases_path)
+                .with_context(|| format!("Cannot read aliases file: {}", aliases_path.display()))?;
+            serde_json::from_str(&aliases_store)
+                .with_context(|| format!("Cannot parse aliases file: {}", aliases_path.display()))?
+        } else {
+            BTreeMap::default()
+        };
+
+        Ok(Self {
+            endpoint: file.endpoint,
+            aliases,
+            keys: file.keys,
+            path: Some(path.clone()),
+        })
+    }
+
+    /// Call `method` on the daemon.
+    pub async fn call<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: impl Serialize,
+    ) -> Result<R, Error> {
+        let result = if let Some(address) = self.endpoint.strip_prefix("tcp:") {
+            match tokio::net::TcpStream::connect(address).await {
+                Ok(stream) => call_over(stream, method, params).await,
+                Err(e) => Err(e.into()),
+            }
+        } else if let Some(path) = self.endpoint.strip_prefix("unix:") {
+            call_unix(path, method, params).await
+        } else {
+            bail!(
+                "Invalid remote signer endpoint {}, expected unix:<path> or tcp:<host>:<port>",
+                self.endpoint
+            )
+        };
+
+        result.map_err(|e| match e {
+            JsonRpcError::RemoteError(e) if e.code == POLICY_VIOLATION_ERROR_CODE => {
+                anyhow!("Remote signer policy refused the request: {}", e.message)
+            }
+            JsonRpcError::IoError(e) => {
+                anyhow!("Cannot reach remote signer at {}: {e}", self.endpoint)
+            }
+            e => anyhow!("Remote signer call {method} failed: {e}"),
+        })
+    }
+
+    /// Return all keys held by the daemon, indexed or not.
+    pub async fn available_keys(&self) -> Result<Vec<ExternalKey>, Error> {
+        let response: KeysResponse = self.call("keys", json![null]).await?;
+        Ok(response.keys)
+    }
+
+    /// Index a key of the daemon, so that it can be used by the CLI.
+    pub async fn add_existing(
+        &mut self,
+        key_id: String,
+        alias: Option<String>,
+    ) -> Result<ExternalKey, Error> {
+        let key: ExternalKey = self.call("public_key", json![key_id]).await?;
+        self.index(key.clone(), alias)?;
+        self.save().await?;
+        Ok(key)
+    }
+
+    pub fn is_indexed(&self, key: &ExternalKey) -> bool {
+        self.keys.contains_key(&MySoAddress::from(&key.public_key))
+    }
+
+    fn index(&mut self, key: ExternalKey, alias: Option<String>) -> Result<MySoAddress, Error> {
+        let address = MySoAddress::from(&key.public_key);
+        let alias = match self.aliases.get(&address) {
+            Some(existing) if alias.is_none() => existing.alias.clone(),
+            _ => self.create_alias(alias)?,
+        };
+        self.aliases.insert(
+            address,
+            Alias {
+                alias,
+                public_key_base64: key.public_key.encode_base64(),
+            },
+        );
+        self.keys.insert(address, key);
+        Ok(address)
+    }
+
+    fn key(&self, address: &MySoAddress) -> Result<&ExternalKey, signature::Error> {
+        self.keys.get(address).ok_or_else(|| {
+            signature::Error::from_source(anyhow!("Key corresponding to {address} not found"))
+        })
+    }
+
+    pub async fn save_aliases(&self) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            bail!("Path is not set for remote signer keystore");
+        };
+        let aliases_store = serde_json::to_string_pretty(&self.aliases)?;
+        let aliases_path = path.with_extension(ALIASES_FILE_EXTENSION);
+        tokio::task::spawn_blocking(move || std::fs::write(aliases_path, aliases_store))
+            .await?
+            .with_context(|| {
+                format!(
+                    "Cannot write aliases to file: {}",
+                    path.with_extension(ALIASES_FILE_EXTENSION).display()
+                )
+            })
+    }
+
+    pub async fn save_keystore(&self) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            bail!("Path is not set for remote signer keystore");
+        };
+        let store = serde_json::to_string_pretty(&RemoteSignerFile {
+            endpoint: self.endpoint.clone(),
+            keys: self.keys.clone(),
+        })?;
+        let keystore_path = path.clone();
+        tokio::task::spawn_blocking(move || std::fs::write(keystore_path, store))
+            .await?
+            .with_context(|| format!("Cannot write keystore to file: {}", path.display()))
+    }
+
+    pub async fn save(&self) -> Result<(), Error> {
+        self.save_aliases().await?;
+        self.save_keystore().await?;
+        Ok(())
+    }
+}
+
+async fn call_over<S, R>(stream: S, method: &str, params: impl Serialize) -> Result<R, JsonRpcError>
+where
+    S: AsyncRead + AsyncWrite,
+    R: DeserializeOwned,
+{
+    let (reader, writer) = tokio::io::split(stream);
+    Endpoint::new(reader, writer).call(method, params).await
+}
+
+#[cfg(unix)]
+async fn call_unix<R: DeserializeOwned>(
+    path: &str,
+    method: &str,
+    params: impl Serialize,
+) -> Result<R, JsonRpcError> {
+    let stream = tokio::net::UnixStream::connect(path).await?;
+    call_over(stream, method, params).await
+}
+
+#[cfg(not(unix))]
+async fn call_unix<R: DeserializeOwned>(
+    path: &str,
+    _method: &str,
+    _params: impl Serialize,
+) -> Result<R, JsonRpcError> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("Unix socket {path} is not supported on this platform"),
+    )
+    .into())
+}
+
+impl Serialize for RemoteSigner {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(
+            self.path
+                .as_ref()
+                .unwrap_or(&PathBuf::default())
+                .to_str()
+                .unwrap_or(""),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for RemoteSigner {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        RemoteSigner::load(&PathBuf::from(String::deserialize(deserializer)?))
+            .map_err(D::Error::custom)
+    }
+}
+
+#[async_trait]
+impl AccountKeystore for RemoteSigner {
+    /// Ask the daemon to create a new key and index it.
+    async fn generate(
+        &mut self,
+        alias: Option<String>,
+        _opts: GenerateOptions,
+    ) -> Result<GeneratedKey, Error> {
+        let key: ExternalKey = self.call("create_key", json![null]).await?;
+        let public_key = key.public_key.clone();
+        let address = self.index(key, alias)?;
+        self.save().await?;
+        Ok(GeneratedKey {
+            address,
+            scheme: public_key.scheme(),
+            public_key,
+        })
+    }
+
+    async fn import(&mut self, _alias: Option<String>, _keypair: MySoKeyPair) -> Result<(), Error> {
+        Err(anyhow!(
+            "Import not supported for remote signer keys, import the key into the signing daemon instead."
+        ))
+    }
+
+    async fn remove(&mut self, address: MySoAddress) -> Result<(), Error> {
+        self.aliases.remove(&address);
+        self.keys.remove(&address);
+        self.save().await?;
+        Ok(())
+    }
+
+    fn entries(&self) -> Vec<PublicKey> {
+        self.keys
+            .values()
+            .map(|key| key.public_key.clone())
+            .collect()
+    }
+
+    fn export(&self, _address: &MySoAddress) -> Result<&MySoKeyPair, Error> {
+        Err(anyhow!("Export not supported for remote signer keys."))
+    }
+
+    async fn sign_hashed(
+        &self,
+        address: &MySoAddress,
+        msg: &[u8],
+    ) -> Result<Signature, signature::Error> {
+        let key = self.key(address)?;
+        let sign_request = SignRequest {
+            key_id: key.key_id.clone(),
+            msg: general_purpose::STANDARD.encode(msg),
+            intent: None,
+        };
+        let result: SignResponse = self
+            .call("sign_hashed", sign_request)
+            .await
+            .map_err(signature::Error::from_source)?;
+
+        verify_hashed_signature(&result.signature, *address, msg)?;
+        Ok(result.signature)
+    }
+
+    async fn sign_secure<T>(
+        &self,
+        address: &MySoAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize + Sync,
+    {
+        let key = self.key(address)?;
+        let msg_bcs = bcs::to_bytes(msg).map_err(|e| {
+            signature::Error::from_source(anyhow!("Failed to serialize message: {}", e))
+        })?;
+        let sign_request = SignRequest {
+            key_id: key.key_id.clone(),
+            msg: general_purpose::STANDARD.encode(&msg_bcs),
+            intent: Some(intent.clone()),
+        };
+        let result: SignResponse = self
+            .call("sign", sign_request)
+            .await
+            .map_err(signature::Error::from_source)?;
+
+        result
+            .signature
+            .verify_secure(
+                &IntentMessage::new(intent, msg),
+                *address,
+                key.public_key.scheme(),
+            )
+            .map_err(|e| {
+                signature::Error::from_source(anyhow!("Signature verification failed: {}", e))
+            })?;
+        Ok(result.signature)
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&MySoAddress, &Alias)> {
+        self.aliases.iter().collect()
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        self.aliases.values().collect()
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        self.aliases.values_mut().collect()
+    }
+
+    fn get_alias(&self, address: &MySoAddress) -> Result<String, Error> {
+        match self.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    fn create_alias(&self, alias: Option<String>) -> Result<String, Error> {
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .aliases()
+                    .into_iter()
+                    .map(|x| x.alias.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    async fn update_alias(
+        &mut self,
+        old_alias: &str,
+        new_alias: Option<&str>,
+    ) -> Result<String, Error> {
+        let new_alias_name = self.update_alias_value(old_alias, new_alias)?;
+        self.save_aliases().await?;
+        Ok(new_alias_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{POLICY_VIOLATION_ERROR_CODE, RemoteSigner};
+    use crate::external::{ExternalKey, KeysResponse, SignRequest, SignResponse};
+    use crate::keystore::AccountKeystore;
+    use crate::signing_policy::SigningPolicy;
+    use base64::{Engine as _, engine::general_purpose};
+    use jsonrpc::types::{JsonRpcResult, RemoteError, Request, Response};
+    use myso_types::base_types::{MySoAddress, random_object_ref};
+    use myso_types::crypto::{MySoKeyPair, Signature, get_key_pair};
+    use myso_types::transaction::TransactionData;
+    use serde_json::{Value as JsonValue, json};
+    use shared_crypto::intent::{Intent, IntentMessage};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    const KEY_ID: &str = "treasury";
+
+    /// Serves the remote signer protocol for a single key, checking requests with `policy`.
+    async fn spawn_daemon(keypair: MySoKeyPair, policy: SigningPolicy) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("tcp:{}", listener.local_addr().unwrap());
+        let keypair = Arc::new(keypair);
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let (reader, mut writer) = stream.into_split();
+                let mut line = String::new();
+                BufReader::new(reader).read_line(&mut line).await.unwrap();
+                let request: Request<JsonValue> = serde_json::from_str(&line).unwrap();
+                let response = Response {
+                    jsonrpc: request.jsonrpc,
+                    id: request.id,
+                    result: match handle(&keypair, &policy, &request.method, request.params) {
+                        Ok(result) => JsonRpcResult::Ok { result },
+                        Err(error) => JsonRpcResult::Err { error },
+                    },
+                };
+                let mut response = serde_json::to_vec(&response).unwrap();
+                response.push(b'\n');
+                writer.write_all(&response).await.unwrap();
+            }
+        });
+        endpoint
+    }
+
+    fn handle(
+        keypair: &MySoKeyPair,
+        policy: &SigningPolicy,
+        method: &str,
+        params: JsonValue,
+    ) -> Result<JsonValue, RemoteError> {
+        let key = ExternalKey {
+            public_key: keypair.public(),
+            key_id: KEY_ID.to_string(),
+        };
+        let refuse = |e: anyhow::Error| RemoteError {
+            code: POLICY_VIOLATION_ERROR_CODE,
+            message: e.to_string(),
+            data: None,
+        };
+        Ok(match method {
+            "keys" => json!(KeysResponse { keys: vec![key] }),
+            "public_key" => json!(key),
+            "sign" => {
+                let request: SignRequest = serde_json::from_value(params).unwrap();
+                let intent = request.intent.unwrap();
+                let msg = general_purpose::STANDARD.decode(request.msg).unwrap();
+                policy.check(&intent, &msg).map_err(refuse)?;
+                let tx_data: TransactionData = bcs::from_bytes(&msg).unwrap();
+                json!(SignResponse {
+                    signature: Signature::new_secure(&IntentMessage::new(intent, tx_data), keypair),
+                })
+            }
+            _ => {
+                return Err(RemoteError {
+                    code: -32601,
+                    message: format!("Method {method} not found"),
+                    data: None,
+                });
+            }
+        })
+    }
+
+    fn transfer(sender: MySoAddress, amount: u64) -> TransactionData {
+        TransactionData::new_transfer_myso(
+            MySoAddress::random_for_testing_only(),
+            sender,
+            Some(amount),
+            random_object_ref(),
+            1_000,
+            1000,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_index_and_sign() {
+        let (address, keypair) = get_key_pair();
+        let keypair = MySoKeyPair::Ed25519(keypair);
+        let policy = SigningPolicy {
+            max_spend_per_transaction: Some(10_000),
+            ..Default::default()
+        };
+        let endpoint = spawn_daemon(keypair, policy).await;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("remote.keystore");
+        RemoteSigner::new(endpoint, Some(path.clone()))
+            .save()
+            .await
+            .unwrap();
+        let mut keystore = RemoteSigner::load(&path).unwrap();
+        assert!(keystore.addresses().is_empty());
+
+        let keys = keystore.available_keys().await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert!(!keystore.is_indexed(&keys[0]));
+        keystore
+            .add_existing(KEY_ID.to_string(), Some("treasury".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(keystore.addresses(), vec![address]);
+
+        // The indexed key survives a reload, and signs within the policy of the daemon.
+        let keystore = RemoteSigner::load(&path).unwrap();
+        assert_eq!(keystore.get_alias(&address).unwrap(), "treasury");
+        keystore
+            .sign_secure(
+                &address,
+                &transfer(address, 5_000),
+                Intent::myso_transaction(),
+            )
+            .await
+            .unwrap();
+
+        let err = keystore
+            .sign_secure(
+                &address,
+                &transfer(address, 50_000),
+                Intent::myso_transaction(),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{err:?}").contains("policy refused"),
+            "unexpected error: {err:?}"
+        );
+
+        assert!(keystore.sign_hashed(&address, b"digest").await.is_err());
+        assert!(keystore.export(&address).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_endpoint() {
+        let keystore = RemoteSigner::new("http://localhost:9300".to_string(), None);
+        assert!(keystore.available_keys().await.is_err());
+    }
+}
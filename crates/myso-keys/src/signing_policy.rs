@@ -0,0 +1,413 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing policies for remote signing daemons.
+//!
+//! A daemon serving the protocol in [`crate::remote`] receives the full BCS encoded message
+//! with its intent, so it can decide what it is willing to sign. [`SigningPolicy`] is the
+//! policy daemons written in Rust can load from their configuration and check every `sign`
+//! request against.
+
+use std::str::FromStr;
+
+use anyhow::{Error, anyhow, bail, ensure};
+use myso_types::base_types::ObjectID;
+use myso_types::transaction::{
+    Argument, CallArg, Command, ProgrammableMoveCall, ProgrammableTransaction, TransactionData,
+    TransactionDataAPI, TransactionKind,
+};
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentScope};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct SigningPolicy {
+    /// Move functions transactions may call, as `package::module::function`. The module and
+    /// function can be `*` to allow any. Transactions calling other functions are refused.
+    pub allowed_move_targets: Vec<String>,
+    /// Whether transactions may publish or upgrade packages.
+    pub allow_publish: bool,
+    /// Whether personal messages may be signed.
+    pub allow_personal_messages: bool,
+    /// Whether pre-hashed messages may be signed. Their content cannot be checked, so this
+    /// bypasses every other rule.
+    pub allow_sign_hashed: bool,
+    /// Maximum gas budget of a transaction.
+    pub max_gas_budget: Option<u64>,
+    /// Maximum amount a transaction may spend from its gas coin, gas budget included. Only
+    /// coins split from the gas coin by a constant amount may then be transferred, since the
+    /// value of other objects cannot be known without reading the chain, and only pure values
+    /// may be passed to Move calls, splits, merges or vectors.
+    pub max_spend_per_transaction: Option<u64>,
+}
+
+impl SigningPolicy {
+    /// Checks a `sign` request: `msg` is the BCS encoded value signed under `intent`.
+    pub fn check(&self, intent: &Intent, msg: &[u8]) -> Result<(), Error> {
+        match intent.scope {
+            IntentScope::TransactionData => {
+                let tx_data: TransactionData = bcs::from_bytes(msg)
+                    .map_err(|e| anyhow!("Cannot deserialize transaction data: {e}"))?;
+                self.check_transaction(&tx_data)
+            }
+            IntentScope::PersonalMessage => {
+                ensure!(
+                    self.allow_personal_messages,
+                    "Signing personal messages is not allowed"
+                );
+                Ok(())
+            }
+            scope => bail!("Signing messages with intent scope {scope:?} is not allowed"),
+        }
+    }
+
+    /// Checks a `sign_hashed` request.
+    pub fn check_hashed(&self) -> Result<(), Error> {
+        ensure!(
+            self.allow_sign_hashed,
+            "Signing pre-hashed messages is not allowed"
+        );
+        Ok(())
+    }
+
+    pub fn check_transaction(&self, tx_data: &TransactionData) -> Result<(), Error> {
+        let gas_budget = tx_data.gas_budget();
+        if let Some(max_gas_budget) = self.max_gas_budget {
+            ensure!(
+                gas_budget <= max_gas_budget,
+                "Gas budget {gas_budget} exceeds the maximum of {max_gas_budget}"
+            );
+        }
+        let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind() else {
+            bail!("Only programmable transactions can be signed");
+        };
+
+        for command in &pt.commands {
+            match command {
+                Command::MoveCall(call) => ensure!(
+                    self.allows_move_call(call)?,
+                    "Calling {}::{}::{} is not allowed",
+                    call.package,
+                    call.module,
+                    call.function
+                ),
+                Command::Publish(..) | Command::Upgrade(..) => ensure!(
+                    self.allow_publish,
+                    "Publishing or upgrading packages is not allowed"
+                ),
+                _ => (),
+            }
+        }
+
+        if let Some(max_spend) = self.max_spend_per_transaction {
+            let spend = gas_coin_spend(pt)?
+                .checked_add(gas_budget)
+                .ok_or_else(|| anyhow!("Transaction spend overflows"))?;
+            ensure!(
+                spend <= max_spend,
+                "Transaction spends up to {spend}, more than the maximum of {max_spend}"
+            );
+        }
+        Ok(())
+    }
+
+    fn allows_move_call(&self, call: &ProgrammableMoveCall) -> Result<bool, Error> {
+        for target in &self.allowed_move_targets {
+            let mut parts = target.split("::");
+            let (Some(package), Some(module), Some(function), None) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                bail!("Invalid Move target {target}, expected package::module::function");
+            };
+            let package = ObjectID::from_str(package)
+                .map_err(|e| anyhow!("Invalid package in Move target {target}: {e}"))?;
+            if package == call.package
+                && (module == "*" || module == call.module)
+                && (function == "*" || function == call.function)
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Returns the amount split from the gas coin. Fails if a transaction may move value that
+/// cannot be bounded this way.
+fn gas_coin_spend(pt: &ProgrammableTransaction) -> Result<u64, Error> {
+    let mut spend: u64 = 0;
+    let mut gas_splits = vec![];
+    for (index, command) in pt.commands.iter().enumerate() {
+        match command {
+            Command::SplitCoins(Argument::GasCoin, amounts) => {
+                for amount in amounts {
+                    let amount = match amount {
+                        Argument::Input(input) => match pt.inputs.get(*input as usize) {
+                            Some(CallArg::Pure(bytes)) => bcs::from_bytes::<u64>(bytes).ok(),
+                            _ => None,
+                        },
+                        _ => None,
+                    }
+                    .ok_or_else(|| anyhow!("Gas coin split amounts must be constant"))?;
+                    spend = spend
+                        .checked_add(amount)
+                        .ok_or_else(|| anyhow!("Transaction spend overflows"))?;
+                }
+                gas_splits.push(index as u16);
+            }
+            Command::SplitCoins(coin, amounts) => {
+                check_gas_free(pt, &gas_splits, std::slice::from_ref(coin))?;
+                check_gas_free(pt, &gas_splits, amounts)?;
+            }
+            Command::MoveCall(call) => check_gas_free(pt, &gas_splits, &call.arguments)?,
+            Command::MergeCoins(target, sources) => {
+                check_gas_free(pt, &gas_splits, std::slice::from_ref(target))?;
+                check_gas_free(pt, &gas_splits, sources)?;
+            }
+            Command::MakeMoveVec(_, elements) => check_gas_free(pt, &gas_splits, elements)?,
+            Command::TransferObjects(objects, _) => {
+                for object in objects {
+                    let from_gas_split = match object {
+                        Argument::Result(result) | Argument::NestedResult(result, _) => {
+                            gas_splits.contains(result)
+                        }
+                        _ => false,
+                    };
+                    ensure!(
+                        from_gas_split,
+                        "Only coins split from the gas coin can be transferred under a spending cap"
+                    );
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(spend)
+}
+
+/// Fails if any of `args` is the gas coin, a non-pure input such as an object or a funds
+/// withdrawal, or a coin split from the gas coin, since the value a command takes from them
+/// cannot be bounded.
+fn check_gas_free(
+    pt: &ProgrammableTransaction,
+    gas_splits: &[u16],
+    args: &[Argument],
+) -> Result<(), Error> {
+    for arg in args {
+        let gas_free = match arg {
+            Argument::GasCoin => false,
+            Argument::Input(input) => {
+                matches!(pt.inputs.get(*input as usize), Some(CallArg::Pure(_)))
+            }
+            Argument::Result(result) | Argument::NestedResult(result, _) => {
+                !gas_splits.contains(result)
+            }
+        };
+        ensure!(
+            gas_free,
+            "Coins and objects can only be passed to transfers under a spending cap"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SigningPolicy;
+    use myso_types::base_types::{MySoAddress, ObjectID, random_object_ref};
+    use myso_types::gas_coin::GAS;
+    use myso_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use myso_types::transaction::{Argument, FundsWithdrawalArg, TransactionData};
+    use shared_crypto::intent::Intent;
+    use std::str::FromStr;
+
+    fn transfer(amount: Option<u64>, gas_budget: u64) -> TransactionData {
+        TransactionData::new_transfer_myso(
+            MySoAddress::random_for_testing_only(),
+            MySoAddress::random_for_testing_only(),
+            amount,
+            random_object_ref(),
+            gas_budget,
+            1000,
+        )
+    }
+
+    fn move_call(module: &str, function: &str) -> TransactionData {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .move_call(
+                ObjectID::from_str("0x2").unwrap(),
+                module.parse().unwrap(),
+                function.parse().unwrap(),
+                vec![],
+                vec![],
+            )
+            .unwrap();
+        TransactionData::new_programmable(
+            MySoAddress::random_for_testing_only(),
+            vec![random_object_ref()],
+            builder.finish(),
+            1_000,
+            1000,
+        )
+    }
+
+    #[test]
+    fn test_spending_cap() {
+        let policy = SigningPolicy {
+            max_spend_per_transaction: Some(10_000),
+            ..Default::default()
+        };
+        assert!(
+            policy
+                .check_transaction(&transfer(Some(9_000), 1_000))
+                .is_ok()
+        );
+        assert!(
+            policy
+                .check_transaction(&transfer(Some(9_001), 1_000))
+                .is_err()
+        );
+        // Transferring the whole gas coin cannot be bounded.
+        assert!(policy.check_transaction(&transfer(None, 1_000)).is_err());
+        // Without a cap, anything goes.
+        assert!(
+            SigningPolicy::default()
+                .check_transaction(&transfer(None, 1_000))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_spending_cap_move_calls() {
+        let policy = SigningPolicy {
+            allowed_move_targets: vec!["0x2::pay::*".to_string()],
+            max_spend_per_transaction: Some(10_000),
+            ..Default::default()
+        };
+        assert!(policy.check_transaction(&move_call("pay", "split")).is_ok());
+
+        // Passing the gas coin to a Move call could spend all of it.
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let amount = builder.pure(u64::MAX).unwrap();
+        let recipient = builder
+            .pure(MySoAddress::random_for_testing_only())
+            .unwrap();
+        builder.programmable_move_call(
+            ObjectID::from_str("0x2").unwrap(),
+            "pay".parse().unwrap(),
+            "split_and_transfer".parse().unwrap(),
+            vec![],
+            vec![Argument::GasCoin, amount, recipient],
+        );
+        let tx_data = TransactionData::new_programmable(
+            MySoAddress::random_for_testing_only(),
+            vec![random_object_ref()],
+            builder.finish(),
+            1_000,
+            1000,
+        );
+        assert!(policy.check_transaction(&tx_data).is_err());
+        // Without a cap, the call is allowed.
+        let uncapped = SigningPolicy {
+            max_spend_per_transaction: None,
+            ..policy
+        };
+        assert!(uncapped.check_transaction(&tx_data).is_ok());
+
+        // Funds withdrawn from the address balance are not counted against the cap either.
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let withdrawal = builder
+            .funds_withdrawal(FundsWithdrawalArg::balance_from_sender(
+                u64::MAX,
+                GAS::type_tag(),
+            ))
+            .unwrap();
+        let recipient = builder
+            .pure(MySoAddress::random_for_testing_only())
+            .unwrap();
+        builder.programmable_move_call(
+            ObjectID::from_str("0x2").unwrap(),
+            "pay".parse().unwrap(),
+            "transfer_balance".parse().unwrap(),
+            vec![],
+            vec![withdrawal, recipient],
+        );
+        let tx_data = TransactionData::new_programmable(
+            MySoAddress::random_for_testing_only(),
+            vec![random_object_ref()],
+            builder.finish(),
+            1_000,
+            1000,
+        );
+        assert!(uncapped.check_transaction(&tx_data).is_ok());
+        let capped = SigningPolicy {
+            max_spend_per_transaction: Some(10_000),
+            ..uncapped
+        };
+        assert!(capped.check_transaction(&tx_data).is_err());
+    }
+
+    #[test]
+    fn test_max_gas_budget() {
+        let policy = SigningPolicy {
+            max_gas_budget: Some(1_000),
+            ..Default::default()
+        };
+        assert!(policy.check_transaction(&transfer(Some(1), 1_000)).is_ok());
+        assert!(policy.check_transaction(&transfer(Some(1), 1_001)).is_err());
+    }
+
+    #[test]
+    fn test_allowed_move_targets() {
+        let policy = SigningPolicy {
+            allowed_move_targets: vec!["0x2::coin::join".to_string(), "0x2::pay::*".to_string()],
+            ..Default::default()
+        };
+        assert!(policy.check_transaction(&move_call("coin", "join")).is_ok());
+        assert!(policy.check_transaction(&move_call("pay", "split")).is_ok());
+        assert!(
+            policy
+                .check_transaction(&move_call("coin", "split"))
+                .is_err()
+        );
+        assert!(
+            SigningPolicy::default()
+                .check_transaction(&move_call("coin", "join"))
+                .is_err()
+        );
+
+        let invalid = SigningPolicy {
+            allowed_move_targets: vec!["0x2::coin".to_string()],
+            ..Default::default()
+        };
+        assert!(
+            invalid
+                .check_transaction(&move_call("coin", "join"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_intents() {
+        let policy = SigningPolicy::default();
+        let tx_data = transfer(Some(1), 1_000);
+        assert!(
+            policy
+                .check(
+                    &Intent::myso_transaction(),
+                    &bcs::to_bytes(&tx_data).unwrap()
+                )
+                .is_ok()
+        );
+        assert!(policy.check(&Intent::personal_message(), b"hello").is_err());
+        assert!(policy.check_hashed().is_err());
+
+        let policy = SigningPolicy {
+            allow_personal_messages: true,
+            ..Default::default()
+        };
+        assert!(policy.check(&Intent::personal_message(), b"hello").is_ok());
+    }
+}
@@ -0,0 +1,877 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Threshold Ed25519 keys: an m-of-n key whose signatures are plain Ed25519 signatures, so the
+//! key looks like a single key on chain.
+//!
+//! Keys are created with a Pedersen distributed key generation, where no participant ever
+//! holds the whole private key, and signed with FROST(Ed25519, SHA-512) as specified in
+//! RFC 9591. Both take rounds of messages between participants, exchanged as Base64 encoded
+//! BCS packages:
+//!
+//! 1. `dkg_round1`: every participant broadcasts a [`DkgRound1Package`].
+//! 2. `dkg_round2`: every participant checks the other round 1 packages and sends each other
+//!    participant a [`DkgRound2Package`]. These carry secret shares and must be sent over a
+//!    confidential channel.
+//! 3. `dkg_finalize`: every participant checks the round 2 packages addressed to it, and ends
+//!    up with a [`KeyShare`] of the same group key.
+//!
+//! To sign, at least `threshold` participants `commit` to single use nonces, `sign` the message
+//! given all the [`SigningCommitments`], and anyone holding a key share can `aggregate` the
+//! [`SignatureShare`]s into the signature.
+//!
+//! Secret state is kept in a [`ThresholdKeystore`], a JSON file stored next to the file based
+//! keystore and readable by its owner only, like its aliases file. Key shares are not kept in
+//! the keystore itself: a share cannot sign on its own, so it is not a [`MySoKeyPair`], and
+//! older clients would fail to load a keystore holding entries they cannot parse. The file
+//! also holds DKGs in progress and single use nonces, which change with every signature.
+//!
+//! [`MySoKeyPair`]: myso_types::crypto::MySoKeyPair
+
+#[cfg(unix)]
+use crate::keystore::set_reduced_file_permissions;
+
+use anyhow::{Context, Error, anyhow, bail, ensure};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{Identity, IsIdentity};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::{HashFunction, Sha512};
+use fastcrypto::traits::ToFromBytes;
+use myso_types::base_types::MySoAddress;
+use myso_types::crypto::{DefaultHash, PublicKey, Signature, SignatureScheme};
+use rand::RngCore;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use shared_crypto::intent::IntentMessage;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const THRESHOLD_FILE_EXTENSION: &str = "threshold";
+
+/// Context string of FROST(Ed25519, SHA-512).
+const CONTEXT_STRING: &[u8] = b"FROST-ED25519-SHA512-v1";
+
+/// A scalar, encoded as Base64 in JSON.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct EncodedScalar(Scalar);
+
+/// A compressed Edwards point, encoded as Base64 in JSON.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct EncodedPoint(EdwardsPoint);
+
+fn serialize_bytes<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&Base64::encode(bytes))
+    } else {
+        bytes.serialize(serializer)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+    use serde::de::Error;
+    if deserializer.is_human_readable() {
+        Base64::decode(&String::deserialize(deserializer)?)
+            .map_err(D::Error::custom)?
+            .try_into()
+            .map_err(|_| D::Error::custom("Expected 32 bytes"))
+    } else {
+        <[u8; 32]>::deserialize(deserializer)
+    }
+}
+
+impl Serialize for EncodedScalar {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.0.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EncodedScalar {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        Option::from(Scalar::from_canonical_bytes(deserialize_bytes(
+            deserializer,
+        )?))
+        .map(Self)
+        .ok_or_else(|| D::Error::custom("Invalid scalar"))
+    }
+}
+
+impl Serialize for EncodedPoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.0.compress().to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EncodedPoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        let point = CompressedEdwardsY(deserialize_bytes(deserializer)?)
+            .decompress()
+            .ok_or_else(|| D::Error::custom("Invalid point"))?;
+        // Every point exchanged or stored is a commitment or public key in the prime order
+        // subgroup, so the identity and points with a torsion component are never valid.
+        if point.is_identity() {
+            return Err(D::Error::custom("Point is the identity"));
+        }
+        if !point.is_torsion_free() {
+            return Err(D::Error::custom("Point is not in the prime order subgroup"));
+        }
+        Ok(Self(point))
+    }
+}
+
+/// A message exchanged between participants, passed around as Base64 encoded BCS.
+pub trait ThresholdPackage: Serialize + DeserializeOwned {
+    fn encode_base64(&self) -> String {
+        Base64::encode(bcs::to_bytes(self).expect("Package serialization should not fail"))
+    }
+
+    fn decode_base64(value: &str) -> Result<Self, Error> {
+        let bytes = Base64::decode(value).map_err(|e| anyhow!("Invalid Base64 package: {e}"))?;
+        bcs::from_bytes(&bytes).map_err(|e| anyhow!("Invalid package: {e}"))
+    }
+}
+
+/// Commitments to the secret polynomial of a participant, broadcast in the first DKG round.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DkgRound1Package {
+    pub identifier: u16,
+    commitments: Vec<EncodedPoint>,
+    /// Proof of knowledge of the secret, a Schnorr signature with its constant term.
+    proof_commitment: EncodedPoint,
+    proof_response: EncodedScalar,
+}
+
+/// The share of the secret of `sender` for `recipient`, sent privately in the second DKG round.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DkgRound2Package {
+    pub sender: u16,
+    pub recipient: u16,
+    share: EncodedScalar,
+}
+
+/// Commitments to the nonces of a signer, shared with the other signers before signing.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SigningCommitments {
+    pub identifier: u16,
+    hiding: EncodedPoint,
+    binding: EncodedPoint,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SignatureShare {
+    pub identifier: u16,
+    share: EncodedScalar,
+}
+
+impl ThresholdPackage for DkgRound1Package {}
+impl ThresholdPackage for DkgRound2Package {}
+impl ThresholdPackage for SigningCommitments {}
+impl ThresholdPackage for SignatureShare {}
+
+/// The share of a participant in a threshold key.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KeyShare {
+    pub identifier: u16,
+    pub threshold: u16,
+    pub max_signers: u16,
+    secret_share: EncodedScalar,
+    group_public_key: EncodedPoint,
+    /// Public keys of the shares of every participant, used to check signature shares.
+    verifying_shares: BTreeMap<u16, EncodedPoint>,
+}
+
+impl KeyShare {
+    /// The Ed25519 public key of the group.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::try_from_bytes(
+            SignatureScheme::ED25519,
+            &self.group_public_key.0.compress().to_bytes(),
+        )
+        .expect("Group public key is a valid Ed25519 key")
+    }
+
+    pub fn address(&self) -> MySoAddress {
+        (&self.public_key()).into()
+    }
+}
+
+/// A DKG in progress.
+#[derive(Serialize, Deserialize)]
+struct DkgSession {
+    identifier: u16,
+    threshold: u16,
+    max_signers: u16,
+    coefficients: Vec<EncodedScalar>,
+    /// Round 1 packages of the other participants, known after round 2.
+    #[serde(default)]
+    round1_packages: Vec<DkgRound1Package>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SigningNonces {
+    hiding: EncodedScalar,
+    binding: EncodedScalar,
+    commitments: SigningCommitments,
+}
+
+/// Secret state of threshold keys: DKGs in progress, key shares and signing nonces.
+#[derive(Default, Serialize, Deserialize)]
+pub struct ThresholdKeystore {
+    #[serde(default)]
+    dkg_sessions: BTreeMap<String, DkgSession>,
+    #[serde(default)]
+    keys: BTreeMap<MySoAddress, KeyShare>,
+    #[serde(default)]
+    nonces: BTreeMap<MySoAddress, Vec<SigningNonces>>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl ThresholdKeystore {
+    /// Loads the threshold keystore stored next to the keystore at `keystore_path`.
+    pub fn load_or_create(keystore_path: &Path) -> Result<Self, Error> {
+        let path = keystore_path.with_extension(THRESHOLD_FILE_EXTENSION);
+        let mut keystore: Self = if path.exists() {
+            let store = fs::read_to_string(&path)
+                .with_context(|| format!("Cannot read threshold keystore: {}", path.display()))?;
+            serde_json::from_str(&store).with_context(|| {
+                format!("Cannot deserialize threshold keystore: {}", path.display())
+            })?
+        } else {
+            Self::default()
+        };
+        keystore.path = Some(path);
+        Ok(keystore)
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let store = serde_json::to_string_pretty(self)?;
+        fs::write(path, store)
+            .with_context(|| format!("Cannot write threshold keystore: {}", path.display()))?;
+        #[cfg(unix)]
+        set_reduced_file_permissions(path)?;
+        Ok(())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &KeyShare> {
+        self.keys.values()
+    }
+
+    pub fn key(&self, address: &MySoAddress) -> Result<&KeyShare, Error> {
+        self.keys
+            .get(address)
+            .ok_or_else(|| anyhow!("No threshold key share for address {address}"))
+    }
+
+    /// Starts a DKG as participant `identifier` of `max_signers`, for a key that needs
+    /// `threshold` signers.
+    pub fn dkg_round1(
+        &mut self,
+        session: &str,
+        identifier: u16,
+        threshold: u16,
+        max_signers: u16,
+    ) -> Result<DkgRound1Package, Error> {
+        ensure!(
+            !self.dkg_sessions.contains_key(session),
+            "DKG session {session} already exists"
+        );
+        ensure!(
+            threshold >= 2 && threshold <= max_signers,
+            "Threshold must be between 2 and the number of signers"
+        );
+        ensure!(
+            (1..=max_signers).contains(&identifier),
+            "Identifier must be between 1 and the number of signers"
+        );
+
+        let coefficients: Vec<Scalar> = (0..threshold).map(|_| random_scalar()).collect();
+        let commitments: Vec<EdwardsPoint> =
+            coefficients.iter().map(EdwardsPoint::mul_base).collect();
+        let nonce = random_scalar();
+        let proof_commitment = EdwardsPoint::mul_base(&nonce);
+        let challenge = dkg_challenge(identifier, &commitments[0], &proof_commitment);
+        let package = DkgRound1Package {
+            identifier,
+            commitments: commitments.into_iter().map(EncodedPoint).collect(),
+            proof_commitment: EncodedPoint(proof_commitment),
+            proof_response: EncodedScalar(nonce + coefficients[0] * challenge),
+        };
+
+        self.dkg_sessions.insert(
+            session.to_string(),
+            DkgSession {
+                identifier,
+                threshold,
+                max_signers,
+                coefficients: coefficients.into_iter().map(EncodedScalar).collect(),
+                round1_packages: vec![],
+            },
+        );
+        self.save()?;
+        Ok(package)
+    }
+
+    /// Checks the round 1 packages of every other participant, and returns the round 2
+    /// package for each of them.
+    pub fn dkg_round2(
+        &mut self,
+        session: &str,
+        mut round1_packages: Vec<DkgRound1Package>,
+    ) -> Result<Vec<DkgRound2Package>, Error> {
+        let dkg = self
+            .dkg_sessions
+            .get_mut(session)
+            .ok_or_else(|| anyhow!("DKG session {session} does not exist"))?;
+        ensure!(
+            dkg.round1_packages.is_empty(),
+            "Round 2 of DKG session {session} was already run"
+        );
+        round1_packages.sort_by_key(|p| p.identifier);
+        let identifiers: BTreeSet<_> = round1_packages.iter().map(|p| p.identifier).collect();
+        let expected: BTreeSet<_> = (1..=dkg.max_signers)
+            .filter(|i| *i != dkg.identifier)
+            .collect();
+        ensure!(
+            identifiers == expected && round1_packages.len() == expected.len(),
+            "Expected one round 1 package from each of participants {expected:?}"
+        );
+
+        for package in &round1_packages {
+            ensure!(
+                package.commitments.len() == dkg.threshold as usize,
+                "Round 1 package of participant {} has a wrong number of commitments",
+                package.identifier
+            );
+            let challenge = dkg_challenge(
+                package.identifier,
+                &package.commitments[0].0,
+                &package.proof_commitment.0,
+            );
+            ensure!(
+                EdwardsPoint::mul_base(&package.proof_response.0)
+                    - package.commitments[0].0 * challenge
+                    == package.proof_commitment.0,
+                "Invalid proof of knowledge from participant {}",
+                package.identifier
+            );
+        }
+
+        let packages = round1_packages
+            .iter()
+            .map(|package| DkgRound2Package {
+                sender: dkg.identifier,
+                recipient: package.identifier,
+                share: EncodedScalar(evaluate_polynomial(&dkg.coefficients, package.identifier)),
+            })
+            .collect();
+        dkg.round1_packages = round1_packages;
+        self.save()?;
+        Ok(packages)
+    }
+
+    /// Checks the round 2 packages sent to this participant by every other participant, and
+    /// stores the resulting key share.
+    pub fn dkg_finalize(
+        &mut self,
+        session: &str,
+        round2_packages: Vec<DkgRound2Package>,
+    ) -> Result<KeyShare, Error> {
+        let dkg = self
+            .dkg_sessions
+            .get(session)
+            .ok_or_else(|| anyhow!("DKG session {session} does not exist"))?;
+        ensure!(
+            !dkg.round1_packages.is_empty(),
+            "Round 2 of DKG session {session} has not been run"
+        );
+
+        let mut secret_share = evaluate_polynomial(&dkg.coefficients, dkg.identifier);
+        for round1_package in &dkg.round1_packages {
+            let sender = round1_package.identifier;
+            let mut shares = round2_packages.iter().filter(|p| p.sender == sender);
+            let (Some(package), None) = (shares.next(), shares.next()) else {
+                bail!("Expected one round 2 package from participant {sender}");
+            };
+            ensure!(
+                package.recipient == dkg.identifier,
+                "Round 2 package from participant {sender} is for participant {}",
+                package.recipient
+            );
+            ensure!(
+                EdwardsPoint::mul_base(&package.share.0)
+                    == evaluate_commitments(&round1_package.commitments, dkg.identifier),
+                "Invalid share from participant {sender}"
+            );
+            secret_share += package.share.0;
+        }
+        ensure!(
+            round2_packages.len() == dkg.round1_packages.len(),
+            "Unexpected round 2 packages"
+        );
+
+        let own_commitments: Vec<EncodedPoint> = dkg
+            .coefficients
+            .iter()
+            .map(|c| EncodedPoint(EdwardsPoint::mul_base(&c.0)))
+            .collect();
+        let all_commitments: Vec<&[EncodedPoint]> = dkg
+            .round1_packages
+            .iter()
+            .map(|p| p.commitments.as_slice())
+            .chain([own_commitments.as_slice()])
+            .collect();
+        let group_public_key = all_commitments
+            .iter()
+            .fold(EdwardsPoint::identity(), |sum, c| sum + c[0].0);
+        let verifying_shares = (1..=dkg.max_signers)
+            .map(|i| {
+                let share = all_commitments
+                    .iter()
+                    .fold(EdwardsPoint::identity(), |sum, c| {
+                        sum + evaluate_commitments(c, i)
+                    });
+                (i, EncodedPoint(share))
+            })
+            .collect();
+
+        let key_share = KeyShare {
+            identifier: dkg.identifier,
+            threshold: dkg.threshold,
+            max_signers: dkg.max_signers,
+            secret_share: EncodedScalar(secret_share),
+            group_public_key: EncodedPoint(group_public_key),
+            verifying_shares,
+        };
+        self.keys.insert(key_share.address(), key_share.clone());
+        self.dkg_sessions.remove(session);
+        self.save()?;
+        Ok(key_share)
+    }
+
+    /// Generates single use nonces to sign with the key of `address`, and returns the
+    /// commitments to share with the other signers.
+    pub fn commit(&mut self, address: &MySoAddress) -> Result<SigningCommitments, Error> {
+        let key = self.key(address)?;
+        let hiding = nonce(&key.secret_share.0);
+        let binding = nonce(&key.secret_share.0);
+        let commitments = SigningCommitments {
+            identifier: key.identifier,
+            hiding: EncodedPoint(EdwardsPoint::mul_base(&hiding)),
+            binding: EncodedPoint(EdwardsPoint::mul_base(&binding)),
+        };
+        self.nonces
+            .entry(*address)
+            .or_default()
+            .push(SigningNonces {
+                hiding: EncodedScalar(hiding),
+                binding: EncodedScalar(binding),
+                commitments: commitments.clone(),
+            });
+        self.save()?;
+        Ok(commitments)
+    }
+
+    /// Signs `msg` with the key of `address`, using the nonces committed to in `commitments`.
+    /// The nonces are deleted, so a commitment can only be used once.
+    pub fn sign(
+        &mut self,
+        address: &MySoAddress,
+        msg: &[u8],
+        mut commitments: Vec<SigningCommitments>,
+    ) -> Result<SignatureShare, Error> {
+        let key = self.key(address)?.clone();
+        check_commitments(&key, &mut commitments)?;
+        let nonces = self.nonces.entry(*address).or_default();
+        let position = nonces
+            .iter()
+            .position(|n| commitments.contains(&n.commitments))
+            .ok_or_else(|| anyhow!("No unused nonces match the signing commitments"))?;
+        let nonces = nonces.remove(position);
+        // Persist the removal before signing, so that the nonces are never used twice.
+        self.save()?;
+
+        let binding_factors = binding_factors(&key.group_public_key.0, &commitments, msg);
+        let group_commitment = group_commitment(&commitments, &binding_factors);
+        let challenge = challenge(&group_commitment, &key.group_public_key.0, msg);
+        let identifiers: Vec<u16> = commitments.iter().map(|c| c.identifier).collect();
+        let share = nonces.hiding.0
+            + nonces.binding.0 * binding_factors[&key.identifier]
+            + lagrange_coefficient(key.identifier, &identifiers) * key.secret_share.0 * challenge;
+        Ok(SignatureShare {
+            identifier: key.identifier,
+            share: EncodedScalar(share),
+        })
+    }
+
+    /// Checks the signature shares of the signers and combines them into an Ed25519 signature
+    /// of `msg` by the key of `address`.
+    pub fn aggregate(
+        &self,
+        address: &MySoAddress,
+        msg: &[u8],
+        mut commitments: Vec<SigningCommitments>,
+        shares: Vec<SignatureShare>,
+    ) -> Result<Signature, Error> {
+        let key = self.key(address)?;
+        check_commitments(key, &mut commitments)?;
+        let binding_factors = binding_factors(&key.group_public_key.0, &commitments, msg);
+        let group_commitment = group_commitment(&commitments, &binding_factors);
+        let challenge = challenge(&group_commitment, &key.group_public_key.0, msg);
+        let identifiers: Vec<u16> = commitments.iter().map(|c| c.identifier).collect();
+
+        let mut signature = Scalar::ZERO;
+        for c in &commitments {
+            let mut matching = shares.iter().filter(|s| s.identifier == c.identifier);
+            let (Some(share), None) = (matching.next(), matching.next()) else {
+                bail!(
+                    "Expected one signature share from participant {}",
+                    c.identifier
+                );
+            };
+            let commitment = c.hiding.0 + c.binding.0 * binding_factors[&c.identifier];
+            let verifying_share = key.verifying_shares[&c.identifier].0;
+            ensure!(
+                EdwardsPoint::mul_base(&share.share.0)
+                    == commitment
+                        + verifying_share
+                            * (challenge * lagrange_coefficient(c.identifier, &identifiers)),
+                "Invalid signature share from participant {}",
+                c.identifier
+            );
+            signature += share.share.0;
+        }
+        ensure!(
+            shares.len() == commitments.len(),
+            "Signature shares from participants without commitments"
+        );
+
+        let mut bytes = vec![SignatureScheme::ED25519.flag()];
+        bytes.extend_from_slice(&group_commitment.compress().to_bytes());
+        bytes.extend_from_slice(&signature.to_bytes());
+        bytes.extend_from_slice(&key.group_public_key.0.compress().to_bytes());
+        Signature::from_bytes(&bytes).map_err(|e| anyhow!("Invalid signature: {e}"))
+    }
+}
+
+/// The message a signature on `intent_msg` commits to, as in `Signature::new_secure`.
+pub fn signing_digest<T: Serialize>(intent_msg: &IntentMessage<T>) -> Result<[u8; 32], Error> {
+    let mut hasher = DefaultHash::default();
+    hasher.update(bcs::to_bytes(intent_msg)?);
+    Ok(hasher.finalize().digest)
+}
+
+fn check_commitments(key: &KeyShare, commitments: &mut [SigningCommitments]) -> Result<(), Error> {
+    commitments.sort_by_key(|c| c.identifier);
+    ensure!(
+        commitments.len() >= key.threshold as usize,
+        "At least {} signers are needed",
+        key.threshold
+    );
+    ensure!(
+        commitments
+            .windows(2)
+            .all(|w| w[0].identifier != w[1].identifier),
+        "Duplicate signing commitments"
+    );
+    ensure!(
+        commitments
+            .iter()
+            .all(|c| key.verifying_shares.contains_key(&c.identifier)),
+        "Signing commitments from unknown participants"
+    );
+    Ok(())
+}
+
+fn random_scalar() -> Scalar {
+    let mut bytes = [0u8; 64];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&hash(parts))
+}
+
+fn hash(parts: &[&[u8]]) -> [u8; 64] {
+    let mut hasher = Sha512::default();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().digest
+}
+
+fn identifier_bytes(identifier: u16) -> [u8; 32] {
+    Scalar::from(identifier as u64).to_bytes()
+}
+
+fn dkg_challenge(identifier: u16, secret_commitment: &EdwardsPoint, r: &EdwardsPoint) -> Scalar {
+    hash_to_scalar(&[
+        CONTEXT_STRING,
+        b"dkg",
+        &identifier_bytes(identifier),
+        &secret_commitment.compress().to_bytes(),
+        &r.compress().to_bytes(),
+    ])
+}
+
+/// H3 of RFC 9591, mixing the secret into fresh randomness.
+fn nonce(secret: &Scalar) -> Scalar {
+    let mut random_bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut random_bytes);
+    hash_to_scalar(&[CONTEXT_STRING, b"nonce", &random_bytes, &secret.to_bytes()])
+}
+
+fn evaluate_polynomial(coefficients: &[EncodedScalar], x: u16) -> Scalar {
+    let x = Scalar::from(x as u64);
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::ZERO, |value, c| value * x + c.0)
+}
+
+fn evaluate_commitments(commitments: &[EncodedPoint], x: u16) -> EdwardsPoint {
+    let x = Scalar::from(x as u64);
+    commitments
+        .iter()
+        .rev()
+        .fold(EdwardsPoint::identity(), |value, c| value * x + c.0)
+}
+
+fn lagrange_coefficient(identifier: u16, identifiers: &[u16]) -> Scalar {
+    let x = Scalar::from(identifier as u64);
+    let (numerator, denominator) = identifiers
+        .iter()
+        .filter(|j| **j != identifier)
+        .map(|j| Scalar::from(*j as u64))
+        .fold((Scalar::ONE, Scalar::ONE), |(num, den), j| {
+            (num * j, den * (j - x))
+        });
+    numerator * denominator.invert()
+}
+
+/// Binding factors of the signers, from RFC 9591. `commitments` are sorted by identifier.
+fn binding_factors(
+    group_public_key: &EdwardsPoint,
+    commitments: &[SigningCommitments],
+    msg: &[u8],
+) -> BTreeMap<u16, Scalar> {
+    let encoded_commitments: Vec<u8> = commitments
+        .iter()
+        .flat_map(|c| {
+            [
+                identifier_bytes(c.identifier),
+                c.hiding.0.compress().to_bytes(),
+                c.binding.0.compress().to_bytes(),
+            ]
+        })
+        .flatten()
+        .collect();
+    let msg_hash = hash(&[CONTEXT_STRING, b"msg", msg]);
+    let commitments_hash = hash(&[CONTEXT_STRING, b"com", &encoded_commitments]);
+    let group_public_key = group_public_key.compress().to_bytes();
+    commitments
+        .iter()
+        .map(|c| {
+            let factor = hash_to_scalar(&[
+                CONTEXT_STRING,
+                b"rho",
+                &group_public_key,
+                &msg_hash,
+                &commitments_hash,
+                &identifier_bytes(c.identifier),
+            ]);
+            (c.identifier, factor)
+        })
+        .collect()
+}
+
+fn group_commitment(
+    commitments: &[SigningCommitments],
+    binding_factors: &BTreeMap<u16, Scalar>,
+) -> EdwardsPoint {
+    commitments.iter().fold(EdwardsPoint::identity(), |sum, c| {
+        sum + c.hiding.0 + c.binding.0 * binding_factors[&c.identifier]
+    })
+}
+
+/// The Ed25519 challenge, so that the aggregated signature verifies as an Ed25519 signature.
+fn challenge(
+    group_commitment: &EdwardsPoint,
+    group_public_key: &EdwardsPoint,
+    msg: &[u8],
+) -> Scalar {
+    hash_to_scalar(&[
+        &group_commitment.compress().to_bytes(),
+        &group_public_key.compress().to_bytes(),
+        msg,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DkgRound1Package, DkgRound2Package, EncodedPoint, KeyShare, ThresholdKeystore,
+        ThresholdPackage, signing_digest,
+    };
+    use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, EIGHT_TORSION};
+    use curve25519_dalek::edwards::EdwardsPoint;
+    use curve25519_dalek::traits::Identity;
+    use myso_types::base_types::MySoAddress;
+    use myso_types::crypto::SignatureScheme;
+    use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+    use tempfile::TempDir;
+
+    /// Runs a DKG between `max_signers` keystores and returns them with their key shares.
+    fn dkg(threshold: u16, max_signers: u16) -> (Vec<ThresholdKeystore>, Vec<KeyShare>) {
+        let mut keystores: Vec<_> = (0..max_signers)
+            .map(|_| ThresholdKeystore::default())
+            .collect();
+        let round1: Vec<_> = keystores
+            .iter_mut()
+            .enumerate()
+            .map(|(i, k)| {
+                k.dkg_round1("treasury", i as u16 + 1, threshold, max_signers)
+                    .unwrap()
+            })
+            .collect();
+        let round2: Vec<DkgRound2Package> = keystores
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(i, k)| {
+                let others = round1
+                    .iter()
+                    .filter(|p| p.identifier != i as u16 + 1)
+                    // Packages go through their encoded form.
+                    .map(|p| DkgRound1Package::decode_base64(&p.encode_base64()).unwrap())
+                    .collect();
+                k.dkg_round2("treasury", others).unwrap()
+            })
+            .collect();
+        let shares = keystores
+            .iter_mut()
+            .enumerate()
+            .map(|(i, k)| {
+                let mine = round2
+                    .iter()
+                    .filter(|p| p.recipient == i as u16 + 1)
+                    .cloned()
+                    .collect();
+                k.dkg_finalize("treasury", mine).unwrap()
+            })
+            .collect();
+        (keystores, shares)
+    }
+
+    #[test]
+    fn test_dkg_and_sign() {
+        let (mut keystores, shares) = dkg(2, 3);
+        let address = shares[0].address();
+        assert!(shares.iter().all(|s| s.address() == address));
+        assert_eq!(shares[0].public_key().scheme(), SignatureScheme::ED25519);
+
+        let intent_msg = IntentMessage::new(
+            Intent::personal_message(),
+            PersonalMessage {
+                message: b"hello".to_vec(),
+            },
+        );
+        let digest = signing_digest(&intent_msg).unwrap();
+
+        // Participants 1 and 3 sign.
+        let commitments = vec![
+            keystores[0].commit(&address).unwrap(),
+            keystores[2].commit(&address).unwrap(),
+        ];
+        let signature_shares = vec![
+            keystores[0]
+                .sign(&address, &digest, commitments.clone())
+                .unwrap(),
+            keystores[2]
+                .sign(&address, &digest, commitments.clone())
+                .unwrap(),
+        ];
+        // Nonces are single use.
+        assert!(
+            keystores[0]
+                .sign(&address, &digest, commitments.clone())
+                .is_err()
+        );
+
+        // Any participant can aggregate, including one that did not sign.
+        let signature = keystores[1]
+            .aggregate(
+                &address,
+                &digest,
+                commitments.clone(),
+                signature_shares.clone(),
+            )
+            .unwrap();
+        signature
+            .verify_secure(&intent_msg, address, SignatureScheme::ED25519)
+            .unwrap();
+
+        // A tampered share is detected.
+        let mut tampered = signature_shares;
+        tampered.swap(0, 1);
+        tampered[0].identifier = commitments[0].identifier;
+        tampered[1].identifier = commitments[1].identifier;
+        assert!(
+            keystores[1]
+                .aggregate(&address, &digest, commitments, tampered)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_not_enough_signers() {
+        let (mut keystores, shares) = dkg(3, 4);
+        let address = shares[0].address();
+        let commitments = vec![
+            keystores[0].commit(&address).unwrap(),
+            keystores[1].commit(&address).unwrap(),
+        ];
+        assert!(keystores[0].sign(&address, b"digest", commitments).is_err());
+        assert!(
+            keystores[0]
+                .sign(&MySoAddress::ZERO, b"digest", vec![])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_persisted_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let keystore_path = temp_dir.path().join("myso.keystore");
+        let mut keystore = ThresholdKeystore::load_or_create(&keystore_path).unwrap();
+        keystore.dkg_round1("treasury", 1, 2, 2).unwrap();
+        assert!(keystore.dkg_round1("treasury", 1, 2, 2).is_err());
+
+        let reloaded = ThresholdKeystore::load_or_create(&keystore_path).unwrap();
+        assert!(reloaded.dkg_sessions.contains_key("treasury"));
+        assert!(keystore_path.with_extension("threshold").exists());
+    }
+
+    fn decode_point(point: EdwardsPoint) -> Result<EncodedPoint, bcs::Error> {
+        bcs::from_bytes(&bcs::to_bytes(&point.compress().to_bytes()).unwrap())
+    }
+
+    #[test]
+    fn test_identity_point() {
+        assert!(decode_point(ED25519_BASEPOINT_POINT).is_ok());
+        assert!(decode_point(EdwardsPoint::identity()).is_err());
+    }
+
+    #[test]
+    fn test_torsion_point() {
+        assert!(decode_point(EIGHT_TORSION[1]).is_err());
+        assert!(decode_point(ED25519_BASEPOINT_POINT + EIGHT_TORSION[1]).is_err());
+    }
+}
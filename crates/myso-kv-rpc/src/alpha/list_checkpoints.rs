@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use myso_kvstore::KeyValueStoreReader;
+use myso_rpc::field::{FieldMask, FieldMaskTree, FieldMaskUtil};
+use myso_rpc::proto::myso::rpc::v2::Checkpoint;
+use myso_rpc_api::grpc::alpha::archive_service_proto::{
+    ListCheckpointsRequest, ListCheckpointsResponse,
+};
+use myso_rpc_api::{ErrorReason, RpcError, proto::google::rpc::bad_request::FieldViolation};
+
+use crate::KvClient;
+use crate::alpha::{decode_page_token, encode_page_token, page_size};
+use crate::v2::get_checkpoint::{READ_MASK_DEFAULT, checkpoint_to_response};
+
+const MAX_PAGE_SIZE: u32 = 1000;
+
+pub(crate) async fn list_checkpoints(
+    mut client: KvClient,
+    request: ListCheckpointsRequest,
+) -> Result<ListCheckpointsResponse, RpcError> {
+    let read_mask = {
+        let read_mask = request
+            .read_mask
+            .unwrap_or_else(|| FieldMask::from_str(READ_MASK_DEFAULT));
+        read_mask.validate::<Checkpoint>().map_err(|path| {
+            FieldViolation::new("read_mask")
+                .with_description(format!("invalid read_mask path: {path}"))
+                .with_reason(ErrorReason::FieldInvalid)
+        })?;
+        FieldMaskTree::from(read_mask)
+    };
+
+    // Full checkpoint contents are only available through `GetCheckpoint`, which reads them from
+    // the checkpoint bucket.
+    if read_mask.contains(Checkpoint::TRANSACTIONS_FIELD)
+        || read_mask.contains(Checkpoint::OBJECTS_FIELD)
+    {
+        return Err(FieldViolation::new("read_mask")
+            .with_description("transactions and objects are not supported when listing")
+            .with_reason(ErrorReason::FieldInvalid)
+            .into());
+    }
+
+    let page_size = page_size(request.page_size, MAX_PAGE_SIZE);
+
+    // The page token is the sequence number of the first checkpoint on the next page.
+    let start = match &request.page_token {
+        Some(token) => decode_page_token(token)?,
+        None => request.start.unwrap_or(0),
+    };
+    let end = request.end.unwrap_or(u64::MAX);
+
+    let mut checkpoints = client
+        .get_checkpoint_range(start..end, page_size + 1)
+        .await?;
+
+    let next_page_token = if checkpoints.len() > page_size {
+        checkpoints
+            .pop()
+            .map(|c| encode_page_token(&c.summary.sequence_number))
+    } else {
+        None
+    };
+
+    let mut response = ListCheckpointsResponse::default();
+    response.checkpoints = checkpoints
+        .into_iter()
+        .map(|c| checkpoint_to_response(c, &read_mask))
+        .collect::<Result<_, _>>()?;
+    response.next_page_token = next_page_token;
+    Ok(response)
+}
@@ -0,0 +1,66 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use myso_kvstore::KeyValueStoreReader;
+use myso_rpc::merge::Merge;
+use myso_rpc::proto::myso::rpc::v2::Object;
+use myso_rpc_api::grpc::alpha::archive_service_proto::{
+    ListObjectVersionsRequest, ListObjectVersionsResponse,
+};
+use myso_rpc_api::{RpcError, grpc::v2::ledger_service::validate_get_object_requests};
+use myso_types::base_types::{ObjectID, SequenceNumber};
+
+use crate::KvClient;
+use crate::alpha::{decode_page_token, encode_page_token, page_size};
+
+const MAX_PAGE_SIZE: u32 = 1000;
+
+pub(crate) async fn list_object_versions(
+    mut client: KvClient,
+    request: ListObjectVersionsRequest,
+) -> Result<ListObjectVersionsResponse, RpcError> {
+    let (requests, read_mask) =
+        validate_get_object_requests(vec![(request.object_id, None)], request.read_mask)?;
+    let object_id: ObjectID = requests[0].0.into();
+
+    let page_size = page_size(request.page_size, MAX_PAGE_SIZE);
+
+    // The page token is the first version on the next page.
+    let start = match &request.page_token {
+        Some(token) => decode_page_token(token)?,
+        None => request.start_version.unwrap_or(0),
+    };
+    let end = request
+        .end_version
+        .unwrap_or(u64::MAX)
+        .min(SequenceNumber::MAX.value());
+
+    let mut objects = client
+        .get_object_versions(
+            &object_id,
+            SequenceNumber::from_u64(start)..SequenceNumber::from_u64(end),
+            page_size + 1,
+        )
+        .await?;
+
+    let next_page_token = if objects.len() > page_size {
+        objects
+            .pop()
+            .map(|o| encode_page_token(&o.version().value()))
+    } else {
+        None
+    };
+
+    let mut response = ListObjectVersionsResponse::default();
+    response.objects = objects
+        .iter()
+        .map(|object| {
+            let mut message = Object::default();
+            message.merge(object, &read_mask);
+            message
+        })
+        .collect();
+    response.next_page_token = next_page_token;
+    Ok(response)
+}
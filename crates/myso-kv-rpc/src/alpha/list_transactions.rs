@@ -0,0 +1,117 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use futures::TryStreamExt;
+use myso_kvstore::KeyValueStoreReader;
+use myso_rpc::field::{FieldMask, FieldMaskTree, FieldMaskUtil};
+use myso_rpc::proto::myso::rpc::v2::ExecutedTransaction;
+use myso_rpc_api::grpc::alpha::archive_service_proto::{
+    ListTransactionsRequest, ListTransactionsResponse,
+};
+use myso_rpc_api::{
+    ErrorReason, RpcError, TransactionNotFoundError,
+    proto::google::rpc::bad_request::FieldViolation,
+};
+use myso_types::base_types::TransactionDigest;
+use serde::{Deserialize, Serialize};
+
+use crate::KvClient;
+use crate::alpha::{decode_page_token, encode_page_token, page_size};
+use crate::v2::get_transaction::{READ_MASK_DEFAULT, transaction_to_response};
+
+const MAX_PAGE_SIZE: u32 = 200;
+
+/// Position of the first transaction on the next page.
+#[derive(Serialize, Deserialize)]
+struct PageToken {
+    checkpoint: u64,
+    transaction_idx: u64,
+}
+
+pub(crate) async fn list_transactions(
+    mut client: KvClient,
+    request: ListTransactionsRequest,
+) -> Result<ListTransactionsResponse, RpcError> {
+    let read_mask = {
+        let read_mask = request
+            .read_mask
+            .unwrap_or_else(|| FieldMask::from_str(READ_MASK_DEFAULT));
+        read_mask
+            .validate::<ExecutedTransaction>()
+            .map_err(|path| {
+                FieldViolation::new("read_mask")
+                    .with_description(format!("invalid read_mask path: {path}"))
+                    .with_reason(ErrorReason::FieldInvalid)
+            })?;
+        FieldMaskTree::from(read_mask)
+    };
+
+    let page_size = page_size(request.page_size, MAX_PAGE_SIZE);
+
+    let start = match &request.page_token {
+        Some(token) => decode_page_token(token)?,
+        None => PageToken {
+            checkpoint: request.start_checkpoint.unwrap_or(0),
+            transaction_idx: 0,
+        },
+    };
+    let end = request.end_checkpoint.unwrap_or(u64::MAX);
+
+    // Gather the digests of the transactions on this page (plus one, to detect whether there is
+    // a next page) from the contents of the checkpoints in range.
+    let mut digests: Vec<(TransactionDigest, PageToken)> = vec![];
+    {
+        let mut checkpoints = client.stream_checkpoints(start.checkpoint..end);
+        'checkpoints: while let Some(checkpoint) = checkpoints.try_next().await? {
+            let sequence_number = checkpoint.summary.sequence_number;
+            let skip = if sequence_number == start.checkpoint {
+                start.transaction_idx as usize
+            } else {
+                0
+            };
+
+            for (idx, execution) in checkpoint.contents.iter().enumerate().skip(skip) {
+                digests.push((
+                    execution.transaction,
+                    PageToken {
+                        checkpoint: sequence_number,
+                        transaction_idx: idx as u64,
+                    },
+                ));
+
+                if digests.len() > page_size {
+                    break 'checkpoints;
+                }
+            }
+        }
+    }
+
+    let next_page_token = if digests.len() > page_size {
+        digests.pop().map(|(_, token)| encode_page_token(&token))
+    } else {
+        None
+    };
+
+    let digests: Vec<_> = digests.into_iter().map(|(digest, _)| digest).collect();
+    let mut found: HashMap<_, _> = client
+        .stream_transactions(digests.clone())
+        .map_ok(|tx| (*tx.transaction.digest(), tx))
+        .try_collect()
+        .await?;
+
+    let mut response = ListTransactionsResponse::default();
+    response.transactions = digests
+        .into_iter()
+        .map(|digest| {
+            let tx = found
+                .remove(&digest)
+                .ok_or(TransactionNotFoundError(digest.into()))?;
+            transaction_to_response(tx, &read_mask)
+        })
+        .collect::<Result<_, RpcError>>()?;
+    response.next_page_token = next_page_token;
+    Ok(response)
+}
@@ -0,0 +1,387 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use myso_rpc_api::RpcError;
+use myso_rpc_api::grpc::alpha::archive_service_proto::archive_service_server::ArchiveService;
+use myso_rpc_api::grpc::alpha::archive_service_proto::{
+    ListCheckpointsRequest, ListCheckpointsResponse, ListObjectVersionsRequest,
+    ListObjectVersionsResponse, ListTransactionsRequest, ListTransactionsResponse,
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::KvRpcServer;
+
+mod list_checkpoints;
+mod list_object_versions;
+mod list_transactions;
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+#[tonic::async_trait]
+impl ArchiveService for KvRpcServer {
+    async fn list_checkpoints(
+        &self,
+        request: tonic::Request<ListCheckpointsRequest>,
+    ) -> Result<tonic::Response<ListCheckpointsResponse>, tonic::Status> {
+        list_checkpoints::list_checkpoints(self.client.clone(), request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn list_object_versions(
+        &self,
+        request: tonic::Request<ListObjectVersionsRequest>,
+    ) -> Result<tonic::Response<ListObjectVersionsResponse>, tonic::Status> {
+        list_object_versions::list_object_versions(self.client.clone(), request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+
+    async fn list_transactions(
+        &self,
+        request: tonic::Request<ListTransactionsRequest>,
+    ) -> Result<tonic::Response<ListTransactionsResponse>, tonic::Status> {
+        list_transactions::list_transactions(self.client.clone(), request.into_inner())
+            .await
+            .map(tonic::Response::new)
+            .map_err(Into::into)
+    }
+}
+
+fn page_size(page_size: Option<u32>, max: u32) -> usize {
+    page_size
+        .map(|s| s.clamp(1, max))
+        .unwrap_or(DEFAULT_PAGE_SIZE.min(max)) as usize
+}
+
+fn decode_page_token<T: DeserializeOwned>(page_token: &[u8]) -> Result<T, RpcError> {
+    bcs::from_bytes(page_token).map_err(|_| {
+        RpcError::new(
+            tonic::Code::InvalidArgument,
+            "invalid page_token".to_string(),
+        )
+    })
+}
+
+fn encode_page_token<T: Serialize>(page_token: &T) -> Vec<u8> {
+    bcs::to_bytes(page_token).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use myso_indexer_alt_framework::pipeline::Processor;
+    use myso_kvstore::{
+        CheckpointsPipeline, ObjectsPipeline, RocksDbClient, TransactionsPipeline, tables,
+    };
+    use myso_rpc::field::FieldMaskUtil;
+    use myso_types::test_checkpoint_data_builder::TestCheckpointBuilder;
+    use prost_types::FieldMask;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::KvClient;
+
+    /// Transactions per checkpoint written by [`setup`]. Checkpoint 2 is left empty.
+    const TXS_PER_CHECKPOINT: [usize; 5] = [2, 3, 0, 1, 2];
+
+    struct Fixture {
+        _dir: TempDir,
+        client: KvClient,
+        /// Digests of all transactions, in checkpoint order.
+        digests: Vec<String>,
+        /// All versions of the object with index 0, in ascending order.
+        versions: Vec<u64>,
+        object_id: String,
+    }
+
+    /// Write checkpoints 0 to 4 to a fresh RocksDB KV store. Every transaction mutates the same
+    /// object, so that it has one version per transaction.
+    async fn setup() -> Fixture {
+        let dir = tempfile::tempdir().unwrap();
+        let db = RocksDbClient::open(dir.path()).unwrap();
+
+        let mut builder = TestCheckpointBuilder::new(0);
+        let mut digests = vec![];
+        let mut versions = vec![];
+        for (cp, num_txs) in TXS_PER_CHECKPOINT.into_iter().enumerate() {
+            for tx in 0..num_txs {
+                builder = builder.start_transaction(0);
+                builder = if cp == 0 && tx == 0 {
+                    builder.create_owned_object(0)
+                } else {
+                    builder.mutate_owned_object(0)
+                };
+                builder = builder.finish_transaction();
+            }
+
+            let checkpoint = Arc::new(builder.build_checkpoint());
+            digests.extend(
+                checkpoint
+                    .transactions
+                    .iter()
+                    .map(|tx| tx.transaction.digest().to_string()),
+            );
+            versions.extend(
+                checkpoint
+                    .object_set
+                    .iter()
+                    .filter(|o| o.id() == TestCheckpointBuilder::derive_object_id(0))
+                    .map(|o| o.version().value()),
+            );
+
+            let entries = CheckpointsPipeline.process(&checkpoint).await.unwrap();
+            db.write_entries(tables::checkpoints::NAME, entries)
+                .unwrap();
+            let entries = TransactionsPipeline.process(&checkpoint).await.unwrap();
+            db.write_entries(tables::transactions::NAME, entries)
+                .unwrap();
+            let entries = ObjectsPipeline.process(&checkpoint).await.unwrap();
+            db.write_entries(tables::objects::NAME, entries).unwrap();
+        }
+
+        versions.sort();
+        versions.dedup();
+        Fixture {
+            _dir: dir,
+            client: KvClient::RocksDb(db),
+            digests,
+            versions,
+            object_id: TestCheckpointBuilder::derive_object_id(0).to_string(),
+        }
+    }
+
+    fn code(err: RpcError) -> tonic::Code {
+        tonic::Status::from(err).code()
+    }
+
+    #[tokio::test]
+    async fn test_list_checkpoints_pages() {
+        let f = setup().await;
+
+        let mut pages = vec![];
+        let mut page_token = None;
+        loop {
+            let mut request = ListCheckpointsRequest::default();
+            request.page_size = Some(2);
+            request.page_token = page_token;
+            let response = list_checkpoints::list_checkpoints(f.client.clone(), request)
+                .await
+                .unwrap();
+
+            pages.push(
+                response
+                    .checkpoints
+                    .iter()
+                    .map(|c| c.sequence_number.unwrap())
+                    .collect::<Vec<_>>(),
+            );
+            page_token = response.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(pages, vec![vec![0, 1], vec![2, 3], vec![4]]);
+    }
+
+    #[tokio::test]
+    async fn test_list_checkpoints_bounds() {
+        let f = setup().await;
+
+        // The end of the range is exclusive, and a page that reaches it has no next page.
+        let mut request = ListCheckpointsRequest::default();
+        request.start = Some(1);
+        request.end = Some(3);
+        request.page_size = Some(2);
+        let response = list_checkpoints::list_checkpoints(f.client.clone(), request)
+            .await
+            .unwrap();
+        let seqs: Vec<_> = response
+            .checkpoints
+            .iter()
+            .map(|c| c.sequence_number.unwrap())
+            .collect();
+        assert_eq!(seqs, vec![1, 2]);
+        assert_eq!(response.next_page_token, None);
+
+        // The page token takes precedence over the start of the range.
+        let mut request = ListCheckpointsRequest::default();
+        request.start = Some(0);
+        request.page_token = Some(encode_page_token(&3u64));
+        let response = list_checkpoints::list_checkpoints(f.client.clone(), request)
+            .await
+            .unwrap();
+        let seqs: Vec<_> = response
+            .checkpoints
+            .iter()
+            .map(|c| c.sequence_number.unwrap())
+            .collect();
+        assert_eq!(seqs, vec![3, 4]);
+
+        // Past the last checkpoint.
+        let mut request = ListCheckpointsRequest::default();
+        request.start = Some(5);
+        let response = list_checkpoints::list_checkpoints(f.client.clone(), request)
+            .await
+            .unwrap();
+        assert!(response.checkpoints.is_empty());
+        assert_eq!(response.next_page_token, None);
+    }
+
+    #[tokio::test]
+    async fn test_list_checkpoints_invalid_request() {
+        let f = setup().await;
+
+        let mut request = ListCheckpointsRequest::default();
+        request.page_token = Some(vec![1, 2, 3]);
+        let err = list_checkpoints::list_checkpoints(f.client.clone(), request)
+            .await
+            .unwrap_err();
+        assert_eq!(code(err), tonic::Code::InvalidArgument);
+
+        let mut request = ListCheckpointsRequest::default();
+        request.read_mask = Some(FieldMask::from_paths(["sequence_number", "transactions"]));
+        let err = list_checkpoints::list_checkpoints(f.client.clone(), request)
+            .await
+            .unwrap_err();
+        assert_eq!(code(err), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_list_transactions_pages() {
+        let f = setup().await;
+
+        // Pages of 2 split checkpoint 1 between pages, and skip over the empty checkpoint 2.
+        let mut pages = vec![];
+        let mut page_token = None;
+        loop {
+            let mut request = ListTransactionsRequest::default();
+            request.page_size = Some(2);
+            request.page_token = page_token;
+            let response = list_transactions::list_transactions(f.client.clone(), request)
+                .await
+                .unwrap();
+
+            pages.push(
+                response
+                    .transactions
+                    .into_iter()
+                    .map(|tx| tx.digest.unwrap())
+                    .collect::<Vec<_>>(),
+            );
+            page_token = response.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(pages.len(), 4);
+        assert!(pages.iter().all(|p| p.len() <= 2));
+        assert_eq!(pages.concat(), f.digests);
+    }
+
+    #[tokio::test]
+    async fn test_list_transactions_bounds() {
+        let f = setup().await;
+
+        // Checkpoints 1 and 2 hold the 3rd to 5th transactions.
+        let mut request = ListTransactionsRequest::default();
+        request.start_checkpoint = Some(1);
+        request.end_checkpoint = Some(3);
+        let response = list_transactions::list_transactions(f.client.clone(), request)
+            .await
+            .unwrap();
+        let digests: Vec<_> = response
+            .transactions
+            .into_iter()
+            .map(|tx| tx.digest.unwrap())
+            .collect();
+        assert_eq!(digests, f.digests[2..5]);
+        assert_eq!(response.next_page_token, None);
+
+        let mut request = ListTransactionsRequest::default();
+        request.page_token = Some(b"not a token".to_vec());
+        let err = list_transactions::list_transactions(f.client.clone(), request)
+            .await
+            .unwrap_err();
+        assert_eq!(code(err), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_list_object_versions_pages() {
+        let f = setup().await;
+        assert_eq!(f.versions.len(), 8);
+
+        let mut versions = vec![];
+        let mut page_token = None;
+        loop {
+            let mut request = ListObjectVersionsRequest::default();
+            request.object_id = Some(f.object_id.clone());
+            request.page_size = Some(3);
+            request.page_token = page_token;
+            let response = list_object_versions::list_object_versions(f.client.clone(), request)
+                .await
+                .unwrap();
+
+            assert!(response.objects.len() <= 3);
+            versions.extend(response.objects.iter().map(|o| o.version.unwrap()));
+            page_token = response.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(versions, f.versions);
+    }
+
+    #[tokio::test]
+    async fn test_list_object_versions_bounds() {
+        let f = setup().await;
+
+        // The end version is exclusive.
+        let mut request = ListObjectVersionsRequest::default();
+        request.object_id = Some(f.object_id.clone());
+        request.start_version = Some(f.versions[2]);
+        request.end_version = Some(f.versions[5]);
+        let response = list_object_versions::list_object_versions(f.client.clone(), request)
+            .await
+            .unwrap();
+        let versions: Vec<_> = response
+            .objects
+            .iter()
+            .map(|o| o.version.unwrap())
+            .collect();
+        assert_eq!(versions, f.versions[2..5]);
+        assert_eq!(response.next_page_token, None);
+
+        // Versions past the maximum sequence number are clamped rather than rejected.
+        let mut request = ListObjectVersionsRequest::default();
+        request.object_id = Some(f.object_id.clone());
+        request.end_version = Some(u64::MAX);
+        let response = list_object_versions::list_object_versions(f.client.clone(), request)
+            .await
+            .unwrap();
+        assert_eq!(response.objects.len(), f.versions.len());
+
+        let mut request = ListObjectVersionsRequest::default();
+        request.object_id = Some("0xnot_an_id".to_string());
+        let err = list_object_versions::list_object_versions(f.client.clone(), request)
+            .await
+            .unwrap_err();
+        assert_eq!(code(err), tonic::Code::InvalidArgument);
+    }
+
+    #[test]
+    fn test_page_size() {
+        assert_eq!(page_size(None, 1000), DEFAULT_PAGE_SIZE as usize);
+        assert_eq!(page_size(None, 10), 10);
+        assert_eq!(page_size(Some(0), 1000), 1);
+        assert_eq!(page_size(Some(5000), 1000), 1000);
+    }
+}
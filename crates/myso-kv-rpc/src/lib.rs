@@ -2,23 +2,43 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use myso_kvstore::{BigTableClient, KeyValueStoreReader};
+use anyhow::Result;
+use async_trait::async_trait;
+use myso_kvstore::{
+    BigTableClient, CheckpointData, EpochData, KeyValueStoreReader, RocksDbClient, TransactionData,
+    TransactionEventsData, Watermark,
+};
 use myso_rpc::proto::myso::rpc::v2::GetServiceInfoResponse;
 use myso_rpc_api::ServerVersion;
-use myso_types::digests::ChainIdentifier;
+use myso_types::base_types::{ObjectID, ObjectType, SequenceNumber};
+use myso_types::committee::EpochId;
+use myso_types::digests::{ChainIdentifier, CheckpointDigest, TransactionDigest};
 use myso_types::message_envelope::Message;
+use myso_types::messages_checkpoint::CheckpointSequenceNumber;
+use myso_types::object::Object;
+use myso_types::storage::ObjectKey;
 use prometheus::Registry;
+use std::ops::Range;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time::{Duration, sleep};
 use tracing::error;
 
+mod alpha;
 mod v2;
 
+/// The KV store backend that the server reads from.
+#[derive(Clone)]
+pub enum KvClient {
+    BigTable(BigTableClient),
+    RocksDb(RocksDbClient),
+}
+
 #[derive(Clone)]
 pub struct KvRpcServer {
     chain_id: ChainIdentifier,
-    client: BigTableClient,
+    client: KvClient,
     server_version: Option<ServerVersion>,
     checkpoint_bucket: Option<String>,
     cache: Arc<RwLock<Option<GetServiceInfoResponse>>>,
@@ -33,7 +53,7 @@ impl KvRpcServer {
         server_version: Option<ServerVersion>,
         registry: &Registry,
     ) -> anyhow::Result<Self> {
-        let mut client = BigTableClient::new_remote(
+        let client = BigTableClient::new_remote(
             instance_id,
             project_id,
             false,
@@ -43,6 +63,33 @@ impl KvRpcServer {
             app_profile_id,
         )
         .await?;
+        Self::from_client(
+            KvClient::BigTable(client),
+            checkpoint_bucket,
+            server_version,
+        )
+        .await
+    }
+
+    /// Serve reads from the embedded RocksDB database at `path`, written to by a separate indexer
+    /// process. The database is opened as a secondary instance, keeping its own logs in
+    /// `secondary_path`, and periodically catches up with the indexer's writes.
+    pub async fn new_rocksdb(
+        path: impl AsRef<Path>,
+        secondary_path: impl AsRef<Path>,
+        checkpoint_bucket: Option<String>,
+        server_version: Option<ServerVersion>,
+    ) -> anyhow::Result<Self> {
+        let client = RocksDbClient::open_secondary(path, secondary_path)?;
+        Self::from_client(KvClient::RocksDb(client), checkpoint_bucket, server_version).await
+    }
+
+    async fn from_client(
+        mut client: KvClient,
+        checkpoint_bucket: Option<String>,
+        server_version: Option<ServerVersion>,
+    ) -> anyhow::Result<Self> {
+        client.catch_up()?;
         let genesis = client
             .get_checkpoints(&[0])
             .await?
@@ -62,6 +109,12 @@ impl KvRpcServer {
         let server_clone = server.clone();
         tokio::spawn(async move {
             loop {
+                // Refresh the secondary before reading the watermark, so that the watermark never
+                // gets ahead of the data the server can read.
+                if let Err(e) = server_clone.client.catch_up() {
+                    error!("Failed to catch up with the KV store: {:?}", e);
+                }
+
                 match v2::get_service_info(
                     server_clone.client.clone(),
                     server_clone.chain_id,
@@ -82,3 +135,129 @@ impl KvRpcServer {
         Ok(server)
     }
 }
+
+impl KvClient {
+    /// Bring the client up-to-date with the latest writes to the KV store. This is only necessary
+    /// for RocksDB, where the server reads from a secondary instance of the indexer's database.
+    fn catch_up(&self) -> Result<()> {
+        match self {
+            KvClient::BigTable(_) => Ok(()),
+            KvClient::RocksDb(client) => client.try_catch_up_with_primary(),
+        }
+    }
+}
+
+#[async_trait]
+impl KeyValueStoreReader for KvClient {
+    async fn get_objects(&mut self, objects: &[ObjectKey]) -> Result<Vec<Object>> {
+        match self {
+            KvClient::BigTable(client) => client.get_objects(objects).await,
+            KvClient::RocksDb(client) => client.get_objects(objects).await,
+        }
+    }
+
+    async fn get_transactions(
+        &mut self,
+        transactions: &[TransactionDigest],
+    ) -> Result<Vec<TransactionData>> {
+        match self {
+            KvClient::BigTable(client) => client.get_transactions(transactions).await,
+            KvClient::RocksDb(client) => client.get_transactions(transactions).await,
+        }
+    }
+
+    async fn get_checkpoints(
+        &mut self,
+        sequence_numbers: &[CheckpointSequenceNumber],
+    ) -> Result<Vec<CheckpointData>> {
+        match self {
+            KvClient::BigTable(client) => client.get_checkpoints(sequence_numbers).await,
+            KvClient::RocksDb(client) => client.get_checkpoints(sequence_numbers).await,
+        }
+    }
+
+    async fn get_checkpoint_range(
+        &mut self,
+        range: Range<CheckpointSequenceNumber>,
+        limit: usize,
+    ) -> Result<Vec<CheckpointData>> {
+        match self {
+            KvClient::BigTable(client) => client.get_checkpoint_range(range, limit).await,
+            KvClient::RocksDb(client) => client.get_checkpoint_range(range, limit).await,
+        }
+    }
+
+    async fn get_checkpoint_by_digest(
+        &mut self,
+        digest: CheckpointDigest,
+    ) -> Result<Option<CheckpointData>> {
+        match self {
+            KvClient::BigTable(client) => client.get_checkpoint_by_digest(digest).await,
+            KvClient::RocksDb(client) => client.get_checkpoint_by_digest(digest).await,
+        }
+    }
+
+    async fn get_watermark_for_pipelines(
+        &mut self,
+        pipelines: &[&str],
+    ) -> Result<Option<Watermark>> {
+        match self {
+            KvClient::BigTable(client) => client.get_watermark_for_pipelines(pipelines).await,
+            KvClient::RocksDb(client) => client.get_watermark_for_pipelines(pipelines).await,
+        }
+    }
+
+    async fn get_latest_object(&mut self, object_id: &ObjectID) -> Result<Option<Object>> {
+        match self {
+            KvClient::BigTable(client) => client.get_latest_object(object_id).await,
+            KvClient::RocksDb(client) => client.get_latest_object(object_id).await,
+        }
+    }
+
+    async fn get_object_versions(
+        &mut self,
+        object_id: &ObjectID,
+        versions: Range<SequenceNumber>,
+        limit: usize,
+    ) -> Result<Vec<Object>> {
+        match self {
+            KvClient::BigTable(client) => {
+                client.get_object_versions(object_id, versions, limit).await
+            }
+            KvClient::RocksDb(client) => {
+                client.get_object_versions(object_id, versions, limit).await
+            }
+        }
+    }
+
+    async fn get_epoch(&mut self, epoch_id: EpochId) -> Result<Option<EpochData>> {
+        match self {
+            KvClient::BigTable(client) => client.get_epoch(epoch_id).await,
+            KvClient::RocksDb(client) => client.get_epoch(epoch_id).await,
+        }
+    }
+
+    async fn get_latest_epoch(&mut self) -> Result<Option<EpochData>> {
+        match self {
+            KvClient::BigTable(client) => client.get_latest_epoch().await,
+            KvClient::RocksDb(client) => client.get_latest_epoch().await,
+        }
+    }
+
+    async fn get_events_for_transactions(
+        &mut self,
+        keys: &[TransactionDigest],
+    ) -> Result<Vec<(TransactionDigest, TransactionEventsData)>> {
+        match self {
+            KvClient::BigTable(client) => client.get_events_for_transactions(keys).await,
+            KvClient::RocksDb(client) => client.get_events_for_transactions(keys).await,
+        }
+    }
+
+    async fn get_object_types(&mut self, object_ids: &[ObjectID]) -> Result<Vec<ObjectType>> {
+        match self {
+            KvClient::BigTable(client) => client.get_object_types(object_ids).await,
+            KvClient::RocksDb(client) => client.get_object_types(object_ids).await,
+        }
+    }
+}
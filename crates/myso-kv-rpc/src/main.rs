@@ -7,9 +7,11 @@ use axum::Router;
 use axum::routing::get;
 use clap::Parser;
 use myso_kv_rpc::KvRpcServer;
+use myso_rpc_api::grpc::alpha::archive_service_proto::archive_service_server::ArchiveServiceServer;
 use myso_rpc_api::{RpcMetrics, RpcMetricsMakeCallbackHandler, ServerVersion};
 use mysten_network::callback::CallbackLayer;
 use prometheus::Registry;
+use std::path::PathBuf;
 use std::sync::Arc;
 use telemetry_subscribers::TelemetryConfig;
 use tonic::transport::{Identity, Server, ServerTlsConfig};
@@ -18,8 +20,12 @@ bin_version::bin_version!();
 
 #[derive(Parser)]
 struct App {
-    credentials: String,
-    instance_id: String,
+    /// Path to the GCP service account credentials used to read from BigTable.
+    #[clap(required_unless_present = "rocksdb_path")]
+    credentials: Option<String>,
+    /// BigTable instance to read from.
+    #[clap(required_unless_present = "rocksdb_path")]
+    instance_id: Option<String>,
     #[clap(default_value = "[::1]:8000")]
     address: String,
     #[clap(default_value = "127.0.0.1")]
@@ -37,6 +43,19 @@ struct App {
     app_profile_id: Option<String>,
     #[clap(long = "checkpoint-bucket")]
     checkpoint_bucket: Option<String>,
+    /// Read from the embedded RocksDB database written by `myso-kvstore-alt --rocksdb-path`,
+    /// instead of BigTable. The credentials and instance ID are not required in this mode, but
+    /// positional arguments keep their order, so they must still be given to set the address.
+    #[clap(
+        long = "rocksdb-path",
+        requires = "rocksdb_secondary_path",
+        conflicts_with_all = ["bigtable_project", "app_profile_id"]
+    )]
+    rocksdb_path: Option<PathBuf>,
+    /// Directory where the server keeps its own logs for the RocksDB database, which it opens as a
+    /// secondary instance.
+    #[clap(long = "rocksdb-secondary-path")]
+    rocksdb_secondary_path: Option<PathBuf>,
 }
 
 async fn health_check() -> &'static str {
@@ -50,24 +69,35 @@ async fn main() -> Result<()> {
         .install_default()
         .expect("Failed to install CryptoProvider");
     let app = App::parse();
-    unsafe {
-        std::env::set_var("GOOGLE_APPLICATION_CREDENTIALS", app.credentials.clone());
-    };
+    if let Some(credentials) = &app.credentials {
+        unsafe {
+            std::env::set_var("GOOGLE_APPLICATION_CREDENTIALS", credentials);
+        };
+    }
     let server_version = Some(ServerVersion::new("myso-kv-rpc", VERSION));
     let registry_service = mysten_metrics::start_prometheus_server(
         format!("{}:{}", app.metrics_host, app.metrics_port).parse()?,
     );
     let registry: Registry = registry_service.default_registry();
     mysten_metrics::init_metrics(&registry);
-    let server = KvRpcServer::new(
-        app.instance_id,
-        app.bigtable_project,
-        app.app_profile_id,
-        app.checkpoint_bucket,
-        server_version,
-        &registry,
-    )
-    .await?;
+    let server = match (app.rocksdb_path, app.rocksdb_secondary_path) {
+        (Some(path), Some(secondary_path)) => {
+            KvRpcServer::new_rocksdb(path, secondary_path, app.checkpoint_bucket, server_version)
+                .await?
+        }
+        _ => {
+            KvRpcServer::new(
+                app.instance_id
+                    .expect("instance_id is required without rocksdb_path"),
+                app.bigtable_project,
+                app.app_profile_id,
+                app.checkpoint_bucket,
+                server_version,
+                &registry,
+            )
+            .await?
+        }
+    };
     let addr = app.address.parse()?;
     let mut builder = Server::builder();
     if !app.tls_cert.is_empty() && !app.tls_key.is_empty() {
@@ -104,8 +134,11 @@ async fn main() -> Result<()> {
             Arc::new(RpcMetrics::new(&registry)),
         )))
         .add_service(
-            myso_rpc::proto::myso::rpc::v2::ledger_service_server::LedgerServiceServer::new(server),
+            myso_rpc::proto::myso::rpc::v2::ledger_service_server::LedgerServiceServer::new(
+                server.clone(),
+            ),
         )
+        .add_service(ArchiveServiceServer::new(server))
         .add_service(reflection_v1)
         .add_service(reflection_v1alpha)
         .serve(addr)
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use myso_data_ingestion_core::{CheckpointReader, create_remote_store_client};
-use myso_kvstore::{BigTableClient, CHECKPOINTS_PIPELINE, KeyValueStoreReader};
+use myso_kvstore::{CHECKPOINTS_PIPELINE, CheckpointData, KeyValueStoreReader};
 use myso_rpc::field::{FieldMask, FieldMaskTree, FieldMaskUtil};
 use myso_rpc::merge::Merge;
 use myso_rpc::proto::myso::rpc::v2::get_checkpoint_request::CheckpointId;
@@ -13,10 +13,12 @@ use myso_rpc_api::{
 };
 use myso_types::digests::CheckpointDigest;
 
+use crate::KvClient;
+
 pub const READ_MASK_DEFAULT: &str = "sequence_number,digest";
 
 pub async fn get_checkpoint(
-    mut client: BigTableClient,
+    mut client: KvClient,
     request: GetCheckpointRequest,
     checkpoint_bucket: Option<String>,
 ) -> Result<GetCheckpointResponse, RpcError> {
@@ -62,18 +64,7 @@ pub async fn get_checkpoint(
         }
     };
     let sequence_number = checkpoint.summary.sequence_number;
-    let mut message = Checkpoint::default();
-    let summary: myso_sdk_types::CheckpointSummary = checkpoint.summary.try_into()?;
-    let signatures: myso_sdk_types::ValidatorAggregatedSignature = checkpoint.signatures.into();
-    message.merge(&summary, &read_mask);
-    message.merge(signatures, &read_mask);
-
-    if read_mask.contains(Checkpoint::CONTENTS_FIELD.name) {
-        message.merge(
-            myso_sdk_types::CheckpointContents::try_from(checkpoint.contents)?,
-            &read_mask,
-        );
-    }
+    let mut message = checkpoint_to_response(checkpoint, &read_mask)?;
 
     if (read_mask.contains(Checkpoint::TRANSACTIONS_FIELD)
         || read_mask.contains(Checkpoint::OBJECTS_FIELD))
@@ -91,3 +82,25 @@ pub async fn get_checkpoint(
 
     Ok(GetCheckpointResponse::new(message))
 }
+
+/// Convert the parts of `checkpoint` that are stored in the KV store (its summary, signatures and
+/// contents) into a response message, according to `read_mask`.
+pub(crate) fn checkpoint_to_response(
+    checkpoint: CheckpointData,
+    read_mask: &FieldMaskTree,
+) -> Result<Checkpoint, RpcError> {
+    let mut message = Checkpoint::default();
+    let summary: myso_sdk_types::CheckpointSummary = checkpoint.summary.try_into()?;
+    let signatures: myso_sdk_types::ValidatorAggregatedSignature = checkpoint.signatures.into();
+    message.merge(&summary, read_mask);
+    message.merge(signatures, read_mask);
+
+    if read_mask.contains(Checkpoint::CONTENTS_FIELD.name) {
+        message.merge(
+            myso_sdk_types::CheckpointContents::try_from(checkpoint.contents)?,
+            read_mask,
+        );
+    }
+
+    Ok(message)
+}
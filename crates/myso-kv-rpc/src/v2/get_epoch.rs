@@ -2,7 +2,7 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use myso_kvstore::{BigTableClient, KeyValueStoreReader};
+use myso_kvstore::KeyValueStoreReader;
 use myso_protocol_config::{Chain, ProtocolConfig};
 use myso_rpc::field::{FieldMask, FieldMaskTree, FieldMaskUtil};
 use myso_rpc::merge::Merge;
@@ -17,10 +17,12 @@ use myso_rpc_api::{
 use myso_sdk_types::ValidatorCommittee;
 use myso_types::myso_system_state::MySoSystemStateTrait;
 
+use crate::KvClient;
+
 pub const READ_MASK_DEFAULT: &str = "epoch,first_checkpoint,last_checkpoint,start,end,reference_gas_price,protocol_config.protocol_version";
 
 pub async fn get_epoch(
-    mut client: BigTableClient,
+    mut client: KvClient,
     request: GetEpochRequest,
     chain: Chain,
 ) -> myso_rpc_api::Result<GetEpochResponse> {
@@ -2,7 +2,7 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use myso_kvstore::{BigTableClient, KeyValueStoreReader};
+use myso_kvstore::KeyValueStoreReader;
 use myso_rpc::merge::Merge;
 use myso_rpc::proto::myso::rpc::v2::BatchGetObjectsRequest;
 use myso_rpc::proto::myso::rpc::v2::BatchGetObjectsResponse;
@@ -16,10 +16,12 @@ use myso_rpc_api::{
 use myso_types::storage::ObjectKey;
 use std::collections::HashMap;
 
+use crate::KvClient;
+
 pub const MAX_BATCH_REQUESTS: usize = 1000;
 
 pub(crate) async fn get_object(
-    mut client: BigTableClient,
+    mut client: KvClient,
     GetObjectRequest {
         object_id,
         version,
@@ -48,7 +50,7 @@ pub(crate) async fn get_object(
 }
 
 pub(crate) async fn batch_get_objects(
-    mut client: BigTableClient,
+    mut client: KvClient,
     BatchGetObjectsRequest {
         requests,
         read_mask,
@@ -2,7 +2,7 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use myso_kvstore::{BigTableClient, KeyValueStoreReader, TransactionData};
+use myso_kvstore::{KeyValueStoreReader, TransactionData};
 use myso_rpc::field::{FieldMask, FieldMaskTree, FieldMaskUtil};
 use myso_rpc::merge::Merge;
 use myso_rpc::proto::myso::rpc::v2::{
@@ -18,11 +18,13 @@ use myso_types::base_types::TransactionDigest;
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use crate::KvClient;
+
 pub const MAX_BATCH_REQUESTS: usize = 200;
 pub const READ_MASK_DEFAULT: &str = "digest";
 
 pub async fn get_transaction(
-    mut client: BigTableClient,
+    mut client: KvClient,
     request: GetTransactionRequest,
 ) -> Result<GetTransactionResponse, RpcError> {
     let transaction_digest = request
@@ -64,7 +66,7 @@ pub async fn get_transaction(
 }
 
 pub async fn batch_get_transactions(
-    mut client: BigTableClient,
+    mut client: KvClient,
     BatchGetTransactionsRequest {
         digests, read_mask, ..
     }: BatchGetTransactionsRequest,
@@ -115,7 +117,7 @@ pub async fn batch_get_transactions(
     Ok(BatchGetTransactionsResponse::new(transactions))
 }
 
-fn transaction_to_response(
+pub(crate) fn transaction_to_response(
     source: TransactionData,
     mask: &FieldMaskTree,
 ) -> Result<ExecutedTransaction, RpcError> {
@@ -2,7 +2,7 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use myso_kvstore::{BigTableClient, KeyValueStoreReader};
+use myso_kvstore::KeyValueStoreReader;
 use myso_rpc::proto::myso::rpc::v2::{
     BatchGetObjectsRequest, BatchGetObjectsResponse, BatchGetTransactionsRequest,
     BatchGetTransactionsResponse, GetCheckpointRequest, GetCheckpointResponse, GetEpochRequest,
@@ -15,12 +15,12 @@ use myso_rpc_api::{CheckpointNotFoundError, RpcError, ServerVersion};
 use myso_sdk_types::Digest;
 use myso_types::digests::ChainIdentifier;
 
-use crate::KvRpcServer;
+use crate::{KvClient, KvRpcServer};
 
-mod get_checkpoint;
+pub(crate) mod get_checkpoint;
 mod get_epoch;
 mod get_object;
-mod get_transaction;
+pub(crate) mod get_transaction;
 
 #[tonic::async_trait]
 impl LedgerService for KvRpcServer {
@@ -115,7 +115,7 @@ impl LedgerService for KvRpcServer {
 }
 
 pub(crate) async fn get_service_info(
-    mut client: BigTableClient,
+    mut client: KvClient,
     chain_id: ChainIdentifier,
     server_version: Option<ServerVersion>,
 ) -> Result<GetServiceInfoResponse, RpcError> {
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::future::Future;
+use std::ops::Range;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -24,6 +25,7 @@ use http::Response;
 use myso_types::base_types::EpochId;
 use myso_types::base_types::ObjectID;
 use myso_types::base_types::ObjectType;
+use myso_types::base_types::SequenceNumber;
 use myso_types::base_types::TransactionDigest;
 use myso_types::digests::CheckpointDigest;
 use myso_types::messages_checkpoint::CheckpointSequenceNumber;
@@ -55,6 +57,7 @@ use crate::bigtable::proto::bigtable::v2::request_stats::StatsView;
 use crate::bigtable::proto::bigtable::v2::row_filter::Chain;
 use crate::bigtable::proto::bigtable::v2::row_filter::Filter;
 use crate::bigtable::proto::bigtable::v2::row_range::EndKey;
+use crate::bigtable::proto::bigtable::v2::row_range::StartKey;
 use crate::tables;
 
 /// Error returned when a batch write has per-entry failures.
@@ -510,6 +513,69 @@ impl BigTableClient {
         Ok(result)
     }
 
+    /// Read up to `limit` rows from `table` with keys in the range `[start, end)`, in ascending
+    /// order of key.
+    async fn range_scan(
+        &mut self,
+        table_name: &str,
+        start: Vec<u8>,
+        end: Vec<u8>,
+        limit: usize,
+    ) -> Result<Vec<(Bytes, Vec<(Bytes, Bytes)>)>> {
+        let start_time = Instant::now();
+        let result = self
+            .range_scan_internal(table_name, start, end, limit)
+            .await;
+        let elapsed_ms = start_time.elapsed().as_millis() as f64;
+        let labels = [&self.client_name, table_name];
+        let Some(metrics) = &self.metrics else {
+            return result;
+        };
+
+        match &result {
+            Ok(rows) => {
+                metrics.kv_scan_success.with_label_values(&labels).inc();
+                if rows.is_empty() {
+                    metrics.kv_scan_not_found.with_label_values(&labels).inc();
+                }
+                metrics
+                    .kv_scan_latency_ms
+                    .with_label_values(&labels)
+                    .observe(elapsed_ms);
+            }
+            Err(_) => metrics.kv_scan_error.with_label_values(&labels).inc(),
+        }
+
+        result
+    }
+
+    async fn range_scan_internal(
+        &mut self,
+        table_name: &str,
+        start: Vec<u8>,
+        end: Vec<u8>,
+        limit: usize,
+    ) -> Result<Vec<(Bytes, Vec<(Bytes, Bytes)>)>> {
+        let range = RowRange {
+            start_key: Some(StartKey::StartKeyClosed(start.into())),
+            end_key: Some(EndKey::EndKeyOpen(end.into())),
+        };
+        let request = ReadRowsRequest {
+            table_name: format!("{}{}", self.table_prefix, table_name),
+            rows_limit: limit as i64,
+            rows: Some(RowSet {
+                row_keys: vec![],
+                row_ranges: vec![range],
+            }),
+            filter: Some(RowFilter {
+                filter: Some(Filter::CellsPerColumnLimitFilter(1)),
+            }),
+            request_stats_view: 2,
+            ..ReadRowsRequest::default()
+        };
+        self.read_rows(request, table_name).await
+    }
+
     async fn reversed_scan(
         &mut self,
         table_name: &str,
@@ -762,6 +828,49 @@ impl KeyValueStoreReader for BigTableClient {
         }
         Ok(result)
     }
+
+    async fn get_checkpoint_range(
+        &mut self,
+        range: Range<CheckpointSequenceNumber>,
+        limit: usize,
+    ) -> Result<Vec<CheckpointData>> {
+        if range.is_empty() || limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let start = tables::checkpoints::encode_key(range.start);
+        let end = tables::checkpoints::encode_key(range.end);
+        let mut checkpoints = vec![];
+        for (_, row) in self
+            .range_scan(tables::checkpoints::NAME, start, end, limit)
+            .await?
+        {
+            checkpoints.push(tables::checkpoints::decode(&row)?);
+        }
+        Ok(checkpoints)
+    }
+
+    async fn get_object_versions(
+        &mut self,
+        object_id: &ObjectID,
+        versions: Range<SequenceNumber>,
+        limit: usize,
+    ) -> Result<Vec<Object>> {
+        if versions.is_empty() || limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let start = Self::raw_object_key(&ObjectKey(*object_id, versions.start));
+        let end = Self::raw_object_key(&ObjectKey(*object_id, versions.end));
+        let mut objects = vec![];
+        for (_, row) in self
+            .range_scan(tables::objects::NAME, start, end, limit)
+            .await?
+        {
+            objects.push(tables::objects::decode(&row)?);
+        }
+        Ok(objects)
+    }
 }
 
 impl Service<Request<Body>> for AuthChannel {
@@ -2,6 +2,7 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -16,6 +17,9 @@ use myso_kvstore::BIGTABLE_MAX_MUTATIONS;
 use myso_kvstore::BigTableClient;
 use myso_kvstore::BigTableIndexer;
 use myso_kvstore::BigTableStore;
+use myso_kvstore::RocksDbClient;
+use myso_kvstore::RocksDbIndexer;
+use myso_kvstore::RocksDbStore;
 use myso_kvstore::set_max_mutations;
 use myso_kvstore::set_write_legacy_data;
 use telemetry_subscribers::TelemetryConfig;
@@ -36,7 +40,12 @@ fn parse_max_mutations(s: &str) -> Result<usize, String> {
 #[command(about = "KVStore indexer using myso-indexer-alt-framework")]
 struct Args {
     /// BigTable instance ID
-    instance_id: String,
+    #[arg(required_unless_present = "rocksdb_path")]
+    instance_id: Option<String>,
+
+    /// Write to an embedded RocksDB database at this path, instead of BigTable
+    #[arg(long, conflicts_with_all = ["instance_id", "bigtable_project", "app_profile_id"])]
+    rocksdb_path: Option<PathBuf>,
 
     /// GCP project ID for the BigTable instance (defaults to the token provider's project)
     #[arg(long)]
@@ -99,21 +108,6 @@ async fn main() -> Result<()> {
     }
 
     info!("Starting myso-kvstore-alt indexer");
-    info!(instance_id = %args.instance_id);
-
-    let client = BigTableClient::new_remote(
-        args.instance_id,
-        args.bigtable_project,
-        false,
-        None,
-        "myso-kvstore-alt".to_string(),
-        None,
-        args.app_profile_id,
-    )
-    .await?;
-
-    let store = BigTableStore::new(client);
-
     let registry = prometheus::Registry::new_custom(Some("kvstore_alt".into()), None)?;
     let metrics_service =
         myso_indexer_alt_metrics::MetricsService::new(args.metrics_args, registry.clone());
@@ -134,18 +128,53 @@ async fn main() -> Result<()> {
         config.committer.watermark_interval_ms = v.as_millis() as u64;
     }
 
-    let bigtable_indexer = BigTableIndexer::new(
-        store,
-        args.indexer_args,
-        args.client_args,
-        ingestion_config,
-        config,
-        &registry,
-    )
-    .await?;
-
     let metrics_handle = metrics_service.run().await?;
-    let service = bigtable_indexer.indexer.run().await?;
+    let service = if let Some(path) = args.rocksdb_path {
+        info!(rocksdb_path = %path.display());
+        let store = RocksDbStore::new(RocksDbClient::open(path)?);
+
+        RocksDbIndexer::new(
+            store,
+            args.indexer_args,
+            args.client_args,
+            ingestion_config,
+            config,
+            &registry,
+        )
+        .await?
+        .indexer
+        .run()
+        .await?
+    } else {
+        let instance_id = args
+            .instance_id
+            .expect("instance_id is required without rocksdb_path");
+        info!(instance_id = %instance_id);
+
+        let client = BigTableClient::new_remote(
+            instance_id,
+            args.bigtable_project,
+            false,
+            None,
+            "myso-kvstore-alt".to_string(),
+            None,
+            args.app_profile_id,
+        )
+        .await?;
+
+        BigTableIndexer::new(
+            BigTableStore::new(client),
+            args.indexer_args,
+            args.client_args,
+            ingestion_config,
+            config,
+            &registry,
+        )
+        .await?
+        .indexer
+        .run()
+        .await?
+    };
 
     match service.attach(metrics_handle).main().await {
         Ok(()) => {}
@@ -17,6 +17,7 @@ use myso_types::full_checkpoint_content::Checkpoint;
 use crate::bigtable::client::PartialWriteError;
 use crate::bigtable::proto::bigtable::v2::mutate_rows_request::Entry;
 use crate::bigtable::store::BigTableStore;
+use crate::rocks::store::RocksDbStore;
 
 /// BigTable's hard limit for mutations per batch.
 pub const BIGTABLE_MAX_MUTATIONS: usize = 100_000;
@@ -42,6 +43,10 @@ pub trait BigTableProcessor: Processor<Value = Entry> {
 /// for writing entries to BigTable. Individual pipelines implement `BigTableProcessor`.
 pub struct BigTableHandler<P>(P);
 
+/// Generic wrapper that implements `concurrent::Handler` for any `BigTableProcessor`, writing its
+/// entries to the embedded RocksDB backend instead of BigTable.
+pub struct RocksDbHandler<P>(P);
+
 /// Batch of BigTable entries.
 /// Uses RwLock for interior mutability so we can remove succeeded entries on partial write failures.
 #[derive(Default)]
@@ -64,6 +69,33 @@ where
     }
 }
 
+impl<P> RocksDbHandler<P>
+where
+    P: BigTableProcessor,
+{
+    pub fn new(processor: P) -> Self {
+        Self(processor)
+    }
+}
+
+impl BigTableBatch {
+    /// Add entries from `values` to the batch, until it reaches the maximum number of mutations.
+    fn add(&self, values: &mut std::vec::IntoIter<Entry>) -> BatchStatus {
+        let mut inner = self.inner.write().unwrap();
+
+        for entry in values {
+            inner.total_mutations += entry.mutations.len();
+            inner.entries.insert(entry.row_key.clone(), entry);
+
+            if inner.total_mutations == max_mutations() {
+                return BatchStatus::Ready;
+            }
+        }
+
+        BatchStatus::Pending
+    }
+}
+
 #[async_trait::async_trait]
 impl<P> Processor for BigTableHandler<P>
 where
@@ -93,18 +125,7 @@ where
         batch: &mut Self::Batch,
         values: &mut std::vec::IntoIter<Self::Value>,
     ) -> BatchStatus {
-        let mut inner = batch.inner.write().unwrap();
-
-        for entry in values {
-            inner.total_mutations += entry.mutations.len();
-            inner.entries.insert(entry.row_key.clone(), entry);
-
-            if inner.total_mutations == max_mutations() {
-                return BatchStatus::Ready;
-            }
-        }
-
-        BatchStatus::Pending
+        batch.add(values)
     }
 
     async fn commit<'a>(
@@ -142,6 +163,59 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<P> Processor for RocksDbHandler<P>
+where
+    P: BigTableProcessor + Send + Sync,
+{
+    const NAME: &'static str = P::NAME;
+    const FANOUT: usize = <P as BigTableProcessor>::FANOUT;
+    type Value = Entry;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> anyhow::Result<Vec<Self::Value>> {
+        self.0.process(checkpoint).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<P> Handler for RocksDbHandler<P>
+where
+    P: BigTableProcessor + Send + Sync,
+{
+    type Store = RocksDbStore;
+    type Batch = BigTableBatch;
+
+    const MIN_EAGER_ROWS: usize = P::MIN_EAGER_ROWS;
+
+    fn batch(
+        &self,
+        batch: &mut Self::Batch,
+        values: &mut std::vec::IntoIter<Self::Value>,
+    ) -> BatchStatus {
+        batch.add(values)
+    }
+
+    async fn commit<'a>(
+        &self,
+        batch: &Self::Batch,
+        conn: &mut <Self::Store as Store>::Connection<'a>,
+    ) -> anyhow::Result<usize> {
+        let entries: Vec<Entry> = batch
+            .inner
+            .read()
+            .unwrap()
+            .entries
+            .values()
+            .cloned()
+            .collect();
+
+        // RocksDB writes the whole batch atomically, so there are no partial failures to handle.
+        let count = entries.len();
+        conn.client().write_entries(P::TABLE, entries)?;
+        Ok(count)
+    }
+}
+
 /// Set the maximum mutations per batch. Must be called before creating any BigTableHandler.
 /// Panics if called more than once or if value >= BIGTABLE_MAX_MUTATIONS.
 pub fn set_max_mutations(value: usize) {
@@ -164,6 +238,7 @@ mod tests {
     use crate::bigtable::client::BigTableClient;
     use crate::bigtable::mock_server::{ExpectedCall, MockBigtableServer};
     use crate::bigtable::store::BigTableStore;
+    use crate::rocks::client::RocksDbClient;
     use crate::tables;
 
     /// Simple test processor for testing the handler.
@@ -183,6 +258,23 @@ mod tests {
         const TABLE: &'static str = "test_table";
     }
 
+    /// Test processor that writes to a table known to the RocksDB backend.
+    struct EpochsTestProcessor;
+
+    #[async_trait::async_trait]
+    impl Processor for EpochsTestProcessor {
+        const NAME: &'static str = "test_epochs_pipeline";
+        type Value = Entry;
+
+        async fn process(&self, _: &Arc<Checkpoint>) -> anyhow::Result<Vec<Entry>> {
+            Ok(vec![])
+        }
+    }
+
+    impl BigTableProcessor for EpochsTestProcessor {
+        const TABLE: &'static str = tables::epochs::NAME;
+    }
+
     fn make_entry(key: &[u8]) -> Entry {
         tables::make_entry(key.to_vec(), [("col", Bytes::from_static(b"value"))], None)
     }
@@ -259,4 +351,40 @@ mod tests {
             assert_eq!(inner.entries.len(), 3);
         }
     }
+
+    #[tokio::test]
+    async fn test_rocksdb_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = RocksDbClient::open(dir.path()).unwrap();
+        let store = RocksDbStore::new(client.clone());
+        let mut conn = store.connect().await.unwrap();
+
+        let handler = RocksDbHandler::new(EpochsTestProcessor);
+        let mut batch = BigTableBatch::default();
+        let entries: Vec<Entry> = (0..3u64)
+            .map(|i| {
+                tables::make_entry(
+                    tables::epochs::encode_key(i),
+                    tables::epochs::encode_end(i * 1000, i * 10),
+                    None,
+                )
+            })
+            .collect();
+        handler.batch(&mut batch, &mut entries.into_iter());
+
+        assert_eq!(handler.commit(&batch, &mut conn).await.unwrap(), 3);
+
+        let rows = client
+            .multi_get(
+                tables::epochs::NAME,
+                (0..4u64).map(tables::epochs::encode_key).collect(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(rows.len(), 3);
+        for (i, (_, row)) in rows.iter().enumerate() {
+            let epoch = tables::epochs::decode(row).unwrap();
+            assert_eq!(epoch.end_checkpoint, Some(i as u64 * 10));
+        }
+    }
 }
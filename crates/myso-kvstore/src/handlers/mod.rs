@@ -7,7 +7,9 @@ pub use checkpoints_by_digest::CheckpointsByDigestPipeline;
 pub use epochs_end::EpochEndPipeline;
 pub use epochs_legacy::{EpochLegacyBatch, EpochLegacyPipeline, PrevEpochUpdate};
 pub use epochs_start::EpochStartPipeline;
-pub use handler::{BIGTABLE_MAX_MUTATIONS, BigTableHandler, BigTableProcessor, set_max_mutations};
+pub use handler::{
+    BIGTABLE_MAX_MUTATIONS, BigTableHandler, BigTableProcessor, RocksDbHandler, set_max_mutations,
+};
 pub use object_types::ObjectTypesPipeline;
 pub use objects::ObjectsPipeline;
 pub use transactions::TransactionsPipeline;
@@ -16,8 +16,12 @@ pub use crate::handlers::EpochStartPipeline;
 pub use crate::handlers::ObjectTypesPipeline;
 pub use crate::handlers::ObjectsPipeline;
 pub use crate::handlers::PrevEpochUpdate;
+pub use crate::handlers::RocksDbHandler;
 pub use crate::handlers::TransactionsPipeline;
 pub use crate::handlers::set_max_mutations;
+pub use crate::rocks::client::RocksDbClient;
+pub use crate::rocks::store::RocksDbConnection;
+pub use crate::rocks::store::RocksDbStore;
 
 pub const CHECKPOINTS_PIPELINE: &str =
     <BigTableHandler<CheckpointsPipeline> as myso_indexer_alt_framework::pipeline::Processor>::NAME;
@@ -51,10 +55,15 @@ pub const ALL_PIPELINE_NAMES: [&str; 8] = [
     EPOCH_LEGACY_PIPELINE,
 ];
 
+use std::ops::Range;
 use std::sync::OnceLock;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use futures::stream;
+use futures::stream::BoxStream;
 use myso_indexer_alt_framework::Indexer;
 use myso_indexer_alt_framework::IndexerArgs;
 use myso_indexer_alt_framework::ingestion::ClientArgs;
@@ -63,6 +72,7 @@ use myso_indexer_alt_framework::pipeline::concurrent::ConcurrentConfig;
 use myso_types::balance_change::BalanceChange;
 use myso_types::base_types::ObjectID;
 use myso_types::base_types::ObjectType;
+use myso_types::base_types::SequenceNumber;
 use myso_types::committee::EpochId;
 use myso_types::crypto::AuthorityStrongQuorumSignInfo;
 use myso_types::digests::CheckpointDigest;
@@ -82,6 +92,7 @@ use serde::Serialize;
 
 mod bigtable;
 mod handlers;
+mod rocks;
 pub mod tables;
 
 static WRITE_LEGACY_DATA: OnceLock<bool> = OnceLock::new();
@@ -102,6 +113,12 @@ pub struct BigTableIndexer {
     pub indexer: Indexer<BigTableStore>,
 }
 
+/// Indexer writing to the embedded RocksDB backend. It runs the same pipelines as
+/// [`BigTableIndexer`], except for the legacy epochs pipeline.
+pub struct RocksDbIndexer {
+    pub indexer: Indexer<RocksDbStore>,
+}
+
 #[derive(Clone, Debug)]
 pub struct CheckpointData {
     pub summary: CheckpointSummary,
@@ -162,6 +179,10 @@ const WATERMARK_PIPELINES: [&str; 7] = [
     EPOCH_END_PIPELINE,
 ];
 
+/// Number of rows fetched from the store at a time by the streaming methods of
+/// [`KeyValueStoreReader`].
+pub const STREAM_PAGE_SIZE: usize = 100;
+
 #[async_trait]
 pub trait KeyValueStoreReader {
     async fn get_objects(&mut self, objects: &[ObjectKey]) -> Result<Vec<Object>>;
@@ -196,6 +217,104 @@ pub trait KeyValueStoreReader {
         keys: &[TransactionDigest],
     ) -> Result<Vec<(TransactionDigest, TransactionEventsData)>>;
     async fn get_object_types(&mut self, object_ids: &[ObjectID]) -> Result<Vec<ObjectType>>;
+    /// Return up to `limit` checkpoints with sequence numbers in `range`, in ascending order.
+    async fn get_checkpoint_range(
+        &mut self,
+        range: Range<CheckpointSequenceNumber>,
+        limit: usize,
+    ) -> Result<Vec<CheckpointData>>;
+    /// Return up to `limit` versions of `object_id` within `versions`, in ascending order.
+    async fn get_object_versions(
+        &mut self,
+        object_id: &ObjectID,
+        versions: Range<SequenceNumber>,
+        limit: usize,
+    ) -> Result<Vec<Object>>;
+
+    /// Stream the checkpoints with sequence numbers in `range`, in ascending order. Checkpoints
+    /// are fetched from the store a page at a time, as the stream is consumed.
+    fn stream_checkpoints(
+        &mut self,
+        range: Range<CheckpointSequenceNumber>,
+    ) -> BoxStream<'_, Result<CheckpointData>>
+    where
+        Self: Send + Sized,
+    {
+        stream::try_unfold((self, range), |(store, range)| async move {
+            if range.is_empty() {
+                return Ok(None);
+            }
+
+            let page = store
+                .get_checkpoint_range(range.clone(), STREAM_PAGE_SIZE)
+                .await?;
+
+            let Some(last) = page.last() else {
+                return Ok(None);
+            };
+
+            let next = last.summary.sequence_number.saturating_add(1)..range.end;
+            Ok(Some((page, (store, next))))
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Stream the versions of `object_id` within `versions`, in ascending order. Versions are
+    /// fetched from the store a page at a time, as the stream is consumed.
+    fn stream_object_versions(
+        &mut self,
+        object_id: ObjectID,
+        versions: Range<SequenceNumber>,
+    ) -> BoxStream<'_, Result<Object>>
+    where
+        Self: Send + Sized,
+    {
+        stream::try_unfold((self, versions), move |(store, versions)| async move {
+            if versions.is_empty() {
+                return Ok(None);
+            }
+
+            let page = store
+                .get_object_versions(&object_id, versions.clone(), STREAM_PAGE_SIZE)
+                .await?;
+
+            let Some(last) = page.last() else {
+                return Ok(None);
+            };
+
+            let next = SequenceNumber::from_u64(last.version().value().saturating_add(1));
+            Ok(Some((page, (store, next..versions.end))))
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Stream the transactions (including their events) with the given `digests`, in the order
+    /// they were requested, skipping transactions that are not found. Transactions are fetched
+    /// from the store in batches, as the stream is consumed.
+    fn stream_transactions(
+        &mut self,
+        digests: Vec<TransactionDigest>,
+    ) -> BoxStream<'_, Result<TransactionData>>
+    where
+        Self: Send + Sized,
+    {
+        stream::try_unfold((self, digests, 0), |(store, digests, offset)| async move {
+            if offset >= digests.len() {
+                return Ok(None);
+            }
+
+            let end = digests.len().min(offset + STREAM_PAGE_SIZE);
+            let page = store.get_transactions(&digests[offset..end]).await?;
+            Ok(Some((page, (store, digests, end))))
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
 }
 
 impl BigTableIndexer {
@@ -256,6 +375,58 @@ impl BigTableIndexer {
     }
 }
 
+impl RocksDbIndexer {
+    pub async fn new(
+        store: RocksDbStore,
+        indexer_args: IndexerArgs,
+        client_args: ClientArgs,
+        ingestion_config: IngestionConfig,
+        config: ConcurrentConfig,
+        registry: &Registry,
+    ) -> Result<Self> {
+        let mut indexer = Indexer::new(
+            store,
+            indexer_args,
+            client_args,
+            ingestion_config,
+            None,
+            registry,
+        )
+        .await?;
+
+        indexer
+            .concurrent_pipeline(RocksDbHandler::new(CheckpointsPipeline), config.clone())
+            .await?;
+        indexer
+            .concurrent_pipeline(
+                RocksDbHandler::new(CheckpointsByDigestPipeline),
+                config.clone(),
+            )
+            .await?;
+        indexer
+            .concurrent_pipeline(RocksDbHandler::new(TransactionsPipeline), config.clone())
+            .await?;
+        indexer
+            .concurrent_pipeline(RocksDbHandler::new(ObjectsPipeline), config.clone())
+            .await?;
+        indexer
+            .concurrent_pipeline(RocksDbHandler::new(ObjectTypesPipeline), config.clone())
+            .await?;
+        indexer
+            .concurrent_pipeline(RocksDbHandler::new(EpochStartPipeline), config.clone())
+            .await?;
+        indexer
+            .concurrent_pipeline(RocksDbHandler::new(EpochEndPipeline), config)
+            .await?;
+
+        Ok(Self { indexer })
+    }
+
+    pub fn pipeline_names(&self) -> Vec<&'static str> {
+        self.indexer.pipelines().collect()
+    }
+}
+
 impl From<myso_indexer_alt_framework_store_traits::CommitterWatermark> for Watermark {
     fn from(w: myso_indexer_alt_framework_store_traits::CommitterWatermark) -> Self {
         Self {
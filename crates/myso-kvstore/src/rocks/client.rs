@@ -0,0 +1,655 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::bail;
+use async_trait::async_trait;
+use bytes::Bytes;
+use myso_types::base_types::EpochId;
+use myso_types::base_types::ObjectID;
+use myso_types::base_types::ObjectType;
+use myso_types::base_types::SequenceNumber;
+use myso_types::base_types::TransactionDigest;
+use myso_types::digests::CheckpointDigest;
+use myso_types::messages_checkpoint::CheckpointSequenceNumber;
+use myso_types::object::Object;
+use myso_types::storage::ObjectKey;
+
+use crate::CheckpointData;
+use crate::EpochData;
+use crate::KeyValueStoreReader;
+use crate::TransactionData;
+use crate::TransactionEventsData;
+use crate::Watermark;
+use crate::bigtable::proto::bigtable::v2::mutate_rows_request::Entry;
+use crate::bigtable::proto::bigtable::v2::mutation;
+use crate::rocks::TABLES;
+use crate::rocks::cell_key;
+use crate::rocks::row_prefix;
+use crate::rocks::split_cell_key;
+use crate::tables;
+
+/// A row read from RocksDB: its key, and its cells as (column qualifier, value) pairs, ordered by
+/// column qualifier. This matches the shape of rows read from BigTable, so the same decoders can be
+/// used for both.
+type Row = (Bytes, Vec<(Bytes, Bytes)>);
+
+/// A client for the KV store, backed by an embedded RocksDB database. Cloning the client shares the
+/// underlying database.
+#[derive(Clone)]
+pub struct RocksDbClient {
+    db: Arc<rocksdb::DB>,
+}
+
+impl RocksDbClient {
+    /// Open (or create) the database at `path` for reading and writing. Only one process can hold
+    /// the database open for writing at a time.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let mut options = rocksdb::Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = rocksdb::DB::open_cf(&options, path, TABLES)
+            .context("Failed to open RocksDB KV store")?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Open the database at `primary` as a read-only secondary instance, which can be used while
+    /// another process writes to the database. `secondary` is a directory where the instance keeps
+    /// its own logs. The secondary only sees writes to the primary after a call to
+    /// [`Self::try_catch_up_with_primary`].
+    pub fn open_secondary(primary: impl AsRef<Path>, secondary: impl AsRef<Path>) -> Result<Self> {
+        let mut options = rocksdb::Options::default();
+        options.set_max_open_files(-1);
+
+        let db = rocksdb::DB::open_cf_as_secondary(&options, primary, secondary, TABLES)
+            .context("Failed to open RocksDB KV store as secondary")?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Bring a secondary instance up-to-date with the writes made to its primary. This is a no-op
+    /// for primary instances.
+    pub fn try_catch_up_with_primary(&self) -> Result<()> {
+        Ok(self.db.try_catch_up_with_primary()?)
+    }
+
+    /// Get the pipeline watermark from the watermarks table.
+    pub fn get_pipeline_watermark(&self, pipeline: &str) -> Result<Option<Watermark>> {
+        let key = tables::watermarks::encode_key(pipeline);
+        match self
+            .multi_get(tables::watermarks::NAME, vec![key], None)?
+            .pop()
+        {
+            Some((_, row)) => Ok(Some(tables::watermarks::decode(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Set the pipeline watermark in the watermarks table.
+    pub fn set_pipeline_watermark(&self, pipeline: &str, watermark: &Watermark) -> Result<()> {
+        let entry = tables::make_entry(
+            tables::watermarks::encode_key(pipeline),
+            tables::watermarks::encode(watermark)?,
+            Some(watermark.timestamp_ms_hi_inclusive),
+        );
+        self.write_entries(tables::watermarks::NAME, [entry])
+    }
+
+    /// Write pre-built entries to `table`, atomically. Only `SetCell` mutations are supported.
+    /// Cell timestamps are ignored: later writes to a cell replace earlier ones.
+    pub fn write_entries(
+        &self,
+        table: &str,
+        entries: impl IntoIterator<Item = Entry>,
+    ) -> Result<()> {
+        let cf = self.cf(table)?;
+        let mut batch = rocksdb::WriteBatch::default();
+
+        for entry in entries {
+            for m in entry.mutations {
+                let Some(mutation::Mutation::SetCell(cell)) = m.mutation else {
+                    bail!(
+                        "Unsupported mutation for row {:?} in {table}",
+                        entry.row_key
+                    );
+                };
+
+                batch.put_cf(
+                    &cf,
+                    cell_key(&entry.row_key, &cell.column_qualifier)?,
+                    cell.value,
+                );
+            }
+        }
+
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Read the rows with the given `keys` from `table`, in the order they were requested. Rows
+    /// that do not exist are skipped. If `columns` is provided, only cells in those columns are
+    /// returned.
+    pub fn multi_get(
+        &self,
+        table: &str,
+        keys: Vec<Vec<u8>>,
+        columns: Option<&[&str]>,
+    ) -> Result<Vec<Row>> {
+        let cf = self.cf(table)?;
+        let mut rows = vec![];
+
+        for key in keys {
+            let mut cells = self.read_row(&cf, &key)?;
+            if let Some(columns) = columns {
+                cells.retain(|(col, _)| columns.iter().any(|c| c.as_bytes() == col.as_ref()));
+            }
+
+            if !cells.is_empty() {
+                rows.push((Bytes::from(key), cells));
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Read the last row in `table` whose key is less than or equal to `upper_limit`, and has the
+    /// same length as it.
+    pub fn reversed_scan(&self, table: &str, upper_limit: &[u8]) -> Result<Option<Row>> {
+        let cf = self.cf(table)?;
+
+        // Column qualifiers are printable, so this sorts after every cell in `upper_limit`'s row.
+        let mut bound = row_prefix(upper_limit)?;
+        bound.push(0xff);
+
+        let mut iter = self.db.raw_iterator_cf(&cf);
+        iter.seek_for_prev(&bound);
+
+        let Some(key) = iter.key() else {
+            iter.status()?;
+            return Ok(None);
+        };
+
+        let (row_key, _) = split_cell_key(key)?;
+        if row_key.len() != upper_limit.len() {
+            return Ok(None);
+        }
+
+        let row_key = row_key.to_vec();
+        let cells = self.read_row(&cf, &row_key)?;
+        Ok(Some((Bytes::from(row_key), cells)))
+    }
+
+    /// Read up to `limit` rows from `table` with keys in the range `[start, end)`, in ascending
+    /// order of key. `start` and `end` must have the same length as the row keys being scanned.
+    pub fn range_scan(
+        &self,
+        table: &str,
+        start: &[u8],
+        end: &[u8],
+        limit: usize,
+    ) -> Result<Vec<Row>> {
+        let cf = self.cf(table)?;
+        let lower = row_prefix(start)?;
+        let upper = row_prefix(end)?;
+
+        let mut iter = self.db.raw_iterator_cf(&cf);
+        iter.seek(&lower);
+
+        let mut rows: Vec<Row> = vec![];
+        while let (Some(key), Some(value)) = (iter.key(), iter.value()) {
+            if key >= upper.as_slice() {
+                break;
+            }
+
+            let (row_key, column) = split_cell_key(key)?;
+            let cell = (Bytes::copy_from_slice(column), Bytes::copy_from_slice(value));
+            match rows.last_mut() {
+                Some((last, cells)) if last.as_ref() == row_key => cells.push(cell),
+                _ if rows.len() == limit => break,
+                _ => rows.push((Bytes::copy_from_slice(row_key), vec![cell])),
+            }
+
+            iter.next();
+        }
+
+        iter.status()?;
+        Ok(rows)
+    }
+
+    /// Read all the cells in the row with key `row_key`.
+    fn read_row(
+        &self,
+        cf: &Arc<rocksdb::BoundColumnFamily<'_>>,
+        row_key: &[u8],
+    ) -> Result<Vec<(Bytes, Bytes)>> {
+        let prefix = row_prefix(row_key)?;
+        let mut iter = self.db.raw_iterator_cf(cf);
+        iter.seek(&prefix);
+
+        let mut cells = vec![];
+        while let (Some(key), Some(value)) = (iter.key(), iter.value()) {
+            let Some(column) = key.strip_prefix(prefix.as_slice()) else {
+                break;
+            };
+
+            cells.push((
+                Bytes::copy_from_slice(column),
+                Bytes::copy_from_slice(value),
+            ));
+            iter.next();
+        }
+
+        iter.status()?;
+        Ok(cells)
+    }
+
+    fn cf(&self, table: &str) -> Result<Arc<rocksdb::BoundColumnFamily<'_>>> {
+        self.db
+            .cf_handle(table)
+            .with_context(|| format!("Unknown table {table}"))
+    }
+}
+
+#[async_trait]
+impl KeyValueStoreReader for RocksDbClient {
+    async fn get_objects(&mut self, object_keys: &[ObjectKey]) -> Result<Vec<Object>> {
+        let keys = object_keys
+            .iter()
+            .map(tables::objects::encode_key)
+            .collect();
+        let mut objects = vec![];
+        for (_, row) in self.multi_get(tables::objects::NAME, keys, None)? {
+            objects.push(tables::objects::decode(&row)?);
+        }
+        Ok(objects)
+    }
+
+    async fn get_transactions(
+        &mut self,
+        transactions: &[TransactionDigest],
+    ) -> Result<Vec<TransactionData>> {
+        let keys = transactions
+            .iter()
+            .map(tables::transactions::encode_key)
+            .collect();
+        let mut result = vec![];
+        for (_, row) in self.multi_get(tables::transactions::NAME, keys, None)? {
+            result.push(tables::transactions::decode(&row)?);
+        }
+        Ok(result)
+    }
+
+    async fn get_checkpoints(
+        &mut self,
+        sequence_numbers: &[CheckpointSequenceNumber],
+    ) -> Result<Vec<CheckpointData>> {
+        let keys = sequence_numbers
+            .iter()
+            .copied()
+            .map(tables::checkpoints::encode_key)
+            .collect();
+        let mut checkpoints = vec![];
+        for (_, row) in self.multi_get(tables::checkpoints::NAME, keys, None)? {
+            checkpoints.push(tables::checkpoints::decode(&row)?);
+        }
+        Ok(checkpoints)
+    }
+
+    async fn get_checkpoint_by_digest(
+        &mut self,
+        digest: CheckpointDigest,
+    ) -> Result<Option<CheckpointData>> {
+        let key = tables::checkpoints_by_digest::encode_key(&digest);
+        let Some((_, row)) = self
+            .multi_get(tables::checkpoints_by_digest::NAME, vec![key], None)?
+            .pop()
+        else {
+            return Ok(None);
+        };
+
+        let sequence_number = tables::checkpoints_by_digest::decode(&row)?;
+        Ok(self.get_checkpoints(&[sequence_number]).await?.pop())
+    }
+
+    async fn get_watermark_for_pipelines(
+        &mut self,
+        pipelines: &[&str],
+    ) -> Result<Option<Watermark>> {
+        let mut min_wm: Option<Watermark> = None;
+        for pipeline in pipelines {
+            let Some(wm) = self.get_pipeline_watermark(pipeline)? else {
+                return Ok(None);
+            };
+
+            min_wm = Some(match min_wm {
+                Some(prev) if prev.checkpoint_hi_inclusive <= wm.checkpoint_hi_inclusive => prev,
+                _ => wm,
+            });
+        }
+
+        Ok(min_wm)
+    }
+
+    async fn get_latest_object(&mut self, object_id: &ObjectID) -> Result<Option<Object>> {
+        let upper_limit = tables::objects::encode_key(&ObjectKey::max_for_id(object_id));
+        match self.reversed_scan(tables::objects::NAME, &upper_limit)? {
+            // The scan can land on an earlier object if this object doesn't exist.
+            Some((key, row)) if key.starts_with(object_id.as_ref()) => {
+                Ok(Some(tables::objects::decode(&row)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn get_epoch(&mut self, epoch_id: EpochId) -> Result<Option<EpochData>> {
+        let key = tables::epochs::encode_key(epoch_id);
+        match self.multi_get(tables::epochs::NAME, vec![key], None)?.pop() {
+            Some((_, row)) => Ok(Some(tables::epochs::decode(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_latest_epoch(&mut self) -> Result<Option<EpochData>> {
+        let upper_limit = tables::epochs::encode_key_upper_bound();
+        match self.reversed_scan(tables::epochs::NAME, &upper_limit)? {
+            Some((_, row)) => Ok(Some(tables::epochs::decode(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_events_for_transactions(
+        &mut self,
+        transaction_digests: &[TransactionDigest],
+    ) -> Result<Vec<(TransactionDigest, TransactionEventsData)>> {
+        let keys = transaction_digests
+            .iter()
+            .map(tables::transactions::encode_key)
+            .collect();
+        let columns = [
+            tables::transactions::col::EVENTS,
+            tables::transactions::col::TIMESTAMP,
+        ];
+
+        let mut results = vec![];
+        for (key, row) in self.multi_get(tables::transactions::NAME, keys, Some(&columns))? {
+            let events_data = tables::transactions::decode_events(&row)?;
+
+            let key_array: [u8; 32] = key
+                .as_ref()
+                .try_into()
+                .context("Failed to deserialize transaction digest")?;
+
+            results.push((TransactionDigest::from(key_array), events_data));
+        }
+
+        Ok(results)
+    }
+
+    async fn get_object_types(&mut self, object_ids: &[ObjectID]) -> Result<Vec<ObjectType>> {
+        let keys = object_ids
+            .iter()
+            .map(tables::object_types::encode_key)
+            .collect();
+        let mut result = Vec::with_capacity(object_ids.len());
+        for (_, row) in self.multi_get(tables::object_types::NAME, keys, None)? {
+            result.push(tables::object_types::decode(&row)?);
+        }
+        Ok(result)
+    }
+
+    async fn get_checkpoint_range(
+        &mut self,
+        range: Range<CheckpointSequenceNumber>,
+        limit: usize,
+    ) -> Result<Vec<CheckpointData>> {
+        if range.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let start = tables::checkpoints::encode_key(range.start);
+        let end = tables::checkpoints::encode_key(range.end);
+        let mut checkpoints = vec![];
+        for (_, row) in self.range_scan(tables::checkpoints::NAME, &start, &end, limit)? {
+            checkpoints.push(tables::checkpoints::decode(&row)?);
+        }
+        Ok(checkpoints)
+    }
+
+    async fn get_object_versions(
+        &mut self,
+        object_id: &ObjectID,
+        versions: Range<SequenceNumber>,
+        limit: usize,
+    ) -> Result<Vec<Object>> {
+        if versions.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let start = tables::objects::encode_key(&ObjectKey(*object_id, versions.start));
+        let end = tables::objects::encode_key(&ObjectKey(*object_id, versions.end));
+        let mut objects = vec![];
+        for (_, row) in self.range_scan(tables::objects::NAME, &start, &end, limit)? {
+            objects.push(tables::objects::decode(&row)?);
+        }
+        Ok(objects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    fn watermark(checkpoint: u64) -> Watermark {
+        Watermark {
+            epoch_hi_inclusive: 0,
+            checkpoint_hi_inclusive: checkpoint,
+            tx_hi: checkpoint,
+            timestamp_ms_hi_inclusive: checkpoint * 1000,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_columns_from_different_writes_are_merged() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut client = RocksDbClient::open(dir.path()).unwrap();
+
+        let start = tables::make_entry(
+            tables::epochs::encode_key(1),
+            [(
+                tables::epochs::col::EPOCH,
+                Bytes::from(1u64.to_be_bytes().to_vec()),
+            )],
+            None,
+        );
+        let end = tables::make_entry(
+            tables::epochs::encode_key(1),
+            tables::epochs::encode_end(2000, 20),
+            None,
+        );
+
+        client.write_entries(tables::epochs::NAME, [start]).unwrap();
+        client.write_entries(tables::epochs::NAME, [end]).unwrap();
+
+        let epoch = client.get_epoch(1).await.unwrap().unwrap();
+        assert_eq!(epoch.epoch, Some(1));
+        assert_eq!(epoch.end_timestamp_ms, Some(2000));
+        assert_eq!(epoch.end_checkpoint, Some(20));
+
+        assert!(client.get_epoch(2).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_latest_epoch() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut client = RocksDbClient::open(dir.path()).unwrap();
+        assert!(client.get_latest_epoch().await.unwrap().is_none());
+
+        for epoch in [0u64, 255, 256, 3] {
+            let entry = tables::make_entry(
+                tables::epochs::encode_key(epoch),
+                [(
+                    tables::epochs::col::EPOCH,
+                    Bytes::from(epoch.to_be_bytes().to_vec()),
+                )],
+                None,
+            );
+            client.write_entries(tables::epochs::NAME, [entry]).unwrap();
+        }
+
+        let latest = client.get_latest_epoch().await.unwrap().unwrap();
+        assert_eq!(latest.epoch, Some(256));
+    }
+
+    #[tokio::test]
+    async fn test_latest_object() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut client = RocksDbClient::open(dir.path()).unwrap();
+
+        let id = ObjectID::from_single_byte(1);
+        let other = ObjectID::from_single_byte(2);
+        let missing = ObjectID::from_single_byte(3);
+
+        for (id, version) in [(id, 2), (id, 5), (other, 7)] {
+            let mut object = Object::immutable_with_id_for_testing(id);
+            object
+                .data
+                .try_as_move_mut()
+                .unwrap()
+                .increment_version_to(SequenceNumber::from_u64(version));
+
+            let key = ObjectKey(id, SequenceNumber::from_u64(version));
+            let entry = tables::make_entry(
+                tables::objects::encode_key(&key),
+                tables::objects::encode(&object).unwrap(),
+                None,
+            );
+            client
+                .write_entries(tables::objects::NAME, [entry])
+                .unwrap();
+        }
+
+        let latest = client.get_latest_object(&id).await.unwrap().unwrap();
+        assert_eq!(latest.id(), id);
+        assert_eq!(latest.version(), SequenceNumber::from_u64(5));
+
+        // The scan for a missing object lands on the latest version of `other`, which must not be
+        // returned.
+        assert!(client.get_latest_object(&missing).await.unwrap().is_none());
+
+        let objects = client
+            .get_objects(&[
+                ObjectKey(id, SequenceNumber::from_u64(2)),
+                ObjectKey(id, SequenceNumber::from_u64(3)),
+                ObjectKey(other, SequenceNumber::from_u64(7)),
+            ])
+            .await
+            .unwrap();
+        let versions: Vec<_> = objects.iter().map(|o| (o.id(), o.version())).collect();
+        assert_eq!(
+            versions,
+            vec![
+                (id, SequenceNumber::from_u64(2)),
+                (other, SequenceNumber::from_u64(7)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_object_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut client = RocksDbClient::open(dir.path()).unwrap();
+
+        let id = ObjectID::from_single_byte(1);
+        let other = ObjectID::from_single_byte(2);
+        for (id, version) in [(id, 2), (id, 3), (id, 5), (id, 8), (other, 4)] {
+            let mut object = Object::immutable_with_id_for_testing(id);
+            object
+                .data
+                .try_as_move_mut()
+                .unwrap()
+                .increment_version_to(SequenceNumber::from_u64(version));
+
+            let key = ObjectKey(id, SequenceNumber::from_u64(version));
+            let entry = tables::make_entry(
+                tables::objects::encode_key(&key),
+                tables::objects::encode(&object).unwrap(),
+                None,
+            );
+            client.write_entries(tables::objects::NAME, [entry]).unwrap();
+        }
+
+        let versions = |objects: Vec<Object>| -> Vec<u64> {
+            objects.iter().map(|o| o.version().value()).collect()
+        };
+
+        let range = SequenceNumber::from_u64(3)..SequenceNumber::from_u64(8);
+        let objects = client
+            .get_object_versions(&id, range.clone(), 10)
+            .await
+            .unwrap();
+        assert_eq!(versions(objects), vec![3, 5]);
+
+        let objects = client.get_object_versions(&id, range, 1).await.unwrap();
+        assert_eq!(versions(objects), vec![3]);
+
+        // Streaming pages through all versions, without spilling over into the next object.
+        let objects: Vec<_> = client
+            .stream_object_versions(id, SequenceNumber::MIN..SequenceNumber::MAX)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(versions(objects), vec![2, 3, 5, 8]);
+    }
+
+    #[tokio::test]
+    async fn test_watermark_for_pipelines() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut client = RocksDbClient::open(dir.path()).unwrap();
+
+        client.set_pipeline_watermark("a", &watermark(10)).unwrap();
+        client.set_pipeline_watermark("b", &watermark(5)).unwrap();
+
+        let wm = client
+            .get_watermark_for_pipelines(&["a", "b"])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(wm.checkpoint_hi_inclusive, 5);
+
+        // A pipeline without a watermark means there is no overall watermark.
+        assert!(
+            client
+                .get_watermark_for_pipelines(&["a", "b", "c"])
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_secondary_catches_up() {
+        let primary_dir = tempfile::tempdir().unwrap();
+        let secondary_dir = tempfile::tempdir().unwrap();
+
+        let primary = RocksDbClient::open(primary_dir.path()).unwrap();
+        let secondary =
+            RocksDbClient::open_secondary(primary_dir.path(), secondary_dir.path()).unwrap();
+
+        primary.set_pipeline_watermark("a", &watermark(1)).unwrap();
+        primary.db.flush_wal(true).unwrap();
+
+        secondary.try_catch_up_with_primary().unwrap();
+        let wm = secondary.get_pipeline_watermark("a").unwrap().unwrap();
+        assert_eq!(wm.checkpoint_hi_inclusive, 1);
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Embedded RocksDB backend for the KV store, for operators and tests that cannot use BigTable.
+//!
+//! Each BigTable table is stored in a column family of the same name. Rows are stored one cell
+//! per RocksDB key, so that pipelines writing different columns of the same row (e.g. the epoch
+//! start and end pipelines) do not overwrite each other. Keys are laid out as:
+//!
+//! ```text
+//! [row key length: u16 BE][row key][column qualifier]
+//! ```
+//!
+//! Within a table, row keys have a fixed length (with the exception of watermarks, which are only
+//! read by exact key), so this layout preserves BigTable's row ordering for range scans.
+
+pub(crate) mod client;
+pub(crate) mod store;
+
+use anyhow::Result;
+use anyhow::bail;
+
+use crate::tables;
+
+/// Column families opened by the RocksDB backend, one per table.
+pub(crate) const TABLES: [&str; 7] = [
+    tables::checkpoints::NAME,
+    tables::checkpoints_by_digest::NAME,
+    tables::transactions::NAME,
+    tables::objects::NAME,
+    tables::object_types::NAME,
+    tables::epochs::NAME,
+    tables::watermarks::NAME,
+];
+
+/// The prefix shared by all cells in the row with key `row_key`.
+pub(crate) fn row_prefix(row_key: &[u8]) -> Result<Vec<u8>> {
+    let Ok(len) = u16::try_from(row_key.len()) else {
+        bail!("row key too long: {} bytes", row_key.len());
+    };
+
+    let mut prefix = Vec::with_capacity(2 + row_key.len());
+    prefix.extend_from_slice(&len.to_be_bytes());
+    prefix.extend_from_slice(row_key);
+    Ok(prefix)
+}
+
+/// The RocksDB key for the cell at `column` in the row with key `row_key`.
+pub(crate) fn cell_key(row_key: &[u8], column: &[u8]) -> Result<Vec<u8>> {
+    let mut key = row_prefix(row_key)?;
+    key.extend_from_slice(column);
+    Ok(key)
+}
+
+/// Split a RocksDB key into its row key and column qualifier.
+pub(crate) fn split_cell_key(key: &[u8]) -> Result<(&[u8], &[u8])> {
+    let Some((len, rest)) = key.split_first_chunk::<2>() else {
+        bail!("cell key too short: {} bytes", key.len());
+    };
+
+    let len = u16::from_be_bytes(*len) as usize;
+    if rest.len() < len {
+        bail!("cell key truncated: expected {len} byte row key");
+    }
+
+    Ok(rest.split_at(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_key_roundtrip() {
+        let key = cell_key(b"row", b"col").unwrap();
+        assert_eq!(key, b"\x00\x03rowcol");
+
+        let (row, col) = split_cell_key(&key).unwrap();
+        assert_eq!(row, b"row");
+        assert_eq!(col, b"col");
+
+        let key = cell_key(b"row", b"").unwrap();
+        let (row, col) = split_cell_key(&key).unwrap();
+        assert_eq!(row, b"row");
+        assert_eq!(col, b"");
+    }
+
+    #[test]
+    fn test_cell_key_ordering() {
+        // Cells are grouped by row, and rows of the same length keep their relative order.
+        let a = cell_key(&1u64.to_be_bytes(), b"zz").unwrap();
+        let b = cell_key(&2u64.to_be_bytes(), b"").unwrap();
+        let c = cell_key(&256u64.to_be_bytes(), b"a").unwrap();
+        assert!(a < b);
+        assert!(b < c);
+    }
+
+    #[test]
+    fn test_split_cell_key_truncated() {
+        assert!(split_cell_key(b"\x00").is_err());
+        assert!(split_cell_key(b"\x00\x05row").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! RocksDB Store implementation for myso-indexer-alt-framework.
+//!
+//! This implements the `Store` and `Connection` traits to allow the framework to use an embedded
+//! RocksDB database for watermark storage. Per-pipeline watermarks are stored in the same
+//! `watermark_alt` table as the BigTable backend, so both backends can be read in the same way.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use myso_indexer_alt_framework_store_traits::CommitterWatermark;
+use myso_indexer_alt_framework_store_traits::Connection;
+use myso_indexer_alt_framework_store_traits::PrunerWatermark;
+use myso_indexer_alt_framework_store_traits::ReaderWatermark;
+use myso_indexer_alt_framework_store_traits::Store;
+
+use crate::Watermark;
+use crate::rocks::client::RocksDbClient;
+
+/// A Store implementation backed by an embedded RocksDB database.
+#[derive(Clone)]
+pub struct RocksDbStore {
+    client: RocksDbClient,
+}
+
+/// A connection to RocksDB for watermark operations and data writes.
+pub struct RocksDbConnection<'a> {
+    client: RocksDbClient,
+    _marker: std::marker::PhantomData<&'a ()>,
+}
+
+impl RocksDbStore {
+    pub fn new(client: RocksDbClient) -> Self {
+        Self { client }
+    }
+}
+
+impl RocksDbConnection<'_> {
+    /// Returns a reference to the underlying RocksDB client.
+    pub fn client(&self) -> &RocksDbClient {
+        &self.client
+    }
+}
+
+#[async_trait]
+impl Store for RocksDbStore {
+    type Connection<'c> = RocksDbConnection<'c>;
+
+    async fn connect<'c>(&'c self) -> Result<Self::Connection<'c>> {
+        Ok(RocksDbConnection {
+            client: self.client.clone(),
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+#[async_trait]
+impl Connection for RocksDbConnection<'_> {
+    async fn init_watermark(
+        &mut self,
+        pipeline_task: &str,
+        _default_next_checkpoint: u64,
+    ) -> Result<Option<u64>> {
+        Ok(self
+            .client
+            .get_pipeline_watermark(pipeline_task)?
+            .map(|wm| wm.checkpoint_hi_inclusive))
+    }
+
+    async fn committer_watermark(
+        &mut self,
+        pipeline_task: &str,
+    ) -> Result<Option<CommitterWatermark>> {
+        Ok(self
+            .client
+            .get_pipeline_watermark(pipeline_task)?
+            .map(Into::into))
+    }
+
+    async fn set_committer_watermark(
+        &mut self,
+        pipeline_task: &str,
+        watermark: CommitterWatermark,
+    ) -> Result<bool> {
+        let watermark: Watermark = watermark.into();
+        self.client
+            .set_pipeline_watermark(pipeline_task, &watermark)?;
+        Ok(true)
+    }
+
+    // Reader/pruner watermarks are not needed for concurrent pipelines without pruning.
+
+    async fn reader_watermark(
+        &mut self,
+        _pipeline: &'static str,
+    ) -> Result<Option<ReaderWatermark>> {
+        Ok(None)
+    }
+
+    async fn pruner_watermark(
+        &mut self,
+        _pipeline: &'static str,
+        _delay: Duration,
+    ) -> Result<Option<PrunerWatermark>> {
+        Ok(None)
+    }
+
+    async fn set_reader_watermark(
+        &mut self,
+        _pipeline: &'static str,
+        _reader_lo: u64,
+    ) -> Result<bool> {
+        Ok(false)
+    }
+
+    async fn set_pruner_watermark(
+        &mut self,
+        _pipeline: &'static str,
+        _pruner_hi: u64,
+    ) -> Result<bool> {
+        Ok(false)
+    }
+}
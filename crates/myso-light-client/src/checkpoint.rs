@@ -2,12 +2,11 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::committee::extract_new_committee_info;
+use crate::committee_store::CommitteeStore;
 use crate::config::Config;
 use crate::graphql::query_last_checkpoint_of_epoch;
 use crate::object_store::MySoObjectStore;
 use anyhow::{Result, anyhow};
-use myso_config::genesis::Genesis;
 use myso_data_ingestion_core::end_of_epoch_data;
 use myso_rpc_api::Client;
 use myso_types::{
@@ -175,7 +174,14 @@ async fn sync_checkpoint_list_to_latest_using_graphql(
         }
     };
 
-    // If list is empty, we can't proceed with the normal algorithm
+    // If list is empty, start from the trusted checkpoint if there is one
+    if checkpoints_list.checkpoints.is_empty()
+        && let Some(trusted) = &config.trusted_checkpoint
+    {
+        checkpoints_list.checkpoints.push(trusted.sequence_number);
+    }
+
+    // If list is still empty, we can't proceed with the normal algorithm
     // as we need a starting checkpoint
     if checkpoints_list.checkpoints.is_empty() {
         return Err(anyhow!(
@@ -231,36 +237,44 @@ pub async fn check_and_sync_checkpoints(config: &Config) -> anyhow::Result<()> {
     // Write the fetched checkpoint list to disk
     write_checkpoint_list(config, &checkpoints_list)?;
 
-    // Load the genesis committee
-    let mut genesis_path = config.checkpoint_summary_dir.clone();
-    genesis_path.push(&config.genesis_filename);
-    let genesis_committee = Genesis::load(&genesis_path)?.committee();
+    let object_store = MySoObjectStore::new(config)?;
 
-    // Check the signatures of all checkpoints
-    // And download any missing ones
+    // Resume from the committees verified by a previous sync, or start from the trust anchor
+    let mut committee_store = match CommitteeStore::load_for_config(config)? {
+        Some(store) => store,
+        None => {
+            let anchor_summary = match &config.trusted_checkpoint {
+                Some(trusted) => Some(
+                    read_or_download_checkpoint(config, &object_store, trusted.sequence_number)
+                        .await?,
+                ),
+                None => None,
+            };
+            let store = CommitteeStore::bootstrap(config, anchor_summary.as_ref())?;
+            store.persist()?;
+            store
+        }
+    };
 
-    let mut prev_committee = genesis_committee;
-    let object_store = MySoObjectStore::new(config)?;
+    info!(
+        "Resuming committee sync from epoch {}",
+        committee_store.latest().epoch()
+    );
+
+    // Check the signatures of all checkpoints after the latest verified committee
+    // And download any missing ones
+    let latest_checkpoint = committee_store.latest().checkpoint();
     for ckp_id in &checkpoints_list.checkpoints {
-        // check if there is a file with this name ckp_id.yaml in the checkpoint_summary_dir
-        let mut checkpoint_path = config.checkpoint_summary_dir.clone();
-        checkpoint_path.push(format!("{}.yaml", ckp_id));
-
-        // If file exists read the file otherwise download it from the server
-        let summary = if checkpoint_path.exists() {
-            read_checkpoint(config, *ckp_id)
-                .map_err(|e| anyhow!(format!("Cannot read checkpoint: {e}")))?
-        } else {
-            // Download the checkpoint from the server
-            let summary = object_store
-                .download_checkpoint_summary(*ckp_id)
-                .await
-                .map_err(|e| anyhow!(format!("Cannot download summary: {e}")))?;
-            summary.clone().try_into_verified(&prev_committee)?;
-            // Write the checkpoint summary to a file
-            write_checkpoint(config, &summary)?;
-            summary
-        };
+        if latest_checkpoint.is_some_and(|latest| *ckp_id <= latest) {
+            continue;
+        }
+
+        let summary = read_or_download_checkpoint(config, &object_store, *ckp_id).await?;
+
+        // Verify the summary and extract the new committee information
+        committee_store.insert_end_of_epoch(&summary)?;
+        write_checkpoint(config, &summary)?;
+        committee_store.persist()?;
 
         // Print the id of the checkpoint and the epoch number
         info!(
@@ -268,14 +282,29 @@ pub async fn check_and_sync_checkpoints(config: &Config) -> anyhow::Result<()> {
             summary.epoch(),
             summary.digest()
         );
-
-        // Extract the new committee information
-        prev_committee = extract_new_committee_info(&summary)?;
     }
 
     Ok(())
 }
 
+/// Read a checkpoint summary from the checkpoint summary directory if it is there, otherwise
+/// download it from the object store.
+async fn read_or_download_checkpoint(
+    config: &Config,
+    object_store: &MySoObjectStore,
+    seq: u64,
+) -> anyhow::Result<Envelope<CheckpointSummary, AuthorityQuorumSignInfo<true>>> {
+    // check if there is a file with this name ckp_id.yaml in the checkpoint_summary_dir
+    if config.checkpoint_path(seq, None).exists() {
+        read_checkpoint(config, seq).map_err(|e| anyhow!(format!("Cannot read checkpoint: {e}")))
+    } else {
+        object_store
+            .download_checkpoint_summary(seq)
+            .await
+            .map_err(|e| anyhow!(format!("Cannot download summary: {e}")))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
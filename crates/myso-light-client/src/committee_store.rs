@@ -0,0 +1,572 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::checkpoint::{CheckpointsList, read_checkpoint, read_checkpoint_list};
+use crate::committee::extract_new_committee_info;
+use crate::config::{Config, TrustedCheckpoint};
+use anyhow::{Result, anyhow};
+use myso_config::genesis::Genesis;
+use myso_types::committee::{Committee, EpochId};
+use myso_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointDigest, CheckpointSequenceNumber,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// The point from which the committee chain is trusted.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum TrustAnchor {
+    /// The committee in the configured genesis blob.
+    Genesis,
+    /// The next-epoch committee of a trusted end-of-epoch checkpoint.
+    Checkpoint(TrustedCheckpoint),
+}
+
+impl TrustAnchor {
+    pub fn from_config(config: &Config) -> Self {
+        match &config.trusted_checkpoint {
+            Some(trusted) => TrustAnchor::Checkpoint(trusted.clone()),
+            None => TrustAnchor::Genesis,
+        }
+    }
+}
+
+/// A committee together with the end-of-epoch checkpoint that certified it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CommitteeRecord {
+    pub committee: Committee,
+    /// The end-of-epoch checkpoint of the previous epoch, `None` for the genesis committee.
+    pub certified_by: Option<(CheckpointSequenceNumber, CheckpointDigest)>,
+}
+
+impl CommitteeRecord {
+    pub fn epoch(&self) -> EpochId {
+        self.committee.epoch
+    }
+
+    pub fn checkpoint(&self) -> Option<CheckpointSequenceNumber> {
+        self.certified_by.map(|(seq, _)| seq)
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct StoredCommittees {
+    anchor: TrustAnchor,
+    committees: Vec<CommitteeRecord>,
+}
+
+/// Persistent, verified history of committees starting at a trust anchor.
+///
+/// Every committee after the anchor was extracted from an end-of-epoch checkpoint that carried a
+/// quorum of signatures from the committee before it, so restarting a client only needs to verify
+/// the epochs that changed since the last sync.
+pub struct CommitteeStore {
+    path: PathBuf,
+    anchor: TrustAnchor,
+    committees: BTreeMap<EpochId, CommitteeRecord>,
+}
+
+/// Summary of an offline check of the committee history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitteeHistoryReport {
+    pub anchor_epoch: EpochId,
+    pub latest_epoch: EpochId,
+    pub verified_checkpoints: usize,
+    /// Epochs whose committee was also in the local store and matched it.
+    pub matched_epochs: usize,
+}
+
+impl CommitteeStore {
+    /// Create a new store rooted at `anchor`, whose committee is `record`. Nothing is written to
+    /// disk until the first call to [`CommitteeStore::persist`].
+    pub fn new(path: PathBuf, anchor: TrustAnchor, record: CommitteeRecord) -> Self {
+        let committees = BTreeMap::from([(record.epoch(), record)]);
+        Self {
+            path,
+            anchor,
+            committees,
+        }
+    }
+
+    /// Load the store at `path`, or `None` if it has not been created yet.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let mut reader = fs::File::open(path)?;
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        let stored: StoredCommittees = bcs::from_bytes(&buffer)
+            .map_err(|e| anyhow!("Unable to parse committee store: {e}"))?;
+
+        let committees: BTreeMap<_, _> = stored
+            .committees
+            .into_iter()
+            .map(|record| (record.epoch(), record))
+            .collect();
+
+        if committees.is_empty() {
+            return Err(anyhow!("Committee store at {} is empty", path.display()));
+        }
+
+        Ok(Some(Self {
+            path: path.to_path_buf(),
+            anchor: stored.anchor,
+            committees,
+        }))
+    }
+
+    /// Load the store for `config`, checking that it was created from the anchor the config
+    /// currently asks for.
+    pub fn load_for_config(config: &Config) -> Result<Option<Self>> {
+        let path = config.committee_store_path();
+        let Some(store) = Self::load(&path)? else {
+            return Ok(None);
+        };
+
+        let anchor = TrustAnchor::from_config(config);
+        if store.anchor != anchor {
+            return Err(anyhow!(
+                "Committee store at {} was created from {:?} but the config trusts {:?}. \
+                 Remove it to re-anchor.",
+                path.display(),
+                store.anchor,
+                anchor,
+            ));
+        }
+
+        Ok(Some(store))
+    }
+
+    /// Create a store for `config` from its trust anchor. `anchor_summary` must be the trusted
+    /// checkpoint's summary when the config sets `trusted_checkpoint`, and is ignored otherwise.
+    pub fn bootstrap(
+        config: &Config,
+        anchor_summary: Option<&CertifiedCheckpointSummary>,
+    ) -> Result<Self> {
+        let anchor = TrustAnchor::from_config(config);
+        let record = anchor_record(config, &anchor, anchor_summary)?;
+
+        info!(
+            "Bootstrapping committee store from {:?} at epoch {}",
+            anchor,
+            record.epoch()
+        );
+
+        Ok(Self::new(config.committee_store_path(), anchor, record))
+    }
+
+    pub fn anchor(&self) -> &TrustAnchor {
+        &self.anchor
+    }
+
+    /// The committee of the earliest epoch this store can verify checkpoints for.
+    pub fn anchor_record(&self) -> &CommitteeRecord {
+        self.committees
+            .first_key_value()
+            .map(|(_, record)| record)
+            .expect("committee store is never empty")
+    }
+
+    /// The committee of the latest epoch that has been verified.
+    pub fn latest(&self) -> &CommitteeRecord {
+        self.committees
+            .last_key_value()
+            .map(|(_, record)| record)
+            .expect("committee store is never empty")
+    }
+
+    pub fn committee(&self, epoch: EpochId) -> Option<&Committee> {
+        self.committees.get(&epoch).map(|record| &record.committee)
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &CommitteeRecord> {
+        self.committees.values()
+    }
+
+    /// Verify `summary` against the latest committee and, if it is the end-of-epoch checkpoint
+    /// for that epoch, record the committee it certifies for the next epoch.
+    pub fn insert_end_of_epoch(&mut self, summary: &CertifiedCheckpointSummary) -> Result<()> {
+        let latest = self.latest();
+        if summary.epoch() != latest.epoch() {
+            return Err(anyhow!(
+                "Checkpoint {} is for epoch {}, expected epoch {}",
+                summary.sequence_number(),
+                summary.epoch(),
+                latest.epoch()
+            ));
+        }
+
+        summary
+            .verify_authority_signatures(&latest.committee)
+            .map_err(|e| {
+                anyhow!(
+                    "Checkpoint {} failed verification: {e}",
+                    summary.sequence_number()
+                )
+            })?;
+
+        let committee = extract_new_committee_info(summary)?;
+        let record = CommitteeRecord {
+            committee,
+            certified_by: Some((*summary.sequence_number(), *summary.digest())),
+        };
+        self.committees.insert(record.epoch(), record);
+        Ok(())
+    }
+
+    /// Atomically write the store to disk.
+    pub fn persist(&self) -> Result<()> {
+        let stored = StoredCommittees {
+            anchor: self.anchor.clone(),
+            committees: self.committees.values().cloned().collect(),
+        };
+        let bytes =
+            bcs::to_bytes(&stored).map_err(|_| anyhow!("Unable to serialize committee store"))?;
+
+        let tmp_path = self.path.with_extension("bcs.tmp");
+        let mut writer = fs::File::create(&tmp_path)?;
+        writer.write_all(&bytes)?;
+        writer.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Check the committee history recorded in `archive_dir` without network access.
+///
+/// The archive holds end-of-epoch checkpoint summaries in the same layout as the checkpoint
+/// summary directory (`<seq>.yaml` files, optionally listed in `checkpoints.yaml`). The chain is
+/// re-verified from the configured trust anchor and compared against the local committee store,
+/// if one exists.
+pub fn verify_committee_history(
+    config: &Config,
+    archive_dir: &Path,
+) -> Result<CommitteeHistoryReport> {
+    let archive_config = Config {
+        checkpoint_summary_dir: archive_dir.to_path_buf(),
+        ..config.clone()
+    };
+
+    let mut checkpoints = match read_checkpoint_list(&archive_config) {
+        Ok(CheckpointsList { checkpoints }) => checkpoints,
+        Err(_) => scan_checkpoint_dir(archive_dir)?,
+    };
+    checkpoints.sort();
+    checkpoints.dedup();
+
+    let anchor_summary = match &config.trusted_checkpoint {
+        Some(trusted) => Some(read_archived_checkpoint(
+            &archive_config,
+            trusted.sequence_number,
+        )?),
+        None => None,
+    };
+
+    let mut replayed = CommitteeStore::bootstrap(config, anchor_summary.as_ref())?;
+    let local = CommitteeStore::load_for_config(config)?;
+
+    let anchor_checkpoint = replayed.anchor_record().checkpoint();
+    let mut verified_checkpoints = 0;
+    for seq in checkpoints {
+        if anchor_checkpoint.is_some_and(|anchor| seq <= anchor) {
+            continue;
+        }
+
+        let summary = read_archived_checkpoint(&archive_config, seq)?;
+        replayed.insert_end_of_epoch(&summary)?;
+        verified_checkpoints += 1;
+    }
+
+    let mut matched_epochs = 0;
+    if let Some(local) = &local {
+        for record in replayed.records() {
+            let Some(committee) = local.committee(record.epoch()) else {
+                continue;
+            };
+
+            if *committee != record.committee {
+                return Err(anyhow!(
+                    "Committee for epoch {} in the archive does not match the committee store",
+                    record.epoch()
+                ));
+            }
+            matched_epochs += 1;
+        }
+    }
+
+    Ok(CommitteeHistoryReport {
+        anchor_epoch: replayed.anchor_record().epoch(),
+        latest_epoch: replayed.latest().epoch(),
+        verified_checkpoints,
+        matched_epochs,
+    })
+}
+
+/// Build the record for the committee trusted by `anchor`.
+fn anchor_record(
+    config: &Config,
+    anchor: &TrustAnchor,
+    anchor_summary: Option<&CertifiedCheckpointSummary>,
+) -> Result<CommitteeRecord> {
+    match anchor {
+        TrustAnchor::Genesis => Ok(CommitteeRecord {
+            committee: Genesis::load(config.genesis_path())?.committee(),
+            certified_by: None,
+        }),
+
+        TrustAnchor::Checkpoint(trusted) => {
+            let summary =
+                anchor_summary.ok_or_else(|| anyhow!("Missing summary for trusted checkpoint"))?;
+
+            if *summary.sequence_number() != trusted.sequence_number {
+                return Err(anyhow!(
+                    "Expected trusted checkpoint {}, got {}",
+                    trusted.sequence_number,
+                    summary.sequence_number()
+                ));
+            }
+
+            if *summary.digest() != trusted.digest {
+                return Err(anyhow!(
+                    "Trusted checkpoint {} has digest {}, expected {}",
+                    trusted.sequence_number,
+                    summary.digest(),
+                    trusted.digest
+                ));
+            }
+
+            Ok(CommitteeRecord {
+                committee: extract_new_committee_info(summary)?,
+                certified_by: Some((trusted.sequence_number, trusted.digest)),
+            })
+        }
+    }
+}
+
+fn read_archived_checkpoint(
+    config: &Config,
+    seq: CheckpointSequenceNumber,
+) -> Result<CertifiedCheckpointSummary> {
+    read_checkpoint(config, seq)
+        .map_err(|e| anyhow!("Cannot read checkpoint {seq} from archive: {e}"))
+}
+
+/// List the checkpoint summaries in `dir` by file name, for archives without a
+/// `checkpoints.yaml`.
+fn scan_checkpoint_dir(dir: &Path) -> Result<Vec<CheckpointSequenceNumber>> {
+    let mut checkpoints = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "yaml")
+            && let Some(seq) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse().ok())
+        {
+            checkpoints.push(seq);
+        }
+    }
+    Ok(checkpoints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::{write_checkpoint, write_checkpoint_list};
+    use myso_types::crypto::AuthorityKeyPair;
+    use myso_types::gas::GasCostSummary;
+    use myso_types::messages_checkpoint::{CheckpointContents, CheckpointSummary, EndOfEpochData};
+    use myso_types::supported_protocol_versions::{ProtocolConfig, ProtocolVersion};
+    use tempfile::TempDir;
+
+    fn end_of_epoch(
+        seq: CheckpointSequenceNumber,
+        committee: &Committee,
+        keys: &[AuthorityKeyPair],
+        next: &Committee,
+    ) -> CertifiedCheckpointSummary {
+        let contents = CheckpointContents::new_with_digests_only_for_tests(vec![]);
+        let summary = CheckpointSummary::new(
+            &ProtocolConfig::get_for_max_version_UNSAFE(),
+            committee.epoch,
+            seq,
+            0,
+            &contents,
+            None,
+            GasCostSummary::default(),
+            Some(EndOfEpochData {
+                next_epoch_committee: next.voting_rights.clone(),
+                next_epoch_protocol_version: ProtocolVersion::MAX,
+                epoch_commitments: vec![],
+            }),
+            0,
+            Vec::new(),
+            Vec::new(),
+        );
+        CertifiedCheckpointSummary::new_from_keypairs_for_testing(summary, keys, committee)
+    }
+
+    /// A chain of `len` committees, and the end-of-epoch checkpoints linking them.
+    fn committee_chain(len: usize) -> (Vec<Committee>, Vec<CertifiedCheckpointSummary>) {
+        let mut committees = vec![];
+        let mut keys = vec![];
+        for epoch in 0..len as EpochId {
+            let (committee, epoch_keys) = Committee::new_simple_test_committee();
+            committees.push(Committee::new(
+                epoch,
+                committee.voting_rights.into_iter().collect(),
+            ));
+            keys.push(epoch_keys);
+        }
+
+        let summaries = (1..len)
+            .map(|i| {
+                end_of_epoch(
+                    i as u64 * 100,
+                    &committees[i - 1],
+                    &keys[i - 1],
+                    &committees[i],
+                )
+            })
+            .collect();
+
+        (committees, summaries)
+    }
+
+    fn test_config(dir: &TempDir, trusted: Option<&CertifiedCheckpointSummary>) -> Config {
+        Config {
+            checkpoint_summary_dir: dir.path().to_path_buf(),
+            trusted_checkpoint: trusted.map(|summary| TrustedCheckpoint {
+                sequence_number: *summary.sequence_number(),
+                digest: *summary.digest(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_insert_persist_and_resume() {
+        let dir = TempDir::new().unwrap();
+        let (committees, summaries) = committee_chain(4);
+        let config = test_config(&dir, Some(&summaries[0]));
+
+        let mut store = CommitteeStore::bootstrap(&config, Some(&summaries[0])).unwrap();
+        assert_eq!(store.latest().epoch(), 1);
+
+        store.insert_end_of_epoch(&summaries[1]).unwrap();
+        store.persist().unwrap();
+
+        let mut store = CommitteeStore::load_for_config(&config).unwrap().unwrap();
+        assert_eq!(store.latest().epoch(), 2);
+        assert_eq!(store.latest().checkpoint(), Some(200));
+        assert_eq!(store.committee(2), Some(&committees[2]));
+
+        store.insert_end_of_epoch(&summaries[2]).unwrap();
+        assert_eq!(store.latest().epoch(), 3);
+        assert_eq!(store.committee(0), None);
+    }
+
+    #[test]
+    fn test_reject_wrong_signers() {
+        let dir = TempDir::new().unwrap();
+        let (_, summaries) = committee_chain(4);
+        let config = test_config(&dir, Some(&summaries[0]));
+
+        let mut store = CommitteeStore::bootstrap(&config, Some(&summaries[0])).unwrap();
+
+        // Skipping an epoch means the checkpoint is not signed by the latest committee.
+        assert!(store.insert_end_of_epoch(&summaries[2]).is_err());
+        assert_eq!(store.latest().epoch(), 1);
+    }
+
+    #[test]
+    fn test_reject_foreign_committee_signature() {
+        let dir = TempDir::new().unwrap();
+        let (committees, summaries) = committee_chain(3);
+        let config = test_config(&dir, Some(&summaries[0]));
+
+        let mut store = CommitteeStore::bootstrap(&config, Some(&summaries[0])).unwrap();
+
+        // The checkpoint is for the right epoch, but signed by a committee the store never
+        // trusted.
+        let (foreign, foreign_keys) = Committee::new_simple_test_committee();
+        let foreign = Committee::new(1, foreign.voting_rights.into_iter().collect());
+        let forged = end_of_epoch(200, &foreign, &foreign_keys, &committees[2]);
+        assert_eq!(forged.epoch(), store.latest().epoch());
+
+        let err = store.insert_end_of_epoch(&forged).unwrap_err();
+        assert!(err.to_string().contains("failed verification"), "{err}");
+        assert_eq!(store.latest().epoch(), 1);
+        assert_eq!(store.committee(2), None);
+    }
+
+    #[test]
+    fn test_reject_wrong_anchor_digest() {
+        let dir = TempDir::new().unwrap();
+        let (_, summaries) = committee_chain(3);
+        let mut config = test_config(&dir, Some(&summaries[0]));
+        config.trusted_checkpoint.as_mut().unwrap().digest = *summaries[1].digest();
+
+        assert!(CommitteeStore::bootstrap(&config, Some(&summaries[0])).is_err());
+    }
+
+    #[test]
+    fn test_reject_changed_anchor() {
+        let dir = TempDir::new().unwrap();
+        let (_, summaries) = committee_chain(3);
+
+        let config = test_config(&dir, Some(&summaries[0]));
+        let store = CommitteeStore::bootstrap(&config, Some(&summaries[0])).unwrap();
+        store.persist().unwrap();
+
+        let config = test_config(&dir, Some(&summaries[1]));
+        assert!(CommitteeStore::load_for_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_verify_committee_history() {
+        let dir = TempDir::new().unwrap();
+        let archive = TempDir::new().unwrap();
+        let (_, summaries) = committee_chain(5);
+        let config = test_config(&dir, Some(&summaries[0]));
+
+        let archive_config = test_config(&archive, Some(&summaries[0]));
+        for summary in &summaries {
+            write_checkpoint(&archive_config, summary).unwrap();
+        }
+
+        // The local store has only synced up to epoch 2.
+        let mut store = CommitteeStore::bootstrap(&config, Some(&summaries[0])).unwrap();
+        store.insert_end_of_epoch(&summaries[1]).unwrap();
+        store.persist().unwrap();
+
+        // Without a checkpoint list, the archive directory is scanned.
+        let report = verify_committee_history(&config, archive.path()).unwrap();
+        assert_eq!(
+            report,
+            CommitteeHistoryReport {
+                anchor_epoch: 1,
+                latest_epoch: 4,
+                verified_checkpoints: 3,
+                matched_epochs: 2,
+            }
+        );
+
+        // A list that skips an epoch breaks the chain.
+        write_checkpoint_list(
+            &archive_config,
+            &CheckpointsList {
+                checkpoints: vec![100, 300, 400],
+            },
+        )
+        .unwrap();
+        assert!(verify_committee_history(&config, archive.path()).is_err());
+    }
+}
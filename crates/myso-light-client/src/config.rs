@@ -4,6 +4,7 @@
 
 use anyhow::{Result, anyhow};
 use myso_config::object_storage_config::ObjectStoreConfig;
+use myso_types::messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -17,6 +18,17 @@ pub struct Config {
     pub archive_store_config: Option<ObjectStoreConfig>,
     pub graphql_url: Option<String>,
     pub genesis_filename: String,
+    /// A recent end-of-epoch checkpoint to trust instead of syncing from genesis.
+    #[serde(default)]
+    pub trusted_checkpoint: Option<TrustedCheckpoint>,
+}
+
+/// A weak-subjectivity checkpoint: an end-of-epoch checkpoint whose digest has been obtained out
+/// of band, and whose next-epoch committee is trusted without verifying the chain before it.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TrustedCheckpoint {
+    pub sequence_number: CheckpointSequenceNumber,
+    pub digest: CheckpointDigest,
 }
 
 impl Config {
@@ -59,6 +71,10 @@ impl Config {
     pub fn genesis_path(&self) -> PathBuf {
         self.checkpoint_summary_dir.join(&self.genesis_filename)
     }
+
+    pub fn committee_store_path(&self) -> PathBuf {
+        self.checkpoint_summary_dir.join("committees.bcs")
+    }
 }
 
 #[cfg(test)]
@@ -80,6 +96,7 @@ mod tests {
             }),
             graphql_url: Some("http://localhost:9003".to_string()),
             genesis_filename: "genesis.blob".to_string(),
+            trusted_checkpoint: None,
         };
         (config, temp_dir)
     }
@@ -111,4 +128,24 @@ mod tests {
         let genesis_path = config.genesis_path();
         assert_eq!(genesis_path.file_name().unwrap(), "genesis.blob");
     }
+
+    #[test]
+    fn test_trusted_checkpoint_config() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+checkpoint_summary_dir: "checkpoints_dir"
+full_node_url: "http://localhost:9000"
+object_store_url: "http://localhost:9001"
+genesis_filename: "genesis.blob"
+trusted_checkpoint:
+  sequence_number: 1234
+  digest: "11111111111111111111111111111111"
+"#,
+        )
+        .unwrap();
+
+        let trusted = config.trusted_checkpoint.unwrap();
+        assert_eq!(trusted.sequence_number, 1234);
+        assert_eq!(trusted.digest, CheckpointDigest::new([0; 32]));
+    }
 }
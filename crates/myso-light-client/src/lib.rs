@@ -6,6 +6,8 @@ pub mod proof;
 
 pub mod checkpoint;
 
+pub mod committee_store;
+
 pub mod config;
 
 pub mod object_store;
@@ -12,6 +12,7 @@ use myso_package_resolver::Resolver;
 
 use clap::{Parser, Subcommand};
 use myso_light_client::checkpoint::check_and_sync_checkpoints;
+use myso_light_client::committee_store::verify_committee_history;
 use myso_light_client::config::Config;
 use myso_light_client::package_store::RemotePackageStore;
 use myso_light_client::verifier::{get_verified_effects_and_events, get_verified_object};
@@ -36,6 +37,14 @@ enum SCommands {
     /// Sync all end-of-epoch checkpoints
     Sync {},
 
+    /// Check the committee history against a local directory of end-of-epoch checkpoints,
+    /// without network access
+    VerifyCommittees {
+        /// Directory containing `<seq>.yaml` checkpoint summaries
+        #[arg(short, long, value_name = "DIR")]
+        archive_dir: PathBuf,
+    },
+
     /// Checks a specific transaction using the light client
     Transaction {
         /// Transaction hash
@@ -147,6 +156,17 @@ pub async fn main() {
                 .await
                 .expect("Failed to sync checkpoints");
         }
+        Some(SCommands::VerifyCommittees { archive_dir }) => {
+            let report = verify_committee_history(&config, &archive_dir)
+                .expect("Failed to verify committee history");
+            println!(
+                "Verified {} end-of-epoch checkpoints from epoch {} to epoch {} ({} epochs match the committee store)",
+                report.verified_checkpoints,
+                report.anchor_epoch,
+                report.latest_epoch,
+                report.matched_epochs
+            );
+        }
         _ => {
             println!("No command...");
         }
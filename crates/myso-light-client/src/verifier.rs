@@ -4,6 +4,7 @@
 
 use crate::checkpoint::{CheckpointsList, read_checkpoint, read_checkpoint_list};
 use crate::committee::extract_new_committee_info;
+use crate::committee_store::CommitteeStore;
 use crate::config::Config;
 use crate::object_store::MySoObjectStore;
 use anyhow::{Result, anyhow};
@@ -101,6 +102,24 @@ pub async fn get_verified_effects_and_events(
         .await
         .map_err(|e| anyhow!(format!("Cannot get full checkpoint: {e}")))?;
 
+    let committee = committee_for_checkpoint(config, &full_check_point)?;
+
+    info!("Extracting effects and events for TID: {}", tid);
+    extract_verified_effects_and_events(&full_check_point, &committee, tid)
+        .map_err(|e| anyhow!(format!("Cannot extract effects and events: {e}")))
+}
+
+/// Get the committee that signed `checkpoint`, from the committee store if one has been synced.
+fn committee_for_checkpoint(config: &Config, checkpoint: &CheckpointData) -> Result<Committee> {
+    let epoch = checkpoint.checkpoint_summary.epoch();
+    if let Some(store) = CommitteeStore::load_for_config(config)? {
+        return store.committee(epoch).cloned().ok_or(anyhow!(
+            "No committee for epoch {epoch} in the committee store. Need to Sync."
+        ));
+    }
+
+    let seq = *checkpoint.checkpoint_summary.sequence_number();
+
     // Load the list of stored checkpoints
     let checkpoints_list: CheckpointsList = read_checkpoint_list(config)?;
 
@@ -116,7 +135,7 @@ pub async fn get_verified_effects_and_events(
 
         // Check we have the right checkpoint
         anyhow::ensure!(
-            prev_ckp.epoch().checked_add(1).unwrap() == full_check_point.checkpoint_summary.epoch(),
+            prev_ckp.epoch().checked_add(1).unwrap() == epoch,
             "Checkpoint sequence number does not match. Need to Sync."
         );
 
@@ -129,9 +148,7 @@ pub async fn get_verified_effects_and_events(
         Genesis::load(&genesis_path)?.committee()
     };
 
-    info!("Extracting effects and events for TID: {}", tid);
-    extract_verified_effects_and_events(&full_check_point, &committee, tid)
-        .map_err(|e| anyhow!(format!("Cannot extract effects and events: {e}")))
+    Ok(committee)
 }
 
 /// Get the verified checkpoint sequence number for an object.
@@ -177,33 +194,7 @@ pub async fn get_verified_checkpoint(
         .await
         .map_err(|e| anyhow!(format!("Cannot get full checkpoint: {e}")))?;
 
-    // Load the list of stored checkpoints
-    let checkpoints_list: CheckpointsList = read_checkpoint_list(config)?;
-
-    // find the stored checkpoint before the seq checkpoint
-    let prev_ckp_id = checkpoints_list
-        .checkpoints
-        .iter()
-        .rfind(|ckp_id| **ckp_id < seq);
-
-    let committee = if let Some(prev_ckp_id) = prev_ckp_id {
-        // Read it from the store
-        let prev_ckp = read_checkpoint(config, *prev_ckp_id)?;
-
-        // Check we have the right checkpoint
-        anyhow::ensure!(
-            prev_ckp.epoch().checked_add(1).unwrap() == full_check_point.checkpoint_summary.epoch(),
-            "Checkpoint sequence number does not match. Need to Sync."
-        );
-
-        // Get the committee from the previous checkpoint
-        extract_new_committee_info(&prev_ckp)?
-    } else {
-        // Since we did not find a small committee checkpoint we use the genesis
-        let mut genesis_path = config.checkpoint_summary_dir.clone();
-        genesis_path.push(&config.genesis_filename);
-        Genesis::load(&genesis_path)?.committee()
-    };
+    let committee = committee_for_checkpoint(config, &full_check_point)?;
 
     // Verify that committee signed this checkpoint and checkpoint contents with digest
     full_check_point
@@ -13,13 +13,14 @@ use base64::Engine;
 use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::traits::ToFromBytes;
 use humantime::parse_duration;
+use myso_core::traffic_controller::{DryRunTarget, parse_ip};
 use myso_network::endpoint_manager::{AddressSource, EndpointId};
 use myso_types::{
     base_types::AuthorityName,
     crypto::{NetworkPublicKey, RandomnessPartialSignature, RandomnessRound, RandomnessSignature},
     digests::TransactionDigest,
     error::MySoErrorKind,
-    traffic_control::TrafficControlReconfigParams,
+    traffic_control::{PolicyType, TrafficControlReconfigParams},
 };
 use mysten_network::Multiaddr;
 use serde::Deserialize;
@@ -83,10 +84,40 @@ use tracing::info;
 //
 //  $ curl 'http://127.0.0.1:1337/traffic-control?error_threshold=100&spam_threshold=100&dry_run=true'
 //
+// List clients currently blocked by traffic control, with their expiry and the policy that
+// blocked them
+//
+//  $ curl 'http://127.0.0.1:1337/traffic-control/blocklist'
+//
+// Manually block a client for 10 minutes, or unblock it. Pass `proxied=true` to act on the
+// blocklist of clients proxied through a fullnode.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/traffic-control/block?ip=1.2.3.4&ttl=10m'
+//  $ curl -X POST 'http://127.0.0.1:1337/traffic-control/unblock?ip=1.2.3.4'
+//
+// Dry-run a policy, given as JSON, against recent traffic. `target=error` replays only the
+// tallies of failed requests. The most recent requests are always recorded, so the live
+// policies keep blocking while a candidate policy is tried.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/traffic-control/dry-run?target=spam' \
+//      -d '{"token-bucket": {"client-capacity": 1000, "client-refill-rate": 100}}'
+//
 // Update endpoint address(es) for a peer
 //
 //  $ curl -X POST 'http://127.0.0.1:1337/update-endpoint?endpoint_type=p2p&id=<hex_encoded_peer_id>&addresses=<multiaddr1>,<multiaddr2>'
 //  $ curl -X POST 'http://127.0.0.1:1337/update-endpoint?endpoint_type=consensus&id=<hex_encoded_network_pubkey>&addresses=<multiaddr1>,<multiaddr2>'
+//
+// Report per-table storage statistics of the perpetual store, with recommended pruning settings.
+// `myso-tool storage-stats` prints the same report as tables.
+//
+//  $ curl 'http://127.0.0.1:1337/storage-stats'
+//
+// Render the last rounds of this validator's consensus DAG, or a given range of rounds, with
+// leader decisions and committed sub-dags. `format=dag` (the default) prints the text format
+// of consensus tests, `format=dot` a Graphviz graph that can be rendered to SVG.
+//
+//  $ curl 'http://127.0.0.1:1337/consensus-dag'
+//  $ curl 'http://127.0.0.1:1337/consensus-dag?start_round=100&end_round=120&format=dot' | dot -Tsvg > dag.svg
 
 const NO_TRACING_HANDLE: &str = "tracing handle not available";
 const LOGGING_ROUTE: &str = "/logging";
@@ -103,7 +134,13 @@ const RANDOMNESS_INJECT_FULL_SIG_ROUTE: &str = "/randomness-inject-full-sig";
 const GET_TX_COST_ROUTE: &str = "/get-tx-cost";
 const DUMP_CONSENSUS_TX_COST_ESTIMATES_ROUTE: &str = "/dump-consensus-tx-cost-estimates";
 const TRAFFIC_CONTROL: &str = "/traffic-control";
+const TRAFFIC_CONTROL_BLOCKLIST: &str = "/traffic-control/blocklist";
+const TRAFFIC_CONTROL_BLOCK: &str = "/traffic-control/block";
+const TRAFFIC_CONTROL_UNBLOCK: &str = "/traffic-control/unblock";
+const TRAFFIC_CONTROL_DRY_RUN: &str = "/traffic-control/dry-run";
 const UPDATE_ENDPOINT: &str = "/update-endpoint";
+const STORAGE_STATS: &str = "/storage-stats";
+const CONSENSUS_DAG: &str = "/consensus-dag";
 
 struct AppState {
     node: Arc<MySoNode>,
@@ -156,7 +193,13 @@ pub async fn run_admin_server(
             get(dump_consensus_tx_cost_estimates),
         )
         .route(TRAFFIC_CONTROL, post(traffic_control))
+        .route(TRAFFIC_CONTROL_BLOCKLIST, get(traffic_control_blocklist))
+        .route(TRAFFIC_CONTROL_BLOCK, post(traffic_control_block))
+        .route(TRAFFIC_CONTROL_UNBLOCK, post(traffic_control_unblock))
+        .route(TRAFFIC_CONTROL_DRY_RUN, post(traffic_control_dry_run))
         .route(UPDATE_ENDPOINT, post(update_endpoint))
+        .route(STORAGE_STATS, get(storage_stats))
+        .route(CONSENSUS_DAG, get(consensus_dag))
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -551,6 +594,122 @@ async fn traffic_control(
     }
 }
 
+async fn traffic_control_blocklist(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let blocked = state
+        .node
+        .state()
+        .admin_traffic_controller()
+        .and_then(|traffic_controller| traffic_controller.blocked_clients());
+    match blocked {
+        Ok(blocked) => (
+            StatusCode::OK,
+            serde_json::to_string_pretty(&blocked).unwrap(),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct TrafficControlBlock {
+    ip: String,
+    ttl: String,
+    #[serde(default)]
+    proxied: bool,
+}
+
+async fn traffic_control_block(
+    State(state): State<Arc<AppState>>,
+    args: Query<TrafficControlBlock>,
+) -> (StatusCode, String) {
+    let Query(TrafficControlBlock { ip, ttl, proxied }) = args;
+    let Some(client) = parse_ip(&ip) else {
+        return (StatusCode::BAD_REQUEST, format!("Invalid ip address {ip}"));
+    };
+    let ttl = match parse_duration(&ttl) {
+        Ok(ttl) => ttl,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    let result = state
+        .node
+        .state()
+        .admin_traffic_controller()
+        .and_then(|traffic_controller| traffic_controller.admin_block(client, proxied, ttl));
+    match result {
+        Ok(()) => (
+            StatusCode::OK,
+            format!("Blocked {client} (proxied: {proxied}) for {ttl:?}\n"),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct TrafficControlUnblock {
+    ip: String,
+    #[serde(default)]
+    proxied: bool,
+}
+
+async fn traffic_control_unblock(
+    State(state): State<Arc<AppState>>,
+    args: Query<TrafficControlUnblock>,
+) -> (StatusCode, String) {
+    let Query(TrafficControlUnblock { ip, proxied }) = args;
+    let Some(client) = parse_ip(&ip) else {
+        return (StatusCode::BAD_REQUEST, format!("Invalid ip address {ip}"));
+    };
+    let result = state
+        .node
+        .state()
+        .admin_traffic_controller()
+        .and_then(|traffic_controller| traffic_controller.admin_unblock(client, proxied));
+    match result {
+        Ok(true) => (
+            StatusCode::OK,
+            format!("Unblocked {client} (proxied: {proxied})\n"),
+        ),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            format!("{client} (proxied: {proxied}) is not blocked\n"),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct TrafficControlDryRun {
+    #[serde(default)]
+    target: DryRunTarget,
+}
+
+async fn traffic_control_dry_run(
+    State(state): State<Arc<AppState>>,
+    args: Query<TrafficControlDryRun>,
+    body: String,
+) -> (StatusCode, String) {
+    let Query(TrafficControlDryRun { target }) = args;
+    let policy_type: PolicyType = match serde_json::from_str(&body) {
+        Ok(policy_type) => policy_type,
+        Err(err) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid policy type: {err}"),
+            );
+        }
+    };
+    let traffic_controller = match state.node.state().admin_traffic_controller() {
+        Ok(traffic_controller) => traffic_controller.clone(),
+        Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    };
+    match traffic_controller.dry_run_policy(policy_type, target).await {
+        Ok(report) => (
+            StatusCode::OK,
+            serde_json::to_string_pretty(&report).unwrap(),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
 #[derive(Deserialize)]
 struct UpdateEndpointArgs {
     endpoint_type: String,
@@ -656,3 +815,48 @@ async fn update_endpoint(
         ),
     )
 }
+
+async fn storage_stats(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    match state.node.state().storage_stats() {
+        Ok(stats) => (
+            StatusCode::OK,
+            serde_json::to_string_pretty(&stats).unwrap(),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct ConsensusDag {
+    start_round: Option<u32>,
+    end_round: Option<u32>,
+    format: Option<String>,
+}
+
+async fn consensus_dag(
+    State(state): State<Arc<AppState>>,
+    args: Query<ConsensusDag>,
+) -> (StatusCode, String) {
+    let Query(ConsensusDag {
+        start_round,
+        end_round,
+        format,
+    }) = args;
+
+    let visualizer = match state
+        .node
+        .visualize_consensus_dag(start_round, end_round)
+        .await
+    {
+        Ok(visualizer) => visualizer,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    match format.as_deref().unwrap_or("dag") {
+        "dag" => (StatusCode::OK, visualizer.to_dag_string()),
+        "dot" => (StatusCode::OK, visualizer.to_dot()),
+        format => (
+            StatusCode::BAD_REQUEST,
+            format!("Unknown format {format}, expected dag or dot\n"),
+        ),
+    }
+}
@@ -12,6 +12,7 @@ use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
 use arc_swap::ArcSwap;
+use consensus_core::DagVisualizer;
 use fastcrypto_zkp::bn254::zk_login::JwkId;
 use fastcrypto_zkp::bn254::zk_login::OIDCProvider;
 use futures::future::BoxFuture;
@@ -972,6 +973,21 @@ impl MySoNode {
         Ok(())
     }
 
+    /// Renders rounds of the consensus DAG of this validator, with its leader decisions.
+    pub async fn visualize_consensus_dag(
+        &self,
+        start_round: Option<u32>,
+        end_round: Option<u32>,
+    ) -> MySoResult<DagVisualizer> {
+        self.validator_components
+            .lock()
+            .await
+            .as_ref()
+            .ok_or_else(|| MySoError::from("Node is not a validator"))?
+            .consensus_manager
+            .visualize_dag(start_round, end_round)
+    }
+
     pub fn clear_override_protocol_upgrade_buffer_stake(&self, epoch: EpochId) -> MySoResult {
         self.state
             .clear_override_protocol_upgrade_buffer_stake(epoch)
@@ -0,0 +1,523 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured comparison of replayed effects against the effects recorded on chain.
+//!
+//! Effects are first compared byte for byte (BCS). When they differ, the divergence is broken
+//! down into the parts that are most useful when reviewing a protocol config or framework
+//! change: execution status, gas, object changes and events.
+//! A `DivergenceReport` collects the results for a whole replay run and is saved as JSON.
+
+use anyhow::{Context, Result};
+use fastcrypto::encoding::{Encoding, Hex};
+use myso_types::{
+    base_types::ObjectID,
+    effects::{ObjectChange, TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    event::Event,
+};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+/// A value that differs between the recorded and the replayed effects.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FieldDiff<T> {
+    pub expected: T,
+    pub actual: T,
+}
+
+/// Differences in the gas cost summary.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct GasDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub computation_cost: Option<FieldDiff<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_cost: Option<FieldDiff<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_rebate: Option<FieldDiff<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_refundable_storage_fee: Option<FieldDiff<u64>>,
+}
+
+/// An object whose change differs. `None` means the object was not changed on that side.
+#[derive(Debug, Clone, Serialize)]
+pub struct ObjectChangeDiff {
+    pub object_id: ObjectID,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+/// Differences in the events emitted by the transaction.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventsDiff {
+    pub digest: FieldDiff<Option<String>>,
+    /// The events that differ, by position.
+    /// `None` if the recorded events could not be loaded, in which case only the digests
+    /// can be compared.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<EventDiff>>,
+}
+
+/// An event that differs at `index`. A `None` value means that side emitted fewer events.
+/// Contents are the BCS bytes of the event's Move value, hex encoded.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventDiff {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<FieldDiff<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<FieldDiff<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contents: Option<FieldDiff<Option<String>>>,
+}
+
+/// How a single transaction's replayed effects differ from the recorded ones.
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionDivergence {
+    pub digest: String,
+    pub checkpoint: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<FieldDiff<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<GasDiff>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub object_changes: Vec<ObjectChangeDiff>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<EventsDiff>,
+}
+
+/// A transaction that could not be replayed at all.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayFailure {
+    pub digest: String,
+    pub error: String,
+}
+
+/// The outcome of replaying a range of checkpoints.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct DivergenceReport {
+    pub first_checkpoint: u64,
+    pub last_checkpoint: u64,
+    pub transactions: u64,
+    pub matched: u64,
+    pub diverged: Vec<TransactionDivergence>,
+    pub failed: Vec<ReplayFailure>,
+}
+
+impl TransactionDivergence {
+    /// Compare `actual` against `expected`, returning `None` if they are identical byte for byte.
+    /// `expected_events` are the events recorded on chain, if they could be loaded.
+    pub fn compute(
+        digest: &str,
+        checkpoint: u64,
+        expected: &TransactionEffects,
+        actual: &TransactionEffects,
+        expected_events: Option<&TransactionEvents>,
+        actual_events: &TransactionEvents,
+    ) -> Result<Option<Self>> {
+        let expected_bytes =
+            bcs::to_bytes(expected).context("Failed to serialize expected effects")?;
+        let actual_bytes = bcs::to_bytes(actual).context("Failed to serialize replayed effects")?;
+        if expected_bytes == actual_bytes {
+            return Ok(None);
+        }
+
+        let status = diff(
+            format!("{:?}", expected.status()),
+            format!("{:?}", actual.status()),
+        );
+
+        let (expected_gas, actual_gas) = (expected.gas_cost_summary(), actual.gas_cost_summary());
+        let gas = GasDiff {
+            computation_cost: diff(expected_gas.computation_cost, actual_gas.computation_cost),
+            storage_cost: diff(expected_gas.storage_cost, actual_gas.storage_cost),
+            storage_rebate: diff(expected_gas.storage_rebate, actual_gas.storage_rebate),
+            non_refundable_storage_fee: diff(
+                expected_gas.non_refundable_storage_fee,
+                actual_gas.non_refundable_storage_fee,
+            ),
+        };
+        let gas = (gas.computation_cost.is_some()
+            || gas.storage_cost.is_some()
+            || gas.storage_rebate.is_some()
+            || gas.non_refundable_storage_fee.is_some())
+        .then_some(gas);
+
+        let events = diff(
+            expected.events_digest().map(|d| d.to_string()),
+            actual.events_digest().map(|d| d.to_string()),
+        )
+        .map(|digest| EventsDiff {
+            digest,
+            events: expected_events.map(|expected| diff_events(expected, actual_events)),
+        });
+
+        Ok(Some(Self {
+            digest: digest.to_string(),
+            checkpoint,
+            status,
+            gas,
+            object_changes: diff_object_changes(expected, actual),
+            events,
+        }))
+    }
+}
+
+impl DivergenceReport {
+    pub fn new(first_checkpoint: u64, last_checkpoint: u64) -> Self {
+        Self {
+            first_checkpoint,
+            last_checkpoint,
+            ..Default::default()
+        }
+    }
+
+    /// Record the outcome of replaying one transaction.
+    pub fn add(&mut self, result: Result<Option<TransactionDivergence>>, digest: &str) {
+        self.transactions += 1;
+        match result {
+            Ok(None) => self.matched += 1,
+            Ok(Some(divergence)) => self.diverged.push(divergence),
+            Err(e) => self.failed.push(ReplayFailure {
+                digest: digest.to_string(),
+                error: format!("{e:#}"),
+            }),
+        }
+    }
+
+    /// Whether every transaction replayed and produced the recorded effects.
+    pub fn is_clean(&self) -> bool {
+        self.diverged.is_empty() && self.failed.is_empty()
+    }
+
+    /// Save the report as JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write divergence report {}", path.display()))
+    }
+}
+
+fn diff<T: PartialEq>(expected: T, actual: T) -> Option<FieldDiff<T>> {
+    (expected != actual).then_some(FieldDiff { expected, actual })
+}
+
+fn diff_events(expected: &TransactionEvents, actual: &TransactionEvents) -> Vec<EventDiff> {
+    let len = expected.data.len().max(actual.data.len());
+    (0..len)
+        .filter_map(|index| {
+            let (expected, actual) = (expected.data.get(index), actual.data.get(index));
+            let field = |f: fn(&Event) -> String| diff(expected.map(f), actual.map(f));
+            let event = EventDiff {
+                index,
+                type_: field(|e| e.type_.to_canonical_string(true)),
+                sender: field(|e| e.sender.to_string()),
+                contents: field(|e| Hex::encode(&e.contents)),
+            };
+            (event.type_.is_some() || event.sender.is_some() || event.contents.is_some())
+                .then_some(event)
+        })
+        .collect()
+}
+
+fn diff_object_changes(
+    expected: &TransactionEffects,
+    actual: &TransactionEffects,
+) -> Vec<ObjectChangeDiff> {
+    let expected = describe_object_changes(expected.object_changes());
+    let actual = describe_object_changes(actual.object_changes());
+
+    let ids: BTreeSet<_> = expected.keys().chain(actual.keys()).collect();
+    ids.into_iter()
+        .filter_map(|id| {
+            let (expected, actual) = (expected.get(id), actual.get(id));
+            (expected != actual).then(|| ObjectChangeDiff {
+                object_id: *id,
+                expected: expected.cloned(),
+                actual: actual.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn describe_object_changes(changes: Vec<ObjectChange>) -> BTreeMap<ObjectID, String> {
+    changes
+        .into_iter()
+        .map(|change| {
+            let version = |v: Option<_>| v.map_or("-".to_string(), |v| format!("{v}"));
+            let digest = |d: Option<_>| d.map_or("-".to_string(), |d| format!("{d}"));
+            (
+                change.id,
+                format!(
+                    "{:?} {}@{} -> {}@{}",
+                    change.id_operation,
+                    version(change.input_version),
+                    digest(change.input_digest),
+                    version(change.output_version),
+                    digest(change.output_digest),
+                ),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use myso_types::{
+        effects::TransactionEffects, event::Event, execution_status::ExecutionFailureStatus,
+        execution_status::ExecutionStatus, test_checkpoint_data_builder::TestCheckpointBuilder,
+    };
+
+    use super::*;
+
+    /// The effects and events of a transaction that creates `objects` owned objects, and emits
+    /// `events`.
+    fn effects(objects: u64, events: Vec<Event>) -> (TransactionEffects, TransactionEvents) {
+        let mut builder = TestCheckpointBuilder::new(1).start_transaction(0);
+        for idx in 0..objects {
+            builder = builder.create_owned_object(idx);
+        }
+        if !events.is_empty() {
+            builder = builder.with_events(events);
+        }
+
+        let mut checkpoint = builder.finish_transaction().build_checkpoint();
+        let tx = checkpoint.transactions.remove(0);
+        (tx.effects, tx.events.unwrap_or_default())
+    }
+
+    #[test]
+    fn test_identical_effects() {
+        let (expected, events) = effects(1, vec![]);
+        let divergence = TransactionDivergence::compute(
+            "digest",
+            1,
+            &expected,
+            &expected.clone(),
+            Some(&events),
+            &events,
+        )
+        .unwrap();
+        assert!(divergence.is_none());
+    }
+
+    #[test]
+    fn test_status_and_gas_divergence() {
+        let (expected, events) = effects(1, vec![]);
+        let mut actual = expected.clone();
+        *actual.status_mut_for_testing() = ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::InsufficientGas,
+            command: None,
+        };
+        actual.gas_cost_summary_mut_for_testing().computation_cost += 100;
+
+        let divergence =
+            TransactionDivergence::compute("digest", 1, &expected, &actual, Some(&events), &events)
+                .unwrap()
+                .expect("Effects should diverge");
+
+        assert_eq!(divergence.digest, "digest");
+        assert_eq!(divergence.checkpoint, 1);
+
+        let status = divergence.status.expect("Status should diverge");
+        assert_eq!(status.expected, "Success");
+        assert!(
+            status.actual.contains("InsufficientGas"),
+            "{}",
+            status.actual
+        );
+
+        let gas = divergence.gas.expect("Gas should diverge");
+        let computation_cost = expected.gas_cost_summary().computation_cost;
+        assert_eq!(
+            gas.computation_cost,
+            Some(FieldDiff {
+                expected: computation_cost,
+                actual: computation_cost + 100,
+            })
+        );
+        assert!(gas.storage_cost.is_none());
+        assert!(gas.storage_rebate.is_none());
+        assert!(gas.non_refundable_storage_fee.is_none());
+
+        assert!(divergence.object_changes.is_empty());
+        assert!(divergence.events.is_none());
+    }
+
+    #[test]
+    fn test_object_and_event_divergence() {
+        let event = Event::random_for_testing();
+        let (expected, expected_events) = effects(1, vec![]);
+        let (actual, actual_events) = effects(2, vec![event.clone()]);
+
+        let divergence = TransactionDivergence::compute(
+            "digest",
+            1,
+            &expected,
+            &actual,
+            Some(&expected_events),
+            &actual_events,
+        )
+        .unwrap()
+        .expect("Effects should diverge");
+
+        // Only the object that the replay created in addition differs.
+        let [change] = divergence.object_changes.as_slice() else {
+            panic!(
+                "Expected one object change, got {:?}",
+                divergence.object_changes
+            );
+        };
+        assert_eq!(change.object_id, TestCheckpointBuilder::derive_object_id(1));
+        assert!(change.expected.is_none());
+        assert!(change.actual.as_ref().unwrap().starts_with("Created"));
+
+        let events = divergence.events.expect("Events should diverge");
+        assert_eq!(events.digest.expected, None);
+        assert_eq!(
+            events.digest.actual,
+            actual.events_digest().map(|d| d.to_string())
+        );
+
+        // The replay emitted an event that was not recorded.
+        let [event_diff] = events.events.as_deref().unwrap() else {
+            panic!("Expected one event diff, got {:?}", events.events);
+        };
+        assert_eq!(event_diff.index, 0);
+        assert_eq!(
+            event_diff.type_,
+            Some(FieldDiff {
+                expected: None,
+                actual: Some(event.type_.to_canonical_string(true)),
+            })
+        );
+        assert_eq!(
+            event_diff.contents,
+            Some(FieldDiff {
+                expected: None,
+                actual: Some(Hex::encode(&event.contents)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_event_divergence_by_index() {
+        let (first, second) = (Event::random_for_testing(), Event::random_for_testing());
+        let mut changed = second.clone();
+        changed.contents.push(0);
+
+        let (expected, expected_events) = effects(1, vec![first.clone(), second.clone()]);
+        let (actual, actual_events) = effects(1, vec![first, changed.clone()]);
+
+        let divergence = TransactionDivergence::compute(
+            "digest",
+            1,
+            &expected,
+            &actual,
+            Some(&expected_events),
+            &actual_events,
+        )
+        .unwrap()
+        .expect("Effects should diverge");
+
+        // Only the contents of the second event differ.
+        let events = divergence.events.expect("Events should diverge");
+        let [event_diff] = events.events.as_deref().unwrap() else {
+            panic!("Expected one event diff, got {:?}", events.events);
+        };
+        assert_eq!(event_diff.index, 1);
+        assert!(event_diff.type_.is_none());
+        assert!(event_diff.sender.is_none());
+        assert_eq!(
+            event_diff.contents,
+            Some(FieldDiff {
+                expected: Some(Hex::encode(&second.contents)),
+                actual: Some(Hex::encode(&changed.contents)),
+            })
+        );
+
+        // Without the recorded events, only the digests are compared.
+        let divergence =
+            TransactionDivergence::compute("digest", 1, &expected, &actual, None, &actual_events)
+                .unwrap()
+                .expect("Effects should diverge");
+        let events = divergence.events.expect("Events should diverge");
+        assert!(events.events.is_none());
+    }
+
+    #[test]
+    fn test_report() {
+        let (expected, events) = effects(1, vec![]);
+        let mut actual = expected.clone();
+        actual.gas_cost_summary_mut_for_testing().storage_cost += 1;
+
+        let mut report = DivergenceReport::new(10, 20);
+        assert!(report.is_clean());
+
+        report.add(
+            TransactionDivergence::compute(
+                "matched",
+                10,
+                &expected,
+                &expected,
+                Some(&events),
+                &events,
+            ),
+            "matched",
+        );
+        assert!(report.is_clean());
+
+        report.add(
+            TransactionDivergence::compute(
+                "diverged",
+                15,
+                &expected,
+                &actual,
+                Some(&events),
+                &events,
+            ),
+            "diverged",
+        );
+        report.add(Err(anyhow!("Failed to load objects")), "failed");
+        assert!(!report.is_clean());
+
+        assert_eq!(report.transactions, 3);
+        assert_eq!(report.matched, 1);
+        assert_eq!(report.diverged.len(), 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reports").join("divergence.json");
+        report.save(&path).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["first_checkpoint"], 10);
+        assert_eq!(json["last_checkpoint"], 20);
+        assert_eq!(json["transactions"], 3);
+        assert_eq!(json["matched"], 1);
+
+        // Parts of a divergence that match are left out.
+        let diverged = &json["diverged"][0];
+        assert_eq!(diverged["digest"], "diverged");
+        assert_eq!(diverged["checkpoint"], 15);
+        assert!(diverged.get("status").is_none());
+        assert!(diverged.get("object_changes").is_none());
+        assert!(diverged["gas"].get("computation_cost").is_none());
+        assert_eq!(
+            diverged["gas"]["storage_cost"]["actual"],
+            expected.gas_cost_summary().storage_cost + 1
+        );
+
+        assert_eq!(json["failed"][0]["digest"], "failed");
+        assert_eq!(json["failed"][0]["error"], "Failed to load objects");
+    }
+}
@@ -5,6 +5,7 @@
 use crate::{
     artifacts::{Artifact, ArtifactManager},
     displays::Pretty,
+    divergence::DivergenceReport,
     replay_txn::replay_transaction,
     summary_metrics::TotalMetrics,
 };
@@ -14,7 +15,7 @@ use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use move_package_alt::schema::EnvironmentName;
 use myso_config::myso_config_dir;
 use myso_data_store::{
-    Node, ReadDataStore, SetupStore, StoreSummary,
+    CheckpointStore, Node, ReadDataStore, SetupStore, StoreSummary,
     stores::{DataStore, FileSystemStore, InMemoryStore, ReadThroughStore},
 };
 use myso_json_rpc_types::MySoTransactionBlockEffects;
@@ -24,6 +25,7 @@ use similar::{ChangeTag, TextDiff};
 use std::{
     fs,
     io::Write,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
     time::Duration,
 };
@@ -32,6 +34,7 @@ use ::tracing::{Instrument, debug, error, info_span, warn};
 
 pub mod artifacts;
 pub mod displays;
+pub mod divergence;
 pub mod execution;
 pub mod package_tools;
 pub mod replay_txn;
@@ -40,10 +43,12 @@ pub mod tracing;
 
 const DEFAULT_OUTPUT_DIR: &str = ".replay";
 const CONFIG_FILE_NAME: &str = "replay.toml";
+const DIVERGENCE_REPORT_FILE_NAME: &str = "divergence_report.json";
 
 // Arguments to the replay tool.
-// It allows to replay a single transaction by digest or
-// a file containing multiple digests, one per line.
+// It allows to replay a single transaction by digest,
+// a file containing multiple digests, one per line,
+// or every transaction in a range of checkpoints.
 // This may evolve to something very different in time and
 // it's not meant to be stable.
 // The options available are very convenient for the current
@@ -132,6 +137,20 @@ pub struct ReplayConfigStable {
     #[arg(long = "digests-path")]
     pub digests_path: Option<PathBuf>,
 
+    /// First checkpoint of a range to replay. Every transaction in the range is replayed and
+    /// its effects compared against the recorded ones.
+    #[arg(long = "first-checkpoint")]
+    pub first_checkpoint: Option<u64>,
+
+    /// Last checkpoint (inclusive) of the range to replay. Defaults to `--first-checkpoint`.
+    #[arg(long = "last-checkpoint", requires = "first_checkpoint")]
+    pub last_checkpoint: Option<u64>,
+
+    /// Where to write the divergence report for a checkpoint range replay.
+    /// Defaults to `<output_dir>/divergence_report.json`.
+    #[arg(long = "report-path", requires = "first_checkpoint")]
+    pub report_path: Option<PathBuf>,
+
     /// Terminate a batch replay early if an error occurs when replaying one of the transactions.
     #[arg(long = "terminate-early", num_args = 0, default_missing_value = "true")]
     pub terminate_early: Option<bool>,
@@ -161,6 +180,9 @@ pub struct ReplayConfigStable {
 pub struct ReplayConfigStableInternal {
     pub digest: Option<String>,
     pub digests_path: Option<PathBuf>,
+    pub first_checkpoint: Option<u64>,
+    pub last_checkpoint: Option<u64>,
+    pub report_path: Option<PathBuf>,
     pub terminate_early: bool,
     pub trace: bool,
     pub output_dir: Option<PathBuf>,
@@ -177,6 +199,9 @@ impl Default for ReplayConfigStableInternal {
         Self {
             digest: None,
             digests_path: None,
+            first_checkpoint: None,
+            last_checkpoint: None,
+            report_path: None,
             terminate_early: false,
             trace: false,
             output_dir: None,
@@ -298,6 +323,12 @@ pub fn merge_configs(
 
         digests_path: cli_config.digests_path.or(file_config.digests_path),
 
+        first_checkpoint: cli_config.first_checkpoint.or(file_config.first_checkpoint),
+
+        last_checkpoint: cli_config.last_checkpoint.or(file_config.last_checkpoint),
+
+        report_path: cli_config.report_path.or(file_config.report_path),
+
         terminate_early: cli_config
             .terminate_early
             .or(file_config.terminate_early)
@@ -330,6 +361,9 @@ pub async fn handle_replay_config(
     let ReplayConfigStableInternal {
         digest,
         digests_path,
+        first_checkpoint,
+        last_checkpoint,
+        report_path,
         terminate_early,
         trace,
         output_dir,
@@ -364,9 +398,25 @@ pub async fn handle_replay_config(
         );
     }
 
+    // A checkpoint range takes precedence: its digests are read from the data store
+    // once it is set up.
+    let checkpoints = match (first_checkpoint, last_checkpoint) {
+        (Some(first), last) => {
+            let last = last.unwrap_or(*first);
+            if last < *first {
+                bail!("--last-checkpoint {last} is before --first-checkpoint {first}");
+            }
+            Some(*first..=last)
+        }
+        (None, Some(_)) => bail!("--last-checkpoint requires --first-checkpoint"),
+        (None, None) => None,
+    };
+
     // If a file is specified it is read and the digest ignored.
     // Once we decide on the options we want this is likely to change.
-    let digests = if let Some(digests_path) = digests_path {
+    let digests = if checkpoints.is_some() {
+        vec![]
+    } else if let Some(digests_path) = digests_path {
         // read digests from file
         std::fs::read_to_string(digests_path.clone())
             .map_err(|e| {
@@ -384,13 +434,13 @@ pub async fn handle_replay_config(
         // single digest provided
         vec![tx_digest.clone()]
     } else {
-        bail!("either --digest or --digests-path must be provided");
+        bail!("either --digest, --digests-path or --first-checkpoint must be provided");
     };
 
     debug!("Binary version: {version}");
 
     // Build the selected data store and run replay
-    match store_mode {
+    let report = match store_mode {
         StoreMode::GqlOnly => {
             let gql_store = DataStore::new(node.clone(), version)
                 .map_err(|e| anyhow!("Failed to create data store: {:?}", e))?;
@@ -398,6 +448,7 @@ pub async fn handle_replay_config(
                 &gql_store,
                 &output_root_dir,
                 &digests,
+                checkpoints.clone(),
                 node,
                 *overwrite_existing,
                 *trace,
@@ -406,7 +457,7 @@ pub async fn handle_replay_config(
                 *track_time,
                 *cache_executor,
             )
-            .await?;
+            .await?
         }
         StoreMode::FsThenGql => {
             let fs_store = FileSystemStore::new(node.clone())
@@ -418,6 +469,7 @@ pub async fn handle_replay_config(
                 &store,
                 &output_root_dir,
                 &digests,
+                checkpoints.clone(),
                 node,
                 *overwrite_existing,
                 *trace,
@@ -426,7 +478,7 @@ pub async fn handle_replay_config(
                 *track_time,
                 *cache_executor,
             )
-            .await?;
+            .await?
         }
         StoreMode::FsOnly => {
             let fs_store = FileSystemStore::new(node.clone())
@@ -435,6 +487,7 @@ pub async fn handle_replay_config(
                 &fs_store,
                 &output_root_dir,
                 &digests,
+                checkpoints.clone(),
                 node,
                 *overwrite_existing,
                 *trace,
@@ -443,7 +496,7 @@ pub async fn handle_replay_config(
                 *track_time,
                 *cache_executor,
             )
-            .await?;
+            .await?
         }
         StoreMode::InmemFs => {
             let fs_store = FileSystemStore::new(node.clone())
@@ -454,6 +507,7 @@ pub async fn handle_replay_config(
                 &store,
                 &output_root_dir,
                 &digests,
+                checkpoints.clone(),
                 node,
                 *overwrite_existing,
                 *trace,
@@ -462,7 +516,7 @@ pub async fn handle_replay_config(
                 *track_time,
                 *cache_executor,
             )
-            .await?;
+            .await?
         }
         StoreMode::InmemFsGql => {
             let fs_store = FileSystemStore::new(node.clone())
@@ -476,6 +530,7 @@ pub async fn handle_replay_config(
                 &store,
                 &output_root_dir,
                 &digests,
+                checkpoints.clone(),
                 node,
                 *overwrite_existing,
                 *trace,
@@ -484,7 +539,30 @@ pub async fn handle_replay_config(
                 *track_time,
                 *cache_executor,
             )
-            .await?;
+            .await?
+        }
+    };
+
+    if let Some(report) = report {
+        let report_path = report_path
+            .clone()
+            .unwrap_or_else(|| output_root_dir.join(DIVERGENCE_REPORT_FILE_NAME));
+        report.save(&report_path)?;
+        println!(
+            "Divergence report: tx_count={} matched={} diverged={} failed={} - {}",
+            report.transactions,
+            report.matched,
+            report.diverged.len(),
+            report.failed.len(),
+            report_path.display(),
+        );
+        if !report.is_clean() {
+            bail!(
+                "{} transactions diverged and {} failed to replay, see {}",
+                report.diverged.len(),
+                report.failed.len(),
+                report_path.display(),
+            );
         }
     }
 
@@ -495,6 +573,7 @@ async fn run_replay<S>(
     data_store: &S,
     output_root_dir: &Path,
     digests: &[String],
+    checkpoints: Option<RangeInclusive<u64>>,
     node: &Node,
     overwrite_existing: bool,
     trace: bool,
@@ -502,14 +581,37 @@ async fn run_replay<S>(
     terminate_early: bool,
     track_time: bool,
     cache_executor: bool,
-) -> Result<()>
+) -> Result<Option<DivergenceReport>>
 where
-    S: ReadDataStore + StoreSummary + SetupStore,
+    S: ReadDataStore + CheckpointStore + StoreSummary + SetupStore,
 {
     use crate::replay_txn::ExecutorProvider;
     use std::time::Instant;
 
     data_store.setup(None)?;
+
+    // When replaying a checkpoint range, the transactions come from the data store and
+    // the outcome of every transaction goes into the divergence report.
+    let mut report = None;
+    let range_digests;
+    let digests = if let Some(checkpoints) = checkpoints {
+        let mut all = vec![];
+        for checkpoint in checkpoints.clone() {
+            let txns = data_store
+                .checkpoint_transactions(checkpoint)?
+                .ok_or_else(|| anyhow!("Checkpoint {checkpoint} not found in the data store"))?;
+            all.extend(txns);
+        }
+        report = Some(DivergenceReport::new(
+            *checkpoints.start(),
+            *checkpoints.end(),
+        ));
+        range_digests = all;
+        &range_digests[..]
+    } else {
+        digests
+    };
+
     let mut total_metrics = TotalMetrics::new();
     let mut executor_provider = ExecutorProvider::new(cache_executor);
 
@@ -541,7 +643,7 @@ where
         let tx_total_ms = tx_start.elapsed().as_millis();
 
         let success = result.is_ok();
-        let exec_ms = result.as_ref().ok().copied().unwrap_or(0);
+        let exec_ms = result.as_ref().map_or(0, |outcome| outcome.exec_ms);
 
         total_metrics.add_transaction(success, tx_total_ms, exec_ms);
 
@@ -559,6 +661,14 @@ where
 
         tx_spinner.println(format!("Executed transaction {}{}", tx_digest, time_info));
 
+        if let Some(report) = report.as_mut() {
+            let divergence = result
+                .as_ref()
+                .map(|outcome| outcome.divergence.clone())
+                .map_err(|e| anyhow!("{e:#}"));
+            report.add(divergence, tx_digest);
+        }
+
         match result {
             Err(e) if terminate_early => {
                 error!(tx_digest = %tx_digest, error = ?e, "Replay error; terminating early");
@@ -593,7 +703,7 @@ where
         );
     }
 
-    Ok(())
+    Ok(report)
 }
 
 pub fn print_effects_or_fork<W: Write>(
@@ -13,6 +13,7 @@
 
 use crate::{
     artifacts::{Artifact, ArtifactManager, MoveCallInfo, ReplayCacheSummary},
+    divergence::TransactionDivergence,
     execution::{ReplayExecutor, execute_transaction_to_effects},
     tracing::save_trace_output,
 };
@@ -26,7 +27,8 @@ use myso_types::{
     base_types::{MySoAddress, ObjectID},
     digests::TransactionDigest,
     effects::{
-        InputConsensusObject, TransactionEffects, TransactionEffectsAPI, UnchangedConsensusKind,
+        InputConsensusObject, TransactionEffects, TransactionEffectsAPI, TransactionEvents,
+        UnchangedConsensusKind,
     },
     object::Object,
     transaction::{
@@ -103,6 +105,13 @@ pub struct ReplayTransaction {
     pub object_cache: BTreeMap<ObjectID, BTreeMap<ObjectVersion, Object>>,
 }
 
+// Result of replaying a single transaction.
+pub(crate) struct ReplayOutcome {
+    pub exec_ms: u128,
+    // How the replayed effects differ from the recorded ones, `None` if identical
+    pub divergence: Option<TransactionDivergence>,
+}
+
 //
 // Run a single transaction and print results to stdout
 //
@@ -113,7 +122,7 @@ pub(crate) async fn replay_transaction<S: ReadDataStore>(
     network: String,
     trace: bool,
     executor_provider: &mut ExecutorProvider,
-) -> Result<u128> {
+) -> Result<ReplayOutcome> {
     let _span = info_span!("replay_tx", tx_digest = %tx_digest).entered();
     // load a `ReplayTransaction`
     let replay_txn = match ReplayTransaction::load(
@@ -215,13 +224,37 @@ pub(crate) async fn replay_transaction<S: ReadDataStore>(
         }
     }
 
+    // The recorded events are only needed to explain diverging events.
+    let expected_events_digest = context_and_effects.expected_effects.events_digest();
+    let expected_events =
+        if expected_events_digest == context_and_effects.execution_effects.events_digest() {
+            None
+        } else if expected_events_digest.is_none() {
+            Some(TransactionEvents::default())
+        } else {
+            data_store
+                .transaction_events(tx_digest)
+                .with_context(|| format!("Failed to load events for transaction {}", tx_digest))?
+        };
+    let divergence = TransactionDivergence::compute(
+        tx_digest,
+        context_and_effects.checkpoint,
+        &context_and_effects.expected_effects,
+        &context_and_effects.execution_effects,
+        expected_events.as_ref(),
+        &context_and_effects.inner_store.events,
+    )?;
+
     verify_txn_and_save_effects(
         artifact_manager,
         &context_and_effects.expected_effects,
         &context_and_effects.execution_effects,
     )?;
 
-    Ok(exec_ms)
+    Ok(ReplayOutcome {
+        exec_ms,
+        divergence,
+    })
 }
 
 fn verify_txn_and_save_effects(
@@ -277,6 +277,7 @@ impl Client {
         Ok(SimulateTransactionResponse {
             transaction,
             command_outputs: response.command_outputs,
+            objects: response.transaction().objects().objects().to_vec(),
         })
     }
 
@@ -700,6 +701,8 @@ impl ExecutedTransaction {
 pub struct SimulateTransactionResponse {
     pub transaction: ExecutedTransaction,
     pub command_outputs: Vec<proto::CommandResult>,
+    /// Objects the transaction reads or writes, before and after it.
+    pub objects: Vec<proto::Object>,
 }
 
 /// Attempts to parse `CertifiedCheckpointSummary` from a proto::Checkpoint
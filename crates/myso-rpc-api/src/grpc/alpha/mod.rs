@@ -6,6 +6,10 @@ pub mod event_service;
 pub mod list_authenticated_events;
 pub mod proof_service;
 
+pub mod archive_service_proto {
+    include!("../../proto/generated/myso.rpc.alpha.rs");
+}
+
 pub mod event_service_proto {
     include!("../../proto/generated/myso.rpc.alpha.rs");
 }
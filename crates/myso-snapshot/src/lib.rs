@@ -10,7 +10,8 @@ pub mod reader;
 pub mod uploader;
 mod writer;
 
-use anyhow::Result;
+use anyhow::{Result, ensure};
+use bytes::Bytes;
 use fastcrypto::hash::MultisetHash;
 use indicatif::MultiProgress;
 use indicatif::ProgressBar;
@@ -24,7 +25,9 @@ use myso_core::epoch::committee_store::CommitteeStore;
 use myso_core::global_state_hasher::WrappedObject;
 use myso_protocol_config::Chain;
 use myso_storage::object_store::util::path_to_filesystem;
-use myso_storage::{FileCompression, SHA3_BYTES, compute_sha3_checksum};
+use myso_storage::{
+    FileCompression, SHA3_BYTES, compute_sha3_checksum, compute_sha3_checksum_for_bytes,
+};
 use myso_types::base_types::ObjectID;
 use myso_types::global_state_hash::GlobalStateHash;
 use myso_types::messages_checkpoint::ECMHLiveObjectSetDigest;
@@ -169,6 +172,19 @@ impl FileMetadata {
     pub fn local_file_path(&self, root_path: &std::path::Path, dir_path: &Path) -> Result<PathBuf> {
         path_to_filesystem(root_path.to_path_buf(), &self.file_path(dir_path))
     }
+    /// Checks the content of this file, as stored in the snapshot, against its checksum in the
+    /// MANIFEST.
+    pub fn verify_checksum(&self, bytes: &Bytes) -> Result<()> {
+        let sha3_digest = compute_sha3_checksum_for_bytes(bytes.clone())?;
+        ensure!(
+            sha3_digest == self.sha3_digest,
+            "Checksum mismatch for {:?} file of bucket: {}, part: {}",
+            self.file_type,
+            self.bucket_num,
+            self.part_num
+        );
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -21,8 +21,8 @@ use myso_core::authority::authority_store_tables::{AuthorityPerpetualTables, Liv
 use myso_futures::stream::TrySpawnStreamExt;
 use myso_storage::blob::{Blob, BlobEncoding};
 use myso_storage::object_store::http::HttpDownloaderBuilder;
-use myso_storage::object_store::util::{copy_files, path_to_filesystem};
-use myso_storage::object_store::{ObjectStoreGetExt, ObjectStoreListExt, ObjectStorePutExt};
+use myso_storage::object_store::util::path_to_filesystem;
+use myso_storage::object_store::{ObjectStoreGetExt, ObjectStorePutExt};
 use myso_types::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber};
 use myso_types::global_state_hash::GlobalStateHash;
 use object_store::path::Path;
@@ -55,6 +55,7 @@ pub struct StateSnapshotReaderV1 {
     concurrency: usize,
     max_retries: usize,
     remote_epoch_prefix: Path,
+    stage_object_files: bool,
 }
 
 impl StateSnapshotReaderV1 {
@@ -121,6 +122,80 @@ impl StateSnapshotReaderV1 {
         }
     }
 
+    /// Downloads a file of the snapshot, retrying until its content matches the checksum in the
+    /// MANIFEST.
+    async fn download_verified_file<S: ObjectStoreGetExt>(
+        src: &Path,
+        file_metadata: &FileMetadata,
+        src_store: &S,
+        max_retries: usize,
+    ) -> Result<Bytes> {
+        let mut attempts = 0;
+        let max_attempts = max_retries + 1;
+        loop {
+            attempts += 1;
+            let result = match src_store.get_bytes(src).await {
+                Ok(bytes) => file_metadata.verify_checksum(&bytes).map(|_| bytes),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(bytes) => return Ok(bytes),
+                Err(e) if attempts >= max_attempts => {
+                    return Err(anyhow!(
+                        "Failed to download {} after {} attempts: {}",
+                        src,
+                        attempts,
+                        e
+                    ));
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to download {} (attempt {}/{}): {}, retrying in {}ms",
+                        src,
+                        attempts,
+                        max_attempts,
+                        e,
+                        1000 * attempts
+                    );
+                    tokio::time::sleep(Duration::from_millis(1000 * attempts as u64)).await;
+                }
+            }
+        }
+    }
+
+    /// Reads a file left in the local staging directory by a previous run, if it is there and
+    /// matches the checksum in the MANIFEST.
+    async fn read_staged_file(
+        file_metadata: &FileMetadata,
+        root_path: &std::path::Path,
+        dir_path: &Path,
+    ) -> Result<Option<Bytes>> {
+        let file_path = file_metadata.local_file_path(root_path, dir_path)?;
+        let file_metadata = file_metadata.clone();
+        // Object files are large, so they are read and hashed off the async runtime.
+        tokio::task::spawn_blocking(move || {
+            if !file_path.exists() {
+                return Ok(None);
+            }
+            let bytes = Bytes::from(fs::read(&file_path)?);
+            match file_metadata.verify_checksum(&bytes) {
+                Ok(()) => Ok(Some(bytes)),
+                Err(e) => {
+                    tracing::warn!("Discarding staged file {}: {}", file_path.display(), e);
+                    Ok(None)
+                }
+            }
+        })
+        .await?
+    }
+
+    /// Creates a reader for the snapshot of `epoch`, downloading its MANIFEST and reference files
+    /// to the local staging directory.
+    ///
+    /// With `skip_reset_local_store`, files left in the staging directory by a previous run are
+    /// kept, and the ones matching the MANIFEST are not downloaded again. With
+    /// `stage_object_files`, object files are also kept in the staging directory as they are
+    /// downloaded, so that an interrupted restore can be resumed without downloading them again.
     pub async fn new(
         epoch: u64,
         remote_store_config: &ObjectStoreConfig,
@@ -129,6 +204,7 @@ impl StateSnapshotReaderV1 {
         m: MultiProgress,
         skip_reset_local_store: bool,
         max_retries: usize,
+        stage_object_files: bool,
     ) -> Result<Self> {
         let remote_object_store = if remote_store_config.no_sign_request {
             remote_store_config.make_http()?
@@ -137,8 +213,6 @@ impl StateSnapshotReaderV1 {
         };
         let local_object_store: Arc<dyn ObjectStorePutExt> =
             local_store_config.make().map(Arc::new)?;
-        let local_object_store_list: Arc<dyn ObjectStoreListExt> =
-            local_store_config.make().map(Arc::new)?;
         let local_staging_dir_root = local_store_config
             .directory
             .as_ref()
@@ -231,53 +305,57 @@ impl StateSnapshotReaderV1 {
             }
         }
 
-        let mut src_files = Vec::new();
-        let mut dest_files = Vec::new();
-
-        let existing_files = if skip_reset_local_store {
-            let mut existing = std::collections::HashSet::new();
-            let mut list_stream = local_object_store_list
-                .list_objects(Some(&local_epoch_dir_path))
-                .await;
-            while let Some(Ok(meta)) = list_stream.next().await {
-                existing.insert(meta.location);
-            }
-            Some(existing)
-        } else {
-            None
-        };
-
+        let mut missing_ref_files = Vec::new();
         for entry in ref_files.values() {
             for file_metadata in entry.values() {
-                let dest = file_metadata.file_path(&local_epoch_dir_path);
-                if existing_files
-                    .as_ref()
-                    .is_some_and(|existing| existing.contains(&dest))
+                if skip_reset_local_store
+                    && Self::read_staged_file(
+                        file_metadata,
+                        &local_staging_dir_root,
+                        &local_epoch_dir_path,
+                    )
+                    .await?
+                    .is_some()
                 {
                     continue;
                 }
-                src_files.push(file_metadata.file_path(&remote_epoch_prefix));
-                dest_files.push(dest);
+                missing_ref_files.push(file_metadata.clone());
             }
         }
 
         let progress_bar = m.add(
-            ProgressBar::new(src_files.len() as u64).with_style(
+            ProgressBar::new(missing_ref_files.len() as u64).with_style(
                 ProgressStyle::with_template(
                     "[{elapsed_precise}] {wide_bar} {pos} out of {len} missing .ref files done ({msg})",
                 )
                 .unwrap(),
             ),
         );
-        copy_files(
-            &src_files,
-            &dest_files,
-            &remote_object_store,
-            &local_object_store,
-            download_concurrency,
-            Some(progress_bar.clone()),
-        )
-        .await?;
+        futures::stream::iter(missing_ref_files)
+            .map(|file_metadata| {
+                let remote_object_store = remote_object_store.clone();
+                let local_object_store = local_object_store.clone();
+                let src = file_metadata.file_path(&remote_epoch_prefix);
+                let dest = file_metadata.file_path(&local_epoch_dir_path);
+                let progress_bar = progress_bar.clone();
+                async move {
+                    let bytes = Self::download_verified_file(
+                        &src,
+                        &file_metadata,
+                        &remote_object_store,
+                        max_retries,
+                    )
+                    .await?;
+                    local_object_store.put_bytes(&dest, bytes).await?;
+                    progress_bar.inc(1);
+                    progress_bar.set_message(format!("Downloaded {}", src));
+                    Ok::<(), anyhow::Error>(())
+                }
+            })
+            .boxed()
+            .buffer_unordered(download_concurrency.get())
+            .try_collect::<Vec<()>>()
+            .await?;
         progress_bar.finish_with_message("Missing ref files download complete");
         Ok(StateSnapshotReaderV1 {
             epoch,
@@ -290,6 +368,7 @@ impl StateSnapshotReaderV1 {
             concurrency: download_concurrency.get(),
             max_retries,
             remote_epoch_prefix,
+            stage_object_files,
         })
     }
 
@@ -485,6 +564,7 @@ impl StateSnapshotReaderV1 {
         sha3_digests: Arc<Mutex<DigestByBucketAndPartition>>,
     ) -> Result<(), anyhow::Error> {
         let epoch_dir = self.remote_epoch_prefix.clone();
+        let local_epoch_dir = self.epoch_dir();
         let concurrency = self.concurrency;
         let remote_object_store = self.remote_object_store.clone();
         let input_files: Vec<_> = self
@@ -515,20 +595,51 @@ impl StateSnapshotReaderV1 {
                 futures::stream::iter(input_files.iter())
                     .map(|(bucket, (part_num, file_metadata))| {
                         let epoch_dir_clone = epoch_dir.clone();
+                        let local_epoch_dir_clone = local_epoch_dir.clone();
                         let remote_object_store_clone = remote_object_store.clone();
+                        let local_object_store_clone = self.local_object_store.clone();
                         let sha3_digests_clone = sha3_digests.clone();
                         async move {
-                            // Download object file with retries
-                            let (bytes, sha3_digest) = download_bytes(
-                                remote_object_store_clone,
-                                file_metadata,
-                                epoch_dir_clone,
-                                sha3_digests_clone,
-                                bucket,
-                                part_num,
-                                None,
-                            )
-                            .await;
+                            let staged = if self.stage_object_files {
+                                Self::read_staged_file(
+                                    file_metadata,
+                                    &self.local_staging_dir_root,
+                                    &local_epoch_dir_clone,
+                                )
+                                .await?
+                            } else {
+                                None
+                            };
+                            let (bytes, sha3_digest) = match staged {
+                                Some(bytes) => {
+                                    let sha3_digest =
+                                        ref_digest(&sha3_digests_clone, bucket, part_num).await;
+                                    (bytes, sha3_digest)
+                                }
+                                None => {
+                                    // Download object file with retries
+                                    let (bytes, sha3_digest) = download_bytes(
+                                        remote_object_store_clone,
+                                        file_metadata,
+                                        epoch_dir_clone,
+                                        sha3_digests_clone,
+                                        bucket,
+                                        part_num,
+                                        None,
+                                        self.max_retries,
+                                    )
+                                    .await?;
+                                    if self.stage_object_files {
+                                        local_object_store_clone
+                                            .put_bytes(
+                                                &file_metadata.file_path(&local_epoch_dir_clone),
+                                                bytes.clone(),
+                                            )
+                                            .await?;
+                                    }
+                                    (bytes, sha3_digest)
+                                }
+                            };
                             Ok::<(Bytes, FileMetadata, [u8; 32]), anyhow::Error>((
                                 bytes,
                                 (*file_metadata).clone(),
@@ -665,13 +776,17 @@ pub async fn download_bytes(
     bucket: &&u32,
     part_num: &u32,
     max_timeout_secs: Option<u64>,
-) -> (Bytes, [u8; 32]) {
+    max_retries: usize,
+) -> Result<(Bytes, [u8; 32])> {
     const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300); // 5 minutes for large files
     const INITIAL_BACKOFF: Duration = Duration::from_secs(3);
 
     let backoff_cap = Duration::from_secs(max_timeout_secs.unwrap_or(60));
     let mut backoff = INITIAL_BACKOFF;
     let mut attempts = 0usize;
+    // Failed and timed out downloads are retried indefinitely, but a remote file that keeps
+    // failing its checksum will not fix itself.
+    let mut corrupt_attempts = 0usize;
     let file_path = file_metadata.file_path(&epoch_dir);
     let bytes = loop {
         debug!(
@@ -682,9 +797,22 @@ pub async fn download_bytes(
         match tokio::time::timeout(DOWNLOAD_TIMEOUT, remote_object_store.get_bytes(&file_path))
             .await
         {
-            Ok(Ok(bytes)) => {
-                break bytes;
-            }
+            Ok(Ok(bytes)) => match file_metadata.verify_checksum(&bytes) {
+                Ok(()) => break bytes,
+                Err(err) => {
+                    error!(
+                        "Downloaded corrupt {}: {} (attempt {})",
+                        file_path, err, attempts,
+                    );
+                    corrupt_attempts += 1;
+                    if corrupt_attempts > max_retries {
+                        return Err(err.context(format!(
+                            "Giving up on {} after {} corrupt downloads",
+                            file_path, corrupt_attempts
+                        )));
+                    }
+                }
+            },
             Ok(Err(err)) => {
                 error!(
                     "Failed to download {}: {} (attempt {})",
@@ -708,15 +836,20 @@ pub async fn download_bytes(
         backoff = std::cmp::min(backoff, backoff_cap);
     };
 
+    Ok((bytes, ref_digest(&sha3_digests, bucket, part_num).await))
+}
+
+/// Returns the digest of the object references of a bucket partition, computed from its .ref
+/// file.
+async fn ref_digest(sha3_digests: &Sha3DigestType, bucket: &u32, part_num: &u32) -> [u8; 32] {
     let sha3_digest = sha3_digests.lock().await;
     let bucket_map = sha3_digest
         .get(bucket)
         .expect("Bucket not in digest map")
         .clone();
-    let sha3_digest = *bucket_map
+    *bucket_map
         .get(part_num)
-        .expect("sha3 digest not in bucket map");
-    (bytes, sha3_digest)
+        .expect("sha3 digest not in bucket map")
 }
 
 /// An iterator over all object refs in a .ref file.
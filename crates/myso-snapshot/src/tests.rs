@@ -119,6 +119,7 @@ async fn test_snapshot_basic() -> Result<(), anyhow::Error> {
         MultiProgress::new(),
         false, // skip_reset_local_store
         3,     // max_retries
+        false, // stage_object_files
     )
     .await?;
     let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None, None);
@@ -175,6 +176,7 @@ async fn test_snapshot_empty_db() -> Result<(), anyhow::Error> {
         MultiProgress::new(),
         false, // skip_reset_local_store
         3,     // max_retries
+        false, // stage_object_files
     )
     .await?;
     let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None, None);
@@ -302,6 +304,7 @@ async fn test_snapshot_restore_from_archive() -> Result<(), anyhow::Error> {
         MultiProgress::new(),
         false, // skip_reset_local_store
         3,     // max_retries
+        false, // stage_object_files
     )
     .await?;
     let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None, None);
@@ -312,3 +315,214 @@ async fn test_snapshot_restore_from_archive() -> Result<(), anyhow::Error> {
     compare_live_objects(&perpetual_db, &restored_perpetual_db, true)?;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_snapshot_resume_from_staged_files() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let restored_db_path = temp_dir();
+    let resumed_db_path = temp_dir();
+    let local = temp_dir().join("local_dir");
+    let remote = temp_dir().join("remote_dir");
+    let restored_local = temp_dir().join("local_dir_restore");
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(local),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(remote.clone()),
+        ..Default::default()
+    };
+
+    let snapshot_writer = StateSnapshotWriterV1::new(
+        &local_store_config,
+        &remote_store_config,
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?;
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&db_path, None, None));
+    insert_keys(&perpetual_db, 1000)?;
+    let root_accumulator =
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(&perpetual_db, true).digest());
+    snapshot_writer
+        .write_internal(0, true, perpetual_db.clone(), root_accumulator)
+        .await?;
+    let local_store_restore_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(restored_local),
+        ..Default::default()
+    };
+    let mut snapshot_reader = StateSnapshotReaderV1::new(
+        0,
+        &remote_store_config,
+        &local_store_restore_config,
+        NonZeroUsize::new(1).unwrap(),
+        MultiProgress::new(),
+        false, // skip_reset_local_store
+        3,     // max_retries
+        true,  // stage_object_files
+    )
+    .await?;
+    let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None, None);
+    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+    snapshot_reader
+        .read(&restored_perpetual_db, abort_registration, None)
+        .await?;
+
+    // Object files are now only available from the staging directory.
+    for entry in std::fs::read_dir(remote.join("epoch_0"))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "obj") {
+            std::fs::remove_file(path)?;
+        }
+    }
+    let mut snapshot_reader = StateSnapshotReaderV1::new(
+        0,
+        &remote_store_config,
+        &local_store_restore_config,
+        NonZeroUsize::new(1).unwrap(),
+        MultiProgress::new(),
+        true, // skip_reset_local_store
+        3,    // max_retries
+        true, // stage_object_files
+    )
+    .await?;
+    let resumed_perpetual_db = AuthorityPerpetualTables::open(&resumed_db_path, None, None);
+    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+    snapshot_reader
+        .read(&resumed_perpetual_db, abort_registration, None)
+        .await?;
+    compare_live_objects(&perpetual_db, &resumed_perpetual_db, true)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_corrupt_file() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let local = temp_dir().join("local_dir");
+    let remote = temp_dir().join("remote_dir");
+    let restored_local = temp_dir().join("local_dir_restore");
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(local),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(remote.clone()),
+        ..Default::default()
+    };
+
+    let snapshot_writer = StateSnapshotWriterV1::new(
+        &local_store_config,
+        &remote_store_config,
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?;
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&db_path, None, None));
+    insert_keys(&perpetual_db, 1000)?;
+    let root_accumulator =
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(&perpetual_db, true).digest());
+    snapshot_writer
+        .write_internal(0, true, perpetual_db.clone(), root_accumulator)
+        .await?;
+
+    let ref_file = std::fs::read_dir(remote.join("epoch_0"))?
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "ref"))
+        .expect("Snapshot has no ref file");
+    let mut bytes = std::fs::read(&ref_file)?;
+    bytes.push(0);
+    std::fs::write(&ref_file, bytes)?;
+
+    let local_store_restore_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(restored_local),
+        ..Default::default()
+    };
+    let err = StateSnapshotReaderV1::new(
+        0,
+        &remote_store_config,
+        &local_store_restore_config,
+        NonZeroUsize::new(1).unwrap(),
+        MultiProgress::new(),
+        false, // skip_reset_local_store
+        0,     // max_retries
+        false, // stage_object_files
+    )
+    .await
+    .err()
+    .expect("Corrupt ref file should be rejected");
+    assert!(err.to_string().contains("Checksum mismatch"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_corrupt_object_file() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let restored_db_path = temp_dir();
+    let local = temp_dir().join("local_dir");
+    let remote = temp_dir().join("remote_dir");
+    let restored_local = temp_dir().join("local_dir_restore");
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(local),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(remote.clone()),
+        ..Default::default()
+    };
+
+    let snapshot_writer = StateSnapshotWriterV1::new(
+        &local_store_config,
+        &remote_store_config,
+        FileCompression::Zstd,
+        NonZeroUsize::new(1).unwrap(),
+    )
+    .await?;
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&db_path, None, None));
+    insert_keys(&perpetual_db, 1000)?;
+    let root_accumulator =
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(&perpetual_db, true).digest());
+    snapshot_writer
+        .write_internal(0, true, perpetual_db.clone(), root_accumulator)
+        .await?;
+
+    let obj_file = std::fs::read_dir(remote.join("epoch_0"))?
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "obj"))
+        .expect("Snapshot has no object file");
+    let mut bytes = std::fs::read(&obj_file)?;
+    bytes.push(0);
+    std::fs::write(&obj_file, bytes)?;
+
+    let local_store_restore_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(restored_local),
+        ..Default::default()
+    };
+    let mut snapshot_reader = StateSnapshotReaderV1::new(
+        0,
+        &remote_store_config,
+        &local_store_restore_config,
+        NonZeroUsize::new(1).unwrap(),
+        MultiProgress::new(),
+        false, // skip_reset_local_store
+        0,     // max_retries
+        false, // stage_object_files
+    )
+    .await?;
+    let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None, None);
+    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+    let err = snapshot_reader
+        .read(&restored_perpetual_db, abort_registration, None)
+        .await
+        .expect_err("Corrupt object file should be rejected");
+    assert!(format!("{err:#}").contains("Checksum mismatch"));
+    Ok(())
+}
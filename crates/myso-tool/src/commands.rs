@@ -4,6 +4,8 @@
 
 #[cfg(not(tidehunter))]
 use crate::db_tool::{DbToolCommand, execute_db_tool_command, print_db_all_tables};
+use crate::display::{DisplayTemplateArgs, display_template};
+use crate::storage_stats::{StorageStatsOptions, print_storage_stats};
 use crate::{
     ConciseObjectOutput, GroupedObjectOutput, SnapshotVerifyMode, VerboseObjectOutput,
     check_completed_snapshot, download_db_snapshot, download_formal_snapshot,
@@ -12,7 +14,9 @@ use crate::{
 };
 use anyhow::Result;
 use consensus_core::storage::{Store, rocksdb_store::RocksDBStore};
-use consensus_core::{BlockAPI, CommitAPI, CommitRange};
+use consensus_core::{
+    BlockAPI, CommitAPI, CommitRange, DAG_EXPORT_VERSION, DagExport, DagReplayer, DagVisualizer,
+};
 use futures::TryStreamExt;
 use futures::future::join_all;
 use myso_config::genesis::Genesis;
@@ -45,6 +49,16 @@ pub enum Verbosity {
     Verbose,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+pub enum DagFormat {
+    /// The text format of consensus DAG tests
+    Dag,
+    /// Graphviz source
+    Dot,
+    /// SVG rendered by the Graphviz `dot` binary
+    Svg,
+}
+
 #[derive(Parser)]
 pub enum ToolCommand {
     #[command(name = "scan-consensus-commits")]
@@ -57,6 +71,61 @@ pub enum ToolCommand {
         end_commit: Option<u32>,
     },
 
+    /// Export the blocks, commits and leader schedule scores of a range of rounds from a
+    /// consensus db into a portable file, to be replayed with `replay-consensus-dag`.
+    #[command(name = "export-consensus-dag")]
+    ExportConsensusDag {
+        #[arg(long = "db-path")]
+        db_path: String,
+        #[arg(long = "start-round")]
+        start_round: u32,
+        #[arg(long = "end-round")]
+        end_round: u32,
+        /// Stake of each authority of the epoch, in committee order, separated by commas
+        #[arg(long = "stakes", value_delimiter = ',', required = true)]
+        stakes: Vec<u64>,
+        /// Protocol version of the epoch. The consensus store does not record it, and the commit
+        /// rule depends on it, so it must match the version the epoch ran with
+        #[arg(long = "protocol-version")]
+        protocol_version: u64,
+        /// Start timestamp of the epoch, only needed when exporting from the start of the epoch
+        #[arg(long = "epoch-start-timestamp-ms")]
+        epoch_start_timestamp_ms: Option<u64>,
+        #[arg(long = "output")]
+        output: PathBuf,
+    },
+
+    /// Replay a file written by `export-consensus-dag` through the consensus commit rule, and
+    /// compare the resulting commits and leader schedule scores with the exported ones.
+    #[command(name = "replay-consensus-dag")]
+    ReplayConsensusDag {
+        #[arg(long = "input")]
+        input: PathBuf,
+        /// Print every replayed commit
+        #[arg(long = "verbose")]
+        verbose: bool,
+    },
+
+    /// Render a file written by `export-consensus-dag`, annotated with the leaders, skipped
+    /// leaders, committed sub-dags and missing ancestors found by replaying it. The DAG of a
+    /// running validator is rendered by its `/consensus-dag` admin endpoint instead.
+    #[command(name = "visualize-consensus-dag")]
+    VisualizeConsensusDag {
+        #[arg(long = "input")]
+        input: PathBuf,
+        /// Defaults to the first exported round
+        #[arg(long = "start-round")]
+        start_round: Option<u32>,
+        /// Defaults to the last exported round
+        #[arg(long = "end-round")]
+        end_round: Option<u32>,
+        #[arg(long = "format", value_enum, default_value = "dag")]
+        format: DagFormat,
+        /// Defaults to stdout
+        #[arg(long = "output")]
+        output: Option<PathBuf>,
+    },
+
     /// Inspect if a specific object is or all gas objects owned by an address are locked by validators
     #[command(name = "locked-object")]
     LockedObject {
@@ -143,6 +212,20 @@ pub enum ToolCommand {
         #[command(subcommand)]
         cmd: Option<DbToolCommand>,
     },
+    /// Report per-table storage statistics and recommended pruning settings of a running node,
+    /// from its admin interface. Unlike `db-tool`, it does not open the database, so it takes
+    /// no `--db-path`.
+    #[command(name = "storage-stats")]
+    StorageStats {
+        #[command(flatten)]
+        options: StorageStatsOptions,
+    },
+    /// Check a Display template for errors and render it against a sample value, offline.
+    #[command(name = "display-template")]
+    DisplayTemplate {
+        #[command(flatten)]
+        args: DisplayTemplateArgs,
+    },
     /// Download all packages to the local filesystem from a GraphQL service. Each package gets its
     /// own sub-directory, named for its ID on chain and version containing two metadata files
     /// (linkage.json and origins.json), a file containing the overall object and a file for every
@@ -277,6 +360,11 @@ pub enum ToolCommand {
         /// Defaults to 3 retries. Set to 0 to disable retries.
         #[clap(long = "max-retries", default_value = "3")]
         max_retries: usize,
+
+        /// Resume an interrupted download into the same `--path`, skipping the files it
+        /// already completed.
+        #[clap(long = "resume")]
+        resume: bool,
     },
 
     // Restore from formal (slim, DB agnostic) snapshot. Note that this is only supported
@@ -343,6 +431,27 @@ pub enum ToolCommand {
         /// Defaults to 3 retries. Set to 0 to disable retries.
         #[clap(long = "max-retries", default_value = "3")]
         max_retries: usize,
+
+        /// Keep downloaded object files in the staging directory until the restore completes,
+        /// so that an interrupted restore can be continued with `--resume`. This needs free disk
+        /// space for a second copy of the snapshot's object files, on top of the restored db.
+        #[clap(long = "resumable")]
+        resumable: bool,
+
+        /// Resume an interrupted restore into the same `--path`. Snapshot files left in the
+        /// staging directory by the previous run are checked against the snapshot MANIFEST and
+        /// only the missing or corrupt ones are downloaded again. Object files are only staged
+        /// with `--resumable`, or when resuming. Restored objects are not checked against the
+        /// root state hash as they are restored: the check runs once all of them are, and the
+        /// restored db must be discarded if it fails.
+        #[clap(long = "resume")]
+        resume: bool,
+
+        /// URL of the checkpoint store to sync checkpoint summaries from. Defaults to the
+        /// checkpoint bucket of `--network`. A `file://` URL reads a local mirror, which together
+        /// with `--snapshot-bucket-type file` restores a node without network access.
+        #[clap(long = "ingestion-url")]
+        ingestion_url: Option<String>,
     },
 
     #[clap(name = "replay")]
@@ -372,6 +481,25 @@ pub enum ToolCommand {
     },
 }
 
+/// Renders Graphviz source to SVG with the `dot` binary.
+fn render_svg(dot: &str) -> Result<Vec<u8>> {
+    let mut child = std::process::Command::new("dot")
+        .arg("-Tsvg")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run Graphviz `dot`, is it installed? {e}"))?;
+    std::io::Write::write_all(child.stdin.as_mut().unwrap(), dot.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Graphviz `dot` failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(output.stdout)
+}
+
 async fn check_locked_object(
     myso_client: &Client,
     committee: Arc<BTreeMap<AuthorityPublicKeyBytes, u64>>,
@@ -468,6 +596,107 @@ impl ToolCommand {
                     }
                 }
             }
+            ToolCommand::ExportConsensusDag {
+                db_path,
+                start_round,
+                end_round,
+                stakes,
+                protocol_version,
+                epoch_start_timestamp_ms,
+                output,
+            } => {
+                let rocks_db_store = RocksDBStore::new(&db_path);
+                let mut export = DagExport::from_store(
+                    &rocks_db_store,
+                    stakes,
+                    protocol_version,
+                    start_round..=end_round,
+                )?;
+                export.epoch_start_timestamp_ms = epoch_start_timestamp_ms.unwrap_or_default();
+                std::fs::write(&output, export.to_bytes()?)?;
+                println!(
+                    "Exported {} blocks and {} commits of epoch {} to {}",
+                    export.num_blocks(),
+                    export.num_commits(),
+                    export.epoch,
+                    output.display()
+                );
+                match &export.anchor {
+                    Some((commit_ref, _)) => {
+                        println!("Replay will recover from commit {commit_ref}")
+                    }
+                    None => println!("Replay will start from genesis"),
+                }
+            }
+            ToolCommand::ReplayConsensusDag { input, verbose } => {
+                let export = DagExport::from_bytes(&std::fs::read(&input)?)?;
+                if export.version != DAG_EXPORT_VERSION {
+                    anyhow::bail!(
+                        "Unsupported export version {}, expected {DAG_EXPORT_VERSION}",
+                        export.version
+                    );
+                }
+                let report = DagReplayer::new(export).replay()?;
+
+                if let Some(anchor) = report.anchor {
+                    println!("Recovered from commit {anchor}");
+                }
+                if verbose {
+                    for commit in &report.commits {
+                        println!("{commit}");
+                    }
+                }
+                println!(
+                    "Replayed {} commits, {} exported commits not reached",
+                    report.commits.len(),
+                    report.unreplayed_commits
+                );
+                if let Some(divergence) = &report.commit_divergence {
+                    println!(
+                        "Commit {} diverged: expected {:?}, replayed {:?}",
+                        divergence.index, divergence.expected, divergence.actual
+                    );
+                }
+                for divergence in &report.scores_divergences {
+                    println!(
+                        "Reputation scores at {} diverged: expected {:?}, replayed {:?}",
+                        divergence.commit_ref, divergence.expected, divergence.actual
+                    );
+                }
+                if !report.is_consistent() {
+                    anyhow::bail!("Replay diverged from the exported commits");
+                }
+                println!("Replay matches the exported commits");
+            }
+            ToolCommand::VisualizeConsensusDag {
+                input,
+                start_round,
+                end_round,
+                format,
+                output,
+            } => {
+                let export = DagExport::from_bytes(&std::fs::read(&input)?)?;
+                if export.version != DAG_EXPORT_VERSION {
+                    anyhow::bail!(
+                        "Unsupported export version {}, expected {DAG_EXPORT_VERSION}",
+                        export.version
+                    );
+                }
+                let rounds = start_round.unwrap_or(export.start_round)
+                    ..=end_round.unwrap_or(export.end_round);
+                let report = DagReplayer::new(export.clone()).replay()?;
+                let visualizer = DagVisualizer::from_replay(&export, &report, rounds)?;
+
+                let rendered = match format {
+                    DagFormat::Dag => visualizer.to_dag_string().into_bytes(),
+                    DagFormat::Dot => visualizer.to_dot().into_bytes(),
+                    DagFormat::Svg => render_svg(&visualizer.to_dot())?,
+                };
+                match output {
+                    Some(output) => std::fs::write(output, rendered)?,
+                    None => std::io::Write::write_all(&mut std::io::stdout(), &rendered)?,
+                }
+            }
             ToolCommand::LockedObject {
                 id,
                 fullnode_rpc_url,
@@ -563,6 +792,8 @@ impl ToolCommand {
                     None => print_db_all_tables(path)?,
                 }
             }
+            ToolCommand::StorageStats { options } => print_storage_stats(options).await?,
+            ToolCommand::DisplayTemplate { args } => display_template(args).await?,
             ToolCommand::DumpPackages {
                 rpc_url,
                 output_dir,
@@ -659,6 +890,9 @@ impl ToolCommand {
                 latest,
                 verbose,
                 max_retries,
+                resumable,
+                resume,
+                ingestion_url,
             } => {
                 if !verbose {
                     tracing_handle
@@ -755,11 +989,14 @@ impl ToolCommand {
                     }
                 };
 
-                let ingestion_url = match network {
-                    Chain::Mainnet => "https://checkpoints.mainnet.mysocial.network",
-                    Chain::Testnet => "https://checkpoints.testnet.mysocial.network",
-                    _ => panic!("Cannot generate default ingestion url for unknown network"),
-                };
+                let ingestion_url = ingestion_url.unwrap_or_else(|| {
+                    match network {
+                        Chain::Mainnet => "https://checkpoints.mainnet.mysocial.network",
+                        Chain::Testnet => "https://checkpoints.testnet.mysocial.network",
+                        _ => panic!("Cannot generate default ingestion url for unknown network"),
+                    }
+                    .to_string()
+                });
 
                 let latest_available_epoch =
                     latest.then_some(get_latest_available_epoch(&snapshot_store_config).await?);
@@ -782,11 +1019,13 @@ impl ToolCommand {
                     epoch_to_download,
                     &genesis,
                     snapshot_store_config,
-                    ingestion_url,
+                    &ingestion_url,
                     num_parallel_downloads,
                     network,
                     verify,
                     max_retries,
+                    resumable,
+                    resume,
                 )
                 .await?;
             }
@@ -803,6 +1042,7 @@ impl ToolCommand {
                 latest,
                 verbose,
                 max_retries,
+                resume,
             } => {
                 if no_sign_request {
                     anyhow::bail!(
@@ -943,6 +1183,7 @@ impl ToolCommand {
                     skip_indexes,
                     num_parallel_downloads,
                     max_retries,
+                    resume,
                 )
                 .await?;
             }
@@ -0,0 +1,443 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline tooling for Display templates: checks a template for errors, reports how much of the
+//! parser's limits it consumes, and renders it against a sample value, without needing to publish
+//! the template on-chain first.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use clap::*;
+use indexmap::IndexMap;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::annotated_value::MoveTypeLayout;
+use myso_display::v1;
+use myso_display::v2::{Display, Format, FormatError, Interpreter, Limits, OwnedSlice, Store};
+use myso_json::MySoJsonValue;
+use myso_types::collection_types::{Entry, VecMap};
+
+#[derive(Parser, Clone)]
+pub struct DisplayTemplateArgs {
+    /// Path to a JSON object mapping each Display field name to its format string.
+    #[arg(long)]
+    template: PathBuf,
+
+    /// Path to the type layout of the value being displayed. The layout is read as BCS if the file
+    /// has a `.bcs` extension, and as JSON otherwise.
+    #[arg(long)]
+    layout: Option<PathBuf>,
+
+    /// Path to a file containing the BCS representation of a sample value to render the template
+    /// against.
+    #[arg(long, requires = "layout", conflicts_with = "json")]
+    bcs: Option<PathBuf>,
+
+    /// Path to a file containing the JSON representation of a sample value to render the template
+    /// against.
+    #[arg(long, requires = "layout")]
+    json: Option<PathBuf>,
+
+    /// Also render the template with the v1 Display implementation, and report the fields whose
+    /// output differs between v1 and v2.
+    #[arg(long, requires = "layout")]
+    compare_v1: bool,
+
+    /// Maximum depth of nested expressions in a single format string.
+    #[arg(long, default_value_t = Limits::default().max_depth)]
+    max_depth: usize,
+
+    /// Maximum number of AST nodes across all format strings in the template.
+    #[arg(long, default_value_t = Limits::default().max_nodes)]
+    max_nodes: usize,
+
+    /// Maximum number of object loads across all format strings in the template.
+    #[arg(long, default_value_t = Limits::default().max_loads)]
+    max_loads: usize,
+
+    /// Maximum depth of Move values output by the template.
+    #[arg(long, default_value_t = 32)]
+    max_output_depth: usize,
+
+    /// Maximum size in bytes of the template's output.
+    #[arg(long, default_value_t = 1024 * 1024)]
+    max_output_size: usize,
+}
+
+/// A sample value to render a template against: its BCS representation and its type layout.
+struct Sample {
+    bytes: Vec<u8>,
+    layout: MoveTypeLayout,
+}
+
+/// A store for use offline, where the only value available is the sample value. It does not
+/// contain any objects, but records the IDs of objects that the template tried to load.
+#[derive(Clone, Default)]
+struct OfflineStore {
+    requested: Arc<Mutex<BTreeSet<AccountAddress>>>,
+}
+
+#[async_trait]
+impl Store for OfflineStore {
+    async fn object(&self, id: AccountAddress) -> anyhow::Result<Option<OwnedSlice>> {
+        self.requested.lock().unwrap().insert(id);
+        Ok(None)
+    }
+}
+
+/// Check the Display template described by `args` for errors, and render it against a sample
+/// value, if one is provided. Fails if the template contains any errors.
+pub async fn display_template(args: DisplayTemplateArgs) -> Result<()> {
+    let template = fs::read_to_string(&args.template)
+        .with_context(|| format!("Failed to read template from {}", args.template.display()))?;
+
+    let template: IndexMap<String, String> =
+        serde_json::from_str(&template).context("Failed to parse template")?;
+
+    let limits = Limits {
+        max_depth: args.max_depth,
+        max_nodes: args.max_nodes,
+        max_loads: args.max_loads,
+    };
+
+    // Parse each format string on its own first, so that errors can be reported against the
+    // format string they came from.
+    let mut errors = 0;
+    for (name, value) in &template {
+        for (role, src) in [("name", name), ("value", value)] {
+            if let Err(e) = Format::parse(limits.clone(), src) {
+                report(name, role, src, &e);
+                errors += 1;
+            }
+        }
+    }
+
+    let display = Display::parse(
+        limits.clone(),
+        template.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+    )
+    .context("Template exceeds limits")?;
+
+    let usage = display.usage();
+    println!("Limits usage:");
+    println!("  depth: {}/{}", usage.depth, limits.max_depth);
+    println!("  nodes: {}/{}", usage.nodes, limits.max_nodes);
+    println!("  loads: {}/{}", usage.loads, limits.max_loads);
+
+    let sample = match (&args.layout, &args.bcs, &args.json) {
+        (Some(layout), Some(bcs), _) => Some(Sample {
+            bytes: fs::read(bcs)
+                .with_context(|| format!("Failed to read sample from {}", bcs.display()))?,
+            layout: read_layout(layout)?,
+        }),
+
+        (Some(layout), None, Some(json)) => {
+            let layout = read_layout(layout)?;
+            let json = fs::read_to_string(json)
+                .with_context(|| format!("Failed to read sample from {}", json.display()))?;
+
+            let json = MySoJsonValue::new(
+                serde_json::from_str(&json).context("Failed to parse sample as JSON")?,
+            )?;
+
+            let bytes = json
+                .to_bcs_bytes(&layout)
+                .context("Failed to convert sample to BCS")?;
+
+            Some(Sample { bytes, layout })
+        }
+
+        (_, _, _) => None,
+    };
+
+    let Some(sample) = sample else {
+        if errors > 0 {
+            bail!("Found {errors} error(s) in Display template");
+        }
+
+        return Ok(());
+    };
+
+    let store = OfflineStore::default();
+    let root = OwnedSlice {
+        bytes: sample.bytes.clone(),
+        layout: sample.layout.clone(),
+    };
+
+    let interpreter = Interpreter::new(root, store.clone());
+    let output = display
+        .display(&interpreter, args.max_output_depth, args.max_output_size)
+        .await
+        .context("Failed to render template")?;
+
+    // The output has an entry for each field of the template, in the same order, but keyed by
+    // the field's rendered name, which may differ from its name in the template.
+    println!();
+    println!("Output:");
+    for ((field, src), (name, value)) in template.iter().zip(&output) {
+        match value {
+            Ok(json) => println!("  {name}: {}", format_value(json)),
+            Err(e) => {
+                println!("  {name}: (error)");
+
+                // Parse errors have already been reported.
+                if Format::parse(limits.clone(), src).is_ok() {
+                    report(field, "value", src, e);
+                    errors += 1;
+                }
+            }
+        }
+    }
+
+    let requested = store.requested.lock().unwrap().clone();
+    if !requested.is_empty() {
+        println!();
+        println!("Objects requested (not available offline):");
+        for id in requested {
+            println!("  {}", id.to_canonical_display(true));
+        }
+    }
+
+    if args.compare_v1 {
+        compare_v1(&template, &output, &sample, &args)?;
+    }
+
+    if errors > 0 {
+        bail!("Found {errors} error(s) in Display template");
+    }
+
+    Ok(())
+}
+
+/// Render `template` using the v1 Display implementation, and compare its output with the `v2`
+/// output.
+fn compare_v1(
+    template: &IndexMap<String, String>,
+    v2: &IndexMap<String, Result<serde_json::Value, FormatError>>,
+    sample: &Sample,
+    args: &DisplayTemplateArgs,
+) -> Result<()> {
+    let fields = VecMap {
+        contents: template
+            .iter()
+            .map(|(key, value)| Entry {
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect(),
+    };
+
+    println!();
+    println!("Comparison with v1:");
+
+    let v1 = match v1::Format::parse(args.max_depth, &fields) {
+        Ok(format) => format.display(args.max_output_size, &sample.bytes, &sample.layout),
+        Err(e) => Err(e),
+    };
+
+    let v1 = match v1 {
+        Ok(v1) => v1,
+        Err(e) => {
+            println!("  v1 failed: {e:#}");
+            return Ok(());
+        }
+    };
+
+    // v1 does not render field names, so its output is keyed by the template's names, while v2's
+    // output follows the template's order.
+    let mut differences = 0;
+    for (name, (_, value)) in template.keys().zip(v2) {
+        let old = match v1.get(name) {
+            Some(Ok(s)) => format!("{s:?}"),
+            Some(Err(e)) => format!("(error: {e:#})"),
+            None => "(missing)".to_owned(),
+        };
+
+        let new = match value {
+            Ok(json) => format_value(json),
+            Err(e) => format!("(error: {e})"),
+        };
+
+        if old != new {
+            differences += 1;
+            println!("  {name}:");
+            println!("    v1: {old}");
+            println!("    v2: {new}");
+        }
+    }
+
+    if differences == 0 {
+        println!("  No differences");
+    }
+
+    Ok(())
+}
+
+/// Read a type layout from `path`, as BCS if it has a `.bcs` extension, and as JSON otherwise.
+fn read_layout(path: &Path) -> Result<MoveTypeLayout> {
+    let bytes =
+        fs::read(path).with_context(|| format!("Failed to read layout from {}", path.display()))?;
+
+    if path.extension().is_some_and(|ext| ext == "bcs") {
+        bcs::from_bytes(&bytes).context("Failed to deserialize layout from BCS")
+    } else {
+        serde_json::from_slice(&bytes).context("Failed to deserialize layout from JSON")
+    }
+}
+
+/// Format a value output by the template: strings are quoted, and a missing value is called out.
+fn format_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => format!("{s:?}"),
+        serde_json::Value::Null => "(no value)".to_owned(),
+        json => json.to_string(),
+    }
+}
+
+/// Report `error`, which was found in the `role` (name or value) of Display field `field`, whose
+/// source is `src`.
+fn report(field: &str, role: &str, src: &str, error: &FormatError) {
+    print!("{}", error_report(field, role, src, error));
+}
+
+/// Describe `error`, which was found in the `role` (name or value) of Display field `field`, whose
+/// source is `src`. If the error can be attributed to a location in `src`, the line containing
+/// that location is included, with the location underlined.
+fn error_report(field: &str, role: &str, src: &str, error: &FormatError) -> String {
+    let mut report = format!("error: in {role} of field {field:?}: {error}\n");
+
+    let span = match error {
+        FormatError::UnexpectedEos { .. } => Some(src.len()..src.len()),
+        error => error.span(),
+    };
+
+    let Some(span) = span else {
+        return report;
+    };
+
+    let start = span.start.min(src.len());
+    let (Some(before), Some(after)) = (src.get(..start), src.get(start..)) else {
+        return report;
+    };
+
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line_end = after.find('\n').map_or(src.len(), |i| start + i);
+    let end = span.end.clamp(start, line_end);
+
+    let indent = src[line_start..start].chars().count();
+    let width = src.get(start..end).map_or(1, |s| s.chars().count().max(1));
+
+    report.push_str(&format!("  | {}\n", &src[line_start..line_end]));
+    report.push_str(&format!(
+        "  | {}{}\n",
+        " ".repeat(indent),
+        "^".repeat(width)
+    ));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::annotated_value::{MoveFieldLayout, MoveStructLayout};
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::StructTag;
+    use std::str::FromStr;
+
+    fn parse_error(src: &str) -> FormatError {
+        Format::parse(Limits::default(), src).unwrap_err()
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(&serde_json::json!("a \"b\"")), r#""a \"b\"""#);
+        assert_eq!(format_value(&serde_json::Value::Null), "(no value)");
+        assert_eq!(format_value(&serde_json::json!(42)), "42");
+        assert_eq!(format_value(&serde_json::json!({"a": [1]})), r#"{"a":[1]}"#);
+    }
+
+    #[test]
+    fn test_error_report_end_of_string() {
+        let src = "hello {name";
+        let error = parse_error(src);
+        let report = error_report("title", "value", src, &error);
+
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(lines.len(), 3, "{report}");
+        assert!(lines[0].starts_with(r#"error: in value of field "title": "#));
+        assert_eq!(lines[1], "  | hello {name");
+        assert_eq!(lines[2], format!("  | {}^", " ".repeat(src.len())));
+    }
+
+    #[test]
+    fn test_error_report_underlines_span_on_its_line() {
+        let src = "first line\n{a.} second";
+        let error = parse_error(src);
+        let span = error.span().expect("error should have a span");
+        let report = error_report("f", "name", src, &error);
+
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(lines.len(), 3, "{report}");
+        assert!(lines[0].starts_with(r#"error: in name of field "f": "#));
+        // Only the line containing the error is printed, and the caret is aligned to it.
+        assert_eq!(lines[1], "  | {a.} second");
+        let line_start = src.find('\n').unwrap() + 1;
+        let caret = lines[2].strip_prefix("  | ").unwrap();
+        assert_eq!(caret.find('^'), Some(span.start - line_start));
+        assert!(caret.trim_start().chars().all(|c| c == '^'));
+    }
+
+    fn write(dir: &Path, name: &str, contents: &[u8]) -> String {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path.to_str().unwrap().to_owned()
+    }
+
+    fn args(dir: &Path, template: &str) -> DisplayTemplateArgs {
+        let layout = MoveTypeLayout::Struct(Box::new(MoveStructLayout::new(
+            StructTag::from_str("0x1::m::S").unwrap(),
+            vec![MoveFieldLayout::new(
+                Identifier::new("n").unwrap(),
+                MoveTypeLayout::U64,
+            )],
+        )));
+
+        let template = write(dir, "template.json", template.as_bytes());
+        let layout = write(dir, "layout.bcs", &bcs::to_bytes(&layout).unwrap());
+        let sample = write(dir, "sample.bcs", &bcs::to_bytes(&42u64).unwrap());
+        DisplayTemplateArgs::try_parse_from([
+            "display-template",
+            "--template",
+            &template,
+            "--layout",
+            &layout,
+            "--bcs",
+            &sample,
+        ])
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_display_template() {
+        let dir = tempfile::tempdir().unwrap();
+        display_template(args(dir.path(), r#"{"name": "Number {n}"}"#))
+            .await
+            .unwrap();
+
+        let err = display_template(args(dir.path(), r#"{"name": "Number {n", "ok": "{n}"}"#))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Found 1 error(s) in Display template");
+
+        // Errors in fields with a templated name are only reported once.
+        let err = display_template(args(dir.path(), r#"{"name_{n}": "Number {n"}"#))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Found 1 error(s) in Display template");
+    }
+}
@@ -15,6 +15,7 @@ use myso_data_ingestion_core::{CheckpointReader, create_remote_store_client, end
 use myso_network::default_mysten_network_config;
 use myso_protocol_config::Chain;
 use myso_rpc_api::Client;
+use myso_storage::compute_sha3_checksum;
 use myso_storage::object_store::http::HttpDownloaderBuilder;
 use myso_storage::object_store::util::MANIFEST_FILENAME;
 use myso_storage::object_store::util::Manifest;
@@ -25,7 +26,7 @@ use myso_types::global_state_hash::GlobalStateHash;
 use myso_types::messages_grpc::LayoutGenerationOption;
 use myso_types::multiaddr::Multiaddr;
 use myso_types::{base_types::*, object::Owner};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
@@ -71,7 +72,9 @@ use typed_store::DBMetrics;
 pub mod commands;
 #[cfg(not(tidehunter))]
 pub mod db_tool;
+pub mod display;
 mod formal_snapshot_util;
+pub mod storage_stats;
 
 async fn fetch_checkpoint_with_retry(
     client: &dyn object_store::ObjectStore,
@@ -819,6 +822,8 @@ pub async fn download_formal_snapshot(
     network: Chain,
     verify: SnapshotVerifyMode,
     max_retries: usize,
+    resumable: bool,
+    resume: bool,
 ) -> Result<(), anyhow::Error> {
     let m = MultiProgress::new();
     let msg = format!(
@@ -898,11 +903,16 @@ pub async fn download_formal_snapshot(
 
     let (_abort_handle, abort_registration) = AbortHandle::new_pair();
     let perpetual_db_clone = perpetual_db.clone();
+    // Snapshot files are staged here until the restore completes, so that an interrupted restore
+    // can be resumed. Object files are as large as the snapshot itself, so they are only staged
+    // when asked to, and there is no point in staging object files read from a local mirror.
     let snapshot_dir = path.parent().unwrap().join("snapshot");
-    if snapshot_dir.exists() {
+    if snapshot_dir.exists() && !resume {
         fs::remove_dir_all(snapshot_dir.clone())?;
     }
     let snapshot_dir_clone = snapshot_dir.clone();
+    let stage_object_files =
+        (resumable || resume) && snapshot_store_config.object_store != Some(ObjectStoreType::File);
 
     // TODO if verify is false, we should skip generating these and
     // not pass in a channel to the reader
@@ -921,8 +931,9 @@ pub async fn download_formal_snapshot(
             &local_store_config,
             NonZeroUsize::new(num_parallel_downloads).unwrap(),
             m_clone,
-            false, // skip_reset_local_store
+            resume, // skip_reset_local_store
             max_retries,
+            stage_object_files,
         )
         .await
         .unwrap_or_else(|err| panic!("Failed to create reader: {}", err));
@@ -969,7 +980,9 @@ pub async fn download_formal_snapshot(
         .get_highest_verified_checkpoint()?
         .expect("Expected nonempty checkpoint store");
 
-    // Perform snapshot state verification
+    // Perform snapshot state verification. The root state hash is only complete once every object
+    // has been restored, so a mismatch is detected after the fact and the restored db must be
+    // discarded.
     if verify != SnapshotVerifyMode::None {
         assert_eq!(
             last_checkpoint.epoch(),
@@ -994,12 +1007,15 @@ pub async fn download_formal_snapshot(
         match commitment {
             CheckpointCommitment::ECMHLiveObjectSetDigest(consensus_digest) => {
                 let local_digest: ECMHLiveObjectSetDigest = root_global_state_hash.digest().into();
-                assert_eq!(
-                    *consensus_digest, local_digest,
-                    "End of epoch {} root state digest {} does not match \
-                    local root state hash {} computed from snapshot data",
-                    epoch, consensus_digest.digest, local_digest.digest,
-                );
+                if *consensus_digest != local_digest {
+                    return Err(anyhow!(
+                        "End of epoch {} root state digest {} does not match \
+                        local root state hash {} computed from snapshot data",
+                        epoch,
+                        consensus_digest.digest,
+                        local_digest.digest,
+                    ));
+                }
                 let progress_bar = m.add(
                     ProgressBar::new(1).with_style(
                         ProgressStyle::with_template(
@@ -1189,6 +1205,7 @@ pub async fn download_db_snapshot(
     skip_indexes: bool,
     num_parallel_downloads: usize,
     max_retries: usize,
+    resume: bool,
 ) -> Result<(), anyhow::Error> {
     let remote_store = if snapshot_store_config.no_sign_request {
         snapshot_store_config.make_http()?
@@ -1226,6 +1243,32 @@ pub async fn download_db_snapshot(
     if !skip_indexes {
         files.extend(epoch_manifest.filter_by_prefix("indexes").lines)
     }
+
+    // Files are recorded in the progress file along with their checksum as they complete, so that
+    // an interrupted download can be resumed without downloading them again. A staged file is only
+    // skipped if it still matches the recorded checksum.
+    let progress_path = path.join(format!("epoch_{}.download_progress", epoch));
+    if resume && progress_path.exists() {
+        let downloaded: HashMap<String, String> = fs::read_to_string(&progress_path)?
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .map(|(file, checksum)| (file.to_string(), checksum.to_string()))
+            .collect();
+        files.retain(|file| {
+            let file_path = format!("epoch_{}/{}", epoch, file);
+            let Some(checksum) = downloaded.get(&file_path) else {
+                return true;
+            };
+            match compute_sha3_checksum(&path.join(&file_path)) {
+                Ok(local) => hex::encode(local) != *checksum,
+                Err(_) => true,
+            }
+        });
+    } else {
+        fs::create_dir_all(path)?;
+        fs::write(&progress_path, "")?;
+    }
+    let mut progress_file = fs::OpenOptions::new().append(true).open(&progress_path)?;
     let local_store = ObjectStoreConfig {
         object_store: Some(ObjectStoreType::File),
         directory: Some(path.to_path_buf()),
@@ -1234,6 +1277,7 @@ pub async fn download_db_snapshot(
     .make()?;
     let m = MultiProgress::new();
     let path = path.to_path_buf();
+    let local_dir = path.clone();
     let snapshot_handle = tokio::spawn(async move {
         let progress_bar = m.add(
             ProgressBar::new(files.len() as u64).with_style(
@@ -1250,6 +1294,7 @@ pub async fn download_db_snapshot(
                 let local_store = local_store.clone();
                 let remote_store = remote_store.clone();
                 let counter_cloned = file_counter.clone();
+                let local_dir = local_dir.clone();
                 async move {
                     counter_cloned.fetch_add(1, Ordering::Relaxed);
                     let file_path = get_path(format!("epoch_{}/{}", epoch, file).as_str());
@@ -1283,12 +1328,25 @@ pub async fn download_db_snapshot(
                         }
                     }
 
-                    Ok::<::object_store::path::Path, anyhow::Error>(file_path.clone())
+                    let local_path = local_dir.join(file_path.as_ref());
+                    let checksum =
+                        tokio::task::spawn_blocking(move || compute_sha3_checksum(&local_path))
+                            .await??;
+                    Ok::<(::object_store::path::Path, [u8; 32]), anyhow::Error>((
+                        file_path.clone(),
+                        checksum,
+                    ))
                 }
             })
             .boxed()
             .buffer_unordered(num_parallel_downloads)
-            .try_for_each(|path| {
+            .try_for_each(|(path, checksum)| {
+                if let Err(e) = io::Write::write_all(
+                    &mut progress_file,
+                    format!("{path} {}\n", hex::encode(checksum)).as_bytes(),
+                ) {
+                    return futures::future::ready(Err(e.into()));
+                }
                 file_counter.fetch_sub(1, Ordering::Relaxed);
                 cloned_progress_bar.inc(1);
                 cloned_progress_bar.set_message(format!(
@@ -1319,5 +1377,6 @@ pub async fn download_db_snapshot(
     if epochs_dir.exists() {
         fs::remove_dir_all(&epochs_dir)?;
     }
+    fs::remove_file(&progress_path)?;
     Ok(())
 }
@@ -0,0 +1,93 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports the storage statistics of a running node, read from its admin interface, so no
+//! database path is needed and the node's database is not opened.
+
+use anyhow::bail;
+use clap::Parser;
+use comfy_table::{Cell, ContentArrangement, Row, Table};
+use myso_core::authority::storage_stats::StorageStats;
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct StorageStatsOptions {
+    /// Address of the admin interface of the node
+    #[arg(long, default_value = "http://127.0.0.1:1337")]
+    admin_url: String,
+    /// Print the raw JSON report
+    #[arg(long)]
+    json: bool,
+}
+
+pub async fn print_storage_stats(opt: StorageStatsOptions) -> anyhow::Result<()> {
+    let url = format!("{}/storage-stats", opt.admin_url.trim_end_matches('/'));
+    let response = reqwest::get(&url).await?;
+    let status = response.status();
+    let body = response.text().await?;
+    if !status.is_success() {
+        bail!("Failed to get storage stats from {url}: {status} {body}");
+    }
+    if opt.json {
+        println!("{body}");
+        return Ok(());
+    }
+    let stats: StorageStats = serde_json::from_str(&body)?;
+
+    let mut table = Table::new();
+    table
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(200)
+        .set_header(vec![
+            "table",
+            "sst_size",
+            "blob_size",
+            "live_data_size",
+            "live_keys",
+            "dead_keys",
+            "reclaimable_by_compaction",
+            "reclaimable_by_pruning",
+            "pending_compaction",
+            "write_amp",
+        ]);
+    for (name, t) in &stats.tables {
+        let mut row = Row::new();
+        row.add_cell(Cell::new(name));
+        row.add_cell(Cell::new(t.stats.total_sst_files_size));
+        row.add_cell(Cell::new(t.stats.total_blob_files_size));
+        row.add_cell(Cell::new(t.stats.live_data_size));
+        row.add_cell(Cell::new(t.stats.estimated_live_keys));
+        row.add_cell(Cell::new(t.stats.estimated_dead_keys));
+        row.add_cell(Cell::new(t.reclaimable_by_compaction));
+        row.add_cell(Cell::new(
+            t.reclaimable_by_pruning
+                .map_or_else(|| "-".to_string(), |b| b.to_string()),
+        ));
+        row.add_cell(Cell::new(t.stats.pending_compaction_bytes));
+        row.add_cell(Cell::new(format!("{:.1}", t.stats.write_amplification)));
+        table.add_row(row);
+    }
+    println!("{table}");
+    println!(
+        "Epoch {}, highest executed checkpoint {}, highest pruned checkpoint {}, total size {} bytes",
+        stats.epoch,
+        stats.highest_executed_checkpoint,
+        stats.highest_pruned_checkpoint,
+        stats.total_size()
+    );
+
+    if stats.recommendations.is_empty() {
+        println!("The pruning configuration needs no change");
+        return Ok(());
+    }
+    println!("\nRecommendations:");
+    for recommendation in &stats.recommendations {
+        println!("- {recommendation}");
+    }
+    println!(
+        "\nRecommended authority-store-pruning-config:\n{}",
+        serde_yaml::to_string(&stats.recommended_pruning_config)?
+    );
+    Ok(())
+}
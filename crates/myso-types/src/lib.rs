@@ -90,6 +90,7 @@ pub mod randomness_state;
 pub mod rpc_proto_conversions;
 pub mod signature;
 pub mod signature_verification;
+pub mod social;
 pub mod storage;
 pub mod supported_protocol_versions;
 pub mod test_checkpoint_data_builder;
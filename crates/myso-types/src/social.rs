@@ -0,0 +1,261 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rust representations of the objects that make up the social protocol (the `social_contracts`
+//! package, published at `0x50c1`).
+//!
+//! Fields of type `myso::url::Url` are represented as `String`s, which share their BCS encoding.
+
+use move_core_types::{ident_str, identifier::IdentStr, language_storage::StructTag};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    MYSO_SOCIAL_ADDRESS,
+    balance::Balance,
+    base_types::{MySoAddress, ObjectID},
+    collection_types::Table,
+};
+
+pub const PROFILE_MODULE_NAME: &IdentStr = ident_str!("profile");
+pub const PROFILE_STRUCT_NAME: &IdentStr = ident_str!("Profile");
+pub const USERNAME_REGISTRY_STRUCT_NAME: &IdentStr = ident_str!("UsernameRegistry");
+pub const CREATE_PROFILE_FUNCTION_NAME: &IdentStr = ident_str!("create_profile");
+pub const UPDATE_PROFILE_FUNCTION_NAME: &IdentStr = ident_str!("update_profile");
+
+pub const POST_MODULE_NAME: &IdentStr = ident_str!("post");
+pub const POST_STRUCT_NAME: &IdentStr = ident_str!("Post");
+pub const POST_CONFIG_STRUCT_NAME: &IdentStr = ident_str!("PostConfig");
+pub const CREATE_POST_FUNCTION_NAME: &IdentStr = ident_str!("create_post");
+pub const DELETE_POST_FUNCTION_NAME: &IdentStr = ident_str!("delete_post");
+pub const TIP_POST_FUNCTION_NAME: &IdentStr = ident_str!("tip_post");
+
+pub const PLATFORM_MODULE_NAME: &IdentStr = ident_str!("platform");
+pub const PLATFORM_STRUCT_NAME: &IdentStr = ident_str!("Platform");
+pub const PLATFORM_REGISTRY_STRUCT_NAME: &IdentStr = ident_str!("PlatformRegistry");
+pub const JOIN_PLATFORM_FUNCTION_NAME: &IdentStr = ident_str!("join_platform");
+
+pub const SOCIAL_GRAPH_MODULE_NAME: &IdentStr = ident_str!("social_graph");
+pub const SOCIAL_GRAPH_STRUCT_NAME: &IdentStr = ident_str!("SocialGraph");
+pub const FOLLOW_FUNCTION_NAME: &IdentStr = ident_str!("follow");
+pub const UNFOLLOW_FUNCTION_NAME: &IdentStr = ident_str!("unfollow");
+
+pub const BLOCK_LIST_MODULE_NAME: &IdentStr = ident_str!("block_list");
+pub const BLOCK_LIST_REGISTRY_STRUCT_NAME: &IdentStr = ident_str!("BlockListRegistry");
+pub const BLOCK_WALLET_FUNCTION_NAME: &IdentStr = ident_str!("block_wallet");
+pub const UNBLOCK_WALLET_FUNCTION_NAME: &IdentStr = ident_str!("unblock_wallet");
+
+/// Rust representation of `social_contracts::profile::Profile`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Profile {
+    pub id: ObjectID,
+    pub display_name: Option<String>,
+    pub bio: String,
+    pub profile_picture: Option<String>,
+    pub cover_photo: Option<String>,
+    pub created_at: u64,
+    pub owner: MySoAddress,
+    pub username: String,
+    pub facebook_username: Option<String>,
+    pub github_username: Option<String>,
+    pub instagram_username: Option<String>,
+    pub linkedin_username: Option<String>,
+    pub reddit_username: Option<String>,
+    pub twitch_username: Option<String>,
+    pub x_username: Option<String>,
+    pub min_offer_amount: Option<u64>,
+    pub badges: Vec<ProfileBadge>,
+    pub selected_badge_id: Option<String>,
+    pub min_message_cost: Option<u64>,
+    pub paid_messaging_enabled: bool,
+    pub version: u64,
+}
+
+/// Rust representation of `social_contracts::profile::ProfileBadge`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct ProfileBadge {
+    pub badge_id: String,
+    pub name: String,
+    pub description: String,
+    pub media_url: String,
+    pub icon_url: String,
+    pub platform_id: MySoAddress,
+    pub issued_at: u64,
+    pub issued_by: MySoAddress,
+    pub badge_type: u8,
+}
+
+/// Rust representation of `social_contracts::profile::UsernameRegistry`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct UsernameRegistry {
+    pub id: ObjectID,
+    /// `Table<String, address>` from usernames to profile IDs.
+    pub usernames: Table,
+    /// `Table<address, address>` from owner addresses to profile IDs.
+    pub address_profiles: Table,
+    pub version: u64,
+}
+
+/// Rust representation of `social_contracts::post::Post`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Post {
+    pub id: ObjectID,
+    pub owner: MySoAddress,
+    pub profile_id: MySoAddress,
+    pub platform_id: MySoAddress,
+    pub content: String,
+    pub media: Option<Vec<String>>,
+    pub mentions: Option<Vec<MySoAddress>>,
+    pub metadata_json: Option<String>,
+    pub post_type: String,
+    pub parent_post_id: Option<MySoAddress>,
+    pub created_at: u64,
+    pub reaction_count: u64,
+    pub comment_count: u64,
+    pub repost_count: u64,
+    pub tips_received: u64,
+    pub removed_from_platform: bool,
+    /// `Table<address, String>` from user addresses to their reactions.
+    pub user_reactions: Table,
+    /// `Table<String, u64>` from reactions to the number of times they were used.
+    pub reaction_counts: Table,
+    pub permissions: u8,
+    pub revenue_redirect_to: Option<MySoAddress>,
+    pub revenue_redirect_percentage: Option<u64>,
+    pub poc_badge: Option<PoCBadge>,
+    pub mydata_id: Option<MySoAddress>,
+    pub promotion_id: Option<MySoAddress>,
+    pub enable_flags: u8,
+    pub spot_id: Option<MySoAddress>,
+    pub spt_id: Option<MySoAddress>,
+    pub version: u64,
+}
+
+/// Rust representation of `social_contracts::post::PoCBadge`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct PoCBadge {
+    pub reasoning: Option<String>,
+    pub evidence_urls: Option<Vec<String>>,
+    pub similarity_score: Option<u64>,
+    pub media_type: Option<u8>,
+    pub oracle_address: Option<MySoAddress>,
+    pub analyzed_at: Option<u64>,
+}
+
+/// Rust representation of `social_contracts::platform::Platform`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Platform {
+    pub id: ObjectID,
+    pub name: String,
+    pub tagline: String,
+    pub description: String,
+    pub logo: String,
+    pub developer: MySoAddress,
+    pub terms_of_service: String,
+    pub privacy_policy: String,
+    pub platforms: Vec<String>,
+    pub links: Vec<String>,
+    pub primary_category: String,
+    pub secondary_category: Option<String>,
+    pub status: PlatformStatus,
+    pub release_date: String,
+    pub shutdown_date: Option<String>,
+    pub created_at: u64,
+    pub treasury: Balance,
+    pub wants_dao_governance: bool,
+    pub delegate_count: Option<u64>,
+    pub delegate_term_epochs: Option<u64>,
+    pub proposal_submission_cost: Option<u64>,
+    pub min_on_chain_age_days: Option<u64>,
+    pub max_votes_per_user: Option<u64>,
+    pub quadratic_base_cost: Option<u64>,
+    pub voting_period_epochs: Option<u64>,
+    pub quorum_votes: Option<u64>,
+    pub governance_registry_id: Option<ObjectID>,
+    pub version: u64,
+}
+
+/// Rust representation of `social_contracts::platform::PlatformStatus`.
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq)]
+pub struct PlatformStatus {
+    pub status: u8,
+}
+
+/// Rust representation of `social_contracts::social_graph::SocialGraph`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct SocialGraph {
+    pub id: ObjectID,
+    /// `Table<address, VecSet<address>>` from addresses to the addresses they follow.
+    pub following: Table,
+    /// `Table<address, VecSet<address>>` from addresses to the addresses that follow them.
+    pub followers: Table,
+    pub version: u64,
+}
+
+impl Profile {
+    pub fn type_() -> StructTag {
+        social_type(PROFILE_MODULE_NAME, PROFILE_STRUCT_NAME)
+    }
+
+    /// Is this `StructTag` a `social_contracts::profile::Profile`?
+    pub fn is_profile(tag: &StructTag) -> bool {
+        is_social_type(tag, PROFILE_MODULE_NAME, PROFILE_STRUCT_NAME)
+    }
+}
+
+impl UsernameRegistry {
+    pub fn type_() -> StructTag {
+        social_type(PROFILE_MODULE_NAME, USERNAME_REGISTRY_STRUCT_NAME)
+    }
+}
+
+impl Post {
+    pub fn type_() -> StructTag {
+        social_type(POST_MODULE_NAME, POST_STRUCT_NAME)
+    }
+
+    /// Is this `StructTag` a `social_contracts::post::Post`?
+    pub fn is_post(tag: &StructTag) -> bool {
+        is_social_type(tag, POST_MODULE_NAME, POST_STRUCT_NAME)
+    }
+}
+
+impl Platform {
+    pub fn type_() -> StructTag {
+        social_type(PLATFORM_MODULE_NAME, PLATFORM_STRUCT_NAME)
+    }
+
+    /// Is this `StructTag` a `social_contracts::platform::Platform`?
+    pub fn is_platform(tag: &StructTag) -> bool {
+        is_social_type(tag, PLATFORM_MODULE_NAME, PLATFORM_STRUCT_NAME)
+    }
+}
+
+impl SocialGraph {
+    pub fn type_() -> StructTag {
+        social_type(SOCIAL_GRAPH_MODULE_NAME, SOCIAL_GRAPH_STRUCT_NAME)
+    }
+
+    /// Is this `StructTag` a `social_contracts::social_graph::SocialGraph`?
+    pub fn is_social_graph(tag: &StructTag) -> bool {
+        is_social_type(tag, SOCIAL_GRAPH_MODULE_NAME, SOCIAL_GRAPH_STRUCT_NAME)
+    }
+}
+
+/// The type of the `social_contracts` struct `module::name`, for structs that have no Rust
+/// representation here, like the shared registries.
+pub fn social_type(module: &IdentStr, name: &IdentStr) -> StructTag {
+    StructTag {
+        address: MYSO_SOCIAL_ADDRESS,
+        module: module.to_owned(),
+        name: name.to_owned(),
+        type_params: vec![],
+    }
+}
+
+fn is_social_type(tag: &StructTag, module: &IdentStr, name: &IdentStr) -> bool {
+    tag.address == MYSO_SOCIAL_ADDRESS
+        && tag.module.as_ident_str() == module
+        && tag.name.as_ident_str() == name
+        && tag.type_params.is_empty()
+}
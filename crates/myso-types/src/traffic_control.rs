@@ -4,6 +4,7 @@
 
 use serde::{Deserialize, Serialize, de::Deserializer};
 use serde_with::serde_as;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 // These values set to loosely attempt to limit
@@ -219,6 +220,112 @@ fn default_sketch_tolerance() -> f64 {
     DEFAULT_SKETCH_TOLERANCE
 }
 
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TokenBucketConfig {
+    /// Maximum number of tokens a direct client can accumulate, i.e. the
+    /// largest burst of request cost that is tolerated
+    #[serde(default = "default_client_capacity")]
+    pub client_capacity: u64,
+    #[serde(default = "default_proxied_client_capacity")]
+    pub proxied_client_capacity: u64,
+    /// Tokens added to a direct client's bucket per second
+    #[serde(default = "default_client_refill_rate")]
+    pub client_refill_rate: u64,
+    #[serde(default = "default_proxied_client_refill_rate")]
+    pub proxied_client_refill_rate: u64,
+    /// Cost of a request whose method is not listed in `method_costs`
+    #[serde(default = "default_request_cost")]
+    pub default_cost: u64,
+    /// Cost per RPC method, keyed by json-rpc method name (e.g. `myso_getObject`)
+    /// or validator service method name (e.g. `submit_transaction`)
+    #[serde(default = "default_method_costs")]
+    pub method_costs: BTreeMap<String, u64>,
+    /// If set, a request carrying a transaction is charged one additional token
+    /// for every `gas_budget_unit` of its gas budget. The gas budget is
+    /// currently only known for requests to the json-rpc write API.
+    #[serde(default)]
+    pub gas_budget_unit: Option<u64>,
+    /// Once more than this many clients are tracked, clients whose bucket
+    /// has refilled completely are forgotten
+    #[serde(default = "default_max_tracked_clients")]
+    pub max_tracked_clients: usize,
+}
+
+impl Default for TokenBucketConfig {
+    fn default() -> Self {
+        Self {
+            client_capacity: default_client_capacity(),
+            proxied_client_capacity: default_proxied_client_capacity(),
+            client_refill_rate: default_client_refill_rate(),
+            proxied_client_refill_rate: default_proxied_client_refill_rate(),
+            default_cost: default_request_cost(),
+            method_costs: default_method_costs(),
+            gas_budget_unit: None,
+            max_tracked_clients: default_max_tracked_clients(),
+        }
+    }
+}
+
+fn default_client_capacity() -> u64 {
+    // As with `FreqThresholdConfig`, a direct client may be a fullnode
+    // proxying many well behaved clients, so keep this generous
+    100_000
+}
+
+fn default_proxied_client_capacity() -> u64 {
+    1_000
+}
+
+fn default_client_refill_rate() -> u64 {
+    10_000
+}
+
+fn default_proxied_client_refill_rate() -> u64 {
+    100
+}
+
+fn default_request_cost() -> u64 {
+    1
+}
+
+fn default_method_costs() -> BTreeMap<String, u64> {
+    [
+        ("myso_executeTransactionBlock", 10),
+        ("myso_dryRunTransactionBlock", 20),
+        ("myso_devInspectTransactionBlock", 20),
+        ("myso_multiGetObjects", 5),
+        ("myso_multiGetTransactionBlocks", 5),
+        ("mysox_queryEvents", 10),
+        ("mysox_queryTransactionBlocks", 10),
+        ("submit_transaction", 10),
+    ]
+    .into_iter()
+    .map(|(method, cost)| (method.to_string(), cost))
+    .collect()
+}
+
+fn default_max_tracked_clients() -> usize {
+    100_000
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AccessListConfig {
+    /// File containing one entry per line, either `allow <ip>` or `deny <ip>`.
+    /// Empty lines and lines starting with `#` are ignored.
+    pub path: PathBuf,
+    /// How often the file is checked for changes
+    #[serde(default = "default_access_list_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+}
+
+fn default_access_list_reload_interval_secs() -> u64 {
+    10
+}
+
 // Serializable representation of policy types, used in config
 // in order to easily change in tests or to killswitch
 #[derive(Clone, Serialize, Deserialize, Debug, Default)]
@@ -233,6 +340,23 @@ pub enum PolicyType {
     #[serde(rename = "freq-threshold", alias = "FreqThreshold")]
     FreqThreshold(FreqThresholdConfig),
 
+    /// Charges each tally a cost derived from the request's RPC method and gas
+    /// budget against a per-client token bucket, and blocks clients whose bucket
+    /// runs dry. Buckets refill at a constant rate up to their capacity.
+    #[serde(rename = "token-bucket", alias = "TokenBucket")]
+    TokenBucket(TokenBucketConfig),
+
+    /// Blocks clients on the deny list of a file that is reloaded whenever it
+    /// changes. When combined with other policies, clients on the allow list
+    /// are never blocked by any of them.
+    #[serde(rename = "access-list", alias = "AccessList")]
+    AccessList(AccessListConfig),
+
+    /// Runs every tally through each of the given policies, blocking a client
+    /// if any of them would block it
+    #[serde(rename = "composite", alias = "Composite")]
+    Composite(Vec<PolicyType>),
+
     /* Below this point are test policies, and thus should not be used in production */
     ///
     /// Simple policy that adds connection_ip to blocklist when the same connection_ip
@@ -5,7 +5,8 @@
 use crate::{
     clever_error_rendering::render_clever_error_opt,
     client_ptb::ptb::PTB,
-    displays::Pretty,
+    displays::{Pretty, TransactionPreview},
+    social_commands::SocialCommand,
     upgrade_compatibility::check_compatibility,
     verifier_meter::{AccumulatingMeter, Accumulator},
 };
@@ -449,6 +450,20 @@ pub enum MySoClientCommands {
         processing: TxProcessingArgs,
     },
 
+    /// Simulate an already serialized transaction and preview the balance changes and object
+    /// changes it would make, without signing or executing it.
+    PreviewTx {
+        /// Base64-encoded BCS-serialized TransactionData.
+        tx_bytes: String,
+    },
+
+    /// Create profiles, publish posts, follow, tip and block on the social protocol.
+    #[clap(name = "social")]
+    Social {
+        #[clap(subcommand)]
+        cmd: SocialCommand,
+    },
+
     /// Split a coin object into multiple coins.
     #[clap(group(ArgGroup::new("split").required(true).args(&["amounts", "count"])))]
     SplitCoin {
@@ -669,6 +684,19 @@ pub struct TxProcessingArgs {
     /// private key corresponding to this address is not in keystore.
     #[arg(long, required = false, value_parser)]
     pub sender: Option<MySoAddress>,
+    /// Before signing the transaction, simulate it and preview the balance changes and object
+    /// changes it makes, then ask for confirmation to continue.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "tx_digest",
+            "dry_run",
+            "dev_inspect",
+            "serialize_unsigned_transaction",
+            "serialize_signed_transaction",
+        ],
+    )]
+    pub preview: bool,
 }
 
 #[derive(Args, Debug, Default)]
@@ -1564,6 +1592,19 @@ impl MySoClientCommands {
                 )
                 .await?
             }
+            MySoClientCommands::PreviewTx { tx_bytes } => {
+                let Ok(bytes) = Base64::decode(&tx_bytes) else {
+                    bail!("Invalid Base64 encoding");
+                };
+
+                let Ok(tx_data): Result<TransactionData, _> = bcs::from_bytes(&bytes) else {
+                    bail!("Failed to parse --tx-bytes as TransactionData");
+                };
+
+                let client = context.grpc_client()?;
+                let preview = TransactionPreview::simulate(&client, &tx_data).await?;
+                MySoClientCommandResult::TransactionPreview(preview)
+            }
             MySoClientCommands::Switch { address, env } => {
                 let mut addr = None;
 
@@ -1774,6 +1815,7 @@ impl MySoClientCommands {
                 )
                 .await?
             }
+            MySoClientCommands::Social { cmd } => cmd.execute(context).await?,
             MySoClientCommands::PTB(ptb) => {
                 let _ = context.cache_chain_id().await?;
                 ptb.execute(context).await?;
@@ -2349,6 +2391,9 @@ impl Display for MySoClientCommandResult {
             MySoClientCommandResult::DevInspect(response) => {
                 writeln!(f, "{}", Pretty(response))?;
             }
+            MySoClientCommandResult::TransactionPreview(preview) => {
+                writeln!(f, "{}", Pretty(preview))?;
+            }
         }
         write!(f, "{}", writer.trim_end_matches('\n'))
     }
@@ -2556,6 +2601,7 @@ pub enum MySoClientCommandResult {
     Switch(SwitchResponse),
     SyncClientState,
     TransactionBlock(ExecutedTransaction),
+    TransactionPreview(TransactionPreview),
     VerifyBytecodeMeter {
         success: bool,
         max_package_ticks: Option<u128>,
@@ -2781,6 +2827,17 @@ fn format_balance(
     format!("{whole}.{fractional}{suffix}")
 }
 
+/// Asks the user a yes or no question on stdin, answering no by default.
+fn confirm(question: &str) -> Result<bool, anyhow::Error> {
+    use std::io::Write as _;
+
+    print!("{question} [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
 /// Helper function to reduce code duplication for executing dry run
 pub async fn execute_dry_run(
     context: &mut WalletContext,
@@ -2901,6 +2958,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
         serialize_unsigned_transaction,
         serialize_signed_transaction,
         sender,
+        preview,
     } = processing;
 
     ensure!(
@@ -3007,6 +3065,14 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
     } else if tx_digest {
         Ok(MySoClientCommandResult::ComputeTransactionDigest(tx_data))
     } else {
+        if preview {
+            let preview = TransactionPreview::simulate(&client, &tx_data).await?;
+            println!("{}", Pretty(&preview));
+            if !confirm("Sign this transaction?")? {
+                bail!("Transaction not signed");
+            }
+        }
+
         let mut signatures = vec![
             context
                 .sign_secure(
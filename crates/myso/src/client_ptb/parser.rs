@@ -45,6 +45,7 @@ struct ProgramParsingState {
     tx_digest_set: bool,
     dry_run_set: bool,
     dev_inspect_set: bool,
+    preview_changes_set: bool,
     gas_object_ids: Option<Vec<Spanned<ObjectID>>>,
     gas_budget: Option<Spanned<u64>>,
     gas_price: Option<Spanned<u64>>,
@@ -79,6 +80,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 tx_digest_set: false,
                 dry_run_set: false,
                 dev_inspect_set: false,
+                preview_changes_set: false,
                 gas_object_ids: None,
                 gas_budget: None,
                 gas_price: None,
@@ -131,6 +133,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 L(T::Command, A::TX_DIGEST) => flag!(tx_digest_set),
                 L(T::Command, A::DRY_RUN) => flag!(dry_run_set),
                 L(T::Command, A::DEV_INSPECT) => flag!(dev_inspect_set),
+                L(T::Command, A::PREVIEW_CHANGES) => flag!(preview_changes_set),
                 L(T::Command, A::PREVIEW) => flag!(preview_set),
                 L(T::Command, A::WARN_SHADOWS) => flag!(warn_shadows_set),
                 L(T::Command, A::GAS_COIN) => {
@@ -258,6 +261,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     tx_digest_set: self.state.tx_digest_set,
                     dry_run_set: self.state.dry_run_set,
                     dev_inspect_set: self.state.dev_inspect_set,
+                    preview_changes_set: self.state.preview_changes_set,
                     gas_budget: self.state.gas_budget,
                     gas_price: self.state.gas_price,
                     gas_sponsor: self.state.gas_sponsor,
@@ -118,6 +118,16 @@ impl PTB {
             !program_metadata.serialize_unsigned_set || !program_metadata.serialize_signed_set,
             "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
         );
+        ensure!(
+            !program_metadata.preview_changes_set
+                || !(program_metadata.tx_digest_set
+                    || program_metadata.dry_run_set
+                    || program_metadata.dev_inspect_set
+                    || program_metadata.serialize_unsigned_set
+                    || program_metadata.serialize_signed_set),
+            "Cannot specify --preview-changes with --tx-digest, --dry-run, --dev-inspect or \
+            --serialize-*-transaction."
+        );
 
         if program_metadata.preview_set {
             println!(
@@ -222,6 +232,7 @@ impl PTB {
             serialize_unsigned_transaction: program_metadata.serialize_unsigned_set,
             serialize_signed_transaction: program_metadata.serialize_signed_set,
             sender: program_metadata.sender.map(|x| x.value.into_inner().into()),
+            preview: program_metadata.preview_changes_set,
         };
 
         let gas_payment = client.transaction_builder().input_refs(&gas).await?;
@@ -366,6 +377,11 @@ pub fn ptb_description() -> clap::Command {
             --"dev-inspect"
             "Perform a dev-inspect of the PTB instead of executing it."
         ))
+        .arg(arg!(
+            --"preview-changes"
+            "Before signing the PTB, simulate it and preview the balance and object changes it \
+            makes, then ask for confirmation to continue."
+        ))
         .arg(arg!(
             --"gas-coin" <ID> ...
             "The object ID of the gas coin to use. If not specified, it will try to use the first \
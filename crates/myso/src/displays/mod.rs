@@ -7,5 +7,8 @@ mod ptb_preview;
 mod simulate;
 mod status;
 mod summary;
+mod tx_preview;
+
+pub use tx_preview::{BalanceDelta, ObjectChangeKind, ObjectDiff, ObjectState, TransactionPreview};
 
 pub struct Pretty<'a, T>(pub &'a T);
@@ -0,0 +1,693 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A preview of what a transaction does, computed by simulating it, to be shown before it is
+//! signed: the coin balances it changes, the objects it touches and how their fields change, and
+//! any pattern that deserves a second look, such as giving away a capability object.
+
+use crate::displays::Pretty;
+use anyhow::Context;
+use colored::Colorize;
+use myso_rpc_api::Client;
+use myso_types::{
+    balance_change::derive_balance_changes_2,
+    base_types::{MySoAddress, ObjectID, SequenceNumber},
+    effects::{TransactionEffects, TransactionEffectsAPI},
+    execution_status::ExecutionStatus,
+    full_checkpoint_content::ObjectSet,
+    gas::GasCostSummary,
+    object::{Object, Owner},
+    parse_myso_struct_tag,
+    storage::ObjectKey,
+    transaction::{TransactionData, TransactionDataAPI},
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+};
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{Panel as TablePanel, Style as TableStyle, style::HorizontalLine},
+};
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionPreview {
+    pub sender: MySoAddress,
+    pub status: ExecutionStatus,
+    pub gas_cost: GasCostSummary,
+    pub balance_changes: Vec<BalanceDelta>,
+    pub object_changes: Vec<ObjectDiff>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceDelta {
+    pub owner: MySoAddress,
+    pub coin_type: String,
+    /// Symbol and decimals from the coin metadata, if it could be found.
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub amount: i128,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectDiff {
+    pub object_id: ObjectID,
+    pub object_type: Option<String>,
+    pub kind: ObjectChangeKind,
+    pub before: Option<ObjectState>,
+    pub after: Option<ObjectState>,
+    /// Fields whose value changes, when the contents of the object are known before and after.
+    pub field_changes: Vec<FieldChange>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectState {
+    pub version: SequenceNumber,
+    pub owner: Owner,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ObjectChangeKind {
+    Created,
+    Mutated,
+    /// The object was owned by an address and is now owned by another address or object.
+    TransferredAway,
+    Wrapped,
+    Unwrapped,
+    Deleted,
+}
+
+impl TransactionPreview {
+    /// Simulates `tx_data` and builds its preview, looking up the metadata of every coin whose
+    /// balance changes.
+    pub async fn simulate(client: &Client, tx_data: &TransactionData) -> anyhow::Result<Self> {
+        let response = client
+            .simulate_transaction(tx_data, true)
+            .await
+            .context("Failed to simulate the transaction")?;
+
+        let mut objects = ObjectSet::default();
+        let mut contents = BTreeMap::new();
+        for proto in &response.objects {
+            let object: Object = proto
+                .bcs()
+                .deserialize()
+                .context("Invalid object in the simulation")?;
+            if let Some(json) = &proto.json {
+                contents.insert(
+                    ObjectKey(object.id(), object.version()),
+                    proto_to_json(json),
+                );
+            }
+            objects.insert(object);
+        }
+        let mut preview = Self::new(
+            tx_data.sender(),
+            &response.transaction.effects,
+            &objects,
+            &contents,
+        );
+
+        let mut coins: BTreeMap<String, Option<(String, u8)>> = BTreeMap::new();
+        for change in &mut preview.balance_changes {
+            if !coins.contains_key(&change.coin_type) {
+                let metadata = match parse_myso_struct_tag(&change.coin_type) {
+                    Ok(struct_tag) => client
+                        .get_coin_info(&struct_tag)
+                        .await
+                        .ok()
+                        .and_then(|info| info.metadata)
+                        .map(|metadata| (metadata.symbol().to_string(), metadata.decimals() as u8)),
+                    Err(_) => None,
+                };
+                coins.insert(change.coin_type.clone(), metadata);
+            }
+            if let Some((symbol, decimals)) = &coins[&change.coin_type] {
+                change.symbol = Some(symbol.clone());
+                change.decimals = Some(*decimals);
+            }
+        }
+        Ok(preview)
+    }
+
+    /// Builds the preview of a simulated transaction from its effects and the objects it reads
+    /// and writes. `contents` holds the JSON rendering of these objects, to diff their fields.
+    /// Coin metadata is left out.
+    pub fn new(
+        sender: MySoAddress,
+        effects: &TransactionEffects,
+        objects: &ObjectSet,
+        contents: &BTreeMap<ObjectKey, Value>,
+    ) -> Self {
+        let before: BTreeMap<ObjectID, ObjectState> = effects
+            .old_object_metadata()
+            .into_iter()
+            .map(|((id, version, _), owner)| (id, ObjectState { version, owner }))
+            .collect();
+
+        let mut object_changes = vec![];
+        let mut diff = |id: ObjectID, kind, after: Option<ObjectState>| {
+            let before = before.get(&id).cloned();
+            let key = |state: &Option<ObjectState>| {
+                state.as_ref().map(|state| ObjectKey(id, state.version))
+            };
+            let object = |state| key(state).and_then(|key| objects.get(&key));
+            let object_type = object(&after)
+                .or_else(|| object(&before))
+                .and_then(|object| object.struct_tag())
+                .map(|tag| tag.to_canonical_string(true));
+            let content = |state| key(state).and_then(|key| contents.get(&key));
+            let field_changes = match (content(&before), content(&after)) {
+                (Some(before), Some(after)) => field_changes(before, after),
+                _ => vec![],
+            };
+            object_changes.push(ObjectDiff {
+                object_id: id,
+                object_type,
+                kind,
+                before,
+                after,
+                field_changes,
+            })
+        };
+        for ((id, version, _), owner) in effects.mutated() {
+            let kind = match before.get(&id) {
+                Some(before) if is_transfer(&before.owner, &owner) => {
+                    ObjectChangeKind::TransferredAway
+                }
+                _ => ObjectChangeKind::Mutated,
+            };
+            diff(id, kind, Some(ObjectState { version, owner }));
+        }
+        for ((id, version, _), owner) in effects.created() {
+            diff(
+                id,
+                ObjectChangeKind::Created,
+                Some(ObjectState { version, owner }),
+            );
+        }
+        for ((id, version, _), owner) in effects.unwrapped() {
+            diff(
+                id,
+                ObjectChangeKind::Unwrapped,
+                Some(ObjectState { version, owner }),
+            );
+        }
+        for (id, _, _) in effects.wrapped() {
+            diff(id, ObjectChangeKind::Wrapped, None);
+        }
+        for (id, _, _) in effects.deleted() {
+            diff(id, ObjectChangeKind::Deleted, None);
+        }
+
+        let balance_changes = derive_balance_changes_2(effects, objects)
+            .into_iter()
+            .map(|change| BalanceDelta {
+                owner: change.address,
+                coin_type: change.coin_type.to_canonical_string(true),
+                symbol: None,
+                decimals: None,
+                amount: change.amount,
+            })
+            .collect();
+
+        let status = effects.status().clone();
+        let warnings = warnings(&status, &object_changes);
+        Self {
+            sender,
+            status,
+            gas_cost: effects.gas_cost_summary().clone(),
+            balance_changes,
+            object_changes,
+            warnings,
+        }
+    }
+}
+
+/// Returns the address owning an object directly, if any.
+fn owner_address(owner: &Owner) -> Option<MySoAddress> {
+    match owner {
+        Owner::AddressOwner(address) | Owner::ConsensusAddressOwner { owner: address, .. } => {
+            Some(*address)
+        }
+        _ => None,
+    }
+}
+
+/// Whether an object owned by an address ends up owned by another address or object.
+fn is_transfer(before: &Owner, after: &Owner) -> bool {
+    let Some(from) = owner_address(before) else {
+        return false;
+    };
+    match after {
+        Owner::ObjectOwner(_) => true,
+        after => owner_address(after).is_some_and(|to| to != from),
+    }
+}
+
+/// Whether `object_type` is a capability, which by convention is a struct named `...Cap`, like
+/// `UpgradeCap` or `TreasuryCap<T>`.
+fn is_capability(object_type: &str) -> bool {
+    let name = object_type.split('<').next().unwrap_or(object_type);
+    name.rsplit("::").next().is_some_and(|n| n.ends_with("Cap"))
+}
+
+fn warnings(status: &ExecutionStatus, object_changes: &[ObjectDiff]) -> Vec<String> {
+    let mut warnings = vec![];
+    if status.is_err() {
+        warnings.push(
+            "The transaction fails in simulation, executing it will only charge gas".to_string(),
+        );
+    }
+
+    for change in object_changes {
+        let id = change.object_id;
+        if let Some(object_type) = &change.object_type
+            && is_capability(object_type)
+        {
+            match (change.kind, &change.after) {
+                (ObjectChangeKind::TransferredAway, Some(after)) => warnings.push(format!(
+                    "Capability {object_type} {id} is transferred to {}",
+                    after.owner
+                )),
+                (ObjectChangeKind::Wrapped, _) => warnings.push(format!(
+                    "Capability {object_type} {id} is wrapped into another object"
+                )),
+                (ObjectChangeKind::Deleted, _) => {
+                    warnings.push(format!("Capability {object_type} {id} is deleted"))
+                }
+                _ => (),
+            }
+        }
+
+        if let (Some(before), Some(after)) = (&change.before, &change.after)
+            && owner_address(&before.owner).is_some()
+        {
+            match after.owner {
+                Owner::Shared { .. } => warnings.push(format!(
+                    "Owned object {id} becomes shared, this cannot be undone"
+                )),
+                Owner::Immutable => warnings.push(format!(
+                    "Owned object {id} becomes immutable, this cannot be undone"
+                )),
+                _ => (),
+            }
+        }
+    }
+    warnings
+}
+
+/// The top level fields whose value differs between two JSON renderings of an object.
+fn field_changes(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let (Value::Object(before), Value::Object(after)) = (before, after) else {
+        if before == after {
+            return vec![];
+        }
+        return vec![FieldChange {
+            field: String::new(),
+            before: Some(before.clone()),
+            after: Some(after.clone()),
+        }];
+    };
+    let fields: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    fields
+        .into_iter()
+        .filter(|field| before.get(*field) != after.get(*field))
+        .map(|field| FieldChange {
+            field: field.clone(),
+            before: before.get(field).cloned(),
+            after: after.get(field).cloned(),
+        })
+        .collect()
+}
+
+fn proto_to_json(value: &prost_types::Value) -> Value {
+    use prost_types::value::Kind;
+    match &value.kind {
+        None | Some(Kind::NullValue(_)) => Value::Null,
+        Some(Kind::NumberValue(number)) => {
+            serde_json::Number::from_f64(*number).map_or(Value::Null, Value::Number)
+        }
+        Some(Kind::StringValue(string)) => Value::String(string.clone()),
+        Some(Kind::BoolValue(bool)) => Value::Bool(*bool),
+        Some(Kind::StructValue(fields)) => Value::Object(
+            fields
+                .fields
+                .iter()
+                .map(|(name, value)| (name.clone(), proto_to_json(value)))
+                .collect(),
+        ),
+        Some(Kind::ListValue(list)) => {
+            Value::Array(list.values.iter().map(proto_to_json).collect())
+        }
+    }
+}
+
+/// Formats a signed amount of the smallest unit of a coin with `decimals` decimals, without
+/// losing precision.
+fn format_amount(amount: i128, decimals: u8) -> String {
+    let sign = if amount < 0 { "-" } else { "+" };
+    let value = amount.unsigned_abs();
+    let Some(unit) = 10u128.checked_pow(decimals as u32) else {
+        return format!("{sign}{value}");
+    };
+    let (whole, fraction) = (value / unit, value % unit);
+    if fraction == 0 {
+        format!("{sign}{whole}")
+    } else {
+        let fraction = format!("{fraction:0width$}", width = decimals as usize);
+        format!("{sign}{whole}.{}", fraction.trim_end_matches('0'))
+    }
+}
+
+fn format_state(state: &Option<ObjectState>) -> String {
+    match state {
+        Some(ObjectState { version, owner }) => format!("v{} {owner}", version.value()),
+        None => "-".to_string(),
+    }
+}
+
+fn format_field_changes(changes: &[FieldChange]) -> String {
+    let value = |value: &Option<Value>| match value {
+        Some(value) => value.to_string(),
+        None => "-".to_string(),
+    };
+    changes
+        .iter()
+        .map(|change| {
+            format!(
+                "{}: {} -> {}",
+                change.field,
+                value(&change.before),
+                value(&change.after)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Display for Pretty<'_, TransactionPreview> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Pretty(preview) = self;
+        writeln!(
+            f,
+            "Transaction preview for sender {}, execution status: {}",
+            preview.sender,
+            Pretty(&preview.status)
+        )?;
+
+        if preview.balance_changes.is_empty() {
+            writeln!(f, "╭─────────────────────────────╮")?;
+            writeln!(f, "│ No balance changes          │")?;
+            writeln!(f, "╰─────────────────────────────╯")?;
+        } else {
+            let mut builder = TableBuilder::default();
+            builder.push_record(vec!["owner", "coin", "amount"]);
+            for change in &preview.balance_changes {
+                let amount = match (&change.symbol, change.decimals) {
+                    (Some(symbol), Some(decimals)) => {
+                        format!("{} {symbol}", format_amount(change.amount, decimals))
+                    }
+                    _ => format!("{} (raw)", format_amount(change.amount, 0)),
+                };
+                builder.push_record(vec![
+                    change.owner.to_string(),
+                    change.coin_type.clone(),
+                    amount,
+                ]);
+            }
+            let mut table = builder.build();
+            table.with(TablePanel::header("Balance Changes"));
+            table.with(TableStyle::rounded().horizontals([
+                HorizontalLine::new(1, TableStyle::modern().get_horizontal()),
+                HorizontalLine::new(2, TableStyle::modern().get_horizontal()),
+            ]));
+            writeln!(f, "{}", table)?;
+        }
+
+        if preview.object_changes.is_empty() {
+            writeln!(f, "╭─────────────────────────────╮")?;
+            writeln!(f, "│ No object changes           │")?;
+            writeln!(f, "╰─────────────────────────────╯")?;
+        } else {
+            let mut builder = TableBuilder::default();
+            builder.push_record(vec![
+                "object", "type", "change", "before", "after", "fields",
+            ]);
+            for change in &preview.object_changes {
+                builder.push_record(vec![
+                    change.object_id.to_string(),
+                    change.object_type.clone().unwrap_or_default(),
+                    format!("{:?}", change.kind),
+                    format_state(&change.before),
+                    format_state(&change.after),
+                    format_field_changes(&change.field_changes),
+                ]);
+            }
+            let mut table = builder.build();
+            table.with(TablePanel::header("Object Changes"));
+            table.with(TableStyle::rounded().horizontals([
+                HorizontalLine::new(1, TableStyle::modern().get_horizontal()),
+                HorizontalLine::new(2, TableStyle::modern().get_horizontal()),
+            ]));
+            writeln!(f, "{}", table)?;
+        }
+
+        writeln!(f, "{}", Pretty(&preview.gas_cost))?;
+        for warning in &preview.warnings {
+            writeln!(f, "{}", format!("[warning] {warning}").yellow().bold())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FieldChange, ObjectChangeKind, TransactionPreview, field_changes, format_amount,
+        is_capability, is_transfer,
+    };
+    use myso_types::{
+        base_types::{MySoAddress, ObjectID, SequenceNumber},
+        digests::TransactionDigest,
+        effects::{TestEffectsBuilder, TransactionEffectsAPI},
+        full_checkpoint_content::ObjectSet,
+        object::{MoveObject, Object, Owner},
+        parse_myso_struct_tag,
+        storage::ObjectKey,
+        transaction::{SenderSignedData, TransactionData},
+    };
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn gas_coin(id: ObjectID, version: SequenceNumber, owner: MySoAddress, value: u64) -> Object {
+        Object::new_move(
+            MoveObject::new_gas_coin(version, id, value),
+            Owner::AddressOwner(owner),
+            TransactionDigest::genesis_marker(),
+        )
+    }
+
+    fn admin_cap(id: ObjectID, version: SequenceNumber, owner: MySoAddress) -> Object {
+        let object = unsafe {
+            MoveObject::new_from_execution_with_limit(
+                parse_myso_struct_tag("0x42::admin::AdminCap")
+                    .unwrap()
+                    .into(),
+                true,
+                version,
+                bcs::to_bytes(&id).unwrap(),
+                u64::MAX,
+            )
+        }
+        .unwrap();
+        Object::new_move(
+            object,
+            Owner::AddressOwner(owner),
+            TransactionDigest::genesis_marker(),
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        let alice = MySoAddress::random_for_testing_only();
+        let bob = MySoAddress::random_for_testing_only();
+        let version = SequenceNumber::from_u64(3);
+        let gas = gas_coin(ObjectID::random(), version, alice, 1_000);
+        let cap = admin_cap(ObjectID::random(), version, alice);
+        let coin_id = ObjectID::random();
+
+        // Alice sends 100 to Bob, and her capability along with it.
+        let tx_data = TransactionData::new_transfer_myso(
+            bob,
+            alice,
+            Some(100),
+            gas.compute_object_reference(),
+            1_000,
+            1_000,
+        );
+        let effects = TestEffectsBuilder::new(&SenderSignedData::new(tx_data, vec![]))
+            .with_mutated_objects([(cap.id(), version, Owner::AddressOwner(bob))])
+            .with_created_objects([(coin_id, Owner::AddressOwner(bob))])
+            .build();
+        let lamport = effects.lamport_version();
+
+        let mut objects = ObjectSet::default();
+        objects.insert(gas.clone());
+        objects.insert(cap.clone());
+        objects.insert(gas_coin(gas.id(), lamport, alice, 900));
+        objects.insert(admin_cap(cap.id(), lamport, bob));
+        objects.insert(gas_coin(coin_id, lamport, bob, 100));
+        let contents = BTreeMap::from([
+            (
+                ObjectKey(gas.id(), version),
+                json!({"id": gas.id().to_string(), "balance": "1000"}),
+            ),
+            (
+                ObjectKey(gas.id(), lamport),
+                json!({"id": gas.id().to_string(), "balance": "900"}),
+            ),
+        ]);
+
+        let preview = TransactionPreview::new(alice, &effects, &objects, &contents);
+        assert_eq!(preview.sender, alice);
+
+        let balance = |owner| {
+            preview
+                .balance_changes
+                .iter()
+                .find(|change| change.owner == owner)
+                .unwrap()
+        };
+        assert_eq!(balance(alice).amount, -100);
+        assert_eq!(balance(bob).amount, 100);
+        assert!(balance(bob).coin_type.ends_with("::myso::MYSO"));
+        assert_eq!(balance(bob).symbol, None);
+
+        let change = |id| {
+            preview
+                .object_changes
+                .iter()
+                .find(|change| change.object_id == id)
+                .unwrap()
+        };
+        let gas_change = change(gas.id());
+        assert_eq!(gas_change.kind, ObjectChangeKind::Mutated);
+        assert_eq!(gas_change.before.as_ref().unwrap().version, version);
+        assert_eq!(gas_change.after.as_ref().unwrap().version, lamport);
+        assert_eq!(
+            gas_change.field_changes,
+            vec![FieldChange {
+                field: "balance".to_string(),
+                before: Some(json!("1000")),
+                after: Some(json!("900")),
+            }]
+        );
+
+        let cap_change = change(cap.id());
+        assert_eq!(cap_change.kind, ObjectChangeKind::TransferredAway);
+        assert!(
+            cap_change
+                .object_type
+                .as_ref()
+                .unwrap()
+                .ends_with("::admin::AdminCap")
+        );
+        // Its contents are not known.
+        assert!(cap_change.field_changes.is_empty());
+
+        assert_eq!(change(coin_id).kind, ObjectChangeKind::Created);
+        assert_eq!(preview.warnings.len(), 1);
+        assert!(preview.warnings[0].contains("is transferred to"));
+    }
+
+    #[test]
+    fn test_field_changes() {
+        let before = json!({"name": "a", "count": 1, "removed": true});
+        let after = json!({"name": "a", "count": 2, "added": [1]});
+        assert_eq!(
+            field_changes(&before, &after),
+            vec![
+                FieldChange {
+                    field: "added".to_string(),
+                    before: None,
+                    after: Some(json!([1])),
+                },
+                FieldChange {
+                    field: "count".to_string(),
+                    before: Some(json!(1)),
+                    after: Some(json!(2)),
+                },
+                FieldChange {
+                    field: "removed".to_string(),
+                    before: Some(json!(true)),
+                    after: None,
+                },
+            ]
+        );
+        assert!(field_changes(&before, &before).is_empty());
+        assert_eq!(field_changes(&json!(1), &json!(2)).len(), 1);
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(-1_500_000_000, 9), "-1.5");
+        assert_eq!(format_amount(2_000_000_000, 9), "+2");
+        assert_eq!(format_amount(1, 9), "+0.000000001");
+        assert_eq!(format_amount(-42, 0), "-42");
+        assert_eq!(format_amount(7, u8::MAX), "+7");
+    }
+
+    #[test]
+    fn test_is_capability() {
+        assert!(is_capability("0x2::package::UpgradeCap"));
+        assert!(is_capability("0x2::coin::TreasuryCap<0x2::myso::MYSO>"));
+        assert!(!is_capability("0x2::coin::Coin<0x2::cap::AdminCap>"));
+        assert!(!is_capability("0x2::coin::Coin<0x2::myso::MYSO>"));
+    }
+
+    #[test]
+    fn test_is_transfer() {
+        let alice = MySoAddress::random_for_testing_only();
+        let bob = MySoAddress::random_for_testing_only();
+        assert!(is_transfer(
+            &Owner::AddressOwner(alice),
+            &Owner::AddressOwner(bob)
+        ));
+        assert!(is_transfer(
+            &Owner::AddressOwner(alice),
+            &Owner::ObjectOwner(bob)
+        ));
+        assert!(!is_transfer(
+            &Owner::AddressOwner(alice),
+            &Owner::AddressOwner(alice)
+        ));
+        assert!(!is_transfer(
+            &Owner::AddressOwner(alice),
+            &Owner::Shared {
+                initial_shared_version: SequenceNumber::new()
+            }
+        ));
+        assert!(!is_transfer(
+            &Owner::ObjectOwner(alice),
+            &Owner::ObjectOwner(bob)
+        ));
+    }
+}
@@ -7,7 +7,7 @@ use anyhow::anyhow;
 use clap::*;
 use json_to_table::{Orientation, json_to_table};
 use myso_keys::external::External;
-use myso_keys::keystore::{AccountKeystore, GenerateOptions, GeneratedKey, Keystore};
+use myso_keys::keystore::{AccountKeystore, GenerateOptions, GeneratedKey, Keystore, RemoteSigner};
 use serde::Serialize;
 use serde_json::json;
 use tracing::info;
@@ -22,6 +22,14 @@ pub enum ExternalKeysCommand {
     ListKeys { signer: String },
     /// Add an existing key to the myso cli for an existing external signer key
     AddExisting { key_id: String, signer: String },
+    /// List all keys held by the signing daemon of a remote signer keystore
+    ListRemoteKeys,
+    /// Add a key held by the signing daemon of a remote signer keystore to the myso cli
+    AddRemote {
+        key_id: String,
+        #[clap(long)]
+        alias: Option<String>,
+    },
 }
 
 #[derive(Serialize)]
@@ -83,6 +91,29 @@ impl ExternalKeysCommand {
                     key: Key::from(key.public_key),
                 }))
             }
+            ExternalKeysCommand::ListRemoteKeys => {
+                let remote_keys = get_remote_keystore(external_keys)?;
+                let keys = remote_keys
+                    .available_keys()
+                    .await?
+                    .into_iter()
+                    .map(|key| ExternalKey {
+                        is_indexed: remote_keys.is_indexed(&key),
+                        key_id: key.key_id,
+                        key: Key::from(key.public_key),
+                    })
+                    .collect();
+                Ok(CommandOutput::ExternalList(keys))
+            }
+            ExternalKeysCommand::AddRemote { key_id, alias } => {
+                let remote_keys = get_remote_keystore(external_keys)?;
+                let key = remote_keys.add_existing(key_id, alias).await?;
+                Ok(CommandOutput::ExternalAddExisting(ExternalKey {
+                    is_indexed: true,
+                    key_id: key.key_id,
+                    key: Key::from(key.public_key),
+                }))
+            }
         }
     }
 }
@@ -134,3 +165,13 @@ fn get_external_keystore(keystore: Option<&mut Keystore>) -> Result<&mut Externa
     };
     Ok(external_keystore)
 }
+
+// unwrap the keystore Option<Keystore> => RemoteSigner
+fn get_remote_keystore(
+    keystore: Option<&mut Keystore>,
+) -> Result<&mut RemoteSigner, anyhow::Error> {
+    let Some(Keystore::Remote(remote_keystore)) = keystore else {
+        return Err(anyhow!("Keystore is not configured for a remote signer"));
+    };
+    Ok(remote_keystore)
+}
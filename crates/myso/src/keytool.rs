@@ -32,6 +32,10 @@ use myso_keys::keypair_file::{
     write_keypair_to_file,
 };
 use myso_keys::keystore::{AccountKeystore, Keystore};
+use myso_keys::threshold::{
+    DkgRound1Package, DkgRound2Package, SignatureShare, SigningCommitments, ThresholdKeystore,
+    ThresholdPackage, signing_digest,
+};
 use myso_sdk::wallet_context::WalletContext;
 use myso_types::base_types::MySoAddress;
 use myso_types::committee::EpochId;
@@ -224,6 +228,72 @@ pub enum KeyToolCommand {
         #[clap(long)]
         base64pk: String,
     },
+    /// Start a distributed key generation for a threshold Ed25519 key, as participant
+    /// `identifier` (from 1 to `max-signers`). Any `threshold` participants can sign with the
+    /// key, which looks like a single Ed25519 key on chain. Prints the round 1 package to
+    /// broadcast to the other participants.
+    ThresholdDkgRound1 {
+        #[clap(long)]
+        session: String,
+        #[clap(long)]
+        identifier: u16,
+        #[clap(long)]
+        threshold: u16,
+        #[clap(long)]
+        max_signers: u16,
+    },
+    /// Run round 2 of a distributed key generation, given the round 1 packages of all other
+    /// participants. Prints a round 2 package for each of them. These contain secret shares
+    /// and must be sent privately to their recipient.
+    ThresholdDkgRound2 {
+        #[clap(long)]
+        session: String,
+        #[clap(long, num_args(1..))]
+        packages: Vec<String>,
+    },
+    /// Finish a distributed key generation, given the round 2 packages sent to this
+    /// participant. The key share is stored next to the keystore, and the threshold key
+    /// address is printed.
+    ThresholdDkgFinalize {
+        #[clap(long)]
+        session: String,
+        #[clap(long, num_args(1..))]
+        packages: Vec<String>,
+    },
+    /// Generate single use nonces to sign with a threshold key, and print the commitments to
+    /// send to the other signers.
+    ThresholdCommit {
+        #[clap(long)]
+        address: MySoAddress,
+    },
+    /// Create a signature share of the Base64 encoded BCS serialized transaction data with a
+    /// threshold key, given the commitments of all signers. If intent is absent, default will
+    /// be used.
+    ThresholdSign {
+        #[clap(long)]
+        address: MySoAddress,
+        #[clap(long)]
+        data: String,
+        #[clap(long, num_args(1..))]
+        commitments: Vec<String>,
+        #[clap(long)]
+        intent: Option<Intent>,
+    },
+    /// Combine the signature shares of all signers into a MySo signature (`flag || sig || pk`
+    /// encoded in Base64) by the threshold key. The commitments and intent must be the ones
+    /// used for signing.
+    ThresholdAggregate {
+        #[clap(long)]
+        address: MySoAddress,
+        #[clap(long)]
+        data: String,
+        #[clap(long, num_args(1..))]
+        commitments: Vec<String>,
+        #[clap(long, num_args(1..))]
+        shares: Vec<String>,
+        #[clap(long)]
+        intent: Option<Intent>,
+    },
     /// This takes [enum MySoKeyPair] of Base64 encoded of 33-byte `flag || privkey`). It
     /// outputs the keypair into a file at the current directory where the address is the filename,
     /// and prints out its MySo address, Base64 encoded public key, the key scheme, and the key scheme flag.
@@ -430,6 +500,27 @@ pub struct SignData {
     myso_signature: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThresholdPackageOutput {
+    identifier: u16,
+    // Participant the package must be sent to privately, if it is not broadcast.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recipient: Option<u16>,
+    // Base64 encoded BCS serialized package.
+    package: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThresholdKey {
+    myso_address: MySoAddress,
+    public_base64_key: String,
+    identifier: u16,
+    threshold: u16,
+    max_signers: u16,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ZkLoginSignAndExecuteTx {
@@ -476,6 +567,12 @@ pub enum CommandOutput {
     Show(Key),
     Sign(SignData),
     SignKMS(SerializedSig),
+    ThresholdDkgRound1(ThresholdPackageOutput),
+    ThresholdDkgRound2(Vec<ThresholdPackageOutput>),
+    ThresholdDkgFinalize(ThresholdKey),
+    ThresholdCommit(ThresholdPackageOutput),
+    ThresholdSign(ThresholdPackageOutput),
+    ThresholdAggregate(SignData),
     ZkLoginSignAndExecuteTx(ZkLoginSignAndExecuteTx),
     ZkLoginInsecureSignPersonalMessage(ZkLoginInsecureSignPersonalMessage),
     ZkLoginSigVerify(ZkLoginSigVerifyResponse),
@@ -938,6 +1035,116 @@ impl KeyToolCommand {
                 })
             }
 
+            KeyToolCommand::ThresholdDkgRound1 {
+                session,
+                identifier,
+                threshold,
+                max_signers,
+            } => {
+                let mut keystore = threshold_keystore(context)?;
+                let package = keystore.dkg_round1(&session, identifier, threshold, max_signers)?;
+                CommandOutput::ThresholdDkgRound1(ThresholdPackageOutput {
+                    identifier,
+                    recipient: None,
+                    package: package.encode_base64(),
+                })
+            }
+
+            KeyToolCommand::ThresholdDkgRound2 { session, packages } => {
+                let mut keystore = threshold_keystore(context)?;
+                let packages = packages
+                    .iter()
+                    .map(|p| DkgRound1Package::decode_base64(p))
+                    .collect::<Result<_, _>>()?;
+                let packages = keystore.dkg_round2(&session, packages)?;
+                CommandOutput::ThresholdDkgRound2(
+                    packages
+                        .into_iter()
+                        .map(|p| ThresholdPackageOutput {
+                            identifier: p.sender,
+                            recipient: Some(p.recipient),
+                            package: p.encode_base64(),
+                        })
+                        .collect(),
+                )
+            }
+
+            KeyToolCommand::ThresholdDkgFinalize { session, packages } => {
+                let mut keystore = threshold_keystore(context)?;
+                let packages = packages
+                    .iter()
+                    .map(|p| DkgRound2Package::decode_base64(p))
+                    .collect::<Result<_, _>>()?;
+                let key = keystore.dkg_finalize(&session, packages)?;
+                CommandOutput::ThresholdDkgFinalize(ThresholdKey {
+                    myso_address: key.address(),
+                    public_base64_key: key.public_key().encode_base64(),
+                    identifier: key.identifier,
+                    threshold: key.threshold,
+                    max_signers: key.max_signers,
+                })
+            }
+
+            KeyToolCommand::ThresholdCommit { address } => {
+                let mut keystore = threshold_keystore(context)?;
+                let commitments = keystore.commit(&address)?;
+                CommandOutput::ThresholdCommit(ThresholdPackageOutput {
+                    identifier: commitments.identifier,
+                    recipient: None,
+                    package: commitments.encode_base64(),
+                })
+            }
+
+            KeyToolCommand::ThresholdSign {
+                address,
+                data,
+                commitments,
+                intent,
+            } => {
+                let mut keystore = threshold_keystore(context)?;
+                let intent_msg = threshold_intent_msg(&data, intent)?;
+                let commitments = commitments
+                    .iter()
+                    .map(|c| SigningCommitments::decode_base64(c))
+                    .collect::<Result<_, _>>()?;
+                let share = keystore.sign(&address, &signing_digest(&intent_msg)?, commitments)?;
+                CommandOutput::ThresholdSign(ThresholdPackageOutput {
+                    identifier: share.identifier,
+                    recipient: None,
+                    package: share.encode_base64(),
+                })
+            }
+
+            KeyToolCommand::ThresholdAggregate {
+                address,
+                data,
+                commitments,
+                shares,
+                intent,
+            } => {
+                let keystore = threshold_keystore(context)?;
+                let intent_msg = threshold_intent_msg(&data, intent)?;
+                let commitments = commitments
+                    .iter()
+                    .map(|c| SigningCommitments::decode_base64(c))
+                    .collect::<Result<_, _>>()?;
+                let shares = shares
+                    .iter()
+                    .map(|s| SignatureShare::decode_base64(s))
+                    .collect::<Result<_, _>>()?;
+                let digest = signing_digest(&intent_msg)?;
+                let myso_signature = keystore.aggregate(&address, &digest, commitments, shares)?;
+                myso_signature.verify_secure(&intent_msg, address, SignatureScheme::ED25519)?;
+                CommandOutput::ThresholdAggregate(SignData {
+                    myso_address: address,
+                    raw_tx_data: data,
+                    intent: intent_msg.intent.clone(),
+                    raw_intent_msg: Base64::encode(bcs::to_bytes(&intent_msg)?),
+                    digest: Base64::encode(digest),
+                    myso_signature: myso_signature.encode_base64(),
+                })
+            }
+
             KeyToolCommand::Unpack { keypair } => {
                 let keypair = MySoKeyPair::decode_base64(&keypair)
                     .map_err(|_| anyhow!("Invalid Base64 encode keypair"))?;
@@ -1390,7 +1597,7 @@ impl Display for CommandOutput {
             // Sign needs to be manually built because we need to wrap the very long
             // rawTxData string and rawIntentMsg strings into multiple rows due to
             // their lengths, which we cannot do with a JsonTable
-            CommandOutput::Sign(data) => {
+            CommandOutput::Sign(data) | CommandOutput::ThresholdAggregate(data) => {
                 let intent_table = json_to_table(&json!(&data.intent))
                     .with(tabled::settings::Style::rounded().horizontals([]))
                     .to_string();
@@ -1457,6 +1664,31 @@ impl Debug for CommandOutput {
     }
 }
 
+/// Loads the threshold key shares stored next to the file based keystore.
+fn threshold_keystore(context: &WalletContext) -> Result<ThresholdKeystore, anyhow::Error> {
+    let Keystore::File(keystore) = &context.config.keystore else {
+        return Err(anyhow!("Threshold keys require a file based keystore"));
+    };
+    let path = keystore
+        .path()
+        .ok_or_else(|| anyhow!("Threshold keys require a persisted keystore"))?;
+    ThresholdKeystore::load_or_create(path)
+}
+
+fn threshold_intent_msg(
+    data: &str,
+    intent: Option<Intent>,
+) -> Result<IntentMessage<TransactionData>, anyhow::Error> {
+    let msg: TransactionData = bcs::from_bytes(
+        &Base64::decode(data)
+            .map_err(|e| anyhow!("Cannot deserialize data as TransactionData {:?}", e))?,
+    )?;
+    Ok(IntentMessage::new(
+        intent.unwrap_or_else(Intent::myso_transaction),
+        msg,
+    ))
+}
+
 /// Converts legacy formatted private key to 33 bytes bech32 encoded private key or vice versa.
 /// It can handle:
 /// 1) Hex encoded 32 byte private key (assumes scheme is Ed25519), this is the legacy wallet format
@@ -15,6 +15,7 @@ pub mod genesis_inspector;
 pub mod keytool;
 pub mod mvr_resolver;
 pub mod myso_commands;
+pub mod social_commands;
 pub mod trace_analysis_commands;
 pub mod upgrade_compatibility;
 pub mod validator_commands;
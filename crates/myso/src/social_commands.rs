@@ -0,0 +1,1001 @@
+// Copyright (c) Mysten Labs, Inc.
+// Copyright (c) The Social Proof Foundation, LLC.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `myso client social`: typed commands for the social protocol (the `social_contracts` package,
+//! published at `0x50c1`).
+//!
+//! Most social functions take shared registries as arguments. These are created by the one-time
+//! bootstrap transaction rather than at fixed addresses, so they are found by walking the versions
+//! of the `BootstrapKey` created at genesis back to the transaction that used it, and cached per
+//! chain next to the client config.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use anyhow::{Context, anyhow, bail, ensure};
+use clap::*;
+use move_core_types::ident_str;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use myso_rpc_api::Client;
+use myso_sdk::wallet_context::WalletContext;
+use myso_types::{
+    MYSO_FRAMEWORK_ADDRESS, MYSO_SOCIAL_PACKAGE_ID,
+    base_types::{MySoAddress, ObjectID, SequenceNumber},
+    effects::TransactionEffectsAPI,
+    gas_coin::GAS,
+    id::UID,
+    object::{Object, Owner},
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    social::{
+        BLOCK_LIST_MODULE_NAME, BLOCK_LIST_REGISTRY_STRUCT_NAME, BLOCK_WALLET_FUNCTION_NAME,
+        CREATE_POST_FUNCTION_NAME, CREATE_PROFILE_FUNCTION_NAME, DELETE_POST_FUNCTION_NAME,
+        FOLLOW_FUNCTION_NAME, JOIN_PLATFORM_FUNCTION_NAME, PLATFORM_MODULE_NAME,
+        PLATFORM_REGISTRY_STRUCT_NAME, POST_CONFIG_STRUCT_NAME, POST_MODULE_NAME,
+        PROFILE_MODULE_NAME, Profile, SOCIAL_GRAPH_MODULE_NAME, SOCIAL_GRAPH_STRUCT_NAME,
+        TIP_POST_FUNCTION_NAME, UNBLOCK_WALLET_FUNCTION_NAME, UNFOLLOW_FUNCTION_NAME,
+        UPDATE_PROFILE_FUNCTION_NAME, USERNAME_REGISTRY_STRUCT_NAME, social_type,
+    },
+    transaction::{
+        Argument, Command, ObjectArg, ProgrammableTransaction, SharedObjectMutability,
+        TransactionKind,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::client_commands::{
+    GasDataArgs, MySoClientCommandResult, PaymentArgs, TxProcessingArgs,
+    dry_run_or_execute_or_serialize,
+};
+
+/// File next to the client config caching the social shared objects of each chain.
+pub const SOCIAL_OBJECTS_CACHE_FILE: &str = "social_objects.json";
+
+const BOOTSTRAP_KEY_MODULE_NAME: &IdentStr = ident_str!("bootstrap_key");
+const BOOTSTRAP_KEY_STRUCT_NAME: &IdentStr = ident_str!("BootstrapKey");
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum SocialCommand {
+    /// Create the profile of the sender, registering its username
+    CreateProfile {
+        /// Username, between 2 and 50 characters
+        #[clap(long)]
+        username: String,
+        #[clap(long)]
+        display_name: Option<String>,
+        #[clap(long, default_value = "")]
+        bio: String,
+        /// URL of the profile picture
+        #[clap(long)]
+        profile_picture: Option<String>,
+        /// URL of the cover photo
+        #[clap(long)]
+        cover_photo: Option<String>,
+        #[clap(flatten)]
+        tx: SocialTxArgs,
+    },
+    /// Update the profile of the sender. Fields that are not passed are left unchanged.
+    UpdateProfile {
+        #[clap(long)]
+        display_name: Option<String>,
+        #[clap(long)]
+        bio: Option<String>,
+        /// URL of the profile picture
+        #[clap(long)]
+        profile_picture: Option<String>,
+        /// URL of the cover photo
+        #[clap(long)]
+        cover_photo: Option<String>,
+        #[clap(long)]
+        x_username: Option<String>,
+        #[clap(long)]
+        github_username: Option<String>,
+        /// Minimum amount (in MIST) of offers to buy the profile
+        #[clap(long)]
+        min_offer_amount: Option<u64>,
+        #[clap(flatten)]
+        tx: SocialTxArgs,
+    },
+    /// Publish a post on a platform
+    Post {
+        /// Object ID of the platform
+        #[clap(long)]
+        platform: ObjectID,
+        #[clap(long)]
+        content: String,
+        /// URLs of attached media
+        #[clap(long, num_args(1..))]
+        media: Vec<String>,
+        /// Addresses mentioned in the post
+        #[clap(long, num_args(1..))]
+        mentions: Vec<MySoAddress>,
+        /// Whether other users can comment, react, repost, quote or tip. All are allowed by
+        /// default.
+        #[clap(long)]
+        allow_comments: Option<bool>,
+        #[clap(long)]
+        allow_reactions: Option<bool>,
+        #[clap(long)]
+        allow_reposts: Option<bool>,
+        #[clap(long)]
+        allow_quotes: Option<bool>,
+        #[clap(long)]
+        allow_tips: Option<bool>,
+        #[clap(flatten)]
+        tx: SocialTxArgs,
+    },
+    /// Delete a post of the sender
+    DeletePost {
+        /// Object ID of the post
+        post: ObjectID,
+        #[clap(flatten)]
+        tx: SocialTxArgs,
+    },
+    /// Follow an address
+    Follow {
+        address: MySoAddress,
+        #[clap(flatten)]
+        tx: SocialTxArgs,
+    },
+    /// Unfollow an address
+    Unfollow {
+        address: MySoAddress,
+        #[clap(flatten)]
+        tx: SocialTxArgs,
+    },
+    /// Tip the creator of a post
+    Tip {
+        /// Object ID of the post
+        post: ObjectID,
+        /// Amount to tip, in the smallest unit of the coin
+        #[clap(long)]
+        amount: u64,
+        /// Coin to tip from, for instance to tip in another currency than MYSO. By default the
+        /// tip is split from the gas coin.
+        #[clap(long)]
+        coin: Option<ObjectID>,
+        #[clap(flatten)]
+        tx: SocialTxArgs,
+    },
+    /// Join a platform
+    JoinPlatform {
+        /// Object ID of the platform
+        platform: ObjectID,
+        #[clap(flatten)]
+        tx: SocialTxArgs,
+    },
+    /// Block an address. Follows between the sender and the address are removed.
+    Block {
+        address: MySoAddress,
+        #[clap(flatten)]
+        tx: SocialTxArgs,
+    },
+    /// Unblock an address
+    Unblock {
+        address: MySoAddress,
+        #[clap(flatten)]
+        tx: SocialTxArgs,
+    },
+}
+
+#[derive(Args, Debug, Default)]
+pub struct SocialTxArgs {
+    #[clap(flatten)]
+    payment: PaymentArgs,
+    #[clap(flatten)]
+    gas_data: GasDataArgs,
+    #[clap(flatten)]
+    processing: TxProcessingArgs,
+}
+
+/// A shared object, as needed to pass it to a transaction.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+struct SharedObject {
+    id: ObjectID,
+    initial_shared_version: SequenceNumber,
+}
+
+/// The shared objects of the social protocol.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SocialObjects {
+    username_registry: SharedObject,
+    platform_registry: SharedObject,
+    block_list_registry: SharedObject,
+    social_graph: SharedObject,
+    post_config: SharedObject,
+}
+
+/// Objects a command is about, fetched from the chain before building its transaction.
+#[derive(Default)]
+struct FetchedObjects {
+    /// Profile of the sender.
+    profile: Option<Object>,
+    /// Post or platform.
+    target: Option<Object>,
+    /// Coin to tip from.
+    coin: Option<Object>,
+}
+
+impl SocialCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<MySoClientCommandResult, anyhow::Error> {
+        let tx = self.tx_args();
+        let sender = match tx.processing.sender {
+            Some(sender) => sender,
+            None => context.infer_sender(&tx.payment.gas).await?,
+        };
+        let mut client = context.grpc_client()?;
+        let objects = SocialObjects::load_or_resolve(context, &mut client).await?;
+        let fetched = self.fetch_objects(&mut client, sender).await?;
+        let pt = self.build(&objects, fetched)?;
+
+        let tx = self.into_tx_args();
+        let gas_payment = client
+            .transaction_builder()
+            .input_refs(&tx.payment.gas)
+            .await?;
+        dry_run_or_execute_or_serialize(
+            sender,
+            TransactionKind::programmable(pt),
+            context,
+            gas_payment,
+            tx.gas_data,
+            tx.processing,
+        )
+        .await
+    }
+
+    fn tx_args(&self) -> &SocialTxArgs {
+        match self {
+            SocialCommand::CreateProfile { tx, .. }
+            | SocialCommand::UpdateProfile { tx, .. }
+            | SocialCommand::Post { tx, .. }
+            | SocialCommand::DeletePost { tx, .. }
+            | SocialCommand::Follow { tx, .. }
+            | SocialCommand::Unfollow { tx, .. }
+            | SocialCommand::Tip { tx, .. }
+            | SocialCommand::JoinPlatform { tx, .. }
+            | SocialCommand::Block { tx, .. }
+            | SocialCommand::Unblock { tx, .. } => tx,
+        }
+    }
+
+    fn into_tx_args(self) -> SocialTxArgs {
+        match self {
+            SocialCommand::CreateProfile { tx, .. }
+            | SocialCommand::UpdateProfile { tx, .. }
+            | SocialCommand::Post { tx, .. }
+            | SocialCommand::DeletePost { tx, .. }
+            | SocialCommand::Follow { tx, .. }
+            | SocialCommand::Unfollow { tx, .. }
+            | SocialCommand::Tip { tx, .. }
+            | SocialCommand::JoinPlatform { tx, .. }
+            | SocialCommand::Block { tx, .. }
+            | SocialCommand::Unblock { tx, .. } => tx,
+        }
+    }
+
+    async fn fetch_objects(
+        &self,
+        client: &mut Client,
+        sender: MySoAddress,
+    ) -> Result<FetchedObjects, anyhow::Error> {
+        let mut fetched = FetchedObjects::default();
+        match self {
+            SocialCommand::UpdateProfile { .. } => {
+                fetched.profile = Some(owned_profile(client, sender).await?);
+            }
+            SocialCommand::Post { platform, .. } | SocialCommand::JoinPlatform { platform, .. } => {
+                fetched.target = Some(client.get_object(*platform).await?);
+            }
+            SocialCommand::DeletePost { post, .. } => {
+                fetched.target = Some(client.get_object(*post).await?);
+            }
+            SocialCommand::Tip { post, coin, .. } => {
+                fetched.target = Some(client.get_object(*post).await?);
+                if let Some(coin) = coin {
+                    fetched.coin = Some(client.get_object(*coin).await?);
+                }
+            }
+            _ => (),
+        }
+        Ok(fetched)
+    }
+
+    /// Builds the transaction of the command, given the objects it is about.
+    fn build(
+        &self,
+        objects: &SocialObjects,
+        fetched: FetchedObjects,
+    ) -> Result<ProgrammableTransaction, anyhow::Error> {
+        let target = |mutable| {
+            fetched
+                .target
+                .as_ref()
+                .map(|object| object_arg(object, mutable))
+                .ok_or_else(|| anyhow!("Missing target object"))
+        };
+        let mut builder = ProgrammableTransactionBuilder::new();
+        match self {
+            SocialCommand::CreateProfile {
+                username,
+                display_name,
+                bio,
+                profile_picture,
+                cover_photo,
+                tx: _,
+            } => {
+                let arguments = vec![
+                    builder.obj(objects.username_registry.arg(true))?,
+                    builder.pure(display_name.clone().unwrap_or_default())?,
+                    builder.pure(username)?,
+                    builder.pure(bio)?,
+                    builder.pure(profile_picture.clone().unwrap_or_default().into_bytes())?,
+                    builder.pure(cover_photo.clone().unwrap_or_default().into_bytes())?,
+                ];
+                move_call(
+                    &mut builder,
+                    PROFILE_MODULE_NAME,
+                    CREATE_PROFILE_FUNCTION_NAME,
+                    vec![],
+                    arguments,
+                );
+            }
+
+            SocialCommand::UpdateProfile {
+                display_name,
+                bio,
+                profile_picture,
+                cover_photo,
+                x_username,
+                github_username,
+                min_offer_amount,
+                tx: _,
+            } => {
+                let profile = fetched
+                    .profile
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("Missing profile"))?;
+                let current: Profile = bcs::from_bytes(
+                    profile
+                        .data
+                        .try_as_move()
+                        .ok_or_else(|| anyhow!("Profile is not a Move object"))?
+                        .contents(),
+                )?;
+                // Empty strings and `None`s leave fields unchanged, except for the bio.
+                let arguments = vec![
+                    builder.obj(ObjectArg::ImmOrOwnedObject(
+                        profile.compute_object_reference(),
+                    ))?,
+                    builder.pure(display_name.clone().unwrap_or_default())?,
+                    builder.pure(bio.clone().unwrap_or(current.bio))?,
+                    builder.pure(profile_picture.clone().unwrap_or_default().into_bytes())?,
+                    builder.pure(cover_photo.clone().unwrap_or_default().into_bytes())?,
+                    builder.pure(None::<String>)?,
+                    builder.pure(github_username)?,
+                    builder.pure(None::<String>)?,
+                    builder.pure(None::<String>)?,
+                    builder.pure(None::<String>)?,
+                    builder.pure(None::<String>)?,
+                    builder.pure(x_username)?,
+                    builder.pure(min_offer_amount)?,
+                ];
+                move_call(
+                    &mut builder,
+                    PROFILE_MODULE_NAME,
+                    UPDATE_PROFILE_FUNCTION_NAME,
+                    vec![],
+                    arguments,
+                );
+            }
+
+            SocialCommand::Post {
+                platform,
+                content,
+                media,
+                mentions,
+                allow_comments,
+                allow_reactions,
+                allow_reposts,
+                allow_quotes,
+                allow_tips,
+                tx: _,
+            } => {
+                let platform = target(false)?;
+                let arguments = vec![
+                    builder.obj(objects.username_registry.arg(false))?,
+                    builder.obj(objects.platform_registry.arg(false))?,
+                    builder.obj(platform)?,
+                    builder.obj(objects.block_list_registry.arg(false))?,
+                    builder.obj(objects.post_config.arg(false))?,
+                    builder.pure(content)?,
+                    builder.pure((!media.is_empty()).then_some(media))?,
+                    builder.pure((!mentions.is_empty()).then_some(mentions))?,
+                    builder.pure(None::<String>)?,
+                    builder.pure(allow_comments)?,
+                    builder.pure(allow_reactions)?,
+                    builder.pure(allow_reposts)?,
+                    builder.pure(allow_quotes)?,
+                    builder.pure(allow_tips)?,
+                    // Social proof tokens, proof of creativity and SPoT are not enabled.
+                    builder.pure(None::<bool>)?,
+                    builder.pure(None::<bool>)?,
+                    builder.pure(None::<bool>)?,
+                ];
+                move_call(
+                    &mut builder,
+                    POST_MODULE_NAME,
+                    CREATE_POST_FUNCTION_NAME,
+                    vec![],
+                    arguments,
+                );
+            }
+
+            SocialCommand::DeletePost { .. } => {
+                let arguments = vec![builder.obj(target(true)?)?];
+                move_call(
+                    &mut builder,
+                    POST_MODULE_NAME,
+                    DELETE_POST_FUNCTION_NAME,
+                    vec![],
+                    arguments,
+                );
+            }
+
+            SocialCommand::Follow { address, .. } => {
+                let arguments = vec![
+                    builder.obj(objects.social_graph.arg(true))?,
+                    builder.pure(address)?,
+                ];
+                move_call(
+                    &mut builder,
+                    SOCIAL_GRAPH_MODULE_NAME,
+                    FOLLOW_FUNCTION_NAME,
+                    vec![],
+                    arguments,
+                );
+            }
+
+            SocialCommand::Unfollow { address, .. } => {
+                let arguments = vec![
+                    builder.obj(objects.social_graph.arg(true))?,
+                    builder.pure(address)?,
+                ];
+                move_call(
+                    &mut builder,
+                    SOCIAL_GRAPH_MODULE_NAME,
+                    UNFOLLOW_FUNCTION_NAME,
+                    vec![],
+                    arguments,
+                );
+            }
+
+            SocialCommand::Tip { amount, .. } => {
+                ensure!(*amount > 0, "Tip amount must be positive");
+                let post = builder.obj(target(true)?)?;
+                let amount_arg = builder.pure(*amount)?;
+                let split_from_gas = fetched.coin.is_none();
+                let (coin, coin_type) = match &fetched.coin {
+                    Some(coin) => {
+                        let coin_type = coin
+                            .coin_type_maybe()
+                            .ok_or_else(|| anyhow!("Object {} is not a coin", coin.id()))?;
+                        let coin = builder
+                            .obj(ObjectArg::ImmOrOwnedObject(coin.compute_object_reference()))?;
+                        (coin, coin_type)
+                    }
+                    None => {
+                        let coin = builder
+                            .command(Command::SplitCoins(Argument::GasCoin, vec![amount_arg]));
+                        (coin, GAS::type_tag())
+                    }
+                };
+                move_call(
+                    &mut builder,
+                    POST_MODULE_NAME,
+                    TIP_POST_FUNCTION_NAME,
+                    vec![coin_type],
+                    vec![post, coin, amount_arg],
+                );
+                // The coin split from gas is empty after the tip.
+                if split_from_gas {
+                    builder.command(Command::MergeCoins(Argument::GasCoin, vec![coin]));
+                }
+            }
+
+            SocialCommand::JoinPlatform { .. } => {
+                let arguments = vec![
+                    builder.obj(objects.platform_registry.arg(false))?,
+                    builder.obj(objects.block_list_registry.arg(false))?,
+                    builder.obj(target(true)?)?,
+                ];
+                move_call(
+                    &mut builder,
+                    PLATFORM_MODULE_NAME,
+                    JOIN_PLATFORM_FUNCTION_NAME,
+                    vec![],
+                    arguments,
+                );
+            }
+
+            SocialCommand::Block { address, .. } => {
+                let arguments = vec![
+                    builder.obj(objects.block_list_registry.arg(true))?,
+                    builder.obj(objects.social_graph.arg(true))?,
+                    builder.pure(address)?,
+                ];
+                move_call(
+                    &mut builder,
+                    BLOCK_LIST_MODULE_NAME,
+                    BLOCK_WALLET_FUNCTION_NAME,
+                    vec![],
+                    arguments,
+                );
+            }
+
+            SocialCommand::Unblock { address, .. } => {
+                let arguments = vec![
+                    builder.obj(objects.block_list_registry.arg(true))?,
+                    builder.pure(address)?,
+                ];
+                move_call(
+                    &mut builder,
+                    BLOCK_LIST_MODULE_NAME,
+                    UNBLOCK_WALLET_FUNCTION_NAME,
+                    vec![],
+                    arguments,
+                );
+            }
+        }
+        Ok(builder.finish())
+    }
+}
+
+impl SharedObject {
+    fn arg(&self, mutable: bool) -> ObjectArg {
+        ObjectArg::SharedObject {
+            id: self.id,
+            initial_shared_version: self.initial_shared_version,
+            mutability: if mutable {
+                SharedObjectMutability::Mutable
+            } else {
+                SharedObjectMutability::Immutable
+            },
+        }
+    }
+}
+
+impl SocialObjects {
+    /// Loads the social shared objects of the active chain from the cache, or resolves them.
+    async fn load_or_resolve(
+        context: &WalletContext,
+        client: &mut Client,
+    ) -> Result<Self, anyhow::Error> {
+        let chain_id = context.load_or_cache_chain_id().await?;
+        let cache_path = context
+            .config
+            .path()
+            .with_file_name(SOCIAL_OBJECTS_CACHE_FILE);
+        let mut cache: BTreeMap<String, SocialObjects> = fs::read_to_string(&cache_path)
+            .ok()
+            .and_then(|cache| serde_json::from_str(&cache).ok())
+            .unwrap_or_default();
+        if let Some(objects) = cache.get(&chain_id) {
+            return Ok(objects.clone());
+        }
+
+        let objects = Self::resolve(client).await?;
+        cache.insert(chain_id, objects.clone());
+        fs::write(&cache_path, serde_json::to_string_pretty(&cache)?).with_context(|| {
+            format!(
+                "Cannot write social objects cache: {}",
+                cache_path.display()
+            )
+        })?;
+        Ok(objects)
+    }
+
+    /// Finds the shared objects created by the bootstrap transaction, the one that marked the
+    /// `BootstrapKey` created at genesis as used. The key is shared, so later transactions taking
+    /// it, even failed ones, may have modified it since: its versions are walked back to the last
+    /// one that was not used.
+    async fn resolve(client: &mut Client) -> Result<Self, anyhow::Error> {
+        let bootstrap_key_type = StructTag {
+            address: MYSO_FRAMEWORK_ADDRESS,
+            module: BOOTSTRAP_KEY_MODULE_NAME.to_owned(),
+            name: BOOTSTRAP_KEY_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        };
+        let genesis = client.get_full_checkpoint(0).await?;
+        let key_id = genesis
+            .object_set
+            .iter()
+            .find(|object| object.struct_tag().as_ref() == Some(&bootstrap_key_type))
+            .ok_or_else(|| anyhow!("No bootstrap key was created at genesis"))?
+            .id();
+
+        let mut key = client.get_object(key_id).await?;
+        ensure!(
+            bootstrap_key_used(&key)?,
+            "The social protocol has not been bootstrapped"
+        );
+        let bootstrap = loop {
+            let transaction = client.get_transaction(&key.previous_transaction).await?;
+            let previous_version = transaction
+                .effects
+                .modified_at_versions()
+                .into_iter()
+                .find_map(|(id, version)| (id == key_id).then_some(version))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Transaction {} did not modify the bootstrap key",
+                        key.previous_transaction
+                    )
+                })?;
+            let previous = client
+                .get_object_with_version(key_id, previous_version)
+                .await?;
+            if !bootstrap_key_used(&previous)? {
+                break transaction;
+            }
+            key = previous;
+        };
+
+        let created: Vec<ObjectID> = bootstrap
+            .effects
+            .created()
+            .into_iter()
+            .filter(|(_, owner)| owner.is_shared())
+            .map(|((id, _, _), _)| id)
+            .collect();
+        Self::from_created(&client.batch_get_objects(&created).await?)
+    }
+
+    /// Finds the social shared objects among the objects created by the bootstrap transaction.
+    fn from_created(created: &[Object]) -> Result<Self, anyhow::Error> {
+        let find = |module: &IdentStr, name: &IdentStr| {
+            let type_ = social_type(module, name);
+            created
+                .iter()
+                .find(|object| object.struct_tag().as_ref() == Some(&type_))
+                .and_then(|object| match object.owner {
+                    Owner::Shared {
+                        initial_shared_version,
+                    } => Some(SharedObject {
+                        id: object.id(),
+                        initial_shared_version,
+                    }),
+                    _ => None,
+                })
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Cannot find the shared {type_}, has the social protocol been bootstrapped?"
+                    )
+                })
+        };
+
+        Ok(Self {
+            username_registry: find(PROFILE_MODULE_NAME, USERNAME_REGISTRY_STRUCT_NAME)?,
+            platform_registry: find(PLATFORM_MODULE_NAME, PLATFORM_REGISTRY_STRUCT_NAME)?,
+            block_list_registry: find(BLOCK_LIST_MODULE_NAME, BLOCK_LIST_REGISTRY_STRUCT_NAME)?,
+            social_graph: find(SOCIAL_GRAPH_MODULE_NAME, SOCIAL_GRAPH_STRUCT_NAME)?,
+            post_config: find(POST_MODULE_NAME, POST_CONFIG_STRUCT_NAME)?,
+        })
+    }
+}
+
+fn move_call(
+    builder: &mut ProgrammableTransactionBuilder,
+    module: &IdentStr,
+    function: &IdentStr,
+    type_arguments: Vec<TypeTag>,
+    arguments: Vec<Argument>,
+) {
+    builder.programmable_move_call(
+        MYSO_SOCIAL_PACKAGE_ID,
+        module.to_owned(),
+        function.to_owned(),
+        type_arguments,
+        arguments,
+    );
+}
+
+/// The argument for a post or platform, which are shared, or any other object.
+fn object_arg(object: &Object, mutable: bool) -> ObjectArg {
+    match object.owner {
+        Owner::Shared {
+            initial_shared_version,
+        } => SharedObject {
+            id: object.id(),
+            initial_shared_version,
+        }
+        .arg(mutable),
+        _ => ObjectArg::ImmOrOwnedObject(object.compute_object_reference()),
+    }
+}
+
+/// Whether the bootstrap key has been used, from the contents of a `BootstrapKey` object.
+fn bootstrap_key_used(key: &Object) -> Result<bool, anyhow::Error> {
+    let contents = key
+        .data
+        .try_as_move()
+        .ok_or_else(|| anyhow!("Bootstrap key is not a Move object"))?
+        .contents();
+    let (_, used, _): (UID, bool, u64) = bcs::from_bytes(contents)
+        .map_err(|e| anyhow!("Cannot deserialize the bootstrap key: {e}"))?;
+    Ok(used)
+}
+
+async fn owned_profile(client: &Client, owner: MySoAddress) -> Result<Object, anyhow::Error> {
+    let page = client
+        .get_owned_objects(owner, Some(Profile::type_()), Some(1), None)
+        .await?;
+    match page.items.into_iter().next() {
+        Some(profile) => Ok(profile),
+        None => bail!(
+            "Address {owner} has no profile, create one with `myso client social create-profile`"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myso_types::digests::TransactionDigest;
+    use myso_types::object::{MoveObject, OBJECT_START_VERSION};
+    use myso_types::transaction::CallArg;
+
+    /// A Move object of type `type_`, with `fields` after its ID.
+    fn object(type_: StructTag, owner: Owner, fields: &[u8]) -> Object {
+        let mut contents = bcs::to_bytes(&ObjectID::random()).unwrap();
+        contents.extend_from_slice(fields);
+        let object = unsafe {
+            MoveObject::new_from_execution_with_limit(
+                type_.into(),
+                false,
+                OBJECT_START_VERSION,
+                contents,
+                u64::MAX,
+            )
+        }
+        .unwrap();
+        Object::new_move(object, owner, TransactionDigest::genesis_marker())
+    }
+
+    fn shared(type_: StructTag) -> Object {
+        object(
+            type_,
+            Owner::Shared {
+                initial_shared_version: OBJECT_START_VERSION,
+            },
+            &[],
+        )
+    }
+
+    fn social_objects() -> SocialObjects {
+        let shared = || SharedObject {
+            id: ObjectID::random(),
+            initial_shared_version: OBJECT_START_VERSION,
+        };
+        SocialObjects {
+            username_registry: shared(),
+            platform_registry: shared(),
+            block_list_registry: shared(),
+            social_graph: shared(),
+            post_config: shared(),
+        }
+    }
+
+    fn social_call(
+        module: &IdentStr,
+        function: &IdentStr,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<Argument>,
+    ) -> Command {
+        Command::move_call(
+            MYSO_SOCIAL_PACKAGE_ID,
+            module.to_owned(),
+            function.to_owned(),
+            type_arguments,
+            arguments,
+        )
+    }
+
+    #[test]
+    fn test_from_created() {
+        let mut created = vec![
+            Object::shared_for_testing(),
+            shared(social_type(
+                PROFILE_MODULE_NAME,
+                USERNAME_REGISTRY_STRUCT_NAME,
+            )),
+            shared(social_type(
+                PLATFORM_MODULE_NAME,
+                PLATFORM_REGISTRY_STRUCT_NAME,
+            )),
+            shared(social_type(
+                BLOCK_LIST_MODULE_NAME,
+                BLOCK_LIST_REGISTRY_STRUCT_NAME,
+            )),
+            shared(social_type(
+                SOCIAL_GRAPH_MODULE_NAME,
+                SOCIAL_GRAPH_STRUCT_NAME,
+            )),
+        ];
+        assert!(SocialObjects::from_created(&created).is_err());
+
+        // An owned object of the right type is not the registry.
+        created.push(object(
+            social_type(POST_MODULE_NAME, POST_CONFIG_STRUCT_NAME),
+            Owner::Immutable,
+            &[],
+        ));
+        assert!(SocialObjects::from_created(&created).is_err());
+
+        created.push(shared(social_type(
+            POST_MODULE_NAME,
+            POST_CONFIG_STRUCT_NAME,
+        )));
+        let objects = SocialObjects::from_created(&created).unwrap();
+        assert_eq!(objects.username_registry.id, created[1].id());
+        assert_eq!(objects.platform_registry.id, created[2].id());
+        assert_eq!(objects.block_list_registry.id, created[3].id());
+        assert_eq!(objects.social_graph.id, created[4].id());
+        assert_eq!(objects.post_config.id, created[6].id());
+        assert_eq!(
+            objects.post_config.initial_shared_version,
+            OBJECT_START_VERSION
+        );
+    }
+
+    #[test]
+    fn test_bootstrap_key_used() {
+        let key = |used: bool| {
+            object(
+                StructTag {
+                    address: MYSO_FRAMEWORK_ADDRESS,
+                    module: BOOTSTRAP_KEY_MODULE_NAME.to_owned(),
+                    name: BOOTSTRAP_KEY_STRUCT_NAME.to_owned(),
+                    type_params: vec![],
+                },
+                Owner::Shared {
+                    initial_shared_version: OBJECT_START_VERSION,
+                },
+                &bcs::to_bytes(&(used, 1u64)).unwrap(),
+            )
+        };
+        assert!(!bootstrap_key_used(&key(false)).unwrap());
+        assert!(bootstrap_key_used(&key(true)).unwrap());
+        assert!(bootstrap_key_used(&Object::immutable_for_testing()).is_err());
+    }
+
+    #[test]
+    fn test_build_follow() {
+        let objects = social_objects();
+        let address = MySoAddress::random_for_testing_only();
+        let pt = SocialCommand::Follow {
+            address,
+            tx: SocialTxArgs::default(),
+        }
+        .build(&objects, FetchedObjects::default())
+        .unwrap();
+        assert_eq!(
+            pt.inputs,
+            vec![
+                CallArg::Object(objects.social_graph.arg(true)),
+                CallArg::Pure(bcs::to_bytes(&address).unwrap()),
+            ]
+        );
+        assert_eq!(
+            pt.commands,
+            vec![social_call(
+                SOCIAL_GRAPH_MODULE_NAME,
+                FOLLOW_FUNCTION_NAME,
+                vec![],
+                vec![Argument::Input(0), Argument::Input(1)],
+            )]
+        );
+    }
+
+    #[test]
+    fn test_build_tip() {
+        let objects = social_objects();
+        let post = Object::shared_for_testing();
+        let tip = |coin: Option<ObjectID>| SocialCommand::Tip {
+            post: post.id(),
+            amount: 5,
+            coin,
+            tx: SocialTxArgs::default(),
+        };
+
+        // The post must have been fetched.
+        assert!(
+            tip(None)
+                .build(&objects, FetchedObjects::default())
+                .is_err()
+        );
+
+        // Tips are split from the gas coin by default.
+        let pt = tip(None)
+            .build(
+                &objects,
+                FetchedObjects {
+                    target: Some(post.clone()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            pt.inputs,
+            vec![
+                CallArg::Object(object_arg(&post, true)),
+                CallArg::Pure(bcs::to_bytes(&5u64).unwrap()),
+            ]
+        );
+        assert_eq!(
+            pt.commands,
+            vec![
+                Command::SplitCoins(Argument::GasCoin, vec![Argument::Input(1)]),
+                social_call(
+                    POST_MODULE_NAME,
+                    TIP_POST_FUNCTION_NAME,
+                    vec![GAS::type_tag()],
+                    vec![Argument::Input(0), Argument::Result(0), Argument::Input(1)],
+                ),
+                Command::MergeCoins(Argument::GasCoin, vec![Argument::Result(0)]),
+            ]
+        );
+
+        let coin = Object::new_gas_for_testing();
+        let pt = tip(Some(coin.id()))
+            .build(
+                &objects,
+                FetchedObjects {
+                    target: Some(post.clone()),
+                    coin: Some(coin.clone()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            pt.inputs[2],
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(coin.compute_object_reference()))
+        );
+        assert_eq!(
+            pt.commands,
+            vec![social_call(
+                POST_MODULE_NAME,
+                TIP_POST_FUNCTION_NAME,
+                vec![GAS::type_tag()],
+                vec![Argument::Input(0), Argument::Input(2), Argument::Input(1)],
+            )]
+        );
+    }
+
+    #[test]
+    fn test_object_arg() {
+        let post = Object::shared_for_testing();
+        assert_eq!(
+            object_arg(&post, false),
+            ObjectArg::SharedObject {
+                id: post.id(),
+                initial_shared_version: post.version(),
+                mutability: SharedObjectMutability::Immutable,
+            }
+        );
+        let owned = Object::with_id_owner_for_testing(
+            ObjectID::random(),
+            MySoAddress::random_for_testing_only(),
+        );
+        assert_eq!(
+            object_arg(&owned, true),
+            ObjectArg::ImmOrOwnedObject(owned.compute_object_reference())
+        );
+    }
+}
@@ -635,3 +635,141 @@ async fn test_sign_command() -> Result<(), anyhow::Error> {
     .await?;
     Ok(())
 }
+
+#[test]
+async fn test_threshold_sign_command() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let mut contexts: Vec<WalletContext> = (1..=3)
+        .map(|i| {
+            let path = temp_dir.path().join(format!("myso{i}.keystore"));
+            let keystore = Keystore::from(FileBasedKeystore::load_or_create(&path).unwrap());
+            WalletContext::new_for_tests(keystore, None, None)
+        })
+        .collect();
+
+    fn package(output: CommandOutput) -> String {
+        match output {
+            CommandOutput::ThresholdDkgRound1(p)
+            | CommandOutput::ThresholdCommit(p)
+            | CommandOutput::ThresholdSign(p) => p.package,
+            _ => panic!("Unexpected output"),
+        }
+    }
+
+    // Round 1: every participant broadcasts a package.
+    let mut round1 = vec![];
+    for (i, context) in contexts.iter_mut().enumerate() {
+        let output = KeyToolCommand::ThresholdDkgRound1 {
+            session: "ops".to_string(),
+            identifier: i as u16 + 1,
+            threshold: 2,
+            max_signers: 3,
+        }
+        .execute(context)
+        .await?;
+        round1.push(package(output));
+    }
+
+    // Round 2: every participant sends a package to each other participant.
+    let mut round2: Vec<(u16, String)> = vec![];
+    for (i, context) in contexts.iter_mut().enumerate() {
+        let packages = round1
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, p)| p.clone())
+            .collect();
+        let CommandOutput::ThresholdDkgRound2(outputs) = KeyToolCommand::ThresholdDkgRound2 {
+            session: "ops".to_string(),
+            packages,
+        }
+        .execute(context)
+        .await?
+        else {
+            panic!("Unexpected output");
+        };
+        round2.extend(
+            outputs
+                .into_iter()
+                .map(|output| (output.recipient.unwrap(), output.package)),
+        );
+    }
+
+    let mut addresses = vec![];
+    for (i, context) in contexts.iter_mut().enumerate() {
+        let packages = round2
+            .iter()
+            .filter(|(recipient, _)| *recipient == i as u16 + 1)
+            .map(|(_, p)| p.clone())
+            .collect();
+        let CommandOutput::ThresholdDkgFinalize(key) = KeyToolCommand::ThresholdDkgFinalize {
+            session: "ops".to_string(),
+            packages,
+        }
+        .execute(context)
+        .await?
+        else {
+            panic!("Unexpected output");
+        };
+        addresses.push(key.myso_address);
+    }
+    assert!(addresses.iter().all(|a| *a == addresses[0]));
+    let address = addresses[0];
+
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let tx_data = TransactionData::new_transfer_myso(
+        MySoAddress::random_for_testing_only(),
+        address,
+        Some(1),
+        gas,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        1,
+    );
+    let data = Base64::encode(bcs::to_bytes(&tx_data)?);
+
+    // Participants 2 and 3 sign.
+    let mut commitments = vec![];
+    for context in &mut contexts[1..] {
+        let output = KeyToolCommand::ThresholdCommit { address }
+            .execute(context)
+            .await?;
+        commitments.push(package(output));
+    }
+    let mut shares = vec![];
+    for context in &mut contexts[1..] {
+        let output = KeyToolCommand::ThresholdSign {
+            address,
+            data: data.clone(),
+            commitments: commitments.clone(),
+            intent: None,
+        }
+        .execute(context)
+        .await?;
+        shares.push(package(output));
+    }
+
+    // The aggregated signature is a single Ed25519 signature by the threshold address.
+    let CommandOutput::ThresholdAggregate(signed) = KeyToolCommand::ThresholdAggregate {
+        address,
+        data,
+        commitments,
+        shares,
+        intent: None,
+    }
+    .execute(&mut contexts[0])
+    .await?
+    else {
+        panic!("Unexpected output");
+    };
+    let signature = Signature::decode_base64(&signed.myso_signature).unwrap();
+    signature.verify_secure(
+        &shared_crypto::intent::IntentMessage::new(Intent::myso_transaction(), tx_data),
+        address,
+        SignatureScheme::ED25519,
+    )?;
+    Ok(())
+}
@@ -249,6 +249,13 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                     (stringify!(#cf_names).to_owned(), (stringify!(#key_names).to_owned(), stringify!(#value_names).to_owned())),
                 )*].into_iter().collect()
             }
+
+            /// Returns storage statistics of every table, keyed by column family name
+            pub fn storage_stats(&self) -> Result<std::collections::BTreeMap<String, typed_store::traits::TableStorageStats>, typed_store::TypedStoreError> {
+                Ok(vec![#(
+                    (stringify!(#cf_names).to_owned(), self.#field_names.storage_stats()?),
+                )*].into_iter().collect())
+            }
         }
     };
 
@@ -10,8 +10,9 @@ use std::time::Duration;
 use mysten_metrics::RegistryService;
 use once_cell::sync::OnceCell;
 use prometheus::{
-    HistogramVec, IntCounterVec, IntGaugeVec, Registry, register_histogram_vec_with_registry,
-    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry,
+    GaugeVec, HistogramVec, IntCounterVec, IntGaugeVec, Registry, register_gauge_vec_with_registry,
+    register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+    register_int_gauge_vec_with_registry,
 };
 use rocksdb::perf::set_perf_stats;
 use rocksdb::{PerfContext, PerfMetric, PerfStatsLevel};
@@ -108,6 +109,7 @@ pub struct ColumnFamilyMetrics {
     pub rocksdb_background_errors: IntGaugeVec,
     pub rocksdb_estimated_num_keys: IntGaugeVec,
     pub rocksdb_base_level: IntGaugeVec,
+    pub rocksdb_write_amplification: GaugeVec,
 }
 
 impl ColumnFamilyMetrics {
@@ -292,6 +294,13 @@ impl ColumnFamilyMetrics {
                 registry,
             )
             .unwrap(),
+            rocksdb_write_amplification: register_gauge_vec_with_registry!(
+                "rocksdb_write_amplification",
+                "The bytes written by flushes and compactions per byte flushed from memtables",
+                &["cf_name"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
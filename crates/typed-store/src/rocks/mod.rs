@@ -23,7 +23,7 @@ use crate::util::{
 use crate::{DbIterator, StorageType, TypedStoreError};
 use crate::{
     metrics::{DBMetrics, RocksDBPerfContext, SamplingInterval},
-    traits::{Map, TableSummary},
+    traits::{Map, TableStorageStats, TableSummary},
 };
 use backoff::backoff::Backoff;
 use fastcrypto::hash::{Digest, HashFunction};
@@ -772,6 +772,87 @@ impl<K, V> DBMap<K, V> {
         }
     }
 
+    fn get_rocksdb_string_property(
+        rocksdb: &RocksDB,
+        cf: &impl AsColumnFamilyRef,
+        property_name: &std::ffi::CStr,
+    ) -> Result<String, TypedStoreError> {
+        match rocksdb.underlying.property_value_cf(cf, property_name) {
+            Ok(value) => Ok(value.unwrap_or_default()),
+            Err(e) => Err(TypedStoreError::RocksDBError(e.into_string())),
+        }
+    }
+
+    /// Reads the write amplification from the `Sum` row of the compaction stats of a column
+    /// family, which RocksDB only exposes as a formatted table.
+    fn get_rocksdb_write_amplification(
+        rocksdb: &RocksDB,
+        cf: &impl AsColumnFamilyRef,
+    ) -> Result<f64, TypedStoreError> {
+        let stats =
+            Self::get_rocksdb_string_property(rocksdb, cf, properties::CFSTATS_NO_FILE_HISTOGRAM)?;
+        let mut column = None;
+        for line in stats.lines() {
+            let tokens: Vec<_> = line.split_whitespace().collect();
+            match tokens.first() {
+                Some(&"Level") => column = tokens.iter().position(|t| *t == "W-Amp"),
+                // The size column of the rows is a value and a unit, one token more than the
+                // header.
+                Some(&"Sum") => {
+                    return Ok(column
+                        .and_then(|column| tokens.get(column + 1))
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or_default());
+                }
+                _ => (),
+            }
+        }
+        Ok(0.0)
+    }
+
+    /// Returns storage statistics of the table from RocksDB properties. Only RocksDB
+    /// storage is supported, other storages and dropped column families report empty
+    /// statistics.
+    pub fn storage_stats(&self) -> Result<TableStorageStats, TypedStoreError> {
+        let Storage::Rocks(rocksdb) = &self.db.storage else {
+            return Ok(TableStorageStats::default());
+        };
+        let Some(cf) = rocksdb.underlying.cf_handle(&self.cf) else {
+            return Ok(TableStorageStats::default());
+        };
+        let int_property = |name: &std::ffi::CStr| {
+            Self::get_rocksdb_int_property(rocksdb, &cf, name).map(|value| value as u64)
+        };
+
+        // Aggregated table properties look like `# entries=10; # deletions=2; ...`.
+        let table_properties = Self::get_rocksdb_string_property(
+            rocksdb,
+            &cf,
+            properties::AGGREGATED_TABLE_PROPERTIES,
+        )?;
+        let table_property = |name: &str| {
+            table_properties
+                .split(';')
+                .filter_map(|property| property.split_once('='))
+                .find(|(key, _)| key.trim() == name)
+                .and_then(|(_, value)| value.trim().parse::<u64>().ok())
+                .unwrap_or_default()
+        };
+        let num_entries = table_property("# entries");
+        let estimated_live_keys = int_property(properties::ESTIMATE_NUM_KEYS)?;
+
+        Ok(TableStorageStats {
+            total_sst_files_size: int_property(properties::TOTAL_SST_FILES_SIZE)?,
+            total_blob_files_size: int_property(ROCKSDB_PROPERTY_TOTAL_BLOB_FILES_SIZE)?,
+            live_data_size: int_property(properties::ESTIMATE_LIVE_DATA_SIZE)?,
+            estimated_live_keys,
+            estimated_dead_keys: num_entries.saturating_sub(estimated_live_keys),
+            num_deletions: table_property("# deletions"),
+            pending_compaction_bytes: int_property(properties::ESTIMATE_PENDING_COMPACTION_BYTES)?,
+            write_amplification: Self::get_rocksdb_write_amplification(rocksdb, &cf)?,
+        })
+    }
+
     fn report_rocksdb_metrics(
         database: &Arc<Database>,
         cf_name: &str,
@@ -998,6 +1079,13 @@ impl<K, V> DBMap<K, V> {
                 Self::get_rocksdb_int_property(rocksdb, &cf, properties::BASE_LEVEL)
                     .unwrap_or(METRICS_ERROR),
             );
+        db_metrics
+            .cf_metrics
+            .rocksdb_write_amplification
+            .with_label_values(&[cf_name])
+            .set(
+                Self::get_rocksdb_write_amplification(rocksdb, &cf).unwrap_or(METRICS_ERROR as f64),
+            );
     }
 
     pub fn checkpoint_db(&self, path: &Path) -> Result<(), TypedStoreError> {
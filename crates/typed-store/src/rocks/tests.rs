@@ -664,6 +664,38 @@ async fn open_as_secondary_test() {
     assert_eq!(secondary_db.get(&0).unwrap(), Some("10".to_string()));
 }
 
+#[tokio::test]
+async fn test_storage_stats() {
+    let db = open_map::<_, u32, String>(temp_dir(), None);
+    let stats = db.storage_stats().expect("Failed to get storage stats");
+    assert_eq!(stats.total_sst_files_size, 0);
+    assert_eq!(stats.num_deletions, 0);
+
+    db.multi_insert((0..1000u32).map(|i| (i, i.to_string())))
+        .expect("Failed to insert");
+    db.flush().expect("Failed to flush");
+    db.multi_remove(0..500u32).expect("Failed to remove");
+    db.flush().expect("Failed to flush");
+
+    let stats = db.storage_stats().expect("Failed to get storage stats");
+    assert!(stats.total_sst_files_size > 0);
+    assert!(stats.live_data_size > 0);
+    assert!(stats.estimated_live_keys > 0);
+    assert!(stats.estimated_dead_keys > 0);
+    assert_eq!(stats.num_deletions, 500);
+    assert!(stats.write_amplification > 0.0);
+
+    // A full compaction drops the removed entries along with their tombstones.
+    db.compact_range(&0u32, &1000u32)
+        .expect("Failed to compact");
+    let compacted = db.storage_stats().expect("Failed to get storage stats");
+    assert!(compacted.total_sst_files_size > 0);
+    assert!(compacted.total_sst_files_size < stats.total_sst_files_size);
+    assert_eq!(compacted.num_deletions, 0);
+    assert!(compacted.estimated_dead_keys < stats.estimated_dead_keys);
+    assert!(compacted.write_amplification > 0.0);
+}
+
 fn open_map<P: AsRef<Path>, K, V>(path: P, opt_cf: Option<&str>) -> DBMap<K, V> {
     let cf_key = opt_cf.unwrap_or(rocksdb::DEFAULT_COLUMN_FAMILY_NAME);
     DBMap::<K, V>::reopen(
@@ -2,7 +2,7 @@
 // Copyright (c) The Social Proof Foundation, LLC.
 // SPDX-License-Identifier: Apache-2.0
 use crate::TypedStoreError;
-use serde::{Serialize, de::DeserializeOwned};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::ops::RangeBounds;
 use std::{borrow::Borrow, error::Error};
 
@@ -101,3 +101,27 @@ pub struct TableSummary {
     pub key_hist: hdrhistogram::Histogram<u64>,
     pub value_hist: hdrhistogram::Histogram<u64>,
 }
+
+/// Storage statistics of a table, estimated from RocksDB properties without scanning it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TableStorageStats {
+    pub total_sst_files_size: u64,
+    pub total_blob_files_size: u64,
+    /// Size of the data that would remain after a full compaction.
+    pub live_data_size: u64,
+    pub estimated_live_keys: u64,
+    /// Entries in sst files that are overwritten or deleted, tombstones included.
+    pub estimated_dead_keys: u64,
+    pub num_deletions: u64,
+    pub pending_compaction_bytes: u64,
+    /// Bytes written by flushes and compactions per byte flushed from memtables.
+    pub write_amplification: f64,
+}
+
+impl TableStorageStats {
+    /// Disk space a full compaction of the table would free.
+    pub fn reclaimable_by_compaction(&self) -> u64 {
+        self.total_sst_files_size
+            .saturating_sub(self.live_data_size)
+    }
+}